@@ -0,0 +1,870 @@
+//! PulsarTrack - Ad Auction (Soroban)
+//! Inventory allocation between campaigns and publisher nodes. Advertisers
+//! post bid requests (max CPM, targeting, number of slots); live publisher
+//! nodes answer with open or sealed CPM asks; a deterministic clearing pass
+//! awards each slot to the lowest remaining ask and settlement pays winners
+//! through the payment processor.
+//!
+//! Posting escrows the most the request can cost, every slot at `max_cpm`.
+//! Anyone may settle a cleared request: winners are paid from the escrow and
+//! the rest goes back to the advertiser, as does the whole escrow of an
+//! unfilled or cancelled request.
+//!
+//! Clearing order: ascending CPM, ties broken by submission order. Winners are
+//! paid their own ask for `impressions_per_slot` impressions.
+//!
+//! Events (third topic is `pulsar_common_admin::EVENT_SCHEMA_VERSION`):
+//! - ("adauction", "posted"): [request_id: u64, advertiser: Address, campaign_id: u64]
+//! - ("adauction", "bid"): [request_id: u64, publisher: Address, cpm: i128]
+//! - ("adauction", "sealed"): [request_id: u64, publisher: Address]
+//! - ("adauction", "revealed"): [request_id: u64, publisher: Address, cpm: i128]
+//! - ("adauction", "won"): [request_id: u64, slot: u32, publisher: Address, cpm: i128]
+//! - ("adauction", "cleared"): [request_id: u64, filled_slots: u32]
+//! - ("adauction", "settled"): [request_id: u64, slot: u32, payment_id: u64, amount: i128]
+//! - ("adauction", "refunded"): [request_id: u64, amount: i128]
+//! - ("adauction", "cancelled"): [request_id: u64]
+
+#![no_std]
+#![allow(clippy::too_many_arguments)]
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractimpl, contracttype, symbol_short, token, Address, Bytes, BytesN, Env,
+    IntoVal, String, Symbol, Vec,
+};
+
+pub use pulsar_common_errors::PulsarError;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BidMode {
+    Open,
+    Sealed,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RequestStatus {
+    Open,
+    Cleared,
+    Settled,
+    Unfilled,
+    Cancelled,
+}
+
+/// Empty `zone` or `categories` match any node
+#[contracttype]
+#[derive(Clone)]
+pub struct Targeting {
+    pub zone: String,
+    pub categories: Vec<String>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct BidRequest {
+    pub request_id: u64,
+    pub advertiser: Address,
+    pub campaign_id: u64,
+    pub token: Address,
+    pub max_cpm: i128,
+    pub impressions_per_slot: u64,
+    pub slot_count: u32,
+    pub targeting: Targeting,
+    pub mode: BidMode,
+    pub bid_deadline: u64,
+    pub reveal_deadline: u64, // equals bid_deadline for open requests
+    pub status: RequestStatus,
+    pub bid_count: u32,
+    pub filled_slots: u32,
+    pub escrow: i128, // held until settlement, cancellation or an unfilled clear
+    pub created_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct SlotBid {
+    pub publisher: Address,
+    pub cpm: i128, // 0 until a sealed bid is revealed
+    pub commitment: Option<BytesN<32>>,
+    pub revealed: bool,
+    pub submitted_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct SlotWin {
+    pub publisher: Address,
+    pub cpm: i128,
+    pub amount: i128,
+    pub payment_id: Option<u64>,
+}
+
+/// Mirror of the publisher-network node type, decoded from `get_node`.
+#[contracttype]
+#[derive(Clone)]
+pub enum NodeType {
+    Standard,
+    Premium,
+    Enterprise,
+}
+
+/// Mirror of the publisher-network node record, decoded from `get_node`.
+#[contracttype]
+#[derive(Clone)]
+pub struct NetworkNode {
+    pub publisher: Address,
+    pub node_type: NodeType,
+    pub capacity: u64,
+    pub min_cpm: i128,
+    pub geographic_zone: String,
+    pub content_categories: Vec<String>,
+    pub is_active: bool,
+    pub joined_at: u64,
+    pub last_heartbeat: u64,
+    pub stake: i128,
+    pub deactivated_at: Option<u64>,
+    pub is_suspended: bool,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    PendingAdmin,
+    PublisherNetwork,
+    PaymentProcessor,
+    RequestCounter,
+    Request(u64),
+    Bid(u64, u32),          // request_id, bid_index
+    BidIndex(u64, Address), // request_id, publisher
+    Win(u64, u32),          // request_id, slot
+}
+
+const MAX_SLOTS: u32 = 10;
+const MAX_BIDS_PER_REQUEST: u32 = 50;
+
+const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
+const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = 17_280;
+const PERSISTENT_BUMP_AMOUNT: u32 = 86_400;
+
+#[contract]
+pub struct AdAuctionContract;
+
+#[contractimpl]
+impl AdAuctionContract {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        publisher_network: Address,
+        payment_processor: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(PulsarError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::PublisherNetwork, &publisher_network);
+        env.storage()
+            .instance()
+            .set(&DataKey::PaymentProcessor, &payment_processor);
+        env.storage()
+            .instance()
+            .set(&DataKey::RequestCounter, &0u64);
+        Ok(())
+    }
+
+    /// Opens a request for `slot_count` slots of `impressions_per_slot`
+    /// impressions each. Sealed requests get a reveal phase of
+    /// `reveal_window_secs` after bidding closes; open requests ignore it.
+    pub fn post_bid_request(
+        env: Env,
+        advertiser: Address,
+        campaign_id: u64,
+        token: Address,
+        max_cpm: i128,
+        impressions_per_slot: u64,
+        slot_count: u32,
+        targeting: Targeting,
+        mode: BidMode,
+        bid_window_secs: u64,
+        reveal_window_secs: u64,
+    ) -> Result<u64, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        advertiser.require_auth();
+
+        if max_cpm <= 0 || impressions_per_slot == 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        if slot_count == 0 || slot_count > MAX_SLOTS {
+            return Err(PulsarError::InvalidInput);
+        }
+        if bid_window_secs == 0 || (mode == BidMode::Sealed && reveal_window_secs == 0) {
+            return Err(PulsarError::InvalidInput);
+        }
+        let escrow = Self::_slot_cost(max_cpm, impressions_per_slot)?
+            .checked_mul(slot_count as i128)
+            .ok_or(PulsarError::InvalidAmount)?;
+        if escrow <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        token::Client::new(&env, &token).transfer(
+            &advertiser,
+            &env.current_contract_address(),
+            &escrow,
+        );
+
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::RequestCounter)
+            .unwrap_or(0);
+        let request_id = counter + 1;
+
+        let now = env.ledger().timestamp();
+        let bid_deadline = now + bid_window_secs;
+        let reveal_deadline = match mode {
+            BidMode::Open => bid_deadline,
+            BidMode::Sealed => bid_deadline + reveal_window_secs,
+        };
+
+        let request = BidRequest {
+            request_id,
+            advertiser: advertiser.clone(),
+            campaign_id,
+            token,
+            max_cpm,
+            impressions_per_slot,
+            slot_count,
+            targeting,
+            mode,
+            bid_deadline,
+            reveal_deadline,
+            status: RequestStatus::Open,
+            bid_count: 0,
+            filled_slots: 0,
+            escrow,
+            created_at: now,
+        };
+        Self::_save_request(&env, &request);
+        env.storage()
+            .instance()
+            .set(&DataKey::RequestCounter, &request_id);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("adauction"),
+            symbol_short!("posted"),
+            (request_id, advertiser, campaign_id),
+        );
+
+        Ok(request_id)
+    }
+
+    pub fn submit_bid(
+        env: Env,
+        publisher: Address,
+        request_id: u64,
+        cpm: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        publisher.require_auth();
+
+        let mut request = Self::_load_request(&env, request_id)?;
+        if request.mode != BidMode::Open {
+            return Err(PulsarError::InvalidState);
+        }
+        Self::_check_bidding_open(&env, &request)?;
+        Self::_check_cpm(&request, cpm)?;
+        Self::_validate_publisher(&env, &request, &publisher)?;
+
+        let bid = SlotBid {
+            publisher: publisher.clone(),
+            cpm,
+            commitment: None,
+            revealed: true,
+            submitted_at: env.ledger().timestamp(),
+        };
+        Self::_store_new_bid(&env, &mut request, &bid)?;
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("adauction"),
+            symbol_short!("bid"),
+            (request_id, publisher, cpm),
+        );
+        Ok(())
+    }
+
+    /// `commitment` is sha256 of the ask as 16 big-endian bytes followed by
+    /// the 32-byte salt later passed to `reveal_bid`.
+    pub fn submit_sealed_bid(
+        env: Env,
+        publisher: Address,
+        request_id: u64,
+        commitment: BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        publisher.require_auth();
+
+        let mut request = Self::_load_request(&env, request_id)?;
+        if request.mode != BidMode::Sealed {
+            return Err(PulsarError::InvalidState);
+        }
+        Self::_check_bidding_open(&env, &request)?;
+        Self::_validate_publisher(&env, &request, &publisher)?;
+
+        let bid = SlotBid {
+            publisher: publisher.clone(),
+            cpm: 0,
+            commitment: Some(commitment),
+            revealed: false,
+            submitted_at: env.ledger().timestamp(),
+        };
+        Self::_store_new_bid(&env, &mut request, &bid)?;
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("adauction"),
+            symbol_short!("sealed"),
+            (request_id, publisher),
+        );
+        Ok(())
+    }
+
+    pub fn reveal_bid(
+        env: Env,
+        publisher: Address,
+        request_id: u64,
+        cpm: i128,
+        salt: BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        publisher.require_auth();
+
+        let request = Self::_load_request(&env, request_id)?;
+        if request.status != RequestStatus::Open {
+            return Err(PulsarError::InvalidState);
+        }
+        let now = env.ledger().timestamp();
+        if now <= request.bid_deadline || now > request.reveal_deadline {
+            return Err(PulsarError::InvalidState);
+        }
+
+        let index: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BidIndex(request_id, publisher.clone()))
+            .ok_or(PulsarError::NotFound)?;
+        let mut bid: SlotBid = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Bid(request_id, index))
+            .unwrap();
+        if bid.revealed {
+            return Err(PulsarError::AlreadyExists);
+        }
+        if bid.commitment != Some(Self::_commitment(&env, cpm, &salt)) {
+            return Err(PulsarError::InvalidInput);
+        }
+        Self::_check_cpm(&request, cpm)?;
+
+        bid.cpm = cpm;
+        bid.revealed = true;
+        let _ttl_key = DataKey::Bid(request_id, index);
+        env.storage().persistent().set(&_ttl_key, &bid);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("adauction"),
+            symbol_short!("revealed"),
+            (request_id, publisher, cpm),
+        );
+        Ok(())
+    }
+
+    /// Permissionless once bidding (and revealing) has closed. Awards slots to
+    /// the lowest revealed asks; unrevealed sealed bids are ignored.
+    pub fn clear_request(env: Env, request_id: u64) -> Result<u32, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let mut request = Self::_load_request(&env, request_id)?;
+        if request.status != RequestStatus::Open {
+            return Err(PulsarError::InvalidState);
+        }
+        if env.ledger().timestamp() <= request.reveal_deadline {
+            return Err(PulsarError::TooEarly);
+        }
+
+        let mut eligible: Vec<u32> = Vec::new(&env);
+        for index in 0..request.bid_count {
+            let bid: SlotBid = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Bid(request_id, index))
+                .unwrap();
+            if bid.revealed {
+                eligible.push_back(index);
+            }
+        }
+
+        let mut filled = 0u32;
+        while filled < request.slot_count && !eligible.is_empty() {
+            // Lowest CPM wins; `eligible` stays in submission order so the
+            // strict comparison keeps the earliest bid on ties.
+            let mut best_pos = 0u32;
+            let mut best: Option<SlotBid> = None;
+            for pos in 0..eligible.len() {
+                let bid: SlotBid = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::Bid(request_id, eligible.get(pos).unwrap()))
+                    .unwrap();
+                let better = match &best {
+                    Some(b) => bid.cpm < b.cpm,
+                    None => true,
+                };
+                if better {
+                    best_pos = pos;
+                    best = Some(bid);
+                }
+            }
+            eligible.remove(best_pos);
+            let winner = best.unwrap();
+
+            let win = SlotWin {
+                publisher: winner.publisher.clone(),
+                cpm: winner.cpm,
+                amount: Self::_slot_cost(winner.cpm, request.impressions_per_slot)?,
+                payment_id: None,
+            };
+            let _ttl_key = DataKey::Win(request_id, filled);
+            env.storage().persistent().set(&_ttl_key, &win);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+
+            pulsar_common_admin::publish_event(
+                &env,
+                symbol_short!("adauction"),
+                symbol_short!("won"),
+                (request_id, filled, winner.publisher, winner.cpm),
+            );
+            filled += 1;
+        }
+
+        request.filled_slots = filled;
+        request.status = if filled == 0 {
+            RequestStatus::Unfilled
+        } else {
+            RequestStatus::Cleared
+        };
+        if filled == 0 {
+            Self::_refund_escrow(&env, &mut request, 0);
+        }
+        Self::_save_request(&env, &request);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("adauction"),
+            symbol_short!("cleared"),
+            (request_id, filled),
+        );
+
+        Ok(filled)
+    }
+
+    /// Pays every winning slot through the payment processor out of the
+    /// escrow and refunds the unspent part to the advertiser. Permissionless
+    /// once the request is cleared.
+    pub fn settle_request(env: Env, request_id: u64) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let mut request = Self::_load_request(&env, request_id)?;
+        if request.status != RequestStatus::Cleared {
+            return Err(PulsarError::InvalidState);
+        }
+
+        let processor: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::PaymentProcessor)
+            .unwrap();
+        let contract = env.current_contract_address();
+        let mut total = 0i128;
+        for slot in 0..request.filled_slots {
+            let _ttl_key = DataKey::Win(request_id, slot);
+            let mut win: SlotWin = env.storage().persistent().get(&_ttl_key).unwrap();
+            if win.amount > 0 {
+                // The processor pulls the payment from this contract one call
+                // deeper, so the escrow transfer has to be pre-authorized
+                env.authorize_as_current_contract(Vec::from_array(
+                    &env,
+                    [InvokerContractAuthEntry::Contract(SubContractInvocation {
+                        context: ContractContext {
+                            contract: request.token.clone(),
+                            fn_name: symbol_short!("transfer"),
+                            args: Vec::from_array(
+                                &env,
+                                [
+                                    contract.into_val(&env),
+                                    win.publisher.into_val(&env),
+                                    win.amount.into_val(&env),
+                                ],
+                            ),
+                        },
+                        sub_invocations: Vec::new(&env),
+                    })],
+                ));
+                let payment_id: u64 = env.invoke_contract(
+                    &processor,
+                    &Symbol::new(&env, "process_payment"),
+                    Vec::from_array(
+                        &env,
+                        [
+                            contract.into_val(&env),
+                            win.publisher.into_val(&env),
+                            request.token.into_val(&env),
+                            win.amount.into_val(&env),
+                        ],
+                    ),
+                );
+                win.payment_id = Some(payment_id);
+                env.storage().persistent().set(&_ttl_key, &win);
+                env.storage().persistent().extend_ttl(
+                    &_ttl_key,
+                    PERSISTENT_LIFETIME_THRESHOLD,
+                    PERSISTENT_BUMP_AMOUNT,
+                );
+                total += win.amount;
+
+                pulsar_common_admin::publish_event(
+                    &env,
+                    symbol_short!("adauction"),
+                    symbol_short!("settled"),
+                    (request_id, slot, payment_id, win.amount),
+                );
+            }
+        }
+
+        request.status = RequestStatus::Settled;
+        Self::_refund_escrow(&env, &mut request, total);
+        Self::_save_request(&env, &request);
+
+        Ok(total)
+    }
+
+    /// Withdraws a request that has not received any bids yet and refunds
+    /// its escrow
+    pub fn cancel_request(
+        env: Env,
+        advertiser: Address,
+        request_id: u64,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        advertiser.require_auth();
+
+        let mut request = Self::_load_request(&env, request_id)?;
+        if request.advertiser != advertiser {
+            return Err(PulsarError::Unauthorized);
+        }
+        if request.status != RequestStatus::Open {
+            return Err(PulsarError::InvalidState);
+        }
+        if request.bid_count > 0 {
+            return Err(PulsarError::InvalidState);
+        }
+
+        request.status = RequestStatus::Cancelled;
+        Self::_refund_escrow(&env, &mut request, 0);
+        Self::_save_request(&env, &request);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("adauction"),
+            symbol_short!("cancelled"),
+            request_id,
+        );
+        Ok(())
+    }
+
+    pub fn set_publisher_network(
+        env: Env,
+        admin: Address,
+        publisher_network: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::PublisherNetwork, &publisher_network);
+        Ok(())
+    }
+
+    pub fn set_payment_processor(
+        env: Env,
+        admin: Address,
+        payment_processor: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::PaymentProcessor, &payment_processor);
+        Ok(())
+    }
+
+    pub fn get_request(env: Env, request_id: u64) -> Option<BidRequest> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Request(request_id))
+    }
+
+    pub fn get_bid(env: Env, request_id: u64, index: u32) -> Option<SlotBid> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Bid(request_id, index))
+    }
+
+    pub fn get_win(env: Env, request_id: u64, slot: u32) -> Option<SlotWin> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Win(request_id, slot))
+    }
+
+    pub fn get_request_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::RequestCounter)
+            .unwrap_or(0)
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::propose_admin(
+            &env,
+            &DataKey::Admin,
+            &DataKey::PendingAdmin,
+            current_admin,
+            new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
+
+    // ─── internal helpers ────────────────────────────────────────────────────
+
+    fn _load_request(env: &Env, request_id: u64) -> Result<BidRequest, PulsarError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Request(request_id))
+            .ok_or(PulsarError::NotFound)
+    }
+
+    fn _save_request(env: &Env, request: &BidRequest) {
+        let _ttl_key = DataKey::Request(request.request_id);
+        env.storage().persistent().set(&_ttl_key, request);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// Returns the escrow left after `spent` to the advertiser
+    fn _refund_escrow(env: &Env, request: &mut BidRequest, spent: i128) {
+        let refund = request.escrow - spent;
+        request.escrow = 0;
+        if refund <= 0 {
+            return;
+        }
+        token::Client::new(env, &request.token).transfer(
+            &env.current_contract_address(),
+            &request.advertiser,
+            &refund,
+        );
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("adauction"),
+            symbol_short!("refunded"),
+            (request.request_id, refund),
+        );
+    }
+
+    fn _check_bidding_open(env: &Env, request: &BidRequest) -> Result<(), PulsarError> {
+        if request.status != RequestStatus::Open {
+            return Err(PulsarError::InvalidState);
+        }
+        if env.ledger().timestamp() > request.bid_deadline {
+            return Err(PulsarError::Expired);
+        }
+        Ok(())
+    }
+
+    fn _check_cpm(request: &BidRequest, cpm: i128) -> Result<(), PulsarError> {
+        if cpm <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        if cpm > request.max_cpm {
+            return Err(PulsarError::InvalidState);
+        }
+        Ok(())
+    }
+
+    /// The node must be live and match the request's zone and, when given,
+    /// at least one of its categories
+    fn _validate_publisher(
+        env: &Env,
+        request: &BidRequest,
+        publisher: &Address,
+    ) -> Result<(), PulsarError> {
+        let network: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::PublisherNetwork)
+            .unwrap();
+        let live: bool = env.invoke_contract(
+            &network,
+            &Symbol::new(env, "is_live"),
+            Vec::from_array(env, [publisher.into_val(env)]),
+        );
+        if !live {
+            return Err(PulsarError::InvalidState);
+        }
+
+        let node: Option<NetworkNode> = env.invoke_contract(
+            &network,
+            &Symbol::new(env, "get_node"),
+            Vec::from_array(env, [publisher.into_val(env)]),
+        );
+        let node = node.ok_or(PulsarError::NotFound)?;
+        let targeting = &request.targeting;
+        if !targeting.zone.is_empty() && targeting.zone != node.geographic_zone {
+            return Err(PulsarError::InvalidInput);
+        }
+        if !targeting.categories.is_empty()
+            && !targeting
+                .categories
+                .iter()
+                .any(|c| node.content_categories.contains(&c))
+        {
+            return Err(PulsarError::InvalidInput);
+        }
+        Ok(())
+    }
+
+    fn _store_new_bid(
+        env: &Env,
+        request: &mut BidRequest,
+        bid: &SlotBid,
+    ) -> Result<(), PulsarError> {
+        let index_key = DataKey::BidIndex(request.request_id, bid.publisher.clone());
+        if env.storage().persistent().has(&index_key) {
+            return Err(PulsarError::AlreadyExists);
+        }
+        if request.bid_count >= MAX_BIDS_PER_REQUEST {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        let index = request.bid_count;
+        let _ttl_key = DataKey::Bid(request.request_id, index);
+        env.storage().persistent().set(&_ttl_key, bid);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage().persistent().set(&index_key, &index);
+        env.storage().persistent().extend_ttl(
+            &index_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        request.bid_count += 1;
+        Self::_save_request(env, request);
+        Ok(())
+    }
+
+    fn _commitment(env: &Env, cpm: i128, salt: &BytesN<32>) -> BytesN<32> {
+        let mut data = Bytes::from_array(env, &cpm.to_be_bytes());
+        data.append(&Bytes::from(salt.clone()));
+        env.crypto().sha256(&data).into()
+    }
+
+    fn _slot_cost(cpm: i128, impressions: u64) -> Result<i128, PulsarError> {
+        Ok(cpm
+            .checked_mul(impressions as i128)
+            .ok_or(PulsarError::InvalidAmount)?
+            / 1_000)
+    }
+}
+
+mod test;
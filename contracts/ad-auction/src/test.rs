@@ -0,0 +1,367 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Bytes, BytesN, Env, String,
+};
+
+mod network_mock {
+    use super::{NetworkNode, NodeType};
+    use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
+
+    #[contract]
+    pub struct MockNetwork;
+
+    #[contractimpl]
+    impl MockNetwork {
+        pub fn add_node(
+            env: Env,
+            publisher: Address,
+            zone: String,
+            categories: Vec<String>,
+            live: bool,
+        ) {
+            let node = NetworkNode {
+                publisher: publisher.clone(),
+                node_type: NodeType::Standard,
+                capacity: 100_000,
+                min_cpm: 1,
+                geographic_zone: zone,
+                content_categories: categories,
+                is_active: live,
+                joined_at: 0,
+                last_heartbeat: 0,
+                stake: 0,
+                deactivated_at: None,
+                is_suspended: false,
+            };
+            env.storage().persistent().set(&publisher, &node);
+        }
+
+        pub fn is_live(env: Env, publisher: Address) -> bool {
+            env.storage()
+                .persistent()
+                .get::<Address, NetworkNode>(&publisher)
+                .map(|n| n.is_active)
+                .unwrap_or(false)
+        }
+
+        pub fn get_node(env: Env, publisher: Address) -> Option<NetworkNode> {
+            env.storage().persistent().get(&publisher)
+        }
+    }
+}
+
+mod processor_mock {
+    use soroban_sdk::{contract, contractimpl, token, Address, Env};
+
+    #[contract]
+    pub struct MockProcessor;
+
+    #[contractimpl]
+    impl MockProcessor {
+        pub fn process_payment(
+            env: Env,
+            payer: Address,
+            recipient: Address,
+            token: Address,
+            amount: i128,
+        ) -> u64 {
+            payer.require_auth();
+            token::Client::new(&env, &token).transfer(&payer, &recipient, &amount);
+            let id: u64 = env.storage().instance().get(&0u32).unwrap_or(0) + 1;
+            env.storage().instance().set(&0u32, &id);
+            id
+        }
+    }
+}
+
+// ─── helpers ─────────────────────────────────────────────────────────────────
+
+struct Setup<'a> {
+    client: AdAuctionContractClient<'a>,
+    network: network_mock::MockNetworkClient<'a>,
+    token: Address,
+    advertiser: Address,
+}
+
+fn setup(env: &Env) -> Setup<'_> {
+    let admin = Address::generate(env);
+    let token_admin = Address::generate(env);
+    let token = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let advertiser = Address::generate(env);
+    StellarAssetClient::new(env, &token).mint(&advertiser, &1_000_000);
+
+    let network_id = env.register(network_mock::MockNetwork, ());
+    let processor_id = env.register(processor_mock::MockProcessor, ());
+    let contract_id = env.register(AdAuctionContract, ());
+    let client = AdAuctionContractClient::new(env, &contract_id);
+    client.initialize(&admin, &network_id, &processor_id);
+
+    Setup {
+        client,
+        network: network_mock::MockNetworkClient::new(env, &network_id),
+        token,
+        advertiser,
+    }
+}
+
+fn publisher(env: &Env, s: &Setup, zone: &str) -> Address {
+    let p = Address::generate(env);
+    s.network.add_node(
+        &p,
+        &String::from_str(env, zone),
+        &vec![env, String::from_str(env, "sports")],
+        &true,
+    );
+    p
+}
+
+fn any_target(env: &Env) -> Targeting {
+    Targeting {
+        zone: String::from_str(env, ""),
+        categories: Vec::new(env),
+    }
+}
+
+fn post(s: &Setup, slots: u32, targeting: Targeting, mode: BidMode) -> u64 {
+    s.client.post_bid_request(
+        &s.advertiser,
+        &7u64,
+        &s.token,
+        &500i128,
+        &1_000u64,
+        &slots,
+        &targeting,
+        &mode,
+        &100u64,
+        &100u64,
+    )
+}
+
+fn commit(env: &Env, cpm: i128, salt: &BytesN<32>) -> BytesN<32> {
+    let mut data = Bytes::from_array(env, &cpm.to_be_bytes());
+    data.append(&Bytes::from(salt.clone()));
+    env.crypto().sha256(&data).into()
+}
+
+fn advance(env: &Env, secs: u64) {
+    env.ledger().with_mut(|li| li.timestamp += secs);
+}
+
+// ─── tests ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_open_auction_clears_lowest_asks_per_slot() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let id = post(&s, 2, any_target(&env), BidMode::Open);
+
+    let p1 = publisher(&env, &s, "EU");
+    let p2 = publisher(&env, &s, "EU");
+    let p3 = publisher(&env, &s, "US");
+    s.client.submit_bid(&p1, &id, &300i128);
+    s.client.submit_bid(&p2, &id, &200i128);
+    s.client.submit_bid(&p3, &id, &300i128);
+
+    advance(&env, 101);
+    assert_eq!(s.client.clear_request(&id), 2);
+
+    let first = s.client.get_win(&id, &0).unwrap();
+    assert_eq!(first.publisher, p2);
+    assert_eq!(first.amount, 200);
+    // Tie at 300 goes to the earlier bid
+    let second = s.client.get_win(&id, &1).unwrap();
+    assert_eq!(second.publisher, p1);
+    assert!(s.client.get_win(&id, &2).is_none());
+
+    // Escrowed 2 slots at the 500 max; the 500 not spent goes back
+    let token = TokenClient::new(&env, &s.token);
+    assert_eq!(token.balance(&s.advertiser), 1_000_000 - 1_000);
+    assert_eq!(s.client.settle_request(&id), 500);
+    assert_eq!(token.balance(&p1), 300);
+    assert_eq!(token.balance(&p2), 200);
+    assert_eq!(token.balance(&p3), 0);
+    assert_eq!(token.balance(&s.advertiser), 1_000_000 - 500);
+    assert_eq!(token.balance(&s.client.address), 0);
+    assert_eq!(s.client.get_win(&id, &0).unwrap().payment_id, Some(1));
+    assert!(s.client.get_request(&id).unwrap().status == RequestStatus::Settled);
+}
+
+#[test]
+fn test_sealed_auction_ignores_unrevealed_bids() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let id = post(&s, 1, any_target(&env), BidMode::Sealed);
+
+    let p1 = publisher(&env, &s, "EU");
+    let p2 = publisher(&env, &s, "EU");
+    let salt1 = BytesN::from_array(&env, &[1u8; 32]);
+    let salt2 = BytesN::from_array(&env, &[2u8; 32]);
+    s.client
+        .submit_sealed_bid(&p1, &id, &commit(&env, 250, &salt1));
+    s.client
+        .submit_sealed_bid(&p2, &id, &commit(&env, 100, &salt2));
+
+    assert!(s.client.try_reveal_bid(&p1, &id, &250i128, &salt1).is_err());
+    advance(&env, 101);
+    s.client.reveal_bid(&p1, &id, &250i128, &salt1);
+    assert!(s.client.try_reveal_bid(&p2, &id, &90i128, &salt2).is_err());
+
+    advance(&env, 100);
+    assert_eq!(s.client.clear_request(&id), 1);
+    assert_eq!(s.client.get_win(&id, &0).unwrap().publisher, p1);
+}
+
+#[test]
+fn test_bid_outside_target_zone_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let targeting = Targeting {
+        zone: String::from_str(&env, "EU"),
+        categories: vec![&env, String::from_str(&env, "sports")],
+    };
+    let id = post(&s, 1, targeting, BidMode::Open);
+
+    let p = publisher(&env, &s, "US");
+    assert_eq!(
+        s.client.try_submit_bid(&p, &id, &100i128),
+        Err(Ok(PulsarError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_bid_from_inactive_node_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let id = post(&s, 1, any_target(&env), BidMode::Open);
+
+    let p = Address::generate(&env);
+    s.network
+        .add_node(&p, &String::from_str(&env, "EU"), &Vec::new(&env), &false);
+    assert_eq!(
+        s.client.try_submit_bid(&p, &id, &100i128),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_bid_above_max_cpm_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let id = post(&s, 1, any_target(&env), BidMode::Open);
+
+    let p = publisher(&env, &s, "EU");
+    assert_eq!(
+        s.client.try_submit_bid(&p, &id, &501i128),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_clear_before_deadline_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let id = post(&s, 1, any_target(&env), BidMode::Open);
+    assert_eq!(
+        s.client.try_clear_request(&id),
+        Err(Ok(PulsarError::TooEarly))
+    );
+}
+
+#[test]
+fn test_clear_without_bids_is_unfilled() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let id = post(&s, 1, any_target(&env), BidMode::Open);
+
+    advance(&env, 101);
+    assert_eq!(s.client.clear_request(&id), 0);
+    assert!(s.client.get_request(&id).unwrap().status == RequestStatus::Unfilled);
+    assert!(s.client.try_settle_request(&id).is_err());
+    let token = TokenClient::new(&env, &s.token);
+    assert_eq!(token.balance(&s.advertiser), 1_000_000);
+}
+
+#[test]
+fn test_cancel_request_only_without_bids() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let empty = post(&s, 1, any_target(&env), BidMode::Open);
+    let bid_on = post(&s, 1, any_target(&env), BidMode::Open);
+    let p = publisher(&env, &s, "EU");
+    s.client.submit_bid(&p, &bid_on, &100i128);
+
+    s.client.cancel_request(&s.advertiser, &empty);
+    assert!(s.client.get_request(&empty).unwrap().status == RequestStatus::Cancelled);
+    // Only the request still open keeps its escrow
+    let token = TokenClient::new(&env, &s.token);
+    assert_eq!(token.balance(&s.client.address), 500);
+    assert!(s.client.try_cancel_request(&s.advertiser, &bid_on).is_err());
+}
+
+#[test]
+fn test_duplicate_bid_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let id = post(&s, 1, any_target(&env), BidMode::Open);
+
+    let p = publisher(&env, &s, "EU");
+    s.client.submit_bid(&p, &id, &100i128);
+    assert_eq!(
+        s.client.try_submit_bid(&p, &id, &90i128),
+        Err(Ok(PulsarError::AlreadyExists))
+    );
+}
+
+#[test]
+fn test_settle_without_advertiser_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let id = post(&s, 1, any_target(&env), BidMode::Open);
+    let p = publisher(&env, &s, "EU");
+    s.client.submit_bid(&p, &id, &100i128);
+    advance(&env, 101);
+    s.client.clear_request(&id);
+
+    // The winner settles with no other signature
+    env.set_auths(&[]);
+    assert_eq!(s.client.settle_request(&id), 100);
+    let token = TokenClient::new(&env, &s.token);
+    assert_eq!(token.balance(&p), 100);
+    assert_eq!(token.balance(&s.advertiser), 1_000_000 - 100);
+}
+
+#[test]
+fn test_bid_in_wrong_mode_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let open = post(&s, 1, any_target(&env), BidMode::Open);
+    let sealed = post(&s, 1, any_target(&env), BidMode::Sealed);
+    let p = publisher(&env, &s, "EU");
+    let salt = BytesN::from_array(&env, &[1u8; 32]);
+
+    assert_eq!(
+        s.client
+            .try_submit_sealed_bid(&p, &open, &commit(&env, 100, &salt)),
+        Err(Ok(PulsarError::InvalidState))
+    );
+    assert_eq!(
+        s.client.try_submit_bid(&p, &sealed, &100i128),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
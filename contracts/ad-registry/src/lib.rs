@@ -4,6 +4,8 @@
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
 
+pub use pulsar_common_errors::PulsarError;
+
 // ============================================================
 // Data Types
 // ============================================================
@@ -117,12 +119,12 @@ pub struct AdRegistryContract;
 #[contractimpl]
 impl AdRegistryContract {
     /// Initialize the contract with an admin address
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -134,6 +136,7 @@ impl AdRegistryContract {
             .instance()
             .set(&DataKey::MaxContentSize, &10_485_760u64); // 10MB
         env.storage().instance().set(&DataKey::FlagThreshold, &5u32);
+        Ok(())
     }
 
     /// Register new ad content
@@ -147,14 +150,18 @@ impl AdRegistryContract {
         description: String,
         call_to_action: String,
         landing_url: String,
-    ) -> u64 {
+    ) -> Result<u64, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         let caller = env.current_contract_address();
         let _ = caller; // will be overridden by auth
                         // Use invoker auth pattern
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         let _ = admin;
 
         let min_size: u64 = env
@@ -169,7 +176,7 @@ impl AdRegistryContract {
             .unwrap_or(10_485_760);
 
         if size < min_size || size > max_size {
-            panic!("invalid content size");
+            return Err(PulsarError::InvalidInput);
         }
 
         let nonce: u64 = env
@@ -231,30 +238,41 @@ impl AdRegistryContract {
             .instance()
             .set(&DataKey::ContentNonce, &content_id);
 
-        env.events().publish(
-            (symbol_short!("register"), symbol_short!("content")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("register"),
+            symbol_short!("content"),
             (content_id, campaign_id),
         );
 
-        content_id
+        Ok(content_id)
     }
 
     /// Update content status (admin only)
-    pub fn update_status(env: Env, admin: Address, content_id: u64, new_status: ContentStatus) {
+    pub fn update_status(
+        env: Env,
+        admin: Address,
+        content_id: u64,
+        new_status: ContentStatus,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut content: AdContent = env
             .storage()
             .persistent()
             .get(&DataKey::Content(content_id))
-            .expect("content not found");
+            .ok_or(PulsarError::NotFound)?;
         content.status = new_status;
         content.updated_at = env.ledger().timestamp();
         let _ttl_key = DataKey::Content(content_id);
@@ -264,10 +282,16 @@ impl AdRegistryContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     /// Flag content for review
-    pub fn flag_content(env: Env, reporter: Address, content_id: u64, reason: String) {
+    pub fn flag_content(
+        env: Env,
+        reporter: Address,
+        content_id: u64,
+        reason: String,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -277,10 +301,10 @@ impl AdRegistryContract {
             .storage()
             .persistent()
             .get(&DataKey::Content(content_id))
-            .expect("content not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if content.owner == reporter {
-            panic!("cannot flag own content");
+            return Err(PulsarError::InvalidInput);
         }
 
         let flag = FlagRecord {
@@ -317,10 +341,11 @@ impl AdRegistryContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     /// Track a content view
-    pub fn track_view(env: Env, content_id: u64) {
+    pub fn track_view(env: Env, content_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -328,18 +353,18 @@ impl AdRegistryContract {
             .storage()
             .persistent()
             .get(&DataKey::Content(content_id))
-            .expect("content not found");
+            .ok_or(PulsarError::NotFound)?;
 
         match content.status {
             ContentStatus::Approved => {}
-            _ => panic!("content not approved"),
+            _ => return Err(PulsarError::InvalidState),
         }
 
         let mut perf: ContentPerformance = env
             .storage()
             .persistent()
             .get(&DataKey::Performance(content_id))
-            .expect("performance not found");
+            .ok_or(PulsarError::NotFound)?;
 
         perf.total_views += 1;
         perf.unique_viewers += 1;
@@ -356,10 +381,11 @@ impl AdRegistryContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     /// Track a content click
-    pub fn track_click(env: Env, content_id: u64) {
+    pub fn track_click(env: Env, content_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -367,7 +393,7 @@ impl AdRegistryContract {
             .storage()
             .persistent()
             .get(&DataKey::Performance(content_id))
-            .expect("performance not found");
+            .ok_or(PulsarError::NotFound)?;
 
         perf.total_clicks += 1;
 
@@ -382,10 +408,11 @@ impl AdRegistryContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     /// Archive content (owner only)
-    pub fn archive_content(env: Env, owner: Address, content_id: u64) {
+    pub fn archive_content(env: Env, owner: Address, content_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -395,10 +422,10 @@ impl AdRegistryContract {
             .storage()
             .persistent()
             .get(&DataKey::Content(content_id))
-            .expect("content not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if content.owner != owner {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         content.status = ContentStatus::Archived;
@@ -410,6 +437,7 @@ impl AdRegistryContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     // ============================================================
@@ -468,32 +496,49 @@ impl AdRegistryContract {
             .unwrap_or(0)
     }
 
-    pub fn set_flag_threshold(env: Env, admin: Address, threshold: u32) {
+    pub fn set_flag_threshold(env: Env, admin: Address, threshold: u32) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         env.storage()
             .instance()
             .set(&DataKey::FlagThreshold, &threshold);
+        Ok(())
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
@@ -4,6 +4,8 @@
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone)]
 pub struct CampaignAnalytics {
@@ -59,12 +61,12 @@ pub struct AnalyticsAggregatorContract;
 
 #[contractimpl]
 impl AnalyticsAggregatorContract {
-    pub fn initialize(env: Env, admin: Address, oracle: Address) {
+    pub fn initialize(env: Env, admin: Address, oracle: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -80,6 +82,7 @@ impl AnalyticsAggregatorContract {
             last_updated: env.ledger().timestamp(),
         };
         env.storage().instance().set(&DataKey::GlobalStats, &global);
+        Ok(())
     }
 
     pub fn record_impression(env: Env, caller: Address, campaign_id: u64, spend: i128) {
@@ -152,7 +155,7 @@ impl AnalyticsAggregatorContract {
         env.storage().instance().set(&DataKey::GlobalStats, &global);
     }
 
-    pub fn record_click(env: Env, caller: Address, campaign_id: u64) {
+    pub fn record_click(env: Env, caller: Address, campaign_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -162,7 +165,7 @@ impl AnalyticsAggregatorContract {
             .storage()
             .persistent()
             .get(&DataKey::CampaignAnalytics(campaign_id))
-            .expect("analytics not found");
+            .ok_or(PulsarError::NotFound)?;
 
         analytics.total_clicks += 1;
         if analytics.total_impressions > 0 {
@@ -181,6 +184,7 @@ impl AnalyticsAggregatorContract {
         let mut global: GlobalStats = env.storage().instance().get(&DataKey::GlobalStats).unwrap();
         global.total_clicks += 1;
         env.storage().instance().set(&DataKey::GlobalStats, &global);
+        Ok(())
     }
 
     pub fn get_campaign_analytics(env: Env, campaign_id: u64) -> Option<CampaignAnalytics> {
@@ -217,18 +221,30 @@ impl AnalyticsAggregatorContract {
             .get(&DataKey::HourlyStats(campaign_id, hour))
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
@@ -0,0 +1,416 @@
+//! PulsarTrack - Analytics Hub (Soroban)
+//! Consolidated per-campaign analytics. Authorized contracts push compact
+//! daily rollups for the part of the picture they own (delivery, fraud,
+//! settlement) and frontends read one dashboard instead of querying each
+//! contract separately.
+//!
+//! Re-pushing a day replaces that source kind's figures for the day, so
+//! sources can push running totals as often as they like.
+//!
+//! Events (third topic is `pulsar_common_admin::EVENT_SCHEMA_VERSION`):
+//! - ("hub", "source"): [source: Address, kind: SourceKind]
+//! - ("hub", "unsource"): [source: Address]
+//! - ("hub", "pushed"): [campaign_id: u64, day: u32, kind: SourceKind]
+
+#![no_std]
+#![allow(clippy::too_many_arguments)]
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Vec};
+
+pub use pulsar_common_errors::PulsarError;
+
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SourceKind {
+    Delivery,   // impressions, clicks, spend (campaign-orchestrator)
+    Fraud,      // verified and flagged views (fraud-prevention)
+    Settlement, // settled payouts (payment-processor / escrow)
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct DailyRollup {
+    pub day: u32, // ledger timestamp / 86_400
+    pub impressions: u64,
+    pub clicks: u64,
+    pub spend: i128,
+    pub verified_views: u64,
+    pub flagged_views: u64,
+    pub settled: i128,
+    pub updated_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct CampaignDashboard {
+    pub campaign_id: u64,
+    pub impressions: u64,
+    pub clicks: u64,
+    pub spend: i128,
+    pub verified_views: u64,
+    pub flagged_views: u64,
+    pub settled: i128,
+    pub ctr_bps: u32,        // clicks * 10_000 / impressions
+    pub cpm: i128,           // spend per 1000 impressions
+    pub fraud_rate_bps: u32, // flagged * 10_000 / (verified + flagged)
+    pub first_day: u32,
+    pub last_day: u32,
+    pub updated_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    PendingAdmin,
+    Source(Address),
+    Daily(u64, u32), // campaign_id, day
+    Dashboard(u64),
+}
+
+const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
+const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
+const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
+
+const SECS_PER_DAY: u64 = 86_400;
+const MAX_RANGE_DAYS: u32 = 31;
+
+#[contract]
+pub struct AnalyticsHubContract;
+
+#[contractimpl]
+impl AnalyticsHubContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(PulsarError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    pub fn add_source(
+        env: Env,
+        admin: Address,
+        source: Address,
+        kind: SourceKind,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::Source(source.clone()), &kind);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("source"),
+            (source, kind),
+        );
+        Ok(())
+    }
+
+    pub fn remove_source(env: Env, admin: Address, source: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .remove(&DataKey::Source(source.clone()));
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("unsource"),
+            source,
+        );
+        Ok(())
+    }
+
+    pub fn push_delivery(
+        env: Env,
+        source: Address,
+        campaign_id: u64,
+        day: u32,
+        impressions: u64,
+        clicks: u64,
+        spend: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_source(&env, &source, SourceKind::Delivery, day)?;
+        if clicks > impressions || spend < 0 {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let mut daily = Self::_load_daily(&env, campaign_id, day);
+        let mut dash = Self::_load_dashboard(&env, campaign_id, day);
+        dash.impressions = dash.impressions - daily.impressions + impressions;
+        dash.clicks = dash.clicks - daily.clicks + clicks;
+        dash.spend = dash.spend - daily.spend + spend;
+        daily.impressions = impressions;
+        daily.clicks = clicks;
+        daily.spend = spend;
+        Self::_save(&env, campaign_id, daily, dash, SourceKind::Delivery);
+        Ok(())
+    }
+
+    pub fn push_fraud(
+        env: Env,
+        source: Address,
+        campaign_id: u64,
+        day: u32,
+        verified_views: u64,
+        flagged_views: u64,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_source(&env, &source, SourceKind::Fraud, day)?;
+
+        let mut daily = Self::_load_daily(&env, campaign_id, day);
+        let mut dash = Self::_load_dashboard(&env, campaign_id, day);
+        dash.verified_views = dash.verified_views - daily.verified_views + verified_views;
+        dash.flagged_views = dash.flagged_views - daily.flagged_views + flagged_views;
+        daily.verified_views = verified_views;
+        daily.flagged_views = flagged_views;
+        Self::_save(&env, campaign_id, daily, dash, SourceKind::Fraud);
+        Ok(())
+    }
+
+    pub fn push_settlement(
+        env: Env,
+        source: Address,
+        campaign_id: u64,
+        day: u32,
+        settled: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_source(&env, &source, SourceKind::Settlement, day)?;
+        if settled < 0 {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let mut daily = Self::_load_daily(&env, campaign_id, day);
+        let mut dash = Self::_load_dashboard(&env, campaign_id, day);
+        dash.settled = dash.settled - daily.settled + settled;
+        daily.settled = settled;
+        Self::_save(&env, campaign_id, daily, dash, SourceKind::Settlement);
+        Ok(())
+    }
+
+    pub fn get_campaign_dashboard(env: Env, campaign_id: u64) -> Option<CampaignDashboard> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Dashboard(campaign_id))
+    }
+
+    pub fn get_daily(env: Env, campaign_id: u64, day: u32) -> Option<DailyRollup> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Daily(campaign_id, day))
+    }
+
+    /// Reported days in `[from_day, to_day]`; days with no rollup are skipped
+    pub fn get_range(
+        env: Env,
+        campaign_id: u64,
+        from_day: u32,
+        to_day: u32,
+    ) -> Result<Vec<DailyRollup>, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if to_day < from_day || to_day - from_day >= MAX_RANGE_DAYS {
+            return Err(PulsarError::InvalidInput);
+        }
+        let mut out = Vec::new(&env);
+        for day in from_day..=to_day {
+            if let Some(daily) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, DailyRollup>(&DataKey::Daily(campaign_id, day))
+            {
+                out.push_back(daily);
+            }
+        }
+        Ok(out)
+    }
+
+    pub fn get_source_kind(env: Env, source: Address) -> Option<SourceKind> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().get(&DataKey::Source(source))
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::propose_admin(
+            &env,
+            &DataKey::Admin,
+            &DataKey::PendingAdmin,
+            current_admin,
+            new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
+
+    // ─── internal helpers ────────────────────────────────────────────────────
+
+    fn _require_admin(env: &Env, admin: &Address) -> Result<(), PulsarError> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if *admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn _require_source(
+        env: &Env,
+        source: &Address,
+        kind: SourceKind,
+        day: u32,
+    ) -> Result<(), PulsarError> {
+        source.require_auth();
+        let stored: Option<SourceKind> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Source(source.clone()));
+        if stored != Some(kind) {
+            return Err(PulsarError::Unauthorized);
+        }
+        if day as u64 > env.ledger().timestamp() / SECS_PER_DAY {
+            return Err(PulsarError::InvalidInput);
+        }
+        Ok(())
+    }
+
+    fn _load_daily(env: &Env, campaign_id: u64, day: u32) -> DailyRollup {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Daily(campaign_id, day))
+            .unwrap_or(DailyRollup {
+                day,
+                impressions: 0,
+                clicks: 0,
+                spend: 0,
+                verified_views: 0,
+                flagged_views: 0,
+                settled: 0,
+                updated_at: 0,
+            })
+    }
+
+    fn _load_dashboard(env: &Env, campaign_id: u64, day: u32) -> CampaignDashboard {
+        let mut dash = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Dashboard(campaign_id))
+            .unwrap_or(CampaignDashboard {
+                campaign_id,
+                impressions: 0,
+                clicks: 0,
+                spend: 0,
+                verified_views: 0,
+                flagged_views: 0,
+                settled: 0,
+                ctr_bps: 0,
+                cpm: 0,
+                fraud_rate_bps: 0,
+                first_day: day,
+                last_day: day,
+                updated_at: 0,
+            });
+        if day < dash.first_day {
+            dash.first_day = day;
+        }
+        if day > dash.last_day {
+            dash.last_day = day;
+        }
+        dash
+    }
+
+    fn _save(
+        env: &Env,
+        campaign_id: u64,
+        mut daily: DailyRollup,
+        mut dash: CampaignDashboard,
+        kind: SourceKind,
+    ) {
+        let now = env.ledger().timestamp();
+        daily.updated_at = now;
+        dash.updated_at = now;
+        dash.ctr_bps = (dash.clicks * 10_000)
+            .checked_div(dash.impressions)
+            .unwrap_or(0) as u32;
+        dash.cpm = if dash.impressions > 0 {
+            dash.spend * 1_000 / dash.impressions as i128
+        } else {
+            0
+        };
+        let checked = dash.verified_views + dash.flagged_views;
+        dash.fraud_rate_bps = (dash.flagged_views * 10_000)
+            .checked_div(checked)
+            .unwrap_or(0) as u32;
+
+        let day = daily.day;
+        let _ttl_key = DataKey::Daily(campaign_id, day);
+        env.storage().persistent().set(&_ttl_key, &daily);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        let _ttl_key = DataKey::Dashboard(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &dash);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("hub"),
+            symbol_short!("pushed"),
+            (campaign_id, day, kind),
+        );
+    }
+}
+
+mod test;
@@ -0,0 +1,144 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+// ─── helpers ─────────────────────────────────────────────────────────────────
+
+struct Setup<'a> {
+    client: AnalyticsHubContractClient<'a>,
+    admin: Address,
+    delivery: Address,
+    fraud: Address,
+    settlement: Address,
+}
+
+/// Hub at day 10 with one source of each kind
+fn setup(env: &Env) -> Setup<'_> {
+    env.ledger().with_mut(|li| li.timestamp = 10 * 86_400 + 100);
+    let admin = Address::generate(env);
+    let contract_id = env.register(AnalyticsHubContract, ());
+    let client = AnalyticsHubContractClient::new(env, &contract_id);
+    client.initialize(&admin);
+
+    let delivery = Address::generate(env);
+    let fraud = Address::generate(env);
+    let settlement = Address::generate(env);
+    client.add_source(&admin, &delivery, &SourceKind::Delivery);
+    client.add_source(&admin, &fraud, &SourceKind::Fraud);
+    client.add_source(&admin, &settlement, &SourceKind::Settlement);
+
+    Setup {
+        client,
+        admin,
+        delivery,
+        fraud,
+        settlement,
+    }
+}
+
+// ─── tests ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_dashboard_combines_all_sources() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    s.client
+        .push_delivery(&s.delivery, &1u64, &9u32, &2_000u64, &40u64, &10_000i128);
+    s.client
+        .push_delivery(&s.delivery, &1u64, &10u32, &2_000u64, &60u64, &10_000i128);
+    s.client
+        .push_fraud(&s.fraud, &1u64, &10u32, &3_800u64, &200u64);
+    s.client
+        .push_settlement(&s.settlement, &1u64, &10u32, &15_000i128);
+
+    let dash = s.client.get_campaign_dashboard(&1u64).unwrap();
+    assert_eq!(dash.impressions, 4_000);
+    assert_eq!(dash.clicks, 100);
+    assert_eq!(dash.spend, 20_000);
+    assert_eq!(dash.settled, 15_000);
+    assert_eq!(dash.ctr_bps, 250);
+    assert_eq!(dash.cpm, 5_000);
+    assert_eq!(dash.fraud_rate_bps, 500);
+    assert_eq!(dash.first_day, 9);
+    assert_eq!(dash.last_day, 10);
+
+    let day = s.client.get_daily(&1u64, &10u32).unwrap();
+    assert_eq!(day.clicks, 60);
+    assert_eq!(day.flagged_views, 200);
+}
+
+#[test]
+fn test_repush_replaces_day_figures() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    s.client
+        .push_delivery(&s.delivery, &1u64, &10u32, &1_000u64, &10u64, &5_000i128);
+    s.client
+        .push_delivery(&s.delivery, &1u64, &10u32, &1_500u64, &12u64, &7_500i128);
+
+    let dash = s.client.get_campaign_dashboard(&1u64).unwrap();
+    assert_eq!(dash.impressions, 1_500);
+    assert_eq!(dash.clicks, 12);
+    assert_eq!(dash.spend, 7_500);
+}
+
+#[test]
+fn test_get_range_skips_missing_days() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    s.client
+        .push_delivery(&s.delivery, &1u64, &3u32, &100u64, &1u64, &50i128);
+    s.client
+        .push_delivery(&s.delivery, &1u64, &7u32, &200u64, &2u64, &90i128);
+
+    let range = s.client.get_range(&1u64, &0u32, &10u32);
+    assert_eq!(range.len(), 2);
+    assert_eq!(range.get(1).unwrap().day, 7);
+    assert!(s.client.try_get_range(&1u64, &0u32, &31u32).is_err());
+}
+
+#[test]
+fn test_source_limited_to_its_kind() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    assert_eq!(
+        s.client
+            .try_push_fraud(&s.delivery, &1u64, &10u32, &10u64, &0u64),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_removed_source_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    s.client.remove_source(&s.admin, &s.settlement);
+    assert_eq!(
+        s.client
+            .try_push_settlement(&s.settlement, &1u64, &10u32, &100i128),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_future_day_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    assert_eq!(
+        s.client
+            .try_push_delivery(&s.delivery, &1u64, &11u32, &100u64, &1u64, &50i128),
+        Err(Ok(PulsarError::InvalidInput))
+    );
+}
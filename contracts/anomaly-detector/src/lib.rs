@@ -2,7 +2,10 @@
 //! On-chain anomaly detection for ad campaign traffic on Stellar.
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
+#![allow(clippy::too_many_arguments)]
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec};
+
+pub use pulsar_common_errors::PulsarError;
 
 #[contracttype]
 #[derive(Clone)]
@@ -50,12 +53,30 @@ pub struct TrafficBaseline {
     pub last_updated: u64,
 }
 
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub enum TrafficMetric {
+    Impressions,
+    Clicks,
+}
+
+/// Outcome of checking traffic against a campaign's baseline, without filing
+/// a report
+#[contracttype]
+#[derive(Clone)]
+pub struct AnomalyEvaluation {
+    pub would_trigger: bool,
+    pub exceeded_metrics: Vec<TrafficMetric>,
+    pub severity_suggestion: AnomalySeverity,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
     Admin,
     PendingAdmin,
     OracleAddress,
+    OracleRegistry,
     ReportCounter,
     SpikeThreshold,
     Report(u64),
@@ -73,12 +94,12 @@ pub struct AnomalyDetectorContract;
 
 #[contractimpl]
 impl AnomalyDetectorContract {
-    pub fn initialize(env: Env, admin: Address, oracle: Address) {
+    pub fn initialize(env: Env, admin: Address, oracle: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -89,6 +110,7 @@ impl AnomalyDetectorContract {
         env.storage()
             .instance()
             .set(&DataKey::SpikeThreshold, &300u32); // 300% = 3x normal
+        Ok(())
     }
 
     pub fn set_baseline(
@@ -98,19 +120,11 @@ impl AnomalyDetectorContract {
         avg_impressions: u64,
         avg_clicks: u64,
         spike_threshold: u32,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        oracle.require_auth();
-        let stored_oracle: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::OracleAddress)
-            .unwrap();
-        if oracle != stored_oracle {
-            panic!("unauthorized");
-        }
+        Self::_require_oracle(&env, &oracle)?;
 
         let baseline = TrafficBaseline {
             campaign_id,
@@ -127,6 +141,7 @@ impl AnomalyDetectorContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     pub fn report_anomaly(
@@ -141,19 +156,11 @@ impl AnomalyDetectorContract {
         auto_action: bool,
         current_impressions_per_hour: u64,
         current_clicks_per_hour: u64,
-    ) -> u64 {
+    ) -> Result<u64, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        oracle.require_auth();
-        let stored_oracle: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::OracleAddress)
-            .unwrap();
-        if oracle != stored_oracle {
-            panic!("unauthorized");
-        }
+        Self::_require_oracle(&env, &oracle)?;
 
         // Validate against baseline if it exists
         let baseline: Option<TrafficBaseline> = env
@@ -162,23 +169,15 @@ impl AnomalyDetectorContract {
             .get(&DataKey::Baseline(campaign_id));
         
         if let Some(b) = baseline {
-            // Calculate threshold multiplier (e.g., 300% = 3.0x)
-            let threshold_multiplier = b.spike_threshold_pct as u64;
-            
-            // Check if current metrics exceed baseline thresholds
-            let impressions_threshold = b.avg_impressions_per_hour
-                .saturating_mul(threshold_multiplier)
-                .saturating_div(100);
-            let clicks_threshold = b.avg_clicks_per_hour
-                .saturating_mul(threshold_multiplier)
-                .saturating_div(100);
-            
             // Validate that at least one metric exceeds the threshold
-            let impressions_exceeded = current_impressions_per_hour > impressions_threshold;
-            let clicks_exceeded = current_clicks_per_hour > clicks_threshold;
-            
-            if !impressions_exceeded && !clicks_exceeded {
-                panic!("metrics do not exceed baseline thresholds");
+            let evaluation = Self::_evaluate(
+                &env,
+                &b,
+                current_impressions_per_hour,
+                current_clicks_per_hour,
+            );
+            if !evaluation.would_trigger {
+                return Err(PulsarError::InvalidState);
             }
         }
 
@@ -191,17 +190,14 @@ impl AnomalyDetectorContract {
 
         // Auto-flag critical publisher anomalies
         if let Some(ref pub_addr) = publisher {
-            match severity {
-                AnomalySeverity::Critical => {
-                    let _ttl_key = DataKey::FlaggedPublisher(pub_addr.clone());
-                    env.storage().persistent().set(&_ttl_key, &true);
-                    env.storage().persistent().extend_ttl(
-                        &_ttl_key,
-                        PERSISTENT_LIFETIME_THRESHOLD,
-                        PERSISTENT_BUMP_AMOUNT,
-                    );
-                }
-                _ => {}
+            if let AnomalySeverity::Critical = severity {
+                let _ttl_key = DataKey::FlaggedPublisher(pub_addr.clone());
+                env.storage().persistent().set(&_ttl_key, &true);
+                env.storage().persistent().extend_ttl(
+                    &_ttl_key,
+                    PERSISTENT_LIFETIME_THRESHOLD,
+                    PERSISTENT_BUMP_AMOUNT,
+                );
             }
         }
 
@@ -230,29 +226,35 @@ impl AnomalyDetectorContract {
             .instance()
             .set(&DataKey::ReportCounter, &report_id);
 
-        env.events().publish(
-            (symbol_short!("anomaly"), symbol_short!("detected")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("anomaly"),
+            symbol_short!("detected"),
             (report_id, campaign_id),
         );
 
-        report_id
+        Ok(report_id)
     }
 
-    pub fn resolve_anomaly(env: Env, admin: Address, report_id: u64) {
+    pub fn resolve_anomaly(env: Env, admin: Address, report_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut report: AnomalyReport = env
             .storage()
             .persistent()
             .get(&DataKey::Report(report_id))
-            .expect("report not found");
+            .ok_or(PulsarError::NotFound)?;
 
         report.resolved = true;
         report.resolved_at = Some(env.ledger().timestamp());
@@ -263,6 +265,32 @@ impl AnomalyDetectorContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
+    }
+
+    /// Dry run of the baseline check `report_anomaly` applies. Campaigns
+    /// without a baseline never trigger.
+    pub fn evaluate(
+        env: Env,
+        campaign_id: u64,
+        impressions_per_hour: u64,
+        clicks_per_hour: u64,
+    ) -> AnomalyEvaluation {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        match env
+            .storage()
+            .persistent()
+            .get::<DataKey, TrafficBaseline>(&DataKey::Baseline(campaign_id))
+        {
+            Some(b) => Self::_evaluate(&env, &b, impressions_per_hour, clicks_per_hour),
+            None => AnomalyEvaluation {
+                would_trigger: false,
+                exceeded_metrics: Vec::new(&env),
+                severity_suggestion: AnomalySeverity::Low,
+            },
+        }
     }
 
     pub fn get_report(env: Env, report_id: u64) -> Option<AnomalyReport> {
@@ -301,18 +329,133 @@ impl AnomalyDetectorContract {
             .unwrap_or(0)
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    /// Once set, oracle checks go through the shared oracle registry
+    /// (domain `Anomaly`) instead of the single stored oracle address
+    pub fn set_oracle_registry(
+        env: Env,
+        admin: Address,
+        registry: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::OracleRegistry, &registry);
+        Ok(())
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
+
+    /// A metric exceeds when it is above its baseline average times
+    /// `spike_threshold_pct`. Severity follows the worst overshoot: up to 1.5x
+    /// the threshold is Low, 2x Medium, 3x High, beyond that Critical.
+    fn _evaluate(
+        env: &Env,
+        baseline: &TrafficBaseline,
+        impressions_per_hour: u64,
+        clicks_per_hour: u64,
+    ) -> AnomalyEvaluation {
+        // e.g. 300% = 3.0x
+        let threshold_multiplier = baseline.spike_threshold_pct as u64;
+        let impressions_threshold = baseline
+            .avg_impressions_per_hour
+            .saturating_mul(threshold_multiplier)
+            .saturating_div(100);
+        let clicks_threshold = baseline
+            .avg_clicks_per_hour
+            .saturating_mul(threshold_multiplier)
+            .saturating_div(100);
+
+        let mut exceeded_metrics = Vec::new(env);
+        let mut worst_pct: u64 = 0; // current as % of threshold
+        for (metric, current, threshold) in [
+            (TrafficMetric::Impressions, impressions_per_hour, impressions_threshold),
+            (TrafficMetric::Clicks, clicks_per_hour, clicks_threshold),
+        ] {
+            if current > threshold {
+                exceeded_metrics.push_back(metric);
+                let pct = current
+                    .saturating_mul(100)
+                    .checked_div(threshold)
+                    .unwrap_or(u64::MAX);
+                worst_pct = worst_pct.max(pct);
+            }
+        }
+
+        let severity_suggestion = match worst_pct {
+            0..=150 => AnomalySeverity::Low,
+            151..=200 => AnomalySeverity::Medium,
+            201..=300 => AnomalySeverity::High,
+            _ => AnomalySeverity::Critical,
+        };
+        AnomalyEvaluation {
+            would_trigger: !exceeded_metrics.is_empty(),
+            exceeded_metrics,
+            severity_suggestion,
+        }
+    }
+
+    fn _is_oracle(env: &Env, oracle: &Address) -> bool {
+        if let Some(registry) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::OracleRegistry)
+        {
+            return pulsar_common_rbac::registry_allows(
+                env,
+                &registry,
+                oracle,
+                pulsar_common_rbac::OracleDomain::Anomaly,
+            );
+        }
+        let stored_oracle: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::OracleAddress)
+            .unwrap();
+        *oracle == stored_oracle
+    }
+
+    fn _require_oracle(env: &Env, oracle: &Address) -> Result<(), PulsarError> {
+        oracle.require_auth();
+        if !Self::_is_oracle(env, oracle) {
+            return Err(PulsarError::Unauthorized);
+        }
+        Ok(())
     }
 }
 
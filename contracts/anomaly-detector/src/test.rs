@@ -2,10 +2,29 @@
 use super::*;
 use soroban_sdk::{testutils::Address as _, Address, Env, String};
 
+mod registry_mock {
+    use pulsar_common_rbac::OracleDomain;
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockOracleRegistry;
+
+    #[contractimpl]
+    impl MockOracleRegistry {
+        pub fn allow(env: Env, oracle: Address, domain: OracleDomain) {
+            env.storage().persistent().set(&(oracle, domain), &true);
+        }
+
+        pub fn is_authorized(env: Env, oracle: Address, domain: OracleDomain) -> bool {
+            env.storage().persistent().has(&(oracle, domain))
+        }
+    }
+}
+
 fn setup(env: &Env) -> (AnomalyDetectorContractClient<'_>, Address, Address) {
     let admin = Address::generate(env);
     let oracle = Address::generate(env);
-    let id = env.register_contract(None, AnomalyDetectorContract);
+    let id = env.register(AnomalyDetectorContract, ());
     let c = AnomalyDetectorContractClient::new(env, &id);
     c.initialize(&admin, &oracle);
     (c, admin, oracle)
@@ -22,23 +41,22 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
-    let id = env.register_contract(None, AnomalyDetectorContract);
+    let id = env.register(AnomalyDetectorContract, ());
     let c = AnomalyDetectorContractClient::new(&env, &id);
     let a = Address::generate(&env);
     let o = Address::generate(&env);
     c.initialize(&a, &o);
-    c.initialize(&a, &o);
+    assert_eq!(c.try_initialize(&a, &o), Err(Ok(PulsarError::AlreadyInitialized)));
 }
 
 #[test]
 #[should_panic]
 fn test_initialize_non_admin_fails() {
     let env = Env::default();
-    let id = env.register_contract(None, AnomalyDetectorContract);
+    let id = env.register(AnomalyDetectorContract, ());
     let c = AnomalyDetectorContractClient::new(&env, &id);
     c.initialize(&Address::generate(&env), &Address::generate(&env));
 }
@@ -55,12 +73,14 @@ fn test_set_baseline() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_set_baseline_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, _, _) = setup(&env);
-    c.set_baseline(&Address::generate(&env), &1u64, &1000u64, &50u64, &5u32);
+    assert_eq!(
+        c.try_set_baseline(&Address::generate(&env), &1u64, &1000u64, &50u64, &5u32),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
@@ -116,7 +136,6 @@ fn test_get_report_count_initial() {
 }
 
 #[test]
-#[should_panic(expected = "metrics do not exceed baseline thresholds")]
 fn test_report_anomaly_below_threshold() {
     let env = Env::default();
     env.mock_all_auths();
@@ -129,7 +148,7 @@ fn test_report_anomaly_below_threshold() {
     // Try to report anomaly with metrics NOT exceeding threshold
     // 2000 impressions < 3000 threshold (1000 * 300%)
     // 100 clicks < 150 threshold (50 * 300%)
-    c.report_anomaly(
+    let result = c.try_report_anomaly(
         &oracle,
         &1u64,
         &Some(publisher.clone()),
@@ -141,6 +160,7 @@ fn test_report_anomaly_below_threshold() {
         &2000u64, // current_impressions_per_hour (below threshold)
         &100u64,  // current_clicks_per_hour (below threshold)
     );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidState)));
 }
 
 #[test]
@@ -193,3 +213,43 @@ fn test_report_anomaly_clicks_exceed_threshold() {
     );
     assert_eq!(c.get_report_count(), 1);
 }
+
+#[test]
+fn test_evaluate_previews_without_reporting() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, oracle) = setup(&env);
+    c.set_baseline(&oracle, &1u64, &1000u64, &50u64, &300u32);
+
+    // 2000 impressions < 3000 threshold, 200 clicks is 133% of 150
+    let eval = c.evaluate(&1u64, &2000u64, &200u64);
+    assert!(eval.would_trigger);
+    assert_eq!(eval.exceeded_metrics, soroban_sdk::vec![&env, TrafficMetric::Clicks]);
+    assert!(matches!(eval.severity_suggestion, AnomalySeverity::Low));
+    assert_eq!(c.get_report_count(), 0);
+
+    // 10000 impressions is 333% of 3000
+    let eval = c.evaluate(&1u64, &10_000u64, &200u64);
+    assert_eq!(eval.exceeded_metrics.len(), 2);
+    assert!(matches!(eval.severity_suggestion, AnomalySeverity::Critical));
+
+    assert!(!c.evaluate(&1u64, &3000u64, &150u64).would_trigger);
+    assert!(!c.evaluate(&2u64, &u64::MAX, &u64::MAX).would_trigger);
+}
+
+#[test]
+fn test_oracle_registry_replaces_stored_oracle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, oracle) = setup(&env);
+    let registry_id = env.register(registry_mock::MockOracleRegistry, ());
+    let registry = registry_mock::MockOracleRegistryClient::new(&env, &registry_id);
+    let reporter = Address::generate(&env);
+    registry.allow(&reporter, &pulsar_common_rbac::OracleDomain::Anomaly);
+    c.set_oracle_registry(&admin, &registry_id);
+
+    c.set_baseline(&reporter, &1u64, &1000u64, &50u64, &300u32);
+    assert!(c
+        .try_set_baseline(&oracle, &2u64, &1000u64, &50u64, &300u32)
+        .is_err());
+}
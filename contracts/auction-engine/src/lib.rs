@@ -1,7 +1,7 @@
 //! PulsarTrack - Auction Engine (Soroban)
 //! Real-time bidding (RTB) auction system for ad impressions on Stellar.
 //!
-//! Events:
+//! Events (third topic is `pulsar_common_admin::EVENT_SCHEMA_VERSION`):
 //! - ("auction", "created"): [auction_id: u64, publisher: Address]
 //! - ("bid", "placed"): [auction_id: u64, bidder: Address, amount: i128]
 //! - ("auction", "settle"): [auction_id: u64, winner: Option<Address>, amount: Option<i128>]
@@ -11,6 +11,8 @@ use soroban_sdk::{
     contract, contractimpl, contracttype, symbol_short, token, Address, Env, String,
 };
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone, PartialEq)]
 pub enum AuctionStatus {
@@ -69,12 +71,12 @@ pub struct AuctionEngineContract;
 
 #[contractimpl]
 impl AuctionEngineContract {
-    pub fn initialize(env: Env, admin: Address, token: Address) {
+    pub fn initialize(env: Env, admin: Address, token: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -82,6 +84,7 @@ impl AuctionEngineContract {
         env.storage()
             .instance()
             .set(&DataKey::AuctionCounter, &0u64);
+        Ok(())
     }
 
     pub fn create_auction(
@@ -130,15 +133,23 @@ impl AuctionEngineContract {
             .instance()
             .set(&DataKey::AuctionCounter, &auction_id);
 
-        env.events().publish(
-            (symbol_short!("auction"), symbol_short!("created")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("auction"),
+            symbol_short!("created"),
             (auction_id, publisher),
         );
 
         auction_id
     }
 
-    pub fn place_bid(env: Env, bidder: Address, auction_id: u64, amount: i128, campaign_id: u64) {
+    pub fn place_bid(
+        env: Env,
+        bidder: Address,
+        auction_id: u64,
+        amount: i128,
+        campaign_id: u64,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -148,19 +159,19 @@ impl AuctionEngineContract {
             .storage()
             .persistent()
             .get(&DataKey::Auction(auction_id))
-            .expect("auction not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if auction.status != AuctionStatus::Open {
-            panic!("auction not open");
+            return Err(PulsarError::InvalidState);
         }
 
         let now = env.ledger().timestamp();
         if now > auction.end_time {
-            panic!("auction ended");
+            return Err(PulsarError::Expired);
         }
 
         if amount < auction.floor_price {
-            panic!("bid below floor price");
+            return Err(PulsarError::InvalidAmount);
         }
 
         // Check if higher than current best
@@ -170,7 +181,7 @@ impl AuctionEngineContract {
             .get(&DataKey::HighestBid(auction_id));
         if let Some(high) = current_high {
             if amount <= high {
-                panic!("bid too low");
+                return Err(PulsarError::InvalidAmount);
             }
         }
 
@@ -226,13 +237,16 @@ impl AuctionEngineContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("bid"), symbol_short!("placed")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("bid"),
+            symbol_short!("placed"),
             (auction_id, bidder, amount),
         );
+        Ok(())
     }
 
-    pub fn settle_auction(env: Env, caller: Address, auction_id: u64) {
+    pub fn settle_auction(env: Env, caller: Address, auction_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -240,16 +254,20 @@ impl AuctionEngineContract {
             .storage()
             .persistent()
             .get(&DataKey::Auction(auction_id))
-            .expect("auction not found");
+            .ok_or(PulsarError::NotFound)?;
 
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if caller != auction.publisher && caller != admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let now = env.ledger().timestamp();
         if now < auction.end_time && caller != admin {
-            panic!("auction still running");
+            return Err(PulsarError::TooEarly);
         }
 
         auction.status = if auction.winning_bid.is_some() {
@@ -281,10 +299,13 @@ impl AuctionEngineContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("auction"), symbol_short!("settle")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("auction"),
+            symbol_short!("settle"),
             (auction_id, auction.winner, auction.winning_bid),
         );
+        Ok(())
     }
 
     pub fn get_auction(env: Env, auction_id: u64) -> Option<Auction> {
@@ -324,18 +345,30 @@ impl AuctionEngineContract {
             .get(&DataKey::HighestBid(auction_id))
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
@@ -49,7 +49,6 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
@@ -59,7 +58,10 @@ fn test_initialize_twice() {
     let contract_id = env.register_contract(None, AuctionEngineContract);
     let client = AuctionEngineContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token);
-    client.initialize(&admin, &token);
+    assert_eq!(
+        client.try_initialize(&admin, &token),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -168,7 +170,6 @@ fn test_bid_stored_by_index() {
 // ─── bid error paths ─────────────────────────────────────────────────────────
 
 #[test]
-#[should_panic(expected = "bid below floor price")]
 fn test_bid_below_floor_rejected() {
     let env = Env::default();
     env.mock_all_auths();
@@ -179,11 +180,13 @@ fn test_bid_below_floor_rejected() {
     let auction_id =
         client.create_auction(&publisher, &slot(&env), &1_000i128, &5_000i128, &3600u64);
 
-    client.place_bid(&bidder, &auction_id, &500i128, &1u64); // below 1_000
+    assert_eq!(
+        client.try_place_bid(&bidder, &auction_id, &500i128, &1u64),
+        Err(Ok(PulsarError::InvalidAmount))
+    ); // below 1_000
 }
 
 #[test]
-#[should_panic(expected = "bid too low")]
 fn test_bid_not_higher_than_current_rejected() {
     let env = Env::default();
     env.mock_all_auths();
@@ -196,11 +199,13 @@ fn test_bid_not_higher_than_current_rejected() {
         client.create_auction(&publisher, &slot(&env), &1_000i128, &5_000i128, &3600u64);
 
     client.place_bid(&bidder1, &auction_id, &3_000i128, &1u64);
-    client.place_bid(&bidder2, &auction_id, &2_000i128, &2u64); // lower than current best
+    assert_eq!(
+        client.try_place_bid(&bidder2, &auction_id, &2_000i128, &2u64),
+        Err(Ok(PulsarError::InvalidAmount))
+    ); // lower than current best
 }
 
 #[test]
-#[should_panic(expected = "auction ended")]
 fn test_bid_after_auction_ended() {
     let env = Env::default();
     env.mock_all_auths();
@@ -221,7 +226,10 @@ fn test_bid_after_auction_ended() {
         li.timestamp = 200;
     });
 
-    client.place_bid(&bidder, &auction_id, &2_000i128, &1u64);
+    assert_eq!(
+        client.try_place_bid(&bidder, &auction_id, &2_000i128, &1u64),
+        Err(Ok(PulsarError::Expired))
+    );
 }
 
 // ─── settle_auction ──────────────────────────────────────────────────────────
@@ -327,7 +335,6 @@ fn test_settle_auction_no_bids_cancelled() {
 }
 
 #[test]
-#[should_panic(expected = "auction still running")]
 fn test_settle_auction_still_running() {
     let env = Env::default();
     env.mock_all_auths();
@@ -340,11 +347,13 @@ fn test_settle_auction_still_running() {
 
     client.place_bid(&bidder, &auction_id, &2_000i128, &1u64);
     // time has NOT advanced → still running
-    client.settle_auction(&publisher, &auction_id);
+    assert_eq!(
+        client.try_settle_auction(&publisher, &auction_id),
+        Err(Ok(PulsarError::TooEarly))
+    );
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_settle_auction_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
@@ -359,7 +368,10 @@ fn test_settle_auction_unauthorized() {
         li.timestamp = 200;
     });
 
-    client.settle_auction(&stranger, &auction_id); // not publisher or admin
+    assert_eq!(
+        client.try_settle_auction(&stranger, &auction_id),
+        Err(Ok(PulsarError::Unauthorized))
+    ); // not publisher or admin
 }
 
 // ─── admin can force-settle before end_time ──────────────────────────────────
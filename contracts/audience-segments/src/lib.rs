@@ -4,6 +4,8 @@
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone)]
 pub struct Segment {
@@ -48,18 +50,19 @@ pub struct AudienceSegmentsContract;
 
 #[contractimpl]
 impl AudienceSegmentsContract {
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage()
             .instance()
             .set(&DataKey::SegmentCounter, &0u64);
+        Ok(())
     }
 
     pub fn create_segment(
@@ -105,30 +108,42 @@ impl AudienceSegmentsContract {
             .instance()
             .set(&DataKey::SegmentCounter, &segment_id);
 
-        env.events().publish(
-            (symbol_short!("segment"), symbol_short!("created")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("segment"),
+            symbol_short!("created"),
             (segment_id, creator),
         );
 
         segment_id
     }
 
-    pub fn add_member(env: Env, admin: Address, segment_id: u64, member: Address, score: u32) {
+    pub fn add_member(
+        env: Env,
+        admin: Address,
+        segment_id: u64,
+        member: Address,
+        score: u32,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
 
         let segment: Segment = env
             .storage()
             .persistent()
             .get(&DataKey::Segment(segment_id))
-            .expect("segment not found");
+            .ok_or(PulsarError::NotFound)?;
 
         // Either admin or segment creator can add members
         if admin != stored_admin && admin != segment.creator {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         if env
@@ -136,7 +151,7 @@ impl AudienceSegmentsContract {
             .persistent()
             .has(&DataKey::Membership(segment_id, member.clone()))
         {
-            panic!("already a member");
+            return Err(PulsarError::AlreadyExists);
         }
 
         let membership = SegmentMembership {
@@ -166,23 +181,33 @@ impl AudienceSegmentsContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
-    pub fn remove_member(env: Env, admin: Address, segment_id: u64, member: Address) {
+    pub fn remove_member(
+        env: Env,
+        admin: Address,
+        segment_id: u64,
+        member: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
 
         let segment: Segment = env
             .storage()
             .persistent()
             .get(&DataKey::Segment(segment_id))
-            .expect("segment not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if admin != stored_admin && admin != segment.creator {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         env.storage()
@@ -203,6 +228,7 @@ impl AudienceSegmentsContract {
                 PERSISTENT_BUMP_AMOUNT,
             );
         }
+        Ok(())
     }
 
     pub fn is_member(env: Env, segment_id: u64, member: Address) -> bool {
@@ -252,18 +278,30 @@ impl AudienceSegmentsContract {
             .unwrap_or(0)
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
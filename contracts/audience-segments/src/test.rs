@@ -21,7 +21,6 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
@@ -29,7 +28,10 @@ fn test_initialize_twice() {
     let c = AudienceSegmentsContractClient::new(&env, &id);
     let a = Address::generate(&env);
     c.initialize(&a);
-    c.initialize(&a);
+    assert_eq!(
+        c.try_initialize(&a),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
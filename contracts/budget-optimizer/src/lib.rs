@@ -1,13 +1,21 @@
 //! PulsarTrack - Budget Optimizer (Soroban)
 //! Automated campaign budget optimization and allocation on Stellar.
+//!
+//! Spend is capped per day (released by the pacing mode), per week and per
+//! month, and in total. Weeks and months are fixed 7- and 30-day windows of
+//! ledger time; a weekly or monthly budget of 0 leaves that window uncapped.
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env};
+#![allow(clippy::too_many_arguments)]
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Vec};
+
+pub use pulsar_common_errors::PulsarError;
 
 #[contracttype]
 #[derive(Clone)]
 pub struct BudgetAllocation {
     pub campaign_id: u64,
+    pub advertiser: Address,
     pub total_budget: i128,
     pub daily_budget: i128,
     pub hourly_budget: i128,
@@ -17,6 +25,23 @@ pub struct BudgetAllocation {
     pub target_cpa: i128, // Target cost per acquisition
     pub target_ctr: u32,  // Target CTR * 10000
     pub last_optimized: u64,
+    pub pacing: PacingMode,
+    pub started_at: u64,            // spend velocity is measured from here
+    pub alert_thresholds: Vec<u32>, // bps of daily and total budget that raise an alert
+    pub weekly_budget: i128,        // 0 = uncapped
+    pub monthly_budget: i128,       // 0 = uncapped
+    pub spent_week: i128,
+    pub spent_month: i128,
+    pub rollover: RolloverPolicy,
+    pub carried_over: i128, // unspent budget from yesterday available today
+}
+
+/// What happens to daily budget left unspent at the end of a day
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub enum RolloverPolicy {
+    Strict,         // unspent budget is forfeited
+    CarryOver(u32), // carried into the next day, up to this bps of the daily budget
 }
 
 #[contracttype]
@@ -29,6 +54,55 @@ pub enum OptimizationMode {
     MaxReach,       // Maximize reach
 }
 
+/// How the daily budget is released across the hours of the day
+#[contracttype]
+#[derive(Clone)]
+pub enum PacingMode {
+    Even,                // daily_budget / 24 released each hour
+    Accelerated,         // whole daily budget available immediately
+    Dayparted(Vec<u32>), // 24 relative hour weights
+}
+
+/// Oracle-reported delivery over the current rolling window
+#[contracttype]
+#[derive(Clone)]
+pub struct PerformanceMetrics {
+    pub impressions: u64,
+    pub clicks: u64,
+    pub conversions: u64,
+    pub spend: i128,
+    pub window_start: u64,
+    pub updated_at: u64,
+    pub evaluated: bool, // set once auto_optimize has acted on these numbers
+}
+
+#[contracttype]
+#[derive(Clone, PartialEq)]
+pub enum RuleMetric {
+    Cpa, // compared against target_cpa
+    Ctr, // compared against target_ctr
+}
+
+/// `if metric is above/below target * threshold_bps / 10_000 then move the
+/// daily budget by adjustment_bps` (negative to cut)
+#[contracttype]
+#[derive(Clone)]
+pub struct OptimizationRule {
+    pub metric: RuleMetric,
+    pub above: bool,
+    pub threshold_bps: u32,
+    pub adjustment_bps: i32,
+}
+
+/// When the remaining total budget runs out at the observed spend rate
+#[contracttype]
+#[derive(Clone)]
+pub struct BudgetForecast {
+    pub estimated_end_ts: u64,
+    pub days_remaining: u64,
+    pub daily_velocity: i128,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct OptimizationLog {
@@ -39,6 +113,51 @@ pub struct OptimizationLog {
     pub optimized_at: u64,
 }
 
+/// Floor and ceiling a portfolio rebalance must keep a campaign's daily budget within
+#[contracttype]
+#[derive(Clone)]
+pub struct PortfolioMember {
+    pub campaign_id: u64,
+    pub min_daily: i128,
+    pub max_daily: i128,
+}
+
+/// Campaigns of one advertiser sharing a total and daily budget
+#[contracttype]
+#[derive(Clone)]
+pub struct Portfolio {
+    pub portfolio_id: u64,
+    pub advertiser: Address,
+    pub total_budget: i128,
+    pub daily_budget: i128,
+    pub members: Vec<PortfolioMember>,
+    pub created_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct RebalanceRecord {
+    pub portfolio_id: u64,
+    pub caller: Address,
+    pub weights: Vec<u32>,
+    pub daily_budgets: Vec<i128>, // in member order
+    pub rebalanced_at: u64,
+}
+
+/// Daily budget split across creative/targeting variants of one campaign
+#[contracttype]
+#[derive(Clone)]
+pub struct Experiment {
+    pub campaign_id: u64,
+    pub variant_bps: Vec<u32>, // share of the daily budget per variant
+    pub variant_spent_today: Vec<i128>,
+    pub variant_spent_total: Vec<i128>,
+    pub spend_day: u64, // day variant_spent_today refers to
+    pub winner: Option<u32>,
+    pub created_at: u64,
+    pub concluded_at: Option<u64>,
+}
+
 use soroban_sdk::String;
 
 #[contracttype]
@@ -47,9 +166,20 @@ pub enum DataKey {
     Admin,
     PendingAdmin,
     OracleAddress,
+    OracleRegistry,
+    AuthorizedSpender,
+    ConversionSource,
     Allocation(u64),
     OptLog(u64, u32), // campaign_id, log_index
     OptLogCount(u64),
+    Performance(u64),
+    Rules(u64),
+    PortfolioCount,
+    Portfolio(u64),
+    CampaignPortfolio(u64), // campaign_id -> portfolio_id
+    RebalanceCount(u64),
+    Rebalance(u64, u32), // portfolio_id, record index
+    Experiment(u64),
 }
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
@@ -57,23 +187,33 @@ const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
 const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
 
+const ROLLING_WINDOW_SECS: u64 = 604_800; // 7 days
+const MAX_RULES: u32 = 10;
+const MAX_PORTFOLIO_CAMPAIGNS: u32 = 20;
+const MAX_PAGE_SIZE: u32 = 50;
+const MAX_ALERT_THRESHOLDS: u32 = 10;
+const MAX_VARIANTS: u32 = 10;
+const WEEK_SECS: u64 = 604_800;
+const MONTH_SECS: u64 = 2_592_000; // 30 days
+
 #[contract]
 pub struct BudgetOptimizerContract;
 
 #[contractimpl]
 impl BudgetOptimizerContract {
-    pub fn initialize(env: Env, admin: Address, oracle: Address) {
+    pub fn initialize(env: Env, admin: Address, oracle: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage()
             .instance()
             .set(&DataKey::OracleAddress, &oracle);
+        Ok(())
     }
 
     pub fn set_budget_allocation(
@@ -85,18 +225,29 @@ impl BudgetOptimizerContract {
         optimization_mode: OptimizationMode,
         target_cpa: i128,
         target_ctr: u32,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         advertiser.require_auth();
 
         if daily_budget > total_budget {
-            panic!("daily budget exceeds total");
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let existing: Option<BudgetAllocation> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Allocation(campaign_id));
+        if let Some(existing) = &existing {
+            if existing.advertiser != advertiser {
+                return Err(PulsarError::Unauthorized);
+            }
         }
 
         let allocation = BudgetAllocation {
             campaign_id,
+            advertiser,
             total_budget,
             daily_budget,
             hourly_budget: daily_budget / 24,
@@ -106,6 +257,21 @@ impl BudgetOptimizerContract {
             target_cpa,
             target_ctr,
             last_optimized: env.ledger().timestamp(),
+            pacing: existing
+                .clone()
+                .map_or(PacingMode::Accelerated, |a| a.pacing),
+            started_at: env.ledger().timestamp(),
+            alert_thresholds: existing
+                .clone()
+                .map_or(Vec::from_array(&env, [5_000, 8_000, 10_000]), |a| {
+                    a.alert_thresholds
+                }),
+            weekly_budget: existing.clone().map_or(0, |a| a.weekly_budget),
+            monthly_budget: existing.clone().map_or(0, |a| a.monthly_budget),
+            spent_week: 0,
+            spent_month: 0,
+            rollover: existing.map_or(RolloverPolicy::Strict, |a| a.rollover),
+            carried_over: 0,
         };
 
         let _ttl_key = DataKey::Allocation(campaign_id);
@@ -115,44 +281,42 @@ impl BudgetOptimizerContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("budget"),
+            symbol_short!("allocated"),
+            (campaign_id, total_budget, daily_budget),
+        );
+        Ok(())
     }
 
-    pub fn optimize_budget(
+    pub fn set_pacing(
         env: Env,
-        oracle: Address,
+        advertiser: Address,
         campaign_id: u64,
-        new_daily_budget: i128,
-        reason: String,
-    ) {
+        pacing: PacingMode,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        oracle.require_auth();
-        let stored_oracle: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::OracleAddress)
-            .unwrap();
-        if oracle != stored_oracle {
-            panic!("unauthorized");
-        }
+        advertiser.require_auth();
 
         let mut allocation: BudgetAllocation = env
             .storage()
             .persistent()
             .get(&DataKey::Allocation(campaign_id))
-            .expect("allocation not found");
-
-        let old_daily = allocation.daily_budget;
-
-        // Ensure new daily budget doesn't exceed total remaining
-        let remaining = allocation.total_budget - allocation.spent_total;
-        let capped_daily = new_daily_budget.min(remaining);
+            .ok_or(PulsarError::NotFound)?;
+        if allocation.advertiser != advertiser {
+            return Err(PulsarError::Unauthorized);
+        }
 
-        allocation.daily_budget = capped_daily;
-        allocation.hourly_budget = capped_daily / 24;
-        allocation.last_optimized = env.ledger().timestamp();
+        if let PacingMode::Dayparted(weights) = &pacing {
+            if weights.len() != 24 || weights.iter().all(|w| w == 0) {
+                return Err(PulsarError::InvalidInput);
+            }
+        }
 
+        allocation.pacing = pacing;
         let _ttl_key = DataKey::Allocation(campaign_id);
         env.storage().persistent().set(&_ttl_key, &allocation);
         env.storage().persistent().extend_ttl(
@@ -160,63 +324,281 @@ impl BudgetOptimizerContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
+    }
 
-        // Log the optimization
-        let count: u32 = env
+    /// Cap spend per 7-day and 30-day window (0 = uncapped) and choose what
+    /// happens to unspent daily budget
+    pub fn set_period_budgets(
+        env: Env,
+        advertiser: Address,
+        campaign_id: u64,
+        weekly_budget: i128,
+        monthly_budget: i128,
+        rollover: RolloverPolicy,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        advertiser.require_auth();
+
+        let mut allocation: BudgetAllocation = env
             .storage()
             .persistent()
-            .get(&DataKey::OptLogCount(campaign_id))
-            .unwrap_or(0);
-
-        let log = OptimizationLog {
-            campaign_id,
-            old_daily_budget: old_daily,
-            new_daily_budget: capped_daily,
-            reason,
-            optimized_at: env.ledger().timestamp(),
-        };
+            .get(&DataKey::Allocation(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+        if allocation.advertiser != advertiser {
+            return Err(PulsarError::Unauthorized);
+        }
+        if weekly_budget < 0 || monthly_budget < 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        if weekly_budget > 0 && monthly_budget > 0 && weekly_budget > monthly_budget {
+            return Err(PulsarError::InvalidInput);
+        }
+        if let RolloverPolicy::CarryOver(bps) = rollover {
+            if bps == 0 || bps > 10_000 {
+                return Err(PulsarError::InvalidInput);
+            }
+        }
 
-        let _ttl_key = DataKey::OptLog(campaign_id, count);
-        env.storage().persistent().set(&_ttl_key, &log);
+        allocation.weekly_budget = weekly_budget;
+        allocation.monthly_budget = monthly_budget;
+        allocation.rollover = rollover;
+        let _ttl_key = DataKey::Allocation(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &allocation);
         env.storage().persistent().extend_ttl(
             &_ttl_key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
-        let _ttl_key = DataKey::OptLogCount(campaign_id);
-        env.storage().persistent().set(&_ttl_key, &(count + 1));
+        Ok(())
+    }
+
+    pub fn set_alert_thresholds(
+        env: Env,
+        advertiser: Address,
+        campaign_id: u64,
+        thresholds: Vec<u32>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        advertiser.require_auth();
+
+        let mut allocation: BudgetAllocation = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Allocation(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+        if allocation.advertiser != advertiser {
+            return Err(PulsarError::Unauthorized);
+        }
+        if thresholds.len() > MAX_ALERT_THRESHOLDS {
+            return Err(PulsarError::LimitExceeded);
+        }
+        if thresholds.iter().any(|bps| bps == 0 || bps > 10_000) {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        allocation.alert_thresholds = thresholds;
+        let _ttl_key = DataKey::Allocation(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &allocation);
         env.storage().persistent().extend_ttl(
             &_ttl_key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
+    }
 
-        env.events().publish(
-            (symbol_short!("budget"), symbol_short!("optimized")),
-            (campaign_id, capped_daily),
+    /// Add oracle-reported delivery to the campaign's rolling window. The
+    /// window restarts once it is older than ROLLING_WINDOW_SECS.
+    pub fn submit_performance(
+        env: Env,
+        oracle: Address,
+        campaign_id: u64,
+        impressions: u64,
+        clicks: u64,
+        conversions: u64,
+        spend: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_oracle(&env, &oracle)?;
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Allocation(campaign_id))
+        {
+            return Err(PulsarError::NotFound);
+        }
+        if spend < 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+
+        Self::_add_performance(&env, campaign_id, impressions, clicks, conversions, spend);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("budget"),
+            symbol_short!("perf"),
+            (campaign_id, impressions, conversions, spend),
         );
+        Ok(())
     }
 
-    pub fn record_spend(env: Env, admin: Address, campaign_id: u64, amount: i128) {
+    /// Contract allowed to report attributed conversions (the conversion tracker)
+    pub fn set_conversion_source(
+        env: Env,
+        admin: Address,
+        source: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ConversionSource, &source);
+        Ok(())
+    }
+
+    /// Add attributed conversions to the rolling window, feeding the CPA rules
+    pub fn record_conversions(
+        env: Env,
+        source: Address,
+        campaign_id: u64,
+        conversions: u64,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        source.require_auth();
+        let stored: Option<Address> = env.storage().instance().get(&DataKey::ConversionSource);
+        if stored != Some(source) {
+            return Err(PulsarError::Unauthorized);
+        }
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Allocation(campaign_id))
+        {
+            return Err(PulsarError::NotFound);
+        }
+
+        Self::_add_performance(&env, campaign_id, 0, 0, conversions, 0);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("budget"),
+            symbol_short!("convs"),
+            (campaign_id, conversions),
+        );
+        Ok(())
+    }
+
+    /// Replace the campaign's rules; an empty list falls back to the default
+    /// "CPA above 120% of target cuts the daily budget by 10%"
+    pub fn set_optimization_rules(
+        env: Env,
+        advertiser: Address,
+        campaign_id: u64,
+        rules: Vec<OptimizationRule>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        advertiser.require_auth();
+
+        let allocation: BudgetAllocation = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Allocation(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+        if allocation.advertiser != advertiser {
+            return Err(PulsarError::Unauthorized);
+        }
+        if rules.len() > MAX_RULES {
+            return Err(PulsarError::LimitExceeded);
+        }
+        for rule in rules.iter() {
+            if rule.threshold_bps == 0 || rule.adjustment_bps <= -10_000 {
+                return Err(PulsarError::InvalidInput);
+            }
+        }
+
+        let _ttl_key = DataKey::Rules(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &rules);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Ok(())
+    }
+
+    /// Evaluate the campaign's rules against its latest metrics and apply the
+    /// first rule that matches. Anyone may trigger it; each metrics submission
+    /// is acted on at most once. Returns the resulting daily budget.
+    pub fn auto_optimize(env: Env, campaign_id: u64) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
         let mut allocation: BudgetAllocation = env
             .storage()
             .persistent()
             .get(&DataKey::Allocation(campaign_id))
-            .expect("allocation not found");
+            .ok_or(PulsarError::NotFound)?;
+        let mut metrics: PerformanceMetrics = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Performance(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+        if metrics.evaluated {
+            return Err(PulsarError::InvalidState);
+        }
 
-        let current_day = env.ledger().timestamp() / 86_400;
-        let last_day = allocation.last_optimized / 86_400;
-        if current_day > last_day {
-            allocation.spent_today = 0;
+        let rules = Self::get_optimization_rules(env.clone(), campaign_id);
+        let old_daily = allocation.daily_budget;
+        let mut new_daily = old_daily;
+        for rule in rules.iter() {
+            if Self::_rule_matches(&allocation, &metrics, &rule) {
+                new_daily = old_daily + old_daily * rule.adjustment_bps as i128 / 10_000;
+                break;
+            }
         }
 
-        allocation.spent_today += amount;
-        allocation.spent_total += amount;
+        metrics.evaluated = true;
+        let _ttl_key = DataKey::Performance(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &metrics);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        if new_daily == old_daily {
+            return Ok(old_daily);
+        }
+
+        // Ensure new daily budget doesn't exceed total remaining
+        let remaining = allocation.total_budget - allocation.spent_total;
+        let capped_daily = new_daily.min(remaining).max(0);
+
+        Self::_roll_day(&env, &mut allocation);
+        allocation.daily_budget = capped_daily;
+        allocation.hourly_budget = capped_daily / 24;
         allocation.last_optimized = env.ledger().timestamp();
 
         let _ttl_key = DataKey::Allocation(campaign_id);
@@ -226,53 +608,1039 @@ impl BudgetOptimizerContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+
+        Self::_log_optimization(
+            &env,
+            campaign_id,
+            old_daily,
+            capped_daily,
+            String::from_str(&env, "auto_optimize"),
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("budget"),
+            symbol_short!("optimized"),
+            (campaign_id, capped_daily),
+        );
+        Ok(capped_daily)
     }
 
-    pub fn get_allocation(env: Env, campaign_id: u64) -> Option<BudgetAllocation> {
+    pub fn get_performance(env: Env, campaign_id: u64) -> Option<PerformanceMetrics> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         env.storage()
             .persistent()
-            .get(&DataKey::Allocation(campaign_id))
+            .get(&DataKey::Performance(campaign_id))
     }
 
-    pub fn can_spend(env: Env, campaign_id: u64, amount: i128) -> bool {
+    pub fn get_optimization_rules(env: Env, campaign_id: u64) -> Vec<OptimizationRule> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        if let Some(alloc) = env
+        let rules: Vec<OptimizationRule> = env
             .storage()
             .persistent()
-            .get::<DataKey, BudgetAllocation>(&DataKey::Allocation(campaign_id))
-        {
-            let current_day = env.ledger().timestamp() / 86_400;
-            let last_day = alloc.last_optimized / 86_400;
-            let effective_spent_today = if current_day > last_day {
-                0
-            } else {
-                alloc.spent_today
-            };
-
-            effective_spent_today + amount <= alloc.daily_budget
-                && alloc.spent_total + amount <= alloc.total_budget
+            .get(&DataKey::Rules(campaign_id))
+            .unwrap_or(Vec::new(&env));
+        if rules.is_empty() {
+            Vec::from_array(
+                &env,
+                [OptimizationRule {
+                    metric: RuleMetric::Cpa,
+                    above: true,
+                    threshold_bps: 12_000,
+                    adjustment_bps: -1_000,
+                }],
+            )
         } else {
-            false
+            rules
         }
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
-        pulsar_common_admin::propose_admin(
+    pub fn create_portfolio(
+        env: Env,
+        advertiser: Address,
+        total_budget: i128,
+        daily_budget: i128,
+    ) -> Result<u64, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        advertiser.require_auth();
+
+        if total_budget <= 0 || daily_budget <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        if daily_budget > total_budget {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let portfolio_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PortfolioCount)
+            .unwrap_or(0u64)
+            + 1;
+        let portfolio = Portfolio {
+            portfolio_id,
+            advertiser: advertiser.clone(),
+            total_budget,
+            daily_budget,
+            members: Vec::new(&env),
+            created_at: env.ledger().timestamp(),
+        };
+        Self::_save_portfolio(&env, &portfolio);
+        env.storage()
+            .instance()
+            .set(&DataKey::PortfolioCount, &portfolio_id);
+
+        pulsar_common_admin::publish_event(
             &env,
-            &DataKey::Admin,
-            &DataKey::PendingAdmin,
-            current_admin,
-            new_admin,
+            symbol_short!("portfolio"),
+            symbol_short!("created"),
+            (portfolio_id, advertiser),
         );
+        Ok(portfolio_id)
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn add_to_portfolio(
+        env: Env,
+        advertiser: Address,
+        portfolio_id: u64,
+        campaign_id: u64,
+        min_daily: i128,
+        max_daily: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        advertiser.require_auth();
+
+        let mut portfolio: Portfolio = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Portfolio(portfolio_id))
+            .ok_or(PulsarError::NotFound)?;
+        if portfolio.advertiser != advertiser {
+            return Err(PulsarError::Unauthorized);
+        }
+        let allocation: BudgetAllocation = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Allocation(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+        if allocation.advertiser != advertiser {
+            return Err(PulsarError::Unauthorized);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::CampaignPortfolio(campaign_id))
+        {
+            return Err(PulsarError::AlreadyExists);
+        }
+        if min_daily < 0 || max_daily < min_daily {
+            return Err(PulsarError::InvalidInput);
+        }
+        if portfolio.members.len() >= MAX_PORTFOLIO_CAMPAIGNS {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        let mut committed = allocation.total_budget;
+        for member in portfolio.members.iter() {
+            let alloc: BudgetAllocation = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Allocation(member.campaign_id))
+                .unwrap();
+            committed += alloc.total_budget;
+        }
+        if committed > portfolio.total_budget {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        portfolio.members.push_back(PortfolioMember {
+            campaign_id,
+            min_daily,
+            max_daily,
+        });
+        Self::_save_portfolio(&env, &portfolio);
+
+        let _ttl_key = DataKey::CampaignPortfolio(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &portfolio_id);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Ok(())
+    }
+
+    /// Split the portfolio's daily budget across its campaigns by `weights`
+    /// (one per member, in member order), clamped to each member's floor and
+    /// ceiling and to what the campaign has left in total.
+    pub fn rebalance_portfolio(
+        env: Env,
+        caller: Address,
+        portfolio_id: u64,
+        weights: Vec<u32>,
+    ) -> Result<Vec<i128>, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        caller.require_auth();
+
+        let portfolio: Portfolio = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Portfolio(portfolio_id))
+            .ok_or(PulsarError::NotFound)?;
+        if caller != portfolio.advertiser && !Self::_is_oracle(&env, &caller) {
+            return Err(PulsarError::Unauthorized);
+        }
+        if weights.len() != portfolio.members.len() {
+            return Err(PulsarError::InvalidInput);
+        }
+        let weight_sum: u64 = weights.iter().map(|w| w as u64).sum();
+        if weight_sum == 0 {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let mut daily_budgets = Vec::new(&env);
+        for (member, weight) in portfolio.members.iter().zip(weights.iter()) {
+            let mut allocation: BudgetAllocation = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Allocation(member.campaign_id))
+                .unwrap();
+            let share = portfolio.daily_budget * weight as i128 / weight_sum as i128;
+            let remaining = allocation.total_budget - allocation.spent_total;
+            let new_daily = share
+                .clamp(member.min_daily, member.max_daily)
+                .min(remaining)
+                .max(0);
+
+            let old_daily = allocation.daily_budget;
+            Self::_roll_day(&env, &mut allocation);
+            allocation.daily_budget = new_daily;
+            allocation.hourly_budget = new_daily / 24;
+            allocation.last_optimized = env.ledger().timestamp();
+            let _ttl_key = DataKey::Allocation(member.campaign_id);
+            env.storage().persistent().set(&_ttl_key, &allocation);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+            Self::_log_optimization(
+                &env,
+                member.campaign_id,
+                old_daily,
+                new_daily,
+                String::from_str(&env, "portfolio_rebalance"),
+            );
+            daily_budgets.push_back(new_daily);
+        }
+
+        let index: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RebalanceCount(portfolio_id))
+            .unwrap_or(0);
+        let record = RebalanceRecord {
+            portfolio_id,
+            caller,
+            weights,
+            daily_budgets: daily_budgets.clone(),
+            rebalanced_at: env.ledger().timestamp(),
+        };
+        let _ttl_key = DataKey::Rebalance(portfolio_id, index);
+        env.storage().persistent().set(&_ttl_key, &record);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        let _ttl_key = DataKey::RebalanceCount(portfolio_id);
+        env.storage().persistent().set(&_ttl_key, &(index + 1));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("portfolio"),
+            symbol_short!("rebalance"),
+            portfolio_id,
+        );
+        Ok(daily_budgets)
+    }
+
+    pub fn get_portfolio(env: Env, portfolio_id: u64) -> Option<Portfolio> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Portfolio(portfolio_id))
+    }
+
+    pub fn get_rebalance_count(env: Env, portfolio_id: u64) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::RebalanceCount(portfolio_id))
+            .unwrap_or(0)
+    }
+
+    pub fn get_rebalance(env: Env, portfolio_id: u64, index: u32) -> Option<RebalanceRecord> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Rebalance(portfolio_id, index))
+    }
+
+    /// Contract allowed to report spend alongside the admin (the campaign orchestrator)
+    pub fn set_authorized_spender(
+        env: Env,
+        admin: Address,
+        spender: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AuthorizedSpender, &spender);
+        Ok(())
+    }
+
+    /// Campaigns without a budget allocation are not paced here, so their
+    /// spend is accepted and ignored rather than failing the caller
+    pub fn record_spend(
+        env: Env,
+        caller: Address,
+        campaign_id: u64,
+        amount: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_spender(&env, &caller)?;
+        if amount <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Allocation(campaign_id))
+        {
+            return Ok(());
+        }
+        Self::_apply_spend(&env, campaign_id, amount)
+    }
+
+    /// Split the campaign's daily budget across variants (bps, summing to 10_000)
+    pub fn create_experiment(
+        env: Env,
+        advertiser: Address,
+        campaign_id: u64,
+        variants: Vec<u32>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        advertiser.require_auth();
+
+        let allocation: BudgetAllocation = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Allocation(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+        if allocation.advertiser != advertiser {
+            return Err(PulsarError::Unauthorized);
+        }
+        if let Some(existing) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Experiment>(&DataKey::Experiment(campaign_id))
+        {
+            if existing.winner.is_none() {
+                return Err(PulsarError::AlreadyExists);
+            }
+        }
+        if variants.len() < 2 || variants.len() > MAX_VARIANTS {
+            return Err(PulsarError::InvalidInput);
+        }
+        let total_bps: u32 = variants.iter().sum();
+        if total_bps != 10_000 {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let mut zeros = Vec::new(&env);
+        for _ in 0..variants.len() {
+            zeros.push_back(0i128);
+        }
+        let experiment = Experiment {
+            campaign_id,
+            variant_bps: variants,
+            variant_spent_today: zeros.clone(),
+            variant_spent_total: zeros,
+            spend_day: env.ledger().timestamp() / 86_400,
+            winner: None,
+            created_at: env.ledger().timestamp(),
+            concluded_at: None,
+        };
+        Self::_save_experiment(&env, &experiment);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("budget"),
+            symbol_short!("exp_start"),
+            campaign_id,
+        );
+        Ok(())
+    }
+
+    pub fn record_variant_spend(
+        env: Env,
+        caller: Address,
+        campaign_id: u64,
+        variant: u32,
+        amount: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_spender(&env, &caller)?;
+        if amount <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+
+        let mut experiment: Experiment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Experiment(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+        if variant >= experiment.variant_bps.len() {
+            return Err(PulsarError::InvalidInput);
+        }
+        Self::_roll_experiment_day(&env, &mut experiment);
+
+        let allocation: BudgetAllocation = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Allocation(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+        let spent = experiment.variant_spent_today.get(variant).unwrap();
+        if spent + amount > Self::_variant_daily_budget(&allocation, &experiment, variant) {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        experiment.variant_spent_today.set(variant, spent + amount);
+        let total = experiment.variant_spent_total.get(variant).unwrap();
+        experiment.variant_spent_total.set(variant, total + amount);
+        Self::_save_experiment(&env, &experiment);
+
+        Self::_apply_spend(&env, campaign_id, amount)
+    }
+
+    /// Daily budget still available to `variant` today
+    pub fn get_variant_allowance(
+        env: Env,
+        campaign_id: u64,
+        variant: u32,
+    ) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let mut experiment: Experiment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Experiment(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+        if variant >= experiment.variant_bps.len() {
+            return Err(PulsarError::InvalidInput);
+        }
+        Self::_roll_experiment_day(&env, &mut experiment);
+        let allocation: BudgetAllocation = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Allocation(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+        let spent = experiment.variant_spent_today.get(variant).unwrap();
+        Ok((Self::_variant_daily_budget(&allocation, &experiment, variant) - spent).max(0))
+    }
+
+    /// End the experiment and hand the whole daily budget to `winner`
+    pub fn conclude_experiment(
+        env: Env,
+        advertiser: Address,
+        campaign_id: u64,
+        winner: u32,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        advertiser.require_auth();
+
+        let allocation: BudgetAllocation = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Allocation(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+        if allocation.advertiser != advertiser {
+            return Err(PulsarError::Unauthorized);
+        }
+        let mut experiment: Experiment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Experiment(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+        if experiment.winner.is_some() {
+            return Err(PulsarError::AlreadyExists);
+        }
+        if winner >= experiment.variant_bps.len() {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        for index in 0..experiment.variant_bps.len() {
+            let bps = if index == winner { 10_000 } else { 0 };
+            experiment.variant_bps.set(index, bps);
+        }
+        experiment.winner = Some(winner);
+        experiment.concluded_at = Some(env.ledger().timestamp());
+        Self::_save_experiment(&env, &experiment);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("budget"),
+            symbol_short!("exp_end"),
+            (campaign_id, winner),
+        );
+        Ok(())
+    }
+
+    pub fn get_experiment(env: Env, campaign_id: u64) -> Option<Experiment> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Experiment(campaign_id))
+    }
+
+    pub fn get_allocation(env: Env, campaign_id: u64) -> Option<BudgetAllocation> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Allocation(campaign_id))
+    }
+
+    pub fn get_opt_log_count(env: Env, campaign_id: u64) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::OptLogCount(campaign_id))
+            .unwrap_or(0)
+    }
+
+    /// Optimization logs oldest first, at most MAX_PAGE_SIZE per call
+    pub fn list_opt_logs(
+        env: Env,
+        campaign_id: u64,
+        start: u32,
+        limit: u32,
+    ) -> Vec<OptimizationLog> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OptLogCount(campaign_id))
+            .unwrap_or(0);
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+        let mut logs = Vec::new(&env);
+        for index in start..end {
+            if let Some(log) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, OptimizationLog>(&DataKey::OptLog(campaign_id, index))
+            {
+                logs.push_back(log);
+            }
+        }
+        logs
+    }
+
+    /// Project budget exhaustion from the average daily spend since the
+    /// allocation started, capped at the daily budget. None until something
+    /// has been spent.
+    pub fn forecast_exhaustion(
+        env: Env,
+        campaign_id: u64,
+    ) -> Result<Option<BudgetForecast>, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let alloc: BudgetAllocation = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Allocation(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        let now = env.ledger().timestamp();
+        let elapsed = now.saturating_sub(alloc.started_at).max(1);
+        let velocity = (alloc.spent_total * 86_400 / elapsed as i128).min(alloc.daily_budget);
+        if velocity <= 0 {
+            return Ok(None);
+        }
+
+        let remaining = (alloc.total_budget - alloc.spent_total).max(0);
+        let secs_remaining = (remaining * 86_400 / velocity) as u64;
+        Ok(Some(BudgetForecast {
+            estimated_end_ts: now + secs_remaining,
+            days_remaining: secs_remaining / 86_400,
+            daily_velocity: velocity,
+        }))
+    }
+
+    /// How much more may be spent today by the end of `hour` (0-23, UTC)
+    /// under the campaign's pacing mode
+    pub fn get_allowed_spend(env: Env, campaign_id: u64, hour: u32) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if hour > 23 {
+            return Err(PulsarError::InvalidInput);
+        }
+        let alloc: BudgetAllocation = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Allocation(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+        Ok(Self::_allowed_spend(&env, &alloc, hour))
+    }
+
+    pub fn can_spend(env: Env, campaign_id: u64, amount: i128) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if let Some(alloc) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, BudgetAllocation>(&DataKey::Allocation(campaign_id))
+        {
+            let hour = ((env.ledger().timestamp() % 86_400) / 3_600) as u32;
+            amount <= Self::_allowed_spend(&env, &alloc, hour)
+        } else {
+            false
+        }
+    }
+
+    fn _add_performance(
+        env: &Env,
+        campaign_id: u64,
+        impressions: u64,
+        clicks: u64,
+        conversions: u64,
+        spend: i128,
+    ) {
+        let now = env.ledger().timestamp();
+        let mut metrics = match env
+            .storage()
+            .persistent()
+            .get::<DataKey, PerformanceMetrics>(&DataKey::Performance(campaign_id))
+        {
+            Some(m) if now < m.window_start + ROLLING_WINDOW_SECS => m,
+            _ => PerformanceMetrics {
+                impressions: 0,
+                clicks: 0,
+                conversions: 0,
+                spend: 0,
+                window_start: now,
+                updated_at: now,
+                evaluated: false,
+            },
+        };
+        metrics.impressions += impressions;
+        metrics.clicks += clicks;
+        metrics.conversions += conversions;
+        metrics.spend += spend;
+        metrics.updated_at = now;
+        metrics.evaluated = false;
+
+        let _ttl_key = DataKey::Performance(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &metrics);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _require_spender(env: &Env, caller: &Address) -> Result<(), PulsarError> {
+        caller.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        let spender: Option<Address> = env.storage().instance().get(&DataKey::AuthorizedSpender);
+        if *caller != stored_admin && spender.as_ref() != Some(caller) {
+            return Err(PulsarError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn _apply_spend(env: &Env, campaign_id: u64, amount: i128) -> Result<(), PulsarError> {
+        let mut allocation: BudgetAllocation = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Allocation(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        Self::_roll_day(env, &mut allocation);
+        let spent_today_before = allocation.spent_today;
+        let spent_total_before = allocation.spent_total;
+        allocation.spent_today += amount;
+        allocation.spent_total += amount;
+        allocation.spent_week += amount;
+        allocation.spent_month += amount;
+        allocation.last_optimized = env.ledger().timestamp();
+
+        for bps in allocation.alert_thresholds.iter() {
+            if Self::_crosses(
+                spent_today_before,
+                allocation.spent_today,
+                allocation.daily_budget,
+                bps,
+            ) {
+                pulsar_common_admin::publish_event(
+                    env,
+                    symbol_short!("budget"),
+                    symbol_short!("alert"),
+                    (campaign_id, symbol_short!("daily"), bps),
+                );
+            }
+            if Self::_crosses(
+                spent_total_before,
+                allocation.spent_total,
+                allocation.total_budget,
+                bps,
+            ) {
+                pulsar_common_admin::publish_event(
+                    env,
+                    symbol_short!("budget"),
+                    symbol_short!("alert"),
+                    (campaign_id, symbol_short!("total"), bps),
+                );
+            }
+        }
+
+        let _ttl_key = DataKey::Allocation(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &allocation);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Ok(())
+    }
+
+    fn _variant_daily_budget(
+        alloc: &BudgetAllocation,
+        experiment: &Experiment,
+        variant: u32,
+    ) -> i128 {
+        alloc.daily_budget * experiment.variant_bps.get(variant).unwrap() as i128 / 10_000
+    }
+
+    fn _roll_experiment_day(env: &Env, experiment: &mut Experiment) {
+        let today = env.ledger().timestamp() / 86_400;
+        if today > experiment.spend_day {
+            for index in 0..experiment.variant_spent_today.len() {
+                experiment.variant_spent_today.set(index, 0);
+            }
+            experiment.spend_day = today;
+        }
+    }
+
+    fn _save_experiment(env: &Env, experiment: &Experiment) {
+        let _ttl_key = DataKey::Experiment(experiment.campaign_id);
+        env.storage().persistent().set(&_ttl_key, experiment);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _spent_today(env: &Env, alloc: &BudgetAllocation) -> i128 {
+        let current_day = env.ledger().timestamp() / 86_400;
+        let last_day = alloc.last_optimized / 86_400;
+        if current_day > last_day {
+            0
+        } else {
+            alloc.spent_today
+        }
+    }
+
+    /// Budget carried into today under the rollover policy. Only the day
+    /// before today can leave anything unspent that is still carried.
+    fn _carried_over(env: &Env, alloc: &BudgetAllocation) -> i128 {
+        let current_day = env.ledger().timestamp() / 86_400;
+        let last_day = alloc.last_optimized / 86_400;
+        if current_day <= last_day {
+            return alloc.carried_over;
+        }
+        let bps = match alloc.rollover {
+            RolloverPolicy::Strict => return 0,
+            RolloverPolicy::CarryOver(bps) => bps,
+        };
+        let unspent = if current_day == last_day + 1 {
+            alloc.daily_budget + alloc.carried_over - alloc.spent_today
+        } else {
+            alloc.daily_budget
+        };
+        unspent
+            .max(0)
+            .min(alloc.daily_budget * bps as i128 / 10_000)
+    }
+
+    fn _spent_in_window(env: &Env, alloc: &BudgetAllocation, spent: i128, window: u64) -> i128 {
+        if env.ledger().timestamp() / window > alloc.last_optimized / window {
+            0
+        } else {
+            spent
+        }
+    }
+
+    fn _roll_day(env: &Env, alloc: &mut BudgetAllocation) {
+        alloc.carried_over = Self::_carried_over(env, alloc);
+        alloc.spent_week = Self::_spent_in_window(env, alloc, alloc.spent_week, WEEK_SECS);
+        alloc.spent_month = Self::_spent_in_window(env, alloc, alloc.spent_month, MONTH_SECS);
+        alloc.spent_today = Self::_spent_today(env, alloc);
+    }
+
+    fn _rule_matches(
+        alloc: &BudgetAllocation,
+        metrics: &PerformanceMetrics,
+        rule: &OptimizationRule,
+    ) -> bool {
+        match rule.metric {
+            RuleMetric::Cpa => {
+                if alloc.target_cpa <= 0 || metrics.spend == 0 {
+                    return false;
+                }
+                let limit = alloc.target_cpa * rule.threshold_bps as i128;
+                // cpa * 10_000 compared with target * threshold, no conversions = infinite CPA
+                if metrics.conversions == 0 {
+                    return rule.above;
+                }
+                let cpa_scaled = metrics.spend * 10_000 / metrics.conversions as i128;
+                if rule.above {
+                    cpa_scaled > limit
+                } else {
+                    cpa_scaled < limit
+                }
+            }
+            RuleMetric::Ctr => {
+                if metrics.impressions == 0 {
+                    return false;
+                }
+                let ctr = metrics.clicks * 10_000 / metrics.impressions;
+                let limit = alloc.target_ctr as u64 * rule.threshold_bps as u64 / 10_000;
+                if rule.above {
+                    ctr > limit
+                } else {
+                    ctr < limit
+                }
+            }
+        }
+    }
+
+    fn _log_optimization(
+        env: &Env,
+        campaign_id: u64,
+        old_daily_budget: i128,
+        new_daily_budget: i128,
+        reason: String,
+    ) {
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OptLogCount(campaign_id))
+            .unwrap_or(0);
+
+        let log = OptimizationLog {
+            campaign_id,
+            old_daily_budget,
+            new_daily_budget,
+            reason,
+            optimized_at: env.ledger().timestamp(),
+        };
+
+        let _ttl_key = DataKey::OptLog(campaign_id, count);
+        env.storage().persistent().set(&_ttl_key, &log);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        let _ttl_key = DataKey::OptLogCount(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &(count + 1));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// True if spend moved from below `bps` of `budget` to at or above it
+    fn _crosses(before: i128, after: i128, budget: i128, bps: u32) -> bool {
+        let line = budget * bps as i128;
+        budget > 0 && before * 10_000 < line && after * 10_000 >= line
+    }
+
+    fn _save_portfolio(env: &Env, portfolio: &Portfolio) {
+        let _ttl_key = DataKey::Portfolio(portfolio.portfolio_id);
+        env.storage().persistent().set(&_ttl_key, portfolio);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _allowed_spend(env: &Env, alloc: &BudgetAllocation, hour: u32) -> i128 {
+        let released = match &alloc.pacing {
+            PacingMode::Accelerated => alloc.daily_budget,
+            PacingMode::Even => alloc.daily_budget * (hour as i128 + 1) / 24,
+            PacingMode::Dayparted(weights) => {
+                let total: u64 = weights.iter().map(|w| w as u64).sum();
+                let elapsed: u64 = weights
+                    .iter()
+                    .take(hour as usize + 1)
+                    .map(|w| w as u64)
+                    .sum();
+                alloc.daily_budget * elapsed as i128 / total as i128
+            }
+        };
+        let daily_left =
+            released + Self::_carried_over(env, alloc) - Self::_spent_today(env, alloc);
+        let mut left = daily_left.min(alloc.total_budget - alloc.spent_total);
+        if alloc.weekly_budget > 0 {
+            let spent = Self::_spent_in_window(env, alloc, alloc.spent_week, WEEK_SECS);
+            left = left.min(alloc.weekly_budget - spent);
+        }
+        if alloc.monthly_budget > 0 {
+            let spent = Self::_spent_in_window(env, alloc, alloc.spent_month, MONTH_SECS);
+            left = left.min(alloc.monthly_budget - spent);
+        }
+        left.max(0)
+    }
+
+    /// Once set, oracle checks go through the shared oracle registry
+    /// (domain `Budget`) instead of the single stored oracle address
+    pub fn set_oracle_registry(
+        env: Env,
+        admin: Address,
+        registry: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::OracleRegistry, &registry);
+        Ok(())
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::propose_admin(
+            &env,
+            &DataKey::Admin,
+            &DataKey::PendingAdmin,
+            current_admin,
+            new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
+
+    fn _is_oracle(env: &Env, oracle: &Address) -> bool {
+        if let Some(registry) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::OracleRegistry)
+        {
+            return pulsar_common_rbac::registry_allows(
+                env,
+                &registry,
+                oracle,
+                pulsar_common_rbac::OracleDomain::Budget,
+            );
+        }
+        let stored_oracle: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::OracleAddress)
+            .unwrap();
+        *oracle == stored_oracle
+    }
+
+    fn _require_oracle(env: &Env, oracle: &Address) -> Result<(), PulsarError> {
+        oracle.require_auth();
+        if !Self::_is_oracle(env, oracle) {
+            return Err(PulsarError::Unauthorized);
+        }
+        Ok(())
     }
 }
 
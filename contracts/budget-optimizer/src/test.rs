@@ -1,17 +1,42 @@
 #![cfg(test)]
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    Address, Env,
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    Address, Env, IntoVal, Symbol, Val, Vec,
 };
 
+mod registry_mock {
+    use pulsar_common_rbac::OracleDomain;
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockOracleRegistry;
+
+    #[contractimpl]
+    impl MockOracleRegistry {
+        pub fn allow(env: Env, oracle: Address, domain: OracleDomain) {
+            env.storage().persistent().set(&(oracle, domain), &true);
+        }
+
+        pub fn is_authorized(env: Env, oracle: Address, domain: OracleDomain) -> bool {
+            env.storage().persistent().has(&(oracle, domain))
+        }
+    }
+}
+
 fn setup(env: &Env) -> (BudgetOptimizerContractClient<'_>, Address) {
+    let (c, admin, _) = setup_with_oracle(env);
+    (c, admin)
+}
+
+fn setup_with_oracle(env: &Env) -> (BudgetOptimizerContractClient<'_>, Address, Address) {
     let admin = Address::generate(env);
     let oracle = Address::generate(env);
-    let id = env.register_contract(None, BudgetOptimizerContract);
+    let id = env.register(BudgetOptimizerContract, ());
     let c = BudgetOptimizerContractClient::new(env, &id);
     c.initialize(&admin, &oracle);
-    (c, admin)
+    (c, admin, oracle)
 }
 
 #[test]
@@ -22,23 +47,25 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
-    let id = env.register_contract(None, BudgetOptimizerContract);
+    let id = env.register(BudgetOptimizerContract, ());
     let c = BudgetOptimizerContractClient::new(&env, &id);
     let a = Address::generate(&env);
     let o = Address::generate(&env);
     c.initialize(&a, &o);
-    c.initialize(&a, &o);
+    assert_eq!(
+        c.try_initialize(&a, &o),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
 #[should_panic]
 fn test_initialize_non_admin_fails() {
     let env = Env::default();
-    let id = env.register_contract(None, BudgetOptimizerContract);
+    let id = env.register(BudgetOptimizerContract, ());
     let c = BudgetOptimizerContractClient::new(&env, &id);
     c.initialize(&Address::generate(&env), &Address::generate(&env));
 }
@@ -83,6 +110,39 @@ fn test_record_spend() {
     assert_eq!(alloc.spent_today, 5_000);
 }
 
+#[test]
+fn test_record_spend_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    allocate(&env, &c);
+    assert_eq!(
+        c.try_record_spend(&Address::generate(&env), &1u64, &5_000i128),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_record_spend_authorized_spender() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    allocate(&env, &c);
+    let orchestrator = Address::generate(&env);
+    c.set_authorized_spender(&admin, &orchestrator);
+    c.record_spend(&orchestrator, &1u64, &5_000i128);
+    assert_eq!(c.get_allocation(&1u64).unwrap().spent_total, 5_000);
+}
+
+#[test]
+fn test_record_spend_without_allocation_is_ignored() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    c.record_spend(&admin, &7u64, &5_000i128);
+    assert!(c.get_allocation(&7u64).is_none());
+}
+
 #[test]
 fn test_record_spend_resets_on_new_day() {
     let env = Env::default();
@@ -172,3 +232,530 @@ fn test_can_spend_nonexistent() {
     let (c, _) = setup(&env);
     assert!(!c.can_spend(&999u64, &100i128));
 }
+
+fn allocate(env: &Env, c: &BudgetOptimizerContractClient<'_>) -> Address {
+    let advertiser = Address::generate(env);
+    c.set_budget_allocation(
+        &advertiser,
+        &1u64,
+        &100_000i128,
+        &24_000i128,
+        &OptimizationMode::ManualCpc,
+        &500i128,
+        &100u32,
+    );
+    advertiser
+}
+
+#[test]
+fn test_even_pacing_spreads_daily_budget() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let advertiser = allocate(&env, &c);
+    assert_eq!(c.get_allowed_spend(&1u64, &0u32), 24_000);
+
+    c.set_pacing(&advertiser, &1u64, &PacingMode::Even);
+    assert_eq!(c.get_allowed_spend(&1u64, &0u32), 1_000);
+    assert!(!c.can_spend(&1u64, &1_001i128));
+
+    c.record_spend(&admin, &1u64, &1_000i128);
+    assert_eq!(c.get_allowed_spend(&1u64, &0u32), 0);
+    assert_eq!(c.get_allowed_spend(&1u64, &5u32), 5_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 2 * 3_600);
+    assert!(c.can_spend(&1u64, &2_000i128));
+    assert!(!c.can_spend(&1u64, &2_001i128));
+}
+
+#[test]
+fn test_dayparted_pacing() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let advertiser = allocate(&env, &c);
+    let mut weights = Vec::new(&env);
+    for hour in 0..24u32 {
+        weights.push_back(if (18..22).contains(&hour) { 5 } else { 0 });
+    }
+    c.set_pacing(&advertiser, &1u64, &PacingMode::Dayparted(weights));
+
+    assert_eq!(c.get_allowed_spend(&1u64, &17u32), 0);
+    assert_eq!(c.get_allowed_spend(&1u64, &18u32), 6_000);
+    assert_eq!(c.get_allowed_spend(&1u64, &23u32), 24_000);
+}
+
+#[test]
+fn test_dayparted_pacing_needs_24_weights() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let advertiser = allocate(&env, &c);
+    let result = c.try_set_pacing(
+        &advertiser,
+        &1u64,
+        &PacingMode::Dayparted(soroban_sdk::vec![&env, 1u32, 2u32]),
+    );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidInput)));
+}
+
+#[test]
+fn test_set_pacing_not_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    allocate(&env, &c);
+    assert_eq!(
+        c.try_set_pacing(&Address::generate(&env), &1u64, &PacingMode::Even),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_weekly_budget_caps_daily_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let advertiser = allocate(&env, &c);
+    c.set_period_budgets(
+        &advertiser,
+        &1u64,
+        &50_000i128,
+        &0i128,
+        &RolloverPolicy::Strict,
+    );
+
+    c.record_spend(&admin, &1u64, &24_000i128);
+    env.ledger().with_mut(|li| li.timestamp = 86_400);
+    c.record_spend(&admin, &1u64, &24_000i128);
+    env.ledger().with_mut(|li| li.timestamp = 2 * 86_400);
+    assert!(c.can_spend(&1u64, &2_000i128));
+    assert!(!c.can_spend(&1u64, &2_001i128));
+    assert_eq!(c.get_allocation(&1u64).unwrap().spent_week, 48_000);
+
+    // A new week restores the full daily allowance
+    env.ledger().with_mut(|li| li.timestamp = 604_800);
+    assert!(c.can_spend(&1u64, &24_000i128));
+    c.record_spend(&admin, &1u64, &1_000i128);
+    let alloc = c.get_allocation(&1u64).unwrap();
+    assert_eq!(alloc.spent_week, 1_000);
+    assert_eq!(alloc.spent_month, 49_000);
+}
+
+#[test]
+fn test_unspent_daily_budget_carries_over() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let advertiser = allocate(&env, &c);
+    c.set_period_budgets(
+        &advertiser,
+        &1u64,
+        &0i128,
+        &0i128,
+        &RolloverPolicy::CarryOver(5_000),
+    );
+
+    // 4_000 left over, under the 12_000 carry limit
+    c.record_spend(&admin, &1u64, &20_000i128);
+    env.ledger().with_mut(|li| li.timestamp = 86_400);
+    assert_eq!(c.get_allowed_spend(&1u64, &0u32), 28_000);
+    c.record_spend(&admin, &1u64, &28_000i128);
+    assert_eq!(c.get_allocation(&1u64).unwrap().carried_over, 4_000);
+
+    // Nothing left over on day one
+    env.ledger().with_mut(|li| li.timestamp = 2 * 86_400);
+    assert_eq!(c.get_allowed_spend(&1u64, &0u32), 24_000);
+
+    // An idle day carries the limit
+    env.ledger().with_mut(|li| li.timestamp = 4 * 86_400);
+    assert_eq!(c.get_allowed_spend(&1u64, &0u32), 36_000);
+}
+
+#[test]
+fn test_weekly_budget_above_monthly_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let advertiser = allocate(&env, &c);
+    let result = c.try_set_period_budgets(
+        &advertiser,
+        &1u64,
+        &60_000i128,
+        &50_000i128,
+        &RolloverPolicy::Strict,
+    );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidInput)));
+}
+
+#[test]
+fn test_auto_optimize_cuts_budget_on_high_cpa() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, oracle) = setup_with_oracle(&env);
+    allocate(&env, &c);
+
+    // CPA 700 against a 500 target
+    c.submit_performance(&oracle, &1u64, &10_000u64, &200u64, &10u64, &7_000i128);
+    assert_eq!(c.auto_optimize(&1u64), 21_600);
+    let alloc = c.get_allocation(&1u64).unwrap();
+    assert_eq!(alloc.daily_budget, 21_600);
+    assert_eq!(alloc.hourly_budget, 900);
+    assert!(c.get_performance(&1u64).unwrap().evaluated);
+}
+
+#[test]
+fn test_auto_optimize_leaves_budget_on_target() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, oracle) = setup_with_oracle(&env);
+    allocate(&env, &c);
+    c.submit_performance(&oracle, &1u64, &10_000u64, &200u64, &10u64, &5_500i128);
+    assert_eq!(c.auto_optimize(&1u64), 24_000);
+}
+
+#[test]
+fn test_auto_optimize_custom_rules() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, oracle) = setup_with_oracle(&env);
+    let advertiser = allocate(&env, &c);
+    c.set_optimization_rules(
+        &advertiser,
+        &1u64,
+        &soroban_sdk::vec![
+            &env,
+            OptimizationRule {
+                metric: RuleMetric::Ctr,
+                above: true,
+                threshold_bps: 15_000,
+                adjustment_bps: 2_500,
+            },
+        ],
+    );
+    // CTR 2% against a 1% target
+    c.submit_performance(&oracle, &1u64, &10_000u64, &200u64, &0u64, &1_000i128);
+    assert_eq!(c.auto_optimize(&1u64), 30_000);
+}
+
+#[test]
+fn test_auto_optimize_once_per_submission() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, oracle) = setup_with_oracle(&env);
+    allocate(&env, &c);
+    c.submit_performance(&oracle, &1u64, &10_000u64, &200u64, &10u64, &7_000i128);
+    c.auto_optimize(&1u64);
+    assert_eq!(
+        c.try_auto_optimize(&1u64),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_performance_window_rolls_over() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, oracle) = setup_with_oracle(&env);
+    allocate(&env, &c);
+    c.submit_performance(&oracle, &1u64, &100u64, &1u64, &1u64, &500i128);
+    c.submit_performance(&oracle, &1u64, &100u64, &1u64, &1u64, &500i128);
+    assert_eq!(c.get_performance(&1u64).unwrap().impressions, 200);
+
+    env.ledger().with_mut(|li| li.timestamp += 7 * 86_400);
+    c.submit_performance(&oracle, &1u64, &50u64, &1u64, &1u64, &500i128);
+    let metrics = c.get_performance(&1u64).unwrap();
+    assert_eq!(metrics.impressions, 50);
+    assert_eq!(metrics.window_start, 7 * 86_400);
+}
+
+#[test]
+fn test_submit_performance_not_oracle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    allocate(&env, &c);
+    assert_eq!(
+        c.try_submit_performance(&admin, &1u64, &100u64, &1u64, &1u64, &500i128),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_record_conversions_from_source() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, oracle) = setup_with_oracle(&env);
+    allocate(&env, &c);
+    let tracker = Address::generate(&env);
+    c.set_conversion_source(&admin, &tracker);
+
+    c.submit_performance(&oracle, &1u64, &1_000u64, &10u64, &0u64, &5_000i128);
+    c.record_conversions(&tracker, &1u64, &4u64);
+
+    let perf = c.get_performance(&1u64).unwrap();
+    assert_eq!(perf.conversions, 4);
+    assert_eq!(perf.spend, 5_000);
+    assert!(c.try_record_conversions(&oracle, &1u64, &1u64).is_err());
+}
+
+fn allocate_campaign(
+    c: &BudgetOptimizerContractClient<'_>,
+    advertiser: &Address,
+    campaign_id: u64,
+) {
+    c.set_budget_allocation(
+        advertiser,
+        &campaign_id,
+        &50_000i128,
+        &1_000i128,
+        &OptimizationMode::AutoCpm,
+        &500i128,
+        &100u32,
+    );
+}
+
+#[test]
+fn test_rebalance_portfolio() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, oracle) = setup_with_oracle(&env);
+    let advertiser = Address::generate(&env);
+    allocate_campaign(&c, &advertiser, 1);
+    allocate_campaign(&c, &advertiser, 2);
+    allocate_campaign(&c, &advertiser, 3);
+    let portfolio_id = c.create_portfolio(&advertiser, &150_000i128, &10_000i128);
+    c.add_to_portfolio(&advertiser, &portfolio_id, &1u64, &0i128, &10_000i128);
+    c.add_to_portfolio(&advertiser, &portfolio_id, &2u64, &0i128, &3_000i128);
+    c.add_to_portfolio(&advertiser, &portfolio_id, &3u64, &1_500i128, &10_000i128);
+
+    let budgets =
+        c.rebalance_portfolio(&oracle, &portfolio_id, &Vec::from_array(&env, [6u32, 4, 0]));
+    // member 2 capped at its ceiling, member 3 lifted to its floor
+    assert_eq!(budgets, Vec::from_array(&env, [6_000i128, 3_000, 1_500]));
+    assert_eq!(c.get_allocation(&1u64).unwrap().daily_budget, 6_000);
+    assert_eq!(c.get_allocation(&3u64).unwrap().hourly_budget, 62);
+
+    assert_eq!(c.get_rebalance_count(&portfolio_id), 1);
+    let record = c.get_rebalance(&portfolio_id, &0u32).unwrap();
+    assert_eq!(record.caller, oracle);
+    assert_eq!(record.daily_budgets, budgets);
+}
+
+#[test]
+fn test_portfolio_budget_exceeded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let advertiser = Address::generate(&env);
+    allocate_campaign(&c, &advertiser, 1);
+    allocate_campaign(&c, &advertiser, 2);
+    let portfolio_id = c.create_portfolio(&advertiser, &80_000i128, &10_000i128);
+    c.add_to_portfolio(&advertiser, &portfolio_id, &1u64, &0i128, &10_000i128);
+    assert_eq!(
+        c.try_add_to_portfolio(&advertiser, &portfolio_id, &2u64, &0i128, &10_000i128),
+        Err(Ok(PulsarError::LimitExceeded))
+    );
+}
+
+#[test]
+fn test_rebalance_portfolio_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let advertiser = Address::generate(&env);
+    allocate_campaign(&c, &advertiser, 1);
+    let portfolio_id = c.create_portfolio(&advertiser, &50_000i128, &10_000i128);
+    c.add_to_portfolio(&advertiser, &portfolio_id, &1u64, &0i128, &10_000i128);
+    let result = c.try_rebalance_portfolio(
+        &Address::generate(&env),
+        &portfolio_id,
+        &Vec::from_array(&env, [1u32]),
+    );
+    assert_eq!(result, Err(Ok(PulsarError::Unauthorized)));
+}
+
+#[test]
+fn test_add_foreign_campaign_to_portfolio() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let advertiser = Address::generate(&env);
+    allocate_campaign(&c, &Address::generate(&env), 1);
+    let portfolio_id = c.create_portfolio(&advertiser, &50_000i128, &10_000i128);
+    assert_eq!(
+        c.try_add_to_portfolio(&advertiser, &portfolio_id, &1u64, &0i128, &10_000i128),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_list_opt_logs_paginates() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, oracle) = setup_with_oracle(&env);
+    allocate(&env, &c);
+    for _ in 0..3 {
+        c.submit_performance(&oracle, &1u64, &10_000u64, &200u64, &10u64, &7_000i128);
+        c.auto_optimize(&1u64);
+    }
+    assert_eq!(c.get_opt_log_count(&1u64), 3);
+
+    let page = c.list_opt_logs(&1u64, &1u32, &10u32);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().old_daily_budget, 21_600);
+    assert_eq!(page.get(1).unwrap().new_daily_budget, 17_496);
+    assert_eq!(c.list_opt_logs(&1u64, &3u32, &10u32).len(), 0);
+}
+
+#[test]
+fn test_forecast_exhaustion() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    allocate(&env, &c);
+    assert!(c.forecast_exhaustion(&1u64).is_none());
+
+    c.record_spend(&admin, &1u64, &10_000i128);
+    env.ledger().with_mut(|li| li.timestamp = 2 * 86_400);
+    // 5_000 per day with 90_000 left
+    let forecast = c.forecast_exhaustion(&1u64).unwrap();
+    assert_eq!(forecast.daily_velocity, 5_000);
+    assert_eq!(forecast.days_remaining, 18);
+    assert_eq!(forecast.estimated_end_ts, 20 * 86_400);
+}
+
+fn budget_alerts(env: &Env) -> Vec<(u64, Symbol, u32)> {
+    let topics: Vec<Val> = (
+        symbol_short!("budget"),
+        symbol_short!("alert"),
+        pulsar_common_admin::EVENT_SCHEMA_VERSION,
+    )
+        .into_val(env);
+    let mut alerts = Vec::new(env);
+    for (_, event_topics, data) in env.events().all().iter() {
+        if event_topics == topics {
+            alerts.push_back(data.into_val(env));
+        }
+    }
+    alerts
+}
+
+#[test]
+fn test_record_spend_emits_threshold_alerts() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    allocate(&env, &c);
+
+    c.record_spend(&admin, &1u64, &12_000i128);
+    assert_eq!(
+        budget_alerts(&env),
+        soroban_sdk::vec![&env, (1u64, symbol_short!("daily"), 5_000u32)]
+    );
+
+    c.record_spend(&admin, &1u64, &12_000i128);
+    assert_eq!(
+        budget_alerts(&env),
+        soroban_sdk::vec![
+            &env,
+            (1u64, symbol_short!("daily"), 8_000u32),
+            (1u64, symbol_short!("daily"), 10_000u32),
+        ]
+    );
+}
+
+#[test]
+fn test_custom_alert_thresholds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let advertiser = allocate(&env, &c);
+    c.set_alert_thresholds(&advertiser, &1u64, &Vec::from_array(&env, [2_000u32]));
+
+    c.record_spend(&admin, &1u64, &20_000i128);
+    assert_eq!(
+        budget_alerts(&env),
+        soroban_sdk::vec![
+            &env,
+            (1u64, symbol_short!("daily"), 2_000u32),
+            (1u64, symbol_short!("total"), 2_000u32),
+        ]
+    );
+}
+
+#[test]
+fn test_experiment_splits_and_concludes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let advertiser = allocate(&env, &c);
+    c.create_experiment(
+        &advertiser,
+        &1u64,
+        &Vec::from_array(&env, [7_000u32, 3_000]),
+    );
+    assert_eq!(c.get_variant_allowance(&1u64, &0u32), 16_800);
+    assert_eq!(c.get_variant_allowance(&1u64, &1u32), 7_200);
+
+    c.record_variant_spend(&admin, &1u64, &1u32, &7_000i128);
+    assert_eq!(c.get_variant_allowance(&1u64, &1u32), 200);
+    assert_eq!(c.get_allocation(&1u64).unwrap().spent_today, 7_000);
+
+    c.conclude_experiment(&advertiser, &1u64, &1u32);
+    let experiment = c.get_experiment(&1u64).unwrap();
+    assert_eq!(experiment.winner, Some(1));
+    assert_eq!(experiment.variant_spent_total.get(1).unwrap(), 7_000);
+    assert_eq!(c.get_variant_allowance(&1u64, &0u32), 0);
+    assert_eq!(c.get_variant_allowance(&1u64, &1u32), 17_000);
+}
+
+#[test]
+fn test_variant_spend_over_share() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let advertiser = allocate(&env, &c);
+    c.create_experiment(
+        &advertiser,
+        &1u64,
+        &Vec::from_array(&env, [5_000u32, 5_000]),
+    );
+    assert_eq!(
+        c.try_record_variant_spend(&admin, &1u64, &0u32, &12_001i128),
+        Err(Ok(PulsarError::LimitExceeded))
+    );
+}
+
+#[test]
+fn test_experiment_variants_must_sum() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let advertiser = allocate(&env, &c);
+    let result = c.try_create_experiment(
+        &advertiser,
+        &1u64,
+        &Vec::from_array(&env, [5_000u32, 4_000]),
+    );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidInput)));
+}
+
+#[test]
+fn test_oracle_registry_replaces_stored_oracle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, oracle) = setup_with_oracle(&env);
+    allocate(&env, &c);
+    let registry_id = env.register(registry_mock::MockOracleRegistry, ());
+    let registry = registry_mock::MockOracleRegistryClient::new(&env, &registry_id);
+    let reporter = Address::generate(&env);
+    registry.allow(&reporter, &pulsar_common_rbac::OracleDomain::Budget);
+    c.set_oracle_registry(&admin, &registry_id);
+
+    c.submit_performance(&reporter, &1u64, &100u64, &1u64, &1u64, &500i128);
+    assert_eq!(c.get_performance(&1u64).unwrap().impressions, 100);
+    assert!(c
+        .try_submit_performance(&oracle, &1u64, &100u64, &1u64, &1u64, &500i128)
+        .is_err());
+}
@@ -4,6 +4,8 @@
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone)]
 pub struct CampaignSnapshot {
@@ -67,18 +69,19 @@ pub struct CampaignAnalyticsContract;
 
 #[contractimpl]
 impl CampaignAnalyticsContract {
-    pub fn initialize(env: Env, admin: Address, oracle: Address) {
+    pub fn initialize(env: Env, admin: Address, oracle: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage()
             .instance()
             .set(&DataKey::OracleAddress, &oracle);
+        Ok(())
     }
 
     pub fn record_snapshot(
@@ -90,7 +93,7 @@ impl CampaignAnalyticsContract {
         conversions: u64,
         spend: i128,
         reach: u64,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -101,7 +104,7 @@ impl CampaignAnalyticsContract {
             .get(&DataKey::OracleAddress)
             .unwrap();
         if oracle != stored_oracle {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let snapshot = CampaignSnapshot {
@@ -138,6 +141,7 @@ impl CampaignAnalyticsContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     pub fn update_funnel(
@@ -150,7 +154,7 @@ impl CampaignAnalyticsContract {
         sign_ups: u64,
         conversions: u64,
         conversion_value: i128,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -161,7 +165,7 @@ impl CampaignAnalyticsContract {
             .get(&DataKey::OracleAddress)
             .unwrap();
         if oracle != stored_oracle {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let funnel = ConversionFunnel {
@@ -181,6 +185,7 @@ impl CampaignAnalyticsContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     pub fn update_retention(
@@ -192,7 +197,7 @@ impl CampaignAnalyticsContract {
         day30: u32,
         avg_session: u64,
         bounce_rate: u32,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -203,7 +208,7 @@ impl CampaignAnalyticsContract {
             .get(&DataKey::OracleAddress)
             .unwrap();
         if oracle != stored_oracle {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let metrics = RetentionMetrics {
@@ -222,6 +227,7 @@ impl CampaignAnalyticsContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     pub fn get_snapshot(env: Env, campaign_id: u64, index: u32) -> Option<CampaignSnapshot> {
@@ -284,18 +290,30 @@ impl CampaignAnalyticsContract {
             .get(&DataKey::RetentionMetrics(campaign_id))
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
@@ -19,7 +19,6 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
@@ -28,7 +27,10 @@ fn test_initialize_twice() {
     let a = Address::generate(&env);
     let o = Address::generate(&env);
     c.initialize(&a, &o);
-    c.initialize(&a, &o);
+    assert_eq!(
+        c.try_initialize(&a, &o),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -1,14 +1,17 @@
 //! PulsarTrack - Campaign Lifecycle (Soroban)
 //! Manages ad campaign state transitions and lifecycle events on Stellar.
 //!
-//! Events:
+//! Events (third topic is `pulsar_common_admin::EVENT_SCHEMA_VERSION`):
 //! - ("lifecycle", "transit"): [campaign_id: u64]
 //! - ("campaign", "pause"): [campaign_id: u64, actor: Address]
 //! - ("campaign", "resume"): [campaign_id: u64, actor: Address]
 
 #![no_std]
+use pulsar_common_rbac::Role;
 use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone, PartialEq)]
 pub enum LifecycleState {
@@ -77,35 +80,41 @@ pub struct CampaignLifecycleContract;
 
 #[contractimpl]
 impl CampaignLifecycleContract {
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage()
             .instance()
             .set(&DataKey::LifecycleCounter, &0u64);
+        Ok(())
     }
 
-    pub fn set_fraud_contract(env: Env, admin: Address, fraud_contract: Address) {
+    pub fn set_fraud_contract(
+        env: Env,
+        admin: Address,
+        fraud_contract: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if admin != stored_admin {
-            panic!("unauthorized");
-        }
+        pulsar_common_rbac::require_role(&env, &DataKey::Admin, Role::Admin, &admin)?;
         env.storage()
             .instance()
             .set(&DataKey::FraudContract, &fraud_contract);
+        Ok(())
     }
 
-    pub fn pause_for_fraud(env: Env, fraud_contract: Address, campaign_id: u64) {
+    pub fn pause_for_fraud(
+        env: Env,
+        fraud_contract: Address,
+        campaign_id: u64,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -115,9 +124,9 @@ impl CampaignLifecycleContract {
             .storage()
             .instance()
             .get(&DataKey::FraudContract)
-            .expect("fraud contract not set");
+            .ok_or(PulsarError::NotInitialized)?;
         if fraud_contract != stored_fraud_contract {
-            panic!("unauthorized fraud contract");
+            return Err(PulsarError::Unauthorized);
         }
 
         Self::transition(
@@ -126,7 +135,7 @@ impl CampaignLifecycleContract {
             campaign_id,
             LifecycleState::Paused,
             String::from_str(&env, "paused for fraud detection"),
-        );
+        )
     }
 
     pub fn register_campaign(env: Env, advertiser: Address, campaign_id: u64, end_ledger: u32) {
@@ -174,7 +183,7 @@ impl CampaignLifecycleContract {
         campaign_id: u64,
         new_state: LifecycleState,
         reason: String,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -184,25 +193,26 @@ impl CampaignLifecycleContract {
             .storage()
             .persistent()
             .get(&DataKey::Lifecycle(campaign_id))
-            .expect("lifecycle not found");
+            .ok_or(PulsarError::NotFound)?;
 
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         let fraud_contract: Option<Address> = env.storage().instance().get(&DataKey::FraudContract);
 
-        // Only advertiser, admin or authorized fraud contract can transition
-        if actor != lifecycle.advertiser && actor != admin {
+        // Only advertiser, an operator (or admin) or authorized fraud contract can transition
+        if actor != lifecycle.advertiser
+            && !pulsar_common_rbac::has_role(&env, &DataKey::Admin, Role::Operator, &actor)
+        {
             if let Some(fraud_addr) = fraud_contract {
                 if actor != fraud_addr {
-                    panic!("unauthorized");
+                    return Err(PulsarError::Unauthorized);
                 }
             } else {
-                panic!("unauthorized");
+                return Err(PulsarError::Unauthorized);
             }
         }
 
         // Validate state transition
         let old_state = lifecycle.state.clone();
-        Self::_validate_transition(&old_state, &new_state);
+        Self::_validate_transition(&old_state, &new_state)?;
 
         // Apply state
         let now = env.ledger().timestamp();
@@ -210,8 +220,10 @@ impl CampaignLifecycleContract {
             LifecycleState::Active => {
                 lifecycle.activated_at = Some(now);
                 if old_state == LifecycleState::Paused {
-                    env.events().publish(
-                        (symbol_short!("campaign"), symbol_short!("resume")),
+                    pulsar_common_admin::publish_event(
+                        &env,
+                        symbol_short!("campaign"),
+                        symbol_short!("resume"),
                         (campaign_id, actor.clone()),
                     );
                 }
@@ -219,8 +231,10 @@ impl CampaignLifecycleContract {
             LifecycleState::Paused => {
                 lifecycle.paused_at = Some(now);
                 lifecycle.pause_count += 1;
-                env.events().publish(
-                    (symbol_short!("campaign"), symbol_short!("pause")),
+                pulsar_common_admin::publish_event(
+                    &env,
+                    symbol_short!("campaign"),
+                    symbol_short!("pause"),
                     (campaign_id, actor.clone()),
                 );
             }
@@ -270,13 +284,21 @@ impl CampaignLifecycleContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("lifecycle"), symbol_short!("transit")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("lifecycle"),
+            symbol_short!("transit"),
             campaign_id,
         );
+        Ok(())
     }
 
-    pub fn extend_campaign(env: Env, advertiser: Address, campaign_id: u64, extra_ledgers: u32) {
+    pub fn extend_campaign(
+        env: Env,
+        advertiser: Address,
+        campaign_id: u64,
+        extra_ledgers: u32,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -286,25 +308,25 @@ impl CampaignLifecycleContract {
             .storage()
             .persistent()
             .get(&DataKey::Lifecycle(campaign_id))
-            .expect("lifecycle not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if lifecycle.advertiser != advertiser {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         // Only active campaigns can be extended
         if lifecycle.state != LifecycleState::Active {
-            panic!("campaign not active");
+            return Err(PulsarError::InvalidState);
         }
 
         // Reject zero-ledger extensions
         if extra_ledgers == 0 {
-            panic!("extra_ledgers must be greater than zero");
+            return Err(PulsarError::InvalidInput);
         }
 
         // Enforce maximum extension count
         if lifecycle.extension_count >= MAX_EXTENSIONS {
-            panic!("max extensions reached");
+            return Err(PulsarError::LimitExceeded);
         }
 
         // Enforce maximum total duration (original_end_ledger * MAX_DURATION_MULTIPLIER)
@@ -313,7 +335,7 @@ impl CampaignLifecycleContract {
             .saturating_mul(MAX_DURATION_MULTIPLIER);
         let new_end = lifecycle.current_end_ledger.saturating_add(extra_ledgers);
         if new_end > max_end {
-            panic!("extension exceeds max campaign duration");
+            return Err(PulsarError::LimitExceeded);
         }
 
         lifecycle.current_end_ledger = new_end;
@@ -326,6 +348,7 @@ impl CampaignLifecycleContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     pub fn get_lifecycle(env: Env, campaign_id: u64) -> Option<CampaignLifecycle> {
@@ -356,7 +379,7 @@ impl CampaignLifecycleContract {
             .unwrap_or(0)
     }
 
-    fn _validate_transition(from: &LifecycleState, to: &LifecycleState) {
+    fn _validate_transition(from: &LifecycleState, to: &LifecycleState) -> Result<(), PulsarError> {
         let valid = match from {
             LifecycleState::Draft => matches!(
                 to,
@@ -379,22 +402,66 @@ impl CampaignLifecycleContract {
             _ => false,
         };
         if !valid {
-            panic!("invalid state transition");
+            return Err(PulsarError::InvalidState);
         }
+        Ok(())
+    }
+
+    pub fn grant_role(
+        env: Env,
+        admin: Address,
+        role: Role,
+        account: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::grant_role(&env, &DataKey::Admin, admin, role, account)
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn revoke_role(
+        env: Env,
+        admin: Address,
+        role: Role,
+        account: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::revoke_role(&env, &DataKey::Admin, admin, role, account)
+    }
+
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::has_role(&env, &DataKey::Admin, role, &account)
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
@@ -7,7 +7,7 @@ use soroban_sdk::{testutils::Address as _, Address, Env, String};
 fn setup(env: &Env) -> (CampaignLifecycleContractClient<'_>, Address) {
     let admin = Address::generate(env);
 
-    let contract_id = env.register_contract(None, CampaignLifecycleContract);
+    let contract_id = env.register(CampaignLifecycleContract, ());
     let client = CampaignLifecycleContractClient::new(env, &contract_id);
     client.initialize(&admin);
 
@@ -25,23 +25,25 @@ fn test_initialize() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let contract_id = env.register_contract(None, CampaignLifecycleContract);
+    let contract_id = env.register(CampaignLifecycleContract, ());
     let client = CampaignLifecycleContractClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
     client.initialize(&admin);
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let contract_id = env.register_contract(None, CampaignLifecycleContract);
+    let contract_id = env.register(CampaignLifecycleContract, ());
     let client = CampaignLifecycleContractClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
     client.initialize(&admin);
-    client.initialize(&admin);
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -49,7 +51,7 @@ fn test_initialize_twice() {
 fn test_initialize_non_admin_fails() {
     let env = Env::default();
 
-    let contract_id = env.register_contract(None, CampaignLifecycleContract);
+    let contract_id = env.register(CampaignLifecycleContract, ());
     let client = CampaignLifecycleContractClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
     client.initialize(&admin);
@@ -229,7 +231,6 @@ fn test_transition_draft_to_cancelled() {
 // ─── transition (invalid paths) ──────────────────────────────────────────────
 
 #[test]
-#[should_panic(expected = "invalid state transition")]
 fn test_invalid_transition_draft_to_active() {
     let env = Env::default();
     env.mock_all_auths();
@@ -238,16 +239,16 @@ fn test_invalid_transition_draft_to_active() {
 
     client.register_campaign(&advertiser, &1u64, &10_000u32);
     // Draft → Active is invalid; must go through PendingReview first
-    client.transition(
+    let result = client.try_transition(
         &advertiser,
         &1u64,
         &LifecycleState::Active,
         &make_reason(&env),
     );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidState)));
 }
 
 #[test]
-#[should_panic(expected = "invalid state transition")]
 fn test_invalid_transition_completed_to_active() {
     let env = Env::default();
     env.mock_all_auths();
@@ -269,18 +270,18 @@ fn test_invalid_transition_completed_to_active() {
         &make_reason(&env),
     );
     // Completed → Active is invalid
-    client.transition(
+    let result = client.try_transition(
         &advertiser,
         &1u64,
         &LifecycleState::Active,
         &make_reason(&env),
     );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidState)));
 }
 
 // ─── transition (access control) ─────────────────────────────────────────────
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_transition_by_stranger() {
     let env = Env::default();
     env.mock_all_auths();
@@ -289,12 +290,34 @@ fn test_transition_by_stranger() {
     let stranger = Address::generate(&env);
 
     client.register_campaign(&advertiser, &1u64, &10_000u32);
-    client.transition(
+    let result = client.try_transition(
         &stranger,
         &1u64,
         &LifecycleState::PendingReview,
         &make_reason(&env),
     );
+    assert_eq!(result, Err(Ok(PulsarError::Unauthorized)));
+}
+
+#[test]
+fn test_transition_by_operator() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+    let operator = Address::generate(&env);
+
+    client.register_campaign(&advertiser, &1u64, &10_000u32);
+    client.grant_role(&admin, &Role::Operator, &operator);
+    client.transition(
+        &operator,
+        &1u64,
+        &LifecycleState::PendingReview,
+        &make_reason(&env),
+    );
+
+    let lc = client.get_lifecycle(&1u64).unwrap();
+    assert!(matches!(lc.state, LifecycleState::PendingReview));
 }
 
 // ─── pause_for_fraud ─────────────────────────────────────────────────────────
@@ -308,7 +331,7 @@ fn test_pause_for_fraud() {
     env.mock_all_auths();
     let admin = Address::generate(&env);
 
-    let contract_id = env.register_contract(None, CampaignLifecycleContract);
+    let contract_id = env.register(CampaignLifecycleContract, ());
     let client = CampaignLifecycleContractClient::new(&env, &contract_id);
     client.initialize(&admin);
 
@@ -339,7 +362,6 @@ fn test_pause_for_fraud() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized fraud contract")]
 fn test_pause_for_fraud_wrong_contract() {
     let env = Env::default();
     env.mock_all_auths();
@@ -358,7 +380,10 @@ fn test_pause_for_fraud_wrong_contract() {
     );
     client.transition(&admin, &1u64, &LifecycleState::Active, &make_reason(&env));
 
-    client.pause_for_fraud(&wrong_contract, &1u64);
+    assert_eq!(
+        client.try_pause_for_fraud(&wrong_contract, &1u64),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 // ─── extend_campaign ─────────────────────────────────────────────────────────
@@ -420,7 +445,6 @@ fn test_extend_campaign_multiple_times() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_extend_campaign_by_stranger() {
     let env = Env::default();
     env.mock_all_auths();
@@ -429,11 +453,13 @@ fn test_extend_campaign_by_stranger() {
     let stranger = Address::generate(&env);
 
     activate_campaign(&env, &client, &admin, &advertiser, 1, 10_000);
-    client.extend_campaign(&stranger, &1u64, &5_000u32);
+    assert_eq!(
+        client.try_extend_campaign(&stranger, &1u64, &5_000u32),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
-#[should_panic(expected = "campaign not active")]
 fn test_extend_campaign_draft_rejected() {
     let env = Env::default();
     env.mock_all_auths();
@@ -442,11 +468,13 @@ fn test_extend_campaign_draft_rejected() {
 
     // Campaign is in Draft state — not Active
     client.register_campaign(&advertiser, &1u64, &10_000u32);
-    client.extend_campaign(&advertiser, &1u64, &5_000u32);
+    assert_eq!(
+        client.try_extend_campaign(&advertiser, &1u64, &5_000u32),
+        Err(Ok(PulsarError::InvalidState))
+    );
 }
 
 #[test]
-#[should_panic(expected = "campaign not active")]
 fn test_extend_campaign_paused_rejected() {
     let env = Env::default();
     env.mock_all_auths();
@@ -460,11 +488,13 @@ fn test_extend_campaign_paused_rejected() {
         &LifecycleState::Paused,
         &String::from_str(&env, "budget review"),
     );
-    client.extend_campaign(&advertiser, &1u64, &5_000u32);
+    assert_eq!(
+        client.try_extend_campaign(&advertiser, &1u64, &5_000u32),
+        Err(Ok(PulsarError::InvalidState))
+    );
 }
 
 #[test]
-#[should_panic(expected = "extra_ledgers must be greater than zero")]
 fn test_extend_campaign_zero_ledgers_rejected() {
     let env = Env::default();
     env.mock_all_auths();
@@ -472,11 +502,14 @@ fn test_extend_campaign_zero_ledgers_rejected() {
     let advertiser = Address::generate(&env);
 
     activate_campaign(&env, &client, &admin, &advertiser, 1, 10_000);
-    client.extend_campaign(&advertiser, &1u64, &0u32);
+    assert_eq!(
+        client.try_extend_campaign(&advertiser, &1u64, &0u32),
+        Err(Ok(PulsarError::InvalidInput))
+    );
 }
 
 #[test]
-#[should_panic(expected = "max extensions reached")]
+#[should_panic(expected = "Error(Contract, #8)")]
 fn test_extend_campaign_max_extensions_exceeded() {
     let env = Env::default();
     env.mock_all_auths();
@@ -494,7 +527,6 @@ fn test_extend_campaign_max_extensions_exceeded() {
 }
 
 #[test]
-#[should_panic(expected = "extension exceeds max campaign duration")]
 fn test_extend_campaign_exceeds_max_duration() {
     let env = Env::default();
     env.mock_all_auths();
@@ -504,7 +536,10 @@ fn test_extend_campaign_exceeds_max_duration() {
     // original_end_ledger = 10_000, max_end = 30_000
     // Try to extend by 25_000 → 10_000 + 25_000 = 35_000 > 30_000
     activate_campaign(&env, &client, &admin, &advertiser, 1, 10_000);
-    client.extend_campaign(&advertiser, &1u64, &25_000u32);
+    assert_eq!(
+        client.try_extend_campaign(&advertiser, &1u64, &25_000u32),
+        Err(Ok(PulsarError::LimitExceeded))
+    );
 }
 
 #[test]
@@ -526,7 +561,6 @@ fn test_extend_campaign_up_to_max_duration() {
 // ─── set_fraud_contract ──────────────────────────────────────────────────────
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_set_fraud_contract_by_stranger() {
     let env = Env::default();
     env.mock_all_auths();
@@ -534,7 +568,10 @@ fn test_set_fraud_contract_by_stranger() {
     let stranger = Address::generate(&env);
     let fraud = Address::generate(&env);
 
-    client.set_fraud_contract(&stranger, &fraud);
+    assert_eq!(
+        client.try_set_fraud_contract(&stranger, &fraud),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 // ─── read-only ───────────────────────────────────────────────────────────────
@@ -0,0 +1,104 @@
+//! Per-jurisdiction campaign rules. The advertiser's jurisdiction and KYC
+//! level come from kyc-registry; the admin keeps one `ComplianceRule` per
+//! jurisdiction code. Checked by `create_campaign` before any funds move.
+
+use crate::DataKey;
+use crate::PulsarError;
+use soroban_sdk::{contracttype, Address, Env, IntoVal, String, Symbol, Vec};
+
+/// Mirror of kyc-registry's `KycLevel` for cross-contract calls
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub enum KycLevel {
+    None,
+    Basic,
+    Standard,
+    Enhanced,
+}
+
+/// Mirror of kyc-registry's `KycRecord` for cross-contract calls
+#[contracttype]
+#[derive(Clone)]
+pub struct KycRecord {
+    pub account: Address,
+    pub level: KycLevel,
+    pub provider: String,
+    pub document_hash: String,
+    pub jurisdiction: String,
+    pub verified: bool,
+    pub submitted_at: u64,
+    pub verified_at: Option<u64>,
+    pub expires_at: Option<u64>,
+    pub verifier: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ComplianceRule {
+    pub banned_campaign_types: Vec<u32>,
+    pub required_kyc_level: KycLevel,
+    pub max_budget: i128, // 0 = no cap
+}
+
+fn _rank(level: &KycLevel) -> u32 {
+    match level {
+        KycLevel::None => 0,
+        KycLevel::Basic => 1,
+        KycLevel::Standard => 2,
+        KycLevel::Enhanced => 3,
+    }
+}
+
+/// Errors unless `advertiser` may run a campaign of `campaign_type` with
+/// `budget` under their jurisdiction's rule. No-op until a KYC registry is set;
+/// jurisdictions without a rule only need valid KYC.
+pub fn enforce(
+    env: &Env,
+    advertiser: &Address,
+    campaign_type: u32,
+    budget: i128,
+) -> Result<(), PulsarError> {
+    let registry = match env
+        .storage()
+        .instance()
+        .get::<DataKey, Address>(&DataKey::KycRegistry)
+    {
+        Some(registry) => registry,
+        None => return Ok(()),
+    };
+
+    let record: Option<KycRecord> = env.invoke_contract(
+        &registry,
+        &Symbol::new(env, "get_kyc_record"),
+        Vec::from_array(env, [advertiser.clone().into_val(env)]),
+    );
+    let record = match record {
+        Some(record) if record.verified => record,
+        _ => return Err(PulsarError::NotVerified),
+    };
+    if record
+        .expires_at
+        .is_some_and(|expires| expires <= env.ledger().timestamp())
+    {
+        return Err(PulsarError::NotVerified);
+    }
+
+    let rule: ComplianceRule = match env
+        .storage()
+        .persistent()
+        .get(&DataKey::ComplianceRule(record.jurisdiction))
+    {
+        Some(rule) => rule,
+        None => return Ok(()),
+    };
+    if _rank(&record.level) < _rank(&rule.required_kyc_level) {
+        return Err(PulsarError::NotVerified);
+    }
+    if rule.banned_campaign_types.contains(campaign_type) {
+        return Err(PulsarError::InvalidState);
+    }
+    if rule.max_budget > 0 && budget > rule.max_budget {
+        return Err(PulsarError::LimitExceeded);
+    }
+    Ok(())
+}
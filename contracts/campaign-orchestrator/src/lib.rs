@@ -7,6 +7,11 @@ use soroban_sdk::{
     IntoVal, Symbol, Val, Vec as SdkVec,
 };
 
+pub use pulsar_common_errors::PulsarError;
+
+mod compliance;
+pub use compliance::{ComplianceRule, KycLevel, KycRecord};
+
 // Define external contract interfaces for cross-contract calls
 // These match the actual contract implementations
 
@@ -44,6 +49,16 @@ pub struct Campaign {
     pub last_updated: u64,
 }
 
+/// Mirror of identity-registry's `IdentityType` for cross-contract calls
+#[contracttype]
+#[derive(Clone)]
+pub enum IdentityType {
+    Advertiser,
+    Publisher,
+    DataProvider,
+    Operator,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct CampaignType {
@@ -53,6 +68,8 @@ pub struct CampaignType {
     pub min_budget: i128,
 }
 
+/// Legacy per-orchestrator publisher record. Superseded by the
+/// publisher-verification contract once one is configured.
 #[contracttype]
 #[derive(Clone)]
 pub struct VerifiedPublisher {
@@ -84,6 +101,112 @@ pub struct CampaignMetrics {
     pub completion_rate: u32,
 }
 
+/// Mirror of targeting-engine's `TargetingParams` for cross-contract calls
+#[contracttype]
+#[derive(Clone)]
+pub struct TargetingParams {
+    pub geographic_targets: String,
+    pub interest_segments: String,
+    pub excluded_segments: String,
+    pub min_age: u32,
+    pub max_age: u32,
+    pub device_types: String,
+    pub languages: String,
+    pub min_reputation: u32,
+    pub exclude_fraud: bool,
+    pub require_kyc: bool,
+    pub max_cpm: i128,
+}
+
+/// Mirror of targeting-engine's `TargetingConfig` for cross-contract calls
+#[contracttype]
+#[derive(Clone)]
+pub struct TargetingConfig {
+    pub campaign_id: u64,
+    pub advertiser: Address,
+    pub geographic_targets: String,
+    pub interest_segments: String,
+    pub excluded_segments: String,
+    pub min_age: u32,
+    pub max_age: u32,
+    pub device_types: String,
+    pub operating_systems: String,
+    pub languages: String,
+    pub min_reputation_score: u32,
+    pub exclude_fraud: bool,
+    pub require_kyc: bool,
+    pub max_cpm: i128,
+    pub created_at: u64,
+    pub last_updated: u64,
+}
+
+/// Settings to change when cloning a campaign; `None` keeps the source's value
+#[contracttype]
+#[derive(Clone)]
+pub struct CampaignOverrides {
+    pub cost_per_view: Option<i128>,
+    pub duration: Option<u32>,
+    pub target_views: Option<u64>,
+    pub daily_view_limit: Option<u64>,
+    pub refundable: Option<bool>,
+}
+
+/// Reusable campaign settings saved by an advertiser under a name
+#[contracttype]
+#[derive(Clone)]
+pub struct CampaignTemplate {
+    pub campaign_type: u32,
+    pub cost_per_view: i128,
+    pub duration: u32,
+    pub target_views: u64,
+    pub daily_view_limit: u64,
+    pub refundable: bool,
+}
+
+/// Pauses a campaign whose views in the current hour exceed `multiplier`
+/// times its average hourly rate, or `min_hourly_views` if that is higher
+#[contracttype]
+#[derive(Clone)]
+pub struct VelocityGuard {
+    pub multiplier: u32, // 0 = disabled
+    pub min_hourly_views: u64,
+}
+
+/// Which publishers may serve a campaign. `Allow` admits only the listed
+/// publishers (private deals), `Deny` admits everyone but them.
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub enum PublisherListMode {
+    Open,
+    Allow,
+    Deny,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct PublisherList {
+    pub mode: PublisherListMode,
+    pub publishers: soroban_sdk::Vec<Address>,
+}
+
+/// Mirror of publisher-verification's `TierParams` for cross-contract calls
+#[contracttype]
+#[derive(Clone)]
+pub struct TierParams {
+    pub revenue_share_bonus_bps: u32, // paid on top of cost_per_view, from the budget
+    pub daily_cap_multiplier: u32,    // hundredths of daily_view_limit; 100 = 1x
+}
+
+/// Extra funds set aside at creation, split among the campaign's top
+/// publishers by fraud-verified views once it completes
+#[contracttype]
+#[derive(Clone)]
+pub struct BonusPool {
+    pub amount: i128,
+    pub top_n: u32,
+    pub distributed: bool,
+}
+
 // ============================================================
 // Storage Keys
 // ============================================================
@@ -93,6 +216,7 @@ pub struct CampaignMetrics {
 pub enum DataKey {
     Admin,
     PendingAdmin,
+    Paused,
     TokenAddress,
     MinCampaignAmount,
     PlatformFeePct,
@@ -100,14 +224,34 @@ pub enum DataKey {
     TotalPlatformFees,
     Campaign(u64),
     CampaignType(u32),
-    Publisher(Address),
+    Publisher(Address), // deprecated: legacy registry, unused once PublisherVerification is set
     AdvertiserStats(Address),
     DailyViews(u64, u64),
+    HourlyViews(u64, u64), // campaign_id, hour
+    VelocityGuard,
     // Contract addresses for cross-contract validation
     LifecycleContract,
     EscrowContract,
     TargetingContract,
     AuctionContract,
+    MarketplaceContract,
+    IdentityRegistry,
+    NetworkContract,
+    BudgetOptimizer,
+    FeeRouter,
+    PublisherVerification,
+    KycRegistry,
+    ComplianceRule(String), // jurisdiction -> rule
+    Template(Address, String), // advertiser, name
+    TemplateTargeting(Address, String),
+    FraudContract,
+    PublisherBalance(Address), // earnings accrued by batched views, awaiting claim
+    CampaignCreatives(u64), // campaign_id -> Vec<listing_id>
+    PublisherList(u64),     // campaign_id -> PublisherList, absent = Open
+    BonusPool(u64),
+    BonusPublishers(u64),      // campaign_id -> Vec<Address> that served views
+    BonusClaim(u64, Address),  // campaign_id, publisher -> unclaimed bonus
+    BonusPaidViews(u64, Address), // campaign_id, publisher -> views paid while the pool was open
 }
 
 // ============================================================
@@ -119,18 +263,21 @@ const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
 const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
 
+const MAX_PUBLISHER_LIST: u32 = 50;
+const MAX_BONUS_PUBLISHERS: u32 = 50;
+
 #[contract]
 pub struct CampaignOrchestratorContract;
 
 #[contractimpl]
 impl CampaignOrchestratorContract {
     /// Initialize the contract
-    pub fn initialize(env: Env, admin: Address, token_address: Address) {
+    pub fn initialize(env: Env, admin: Address, token_address: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -160,257 +307,1205 @@ impl CampaignOrchestratorContract {
         env.storage()
             .instance()
             .set(&DataKey::CampaignType(1), &default_type);
+        Ok(())
     }
 
     /// Set contract addresses for cross-contract validation (admin only)
-    pub fn set_lifecycle_contract(env: Env, admin: Address, contract_address: Address) {
+    pub fn set_lifecycle_contract(
+        env: Env,
+        admin: Address,
+        contract_address: Address,
+    ) -> Result<(), PulsarError> {
         env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         env.storage().instance().set(&DataKey::LifecycleContract, &contract_address);
+        Ok(())
     }
 
-    pub fn set_escrow_contract(env: Env, admin: Address, contract_address: Address) {
+    pub fn set_escrow_contract(
+        env: Env,
+        admin: Address,
+        contract_address: Address,
+    ) -> Result<(), PulsarError> {
         env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         env.storage().instance().set(&DataKey::EscrowContract, &contract_address);
+        Ok(())
     }
 
-    pub fn set_targeting_contract(env: Env, admin: Address, contract_address: Address) {
+    pub fn set_targeting_contract(
+        env: Env,
+        admin: Address,
+        contract_address: Address,
+    ) -> Result<(), PulsarError> {
         env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         env.storage().instance().set(&DataKey::TargetingContract, &contract_address);
+        Ok(())
     }
 
-    pub fn set_auction_contract(env: Env, admin: Address, contract_address: Address) {
+    pub fn set_auction_contract(
+        env: Env,
+        admin: Address,
+        contract_address: Address,
+    ) -> Result<(), PulsarError> {
         env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         env.storage().instance().set(&DataKey::AuctionContract, &contract_address);
+        Ok(())
     }
 
-    /// Create a new ad campaign
-    pub fn create_campaign(
+    pub fn set_network_contract(
         env: Env,
-        advertiser: Address,
-        campaign_type: u32,
-        budget: i128,
-        cost_per_view: i128,
-        duration: u32,
-        target_views: u64,
-        daily_view_limit: u64,
-        refundable: bool,
-    ) -> u64 {
-        env.storage()
+        admin: Address,
+        contract_address: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
             .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        advertiser.require_auth();
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage().instance().set(&DataKey::NetworkContract, &contract_address);
+        Ok(())
+    }
 
-        let campaign_type_data: CampaignType = env
+    /// Once set, every paid view is reported to the optimizer's `record_spend`;
+    /// campaigns without a budget allocation there are simply not paced.
+    pub fn set_budget_optimizer(
+        env: Env,
+        admin: Address,
+        contract_address: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey::CampaignType(campaign_type))
-            .expect("campaign type not found");
-
-        if budget < campaign_type_data.min_budget {
-            panic!("budget too low");
-        }
-        if duration < campaign_type_data.min_duration || duration > campaign_type_data.max_duration
-        {
-            panic!("invalid duration");
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
         }
+        env.storage().instance().set(&DataKey::BudgetOptimizer, &contract_address);
+        Ok(())
+    }
 
-        let counter: u64 = env
+    /// Forward platform fees to the fee router instead of holding them here
+    pub fn set_fee_router(env: Env, admin: Address, router: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey::CampaignCounter)
-            .unwrap_or(0);
-        let campaign_id = counter + 1;
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage().instance().set(&DataKey::FeeRouter, &router);
+        Ok(())
+    }
 
-        let platform_fee_pct: u32 = env
+    pub fn set_marketplace_contract(
+        env: Env,
+        admin: Address,
+        contract_address: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey::PlatformFeePct)
-            .unwrap_or(2);
-        let platform_fee = (budget * platform_fee_pct as i128) / 100;
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::MarketplaceContract, &contract_address);
+        Ok(())
+    }
 
-        // Transfer budget + fee from advertiser to this contract
-        let token_addr: Address = env
+    /// Configure the view velocity guard; a multiplier of 0 disables it
+    pub fn set_velocity_guard(
+        env: Env,
+        admin: Address,
+        guard: VelocityGuard,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey::TokenAddress)
-            .unwrap();
-        let token_client = token::Client::new(&env, &token_addr);
-        token_client.transfer(
-            &advertiser,
-            &env.current_contract_address(),
-            &(budget + platform_fee),
-        );
-
-        let start_ledger = env.ledger().sequence();
-        let end_ledger = start_ledger + duration;
-
-        let campaign = Campaign {
-            advertiser: advertiser.clone(),
-            campaign_type,
-            budget,
-            remaining_budget: budget,
-            cost_per_view,
-            start_ledger,
-            end_ledger,
-            status: CampaignStatus::Active,
-            target_views,
-            current_views: 0,
-            daily_view_limit,
-            refundable,
-            platform_fee,
-            created_at: env.ledger().timestamp(),
-            last_updated: env.ledger().timestamp(),
-        };
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage().instance().set(&DataKey::VelocityGuard, &guard);
+        Ok(())
+    }
 
-        let _ttl_key = DataKey::Campaign(campaign_id);
-        env.storage().persistent().set(&_ttl_key, &campaign);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+    /// Fraud-prevention pipeline allowed to call `record_views_batch`
+    pub fn set_fraud_contract(
+        env: Env,
+        admin: Address,
+        contract_address: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
-            .set(&DataKey::CampaignCounter, &campaign_id);
-
-        let total_fees: i128 = env
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey::TotalPlatformFees)
-            .unwrap_or(0);
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
         env.storage()
             .instance()
-            .set(&DataKey::TotalPlatformFees, &(total_fees + platform_fee));
-
-        // Update advertiser stats
-        Self::_update_advertiser_stats(&env, &advertiser, campaign_id, budget);
-
-        env.events().publish(
-            (symbol_short!("campaign"), symbol_short!("created")),
-            (campaign_id, advertiser, budget),
-        );
-
-        campaign_id
+            .set(&DataKey::FraudContract, &contract_address);
+        Ok(())
     }
 
-    /// Record a view (publisher earns cost_per_view)
-    pub fn record_view(env: Env, campaign_id: u64, publisher: Address) {
+    /// Require a verified Advertiser identity to create campaigns; unset leaves creation open
+    pub fn set_identity_registry(
+        env: Env,
+        admin: Address,
+        registry: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        publisher.require_auth();
-
-        // CROSS-CONTRACT VALIDATION: Validate campaign status across all contracts
-        Self::_validate_campaign_cross_contract(&env, campaign_id, &publisher);
-
-        let mut campaign: Campaign = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Campaign(campaign_id))
-            .expect("campaign not found");
-
-        // Verify publisher
-        let publisher_data: VerifiedPublisher = env
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
-            .persistent()
-            .get(&DataKey::Publisher(publisher.clone()))
-            .expect("publisher not verified");
-
-        if !publisher_data.verified {
-            panic!("publisher not verified");
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
         }
+        env.storage()
+            .instance()
+            .set(&DataKey::IdentityRegistry, &registry);
+        Ok(())
+    }
 
-        // Check campaign is active
-        match campaign.status {
-            CampaignStatus::Active => {}
-            _ => panic!("campaign not active"),
+    /// Delegate publisher checks to publisher-verification, so a publisher
+    /// suspended or expired there can no longer earn here. Replaces the local
+    /// registry written by `verify_publisher`.
+    pub fn set_publisher_verification(
+        env: Env,
+        admin: Address,
+        contract_address: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
         }
+        env.storage()
+            .instance()
+            .set(&DataKey::PublisherVerification, &contract_address);
+        Ok(())
+    }
 
-        if campaign.current_views >= campaign.target_views {
-            panic!("campaign target reached");
+    /// Enforce per-jurisdiction compliance rules at campaign creation, using
+    /// the advertiser's KYC record from this registry
+    pub fn set_kyc_registry(
+        env: Env,
+        admin: Address,
+        registry: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
         }
+        env.storage().instance().set(&DataKey::KycRegistry, &registry);
+        Ok(())
+    }
 
-        if env.ledger().sequence() > campaign.end_ledger {
-            panic!("campaign expired");
+    /// Admin: set the rule for a KYC jurisdiction code
+    pub fn set_compliance_rule(
+        env: Env,
+        admin: Address,
+        jurisdiction: String,
+        rule: ComplianceRule,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
         }
-
-        if campaign.remaining_budget < campaign.cost_per_view {
-            panic!("insufficient budget");
+        if rule.max_budget < 0 {
+            return Err(PulsarError::InvalidInput);
         }
 
-        // Check daily view limit
-        let current_day = env.ledger().timestamp() / 86_400;
-        let daily_key = DataKey::DailyViews(campaign_id, current_day);
-        let daily_views: u64 = env.storage().temporary().get(&daily_key).unwrap_or(0);
+        let _ttl_key = DataKey::ComplianceRule(jurisdiction.clone());
+        env.storage().persistent().set(&_ttl_key, &rule);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
 
-        if daily_views >= campaign.daily_view_limit {
-            panic!("daily view limit reached");
-        }
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("comply"),
+            symbol_short!("rule"),
+            jurisdiction,
+        );
+        Ok(())
+    }
 
-        // Transfer payment to publisher
-        let token_addr: Address = env
+    pub fn remove_compliance_rule(
+        env: Env,
+        admin: Address,
+        jurisdiction: String,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey::TokenAddress)
-            .unwrap();
-        let token_client = token::Client::new(&env, &token_addr);
-        token_client.transfer(
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ComplianceRule(jurisdiction));
+        Ok(())
+    }
+
+    /// Create a new ad campaign
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_campaign(
+        env: Env,
+        advertiser: Address,
+        campaign_type: u32,
+        budget: i128,
+        cost_per_view: i128,
+        duration: u32,
+        target_views: u64,
+        daily_view_limit: u64,
+        refundable: bool,
+    ) -> Result<u64, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::require_not_paused(&env, &DataKey::Paused)?;
+        advertiser.require_auth();
+
+        Self::_create_campaign(
+            &env,
+            advertiser,
+            campaign_type,
+            budget,
+            cost_per_view,
+            duration,
+            target_views,
+            daily_view_limit,
+            refundable,
+        )
+    }
+
+    /// Create a campaign with a completion bonus of `bonus_amount`, paid on
+    /// top of the budget and shared by the `bonus_top_n` publishers with the
+    /// most verified views once the campaign completes
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_campaign_with_bonus(
+        env: Env,
+        advertiser: Address,
+        campaign_type: u32,
+        budget: i128,
+        cost_per_view: i128,
+        duration: u32,
+        target_views: u64,
+        daily_view_limit: u64,
+        refundable: bool,
+        bonus_amount: i128,
+        bonus_top_n: u32,
+    ) -> Result<u64, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::require_not_paused(&env, &DataKey::Paused)?;
+        advertiser.require_auth();
+
+        if bonus_amount <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        if bonus_top_n == 0 || bonus_top_n > MAX_BONUS_PUBLISHERS {
+            return Err(PulsarError::InvalidAmount);
+        }
+
+        let campaign_id = Self::_create_campaign(
+            &env,
+            advertiser.clone(),
+            campaign_type,
+            budget,
+            cost_per_view,
+            duration,
+            target_views,
+            daily_view_limit,
+            refundable,
+        )?;
+
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenAddress)
+            .unwrap();
+        token::Client::new(&env, &token_addr).transfer(
+            &advertiser,
+            &env.current_contract_address(),
+            &bonus_amount,
+        );
+
+        let pool = BonusPool {
+            amount: bonus_amount,
+            top_n: bonus_top_n,
+            distributed: false,
+        };
+        let _ttl_key = DataKey::BonusPool(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &pool);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("bonus"),
+            symbol_short!("funded"),
+            (campaign_id, bonus_amount, bonus_top_n),
+        );
+        Ok(campaign_id)
+    }
+
+    /// Start a new funded campaign with the type, cost-per-view, limits,
+    /// duration and targeting of one of the advertiser's existing campaigns
+    pub fn clone_campaign(
+        env: Env,
+        advertiser: Address,
+        source_campaign_id: u64,
+        overrides: CampaignOverrides,
+        budget: i128,
+    ) -> Result<u64, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::require_not_paused(&env, &DataKey::Paused)?;
+        advertiser.require_auth();
+
+        let source: Campaign = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Campaign(source_campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+        if source.advertiser != advertiser {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        let campaign_id = Self::_create_campaign(
+            &env,
+            advertiser.clone(),
+            source.campaign_type,
+            budget,
+            overrides.cost_per_view.unwrap_or(source.cost_per_view),
+            overrides
+                .duration
+                .unwrap_or(source.end_ledger - source.start_ledger),
+            overrides.target_views.unwrap_or(source.target_views),
+            overrides.daily_view_limit.unwrap_or(source.daily_view_limit),
+            overrides.refundable.unwrap_or(source.refundable),
+        )?;
+
+        if let Some(targeting_addr) = env.storage().instance().get::<DataKey, Address>(&DataKey::TargetingContract) {
+            let config: Option<TargetingConfig> = env.invoke_contract(
+                &targeting_addr,
+                &Symbol::new(&env, "get_targeting"),
+                SdkVec::from_array(&env, [source_campaign_id.into_val(&env)]),
+            );
+            if let Some(config) = config {
+                let params = TargetingParams {
+                    geographic_targets: config.geographic_targets,
+                    interest_segments: config.interest_segments,
+                    excluded_segments: config.excluded_segments,
+                    min_age: config.min_age,
+                    max_age: config.max_age,
+                    device_types: config.device_types,
+                    languages: config.languages,
+                    min_reputation: config.min_reputation_score,
+                    exclude_fraud: config.exclude_fraud,
+                    require_kyc: config.require_kyc,
+                    max_cpm: config.max_cpm,
+                };
+                Self::_set_targeting(&env, &targeting_addr, &advertiser, campaign_id, params);
+            }
+        }
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("campaign"),
+            symbol_short!("cloned"),
+            (source_campaign_id, campaign_id),
+        );
+
+        Ok(campaign_id)
+    }
+
+    /// Save (or replace) a named template for later `create_from_template`,
+    /// optionally with targeting to apply to each campaign made from it
+    pub fn save_template(
+        env: Env,
+        advertiser: Address,
+        name: String,
+        template: CampaignTemplate,
+        targeting: Option<TargetingParams>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        advertiser.require_auth();
+
+        let campaign_type_data: CampaignType = env
+            .storage()
+            .instance()
+            .get(&DataKey::CampaignType(template.campaign_type))
+            .ok_or(PulsarError::NotFound)?;
+        if template.duration < campaign_type_data.min_duration
+            || template.duration > campaign_type_data.max_duration
+        {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let _ttl_key = DataKey::Template(advertiser.clone(), name.clone());
+        env.storage().persistent().set(&_ttl_key, &template);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let _ttl_key = DataKey::TemplateTargeting(advertiser, name);
+        match targeting {
+            Some(params) => {
+                if params.min_age > params.max_age {
+                    return Err(PulsarError::InvalidInput);
+                }
+                env.storage().persistent().set(&_ttl_key, &params);
+                env.storage().persistent().extend_ttl(
+                    &_ttl_key,
+                    PERSISTENT_LIFETIME_THRESHOLD,
+                    PERSISTENT_BUMP_AMOUNT,
+                );
+            }
+            None => env.storage().persistent().remove(&_ttl_key),
+        }
+        Ok(())
+    }
+
+    pub fn delete_template(env: Env, advertiser: Address, name: String) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        advertiser.require_auth();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Template(advertiser.clone(), name.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::TemplateTargeting(advertiser, name));
+    }
+
+    /// Start a new funded campaign from a saved template
+    pub fn create_from_template(
+        env: Env,
+        advertiser: Address,
+        name: String,
+        budget: i128,
+    ) -> Result<u64, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::require_not_paused(&env, &DataKey::Paused)?;
+        advertiser.require_auth();
+
+        let template: CampaignTemplate = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Template(advertiser.clone(), name.clone()))
+            .ok_or(PulsarError::NotFound)?;
+
+        let campaign_id = Self::_create_campaign(
+            &env,
+            advertiser.clone(),
+            template.campaign_type,
+            budget,
+            template.cost_per_view,
+            template.duration,
+            template.target_views,
+            template.daily_view_limit,
+            template.refundable,
+        )?;
+
+        if let Some(params) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, TargetingParams>(&DataKey::TemplateTargeting(advertiser.clone(), name))
+        {
+            let targeting_addr: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::TargetingContract)
+                .ok_or(PulsarError::NotInitialized)?;
+            Self::_set_targeting(&env, &targeting_addr, &advertiser, campaign_id, params);
+        }
+
+        Ok(campaign_id)
+    }
+
+    /// Record a view (publisher earns cost_per_view plus its tier bonus)
+    pub fn record_view(env: Env, campaign_id: u64, publisher: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::require_not_paused(&env, &DataKey::Paused)?;
+        publisher.require_auth();
+
+        // CROSS-CONTRACT VALIDATION: Validate campaign status across all contracts
+        Self::_validate_campaign_cross_contract(&env, campaign_id, &publisher)?;
+
+        let mut campaign: Campaign = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Campaign(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        Self::_require_publisher_listed(&env, campaign_id, &publisher)?;
+        let verification = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::PublisherVerification);
+        Self::_require_publisher_verified(&env, &verification, &publisher)?;
+        let tier = Self::_tier_params(&env, &verification, &publisher);
+        let payout = Self::_view_payout(&campaign, &tier);
+
+        // Check campaign is active
+        match campaign.status {
+            CampaignStatus::Active => {}
+            _ => return Err(PulsarError::InvalidState),
+        }
+
+        if campaign.current_views >= campaign.target_views {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        if env.ledger().sequence() > campaign.end_ledger {
+            return Err(PulsarError::Expired);
+        }
+
+        if campaign.remaining_budget < payout {
+            return Err(PulsarError::InsufficientBalance);
+        }
+
+        // Check daily view limit; higher tiers may serve past it
+        let current_day = env.ledger().timestamp() / 86_400;
+        let daily_key = DataKey::DailyViews(campaign_id, current_day);
+        let daily_views: u64 = env.storage().temporary().get(&daily_key).unwrap_or(0);
+
+        if daily_views >= Self::_daily_cap(&campaign, &tier) {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        // An abnormal burst pauses the campaign instead of paying for the view
+        if !Self::_check_velocity(&env, campaign_id, &mut campaign, 1) {
+            return Ok(());
+        }
+
+        // Transfer payment to publisher
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenAddress)
+            .unwrap();
+        let token_client = token::Client::new(&env, &token_addr);
+        token_client.transfer(&env.current_contract_address(), &publisher, &payout);
+
+        // Update campaign
+        campaign.remaining_budget -= payout;
+        campaign.current_views += 1;
+        campaign.last_updated = env.ledger().timestamp();
+
+        if campaign.current_views >= campaign.target_views {
+            campaign.status = CampaignStatus::Completed;
+        }
+
+        let _ttl_key = DataKey::Campaign(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &campaign);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage()
+            .temporary()
+            .set(&daily_key, &(daily_views + 1));
+        Self::_track_bonus_publisher(&env, campaign_id, &publisher, 1);
+
+        // Update publisher earnings
+        match verification {
+            Some(verification_addr) => {
+                let _: () = env.invoke_contract(
+                    &verification_addr,
+                    &Symbol::new(&env, "record_impression"),
+                    SdkVec::from_array(
+                        &env,
+                        [
+                            env.current_contract_address().into_val(&env),
+                            publisher.clone().into_val(&env),
+                            payout.into_val(&env),
+                        ],
+                    ),
+                );
+            }
+            None => Self::_update_publisher_earnings(&env, &publisher, payout),
+        }
+
+        // Report the spend so the optimizer's pacing and can_spend stay accurate
+        if let Some(optimizer_addr) = env.storage().instance().get::<DataKey, Address>(&DataKey::BudgetOptimizer) {
+            let _: () = env.invoke_contract(
+                &optimizer_addr,
+                &Symbol::new(&env, "record_spend"),
+                SdkVec::from_array(
+                    &env,
+                    [
+                        env.current_contract_address().into_val(&env),
+                        campaign_id.into_val(&env),
+                        payout.into_val(&env),
+                    ],
+                ),
+            );
+        }
+
+        // Count the impression against the publisher node's daily capacity
+        if let Some(network_addr) = env.storage().instance().get::<DataKey, Address>(&DataKey::NetworkContract) {
+            let _: () = env.invoke_contract(
+                &network_addr,
+                &Symbol::new(&env, "record_impression"),
+                SdkVec::from_array(
+                    &env,
+                    [
+                        env.current_contract_address().into_val(&env),
+                        publisher.clone().into_val(&env),
+                    ],
+                ),
+            );
+        }
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("view"),
+            symbol_short!("recorded"),
+            (campaign_id, publisher),
+        );
+        Ok(())
+    }
+
+    /// Record `count` verified views in one call (fraud contract only). The
+    /// campaign is debited `count` tier-adjusted payouts once and the amount accrues
+    /// to the publisher's claimable balance instead of being paid per view.
+    /// Returns `false` when the velocity guard paused the campaign and
+    /// nothing was accrued.
+    pub fn record_views_batch(
+        env: Env,
+        campaign_id: u64,
+        publisher: Address,
+        count: u64,
+    ) -> Result<bool, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::require_not_paused(&env, &DataKey::Paused)?;
+        let fraud: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FraudContract)
+            .ok_or(PulsarError::NotInitialized)?;
+        fraud.require_auth();
+        if count == 0 {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        Self::_validate_campaign_cross_contract(&env, campaign_id, &publisher)?;
+
+        let mut campaign: Campaign = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Campaign(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        Self::_require_publisher_listed(&env, campaign_id, &publisher)?;
+        let verification = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::PublisherVerification);
+        Self::_require_publisher_verified(&env, &verification, &publisher)?;
+        let tier = Self::_tier_params(&env, &verification, &publisher);
+
+        match campaign.status {
+            CampaignStatus::Active => {}
+            _ => return Err(PulsarError::InvalidState),
+        }
+
+        if campaign.current_views + count > campaign.target_views {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        if env.ledger().sequence() > campaign.end_ledger {
+            return Err(PulsarError::Expired);
+        }
+
+        let amount = Self::_view_payout(&campaign, &tier) * count as i128;
+        if campaign.remaining_budget < amount {
+            return Err(PulsarError::InsufficientBalance);
+        }
+
+        let current_day = env.ledger().timestamp() / 86_400;
+        let daily_key = DataKey::DailyViews(campaign_id, current_day);
+        let daily_views: u64 = env.storage().temporary().get(&daily_key).unwrap_or(0);
+        if daily_views + count > Self::_daily_cap(&campaign, &tier) {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        if !Self::_check_velocity(&env, campaign_id, &mut campaign, count) {
+            return Ok(false);
+        }
+
+        campaign.remaining_budget -= amount;
+        campaign.current_views += count;
+        campaign.last_updated = env.ledger().timestamp();
+        if campaign.current_views >= campaign.target_views {
+            campaign.status = CampaignStatus::Completed;
+        }
+
+        let _ttl_key = DataKey::Campaign(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &campaign);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage()
+            .temporary()
+            .set(&daily_key, &(daily_views + count));
+        Self::_track_bonus_publisher(&env, campaign_id, &publisher, count);
+
+        let _ttl_key = DataKey::PublisherBalance(publisher.clone());
+        let balance: i128 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+        env.storage().persistent().set(&_ttl_key, &(balance + amount));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        match verification {
+            Some(verification_addr) => {
+                let _: () = env.invoke_contract(
+                    &verification_addr,
+                    &Symbol::new(&env, "record_impressions"),
+                    SdkVec::from_array(
+                        &env,
+                        [
+                            env.current_contract_address().into_val(&env),
+                            publisher.clone().into_val(&env),
+                            count.into_val(&env),
+                            amount.into_val(&env),
+                        ],
+                    ),
+                );
+            }
+            None => Self::_update_publisher_earnings(&env, &publisher, amount),
+        }
+
+        if let Some(optimizer_addr) = env.storage().instance().get::<DataKey, Address>(&DataKey::BudgetOptimizer) {
+            let _: () = env.invoke_contract(
+                &optimizer_addr,
+                &Symbol::new(&env, "record_spend"),
+                SdkVec::from_array(
+                    &env,
+                    [
+                        env.current_contract_address().into_val(&env),
+                        campaign_id.into_val(&env),
+                        amount.into_val(&env),
+                    ],
+                ),
+            );
+        }
+
+        if let Some(network_addr) = env.storage().instance().get::<DataKey, Address>(&DataKey::NetworkContract) {
+            let _: () = env.invoke_contract(
+                &network_addr,
+                &Symbol::new(&env, "record_impressions"),
+                SdkVec::from_array(
+                    &env,
+                    [
+                        env.current_contract_address().into_val(&env),
+                        publisher.clone().into_val(&env),
+                        count.into_val(&env),
+                    ],
+                ),
+            );
+        }
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("view"),
+            symbol_short!("batch"),
+            (campaign_id, publisher, count),
+        );
+        Ok(true)
+    }
+
+    /// Withdraw earnings accrued by `record_views_batch`
+    pub fn claim_publisher_earnings(env: Env, publisher: Address) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::require_not_paused(&env, &DataKey::Paused)?;
+        publisher.require_auth();
+
+        let key = DataKey::PublisherBalance(publisher.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount <= 0 {
+            return Err(PulsarError::InsufficientBalance);
+        }
+        env.storage().persistent().remove(&key);
+
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenAddress)
+            .unwrap();
+        token::Client::new(&env, &token_addr).transfer(
             &env.current_contract_address(),
             &publisher,
-            &campaign.cost_per_view,
+            &amount,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("publisher"),
+            symbol_short!("claimed"),
+            (publisher, amount),
         );
+        Ok(amount)
+    }
+
+    /// Split a completed campaign's bonus pool among its top publishers in
+    /// proportion to their fraud-verified views, each capped at the views this
+    /// contract actually paid them for; any rounding dust goes to the top
+    /// publisher. Refunds the advertiser if no publisher has a verified view.
+    /// Anyone may call.
+    pub fn distribute_bonus(env: Env, campaign_id: u64) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::require_not_paused(&env, &DataKey::Paused)?;
 
-        // Update campaign
-        campaign.remaining_budget -= campaign.cost_per_view;
-        campaign.current_views += 1;
-        campaign.last_updated = env.ledger().timestamp();
+        let campaign: Campaign = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Campaign(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+        let pool_key = DataKey::BonusPool(campaign_id);
+        let mut pool: BonusPool = env
+            .storage()
+            .persistent()
+            .get(&pool_key)
+            .ok_or(PulsarError::NotFound)?;
+        if pool.distributed {
+            return Err(PulsarError::AlreadyExists);
+        }
+        match campaign.status {
+            CampaignStatus::Completed => {}
+            _ => return Err(PulsarError::InvalidState),
+        }
+        let fraud: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FraudContract)
+            .ok_or(PulsarError::NotInitialized)?;
 
-        if campaign.current_views >= campaign.target_views {
-            campaign.status = CampaignStatus::Completed;
+        // Rank the tracked publishers by verified views, highest first
+        let publishers: soroban_sdk::Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BonusPublishers(campaign_id))
+            .unwrap_or(SdkVec::new(&env));
+        let mut ranked: soroban_sdk::Vec<(Address, u64)> = SdkVec::new(&env);
+        for publisher in publishers.iter() {
+            let verified: u64 = env.invoke_contract(
+                &fraud,
+                &Symbol::new(&env, "get_publisher_verified_views"),
+                SdkVec::from_array(
+                    &env,
+                    [campaign_id.into_val(&env), publisher.clone().into_val(&env)],
+                ),
+            );
+            let paid: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::BonusPaidViews(campaign_id, publisher.clone()))
+                .unwrap_or(0);
+            let views = verified.min(paid);
+            if views == 0 {
+                continue;
+            }
+            let mut at = ranked.len();
+            for (i, (_, other)) in ranked.iter().enumerate() {
+                if views > other {
+                    at = i as u32;
+                    break;
+                }
+            }
+            ranked.insert(at, (publisher, views));
+        }
+        while ranked.len() > pool.top_n {
+            ranked.pop_back();
         }
 
-        let _ttl_key = DataKey::Campaign(campaign_id);
-        env.storage().persistent().set(&_ttl_key, &campaign);
+        pool.distributed = true;
+        env.storage().persistent().set(&pool_key, &pool);
         env.storage().persistent().extend_ttl(
-            &_ttl_key,
+            &pool_key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+
+        let total_views: u64 = ranked.iter().map(|(_, views)| views).sum();
+        if total_views == 0 {
+            let token_addr: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::TokenAddress)
+                .unwrap();
+            token::Client::new(&env, &token_addr).transfer(
+                &env.current_contract_address(),
+                &campaign.advertiser,
+                &pool.amount,
+            );
+            pulsar_common_admin::publish_event(
+                &env,
+                symbol_short!("bonus"),
+                symbol_short!("refunded"),
+                (campaign_id, pool.amount),
+            );
+            return Ok(());
+        }
+
+        let mut shares: soroban_sdk::Vec<i128> = SdkVec::new(&env);
+        let mut allocated: i128 = 0;
+        for (_, views) in ranked.iter() {
+            let share = pool.amount * views as i128 / total_views as i128;
+            shares.push_back(share);
+            allocated += share;
+        }
+        shares.set(0, shares.get(0).unwrap() + pool.amount - allocated);
+
+        for (i, (publisher, _)) in ranked.iter().enumerate() {
+            let _ttl_key = DataKey::BonusClaim(campaign_id, publisher);
+            env.storage()
+                .persistent()
+                .set(&_ttl_key, &shares.get(i as u32).unwrap());
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("bonus"),
+            symbol_short!("distrib"),
+            (campaign_id, pool.amount, ranked.len()),
+        );
+        Ok(())
+    }
+
+    /// Return the bonus pool of a campaign that can no longer complete (its
+    /// end ledger passed, or it expired, short of its target) to the
+    /// advertiser. Anyone may call.
+    pub fn refund_bonus(env: Env, campaign_id: u64) -> Result<i128, PulsarError> {
         env.storage()
-            .temporary()
-            .set(&daily_key, &(daily_views + 1));
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::require_not_paused(&env, &DataKey::Paused)?;
 
-        // Update publisher earnings
-        Self::_update_publisher_earnings(&env, &publisher, campaign.cost_per_view);
+        let campaign: Campaign = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Campaign(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+        let pool: BonusPool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BonusPool(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+        if pool.distributed {
+            return Err(PulsarError::AlreadyExists);
+        }
+        match campaign.status {
+            CampaignStatus::Completed | CampaignStatus::Cancelled => {
+                return Err(PulsarError::InvalidState)
+            }
+            CampaignStatus::Expired => {}
+            _ if env.ledger().sequence() > campaign.end_ledger => {}
+            _ => return Err(PulsarError::TooEarly),
+        }
 
-        env.events().publish(
-            (symbol_short!("view"), symbol_short!("recorded")),
-            (campaign_id, publisher),
+        let amount = Self::_close_bonus_pool(&env, campaign_id);
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenAddress)
+            .unwrap();
+        token::Client::new(&env, &token_addr).transfer(
+            &env.current_contract_address(),
+            &campaign.advertiser,
+            &amount,
+        );
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("bonus"),
+            symbol_short!("refunded"),
+            (campaign_id, amount),
+        );
+        Ok(amount)
+    }
+
+    /// Withdraw a publisher's share of a distributed bonus pool
+    pub fn claim_bonus(
+        env: Env,
+        publisher: Address,
+        campaign_id: u64,
+    ) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::require_not_paused(&env, &DataKey::Paused)?;
+        publisher.require_auth();
+
+        let key = DataKey::BonusClaim(campaign_id, publisher.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount <= 0 {
+            return Err(PulsarError::InsufficientBalance);
+        }
+        env.storage().persistent().remove(&key);
+
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenAddress)
+            .unwrap();
+        token::Client::new(&env, &token_addr).transfer(
+            &env.current_contract_address(),
+            &publisher,
+            &amount,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("bonus"),
+            symbol_short!("claimed"),
+            (campaign_id, publisher, amount),
         );
+        Ok(amount)
     }
 
     /// Pause a campaign (advertiser only)
-    pub fn pause_campaign(env: Env, advertiser: Address, campaign_id: u64) {
+    pub fn pause_campaign(
+        env: Env,
+        advertiser: Address,
+        campaign_id: u64,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -420,10 +1515,10 @@ impl CampaignOrchestratorContract {
             .storage()
             .persistent()
             .get(&DataKey::Campaign(campaign_id))
-            .expect("campaign not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if campaign.advertiser != advertiser {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         campaign.status = CampaignStatus::Paused;
@@ -435,10 +1530,15 @@ impl CampaignOrchestratorContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     /// Resume a paused campaign
-    pub fn resume_campaign(env: Env, advertiser: Address, campaign_id: u64) {
+    pub fn resume_campaign(
+        env: Env,
+        advertiser: Address,
+        campaign_id: u64,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -448,10 +1548,10 @@ impl CampaignOrchestratorContract {
             .storage()
             .persistent()
             .get(&DataKey::Campaign(campaign_id))
-            .expect("campaign not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if campaign.advertiser != advertiser {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         campaign.status = CampaignStatus::Active;
@@ -463,32 +1563,48 @@ impl CampaignOrchestratorContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
-    /// Cancel campaign and refund remaining budget (if refundable)
-    pub fn cancel_campaign(env: Env, advertiser: Address, campaign_id: u64) {
+    /// Cancel campaign and refund remaining budget (if refundable). A
+    /// completed campaign only returns its leftover budget: it stays
+    /// Completed and its bonus pool is left for `distribute_bonus`.
+    pub fn cancel_campaign(
+        env: Env,
+        advertiser: Address,
+        campaign_id: u64,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::require_not_paused(&env, &DataKey::Paused)?;
         advertiser.require_auth();
 
         let mut campaign: Campaign = env
             .storage()
             .persistent()
             .get(&DataKey::Campaign(campaign_id))
-            .expect("campaign not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if campaign.advertiser != advertiser {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         if !campaign.refundable {
-            panic!("campaign not refundable");
+            return Err(PulsarError::InvalidState);
+        }
+        if matches!(campaign.status, CampaignStatus::Cancelled) {
+            return Err(PulsarError::InvalidState);
         }
 
-        let refund = campaign.remaining_budget;
+        let mut refund = campaign.remaining_budget;
         campaign.remaining_budget = 0;
-        campaign.status = CampaignStatus::Cancelled;
+
+        // Before completion an undistributed bonus goes back with the budget
+        if !matches!(campaign.status, CampaignStatus::Completed) {
+            campaign.status = CampaignStatus::Cancelled;
+            refund += Self::_close_bonus_pool(&env, campaign_id);
+        }
         campaign.last_updated = env.ledger().timestamp();
 
         let _ttl_key = DataKey::Campaign(campaign_id);
@@ -509,21 +1625,152 @@ impl CampaignOrchestratorContract {
             token_client.transfer(&env.current_contract_address(), &advertiser, &refund);
         }
 
-        env.events().publish(
-            (symbol_short!("campaign"), symbol_short!("cancelled")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("campaign"),
+            symbol_short!("cancelled"),
             (campaign_id, refund),
         );
+        Ok(())
+    }
+
+    /// Attach a licensed marketplace creative to a campaign (advertiser only).
+    /// The advertiser must hold a valid license for the listing.
+    pub fn attach_creative(
+        env: Env,
+        advertiser: Address,
+        campaign_id: u64,
+        listing_id: u64,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        advertiser.require_auth();
+
+        let campaign: Campaign = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Campaign(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if campaign.advertiser != advertiser {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        if !Self::_has_creative_license(&env, listing_id, &advertiser)? {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        let key = DataKey::CampaignCreatives(campaign_id);
+        let mut creatives: SdkVec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(SdkVec::new(&env));
+        if creatives.contains(listing_id) {
+            return Err(PulsarError::AlreadyExists);
+        }
+        creatives.push_back(listing_id);
+        env.storage().persistent().set(&key, &creatives);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("creative"),
+            symbol_short!("attached"),
+            (campaign_id, listing_id),
+        );
+        Ok(())
+    }
+
+    /// Restrict which publishers may serve a campaign (advertiser only).
+    /// Replaces any previous list; `Open` clears it.
+    pub fn set_publisher_list(
+        env: Env,
+        advertiser: Address,
+        campaign_id: u64,
+        mode: PublisherListMode,
+        publishers: soroban_sdk::Vec<Address>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        advertiser.require_auth();
+
+        let campaign: Campaign = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Campaign(campaign_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if campaign.advertiser != advertiser {
+            return Err(PulsarError::Unauthorized);
+        }
+        if publishers.len() > MAX_PUBLISHER_LIST {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        let key = DataKey::PublisherList(campaign_id);
+        if mode == PublisherListMode::Open {
+            env.storage().persistent().remove(&key);
+        } else {
+            let list = PublisherList {
+                mode: mode.clone(),
+                publishers: publishers.clone(),
+            };
+            env.storage().persistent().set(&key, &list);
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("campaign"),
+            symbol_short!("pub_list"),
+            (campaign_id, mode, publishers.len()),
+        );
+        Ok(())
+    }
+
+    pub fn get_publisher_list(env: Env, campaign_id: u64) -> Option<PublisherList> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::PublisherList(campaign_id))
     }
 
-    /// Admin: verify a publisher
-    pub fn verify_publisher(env: Env, admin: Address, publisher: Address, initial_score: u32) {
+    /// Admin: verify a publisher in the legacy local registry.
+    /// Deprecated: verify in publisher-verification and register it with
+    /// `set_publisher_verification`; rejected once that is configured.
+    pub fn verify_publisher(
+        env: Env,
+        admin: Address,
+        publisher: Address,
+        initial_score: u32,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
+        }
+        if env.storage().instance().has(&DataKey::PublisherVerification) {
+            return Err(PulsarError::InvalidState);
         }
 
         let publisher_data = VerifiedPublisher {
@@ -542,82 +1789,223 @@ impl CampaignOrchestratorContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("publisher"), symbol_short!("verified")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("publisher"),
+            symbol_short!("verified"),
             publisher,
         );
+        Ok(())
+    }
+
+    /// Admin: set platform fee
+    pub fn set_platform_fee(env: Env, admin: Address, fee_pct: u32) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        if fee_pct > 10 {
+            return Err(PulsarError::LimitExceeded);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::PlatformFeePct, &fee_pct);
+        Ok(())
+    }
+
+    // ============================================================
+    // Read-Only Functions
+    // ============================================================
+
+    pub fn get_campaign(env: Env, campaign_id: u64) -> Option<Campaign> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Campaign(campaign_id))
+    }
+
+    pub fn get_campaign_metrics(env: Env, campaign_id: u64) -> Option<CampaignMetrics> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let campaign: Campaign = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Campaign(campaign_id))?;
+
+        let total_spent = campaign.budget - campaign.remaining_budget;
+        let completion_rate = (campaign.current_views * 100)
+            .checked_div(campaign.target_views)
+            .unwrap_or(0) as u32;
+
+        Some(CampaignMetrics {
+            campaign,
+            total_spent,
+            completion_rate,
+        })
+    }
+
+    /// Deprecated: legacy registry only; read publisher-verification instead
+    pub fn get_publisher_metrics(env: Env, publisher: Address) -> Option<VerifiedPublisher> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Publisher(publisher))
+    }
+
+    pub fn get_template(env: Env, advertiser: Address, name: String) -> Option<CampaignTemplate> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Template(advertiser, name))
+    }
+
+    pub fn get_template_targeting(
+        env: Env,
+        advertiser: Address,
+        name: String,
+    ) -> Option<TargetingParams> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::TemplateTargeting(advertiser, name))
+    }
+
+    pub fn get_compliance_rule(env: Env, jurisdiction: String) -> Option<ComplianceRule> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::ComplianceRule(jurisdiction))
+    }
+
+    pub fn get_velocity_guard(env: Env) -> Option<VelocityGuard> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().get(&DataKey::VelocityGuard)
     }
 
-    /// Admin: set platform fee
-    pub fn set_platform_fee(env: Env, admin: Address, fee_pct: u32) {
+    pub fn get_hourly_views(env: Env, campaign_id: u64) -> u64 {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if admin != stored_admin {
-            panic!("unauthorized");
-        }
-        if fee_pct > 10 {
-            panic!("fee too high");
-        }
+        let current_hour = env.ledger().timestamp() / 3_600;
+        env.storage()
+            .temporary()
+            .get(&DataKey::HourlyViews(campaign_id, current_hour))
+            .unwrap_or(0)
+    }
+
+    pub fn get_publisher_balance(env: Env, publisher: Address) -> i128 {
         env.storage()
             .instance()
-            .set(&DataKey::PlatformFeePct, &fee_pct);
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::PublisherBalance(publisher))
+            .unwrap_or(0)
     }
 
-    // ============================================================
-    // Read-Only Functions
-    // ============================================================
+    pub fn get_advertiser_stats(env: Env, advertiser: Address) -> Option<AdvertiserStats> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::AdvertiserStats(advertiser))
+    }
 
-    pub fn get_campaign(env: Env, campaign_id: u64) -> Option<Campaign> {
+    pub fn get_campaign_creatives(env: Env, campaign_id: u64) -> soroban_sdk::Vec<u64> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         env.storage()
             .persistent()
-            .get(&DataKey::Campaign(campaign_id))
+            .get(&DataKey::CampaignCreatives(campaign_id))
+            .unwrap_or(SdkVec::new(&env))
     }
 
-    pub fn get_campaign_metrics(env: Env, campaign_id: u64) -> Option<CampaignMetrics> {
+    /// Publishers call this before serving a creative: true if the listing is
+    /// attached to the campaign and the advertiser's license is still valid.
+    pub fn verify_creative(
+        env: Env,
+        campaign_id: u64,
+        listing_id: u64,
+    ) -> Result<bool, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        let campaign: Campaign = env
+        let campaign: Campaign = match env
             .storage()
             .persistent()
-            .get(&DataKey::Campaign(campaign_id))?;
-
-        let total_spent = campaign.budget - campaign.remaining_budget;
-        let completion_rate = if campaign.target_views > 0 {
-            ((campaign.current_views * 100) / campaign.target_views) as u32
-        } else {
-            0
+            .get(&DataKey::Campaign(campaign_id))
+        {
+            Some(c) => c,
+            None => return Ok(false),
         };
+        let creatives: SdkVec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CampaignCreatives(campaign_id))
+            .unwrap_or(SdkVec::new(&env));
+        Ok(creatives.contains(listing_id)
+            && Self::_has_creative_license(&env, listing_id, &campaign.advertiser)?)
+    }
 
-        Some(CampaignMetrics {
-            campaign,
-            total_spent,
-            completion_rate,
+    /// Campaigns with ids after `start`, paged by `pulsar_common_admin::page`
+    pub fn list_campaigns(
+        env: Env,
+        start: u64,
+        limit: u32,
+    ) -> (soroban_sdk::Vec<Campaign>, Option<u64>) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::CampaignCounter)
+            .unwrap_or(0);
+        pulsar_common_admin::page(&env, counter, start, limit, |id| {
+            env.storage().persistent().get(&DataKey::Campaign(id))
         })
     }
 
-    pub fn get_publisher_metrics(env: Env, publisher: Address) -> Option<VerifiedPublisher> {
+    pub fn get_bonus_pool(env: Env, campaign_id: u64) -> Option<BonusPool> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         env.storage()
             .persistent()
-            .get(&DataKey::Publisher(publisher))
+            .get(&DataKey::BonusPool(campaign_id))
     }
 
-    pub fn get_advertiser_stats(env: Env, advertiser: Address) -> Option<AdvertiserStats> {
+    pub fn get_bonus_claim(env: Env, campaign_id: u64, publisher: Address) -> i128 {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         env.storage()
             .persistent()
-            .get(&DataKey::AdvertiserStats(advertiser))
+            .get(&DataKey::BonusClaim(campaign_id, publisher))
+            .unwrap_or(0)
     }
 
     pub fn get_campaign_count(env: Env) -> u64 {
@@ -634,8 +2022,184 @@ impl CampaignOrchestratorContract {
     // Internal Helpers
     // ============================================================
 
+    #[allow(clippy::too_many_arguments)]
+    fn _create_campaign(
+        env: &Env,
+        advertiser: Address,
+        campaign_type: u32,
+        budget: i128,
+        cost_per_view: i128,
+        duration: u32,
+        target_views: u64,
+        daily_view_limit: u64,
+        refundable: bool,
+    ) -> Result<u64, PulsarError> {
+        if let Some(registry) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::IdentityRegistry)
+        {
+            let verified: bool = env.invoke_contract(
+                &registry,
+                &Symbol::new(env, "require_verified"),
+                SdkVec::from_array(
+                    env,
+                    [
+                        advertiser.clone().into_val(env),
+                        IdentityType::Advertiser.into_val(env),
+                    ],
+                ),
+            );
+            if !verified {
+                return Err(PulsarError::NotVerified);
+            }
+        }
+
+        compliance::enforce(env, &advertiser, campaign_type, budget)?;
+
+        let campaign_type_data: CampaignType = env
+            .storage()
+            .instance()
+            .get(&DataKey::CampaignType(campaign_type))
+            .ok_or(PulsarError::NotFound)?;
+
+        if budget < campaign_type_data.min_budget {
+            return Err(PulsarError::InvalidAmount);
+        }
+        if duration < campaign_type_data.min_duration || duration > campaign_type_data.max_duration
+        {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::CampaignCounter)
+            .unwrap_or(0);
+        let campaign_id = counter + 1;
+
+        let platform_fee_pct: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PlatformFeePct)
+            .unwrap_or(2);
+        let platform_fee = (budget * platform_fee_pct as i128) / 100;
+
+        // Transfer budget + fee from advertiser to this contract
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenAddress)
+            .unwrap();
+        let token_client = token::Client::new(env, &token_addr);
+        token_client.transfer(
+            &advertiser,
+            &env.current_contract_address(),
+            &(budget + platform_fee),
+        );
+        if platform_fee > 0 {
+            if let Some(router) = env
+                .storage()
+                .instance()
+                .get::<DataKey, Address>(&DataKey::FeeRouter)
+            {
+                token_client.transfer(&env.current_contract_address(), &router, &platform_fee);
+                let _: () = env.invoke_contract(
+                    &router,
+                    &Symbol::new(env, "route_fee"),
+                    SdkVec::from_array(
+                        env,
+                        [
+                            env.current_contract_address().into_val(env),
+                            token_addr.into_val(env),
+                            platform_fee.into_val(env),
+                        ],
+                    ),
+                );
+            }
+        }
+
+        let start_ledger = env.ledger().sequence();
+        let end_ledger = start_ledger + duration;
+
+        let campaign = Campaign {
+            advertiser: advertiser.clone(),
+            campaign_type,
+            budget,
+            remaining_budget: budget,
+            cost_per_view,
+            start_ledger,
+            end_ledger,
+            status: CampaignStatus::Active,
+            target_views,
+            current_views: 0,
+            daily_view_limit,
+            refundable,
+            platform_fee,
+            created_at: env.ledger().timestamp(),
+            last_updated: env.ledger().timestamp(),
+        };
+
+        let _ttl_key = DataKey::Campaign(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &campaign);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::CampaignCounter, &campaign_id);
+
+        let total_fees: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalPlatformFees)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalPlatformFees, &(total_fees + platform_fee));
+
+        // Update advertiser stats
+        Self::_update_advertiser_stats(env, &advertiser, campaign_id, budget);
+
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("campaign"),
+            symbol_short!("created"),
+            (campaign_id, advertiser, budget),
+        );
+
+        Ok(campaign_id)
+    }
+
+    fn _set_targeting(
+        env: &Env,
+        targeting_addr: &Address,
+        advertiser: &Address,
+        campaign_id: u64,
+        params: TargetingParams,
+    ) {
+        let _: () = env.invoke_contract(
+            targeting_addr,
+            &Symbol::new(env, "set_targeting"),
+            SdkVec::from_array(
+                env,
+                [
+                    advertiser.clone().into_val(env),
+                    campaign_id.into_val(env),
+                    params.into_val(env),
+                ],
+            ),
+        );
+    }
+
     /// Validate campaign across all contracts before processing
-    fn _validate_campaign_cross_contract(env: &Env, campaign_id: u64, publisher: &Address) {
+    fn _validate_campaign_cross_contract(
+        env: &Env,
+        campaign_id: u64,
+        publisher: &Address,
+    ) -> Result<(), PulsarError> {
         // 1. Validate campaign lifecycle status
         if let Some(lifecycle_addr) = env.storage().instance().get::<DataKey, Address>(&DataKey::LifecycleContract) {
             // Call get_lifecycle on the lifecycle contract
@@ -646,7 +2210,7 @@ impl CampaignOrchestratorContract {
             );
             
             if lifecycle_result.is_none() {
-                panic!("campaign not found in lifecycle contract");
+                return Err(PulsarError::NotFound);
             }
             
             // Note: In production, you would deserialize the result and check the state
@@ -671,7 +2235,7 @@ impl CampaignOrchestratorContract {
                 );
                 
                 if !can_release {
-                    panic!("escrow cannot be released - insufficient budget or conditions not met");
+                    return Err(PulsarError::InsufficientBalance);
                 }
             }
         }
@@ -702,13 +2266,46 @@ impl CampaignOrchestratorContract {
                     // In production, you might want to compute the score on-the-fly
                     // or have a more lenient policy
                     // For now, we'll allow it but log a warning via events
-                    env.events().publish(
-                        (symbol_short!("warning"), symbol_short!("no_score")),
+                    pulsar_common_admin::publish_event(
+                        env,
+                        symbol_short!("warning"),
+                        symbol_short!("no_score"),
                         (campaign_id, publisher.clone()),
                     );
                 }
             }
         }
+
+        // 4. Validate the publisher's network node is live
+        if let Some(network_addr) = env.storage().instance().get::<DataKey, Address>(&DataKey::NetworkContract) {
+            let live: bool = env.invoke_contract(
+                &network_addr,
+                &Symbol::new(env, "is_live"),
+                SdkVec::from_array(env, [publisher.into_val(env)]),
+            );
+
+            if !live {
+                return Err(PulsarError::InvalidState);
+            }
+        }
+        Ok(())
+    }
+
+    fn _has_creative_license(
+        env: &Env,
+        listing_id: u64,
+        advertiser: &Address,
+    ) -> Result<bool, PulsarError> {
+        let marketplace: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::MarketplaceContract)
+            .ok_or(PulsarError::NotInitialized)?;
+        Ok(env.invoke_contract(
+            &marketplace,
+            &Symbol::new(env, "has_license"),
+            SdkVec::from_array(env, [listing_id.into_val(env), advertiser.into_val(env)]),
+        ))
     }
 
     fn _update_advertiser_stats(env: &Env, advertiser: &Address, campaign_id: u64, budget: i128) {
@@ -745,6 +2342,189 @@ impl CampaignOrchestratorContract {
         );
     }
 
+    fn _require_publisher_verified(
+        env: &Env,
+        verification: &Option<Address>,
+        publisher: &Address,
+    ) -> Result<(), PulsarError> {
+        let verified = match verification {
+            Some(verification_addr) => env.invoke_contract::<bool>(
+                verification_addr,
+                &Symbol::new(env, "is_verified"),
+                SdkVec::from_array(env, [publisher.clone().into_val(env)]),
+            ),
+            None => env
+                .storage()
+                .persistent()
+                .get::<DataKey, VerifiedPublisher>(&DataKey::Publisher(publisher.clone()))
+                .map(|p| p.verified)
+                .unwrap_or(false),
+        };
+        if !verified {
+            return Err(PulsarError::NotVerified);
+        }
+        Ok(())
+    }
+
+    fn _require_publisher_listed(
+        env: &Env,
+        campaign_id: u64,
+        publisher: &Address,
+    ) -> Result<(), PulsarError> {
+        let list: PublisherList = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::PublisherList(campaign_id))
+        {
+            Some(list) => list,
+            None => return Ok(()),
+        };
+        let listed = list.publishers.contains(publisher);
+        match list.mode {
+            PublisherListMode::Allow if !listed => return Err(PulsarError::Unauthorized),
+            PublisherListMode::Deny if listed => return Err(PulsarError::Unauthorized),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Benefits of the publisher's verification tier. Without a verification
+    /// contract every publisher is paid and capped at the campaign's base rates.
+    fn _tier_params(env: &Env, verification: &Option<Address>, publisher: &Address) -> TierParams {
+        match verification {
+            Some(verification_addr) => env.invoke_contract(
+                verification_addr,
+                &Symbol::new(env, "get_tier_params"),
+                SdkVec::from_array(env, [publisher.clone().into_val(env)]),
+            ),
+            None => TierParams {
+                revenue_share_bonus_bps: 0,
+                daily_cap_multiplier: 100,
+            },
+        }
+    }
+
+    fn _view_payout(campaign: &Campaign, tier: &TierParams) -> i128 {
+        campaign.cost_per_view
+            + campaign.cost_per_view * tier.revenue_share_bonus_bps as i128 / 10_000
+    }
+
+    fn _daily_cap(campaign: &Campaign, tier: &TierParams) -> u64 {
+        campaign
+            .daily_view_limit
+            .saturating_mul(tier.daily_cap_multiplier as u64)
+            / 100
+    }
+
+    /// Count `count` views against the current hour. If that breaks the
+    /// velocity guard the views are not counted; the campaign is paused, an
+    /// alert is emitted and false is returned. Campaigns in their first hour
+    /// have no average yet and are only held to `min_hourly_views`.
+    fn _check_velocity(env: &Env, campaign_id: u64, campaign: &mut Campaign, count: u64) -> bool {
+        let guard: VelocityGuard = match env.storage().instance().get(&DataKey::VelocityGuard) {
+            Some(guard) => guard,
+            None => return true,
+        };
+        if guard.multiplier == 0 {
+            return true;
+        }
+
+        let now = env.ledger().timestamp();
+        let hourly_key = DataKey::HourlyViews(campaign_id, now / 3_600);
+        let hourly_views: u64 = env.storage().temporary().get(&hourly_key).unwrap_or(0);
+
+        let elapsed_hours = (now - campaign.created_at) / 3_600;
+        let allowed = match campaign
+            .current_views
+            .saturating_sub(hourly_views)
+            .checked_div(elapsed_hours)
+        {
+            Some(average) => (average * guard.multiplier as u64).max(guard.min_hourly_views),
+            None => guard.min_hourly_views,
+        };
+
+        if hourly_views + count > allowed {
+            campaign.status = CampaignStatus::Paused;
+            campaign.last_updated = now;
+            let _ttl_key = DataKey::Campaign(campaign_id);
+            env.storage().persistent().set(&_ttl_key, &*campaign);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+
+            pulsar_common_admin::publish_event(
+                env,
+                symbol_short!("campaign"),
+                symbol_short!("velocity"),
+                (campaign_id, hourly_views + count, allowed),
+            );
+            return false;
+        }
+
+        env.storage()
+            .temporary()
+            .set(&hourly_key, &(hourly_views + count));
+        true
+    }
+
+    /// Mark an undistributed bonus pool as settled and return its amount, or
+    /// 0 if the campaign has none left
+    fn _close_bonus_pool(env: &Env, campaign_id: u64) -> i128 {
+        let key = DataKey::BonusPool(campaign_id);
+        let Some(mut pool) = env.storage().persistent().get::<DataKey, BonusPool>(&key) else {
+            return 0;
+        };
+        if pool.distributed {
+            return 0;
+        }
+        pool.distributed = true;
+        env.storage().persistent().set(&key, &pool);
+        pool.amount
+    }
+
+    /// Remember a publisher that served a campaign with an open bonus pool, up
+    /// to `MAX_BONUS_PUBLISHERS`
+    fn _track_bonus_publisher(env: &Env, campaign_id: u64, publisher: &Address, views: u64) {
+        match env
+            .storage()
+            .persistent()
+            .get::<DataKey, BonusPool>(&DataKey::BonusPool(campaign_id))
+        {
+            Some(pool) if !pool.distributed => {}
+            _ => return,
+        }
+        let _ttl_key = DataKey::BonusPublishers(campaign_id);
+        let mut publishers: soroban_sdk::Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&_ttl_key)
+            .unwrap_or(SdkVec::new(env));
+        if !publishers.contains(publisher) {
+            if publishers.len() >= MAX_BONUS_PUBLISHERS {
+                return;
+            }
+            publishers.push_back(publisher.clone());
+            env.storage().persistent().set(&_ttl_key, &publishers);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+
+        // Bonus weight never exceeds the views this contract paid for
+        let _ttl_key = DataKey::BonusPaidViews(campaign_id, publisher.clone());
+        let paid: u64 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+        env.storage().persistent().set(&_ttl_key, &(paid + views));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
     fn _update_publisher_earnings(env: &Env, publisher: &Address, earning: i128) {
         let key = DataKey::Publisher(publisher.clone());
         if let Some(mut pub_data) = env
@@ -763,18 +2543,45 @@ impl CampaignOrchestratorContract {
         }
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    /// Emergency stop for campaign creation, view payouts and cancellation refunds
+    pub fn set_paused(env: Env, admin: Address, paused: bool) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::set_paused(&env, &DataKey::Admin, &DataKey::Paused, admin, paused)
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::is_paused(&env, &DataKey::Paused)
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
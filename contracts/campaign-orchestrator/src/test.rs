@@ -1,6 +1,10 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, Env};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    Address, Env,
+};
 
 fn deploy_token(env: &Env, admin: &Address) -> Address {
     env.register_stellar_asset_contract_v2(admin.clone())
@@ -10,6 +14,26 @@ fn mint(env: &Env, token: &Address, to: &Address, amount: i128) {
     StellarAssetClient::new(env, token).mint(to, &amount);
 }
 
+mod identity_mock {
+    use super::IdentityType;
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockIdentityRegistry;
+
+    #[contractimpl]
+    impl MockIdentityRegistry {
+        pub fn set_verified(env: Env, account: Address) {
+            env.storage().persistent().set(&account, &true);
+        }
+
+        pub fn require_verified(env: Env, account: Address, identity_type: IdentityType) -> bool {
+            matches!(identity_type, IdentityType::Advertiser)
+                && env.storage().persistent().get(&account).unwrap_or(false)
+        }
+    }
+}
+
 fn setup(
     env: &Env,
 ) -> (
@@ -21,7 +45,7 @@ fn setup(
     let admin = Address::generate(env);
     let token_admin = Address::generate(env);
     let token = deploy_token(env, &token_admin);
-    let id = env.register_contract(None, CampaignOrchestratorContract);
+    let id = env.register(CampaignOrchestratorContract, ());
     let c = CampaignOrchestratorContractClient::new(env, &id);
     c.initialize(&admin, &token);
     (c, admin, token_admin, token)
@@ -35,12 +59,14 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, admin, _, token) = setup(&env);
-    c.initialize(&admin, &token);
+    assert_eq!(
+        c.try_initialize(&admin, &token),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -113,12 +139,14 @@ fn test_set_platform_fee() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_set_platform_fee_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, _, _, _) = setup(&env);
-    c.set_platform_fee(&Address::generate(&env), &5u32);
+    assert_eq!(
+        c.try_set_platform_fee(&Address::generate(&env), &5u32),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
@@ -128,3 +156,1240 @@ fn test_get_campaign_nonexistent() {
     let (c, _, _, _) = setup(&env);
     assert!(c.get_campaign(&999u64).is_none());
 }
+
+mod marketplace_mock {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockMarketplace;
+
+    #[contractimpl]
+    impl MockMarketplace {
+        pub fn grant(env: Env, listing_id: u64, licensee: Address) {
+            env.storage()
+                .persistent()
+                .set(&(listing_id, licensee), &true);
+        }
+
+        pub fn revoke(env: Env, listing_id: u64, licensee: Address) {
+            env.storage().persistent().remove(&(listing_id, licensee));
+        }
+
+        pub fn has_license(env: Env, listing_id: u64, licensee: Address) -> bool {
+            env.storage().persistent().has(&(listing_id, licensee))
+        }
+    }
+}
+
+mod network_mock {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockNetwork;
+
+    #[contractimpl]
+    impl MockNetwork {
+        pub fn set_live(env: Env, publisher: Address, live: bool) {
+            env.storage().persistent().set(&publisher, &live);
+        }
+
+        pub fn is_live(env: Env, publisher: Address) -> bool {
+            env.storage().persistent().get(&publisher).unwrap_or(false)
+        }
+
+        pub fn record_impression(env: Env, caller: Address, _publisher: Address) {
+            caller.require_auth();
+            let served: u64 = env.storage().instance().get(&caller).unwrap_or(0);
+            env.storage().instance().set(&caller, &(served + 1));
+        }
+
+        pub fn record_impressions(env: Env, caller: Address, _publisher: Address, count: u64) {
+            caller.require_auth();
+            let served: u64 = env.storage().instance().get(&caller).unwrap_or(0);
+            env.storage().instance().set(&caller, &(served + count));
+        }
+
+        pub fn impressions(env: Env, caller: Address) -> u64 {
+            env.storage().instance().get(&caller).unwrap_or(0)
+        }
+    }
+}
+
+mod budget_mock {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockBudgetOptimizer;
+
+    #[contractimpl]
+    impl MockBudgetOptimizer {
+        pub fn record_spend(env: Env, caller: Address, campaign_id: u64, amount: i128) {
+            caller.require_auth();
+            let spent: i128 = env.storage().instance().get(&campaign_id).unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&campaign_id, &(spent + amount));
+        }
+
+        pub fn spent(env: Env, campaign_id: u64) -> i128 {
+            env.storage().instance().get(&campaign_id).unwrap_or(0)
+        }
+    }
+}
+
+fn create_default_campaign(
+    env: &Env,
+    c: &CampaignOrchestratorContractClient<'_>,
+    token: &Address,
+    advertiser: &Address,
+) -> u64 {
+    mint(env, token, advertiser, 5_000_000);
+    c.create_campaign(
+        advertiser,
+        &1u32,
+        &1_000_000i128,
+        &100i128,
+        &1000u32,
+        &10_000u64,
+        &5_000u64,
+        &true,
+    )
+}
+
+#[test]
+fn test_list_campaigns_pages_by_cursor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let advertiser = Address::generate(&env);
+    for _ in 0..3 {
+        create_default_campaign(&env, &c, &token, &advertiser);
+    }
+
+    let (first, cursor) = c.list_campaigns(&0u64, &2u32);
+    assert_eq!(first.len(), 2);
+    assert_eq!(first.get(0).unwrap().advertiser, advertiser);
+    assert_eq!(cursor, Some(2));
+
+    let (rest, cursor) = c.list_campaigns(&2u64, &2u32);
+    assert_eq!(rest.len(), 1);
+    assert_eq!(cursor, None);
+}
+
+#[test]
+fn test_attach_licensed_creative() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let marketplace = env.register(marketplace_mock::MockMarketplace, ());
+    let market = marketplace_mock::MockMarketplaceClient::new(&env, &marketplace);
+    c.set_marketplace_contract(&admin, &marketplace);
+
+    let advertiser = Address::generate(&env);
+    let id = create_default_campaign(&env, &c, &token, &advertiser);
+    market.grant(&7u64, &advertiser);
+
+    c.attach_creative(&advertiser, &id, &7u64);
+    assert_eq!(c.get_campaign_creatives(&id).len(), 1);
+    assert!(c.verify_creative(&id, &7u64));
+    assert!(!c.verify_creative(&id, &8u64));
+
+    // A revoked license no longer verifies for publishers
+    market.revoke(&7u64, &advertiser);
+    assert!(!c.verify_creative(&id, &7u64));
+}
+
+#[test]
+fn test_attach_unlicensed_creative_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let marketplace = env.register(marketplace_mock::MockMarketplace, ());
+    c.set_marketplace_contract(&admin, &marketplace);
+
+    let advertiser = Address::generate(&env);
+    let id = create_default_campaign(&env, &c, &token, &advertiser);
+    assert_eq!(
+        c.try_attach_creative(&advertiser, &id, &7u64),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_create_campaign_with_verified_identity() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let registry = env.register(identity_mock::MockIdentityRegistry, ());
+    c.set_identity_registry(&admin, &registry);
+    let advertiser = Address::generate(&env);
+    identity_mock::MockIdentityRegistryClient::new(&env, &registry).set_verified(&advertiser);
+    assert_eq!(create_default_campaign(&env, &c, &token, &advertiser), 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #13)")]
+fn test_create_campaign_unverified_identity() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let registry = env.register(identity_mock::MockIdentityRegistry, ());
+    c.set_identity_registry(&admin, &registry);
+    create_default_campaign(&env, &c, &token, &Address::generate(&env));
+}
+
+#[test]
+fn test_record_view_live_node() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let network = env.register(network_mock::MockNetwork, ());
+    c.set_network_contract(&admin, &network);
+    let publisher = Address::generate(&env);
+    network_mock::MockNetworkClient::new(&env, &network).set_live(&publisher, &true);
+    c.verify_publisher(&admin, &publisher, &80u32);
+    let campaign_id = create_default_campaign(&env, &c, &token, &Address::generate(&env));
+
+    c.record_view(&campaign_id, &publisher);
+    assert_eq!(c.get_campaign(&campaign_id).unwrap().current_views, 1);
+    assert_eq!(
+        network_mock::MockNetworkClient::new(&env, &network).impressions(&c.address),
+        1
+    );
+}
+
+#[test]
+fn test_record_view_stale_node() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let network = env.register(network_mock::MockNetwork, ());
+    c.set_network_contract(&admin, &network);
+    let publisher = Address::generate(&env);
+    c.verify_publisher(&admin, &publisher, &80u32);
+    let campaign_id = create_default_campaign(&env, &c, &token, &Address::generate(&env));
+    assert_eq!(
+        c.try_record_view(&campaign_id, &publisher),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_record_view_reports_spend() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let optimizer = env.register(budget_mock::MockBudgetOptimizer, ());
+    c.set_budget_optimizer(&admin, &optimizer);
+    let publisher = Address::generate(&env);
+    c.verify_publisher(&admin, &publisher, &80u32);
+    let campaign_id = create_default_campaign(&env, &c, &token, &Address::generate(&env));
+
+    c.record_view(&campaign_id, &publisher);
+    c.record_view(&campaign_id, &publisher);
+    let cost_per_view = c.get_campaign(&campaign_id).unwrap().cost_per_view;
+    assert_eq!(
+        budget_mock::MockBudgetOptimizerClient::new(&env, &optimizer).spent(&campaign_id),
+        2 * cost_per_view
+    );
+}
+
+#[test]
+fn test_create_campaign_while_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let advertiser = Address::generate(&env);
+    mint(&env, &token, &advertiser, 5_000_000);
+    c.set_paused(&admin, &true);
+    assert!(c.is_paused());
+    let result = c.try_create_campaign(
+        &advertiser,
+        &1u32,
+        &1_000_000i128,
+        &100i128,
+        &1000u32,
+        &10_000u64,
+        &5_000u64,
+        &true,
+    );
+    assert_eq!(result, Err(Ok(PulsarError::Paused)));
+}
+
+#[test]
+fn test_set_paused_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, _) = setup(&env);
+    assert_eq!(
+        c.try_set_paused(&Address::generate(&env), &true),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+mod verification_mock {
+    use crate::TierParams;
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockPublisherVerification;
+
+    #[contractimpl]
+    impl MockPublisherVerification {
+        pub fn set_verified(env: Env, publisher: Address, verified: bool) {
+            env.storage().persistent().set(&publisher, &verified);
+        }
+
+        pub fn is_verified(env: Env, publisher: Address) -> bool {
+            env.storage().persistent().get(&publisher).unwrap_or(false)
+        }
+
+        pub fn record_impression(env: Env, _caller: Address, publisher: Address, earning: i128) {
+            if !Self::is_verified(env.clone(), publisher.clone()) {
+                panic!("publisher not verified");
+            }
+            let key = (publisher, 0u32);
+            let total: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            env.storage().persistent().set(&key, &(total + earning));
+        }
+
+        pub fn record_impressions(
+            env: Env,
+            caller: Address,
+            publisher: Address,
+            count: u64,
+            earning: i128,
+        ) {
+            Self::record_impression(env.clone(), caller, publisher.clone(), earning);
+            let key = (publisher, 2u32);
+            let total: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+            env.storage().persistent().set(&key, &(total + count));
+        }
+
+        pub fn impressions(env: Env, publisher: Address) -> u64 {
+            env.storage()
+                .persistent()
+                .get(&(publisher, 2u32))
+                .unwrap_or(0)
+        }
+
+        pub fn earnings(env: Env, publisher: Address) -> i128 {
+            env.storage()
+                .persistent()
+                .get(&(publisher, 0u32))
+                .unwrap_or(0)
+        }
+
+        pub fn set_tier_params(env: Env, publisher: Address, params: TierParams) {
+            env.storage().persistent().set(&(publisher, 1u32), &params);
+        }
+
+        pub fn get_tier_params(env: Env, publisher: Address) -> TierParams {
+            env.storage()
+                .persistent()
+                .get(&(publisher, 1u32))
+                .unwrap_or(TierParams {
+                    revenue_share_bonus_bps: 0,
+                    daily_cap_multiplier: 100,
+                })
+        }
+    }
+}
+
+#[test]
+fn test_record_view_checks_publisher_verification() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let verification = env.register(verification_mock::MockPublisherVerification, ());
+    c.set_publisher_verification(&admin, &verification);
+    let verifier = verification_mock::MockPublisherVerificationClient::new(&env, &verification);
+    let publisher = Address::generate(&env);
+    verifier.set_verified(&publisher, &true);
+    let campaign_id = create_default_campaign(&env, &c, &token, &Address::generate(&env));
+
+    c.record_view(&campaign_id, &publisher);
+    let cost_per_view = c.get_campaign(&campaign_id).unwrap().cost_per_view;
+    assert_eq!(verifier.earnings(&publisher), cost_per_view);
+    assert!(c.get_publisher_metrics(&publisher).is_none());
+}
+
+#[test]
+fn test_record_views_batch_counts_verification_impressions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    c.set_fraud_contract(&admin, &Address::generate(&env));
+    let verification = env.register(verification_mock::MockPublisherVerification, ());
+    c.set_publisher_verification(&admin, &verification);
+    let verifier = verification_mock::MockPublisherVerificationClient::new(&env, &verification);
+    let publisher = Address::generate(&env);
+    verifier.set_verified(&publisher, &true);
+    let campaign_id = create_default_campaign(&env, &c, &token, &Address::generate(&env));
+
+    assert!(c.record_views_batch(&campaign_id, &publisher, &12u64));
+    let cost_per_view = c.get_campaign(&campaign_id).unwrap().cost_per_view;
+    assert_eq!(verifier.impressions(&publisher), 12);
+    assert_eq!(verifier.earnings(&publisher), 12 * cost_per_view);
+}
+
+#[test]
+fn test_tier_bonus_paid_from_budget() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let verification = env.register(verification_mock::MockPublisherVerification, ());
+    c.set_publisher_verification(&admin, &verification);
+    let verifier = verification_mock::MockPublisherVerificationClient::new(&env, &verification);
+    let publisher = Address::generate(&env);
+    verifier.set_verified(&publisher, &true);
+    verifier.set_tier_params(
+        &publisher,
+        &TierParams {
+            revenue_share_bonus_bps: 1_000,
+            daily_cap_multiplier: 100,
+        },
+    );
+    let campaign_id = create_default_campaign(&env, &c, &token, &Address::generate(&env));
+    let before = c.get_campaign(&campaign_id).unwrap().remaining_budget;
+
+    c.record_view(&campaign_id, &publisher);
+
+    // 10% bonus on the 100 stroop cost per view
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&publisher), 110);
+    assert_eq!(verifier.earnings(&publisher), 110);
+    assert_eq!(
+        c.get_campaign(&campaign_id).unwrap().remaining_budget,
+        before - 110
+    );
+}
+
+#[test]
+fn test_tier_multiplier_raises_daily_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let verification = env.register(verification_mock::MockPublisherVerification, ());
+    c.set_publisher_verification(&admin, &verification);
+    let verifier = verification_mock::MockPublisherVerificationClient::new(&env, &verification);
+    let bronze = Address::generate(&env);
+    let gold = Address::generate(&env);
+    verifier.set_verified(&bronze, &true);
+    verifier.set_verified(&gold, &true);
+    verifier.set_tier_params(
+        &gold,
+        &TierParams {
+            revenue_share_bonus_bps: 0,
+            daily_cap_multiplier: 150,
+        },
+    );
+    let advertiser = Address::generate(&env);
+    mint(&env, &token, &advertiser, 5_000_000);
+    let campaign_id = c.create_campaign(
+        &advertiser,
+        &1u32,
+        &1_000_000i128,
+        &100i128,
+        &1000u32,
+        &10_000u64,
+        &2u64,
+        &true,
+    );
+
+    c.record_view(&campaign_id, &bronze);
+    c.record_view(&campaign_id, &bronze);
+    assert!(c.try_record_view(&campaign_id, &bronze).is_err());
+
+    // Gold may serve one more view before reaching 1.5x the base limit
+    c.record_view(&campaign_id, &gold);
+    assert!(c.try_record_view(&campaign_id, &gold).is_err());
+    assert_eq!(c.get_campaign(&campaign_id).unwrap().current_views, 3);
+}
+
+#[test]
+fn test_record_view_ignores_legacy_registry_once_delegated() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let publisher = Address::generate(&env);
+    c.verify_publisher(&admin, &publisher, &80u32);
+    let campaign_id = create_default_campaign(&env, &c, &token, &Address::generate(&env));
+
+    // Suspended in publisher-verification: the old local record no longer counts
+    let verification = env.register(verification_mock::MockPublisherVerification, ());
+    c.set_publisher_verification(&admin, &verification);
+    assert_eq!(
+        c.try_record_view(&campaign_id, &publisher),
+        Err(Ok(PulsarError::NotVerified))
+    );
+}
+
+#[test]
+fn test_verify_publisher_rejected_once_delegated() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, _) = setup(&env);
+    let verification = env.register(verification_mock::MockPublisherVerification, ());
+    c.set_publisher_verification(&admin, &verification);
+    assert_eq!(
+        c.try_verify_publisher(&admin, &Address::generate(&env), &80u32),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+mod kyc_mock {
+    use super::{KycLevel, KycRecord};
+    use soroban_sdk::{contract, contractimpl, Address, Env, String};
+
+    #[contract]
+    pub struct MockKycRegistry;
+
+    #[contractimpl]
+    impl MockKycRegistry {
+        pub fn set_record(env: Env, account: Address, level: KycLevel, jurisdiction: String) {
+            let record = KycRecord {
+                account: account.clone(),
+                level,
+                provider: String::from_str(&env, "provider"),
+                document_hash: String::from_str(&env, "hash"),
+                jurisdiction,
+                verified: true,
+                submitted_at: 0,
+                verified_at: Some(0),
+                expires_at: None,
+                verifier: None,
+            };
+            env.storage().persistent().set(&account, &record);
+        }
+
+        pub fn get_kyc_record(env: Env, account: Address) -> Option<KycRecord> {
+            env.storage().persistent().get(&account)
+        }
+    }
+}
+
+fn setup_compliance(
+    env: &Env,
+    c: &CampaignOrchestratorContractClient<'_>,
+    admin: &Address,
+    level: KycLevel,
+) -> Address {
+    let kyc = env.register(kyc_mock::MockKycRegistry, ());
+    c.set_kyc_registry(admin, &kyc);
+    c.set_compliance_rule(
+        admin,
+        &String::from_str(env, "DE"),
+        &ComplianceRule {
+            banned_campaign_types: soroban_sdk::vec![env, 2u32],
+            required_kyc_level: KycLevel::Standard,
+            max_budget: 1_000_000,
+        },
+    );
+    let advertiser = Address::generate(env);
+    kyc_mock::MockKycRegistryClient::new(env, &kyc).set_record(
+        &advertiser,
+        &level,
+        &String::from_str(env, "DE"),
+    );
+    advertiser
+}
+
+#[test]
+fn test_create_campaign_within_jurisdiction_rule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let advertiser = setup_compliance(&env, &c, &admin, KycLevel::Enhanced);
+    assert_eq!(create_default_campaign(&env, &c, &token, &advertiser), 1);
+    assert!(c
+        .get_compliance_rule(&String::from_str(&env, "DE"))
+        .is_some());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #13)")]
+fn test_create_campaign_kyc_level_too_low() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let advertiser = setup_compliance(&env, &c, &admin, KycLevel::Basic);
+    create_default_campaign(&env, &c, &token, &advertiser);
+}
+
+#[test]
+fn test_create_campaign_over_jurisdiction_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let advertiser = setup_compliance(&env, &c, &admin, KycLevel::Standard);
+    mint(&env, &token, &advertiser, 5_000_000);
+    let result = c.try_create_campaign(
+        &advertiser,
+        &1u32,
+        &2_000_000i128,
+        &100i128,
+        &1000u32,
+        &10_000u64,
+        &5_000u64,
+        &true,
+    );
+    assert_eq!(result, Err(Ok(PulsarError::LimitExceeded)));
+}
+
+#[test]
+fn test_create_campaign_banned_type() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let advertiser = setup_compliance(&env, &c, &admin, KycLevel::Standard);
+    mint(&env, &token, &advertiser, 5_000_000);
+    let result = c.try_create_campaign(
+        &advertiser,
+        &2u32,
+        &1_000_000i128,
+        &100i128,
+        &1000u32,
+        &10_000u64,
+        &5_000u64,
+        &true,
+    );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidState)));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #13)")]
+fn test_create_campaign_without_kyc_record() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    setup_compliance(&env, &c, &admin, KycLevel::Enhanced);
+    create_default_campaign(&env, &c, &token, &Address::generate(&env));
+}
+
+mod targeting_mock {
+    use super::{TargetingConfig, TargetingParams};
+    use soroban_sdk::{contract, contractimpl, Address, Env, String};
+
+    #[contract]
+    pub struct MockTargeting;
+
+    #[contractimpl]
+    impl MockTargeting {
+        pub fn set_targeting(
+            env: Env,
+            advertiser: Address,
+            campaign_id: u64,
+            params: TargetingParams,
+        ) {
+            advertiser.require_auth();
+            let config = TargetingConfig {
+                campaign_id,
+                advertiser,
+                geographic_targets: params.geographic_targets,
+                interest_segments: params.interest_segments,
+                excluded_segments: params.excluded_segments,
+                min_age: params.min_age,
+                max_age: params.max_age,
+                device_types: params.device_types,
+                operating_systems: String::from_str(&env, ""),
+                languages: params.languages,
+                min_reputation_score: params.min_reputation,
+                exclude_fraud: params.exclude_fraud,
+                require_kyc: params.require_kyc,
+                max_cpm: params.max_cpm,
+                created_at: 0,
+                last_updated: 0,
+            };
+            env.storage().persistent().set(&campaign_id, &config);
+        }
+
+        pub fn get_targeting(env: Env, campaign_id: u64) -> Option<TargetingConfig> {
+            env.storage().persistent().get(&campaign_id)
+        }
+    }
+}
+
+fn targeting_params(env: &Env) -> TargetingParams {
+    TargetingParams {
+        geographic_targets: String::from_str(env, "[\"DE\",\"FR\"]"),
+        interest_segments: String::from_str(env, "sports"),
+        excluded_segments: String::from_str(env, ""),
+        min_age: 18,
+        max_age: 35,
+        device_types: String::from_str(env, "mobile"),
+        languages: String::from_str(env, "en"),
+        min_reputation: 400,
+        exclude_fraud: true,
+        require_kyc: false,
+        max_cpm: 5_000,
+    }
+}
+
+#[test]
+fn test_clone_campaign_copies_settings_and_targeting() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let targeting_id = env.register(targeting_mock::MockTargeting, ());
+    let targeting = targeting_mock::MockTargetingClient::new(&env, &targeting_id);
+    c.set_targeting_contract(&admin, &targeting_id);
+
+    let advertiser = Address::generate(&env);
+    let source_id = create_default_campaign(&env, &c, &token, &advertiser);
+    targeting.set_targeting(&advertiser, &source_id, &targeting_params(&env));
+
+    let clone_id = c.clone_campaign(
+        &advertiser,
+        &source_id,
+        &CampaignOverrides {
+            cost_per_view: Some(200),
+            duration: None,
+            target_views: None,
+            daily_view_limit: Some(1_000),
+            refundable: None,
+        },
+        &2_000_000i128,
+    );
+
+    let source = c.get_campaign(&source_id).unwrap();
+    let clone = c.get_campaign(&clone_id).unwrap();
+    assert_eq!(clone.budget, 2_000_000);
+    assert_eq!(clone.remaining_budget, 2_000_000);
+    assert_eq!(clone.cost_per_view, 200);
+    assert_eq!(clone.daily_view_limit, 1_000);
+    assert_eq!(clone.target_views, source.target_views);
+    assert_eq!(clone.campaign_type, source.campaign_type);
+    assert_eq!(
+        clone.end_ledger - clone.start_ledger,
+        source.end_ledger - source.start_ledger
+    );
+    let copied = targeting.get_targeting(&clone_id).unwrap();
+    assert_eq!(copied.min_age, 18);
+    assert_eq!(copied.min_reputation_score, 400);
+    assert_eq!(copied.advertiser, advertiser);
+}
+
+#[test]
+fn test_clone_campaign_of_other_advertiser_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let source_id = create_default_campaign(&env, &c, &token, &Address::generate(&env));
+    let other = Address::generate(&env);
+    mint(&env, &token, &other, 5_000_000);
+    let result = c.try_clone_campaign(
+        &other,
+        &source_id,
+        &CampaignOverrides {
+            cost_per_view: None,
+            duration: None,
+            target_views: None,
+            daily_view_limit: None,
+            refundable: None,
+        },
+        &1_000_000i128,
+    );
+    assert_eq!(result, Err(Ok(PulsarError::Unauthorized)));
+}
+
+#[test]
+fn test_create_from_template() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let targeting_id = env.register(targeting_mock::MockTargeting, ());
+    c.set_targeting_contract(&admin, &targeting_id);
+
+    let advertiser = Address::generate(&env);
+    let name = String::from_str(&env, "monthly-promo");
+    c.save_template(
+        &advertiser,
+        &name,
+        &CampaignTemplate {
+            campaign_type: 1,
+            cost_per_view: 150,
+            duration: 500,
+            target_views: 8_000,
+            daily_view_limit: 400,
+            refundable: false,
+        },
+        &Some(targeting_params(&env)),
+    );
+    assert!(c.get_template(&advertiser, &name).is_some());
+
+    mint(&env, &token, &advertiser, 5_000_000);
+    let first = c.create_from_template(&advertiser, &name, &1_000_000i128);
+    let second = c.create_from_template(&advertiser, &name, &1_500_000i128);
+    assert_ne!(first, second);
+
+    let campaign = c.get_campaign(&second).unwrap();
+    assert_eq!(campaign.budget, 1_500_000);
+    assert_eq!(campaign.cost_per_view, 150);
+    assert_eq!(campaign.end_ledger - campaign.start_ledger, 500);
+    assert!(!campaign.refundable);
+    assert!(
+        targeting_mock::MockTargetingClient::new(&env, &targeting_id)
+            .get_targeting(&second)
+            .is_some()
+    );
+
+    c.delete_template(&advertiser, &name);
+    assert!(c.get_template(&advertiser, &name).is_none());
+    assert!(c.get_template_targeting(&advertiser, &name).is_none());
+}
+
+#[test]
+fn test_create_from_missing_template_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let advertiser = Address::generate(&env);
+    mint(&env, &token, &advertiser, 5_000_000);
+    let result = c.try_create_from_template(
+        &advertiser,
+        &String::from_str(&env, "missing"),
+        &1_000_000i128,
+    );
+    assert_eq!(result, Err(Ok(PulsarError::NotFound)));
+}
+
+#[test]
+fn test_record_views_batch_accrues_publisher_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let fraud = Address::generate(&env);
+    c.set_fraud_contract(&admin, &fraud);
+    let network = env.register(network_mock::MockNetwork, ());
+    c.set_network_contract(&admin, &network);
+    let optimizer = env.register(budget_mock::MockBudgetOptimizer, ());
+    c.set_budget_optimizer(&admin, &optimizer);
+    let publisher = Address::generate(&env);
+    network_mock::MockNetworkClient::new(&env, &network).set_live(&publisher, &true);
+    c.verify_publisher(&admin, &publisher, &80u32);
+    let campaign_id = create_default_campaign(&env, &c, &token, &Address::generate(&env));
+
+    c.record_views_batch(&campaign_id, &publisher, &25u64);
+
+    let campaign = c.get_campaign(&campaign_id).unwrap();
+    let total = 25 * campaign.cost_per_view;
+    assert_eq!(campaign.current_views, 25);
+    assert_eq!(campaign.remaining_budget, 1_000_000 - total);
+    assert_eq!(c.get_publisher_balance(&publisher), total);
+    assert_eq!(
+        budget_mock::MockBudgetOptimizerClient::new(&env, &optimizer).spent(&campaign_id),
+        total
+    );
+    assert_eq!(
+        network_mock::MockNetworkClient::new(&env, &network).impressions(&c.address),
+        25
+    );
+
+    // Nothing is paid until the publisher claims
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&publisher), 0);
+    assert_eq!(c.claim_publisher_earnings(&publisher), total);
+    assert_eq!(token_client.balance(&publisher), total);
+    assert_eq!(c.get_publisher_balance(&publisher), 0);
+}
+
+#[test]
+fn test_record_views_batch_respects_daily_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    c.set_fraud_contract(&admin, &Address::generate(&env));
+    let publisher = Address::generate(&env);
+    c.verify_publisher(&admin, &publisher, &80u32);
+    let campaign_id = create_default_campaign(&env, &c, &token, &Address::generate(&env));
+
+    c.record_views_batch(&campaign_id, &publisher, &4_000u64);
+    assert_eq!(
+        c.try_record_views_batch(&campaign_id, &publisher, &1_001u64),
+        Err(Ok(PulsarError::LimitExceeded))
+    );
+}
+
+#[test]
+fn test_record_views_batch_requires_fraud_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let publisher = Address::generate(&env);
+    c.verify_publisher(&admin, &publisher, &80u32);
+    let campaign_id = create_default_campaign(&env, &c, &token, &Address::generate(&env));
+    assert_eq!(
+        c.try_record_views_batch(&campaign_id, &publisher, &10u64),
+        Err(Ok(PulsarError::NotInitialized))
+    );
+}
+
+#[test]
+fn test_claim_publisher_earnings_empty() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, _) = setup(&env);
+    assert_eq!(
+        c.try_claim_publisher_earnings(&Address::generate(&env)),
+        Err(Ok(PulsarError::InsufficientBalance))
+    );
+}
+
+#[test]
+fn test_velocity_guard_pauses_new_campaign_at_floor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    c.set_velocity_guard(
+        &admin,
+        &VelocityGuard {
+            multiplier: 3,
+            min_hourly_views: 5,
+        },
+    );
+    let publisher = Address::generate(&env);
+    c.verify_publisher(&admin, &publisher, &80u32);
+    let campaign_id = create_default_campaign(&env, &c, &token, &Address::generate(&env));
+
+    for _ in 0..5 {
+        c.record_view(&campaign_id, &publisher);
+    }
+    assert_eq!(c.get_hourly_views(&campaign_id), 5);
+
+    // The sixth view trips the guard: campaign paused, view not paid
+    c.record_view(&campaign_id, &publisher);
+    let campaign = c.get_campaign(&campaign_id).unwrap();
+    assert!(matches!(campaign.status, CampaignStatus::Paused));
+    assert_eq!(campaign.current_views, 5);
+    assert_eq!(
+        token::Client::new(&env, &token).balance(&publisher),
+        5 * campaign.cost_per_view
+    );
+}
+
+#[test]
+fn test_velocity_guard_uses_average_hourly_rate() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    c.set_velocity_guard(
+        &admin,
+        &VelocityGuard {
+            multiplier: 3,
+            min_hourly_views: 10,
+        },
+    );
+    let publisher = Address::generate(&env);
+    c.verify_publisher(&admin, &publisher, &80u32);
+    c.set_fraud_contract(&admin, &Address::generate(&env));
+    let campaign_id = create_default_campaign(&env, &c, &token, &Address::generate(&env));
+
+    for _ in 0..10 {
+        c.record_view(&campaign_id, &publisher);
+    }
+    // Two hours in: average 5 views/hour, so 15 are allowed this hour
+    env.ledger().with_mut(|li| li.timestamp += 2 * 3_600);
+    assert!(c.record_views_batch(&campaign_id, &publisher, &15u64));
+    assert_eq!(c.get_hourly_views(&campaign_id), 15);
+    c.record_view(&campaign_id, &publisher);
+
+    let campaign = c.get_campaign(&campaign_id).unwrap();
+    assert!(matches!(campaign.status, CampaignStatus::Paused));
+    assert_eq!(campaign.current_views, 25);
+}
+
+#[test]
+fn test_record_views_batch_reports_velocity_rejection() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    c.set_velocity_guard(
+        &admin,
+        &VelocityGuard {
+            multiplier: 3,
+            min_hourly_views: 10,
+        },
+    );
+    let publisher = Address::generate(&env);
+    c.verify_publisher(&admin, &publisher, &80u32);
+    c.set_fraud_contract(&admin, &Address::generate(&env));
+    let campaign_id = create_default_campaign(&env, &c, &token, &Address::generate(&env));
+
+    assert!(!c.record_views_batch(&campaign_id, &publisher, &11u64));
+    let campaign = c.get_campaign(&campaign_id).unwrap();
+    assert!(matches!(campaign.status, CampaignStatus::Paused));
+    assert_eq!(campaign.current_views, 0);
+    assert_eq!(c.get_publisher_balance(&publisher), 0);
+}
+
+#[test]
+fn test_allow_list_restricts_publishers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let partner = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    c.verify_publisher(&admin, &partner, &80u32);
+    c.verify_publisher(&admin, &outsider, &80u32);
+    let advertiser = Address::generate(&env);
+    let campaign_id = create_default_campaign(&env, &c, &token, &advertiser);
+
+    c.set_publisher_list(
+        &advertiser,
+        &campaign_id,
+        &PublisherListMode::Allow,
+        &soroban_sdk::vec![&env, partner.clone()],
+    );
+    c.record_view(&campaign_id, &partner);
+    assert!(c.try_record_view(&campaign_id, &outsider).is_err());
+
+    // Opening the campaign clears the list
+    c.set_publisher_list(
+        &advertiser,
+        &campaign_id,
+        &PublisherListMode::Open,
+        &soroban_sdk::vec![&env],
+    );
+    assert!(c.get_publisher_list(&campaign_id).is_none());
+    c.record_view(&campaign_id, &outsider);
+    assert_eq!(c.get_campaign(&campaign_id).unwrap().current_views, 2);
+}
+
+#[test]
+fn test_deny_list_blocks_publisher() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let publisher = Address::generate(&env);
+    c.verify_publisher(&admin, &publisher, &80u32);
+    let advertiser = Address::generate(&env);
+    let campaign_id = create_default_campaign(&env, &c, &token, &advertiser);
+
+    c.set_publisher_list(
+        &advertiser,
+        &campaign_id,
+        &PublisherListMode::Deny,
+        &soroban_sdk::vec![&env, publisher.clone()],
+    );
+    assert_eq!(
+        c.try_record_view(&campaign_id, &publisher),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_publisher_list_advertiser_only() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let campaign_id = create_default_campaign(&env, &c, &token, &Address::generate(&env));
+    let result = c.try_set_publisher_list(
+        &Address::generate(&env),
+        &campaign_id,
+        &PublisherListMode::Deny,
+        &soroban_sdk::vec![&env],
+    );
+    assert_eq!(result, Err(Ok(PulsarError::Unauthorized)));
+}
+
+mod fraud_mock {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockFraud;
+
+    #[contractimpl]
+    impl MockFraud {
+        pub fn set_publisher_verified_views(
+            env: Env,
+            campaign_id: u64,
+            publisher: Address,
+            views: u64,
+        ) {
+            env.storage()
+                .persistent()
+                .set(&(campaign_id, publisher), &views);
+        }
+
+        pub fn get_publisher_verified_views(env: Env, campaign_id: u64, publisher: Address) -> u64 {
+            env.storage()
+                .persistent()
+                .get(&(campaign_id, publisher))
+                .unwrap_or(0)
+        }
+    }
+}
+
+fn create_bonus_campaign(
+    env: &Env,
+    c: &CampaignOrchestratorContractClient<'_>,
+    token: &Address,
+    advertiser: &Address,
+    top_n: u32,
+) -> u64 {
+    mint(env, token, advertiser, 5_000_000);
+    c.create_campaign_with_bonus(
+        advertiser,
+        &1u32,
+        &1_000_000i128,
+        &100i128,
+        &1000u32,
+        &30u64,
+        &5_000u64,
+        &true,
+        &1_000i128,
+        &top_n,
+    )
+}
+
+#[test]
+fn test_bonus_pool_split_among_top_publishers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let fraud = env.register(fraud_mock::MockFraud, ());
+    c.set_fraud_contract(&admin, &fraud);
+    let fraud_client = fraud_mock::MockFraudClient::new(&env, &fraud);
+    let advertiser = Address::generate(&env);
+    let campaign_id = create_bonus_campaign(&env, &c, &token, &advertiser, 2);
+    assert_eq!(c.get_bonus_pool(&campaign_id).unwrap().amount, 1_000);
+
+    let top = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    for (publisher, served, verified) in [(&third, 5u64, 2u64), (&top, 15, 12), (&second, 10, 6)] {
+        c.verify_publisher(&admin, publisher, &80u32);
+        c.record_views_batch(&campaign_id, publisher, &served);
+        fraud_client.set_publisher_verified_views(&campaign_id, publisher, &verified);
+    }
+    assert!(matches!(
+        c.get_campaign(&campaign_id).unwrap().status,
+        CampaignStatus::Completed
+    ));
+
+    c.distribute_bonus(&campaign_id);
+    assert!(c.get_bonus_pool(&campaign_id).unwrap().distributed);
+    // 1_000 * 12/18 = 666 and 1_000 * 6/18 = 333; the dust goes to the top publisher
+    assert_eq!(c.get_bonus_claim(&campaign_id, &top), 667);
+    assert_eq!(c.get_bonus_claim(&campaign_id, &second), 333);
+    assert_eq!(c.get_bonus_claim(&campaign_id, &third), 0);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(c.claim_bonus(&top, &campaign_id), 667);
+    assert_eq!(token_client.balance(&top), 667);
+    assert_eq!(c.get_bonus_claim(&campaign_id, &top), 0);
+}
+
+#[test]
+fn test_bonus_weight_capped_at_paid_views() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let fraud = env.register(fraud_mock::MockFraud, ());
+    c.set_fraud_contract(&admin, &fraud);
+    let fraud_client = fraud_mock::MockFraudClient::new(&env, &fraud);
+    let campaign_id = create_bonus_campaign(&env, &c, &token, &Address::generate(&env), 2);
+
+    let inflated = Address::generate(&env);
+    let honest = Address::generate(&env);
+    for (publisher, served, verified) in [(&inflated, 20u64, 1_000u64), (&honest, 10, 10)] {
+        c.verify_publisher(&admin, publisher, &80u32);
+        c.record_views_batch(&campaign_id, publisher, &served);
+        fraud_client.set_publisher_verified_views(&campaign_id, publisher, &verified);
+    }
+
+    // The inflated publisher's 1_000 verified views only weigh the 20 it was paid for
+    c.distribute_bonus(&campaign_id);
+    assert_eq!(c.get_bonus_claim(&campaign_id, &inflated), 667);
+    assert_eq!(c.get_bonus_claim(&campaign_id, &honest), 333);
+}
+
+#[test]
+fn test_distribute_bonus_before_completion() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    c.set_fraud_contract(&admin, &env.register(fraud_mock::MockFraud, ()));
+    let campaign_id = create_bonus_campaign(&env, &c, &token, &Address::generate(&env), 3);
+
+    assert_eq!(
+        c.try_distribute_bonus(&campaign_id),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_cancel_campaign_refunds_bonus_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let advertiser = Address::generate(&env);
+    let campaign_id = create_bonus_campaign(&env, &c, &token, &advertiser, 3);
+    let token_client = token::Client::new(&env, &token);
+    let before = token_client.balance(&advertiser);
+
+    c.cancel_campaign(&advertiser, &campaign_id);
+
+    assert_eq!(
+        token_client.balance(&advertiser),
+        before + 1_000_000 + 1_000
+    );
+    assert!(c.get_bonus_pool(&campaign_id).unwrap().distributed);
+}
+
+#[test]
+fn test_cancel_after_completion_keeps_bonus_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    c.set_fraud_contract(&admin, &env.register(fraud_mock::MockFraud, ()));
+    let advertiser = Address::generate(&env);
+    let campaign_id = create_bonus_campaign(&env, &c, &token, &advertiser, 3);
+    let publisher = Address::generate(&env);
+    c.verify_publisher(&admin, &publisher, &80u32);
+    c.record_views_batch(&campaign_id, &publisher, &30u64);
+    let token_client = token::Client::new(&env, &token);
+    let before = token_client.balance(&advertiser);
+
+    // Only the leftover budget comes back; the bonus stays for publishers
+    c.cancel_campaign(&advertiser, &campaign_id);
+    let campaign = c.get_campaign(&campaign_id).unwrap();
+    assert!(matches!(campaign.status, CampaignStatus::Completed));
+    assert_eq!(
+        token_client.balance(&advertiser),
+        before + 1_000_000 - 3_000
+    );
+    assert!(!c.get_bonus_pool(&campaign_id).unwrap().distributed);
+    assert_eq!(
+        c.try_refund_bonus(&campaign_id),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_refund_bonus_after_campaign_ends_short() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let advertiser = Address::generate(&env);
+    let campaign_id = create_bonus_campaign(&env, &c, &token, &advertiser, 3);
+    assert_eq!(
+        c.try_refund_bonus(&campaign_id),
+        Err(Ok(PulsarError::TooEarly))
+    );
+
+    let token_client = token::Client::new(&env, &token);
+    let before = token_client.balance(&advertiser);
+    let end_ledger = c.get_campaign(&campaign_id).unwrap().end_ledger;
+    env.ledger()
+        .with_mut(|li| li.sequence_number = end_ledger + 1);
+
+    assert_eq!(c.refund_bonus(&campaign_id), 1_000);
+    assert_eq!(token_client.balance(&advertiser), before + 1_000);
+    assert!(c.get_bonus_pool(&campaign_id).unwrap().distributed);
+    assert_eq!(
+        c.try_refund_bonus(&campaign_id),
+        Err(Ok(PulsarError::AlreadyExists))
+    );
+}
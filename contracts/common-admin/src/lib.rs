@@ -1,5 +1,23 @@
 #![no_std]
-use soroban_sdk::{Address, Env, IntoVal, TryFromVal, Val};
+use pulsar_common_errors::PulsarError;
+use soroban_sdk::{symbol_short, Address, BytesN, Env, IntoVal, Symbol, TryFromVal, Val, Vec};
+
+/// Version carried as the third topic of every suite event. Bump it when any
+/// event's data tuple changes shape so indexers can tell layouts apart.
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// Most ids a single `page` call scans
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// Publish a suite event with topics `(contract, action, EVENT_SCHEMA_VERSION)`.
+/// `contract` is the emitting contract's namespace, e.g. "escrow".
+pub fn publish_event<D>(env: &Env, contract: Symbol, action: Symbol, data: D)
+where
+    D: IntoVal<Env, Val>,
+{
+    env.events()
+        .publish((contract, action, EVENT_SCHEMA_VERSION), data);
+}
 
 pub fn propose_admin<K>(
     env: &Env,
@@ -7,15 +25,21 @@ pub fn propose_admin<K>(
     pending_key: &K,
     current_admin: Address,
     new_admin: Address,
-) where
+) -> Result<(), PulsarError>
+where
     K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
 {
     current_admin.require_auth();
-    let stored: Address = env.storage().instance().get(admin_key).expect("not initialized");
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(admin_key)
+        .ok_or(PulsarError::NotInitialized)?;
     if current_admin != stored {
-        panic!("unauthorized");
+        return Err(PulsarError::Unauthorized);
     }
     env.storage().instance().set(pending_key, &new_admin);
+    Ok(())
 }
 
 pub fn accept_admin<K>(
@@ -23,7 +47,8 @@ pub fn accept_admin<K>(
     admin_key: &K,
     pending_key: &K,
     new_admin: Address,
-) where
+) -> Result<(), PulsarError>
+where
     K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
 {
     new_admin.require_auth();
@@ -31,10 +56,123 @@ pub fn accept_admin<K>(
         .storage()
         .instance()
         .get(pending_key)
-        .expect("no pending admin");
+        .ok_or(PulsarError::NotFound)?;
     if new_admin != pending {
-        panic!("not pending admin");
+        return Err(PulsarError::Unauthorized);
     }
     env.storage().instance().set(admin_key, &new_admin);
     env.storage().instance().remove(pending_key);
+    Ok(())
+}
+
+/// Swap the contract's code in place, keeping its storage. Point the admin at
+/// a governance-dao or timelock-executor address to put upgrades behind a
+/// vote or a delay.
+pub fn upgrade<K>(
+    env: &Env,
+    admin_key: &K,
+    admin: Address,
+    new_wasm_hash: BytesN<32>,
+) -> Result<(), PulsarError>
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(admin_key)
+        .ok_or(PulsarError::NotInitialized)?;
+    if admin != stored {
+        return Err(PulsarError::Unauthorized);
+    }
+    env.deployer()
+        .update_current_contract_wasm(new_wasm_hash.clone());
+    publish_event(env, symbol_short!("admin"), symbol_short!("upgraded"), new_wasm_hash);
+    Ok(())
+}
+
+/// Emergency stop shared by money-moving contracts. Each contract stores the
+/// flag under its own `DataKey::Paused` in instance storage.
+pub fn set_paused<K>(
+    env: &Env,
+    admin_key: &K,
+    paused_key: &K,
+    admin: Address,
+    paused: bool,
+) -> Result<(), PulsarError>
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(admin_key)
+        .ok_or(PulsarError::NotInitialized)?;
+    if admin != stored {
+        return Err(PulsarError::Unauthorized);
+    }
+    write_paused(env, paused_key, paused);
+    Ok(())
+}
+
+/// Store the flag and emit the event, for contracts that authorize pausing
+/// through their own role check
+pub fn write_paused<K>(env: &Env, paused_key: &K, paused: bool)
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    env.storage().instance().set(paused_key, &paused);
+    publish_event(env, symbol_short!("admin"), symbol_short!("paused"), paused);
+}
+
+pub fn is_paused<K>(env: &Env, paused_key: &K) -> bool
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    env.storage().instance().get(paused_key).unwrap_or(false)
+}
+
+pub fn require_not_paused<K>(env: &Env, paused_key: &K) -> Result<(), PulsarError>
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    if is_paused(env, paused_key) {
+        return Err(PulsarError::Paused);
+    }
+    Ok(())
+}
+
+/// Shared cursor convention for `list_*` views over sequential ids 1..=counter.
+/// `start` is the last id the caller has seen (0 for the first page); up to
+/// `limit` ids after it are scanned and those `load` returns are collected.
+/// The cursor returned is the `start` of the next page, or `None` once the
+/// scan reached `counter`. Filtered lists may return short or empty pages
+/// before the end, so callers loop until the cursor is `None`.
+pub fn page<T, F>(
+    env: &Env,
+    counter: u64,
+    start: u64,
+    limit: u32,
+    mut load: F,
+) -> (Vec<T>, Option<u64>)
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    F: FnMut(u64) -> Option<T>,
+{
+    let mut items = Vec::new(env);
+    if start >= counter {
+        return (items, None);
+    }
+    let end = counter.min(start.saturating_add(limit.min(MAX_PAGE_SIZE) as u64));
+    let mut id = start + 1;
+    while id <= end {
+        if let Some(item) = load(id) {
+            items.push_back(item);
+        }
+        id += 1;
+    }
+    let next = if end < counter { Some(end) } else { None };
+    (items, next)
 }
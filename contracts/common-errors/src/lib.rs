@@ -0,0 +1,25 @@
+#![no_std]
+use soroban_sdk::contracterror;
+
+/// Error codes shared across the suite so SDKs and indexers can decode any
+/// contract's failures the same way. Codes are stable: append new variants,
+/// never renumber or reuse one.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PulsarError {
+    Unauthorized = 1,
+    NotInitialized = 2,
+    AlreadyInitialized = 3,
+    NotFound = 4,
+    InvalidAmount = 5,
+    Expired = 6,
+    InvalidState = 7,
+    LimitExceeded = 8,
+    Paused = 9,
+    InsufficientBalance = 10,
+    AlreadyExists = 11,
+    InvalidInput = 12,
+    NotVerified = 13,
+    TooEarly = 14,
+}
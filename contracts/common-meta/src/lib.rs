@@ -0,0 +1,182 @@
+#![no_std]
+//! Signed "execute on behalf" support shared across the suite. A user signs
+//! `message(...)` with their account's ed25519 key and a relayer submits the
+//! `*_signed` entrypoint, paying the transaction fee. The account acted for
+//! is the G-address of the signing key.
+//!
+//! A raw ed25519 signature knows nothing about the account's signer weights,
+//! thresholds or a disabled master key, so relaying is opt-in: the account
+//! enables its key through `set_enabled`, which goes through the account's
+//! own `require_auth` and therefore its full signer configuration. Once
+//! enabled, that key alone authorizes relayed calls to the adopting contract
+//! regardless of later threshold changes; an account rotating or
+//! down-weighting the key must disable it here as well.
+//!
+//! Nonces are sequential per signing key and live in the adopting contract's
+//! persistent storage under `MetaKey`, so a signature is only ever good for
+//! one contract, one action and one call.
+use pulsar_common_admin::publish_event;
+use pulsar_common_errors::PulsarError;
+use soroban_sdk::{contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol};
+
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
+const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
+
+/// Strkey version byte for ed25519 account ids ("G...")
+const ACCOUNT_VERSION_BYTE: u8 = 6 << 3;
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+#[contracttype]
+#[derive(Clone)]
+pub struct MetaAuth {
+    pub signer: BytesN<32>, // ed25519 public key of the account
+    pub nonce: u64,
+    pub expiry: u64, // ledger timestamp after which the signature is void
+    pub signature: BytesN<64>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum MetaKey {
+    Nonce(BytesN<32>),
+    Enabled(BytesN<32>), // present while the key's account accepts relayed calls
+}
+
+/// Opt the account of `signer` in or out of relayed calls to the adopting
+/// contract. Requires the account's authorization.
+pub fn set_enabled(env: &Env, signer: &BytesN<32>, enabled: bool) {
+    let account = account_address(env, signer);
+    account.require_auth();
+
+    let key = MetaKey::Enabled(signer.clone());
+    if enabled {
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+    publish_event(
+        env,
+        symbol_short!("meta"),
+        symbol_short!("enabled"),
+        (account, enabled),
+    );
+}
+
+/// Whether the account of `signer` accepts relayed calls
+pub fn is_enabled(env: &Env, signer: &BytesN<32>) -> bool {
+    env.storage()
+        .persistent()
+        .has(&MetaKey::Enabled(signer.clone()))
+}
+
+/// Next nonce `signer` must use with the adopting contract
+pub fn nonce(env: &Env, signer: &BytesN<32>) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&MetaKey::Nonce(signer.clone()))
+        .unwrap_or(0)
+}
+
+/// Digest the user signs: sha256 over the contract address, the action name,
+/// the XDR of the call's arguments, the nonce and the expiry.
+pub fn message<P>(env: &Env, action: &Symbol, payload: P, nonce: u64, expiry: u64) -> BytesN<32>
+where
+    P: ToXdr,
+{
+    let mut data = env.current_contract_address().to_xdr(env);
+    data.append(&action.clone().to_xdr(env));
+    data.append(&payload.to_xdr(env));
+    data.extend_from_array(&nonce.to_be_bytes());
+    data.extend_from_array(&expiry.to_be_bytes());
+    env.crypto().sha256(&data).into()
+}
+
+/// Check a relayed call and consume its nonce. Returns the account the call
+/// acts for. A key whose account has not opted in, a stale expiry or a stale
+/// nonce is returned as an error; a bad signature still aborts the invocation.
+pub fn verify<P>(
+    env: &Env,
+    action: Symbol,
+    payload: P,
+    auth: &MetaAuth,
+) -> Result<Address, PulsarError>
+where
+    P: ToXdr,
+{
+    if !is_enabled(env, &auth.signer) {
+        return Err(PulsarError::Unauthorized);
+    }
+    if env.ledger().timestamp() > auth.expiry {
+        return Err(PulsarError::Expired);
+    }
+    let expected = nonce(env, &auth.signer);
+    if auth.nonce != expected {
+        return Err(PulsarError::InvalidInput);
+    }
+
+    let digest = message(env, &action, payload, auth.nonce, auth.expiry);
+    env.crypto()
+        .ed25519_verify(&auth.signer, &Bytes::from(digest), &auth.signature);
+
+    let key = MetaKey::Nonce(auth.signer.clone());
+    env.storage().persistent().set(&key, &(expected + 1));
+    env.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+
+    let account = account_address(env, &auth.signer);
+    publish_event(
+        env,
+        symbol_short!("meta"),
+        symbol_short!("relayed"),
+        (account.clone(), action, auth.nonce),
+    );
+    Ok(account)
+}
+
+/// The G-address whose signing key is `public_key`
+pub fn account_address(env: &Env, public_key: &BytesN<32>) -> Address {
+    let mut raw = [0u8; 35];
+    raw[0] = ACCOUNT_VERSION_BYTE;
+    raw[1..33].copy_from_slice(&public_key.to_array());
+    let crc = crc16_xmodem(&raw[..33]);
+    raw[33..].copy_from_slice(&crc.to_le_bytes());
+
+    // 35 bytes -> 56 base32 characters, no padding
+    let mut strkey = [0u8; 56];
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    let mut out = 0;
+    for byte in raw {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            strkey[out] = BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize];
+            out += 1;
+        }
+    }
+    Address::from_string_bytes(&Bytes::from_array(env, &strkey))
+}
+
+fn crc16_xmodem(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
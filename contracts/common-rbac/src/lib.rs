@@ -0,0 +1,143 @@
+#![no_std]
+//! Role-based access control shared across the suite. Grants live in the
+//! adopting contract's persistent storage under `RoleKey`. The address stored
+//! at the contract's admin key holds every role implicitly, so admin
+//! handover through pulsar-common-admin keeps working unchanged.
+//!
+//! Oracle permissions live in the oracle-registry contract instead; the
+//! `OracleDomain` enum and `registry_allows` are shared here so every
+//! consumer encodes the same domain values.
+use pulsar_common_admin::publish_event;
+use pulsar_common_errors::PulsarError;
+use soroban_sdk::{
+    contracttype, symbol_short, Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
+};
+
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
+const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Role {
+    Admin,
+    Operator,
+    Oracle,
+    Reviewer,
+    Pauser,
+}
+
+/// Areas an oracle can be permitted to report on in the oracle registry
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OracleDomain {
+    Fraud,
+    Budget,
+    Anomaly,
+    Reputation,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum RoleKey {
+    Role(Role, Address),
+}
+
+/// Admin-role holders (including the stored admin) may grant any role
+pub fn grant_role<K>(
+    env: &Env,
+    admin_key: &K,
+    granter: Address,
+    role: Role,
+    account: Address,
+) -> Result<(), PulsarError>
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    require_role(env, admin_key, Role::Admin, &granter)?;
+    let key = RoleKey::Role(role, account.clone());
+    env.storage().persistent().set(&key, &true);
+    env.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+    publish_event(
+        env,
+        symbol_short!("rbac"),
+        symbol_short!("granted"),
+        (role, account, granter),
+    );
+    Ok(())
+}
+
+pub fn revoke_role<K>(
+    env: &Env,
+    admin_key: &K,
+    revoker: Address,
+    role: Role,
+    account: Address,
+) -> Result<(), PulsarError>
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    require_role(env, admin_key, Role::Admin, &revoker)?;
+    env.storage()
+        .persistent()
+        .remove(&RoleKey::Role(role, account.clone()));
+    publish_event(
+        env,
+        symbol_short!("rbac"),
+        symbol_short!("revoked"),
+        (role, account, revoker),
+    );
+    Ok(())
+}
+
+/// True for the stored admin, Admin-role holders, and explicit `role` grants
+pub fn has_role<K>(env: &Env, admin_key: &K, role: Role, account: &Address) -> bool
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    let admin: Option<Address> = env.storage().instance().get(admin_key);
+    if admin.as_ref() == Some(account) {
+        return true;
+    }
+    let granted = |r: Role| {
+        env.storage()
+            .persistent()
+            .get::<RoleKey, bool>(&RoleKey::Role(r, account.clone()))
+            .unwrap_or(false)
+    };
+    granted(role) || granted(Role::Admin)
+}
+
+pub fn require_role<K>(
+    env: &Env,
+    admin_key: &K,
+    role: Role,
+    account: &Address,
+) -> Result<(), PulsarError>
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    account.require_auth();
+    if !has_role(env, admin_key, role, account) {
+        return Err(PulsarError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Ask the oracle registry whether `oracle` is staked, live and permitted for
+/// `domain`. Callers are expected to have checked `oracle.require_auth()`.
+pub fn registry_allows(
+    env: &Env,
+    registry: &Address,
+    oracle: &Address,
+    domain: OracleDomain,
+) -> bool {
+    env.invoke_contract(
+        registry,
+        &Symbol::new(env, "is_authorized"),
+        Vec::from_array(env, [oracle.into_val(env), domain.into_val(env)]),
+    )
+}
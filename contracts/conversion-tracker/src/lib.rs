@@ -0,0 +1,500 @@
+//! PulsarTrack - Conversion Tracker (Soroban)
+//! Attribution of off-chain conversions to verified ad views. Registered
+//! relayers (oracles or advertiser-pixel relayers) sign conversion reports
+//! that reference a fraud-prevention view id; each view converts at most
+//! once and only within the attribution window. Per-campaign counts and CPA
+//! are kept here and conversions are forwarded to the budget optimizer.
+//!
+//! Events (third topic is `pulsar_common_admin::EVENT_SCHEMA_VERSION`):
+//! - ("convert", "recorded"): [conversion_id: u64, campaign_id: u64, publisher: Address, value: i128]
+//! - ("convert", "spend"): [campaign_id: u64, amount: i128]
+//! - ("convert", "relayer"): [relayer: Address, kind: Option<RelayerKind>]
+
+#![no_std]
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, IntoVal, Symbol, Vec,
+};
+
+pub use pulsar_common_errors::PulsarError;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RelayerKind {
+    Oracle,
+    Pixel, // advertiser-pixel relayer
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Conversion {
+    pub conversion_id: u64,
+    pub external_id: BytesN<32>, // relayer-side dedup key, e.g. hashed order id
+    pub campaign_id: u64,
+    pub view_id: BytesN<32>,
+    pub publisher: Address,
+    pub relayer: Address,
+    pub value: i128,
+    pub viewed_at: u64,
+    pub converted_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct CampaignConversions {
+    pub conversions: u64,
+    pub total_value: i128,
+    pub spend: i128,
+    pub last_conversion_at: u64,
+}
+
+/// Mirror of the fraud-prevention view record, decoded from `get_view`.
+#[contracttype]
+#[derive(Clone)]
+pub struct ViewRecord {
+    pub campaign_id: u64,
+    pub publisher: Address,
+    pub viewer: Address,
+    pub timestamp: u64,
+    pub verification_score: u32,
+    pub verified: bool,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    PendingAdmin,
+    FraudPrevention,
+    BudgetOptimizer,
+    AuthorizedSpender,
+    AttributionWindow,
+    ConversionCounter,
+    Relayer(Address),
+    Conversion(u64),
+    ExternalId(BytesN<32>),     // -> conversion_id
+    ViewConversion(BytesN<32>), // view_id -> conversion_id
+    CampaignStats(u64),
+}
+
+const DEFAULT_ATTRIBUTION_WINDOW_SECS: u64 = 604_800; // 7 days
+
+const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
+const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
+const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
+
+#[contract]
+pub struct ConversionTrackerContract;
+
+#[contractimpl]
+impl ConversionTrackerContract {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        fraud_prevention: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(PulsarError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::FraudPrevention, &fraud_prevention);
+        env.storage()
+            .instance()
+            .set(&DataKey::ConversionCounter, &0u64);
+        Ok(())
+    }
+
+    pub fn add_relayer(
+        env: Env,
+        admin: Address,
+        relayer: Address,
+        kind: RelayerKind,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+        let _ttl_key = DataKey::Relayer(relayer.clone());
+        env.storage().persistent().set(&_ttl_key, &kind);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("convert"),
+            symbol_short!("relayer"),
+            (relayer, Some(kind)),
+        );
+        Ok(())
+    }
+
+    pub fn remove_relayer(env: Env, admin: Address, relayer: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Relayer(relayer.clone()));
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("convert"),
+            symbol_short!("relayer"),
+            (relayer, None::<RelayerKind>),
+        );
+        Ok(())
+    }
+
+    /// Budget optimizer fed with every recorded conversion. It must list this
+    /// contract as its conversion source, and conversions for campaigns
+    /// without an allocation there are rejected.
+    pub fn set_budget_optimizer(
+        env: Env,
+        admin: Address,
+        optimizer: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::BudgetOptimizer, &optimizer);
+        Ok(())
+    }
+
+    /// Contract allowed to report campaign spend alongside the admin
+    pub fn set_authorized_spender(
+        env: Env,
+        admin: Address,
+        spender: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::AuthorizedSpender, &spender);
+        Ok(())
+    }
+
+    pub fn set_attribution_window(
+        env: Env,
+        admin: Address,
+        window_secs: u64,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+        if window_secs == 0 {
+            return Err(PulsarError::InvalidInput);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AttributionWindow, &window_secs);
+        Ok(())
+    }
+
+    /// Attribute a conversion to a verified view of `campaign_id`. Returns the
+    /// new conversion id.
+    pub fn record_conversion(
+        env: Env,
+        relayer: Address,
+        campaign_id: u64,
+        view_id: BytesN<32>,
+        external_id: BytesN<32>,
+        value: i128,
+    ) -> Result<u64, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        relayer.require_auth();
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Relayer(relayer.clone()))
+        {
+            return Err(PulsarError::Unauthorized);
+        }
+        if value < 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::ExternalId(external_id.clone()))
+        {
+            return Err(PulsarError::AlreadyExists);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::ViewConversion(view_id.clone()))
+        {
+            return Err(PulsarError::AlreadyExists);
+        }
+
+        let fraud: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FraudPrevention)
+            .unwrap();
+        let view: Option<ViewRecord> = env.invoke_contract(
+            &fraud,
+            &Symbol::new(&env, "get_view"),
+            Vec::from_array(&env, [view_id.clone().into_val(&env)]),
+        );
+        let view = view.ok_or(PulsarError::NotFound)?;
+        if !view.verified {
+            return Err(PulsarError::NotVerified);
+        }
+        if view.campaign_id != campaign_id {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let now = env.ledger().timestamp();
+        let window: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AttributionWindow)
+            .unwrap_or(DEFAULT_ATTRIBUTION_WINDOW_SECS);
+        if now.saturating_sub(view.timestamp) > window {
+            return Err(PulsarError::Expired);
+        }
+
+        let conversion_id: u64 = env
+            .storage()
+            .instance()
+            .get::<DataKey, u64>(&DataKey::ConversionCounter)
+            .unwrap_or(0)
+            + 1;
+        let conversion = Conversion {
+            conversion_id,
+            external_id: external_id.clone(),
+            campaign_id,
+            view_id: view_id.clone(),
+            publisher: view.publisher.clone(),
+            relayer,
+            value,
+            viewed_at: view.timestamp,
+            converted_at: now,
+        };
+        let _ttl_key = DataKey::Conversion(conversion_id);
+        env.storage().persistent().set(&_ttl_key, &conversion);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        let _ttl_key = DataKey::ExternalId(external_id);
+        env.storage().persistent().set(&_ttl_key, &conversion_id);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        let _ttl_key = DataKey::ViewConversion(view_id);
+        env.storage().persistent().set(&_ttl_key, &conversion_id);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::ConversionCounter, &conversion_id);
+
+        let mut stats = Self::get_campaign_stats(env.clone(), campaign_id);
+        stats.conversions += 1;
+        stats.total_value += value;
+        stats.last_conversion_at = now;
+        let _ttl_key = DataKey::CampaignStats(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &stats);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        if let Some(optimizer) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::BudgetOptimizer)
+        {
+            let _: () = env.invoke_contract(
+                &optimizer,
+                &Symbol::new(&env, "record_conversions"),
+                Vec::from_array(
+                    &env,
+                    [
+                        env.current_contract_address().into_val(&env),
+                        campaign_id.into_val(&env),
+                        1u64.into_val(&env),
+                    ],
+                ),
+            );
+        }
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("convert"),
+            symbol_short!("recorded"),
+            (conversion_id, campaign_id, view.publisher, value),
+        );
+
+        Ok(conversion_id)
+    }
+
+    /// Spend that the campaign's CPA is measured against
+    pub fn record_spend(
+        env: Env,
+        caller: Address,
+        campaign_id: u64,
+        amount: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        caller.require_auth();
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        let spender: Option<Address> = env.storage().instance().get(&DataKey::AuthorizedSpender);
+        if caller != admin && spender != Some(caller) {
+            return Err(PulsarError::Unauthorized);
+        }
+        if amount <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+
+        let mut stats = Self::get_campaign_stats(env.clone(), campaign_id);
+        stats.spend += amount;
+        let _ttl_key = DataKey::CampaignStats(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &stats);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("convert"),
+            symbol_short!("spend"),
+            (campaign_id, amount),
+        );
+        Ok(())
+    }
+
+    pub fn get_campaign_stats(env: Env, campaign_id: u64) -> CampaignConversions {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::CampaignStats(campaign_id))
+            .unwrap_or(CampaignConversions {
+                conversions: 0,
+                total_value: 0,
+                spend: 0,
+                last_conversion_at: 0,
+            })
+    }
+
+    /// Spend per conversion; None until the campaign has converted
+    pub fn get_cpa(env: Env, campaign_id: u64) -> Option<i128> {
+        let stats = Self::get_campaign_stats(env, campaign_id);
+        if stats.conversions == 0 {
+            return None;
+        }
+        Some(stats.spend / stats.conversions as i128)
+    }
+
+    pub fn get_conversion(env: Env, conversion_id: u64) -> Option<Conversion> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Conversion(conversion_id))
+    }
+
+    pub fn get_view_conversion(env: Env, view_id: BytesN<32>) -> Option<u64> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::ViewConversion(view_id))
+    }
+
+    pub fn get_relayer(env: Env, relayer: Address) -> Option<RelayerKind> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().persistent().get(&DataKey::Relayer(relayer))
+    }
+
+    pub fn get_conversion_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::ConversionCounter)
+            .unwrap_or(0)
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::propose_admin(
+            &env,
+            &DataKey::Admin,
+            &DataKey::PendingAdmin,
+            current_admin,
+            new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
+
+    // ─── internal helpers ────────────────────────────────────────────────────
+
+    fn _require_admin(env: &Env, admin: &Address) -> Result<(), PulsarError> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if *admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        Ok(())
+    }
+}
+
+mod test;
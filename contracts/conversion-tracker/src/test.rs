@@ -0,0 +1,235 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env,
+};
+
+mod fraud_mock {
+    use super::ViewRecord;
+    use soroban_sdk::{contract, contractimpl, BytesN, Env};
+
+    #[contract]
+    pub struct MockFraudPrevention;
+
+    #[contractimpl]
+    impl MockFraudPrevention {
+        pub fn set_view(env: Env, view_id: BytesN<32>, record: ViewRecord) {
+            env.storage().persistent().set(&view_id, &record);
+        }
+
+        pub fn get_view(env: Env, view_id: BytesN<32>) -> Option<ViewRecord> {
+            env.storage().persistent().get(&view_id)
+        }
+    }
+}
+
+mod optimizer_mock {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockOptimizer;
+
+    #[contractimpl]
+    impl MockOptimizer {
+        pub fn record_conversions(env: Env, source: Address, campaign_id: u64, conversions: u64) {
+            source.require_auth();
+            let total: u64 = env.storage().persistent().get(&campaign_id).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&campaign_id, &(total + conversions));
+        }
+
+        pub fn conversions(env: Env, campaign_id: u64) -> u64 {
+            env.storage().persistent().get(&campaign_id).unwrap_or(0)
+        }
+    }
+}
+
+// ─── helpers ─────────────────────────────────────────────────────────────────
+
+struct Setup<'a> {
+    client: ConversionTrackerContractClient<'a>,
+    fraud: fraud_mock::MockFraudPreventionClient<'a>,
+    admin: Address,
+    relayer: Address,
+}
+
+fn setup(env: &Env) -> Setup<'_> {
+    let admin = Address::generate(env);
+    let relayer = Address::generate(env);
+    let fraud_id = env.register(fraud_mock::MockFraudPrevention, ());
+    let id = env.register(ConversionTrackerContract, ());
+    let client = ConversionTrackerContractClient::new(env, &id);
+    client.initialize(&admin, &fraud_id);
+    client.add_relayer(&admin, &relayer, &RelayerKind::Pixel);
+
+    Setup {
+        client,
+        fraud: fraud_mock::MockFraudPreventionClient::new(env, &fraud_id),
+        admin,
+        relayer,
+    }
+}
+
+fn add_view(env: &Env, s: &Setup, seed: u8, campaign_id: u64, verified: bool) -> BytesN<32> {
+    let view_id = BytesN::from_array(env, &[seed; 32]);
+    s.fraud.set_view(
+        &view_id,
+        &ViewRecord {
+            campaign_id,
+            publisher: Address::generate(env),
+            viewer: Address::generate(env),
+            timestamp: env.ledger().timestamp(),
+            verification_score: 90,
+            verified,
+        },
+    );
+    view_id
+}
+
+fn ext(env: &Env, seed: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[seed; 32])
+}
+
+// ─── tests ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_record_conversion_and_cpa() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    assert_eq!(s.client.get_cpa(&1u64), None);
+
+    let v1 = add_view(&env, &s, 1, 1, true);
+    let v2 = add_view(&env, &s, 2, 1, true);
+    let id = s
+        .client
+        .record_conversion(&s.relayer, &1u64, &v1, &ext(&env, 10), &2_500i128);
+    s.client
+        .record_conversion(&s.relayer, &1u64, &v2, &ext(&env, 11), &500i128);
+    s.client.record_spend(&s.admin, &1u64, &9_000i128);
+
+    let conversion = s.client.get_conversion(&id).unwrap();
+    assert_eq!(conversion.campaign_id, 1);
+    assert_eq!(conversion.view_id, v1);
+    assert_eq!(s.client.get_view_conversion(&v1), Some(id));
+
+    let stats = s.client.get_campaign_stats(&1u64);
+    assert_eq!(stats.conversions, 2);
+    assert_eq!(stats.total_value, 3_000);
+    assert_eq!(s.client.get_cpa(&1u64), Some(4_500));
+}
+
+#[test]
+fn test_conversions_feed_budget_optimizer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let optimizer_id = env.register(optimizer_mock::MockOptimizer, ());
+    let optimizer = optimizer_mock::MockOptimizerClient::new(&env, &optimizer_id);
+    s.client.set_budget_optimizer(&s.admin, &optimizer_id);
+
+    let view = add_view(&env, &s, 1, 3, true);
+    s.client
+        .record_conversion(&s.relayer, &3u64, &view, &ext(&env, 10), &0i128);
+    assert_eq!(optimizer.conversions(&3u64), 1);
+}
+
+#[test]
+fn test_unregistered_relayer_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let view = add_view(&env, &s, 1, 1, true);
+    s.client.remove_relayer(&s.admin, &s.relayer);
+    assert_eq!(s.client.get_relayer(&s.relayer), None);
+
+    assert_eq!(
+        s.client
+            .try_record_conversion(&s.relayer, &1u64, &view, &ext(&env, 10), &0i128),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_view_converts_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let view = add_view(&env, &s, 1, 1, true);
+    s.client
+        .record_conversion(&s.relayer, &1u64, &view, &ext(&env, 10), &0i128);
+    assert_eq!(
+        s.client
+            .try_record_conversion(&s.relayer, &1u64, &view, &ext(&env, 11), &0i128),
+        Err(Ok(PulsarError::AlreadyExists))
+    );
+}
+
+#[test]
+fn test_external_id_deduplicated() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let v1 = add_view(&env, &s, 1, 1, true);
+    let v2 = add_view(&env, &s, 2, 1, true);
+    s.client
+        .record_conversion(&s.relayer, &1u64, &v1, &ext(&env, 10), &0i128);
+    assert_eq!(
+        s.client
+            .try_record_conversion(&s.relayer, &1u64, &v2, &ext(&env, 10), &0i128),
+        Err(Ok(PulsarError::AlreadyExists))
+    );
+}
+
+#[test]
+fn test_unverified_or_foreign_views_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let rejected = add_view(&env, &s, 1, 1, false);
+    let other_campaign = add_view(&env, &s, 2, 2, true);
+    let missing = BytesN::from_array(&env, &[9u8; 32]);
+
+    assert!(s
+        .client
+        .try_record_conversion(&s.relayer, &1u64, &rejected, &ext(&env, 10), &0i128)
+        .is_err());
+    assert!(s
+        .client
+        .try_record_conversion(&s.relayer, &1u64, &other_campaign, &ext(&env, 11), &0i128)
+        .is_err());
+    assert!(s
+        .client
+        .try_record_conversion(&s.relayer, &1u64, &missing, &ext(&env, 12), &0i128)
+        .is_err());
+    assert_eq!(s.client.get_conversion_count(), 0);
+}
+
+#[test]
+fn test_attribution_window_enforced() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    s.client.set_attribution_window(&s.admin, &3_600u64);
+    let view = add_view(&env, &s, 1, 1, true);
+
+    env.ledger().with_mut(|li| li.timestamp += 3_601);
+    assert_eq!(
+        s.client
+            .try_record_conversion(&s.relayer, &1u64, &view, &ext(&env, 10), &0i128),
+        Err(Ok(PulsarError::Expired))
+    );
+}
+
+#[test]
+fn test_record_spend_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    assert_eq!(
+        s.client.try_record_spend(&s.relayer, &1u64, &100i128),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
@@ -2,10 +2,14 @@
 //! A marketplace for buying, selling and licensing ad creatives on Stellar.
 
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, token, Address, Env, String,
+    contract, contractimpl, contracttype, symbol_short, token, Address, Env, IntoVal, String,
+    Symbol, Vec,
 };
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone, PartialEq)]
 pub enum ListingStatus {
@@ -13,6 +17,8 @@ pub enum ListingStatus {
     Sold,
     Unlicensed,
     Removed,
+    Auctioning,
+    TakenDown,
 }
 
 #[contracttype]
@@ -34,8 +40,16 @@ pub struct CreativeListing {
     pub description: String,
     pub price: i128,
     pub license_type: LicenseType,
+    pub category: Symbol,
+    pub tags: Vec<Symbol>,
     pub status: ListingStatus,
+    pub version: u32,
     pub sale_count: u64,
+    pub sales_period: u64, // index of the SALES_PERIOD window period_sales covers
+    pub period_sales: u64,
+    pub rating_count: u32,
+    pub rating_total: u32,
+    pub average_rating: u32, // scaled by 100, e.g. 450 = 4.5 stars
     pub created_at: u64,
     pub last_updated: u64,
 }
@@ -49,6 +63,49 @@ pub struct License {
     pub paid_amount: i128,
     pub purchased_at: u64,
     pub expires_at: Option<u64>,
+    pub renewal_interval: u64, // 0 unless the license is Recurring
+    pub renewal_price: i128,
+    pub content_version: u32,
+    pub revoked_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ContentVersion {
+    pub version: u32,
+    pub content_hash: String,
+    pub published_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Auction {
+    pub listing_id: u64,
+    pub reserve_price: i128,
+    pub end_ledger: u32,
+    pub highest_bidder: Option<Address>,
+    pub highest_bid: i128,
+    pub settled: bool,
+}
+
+#[contracttype]
+#[derive(Clone, PartialEq)]
+pub enum EscrowStatus {
+    Held,
+    Released,
+    Disputed,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct PurchaseEscrow {
+    pub listing_id: u64,
+    pub buyer: Address,
+    pub amount: i128,
+    pub funded_at: u64,
+    pub release_after: u64,
+    pub status: EscrowStatus,
+    pub dispute_id: Option<u64>,
 }
 
 #[contracttype]
@@ -62,24 +119,53 @@ pub enum DataKey {
     Listing(u64),
     License(u64, Address), // listing_id, licensee
     ContentOwner(String),  // content_hash -> tracks exclusive licenses
+    Collaborators(u64),    // listing_id -> Vec<(collaborator, share_bps)>
+    ResaleRoyaltyBps(u64), // listing_id -> creator royalty on secondary sales
+    CreatorListingCount(Address),
+    ListingsByCreator(Address, u32), // (creator, index) -> listing_id
+    CategoryListingCount(Symbol),
+    ListingsByCategory(Symbol, u32), // (category, index) -> listing_id
+    Auction(u64),
+    RecurringInterval(u64),   // listing_id -> billing interval in seconds
+    ListingVersion(u64, u32), // (listing_id, version) -> ContentVersion
+    AcceptanceWindow,         // seconds buyers have to accept delivery; 0 = no escrow
+    DisputeContract,
+    PurchaseEscrow(u64, Address), // listing_id, buyer
+    IdentityRegistry,
+    Moderator(Address),
+    Treasury,
+    AccruedFees, // fees held by the contract while no treasury is set
+    FeeRouter,
+    Rating(u64, Address),  // listing_id, licensee -> 1..=5
+    PeriodSales(u64, u64), // listing_id, period -> sales in that period
+    TopListings(Symbol),   // category -> Vec<(listing_id, sale_count)>, best first
 }
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
 const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
 const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
+const MAX_COLLABORATORS: u32 = 10;
+const DEFAULT_RESALE_ROYALTY_BPS: u32 = 500; // 5%
+const MAX_RESALE_ROYALTY_BPS: u32 = 5_000; // 50%
+const MAX_TAGS: u32 = 5;
+const MAX_PAGE_SIZE: u32 = 50;
+const DEFAULT_RECURRING_INTERVAL: u64 = 2_592_000; // 30 days
+const RENEWAL_GRACE_PERIOD: u64 = 259_200; // 3 days
+const SALES_PERIOD: u64 = 604_800; // 7 days
+const LEADERBOARD_SIZE: u32 = 10;
 
 #[contract]
 pub struct CreativeMarketplaceContract;
 
 #[contractimpl]
 impl CreativeMarketplaceContract {
-    pub fn initialize(env: Env, admin: Address, token: Address) {
+    pub fn initialize(env: Env, admin: Address, token: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -90,6 +176,210 @@ impl CreativeMarketplaceContract {
         env.storage()
             .instance()
             .set(&DataKey::PlatformFeeBps, &250u32); // 2.5%
+        Ok(())
+    }
+
+    /// Admin: set the treasury that receives platform fees. Fees accrued
+    /// before a treasury was configured are swept to it.
+    pub fn set_treasury(env: Env, admin: Address, treasury: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        if treasury == env.current_contract_address() {
+            return Err(PulsarError::InvalidInput);
+        }
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+
+        let accrued: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AccruedFees)
+            .unwrap_or(0);
+        if accrued > 0 {
+            let token_addr: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::TokenAddress)
+                .unwrap();
+            token::Client::new(&env, &token_addr).transfer(
+                &env.current_contract_address(),
+                &treasury,
+                &accrued,
+            );
+            env.storage().instance().set(&DataKey::AccruedFees, &0i128);
+        }
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("fee"),
+            symbol_short!("treasury"),
+            (treasury, accrued),
+        );
+        Ok(())
+    }
+
+    /// Admin: route platform fees through the fee router. Takes precedence
+    /// over the treasury.
+    pub fn set_fee_router(env: Env, admin: Address, router: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage().instance().set(&DataKey::FeeRouter, &router);
+        Ok(())
+    }
+
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().get(&DataKey::Treasury)
+    }
+
+    pub fn get_accrued_fees(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::AccruedFees)
+            .unwrap_or(0)
+    }
+
+    /// Admin: set how long buyers have to accept delivery before escrowed
+    /// payments release automatically. Zero pays creators immediately.
+    pub fn set_acceptance_window(
+        env: Env,
+        admin: Address,
+        window_secs: u64,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AcceptanceWindow, &window_secs);
+        Ok(())
+    }
+
+    pub fn set_dispute_contract(
+        env: Env,
+        admin: Address,
+        contract_address: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::DisputeContract, &contract_address);
+        Ok(())
+    }
+
+    /// Admin: require listing creators to hold a Verified identity in the
+    /// given identity-registry contract.
+    pub fn set_identity_registry(
+        env: Env,
+        admin: Address,
+        contract_address: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::IdentityRegistry, &contract_address);
+        Ok(())
+    }
+
+    pub fn add_moderator(env: Env, admin: Address, moderator: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        let _ttl_key = DataKey::Moderator(moderator);
+        env.storage().persistent().set(&_ttl_key, &true);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Ok(())
+    }
+
+    pub fn remove_moderator(
+        env: Env,
+        admin: Address,
+        moderator: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Moderator(moderator));
+        Ok(())
     }
 
     pub fn create_listing(
@@ -100,74 +390,56 @@ impl CreativeMarketplaceContract {
         description: String,
         price: i128,
         license_type: LicenseType,
-    ) -> u64 {
+        category: Symbol,
+        tags: Vec<Symbol>,
+    ) -> Result<u64, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         creator.require_auth();
 
         if price <= 0 {
-            panic!("invalid price");
+            return Err(PulsarError::InvalidAmount);
         }
-
-        // Check for duplicate content hash with exclusive license
-        if env
-            .storage()
-            .persistent()
-            .has(&DataKey::ContentOwner(content_hash.clone()))
-        {
-            panic!("content already listed - check for exclusive licenses");
+        if tags.len() > MAX_TAGS {
+            return Err(PulsarError::LimitExceeded);
         }
+        Self::_require_verified_creator(&env, &creator)?;
 
-        let counter: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::ListingCounter)
-            .unwrap_or(0);
-        let listing_id = counter + 1;
+        Self::_require_content_available(&env, &content_hash)?;
 
+        let listing_id = Self::_next_listing_id(&env);
         let listing = CreativeListing {
             listing_id,
             creator: creator.clone(),
-            content_hash: content_hash.clone(),
+            content_hash,
             title,
             description,
             price,
-            license_type: license_type.clone(),
+            license_type,
+            category,
+            tags,
             status: ListingStatus::Active,
+            version: 1,
             sale_count: 0,
+            sales_period: 0,
+            period_sales: 0,
+            rating_count: 0,
+            rating_total: 0,
+            average_rating: 0,
             created_at: env.ledger().timestamp(),
             last_updated: env.ledger().timestamp(),
         };
+        Self::_store_new_listing(&env, &listing);
 
-        let _ttl_key = DataKey::Listing(listing_id);
-        env.storage().persistent().set(&_ttl_key, &listing);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-        env.storage()
-            .instance()
-            .set(&DataKey::ListingCounter, &listing_id);
-
-        // If this is an exclusive license, mark content as owned
-        if matches!(license_type, LicenseType::Exclusive) {
-            let content_key = DataKey::ContentOwner(content_hash);
-            env.storage().persistent().set(&content_key, &listing_id);
-            env.storage().persistent().extend_ttl(
-                &content_key,
-                PERSISTENT_LIFETIME_THRESHOLD,
-                PERSISTENT_BUMP_AMOUNT,
-            );
-        }
-
-        env.events().publish(
-            (symbol_short!("listing"), symbol_short!("created")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("listing"),
+            symbol_short!("created"),
             (listing_id, creator),
         );
 
-        listing_id
+        Ok(listing_id)
     }
 
     pub fn purchase_license(
@@ -175,7 +447,7 @@ impl CreativeMarketplaceContract {
         buyer: Address,
         listing_id: u64,
         license_duration_secs: Option<u64>,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -185,47 +457,89 @@ impl CreativeMarketplaceContract {
             .storage()
             .persistent()
             .get(&DataKey::Listing(listing_id))
-            .expect("listing not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if listing.status != ListingStatus::Active {
-            panic!("listing not active");
+            return Err(PulsarError::InvalidState);
         }
 
-        // Check not already licensed
-        if env
+        // Check not already licensed (lapsed licenses may be bought again)
+        if let Some(existing) = env
             .storage()
             .persistent()
-            .has(&DataKey::License(listing_id, buyer.clone()))
+            .get::<DataKey, License>(&DataKey::License(listing_id, buyer.clone()))
         {
-            panic!("already licensed");
+            if Self::_is_license_valid(&env, &existing) {
+                return Err(PulsarError::AlreadyExists);
+            }
         }
 
-        // Calculate fee
-        let fee_bps: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::PlatformFeeBps)
-            .unwrap_or(250);
-        let fee = (listing.price * fee_bps as i128) / 10_000;
-        let creator_amount = listing.price - fee;
-
-        // Process payment
         let token_addr: Address = env
             .storage()
             .instance()
             .get(&DataKey::TokenAddress)
             .unwrap();
         let token_client = token::Client::new(&env, &token_addr);
-        token_client.transfer(&buyer, &listing.creator, &creator_amount);
-
-        // Fee to admin
-        if fee > 0 {
-            let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-            token_client.transfer(&buyer, &admin, &fee);
+        let now = env.ledger().timestamp();
+        let acceptance_window: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AcceptanceWindow)
+            .unwrap_or(0);
+        if acceptance_window > 0 {
+            if let Some(pending) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, PurchaseEscrow>(&DataKey::PurchaseEscrow(listing_id, buyer.clone()))
+            {
+                if pending.status == EscrowStatus::Held {
+                    return Err(PulsarError::InvalidState);
+                }
+            }
+            // Hold the payment until the buyer accepts delivery or the window lapses
+            token_client.transfer(&buyer, &env.current_contract_address(), &listing.price);
+            let escrow = PurchaseEscrow {
+                listing_id,
+                buyer: buyer.clone(),
+                amount: listing.price,
+                funded_at: now,
+                release_after: now + acceptance_window,
+                status: EscrowStatus::Held,
+                dispute_id: None,
+            };
+            let _ttl_key = DataKey::PurchaseEscrow(listing_id, buyer.clone());
+            env.storage().persistent().set(&_ttl_key, &escrow);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+            pulsar_common_admin::publish_event(
+                &env,
+                symbol_short!("escrow"),
+                symbol_short!("held"),
+                (
+                    listing_id,
+                    buyer.clone(),
+                    listing.price,
+                    escrow.release_after,
+                ),
+            );
+        } else {
+            Self::_collect_sale_payment(&env, &token_client, &buyer, &listing, listing.price);
         }
 
-        let now = env.ledger().timestamp();
-        let expires_at = license_duration_secs.map(|d| now + d);
+        let (expires_at, renewal_interval) =
+            if matches!(listing.license_type, LicenseType::Recurring) {
+                let interval: u64 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::RecurringInterval(listing_id))
+                    .unwrap_or(DEFAULT_RECURRING_INTERVAL);
+                (Some(now + interval), interval)
+            } else {
+                (license_duration_secs.map(|d| now + d), 0)
+            };
 
         let license = License {
             listing_id,
@@ -234,6 +548,10 @@ impl CreativeMarketplaceContract {
             paid_amount: listing.price,
             purchased_at: now,
             expires_at,
+            renewal_interval,
+            renewal_price: listing.price,
+            content_version: listing.version,
+            revoked_at: None,
         };
 
         let _ttl_key = DataKey::License(listing_id, buyer);
@@ -244,7 +562,7 @@ impl CreativeMarketplaceContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        listing.sale_count += 1;
+        Self::_record_sale(&env, &mut listing);
         listing.last_updated = now;
 
         // Exclusive licenses close the listing
@@ -260,13 +578,24 @@ impl CreativeMarketplaceContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("license"), symbol_short!("purchased")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("license"),
+            symbol_short!("purchased"),
             (listing_id, listing.price),
         );
+        Ok(())
     }
 
-    pub fn remove_listing(env: Env, creator: Address, listing_id: u64) {
+    /// Change the price and description of an active listing. Existing
+    /// licenses are unaffected.
+    pub fn update_listing(
+        env: Env,
+        creator: Address,
+        listing_id: u64,
+        new_price: i128,
+        new_description: String,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -276,21 +605,21 @@ impl CreativeMarketplaceContract {
             .storage()
             .persistent()
             .get(&DataKey::Listing(listing_id))
-            .expect("listing not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if listing.creator != creator {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
-
-        // If this was an exclusive license, clear the content owner
-        if matches!(listing.license_type, LicenseType::Exclusive) {
-            let content_key = DataKey::ContentOwner(listing.content_hash.clone());
-            if env.storage().persistent().has(&content_key) {
-                env.storage().persistent().remove(&content_key);
-            }
+        if listing.status != ListingStatus::Active {
+            return Err(PulsarError::InvalidState);
+        }
+        if new_price <= 0 {
+            return Err(PulsarError::InvalidAmount);
         }
 
-        listing.status = ListingStatus::Removed;
+        let old_price = listing.price;
+        listing.price = new_price;
+        listing.description = new_description;
         listing.last_updated = env.ledger().timestamp();
         let _ttl_key = DataKey::Listing(listing_id);
         env.storage().persistent().set(&_ttl_key, &listing);
@@ -299,57 +628,1710 @@ impl CreativeMarketplaceContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("listing"),
+            symbol_short!("updated"),
+            (listing_id, old_price, new_price),
+        );
+        Ok(())
     }
 
-    pub fn get_listing(env: Env, listing_id: u64) -> Option<CreativeListing> {
+    /// Publish a new content hash for a listing. New licenses cover the new
+    /// version; existing licenses stay pinned to the version they bought.
+    pub fn publish_new_version(
+        env: Env,
+        creator: Address,
+        listing_id: u64,
+        content_hash: String,
+    ) -> Result<u32, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        env.storage()
+        creator.require_auth();
+
+        let mut listing: CreativeListing = env
+            .storage()
             .persistent()
             .get(&DataKey::Listing(listing_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if listing.creator != creator {
+            return Err(PulsarError::Unauthorized);
+        }
+        if listing.status == ListingStatus::Removed || listing.status == ListingStatus::TakenDown {
+            return Err(PulsarError::InvalidState);
+        }
+        if listing.content_hash == content_hash {
+            return Err(PulsarError::InvalidInput);
+        }
+        Self::_require_content_available(&env, &content_hash)?;
+
+        listing.version += 1;
+        listing.content_hash = content_hash;
+        listing.last_updated = env.ledger().timestamp();
+        Self::_record_version(&env, &listing);
+
+        let _ttl_key = DataKey::Listing(listing_id);
+        env.storage().persistent().set(&_ttl_key, &listing);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("listing"),
+            symbol_short!("version"),
+            (listing_id, listing.version),
+        );
+
+        Ok(listing.version)
     }
 
-    pub fn get_license(env: Env, listing_id: u64, licensee: Address) -> Option<License> {
+    /// Buyer confirms the creative was delivered and usable, releasing the
+    /// escrowed payment to the creator.
+    pub fn confirm_delivery(env: Env, buyer: Address, listing_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        env.storage()
-            .persistent()
-            .get(&DataKey::License(listing_id, licensee))
+        buyer.require_auth();
+        Self::_release_purchase_escrow(&env, listing_id, &buyer)
     }
 
-    pub fn has_license(env: Env, listing_id: u64, licensee: Address) -> bool {
+    /// Release an escrowed payment once the acceptance window has passed
+    /// without a dispute. Callable by anyone.
+    pub fn release_escrow(env: Env, listing_id: u64, buyer: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        if let Some(license) = env
+        let escrow: PurchaseEscrow = env
             .storage()
             .persistent()
-            .get::<DataKey, License>(&DataKey::License(listing_id, licensee))
-        {
-            if let Some(expires) = license.expires_at {
-                expires > env.ledger().timestamp()
-            } else {
-                true
-            }
-        } else {
-            false
+            .get(&DataKey::PurchaseEscrow(listing_id, buyer.clone()))
+            .ok_or(PulsarError::NotFound)?;
+        if env.ledger().timestamp() < escrow.release_after {
+            return Err(PulsarError::TooEarly);
         }
+        Self::_release_purchase_escrow(&env, listing_id, &buyer)
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
-        pulsar_common_admin::propose_admin(
-            &env,
-            &DataKey::Admin,
-            &DataKey::PendingAdmin,
-            current_admin,
-            new_admin,
-        );
-    }
+    /// Escalate an escrowed purchase to dispute-resolution within the
+    /// acceptance window. The escrowed funds become the dispute's locked
+    /// claim (buyer as claimant, creator as respondent, filed through
+    /// `file_listing_dispute` so the listing id stays out of the campaign id
+    /// space) and the license is suspended pending the outcome.
+    pub fn dispute_purchase(
+        env: Env,
+        buyer: Address,
+        listing_id: u64,
+        description: String,
+        evidence_hash: String,
+    ) -> Result<u64, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        buyer.require_auth();
+
+        let escrow_key = DataKey::PurchaseEscrow(listing_id, buyer.clone());
+        let mut escrow: PurchaseEscrow = env
+            .storage()
+            .persistent()
+            .get(&escrow_key)
+            .ok_or(PulsarError::NotFound)?;
+        if escrow.status != EscrowStatus::Held {
+            return Err(PulsarError::InvalidState);
+        }
+        if env.ledger().timestamp() >= escrow.release_after {
+            return Err(PulsarError::Expired);
+        }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+        let dispute_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::DisputeContract)
+            .ok_or(PulsarError::NotInitialized)?;
+        let mut listing: CreativeListing = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Listing(listing_id))
+            .unwrap();
+
+        // Hand the escrowed funds back to the buyer so dispute-resolution can
+        // lock them as the claim amount.
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenAddress)
+            .unwrap();
+        let token_client = token::Client::new(&env, &token_addr);
+        token_client.transfer(&env.current_contract_address(), &buyer, &escrow.amount);
+
+        let dispute_id: u64 = env.invoke_contract(
+            &dispute_contract,
+            &Symbol::new(&env, "file_listing_dispute"),
+            Vec::from_array(
+                &env,
+                [
+                    buyer.clone().into_val(&env),
+                    listing.creator.clone().into_val(&env),
+                    listing_id.into_val(&env),
+                    escrow.amount.into_val(&env),
+                    description.into_val(&env),
+                    evidence_hash.into_val(&env),
+                ],
+            ),
+        );
+
+        escrow.status = EscrowStatus::Disputed;
+        escrow.dispute_id = Some(dispute_id);
+        env.storage().persistent().set(&escrow_key, &escrow);
+        env.storage().persistent().extend_ttl(
+            &escrow_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::License(listing_id, buyer.clone()));
+        if listing.status == ListingStatus::Sold {
+            // A disputed exclusive sale puts the creative back on the market
+            listing.status = ListingStatus::Active;
+            listing.last_updated = env.ledger().timestamp();
+            let _ttl_key = DataKey::Listing(listing_id);
+            env.storage().persistent().set(&_ttl_key, &listing);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("disputed"),
+            (listing_id, buyer, dispute_id),
+        );
+
+        Ok(dispute_id)
+    }
+
+    pub fn get_purchase_escrow(
+        env: Env,
+        listing_id: u64,
+        buyer: Address,
+    ) -> Option<PurchaseEscrow> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::PurchaseEscrow(listing_id, buyer))
+    }
+
+    /// Set the billing interval for a Recurring listing. Applies to licenses
+    /// purchased afterwards; existing licenses keep their interval.
+    pub fn set_recurring_interval(
+        env: Env,
+        creator: Address,
+        listing_id: u64,
+        interval_secs: u64,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        creator.require_auth();
+
+        let listing: CreativeListing = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Listing(listing_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if listing.creator != creator {
+            return Err(PulsarError::Unauthorized);
+        }
+        if !matches!(listing.license_type, LicenseType::Recurring) {
+            return Err(PulsarError::InvalidState);
+        }
+        if interval_secs == 0 {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let _ttl_key = DataKey::RecurringInterval(listing_id);
+        env.storage().persistent().set(&_ttl_key, &interval_secs);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Ok(())
+    }
+
+    /// Pay for the next billing period of a Recurring license. Renewal is
+    /// allowed until the grace period after expiry runs out.
+    pub fn renew_license(env: Env, licensee: Address, listing_id: u64) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        licensee.require_auth();
+
+        let listing: CreativeListing = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Listing(listing_id))
+            .ok_or(PulsarError::NotFound)?;
+        let license_key = DataKey::License(listing_id, licensee.clone());
+        let mut license: License = env
+            .storage()
+            .persistent()
+            .get(&license_key)
+            .ok_or(PulsarError::NotFound)?;
+
+        if !matches!(license.license_type, LicenseType::Recurring) {
+            return Err(PulsarError::InvalidState);
+        }
+        if listing.status == ListingStatus::Removed || listing.status == ListingStatus::TakenDown {
+            return Err(PulsarError::InvalidState);
+        }
+        if !Self::_is_license_valid(&env, &license) {
+            return Err(PulsarError::Expired);
+        }
+
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenAddress)
+            .unwrap();
+        let token_client = token::Client::new(&env, &token_addr);
+        Self::_collect_sale_payment(
+            &env,
+            &token_client,
+            &licensee,
+            &listing,
+            license.renewal_price,
+        );
+
+        let new_expiry =
+            license.expires_at.unwrap_or(env.ledger().timestamp()) + license.renewal_interval;
+        license.expires_at = Some(new_expiry);
+        license.paid_amount += license.renewal_price;
+        env.storage().persistent().set(&license_key, &license);
+        env.storage().persistent().extend_ttl(
+            &license_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("license"),
+            symbol_short!("renewed"),
+            (listing_id, licensee, new_expiry),
+        );
+        Ok(())
+    }
+
+    /// List a creative for auction. The winner receives an Exclusive license.
+    pub fn create_auction(
+        env: Env,
+        creator: Address,
+        content_hash: String,
+        reserve_price: i128,
+        end_ledger: u32,
+    ) -> Result<u64, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        creator.require_auth();
+
+        if reserve_price <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        if end_ledger <= env.ledger().sequence() {
+            return Err(PulsarError::InvalidInput);
+        }
+        Self::_require_verified_creator(&env, &creator)?;
+        Self::_require_content_available(&env, &content_hash)?;
+
+        let listing_id = Self::_next_listing_id(&env);
+        let listing = CreativeListing {
+            listing_id,
+            creator: creator.clone(),
+            content_hash,
+            title: String::from_str(&env, ""),
+            description: String::from_str(&env, ""),
+            price: reserve_price,
+            license_type: LicenseType::Exclusive,
+            category: symbol_short!("auction"),
+            tags: Vec::new(&env),
+            status: ListingStatus::Auctioning,
+            version: 1,
+            sale_count: 0,
+            sales_period: 0,
+            period_sales: 0,
+            rating_count: 0,
+            rating_total: 0,
+            average_rating: 0,
+            created_at: env.ledger().timestamp(),
+            last_updated: env.ledger().timestamp(),
+        };
+        Self::_store_new_listing(&env, &listing);
+
+        let auction = Auction {
+            listing_id,
+            reserve_price,
+            end_ledger,
+            highest_bidder: None,
+            highest_bid: 0,
+            settled: false,
+        };
+        let _ttl_key = DataKey::Auction(listing_id);
+        env.storage().persistent().set(&_ttl_key, &auction);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("auction"),
+            symbol_short!("created"),
+            (listing_id, creator, reserve_price, end_ledger),
+        );
+
+        Ok(listing_id)
+    }
+
+    /// Bid on an auction. The bid is escrowed by the contract and the
+    /// previous highest bidder is refunded.
+    pub fn place_bid(
+        env: Env,
+        bidder: Address,
+        listing_id: u64,
+        amount: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        bidder.require_auth();
+
+        let listing: CreativeListing = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Listing(listing_id))
+            .ok_or(PulsarError::NotFound)?;
+        let mut auction: Auction = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Auction(listing_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if listing.status != ListingStatus::Auctioning {
+            return Err(PulsarError::InvalidState);
+        }
+        if env.ledger().sequence() > auction.end_ledger {
+            return Err(PulsarError::Expired);
+        }
+        if bidder == listing.creator {
+            return Err(PulsarError::Unauthorized);
+        }
+        if amount < auction.reserve_price {
+            return Err(PulsarError::InvalidAmount);
+        }
+        if amount <= auction.highest_bid {
+            return Err(PulsarError::InvalidAmount);
+        }
+
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenAddress)
+            .unwrap();
+        let token_client = token::Client::new(&env, &token_addr);
+        token_client.transfer(&bidder, &env.current_contract_address(), &amount);
+
+        if let Some(previous) = auction.highest_bidder.clone() {
+            token_client.transfer(
+                &env.current_contract_address(),
+                &previous,
+                &auction.highest_bid,
+            );
+        }
+
+        auction.highest_bidder = Some(bidder.clone());
+        auction.highest_bid = amount;
+        let _ttl_key = DataKey::Auction(listing_id);
+        env.storage().persistent().set(&_ttl_key, &auction);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("auction"),
+            symbol_short!("bid"),
+            (listing_id, bidder, amount),
+        );
+        Ok(())
+    }
+
+    /// Close an auction after its end ledger. The highest bidder receives an
+    /// Exclusive license; with no bids the content is released for relisting.
+    /// Only a listing still up for auction can be settled.
+    pub fn settle_auction(env: Env, listing_id: u64) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let mut listing: CreativeListing = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Listing(listing_id))
+            .ok_or(PulsarError::NotFound)?;
+        let mut auction: Auction = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Auction(listing_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if auction.settled {
+            return Err(PulsarError::AlreadyExists);
+        }
+        if listing.status != ListingStatus::Auctioning {
+            return Err(PulsarError::InvalidState);
+        }
+        if env.ledger().sequence() <= auction.end_ledger {
+            return Err(PulsarError::TooEarly);
+        }
+
+        let now = env.ledger().timestamp();
+        auction.settled = true;
+
+        if let Some(winner) = auction.highest_bidder.clone() {
+            let token_addr: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::TokenAddress)
+                .unwrap();
+            let token_client = token::Client::new(&env, &token_addr);
+            Self::_collect_sale_payment(
+                &env,
+                &token_client,
+                &env.current_contract_address(),
+                &listing,
+                auction.highest_bid,
+            );
+
+            let license = License {
+                listing_id,
+                licensee: winner.clone(),
+                license_type: LicenseType::Exclusive,
+                paid_amount: auction.highest_bid,
+                purchased_at: now,
+                expires_at: None,
+                renewal_interval: 0,
+                renewal_price: 0,
+                content_version: listing.version,
+                revoked_at: None,
+            };
+            let _ttl_key = DataKey::License(listing_id, winner.clone());
+            env.storage().persistent().set(&_ttl_key, &license);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+
+            listing.price = auction.highest_bid;
+            Self::_record_sale(&env, &mut listing);
+            listing.status = ListingStatus::Sold;
+
+            pulsar_common_admin::publish_event(
+                &env,
+                symbol_short!("auction"),
+                symbol_short!("settled"),
+                (listing_id, winner, auction.highest_bid),
+            );
+        } else {
+            Self::_release_content(&env, &listing);
+            listing.status = ListingStatus::Unlicensed;
+
+            pulsar_common_admin::publish_event(
+                &env,
+                symbol_short!("auction"),
+                symbol_short!("no_bids"),
+                listing_id,
+            );
+        }
+
+        listing.last_updated = now;
+        let _ttl_key = DataKey::Listing(listing_id);
+        env.storage().persistent().set(&_ttl_key, &listing);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        let _ttl_key = DataKey::Auction(listing_id);
+        env.storage().persistent().set(&_ttl_key, &auction);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Ok(())
+    }
+
+    /// Admin or moderator: take a listing down for a compliance reason
+    /// (e.g. a DMCA request). Unlike creator removal, the content hash stays
+    /// claimed and the listing can no longer be licensed or renewed.
+    pub fn takedown_listing(
+        env: Env,
+        moderator: Address,
+        listing_id: u64,
+        reason: String,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        moderator.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if moderator != stored_admin
+            && !env
+                .storage()
+                .persistent()
+                .has(&DataKey::Moderator(moderator.clone()))
+        {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        let mut listing: CreativeListing = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Listing(listing_id))
+            .ok_or(PulsarError::NotFound)?;
+        if listing.status == ListingStatus::TakenDown {
+            return Err(PulsarError::InvalidState);
+        }
+
+        // Refund the leading bid of a running auction
+        if listing.status == ListingStatus::Auctioning {
+            let mut auction: Auction = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Auction(listing_id))
+                .unwrap();
+            if let Some(bidder) = auction.highest_bidder.clone() {
+                let token_addr: Address = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::TokenAddress)
+                    .unwrap();
+                token::Client::new(&env, &token_addr).transfer(
+                    &env.current_contract_address(),
+                    &bidder,
+                    &auction.highest_bid,
+                );
+            }
+            auction.settled = true;
+            let _ttl_key = DataKey::Auction(listing_id);
+            env.storage().persistent().set(&_ttl_key, &auction);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+
+        listing.status = ListingStatus::TakenDown;
+        listing.last_updated = env.ledger().timestamp();
+        let _ttl_key = DataKey::Listing(listing_id);
+        env.storage().persistent().set(&_ttl_key, &listing);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("comply"),
+            symbol_short!("takedown"),
+            (listing_id, listing.creator, moderator, reason),
+        );
+        Ok(())
+    }
+
+    pub fn get_auction(env: Env, listing_id: u64) -> Option<Auction> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Auction(listing_id))
+    }
+
+    pub fn remove_listing(env: Env, creator: Address, listing_id: u64) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        creator.require_auth();
+
+        let mut listing: CreativeListing = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Listing(listing_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if listing.creator != creator {
+            return Err(PulsarError::Unauthorized);
+        }
+        if listing.status == ListingStatus::TakenDown {
+            return Err(PulsarError::InvalidState);
+        }
+        if listing.status == ListingStatus::Auctioning {
+            let mut auction: Auction = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Auction(listing_id))
+                .unwrap();
+            if auction.highest_bidder.is_some() {
+                return Err(PulsarError::InvalidState);
+            }
+            // Close the empty auction along with its listing
+            auction.settled = true;
+            let _ttl_key = DataKey::Auction(listing_id);
+            env.storage().persistent().set(&_ttl_key, &auction);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+
+        // If this was an exclusive license, clear the content owner
+        Self::_release_content(&env, &listing);
+
+        listing.status = ListingStatus::Removed;
+        listing.last_updated = env.ledger().timestamp();
+        let _ttl_key = DataKey::Listing(listing_id);
+        env.storage().persistent().set(&_ttl_key, &listing);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Ok(())
+    }
+
+    /// Configure royalty splits for a listing. Each collaborator receives
+    /// `share_bps` of the creator proceeds (after the platform fee); the
+    /// creator keeps the remainder.
+    pub fn set_collaborators(
+        env: Env,
+        creator: Address,
+        listing_id: u64,
+        collaborators: Vec<(Address, u32)>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        creator.require_auth();
+
+        let listing: CreativeListing = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Listing(listing_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if listing.creator != creator {
+            return Err(PulsarError::Unauthorized);
+        }
+        if listing.status == ListingStatus::Removed {
+            return Err(PulsarError::InvalidState);
+        }
+        if collaborators.len() > MAX_COLLABORATORS {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        let mut total_bps: u32 = 0;
+        for (i, (collaborator, share_bps)) in collaborators.iter().enumerate() {
+            if share_bps == 0 {
+                return Err(PulsarError::InvalidAmount);
+            }
+            if collaborator == creator {
+                return Err(PulsarError::InvalidInput);
+            }
+            for (other, _) in collaborators.iter().skip(i + 1) {
+                if other == collaborator {
+                    return Err(PulsarError::AlreadyExists);
+                }
+            }
+            total_bps += share_bps;
+        }
+        if total_bps > 10_000 {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let _ttl_key = DataKey::Collaborators(listing_id);
+        if collaborators.is_empty() {
+            env.storage().persistent().remove(&_ttl_key);
+        } else {
+            env.storage().persistent().set(&_ttl_key, &collaborators);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("listing"),
+            symbol_short!("collabs"),
+            (listing_id, collaborators.len(), total_bps),
+        );
+        Ok(())
+    }
+
+    /// Set the royalty the creator receives on secondary license sales.
+    pub fn set_resale_royalty(
+        env: Env,
+        creator: Address,
+        listing_id: u64,
+        royalty_bps: u32,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        creator.require_auth();
+
+        let listing: CreativeListing = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Listing(listing_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if listing.creator != creator {
+            return Err(PulsarError::Unauthorized);
+        }
+        if royalty_bps > MAX_RESALE_ROYALTY_BPS {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        let _ttl_key = DataKey::ResaleRoyaltyBps(listing_id);
+        env.storage().persistent().set(&_ttl_key, &royalty_bps);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Ok(())
+    }
+
+    /// Sell a non-exclusive license to another address. The new licensee pays
+    /// `price`, of which the resale royalty goes to the creator (and their
+    /// collaborators) and the rest to the current licensee. The royalty is
+    /// charged on at least the listing's own price, so a resale declared
+    /// cheaper or free still pays the creator in full.
+    pub fn transfer_license(
+        env: Env,
+        current_licensee: Address,
+        listing_id: u64,
+        new_licensee: Address,
+        price: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        current_licensee.require_auth();
+        new_licensee.require_auth();
+
+        if price < 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        if current_licensee == new_licensee {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let listing: CreativeListing = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Listing(listing_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        let old_key = DataKey::License(listing_id, current_licensee.clone());
+        let license: License = env
+            .storage()
+            .persistent()
+            .get(&old_key)
+            .ok_or(PulsarError::NotFound)?;
+
+        if matches!(license.license_type, LicenseType::Exclusive) {
+            return Err(PulsarError::InvalidState);
+        }
+        // A purchase still inside its acceptance window can be disputed and
+        // refunded, so it cannot change hands until the escrow is released
+        if let Some(escrow) =
+            env.storage()
+                .persistent()
+                .get::<DataKey, PurchaseEscrow>(&DataKey::PurchaseEscrow(
+                    listing_id,
+                    current_licensee.clone(),
+                ))
+        {
+            if escrow.status == EscrowStatus::Held {
+                return Err(PulsarError::InvalidState);
+            }
+        }
+
+        let now = env.ledger().timestamp();
+        if !Self::_is_license_valid(&env, &license) {
+            return Err(PulsarError::Expired);
+        }
+
+        let new_key = DataKey::License(listing_id, new_licensee.clone());
+        if let Some(existing) = env.storage().persistent().get::<DataKey, License>(&new_key) {
+            if Self::_is_license_valid(&env, &existing) {
+                return Err(PulsarError::AlreadyExists);
+            }
+        }
+
+        let royalty_bps: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ResaleRoyaltyBps(listing_id))
+            .unwrap_or(DEFAULT_RESALE_ROYALTY_BPS);
+        let royalty = (price.max(listing.price) * royalty_bps as i128) / 10_000;
+        let seller_amount = (price - royalty).max(0);
+
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenAddress)
+            .unwrap();
+        let token_client = token::Client::new(&env, &token_addr);
+        if royalty > 0 {
+            Self::_pay_creator_share(&env, &token_client, &new_licensee, &listing, royalty);
+        }
+        if seller_amount > 0 {
+            token_client.transfer(&new_licensee, &current_licensee, &seller_amount);
+        }
+
+        let transferred = License {
+            listing_id,
+            licensee: new_licensee.clone(),
+            license_type: license.license_type,
+            paid_amount: price,
+            purchased_at: now,
+            expires_at: license.expires_at,
+            renewal_interval: license.renewal_interval,
+            renewal_price: license.renewal_price,
+            content_version: license.content_version,
+            revoked_at: None,
+        };
+
+        env.storage().persistent().remove(&old_key);
+        env.storage().persistent().set(&new_key, &transferred);
+        env.storage().persistent().extend_ttl(
+            &new_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("license"),
+            symbol_short!("transfer"),
+            (listing_id, current_licensee, new_licensee, price, royalty),
+        );
+        Ok(())
+    }
+
+    /// Creator or admin: terminate a licensee's usage rights, e.g. for a
+    /// terms violation. With `refund` set, the unused part of the current
+    /// billing period of a Recurring license is refunded by the caller: the
+    /// creator when they revoke, the admin out of its own funds otherwise.
+    pub fn revoke_license(
+        env: Env,
+        caller: Address,
+        listing_id: u64,
+        licensee: Address,
+        reason: String,
+        refund: bool,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        caller.require_auth();
+
+        let listing: CreativeListing = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Listing(listing_id))
+            .ok_or(PulsarError::NotFound)?;
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if caller != listing.creator && caller != admin {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        let license_key = DataKey::License(listing_id, licensee.clone());
+        let mut license: License = env
+            .storage()
+            .persistent()
+            .get(&license_key)
+            .ok_or(PulsarError::NotFound)?;
+        if license.revoked_at.is_some() {
+            return Err(PulsarError::AlreadyExists);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut refund_amount: i128 = 0;
+        if refund {
+            if !matches!(license.license_type, LicenseType::Recurring) {
+                return Err(PulsarError::InvalidState);
+            }
+            let expires = license.expires_at.unwrap_or(now);
+            let remaining = expires.saturating_sub(now).min(license.renewal_interval);
+            if license.renewal_interval > 0 && remaining > 0 {
+                refund_amount =
+                    (license.renewal_price * remaining as i128) / license.renewal_interval as i128;
+            }
+            if refund_amount > 0 {
+                let token_addr: Address = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::TokenAddress)
+                    .unwrap();
+                token::Client::new(&env, &token_addr).transfer(&caller, &licensee, &refund_amount);
+            }
+        }
+
+        license.revoked_at = Some(now);
+        env.storage().persistent().set(&license_key, &license);
+        env.storage().persistent().extend_ttl(
+            &license_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("license"),
+            symbol_short!("revoked"),
+            (listing_id, licensee, caller, reason, refund_amount),
+        );
+        Ok(())
+    }
+
+    /// Rate a creative from 1 to 5. Only current licensees may rate; rating
+    /// again replaces the previous score.
+    pub fn rate_creative(
+        env: Env,
+        licensee: Address,
+        listing_id: u64,
+        rating: u32,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        licensee.require_auth();
+
+        if !(1..=5).contains(&rating) {
+            return Err(PulsarError::InvalidInput);
+        }
+        let license: License = env
+            .storage()
+            .persistent()
+            .get(&DataKey::License(listing_id, licensee.clone()))
+            .ok_or(PulsarError::Unauthorized)?;
+        if !Self::_is_license_valid(&env, &license) {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        let mut listing: CreativeListing = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Listing(listing_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        let rating_key = DataKey::Rating(listing_id, licensee.clone());
+        if let Some(previous) = env.storage().persistent().get::<DataKey, u32>(&rating_key) {
+            listing.rating_total -= previous;
+        } else {
+            listing.rating_count += 1;
+        }
+        listing.rating_total += rating;
+        listing.average_rating = (listing.rating_total * 100) / listing.rating_count;
+
+        env.storage().persistent().set(&rating_key, &rating);
+        env.storage().persistent().extend_ttl(
+            &rating_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        let _ttl_key = DataKey::Listing(listing_id);
+        env.storage().persistent().set(&_ttl_key, &listing);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("listing"),
+            symbol_short!("rated"),
+            (listing_id, licensee, rating),
+        );
+        Ok(())
+    }
+
+    pub fn get_rating(env: Env, listing_id: u64, licensee: Address) -> Option<u32> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Rating(listing_id, licensee))
+    }
+
+    /// Number of licenses sold for a listing in the given SALES_PERIOD window
+    /// (`timestamp / SALES_PERIOD`).
+    pub fn get_period_sales(env: Env, listing_id: u64, period: u64) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::PeriodSales(listing_id, period))
+            .unwrap_or(0)
+    }
+
+    /// Best-selling listings in a category, from the on-chain leaderboard.
+    pub fn get_top_listings(env: Env, category: Symbol, limit: u32) -> Vec<CreativeListing> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let board: Vec<(u64, u64)> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TopListings(category))
+            .unwrap_or(Vec::new(&env));
+
+        let mut result = Vec::new(&env);
+        for (listing_id, _) in board.iter().take(limit as usize) {
+            if let Some(listing) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, CreativeListing>(&DataKey::Listing(listing_id))
+            {
+                result.push_back(listing);
+            }
+        }
+        result
+    }
+
+    pub fn get_collaborators(env: Env, listing_id: u64) -> Vec<(Address, u32)> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Collaborators(listing_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_listing(env: Env, listing_id: u64) -> Option<CreativeListing> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Listing(listing_id))
+    }
+
+    /// Listings created by `creator`, paged by index position.
+    pub fn list_by_creator(
+        env: Env,
+        creator: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<CreativeListing> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CreatorListingCount(creator.clone()))
+            .unwrap_or(0);
+        Self::_collect_indexed(&env, count, start, limit, |i| {
+            DataKey::ListingsByCreator(creator.clone(), i)
+        })
+    }
+
+    /// Listings in `category`, paged by index position.
+    pub fn list_by_category(
+        env: Env,
+        category: Symbol,
+        start: u32,
+        limit: u32,
+    ) -> Vec<CreativeListing> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CategoryListingCount(category.clone()))
+            .unwrap_or(0);
+        Self::_collect_indexed(&env, count, start, limit, |i| {
+            DataKey::ListingsByCategory(category.clone(), i)
+        })
+    }
+
+    /// Listings with ids after `start`, paged by `pulsar_common_admin::page`
+    pub fn list_listings(env: Env, start: u64, limit: u32) -> (Vec<CreativeListing>, Option<u64>) {
+        Self::_page_listings(&env, start, limit, false)
+    }
+
+    /// Active listings among listing ids `start + 1 ..= start + limit`.
+    pub fn list_active(env: Env, start: u64, limit: u32) -> Vec<CreativeListing> {
+        Self::_page_listings(&env, start, limit, true).0
+    }
+
+    pub fn get_license(env: Env, listing_id: u64, licensee: Address) -> Option<License> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::License(listing_id, licensee))
+    }
+
+    pub fn get_version(env: Env, listing_id: u64, version: u32) -> Option<ContentVersion> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::ListingVersion(listing_id, version))
+    }
+
+    /// The content version a licensee's license covers.
+    pub fn get_licensed_version(
+        env: Env,
+        listing_id: u64,
+        licensee: Address,
+    ) -> Option<ContentVersion> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let license: License = env
+            .storage()
+            .persistent()
+            .get(&DataKey::License(listing_id, licensee))?;
+        env.storage().persistent().get(&DataKey::ListingVersion(
+            listing_id,
+            license.content_version,
+        ))
+    }
+
+    pub fn has_license(env: Env, listing_id: u64, licensee: Address) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if let Some(license) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, License>(&DataKey::License(listing_id, licensee))
+        {
+            Self::_is_license_valid(&env, &license)
+        } else {
+            false
+        }
+    }
+
+    // ============================================================
+    // Internal Helpers
+    // ============================================================
+
+    fn _page_listings(
+        env: &Env,
+        start: u64,
+        limit: u32,
+        active_only: bool,
+    ) -> (Vec<CreativeListing>, Option<u64>) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ListingCounter)
+            .unwrap_or(0);
+        pulsar_common_admin::page(env, counter, start, limit, |id| {
+            env.storage()
+                .persistent()
+                .get::<DataKey, CreativeListing>(&DataKey::Listing(id))
+                .filter(|listing| !active_only || listing.status == ListingStatus::Active)
+        })
+    }
+
+    fn _release_purchase_escrow(
+        env: &Env,
+        listing_id: u64,
+        buyer: &Address,
+    ) -> Result<(), PulsarError> {
+        let escrow_key = DataKey::PurchaseEscrow(listing_id, buyer.clone());
+        let mut escrow: PurchaseEscrow = env
+            .storage()
+            .persistent()
+            .get(&escrow_key)
+            .ok_or(PulsarError::NotFound)?;
+        if escrow.status != EscrowStatus::Held {
+            return Err(PulsarError::InvalidState);
+        }
+        let listing: CreativeListing = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Listing(listing_id))
+            .unwrap();
+
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenAddress)
+            .unwrap();
+        let token_client = token::Client::new(env, &token_addr);
+        Self::_collect_sale_payment(
+            env,
+            &token_client,
+            &env.current_contract_address(),
+            &listing,
+            escrow.amount,
+        );
+
+        escrow.status = EscrowStatus::Released;
+        env.storage().persistent().set(&escrow_key, &escrow);
+        env.storage().persistent().extend_ttl(
+            &escrow_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("escrow"),
+            symbol_short!("released"),
+            (listing_id, buyer.clone(), escrow.amount),
+        );
+        Ok(())
+    }
+
+    /// Count a sale on the listing, its current sales period and the
+    /// category leaderboard. The caller persists the listing.
+    fn _record_sale(env: &Env, listing: &mut CreativeListing) {
+        listing.sale_count += 1;
+
+        let period = env.ledger().timestamp() / SALES_PERIOD;
+        if listing.sales_period != period {
+            listing.sales_period = period;
+            listing.period_sales = 0;
+        }
+        listing.period_sales += 1;
+        let _ttl_key = DataKey::PeriodSales(listing.listing_id, period);
+        env.storage()
+            .persistent()
+            .set(&_ttl_key, &listing.period_sales);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let board_key = DataKey::TopListings(listing.category.clone());
+        let board: Vec<(u64, u64)> = env
+            .storage()
+            .persistent()
+            .get(&board_key)
+            .unwrap_or(Vec::new(env));
+        let mut updated: Vec<(u64, u64)> = Vec::new(env);
+        let mut inserted = false;
+        for (id, sales) in board.iter() {
+            if id == listing.listing_id {
+                continue;
+            }
+            if !inserted && listing.sale_count > sales {
+                updated.push_back((listing.listing_id, listing.sale_count));
+                inserted = true;
+            }
+            updated.push_back((id, sales));
+        }
+        if !inserted {
+            updated.push_back((listing.listing_id, listing.sale_count));
+        }
+        while updated.len() > LEADERBOARD_SIZE {
+            updated.pop_back();
+        }
+        env.storage().persistent().set(&board_key, &updated);
+        env.storage().persistent().extend_ttl(
+            &board_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// Revoked licenses are never valid. Recurring licenses stay valid
+    /// through the renewal grace period; others are valid until `expires_at`.
+    fn _is_license_valid(env: &Env, license: &License) -> bool {
+        if license.revoked_at.is_some() {
+            return false;
+        }
+        match license.expires_at {
+            Some(expires) if matches!(license.license_type, LicenseType::Recurring) => {
+                expires + RENEWAL_GRACE_PERIOD > env.ledger().timestamp()
+            }
+            Some(expires) => expires > env.ledger().timestamp(),
+            None => true,
+        }
+    }
+
+    /// Take the platform fee out of a sale and pay the rest to the creator
+    /// side of the listing.
+    fn _collect_sale_payment(
+        env: &Env,
+        token_client: &token::Client,
+        from: &Address,
+        listing: &CreativeListing,
+        amount: i128,
+    ) {
+        let fee_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PlatformFeeBps)
+            .unwrap_or(250);
+        let fee = (amount * fee_bps as i128) / 10_000;
+        Self::_pay_creator_share(env, token_client, from, listing, amount - fee);
+
+        if fee > 0 {
+            Self::_route_fee(env, token_client, from, listing.listing_id, fee);
+        }
+    }
+
+    /// Send a platform fee to the fee router, or the treasury when no router
+    /// is set. Until either is configured fees accrue in the contract and are
+    /// swept by `set_treasury`.
+    fn _route_fee(
+        env: &Env,
+        token_client: &token::Client,
+        from: &Address,
+        listing_id: u64,
+        fee: i128,
+    ) {
+        if let Some(router) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::FeeRouter)
+        {
+            token_client.transfer(from, &router, &fee);
+            let _: () = env.invoke_contract(
+                &router,
+                &Symbol::new(env, "route_fee"),
+                Vec::from_array(
+                    env,
+                    [
+                        env.current_contract_address().into_val(env),
+                        token_client.address.into_val(env),
+                        fee.into_val(env),
+                    ],
+                ),
+            );
+            pulsar_common_admin::publish_event(
+                env,
+                symbol_short!("fee"),
+                symbol_short!("routed"),
+                (listing_id, router, fee),
+            );
+            return;
+        }
+        let destination = match env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::Treasury)
+        {
+            Some(treasury) => treasury,
+            None => {
+                let accrued: i128 = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::AccruedFees)
+                    .unwrap_or(0);
+                env.storage()
+                    .instance()
+                    .set(&DataKey::AccruedFees, &(accrued + fee));
+                env.current_contract_address()
+            }
+        };
+        if *from != destination {
+            token_client.transfer(from, &destination, &fee);
+        }
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("fee"),
+            symbol_short!("routed"),
+            (listing_id, destination, fee),
+        );
+    }
+
+    /// Split `amount` between the listing's collaborators and its creator.
+    /// The creator receives whatever is left after collaborator shares, so
+    /// rounding dust never gets stuck in the payer's account.
+    fn _pay_creator_share(
+        env: &Env,
+        token_client: &token::Client,
+        from: &Address,
+        listing: &CreativeListing,
+        amount: i128,
+    ) {
+        let collaborators: Vec<(Address, u32)> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Collaborators(listing.listing_id))
+            .unwrap_or(Vec::new(env));
+
+        let mut remaining = amount;
+        for (collaborator, share_bps) in collaborators.iter() {
+            let share = (amount * share_bps as i128) / 10_000;
+            if share > 0 {
+                token_client.transfer(from, &collaborator, &share);
+                remaining -= share;
+                pulsar_common_admin::publish_event(
+                    env,
+                    symbol_short!("royalty"),
+                    symbol_short!("paid"),
+                    (listing.listing_id, collaborator, share),
+                );
+            }
+        }
+
+        if remaining > 0 {
+            token_client.transfer(from, &listing.creator, &remaining);
+        }
+    }
+
+    fn _require_verified_creator(env: &Env, creator: &Address) -> Result<(), PulsarError> {
+        if let Some(registry) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::IdentityRegistry)
+        {
+            let verified: bool = env.invoke_contract(
+                &registry,
+                &Symbol::new(env, "is_verified"),
+                Vec::from_array(env, [creator.into_val(env)]),
+            );
+            if !verified {
+                return Err(PulsarError::NotVerified);
+            }
+        }
+        Ok(())
+    }
+
+    fn _require_content_available(env: &Env, content_hash: &String) -> Result<(), PulsarError> {
+        // Check for duplicate content hash with exclusive license
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::ContentOwner(content_hash.clone()))
+        {
+            return Err(PulsarError::AlreadyExists);
+        }
+        Ok(())
+    }
+
+    fn _next_listing_id(env: &Env) -> u64 {
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ListingCounter)
+            .unwrap_or(0);
+        counter + 1
+    }
+
+    /// Persist a freshly created listing and register it in the discovery
+    /// indexes and, for exclusive content, the content-owner map.
+    fn _store_new_listing(env: &Env, listing: &CreativeListing) {
+        let listing_id = listing.listing_id;
+        let _ttl_key = DataKey::Listing(listing_id);
+        env.storage().persistent().set(&_ttl_key, listing);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::ListingCounter, &listing_id);
+
+        let creator = listing.creator.clone();
+        Self::_index_listing(
+            env,
+            DataKey::CreatorListingCount(creator.clone()),
+            |i| DataKey::ListingsByCreator(creator.clone(), i),
+            listing_id,
+        );
+        let category = listing.category.clone();
+        Self::_index_listing(
+            env,
+            DataKey::CategoryListingCount(category.clone()),
+            |i| DataKey::ListingsByCategory(category.clone(), i),
+            listing_id,
+        );
+
+        Self::_record_version(env, listing);
+    }
+
+    /// Append the listing's current content hash to its version history and,
+    /// for exclusive content, claim the hash in the content-owner map.
+    fn _record_version(env: &Env, listing: &CreativeListing) {
+        let version = ContentVersion {
+            version: listing.version,
+            content_hash: listing.content_hash.clone(),
+            published_at: env.ledger().timestamp(),
+        };
+        let _ttl_key = DataKey::ListingVersion(listing.listing_id, listing.version);
+        env.storage().persistent().set(&_ttl_key, &version);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        // If this is an exclusive license, mark content as owned
+        if matches!(listing.license_type, LicenseType::Exclusive) {
+            let content_key = DataKey::ContentOwner(listing.content_hash.clone());
+            env.storage()
+                .persistent()
+                .set(&content_key, &listing.listing_id);
+            env.storage().persistent().extend_ttl(
+                &content_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+    }
+
+    /// Release every content hash an exclusive listing has claimed.
+    fn _release_content(env: &Env, listing: &CreativeListing) {
+        if !matches!(listing.license_type, LicenseType::Exclusive) {
+            return;
+        }
+        for v in 1..=listing.version {
+            if let Some(version) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, ContentVersion>(&DataKey::ListingVersion(listing.listing_id, v))
+            {
+                let content_key = DataKey::ContentOwner(version.content_hash);
+                if env.storage().persistent().has(&content_key) {
+                    env.storage().persistent().remove(&content_key);
+                }
+            }
+        }
+    }
+
+    fn _index_listing<F>(env: &Env, count_key: DataKey, entry_key: F, listing_id: u64)
+    where
+        F: Fn(u32) -> DataKey,
+    {
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let key = entry_key(count);
+        env.storage().persistent().set(&key, &listing_id);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage().persistent().set(&count_key, &(count + 1));
+        env.storage().persistent().extend_ttl(
+            &count_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _collect_indexed<F>(
+        env: &Env,
+        count: u32,
+        start: u32,
+        limit: u32,
+        entry_key: F,
+    ) -> Vec<CreativeListing>
+    where
+        F: Fn(u32) -> DataKey,
+    {
+        let end = count.min(start.saturating_add(limit.min(MAX_PAGE_SIZE)));
+        let mut result = Vec::new(env);
+        for i in start..end {
+            if let Some(listing_id) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, u64>(&entry_key(i))
+            {
+                if let Some(listing) = env
+                    .storage()
+                    .persistent()
+                    .get::<DataKey, CreativeListing>(&DataKey::Listing(listing_id))
+                {
+                    result.push_back(listing);
+                }
+            }
+        }
+        result
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::propose_admin(
+            &env,
+            &DataKey::Admin,
+            &DataKey::PendingAdmin,
+            current_admin,
+            new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
@@ -1,6 +1,10 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, Env, String};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env, IntoVal, String, Vec,
+};
 
 fn deploy_token(env: &Env, admin: &Address) -> Address {
     env.register_stellar_asset_contract_v2(admin.clone())
@@ -21,7 +25,7 @@ fn setup(
     let admin = Address::generate(env);
     let token_admin = Address::generate(env);
     let token = deploy_token(env, &token_admin);
-    let id = env.register_contract(None, CreativeMarketplaceContract);
+    let id = env.register(CreativeMarketplaceContract, ());
     let c = CreativeMarketplaceContractClient::new(env, &id);
     c.initialize(&admin, &token);
     (c, admin, token_admin, token)
@@ -38,12 +42,14 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, admin, _, token) = setup(&env);
-    c.initialize(&admin, &token);
+    assert_eq!(
+        c.try_initialize(&admin, &token),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -59,6 +65,8 @@ fn test_create_listing() {
         &s(&env, "A beautiful banner"),
         &10_000i128,
         &LicenseType::OneTime,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
     );
     assert_eq!(listing_id, 1);
     let listing = c.get_listing(&listing_id).unwrap();
@@ -66,6 +74,36 @@ fn test_create_listing() {
     assert!(matches!(listing.status, ListingStatus::Active));
 }
 
+#[test]
+fn test_list_listings_pages_by_cursor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, _) = setup(&env);
+    let creator = Address::generate(&env);
+    for hash in ["QmA", "QmB", "QmC"] {
+        c.create_listing(
+            &creator,
+            &s(&env, hash),
+            &s(&env, "Banner Ad"),
+            &s(&env, "A beautiful banner"),
+            &10_000i128,
+            &LicenseType::OneTime,
+            &symbol_short!("banner"),
+            &Vec::new(&env),
+        );
+    }
+
+    let (page, cursor) = c.list_listings(&1u64, &1u32);
+    assert_eq!(page.get(0).unwrap().listing_id, 2);
+    assert_eq!(cursor, Some(2));
+    let (page, cursor) = c.list_listings(&2u64, &50u32);
+    assert_eq!(page.len(), 1);
+    assert_eq!(cursor, None);
+    let (page, cursor) = c.list_listings(&u64::MAX, &50u32);
+    assert_eq!(page.len(), 0);
+    assert_eq!(cursor, None);
+}
+
 #[test]
 fn test_purchase_license() {
     let env = Env::default();
@@ -81,6 +119,8 @@ fn test_purchase_license() {
         &s(&env, "Desc"),
         &10_000i128,
         &LicenseType::OneTime,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
     );
     c.purchase_license(&buyer, &listing_id, &Some(86_400u64));
     assert!(c.has_license(&listing_id, &buyer));
@@ -101,6 +141,8 @@ fn test_remove_listing() {
         &s(&env, "Desc"),
         &10_000i128,
         &LicenseType::OneTime,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
     );
     c.remove_listing(&creator, &listing_id);
     let listing = c.get_listing(&listing_id).unwrap();
@@ -124,7 +166,6 @@ fn test_has_license_false() {
 }
 
 #[test]
-#[should_panic(expected = "content already listed - check for exclusive licenses")]
 fn test_duplicate_exclusive_content_blocked() {
     let env = Env::default();
     env.mock_all_auths();
@@ -140,17 +181,22 @@ fn test_duplicate_exclusive_content_blocked() {
         &s(&env, "First listing"),
         &50_000i128,
         &LicenseType::Exclusive,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
     );
 
     // Attempt to create second listing with same content hash - should panic
-    c.create_listing(
+    let result = c.try_create_listing(
         &creator,
         &content_hash,
         &s(&env, "Duplicate Banner"),
         &s(&env, "Second listing"),
         &30_000i128,
         &LicenseType::Exclusive,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
     );
+    assert_eq!(result, Err(Ok(PulsarError::AlreadyExists)));
 }
 
 #[test]
@@ -169,6 +215,8 @@ fn test_non_exclusive_allows_duplicate_content() {
         &s(&env, "First listing"),
         &10_000i128,
         &LicenseType::OneTime,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
     );
 
     // Create second listing with same content hash - should succeed for non-exclusive
@@ -179,6 +227,8 @@ fn test_non_exclusive_allows_duplicate_content() {
         &s(&env, "Second listing"),
         &15_000i128,
         &LicenseType::Recurring,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
     );
 
     assert_eq!(listing_id_1, 1);
@@ -201,6 +251,8 @@ fn test_remove_exclusive_listing_allows_recreation() {
         &s(&env, "First listing"),
         &50_000i128,
         &LicenseType::Exclusive,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
     );
 
     // Remove the listing
@@ -214,6 +266,8 @@ fn test_remove_exclusive_listing_allows_recreation() {
         &s(&env, "Second listing after removal"),
         &60_000i128,
         &LicenseType::Exclusive,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
     );
 
     assert_eq!(listing_id_2, 2);
@@ -237,6 +291,8 @@ fn test_exclusive_license_marks_sold() {
         &s(&env, "Exclusive content"),
         &100_000i128,
         &LicenseType::Exclusive,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
     );
 
     // Purchase exclusive license
@@ -246,3 +302,996 @@ fn test_exclusive_license_marks_sold() {
     let listing = c.get_listing(&listing_id).unwrap();
     assert!(matches!(listing.status, ListingStatus::Sold));
 }
+
+#[test]
+fn test_purchase_splits_royalties_across_collaborators() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let treasury = Address::generate(&env);
+    c.set_treasury(&admin, &treasury);
+    let creator = Address::generate(&env);
+    let designer = Address::generate(&env);
+    let copywriter = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &buyer, 1_000_000);
+
+    let listing_id = c.create_listing(
+        &creator,
+        &s(&env, "QmTeamWork"),
+        &s(&env, "Team Banner"),
+        &s(&env, "Made by a team"),
+        &100_000i128,
+        &LicenseType::OneTime,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
+    );
+    c.set_collaborators(
+        &creator,
+        &listing_id,
+        &vec![
+            &env,
+            (designer.clone(), 3_000u32),
+            (copywriter.clone(), 1_000u32),
+        ],
+    );
+    assert_eq!(c.get_collaborators(&listing_id).len(), 2);
+
+    c.purchase_license(&buyer, &listing_id, &None);
+
+    // 2.5% fee = 2_500; creator proceeds = 97_500
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&treasury), 2_500);
+    assert_eq!(tc.balance(&admin), 0);
+    assert_eq!(tc.balance(&designer), 29_250);
+    assert_eq!(tc.balance(&copywriter), 9_750);
+    assert_eq!(tc.balance(&creator), 58_500);
+    assert_eq!(tc.balance(&buyer), 900_000);
+}
+
+#[test]
+fn test_set_collaborators_exceeding_total_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, _) = setup(&env);
+    let creator = Address::generate(&env);
+    let listing_id = c.create_listing(
+        &creator,
+        &s(&env, "QmHash"),
+        &s(&env, "Banner"),
+        &s(&env, "Desc"),
+        &10_000i128,
+        &LicenseType::OneTime,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
+    );
+    let result = c.try_set_collaborators(
+        &creator,
+        &listing_id,
+        &vec![
+            &env,
+            (Address::generate(&env), 6_000u32),
+            (Address::generate(&env), 5_000u32),
+        ],
+    );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidInput)));
+}
+
+#[test]
+fn test_set_collaborators_non_creator_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, _) = setup(&env);
+    let creator = Address::generate(&env);
+    let listing_id = c.create_listing(
+        &creator,
+        &s(&env, "QmHash"),
+        &s(&env, "Banner"),
+        &s(&env, "Desc"),
+        &10_000i128,
+        &LicenseType::OneTime,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
+    );
+    let result = c.try_set_collaborators(
+        &Address::generate(&env),
+        &listing_id,
+        &vec![&env, (Address::generate(&env), 1_000u32)],
+    );
+    assert_eq!(result, Err(Ok(PulsarError::Unauthorized)));
+}
+
+#[test]
+fn test_transfer_license_pays_seller_and_creator_royalty() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &seller, 100_000);
+    mint(&env, &token, &buyer, 100_000);
+
+    let listing_id = c.create_listing(
+        &creator,
+        &s(&env, "QmResale"),
+        &s(&env, "Banner"),
+        &s(&env, "Desc"),
+        &10_000i128,
+        &LicenseType::OneTime,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
+    );
+    c.set_resale_royalty(&creator, &listing_id, &1_000u32);
+    c.purchase_license(&seller, &listing_id, &None);
+
+    let tc = TokenClient::new(&env, &token);
+    let creator_before = tc.balance(&creator);
+    c.transfer_license(&seller, &listing_id, &buyer, &20_000i128);
+
+    assert!(!c.has_license(&listing_id, &seller));
+    assert!(c.has_license(&listing_id, &buyer));
+    assert_eq!(
+        c.get_license(&listing_id, &buyer).unwrap().paid_amount,
+        20_000
+    );
+    assert_eq!(tc.balance(&creator) - creator_before, 2_000);
+    assert_eq!(tc.balance(&seller), 90_000 + 18_000);
+    assert_eq!(tc.balance(&buyer), 80_000);
+}
+
+#[test]
+fn test_free_transfer_still_pays_minimum_royalty() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &seller, 100_000);
+    mint(&env, &token, &buyer, 100_000);
+
+    let listing_id = create_one_time(&env, &c, &creator);
+    c.set_resale_royalty(&creator, &listing_id, &1_000u32);
+    c.purchase_license(&seller, &listing_id, &None);
+
+    let tc = TokenClient::new(&env, &token);
+    let creator_before = tc.balance(&creator);
+    let seller_before = tc.balance(&seller);
+    c.transfer_license(&seller, &listing_id, &buyer, &0i128);
+
+    // 10% of the listing price, paid by the new licensee
+    let royalty = c.get_listing(&listing_id).unwrap().price / 10;
+    assert!(royalty > 0);
+    assert_eq!(tc.balance(&creator) - creator_before, royalty);
+    assert_eq!(tc.balance(&buyer), 100_000 - royalty);
+    assert_eq!(tc.balance(&seller), seller_before);
+    assert!(c.has_license(&listing_id, &buyer));
+}
+
+#[test]
+fn test_transfer_exclusive_license_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let seller = Address::generate(&env);
+    mint(&env, &token, &seller, 100_000);
+
+    let listing_id = c.create_listing(
+        &creator,
+        &s(&env, "QmExclusiveResale"),
+        &s(&env, "Banner"),
+        &s(&env, "Desc"),
+        &10_000i128,
+        &LicenseType::Exclusive,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
+    );
+    c.purchase_license(&seller, &listing_id, &None);
+    assert_eq!(
+        c.try_transfer_license(&seller, &listing_id, &Address::generate(&env), &5_000i128),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_listing_discovery_indexes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, _) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let first = c.create_listing(
+        &alice,
+        &s(&env, "QmVideo1"),
+        &s(&env, "Video 1"),
+        &s(&env, "Desc"),
+        &10_000i128,
+        &LicenseType::OneTime,
+        &symbol_short!("video"),
+        &vec![&env, symbol_short!("sports"), symbol_short!("hd")],
+    );
+    c.create_listing(
+        &bob,
+        &s(&env, "QmBanner1"),
+        &s(&env, "Banner 1"),
+        &s(&env, "Desc"),
+        &10_000i128,
+        &LicenseType::OneTime,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
+    );
+    let third = c.create_listing(
+        &alice,
+        &s(&env, "QmVideo2"),
+        &s(&env, "Video 2"),
+        &s(&env, "Desc"),
+        &10_000i128,
+        &LicenseType::OneTime,
+        &symbol_short!("video"),
+        &Vec::new(&env),
+    );
+
+    let by_alice = c.list_by_creator(&alice, &0u32, &10u32);
+    assert_eq!(by_alice.len(), 2);
+    assert_eq!(by_alice.get(0).unwrap().listing_id, first);
+    assert_eq!(by_alice.get(1).unwrap().listing_id, third);
+    assert_eq!(by_alice.get(0).unwrap().tags.len(), 2);
+
+    let videos = c.list_by_category(&symbol_short!("video"), &1u32, &10u32);
+    assert_eq!(videos.len(), 1);
+    assert_eq!(videos.get(0).unwrap().listing_id, third);
+
+    c.remove_listing(&alice, &first);
+    let active = c.list_active(&0u64, &10u32);
+    assert_eq!(active.len(), 2);
+    assert_eq!(c.list_active(&2u64, &10u32).len(), 1);
+}
+
+#[test]
+fn test_auction_refunds_outbid_and_awards_exclusive_license() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint(&env, &token, &alice, 100_000);
+    mint(&env, &token, &bob, 100_000);
+
+    let listing_id = c.create_auction(&creator, &s(&env, "QmUnique"), &10_000i128, &100u32);
+    assert!(matches!(
+        c.get_listing(&listing_id).unwrap().status,
+        ListingStatus::Auctioning
+    ));
+
+    c.place_bid(&alice, &listing_id, &20_000i128);
+    c.place_bid(&bob, &listing_id, &40_000i128);
+
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&alice), 100_000);
+    assert_eq!(tc.balance(&bob), 60_000);
+    assert_eq!(tc.balance(&c.address), 40_000);
+
+    env.ledger().with_mut(|li| li.sequence_number = 101);
+    c.settle_auction(&listing_id);
+
+    let listing = c.get_listing(&listing_id).unwrap();
+    assert!(matches!(listing.status, ListingStatus::Sold));
+    assert!(c.has_license(&listing_id, &bob));
+    assert!(!c.has_license(&listing_id, &alice));
+    assert_eq!(tc.balance(&admin), 0);
+    assert_eq!(tc.balance(&creator), 39_000);
+    // No treasury configured yet: the fee accrues in the contract
+    assert_eq!(c.get_accrued_fees(), 1_000);
+    assert_eq!(tc.balance(&c.address), 1_000);
+}
+
+#[test]
+fn test_auction_rejects_lower_bid() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint(&env, &token, &alice, 100_000);
+    mint(&env, &token, &bob, 100_000);
+
+    let listing_id = c.create_auction(&creator, &s(&env, "QmUnique"), &10_000i128, &100u32);
+    c.place_bid(&alice, &listing_id, &20_000i128);
+    assert_eq!(
+        c.try_place_bid(&bob, &listing_id, &20_000i128),
+        Err(Ok(PulsarError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_auction_without_bids_releases_content() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, _) = setup(&env);
+    let creator = Address::generate(&env);
+    let content_hash = s(&env, "QmUnsold");
+
+    let listing_id = c.create_auction(&creator, &content_hash, &10_000i128, &100u32);
+    env.ledger().with_mut(|li| li.sequence_number = 101);
+    c.settle_auction(&listing_id);
+
+    assert!(matches!(
+        c.get_listing(&listing_id).unwrap().status,
+        ListingStatus::Unlicensed
+    ));
+    let relisted = c.create_auction(&creator, &content_hash, &5_000i128, &200u32);
+    assert_eq!(relisted, listing_id + 1);
+}
+
+#[test]
+fn test_removed_auction_cannot_be_settled() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    mint(&env, &token, &bidder, 100_000);
+
+    let removed = c.create_auction(&creator, &s(&env, "QmRemoved"), &10_000i128, &100u32);
+    c.remove_listing(&creator, &removed);
+    let taken_down = c.create_auction(&creator, &s(&env, "QmTaken"), &10_000i128, &100u32);
+    c.place_bid(&bidder, &taken_down, &20_000i128);
+    c.takedown_listing(&admin, &taken_down, &s(&env, "infringing"));
+
+    env.ledger().with_mut(|li| li.sequence_number = 101);
+    assert_eq!(
+        c.try_settle_auction(&removed),
+        Err(Ok(PulsarError::AlreadyExists))
+    );
+    assert_eq!(
+        c.try_settle_auction(&taken_down),
+        Err(Ok(PulsarError::AlreadyExists))
+    );
+    assert!(matches!(
+        c.get_listing(&removed).unwrap().status,
+        ListingStatus::Removed
+    ));
+    assert!(!c.has_license(&taken_down, &bidder));
+    assert_eq!(TokenClient::new(&env, &token).balance(&bidder), 100_000);
+}
+
+#[test]
+fn test_recurring_license_renewal_and_grace_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &buyer, 1_000_000);
+
+    let listing_id = c.create_listing(
+        &creator,
+        &s(&env, "QmMonthly"),
+        &s(&env, "Monthly Banner"),
+        &s(&env, "Desc"),
+        &10_000i128,
+        &LicenseType::Recurring,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
+    );
+    c.set_recurring_interval(&creator, &listing_id, &1_000u64);
+    c.purchase_license(&buyer, &listing_id, &None);
+
+    let license = c.get_license(&listing_id, &buyer).unwrap();
+    assert_eq!(license.expires_at, Some(1_000));
+    assert_eq!(license.renewal_interval, 1_000);
+
+    // Within the grace window after expiry the license is still usable
+    env.ledger().with_mut(|li| li.timestamp = 1_500);
+    assert!(c.has_license(&listing_id, &buyer));
+    c.renew_license(&buyer, &listing_id);
+    let license = c.get_license(&listing_id, &buyer).unwrap();
+    assert_eq!(license.expires_at, Some(2_000));
+    assert_eq!(license.paid_amount, 20_000);
+
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&buyer), 980_000);
+
+    // Missing the renewal past the grace window lapses the license
+    env.ledger().with_mut(|li| li.timestamp = 2_000 + 259_200);
+    assert!(!c.has_license(&listing_id, &buyer));
+}
+
+#[test]
+fn test_renew_lapsed_license_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &buyer, 1_000_000);
+
+    let listing_id = c.create_listing(
+        &creator,
+        &s(&env, "QmMonthly"),
+        &s(&env, "Monthly Banner"),
+        &s(&env, "Desc"),
+        &10_000i128,
+        &LicenseType::Recurring,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
+    );
+    c.purchase_license(&buyer, &listing_id, &None);
+    env.ledger()
+        .with_mut(|li| li.timestamp = 2_592_000 + 259_200);
+    assert_eq!(
+        c.try_renew_license(&buyer, &listing_id),
+        Err(Ok(PulsarError::Expired))
+    );
+}
+
+#[test]
+fn test_update_listing_changes_price_for_new_buyers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &buyer, 1_000_000);
+
+    let listing_id = c.create_listing(
+        &creator,
+        &s(&env, "QmHash"),
+        &s(&env, "Banner"),
+        &s(&env, "Desc"),
+        &10_000i128,
+        &LicenseType::OneTime,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
+    );
+    c.update_listing(&creator, &listing_id, &25_000i128, &s(&env, "Updated"));
+
+    let listing = c.get_listing(&listing_id).unwrap();
+    assert_eq!(listing.price, 25_000);
+    assert_eq!(listing.description, s(&env, "Updated"));
+
+    c.purchase_license(&buyer, &listing_id, &None);
+    assert_eq!(
+        c.get_license(&listing_id, &buyer).unwrap().paid_amount,
+        25_000
+    );
+}
+
+#[test]
+fn test_licenses_stay_pinned_to_purchased_version() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let early = Address::generate(&env);
+    let late = Address::generate(&env);
+    mint(&env, &token, &early, 1_000_000);
+    mint(&env, &token, &late, 1_000_000);
+
+    let listing_id = c.create_listing(
+        &creator,
+        &s(&env, "QmV1"),
+        &s(&env, "Banner"),
+        &s(&env, "Desc"),
+        &10_000i128,
+        &LicenseType::OneTime,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
+    );
+    c.purchase_license(&early, &listing_id, &None);
+
+    let version = c.publish_new_version(&creator, &listing_id, &s(&env, "QmV2"));
+    assert_eq!(version, 2);
+    c.purchase_license(&late, &listing_id, &None);
+
+    let early_version = c.get_licensed_version(&listing_id, &early).unwrap();
+    assert_eq!(early_version.version, 1);
+    assert_eq!(early_version.content_hash, s(&env, "QmV1"));
+    let late_version = c.get_licensed_version(&listing_id, &late).unwrap();
+    assert_eq!(late_version.version, 2);
+    assert_eq!(late_version.content_hash, s(&env, "QmV2"));
+    assert_eq!(
+        c.get_listing(&listing_id).unwrap().content_hash,
+        s(&env, "QmV2")
+    );
+}
+
+mod dispute_mock {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, String};
+
+    #[contract]
+    pub struct MockDisputeResolution;
+
+    #[contractimpl]
+    impl MockDisputeResolution {
+        pub fn file_listing_dispute(
+            env: Env,
+            claimant: Address,
+            respondent: Address,
+            listing_id: u64,
+            claim_amount: i128,
+            description: String,
+            evidence_hash: String,
+        ) -> u64 {
+            let _ = (claimant, respondent, description, evidence_hash);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("claim"), &(listing_id, claim_amount));
+            7
+        }
+
+        pub fn last_claim(env: Env) -> (u64, i128) {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("claim"))
+                .unwrap()
+        }
+    }
+}
+
+fn create_one_time(env: &Env, c: &CreativeMarketplaceContractClient<'_>, creator: &Address) -> u64 {
+    c.create_listing(
+        creator,
+        &s(env, "QmEscrowed"),
+        &s(env, "Banner"),
+        &s(env, "Desc"),
+        &10_000i128,
+        &LicenseType::OneTime,
+        &symbol_short!("banner"),
+        &Vec::new(env),
+    )
+}
+
+#[test]
+fn test_escrowed_purchase_released_on_confirmation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &buyer, 100_000);
+    c.set_acceptance_window(&admin, &3_600u64);
+
+    let listing_id = create_one_time(&env, &c, &creator);
+    c.purchase_license(&buyer, &listing_id, &None);
+
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&c.address), 10_000);
+    assert_eq!(tc.balance(&creator), 0);
+    assert!(c.has_license(&listing_id, &buyer));
+
+    c.confirm_delivery(&buyer, &listing_id);
+    assert_eq!(tc.balance(&creator), 9_750);
+    assert_eq!(c.get_accrued_fees(), 250);
+
+    let treasury = Address::generate(&env);
+    c.set_treasury(&admin, &treasury);
+    assert_eq!(tc.balance(&treasury), 250);
+    assert_eq!(tc.balance(&c.address), 0);
+    let escrow = c.get_purchase_escrow(&listing_id, &buyer).unwrap();
+    assert!(escrow.status == EscrowStatus::Released);
+}
+
+#[test]
+fn test_escrow_auto_releases_after_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &buyer, 100_000);
+    c.set_acceptance_window(&admin, &3_600u64);
+
+    let listing_id = create_one_time(&env, &c, &creator);
+    c.purchase_license(&buyer, &listing_id, &None);
+
+    env.ledger().with_mut(|li| li.timestamp = 3_600);
+    c.release_escrow(&listing_id, &buyer);
+    assert_eq!(TokenClient::new(&env, &token).balance(&creator), 9_750);
+}
+
+#[test]
+fn test_escrow_release_before_window_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &buyer, 100_000);
+    c.set_acceptance_window(&admin, &3_600u64);
+
+    let listing_id = create_one_time(&env, &c, &creator);
+    c.purchase_license(&buyer, &listing_id, &None);
+    assert_eq!(
+        c.try_release_escrow(&listing_id, &buyer),
+        Err(Ok(PulsarError::TooEarly))
+    );
+}
+
+#[test]
+fn test_transfer_license_blocked_while_escrow_held() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    c.set_acceptance_window(&admin, &3_600u64);
+    let creator = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &seller, 100_000);
+    mint(&env, &token, &buyer, 100_000);
+
+    let listing_id = create_one_time(&env, &c, &creator);
+    c.purchase_license(&seller, &listing_id, &None);
+    assert_eq!(
+        c.try_transfer_license(&seller, &listing_id, &buyer, &20_000i128),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_transfer_license_allowed_after_escrow_release() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    c.set_acceptance_window(&admin, &3_600u64);
+    let creator = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &seller, 100_000);
+    mint(&env, &token, &buyer, 100_000);
+
+    let listing_id = create_one_time(&env, &c, &creator);
+    c.purchase_license(&seller, &listing_id, &None);
+    c.confirm_delivery(&seller, &listing_id);
+    c.transfer_license(&seller, &listing_id, &buyer, &20_000i128);
+    assert!(c.has_license(&listing_id, &buyer));
+    assert!(!c.has_license(&listing_id, &seller));
+}
+
+#[test]
+fn test_dispute_purchase_escalates_to_dispute_resolution() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let disputes = env.register(dispute_mock::MockDisputeResolution, ());
+    c.set_dispute_contract(&admin, &disputes);
+    c.set_acceptance_window(&admin, &3_600u64);
+
+    let creator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &buyer, 100_000);
+    let listing_id = create_one_time(&env, &c, &creator);
+    c.purchase_license(&buyer, &listing_id, &None);
+
+    let dispute_id = c.dispute_purchase(
+        &buyer,
+        &listing_id,
+        &s(&env, "Creative files are corrupted"),
+        &s(&env, "QmEvidence"),
+    );
+    assert_eq!(dispute_id, 7);
+
+    let escrow = c.get_purchase_escrow(&listing_id, &buyer).unwrap();
+    assert!(escrow.status == EscrowStatus::Disputed);
+    assert_eq!(escrow.dispute_id, Some(7));
+    assert!(!c.has_license(&listing_id, &buyer));
+    assert_eq!(
+        dispute_mock::MockDisputeResolutionClient::new(&env, &disputes).last_claim(),
+        (listing_id, 10_000)
+    );
+    // The escrowed funds were handed back for dispute-resolution to lock
+    assert_eq!(TokenClient::new(&env, &token).balance(&c.address), 0);
+    assert_eq!(TokenClient::new(&env, &token).balance(&buyer), 100_000);
+}
+
+mod identity_mock {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockIdentityRegistry;
+
+    #[contractimpl]
+    impl MockIdentityRegistry {
+        pub fn set_verified(env: Env, account: Address, verified: bool) {
+            env.storage().persistent().set(&account, &verified);
+        }
+
+        pub fn is_verified(env: Env, account: Address) -> bool {
+            env.storage().persistent().get(&account).unwrap_or(false)
+        }
+    }
+}
+
+#[test]
+fn test_verified_creator_can_list() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, _) = setup(&env);
+    let registry = env.register(identity_mock::MockIdentityRegistry, ());
+    c.set_identity_registry(&admin, &registry);
+
+    let creator = Address::generate(&env);
+    identity_mock::MockIdentityRegistryClient::new(&env, &registry).set_verified(&creator, &true);
+    assert_eq!(create_one_time(&env, &c, &creator), 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #13)")]
+fn test_unverified_creator_cannot_list() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, _) = setup(&env);
+    let registry = env.register(identity_mock::MockIdentityRegistry, ());
+    c.set_identity_registry(&admin, &registry);
+    create_one_time(&env, &c, &Address::generate(&env));
+}
+
+#[test]
+fn test_takedown_blocks_future_purchases() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let moderator = Address::generate(&env);
+    c.add_moderator(&admin, &moderator);
+
+    let creator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &buyer, 100_000);
+    let listing_id = create_one_time(&env, &c, &creator);
+
+    c.takedown_listing(&moderator, &listing_id, &s(&env, "DMCA notice #42"));
+    assert!(matches!(
+        c.get_listing(&listing_id).unwrap().status,
+        ListingStatus::TakenDown
+    ));
+    assert_eq!(
+        c.try_purchase_license(&buyer, &listing_id, &None),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_takedown_by_non_moderator_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, _) = setup(&env);
+    let creator = Address::generate(&env);
+    let listing_id = create_one_time(&env, &c, &creator);
+    assert_eq!(
+        c.try_takedown_listing(&creator, &listing_id, &s(&env, "self takedown")),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_revoke_license_by_creator() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &buyer, 100_000);
+    let listing_id = create_one_time(&env, &c, &creator);
+    c.purchase_license(&buyer, &listing_id, &None);
+
+    c.revoke_license(
+        &creator,
+        &listing_id,
+        &buyer,
+        &s(&env, "used outside licensed territory"),
+        &false,
+    );
+    assert!(!c.has_license(&listing_id, &buyer));
+    assert!(c
+        .get_license(&listing_id, &buyer)
+        .unwrap()
+        .revoked_at
+        .is_some());
+}
+
+#[test]
+fn test_revoke_recurring_license_with_prorated_refund() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &buyer, 100_000);
+
+    let listing_id = c.create_listing(
+        &creator,
+        &s(&env, "QmMonthly"),
+        &s(&env, "Monthly Banner"),
+        &s(&env, "Desc"),
+        &10_000i128,
+        &LicenseType::Recurring,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
+    );
+    c.set_recurring_interval(&creator, &listing_id, &1_000u64);
+    c.purchase_license(&buyer, &listing_id, &None);
+
+    // A quarter of the period used -> three quarters refunded
+    env.ledger().with_mut(|li| li.timestamp = 250);
+    c.revoke_license(&creator, &listing_id, &buyer, &s(&env, "violation"), &true);
+
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&buyer), 90_000 + 7_500);
+    assert_eq!(tc.balance(&creator), 9_750 - 7_500);
+    assert!(!c.has_license(&listing_id, &buyer));
+}
+
+#[test]
+fn test_admin_revoke_refunds_without_creator_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &buyer, 100_000);
+    mint(&env, &token, &admin, 10_000);
+
+    let listing_id = c.create_listing(
+        &creator,
+        &s(&env, "QmMonthly"),
+        &s(&env, "Monthly Banner"),
+        &s(&env, "Desc"),
+        &10_000i128,
+        &LicenseType::Recurring,
+        &symbol_short!("banner"),
+        &Vec::new(&env),
+    );
+    c.set_recurring_interval(&creator, &listing_id, &1_000u64);
+    c.purchase_license(&buyer, &listing_id, &None);
+    env.ledger().with_mut(|li| li.timestamp = 250);
+
+    // Only the admin signs; the refund comes out of the admin's funds
+    let reason = s(&env, "violation");
+    env.mock_auths(&[MockAuth {
+        address: &admin,
+        invoke: &MockAuthInvoke {
+            contract: &c.address,
+            fn_name: "revoke_license",
+            args: (&admin, listing_id, &buyer, reason.clone(), true).into_val(&env),
+            sub_invokes: &[MockAuthInvoke {
+                contract: &token,
+                fn_name: "transfer",
+                args: (&admin, &buyer, 7_500i128).into_val(&env),
+                sub_invokes: &[],
+            }],
+        },
+    }]);
+    c.revoke_license(&admin, &listing_id, &buyer, &reason, &true);
+
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&buyer), 90_000 + 7_500);
+    assert_eq!(tc.balance(&admin), 2_500);
+    assert_eq!(tc.balance(&creator), 9_750);
+    assert!(!c.has_license(&listing_id, &buyer));
+}
+
+#[test]
+fn test_revoke_license_by_stranger_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &buyer, 100_000);
+    let listing_id = create_one_time(&env, &c, &creator);
+    c.purchase_license(&buyer, &listing_id, &None);
+    let result = c.try_revoke_license(
+        &Address::generate(&env),
+        &listing_id,
+        &buyer,
+        &s(&env, "no reason"),
+        &false,
+    );
+    assert_eq!(result, Err(Ok(PulsarError::Unauthorized)));
+}
+
+#[test]
+fn test_rate_creative_updates_average() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint(&env, &token, &alice, 100_000);
+    mint(&env, &token, &bob, 100_000);
+    let listing_id = create_one_time(&env, &c, &creator);
+    c.purchase_license(&alice, &listing_id, &None);
+    c.purchase_license(&bob, &listing_id, &None);
+
+    c.rate_creative(&alice, &listing_id, &5u32);
+    c.rate_creative(&bob, &listing_id, &2u32);
+    assert_eq!(c.get_listing(&listing_id).unwrap().average_rating, 350);
+
+    // Re-rating replaces the earlier score
+    c.rate_creative(&bob, &listing_id, &4u32);
+    let listing = c.get_listing(&listing_id).unwrap();
+    assert_eq!(listing.rating_count, 2);
+    assert_eq!(listing.average_rating, 450);
+    assert_eq!(listing.period_sales, 2);
+    assert_eq!(c.get_period_sales(&listing_id, &0u64), 2);
+}
+
+#[test]
+fn test_rate_creative_without_license_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, _) = setup(&env);
+    let creator = Address::generate(&env);
+    let listing_id = create_one_time(&env, &c, &creator);
+    assert_eq!(
+        c.try_rate_creative(&Address::generate(&env), &listing_id, &5u32),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_top_listings_ranked_by_sales() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token) = setup(&env);
+    let creator = Address::generate(&env);
+    let first = create_one_time(&env, &c, &creator);
+    let second = create_one_time(&env, &c, &creator);
+
+    let buyers = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for buyer in buyers.iter() {
+        mint(&env, &token, buyer, 100_000);
+    }
+    c.purchase_license(&buyers[0], &first, &None);
+    c.purchase_license(&buyers[1], &second, &None);
+    c.purchase_license(&buyers[2], &second, &None);
+
+    let top = c.get_top_listings(&symbol_short!("banner"), &10u32);
+    assert_eq!(top.len(), 2);
+    assert_eq!(top.get(0).unwrap().listing_id, second);
+    assert_eq!(top.get(1).unwrap().listing_id, first);
+    assert_eq!(c.get_top_listings(&symbol_short!("banner"), &1u32).len(), 1);
+}
+
+#[test]
+fn test_platform_fee_routed_to_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let treasury = Address::generate(&env);
+    c.set_treasury(&admin, &treasury);
+    assert_eq!(c.get_treasury(), Some(treasury.clone()));
+
+    let creator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    mint(&env, &token, &buyer, 100_000);
+    let listing_id = create_one_time(&env, &c, &creator);
+    c.purchase_license(&buyer, &listing_id, &None);
+
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&treasury), 250);
+    assert_eq!(tc.balance(&admin), 0);
+    assert_eq!(c.get_accrued_fees(), 0);
+}
+
+#[test]
+fn test_set_treasury_non_admin_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, _) = setup(&env);
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        c.try_set_treasury(&stranger, &stranger),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
@@ -1,5 +1,10 @@
 //! PulsarTrack - Dispute Resolution (Soroban)
 //! On-chain dispute resolution for PulsarTrack ecosystem participants on Stellar.
+//!
+//! The contract tracks each respondent's open exposure (claims filed and not
+//! yet resolved). Crossing the configured threshold emits
+//! ("dispute", "exposure") and, when a fraud contract is set, flags the
+//! respondent there. The fraud contract must list this contract as an oracle.
 
 #![no_std]
 use soroban_sdk::{
@@ -7,6 +12,8 @@ use soroban_sdk::{
     Symbol, Vec as SdkVec,
 };
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone, PartialEq)]
 pub enum DisputeStatus {
@@ -35,7 +42,8 @@ pub struct Dispute {
     pub dispute_id: u64,
     pub claimant: Address,
     pub respondent: Address,
-    pub campaign_id: u64,
+    pub campaign_id: u64,        // 0 for listing disputes
+    pub listing_id: Option<u64>, // creative-marketplace listing under dispute
     pub claim_amount: i128,
     pub token: Address,
     pub description: String,
@@ -46,6 +54,16 @@ pub struct Dispute {
     pub filed_at: u64,
     pub resolved_at: Option<u64>,
     pub arbitrator: Option<Address>,
+    pub filing_fee: i128, // fee actually paid; refunded on resolution
+}
+
+/// Filing fee as a share of the claim, clamped to `[min_fee, max_fee]`
+#[contracttype]
+#[derive(Clone)]
+pub struct FilingFeeSchedule {
+    pub fee_bps: u32,
+    pub min_fee: i128,
+    pub max_fee: i128, // 0 = no cap
 }
 
 #[contracttype]
@@ -55,12 +73,16 @@ pub enum DataKey {
     PendingAdmin,
     ArbitratorPool,
     DisputeCounter,
-    FilingFee,
+    FilingFee, // legacy flat fee, read only when no schedule is stored
+    FilingFeeSchedule,
     TokenAddress,
     EscrowContract,
     Dispute(u64),
     DisputeEscrow(u64),
     ArbitratorApproved(Address),
+    OpenExposure(Address), // respondent -> sum of open claim amounts
+    ExposureThreshold,
+    FraudContract,
 }
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
@@ -68,37 +90,60 @@ const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 34_560;
 const PERSISTENT_BUMP_AMOUNT: u32 = 259_200;
 
+const TOTAL_BPS: u32 = 10_000;
+
 #[contract]
 pub struct DisputeResolutionContract;
 
 #[contractimpl]
 impl DisputeResolutionContract {
-    pub fn initialize(env: Env, admin: Address, token: Address, filing_fee: i128) {
+    /// `filing_fee` becomes a flat fee; use `set_filing_fee_schedule` to make
+    /// it proportional to the claim.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        filing_fee: i128,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::TokenAddress, &token);
-        env.storage()
-            .instance()
-            .set(&DataKey::FilingFee, &filing_fee);
+        env.storage().instance().set(
+            &DataKey::FilingFeeSchedule,
+            &FilingFeeSchedule {
+                fee_bps: 0,
+                min_fee: filing_fee,
+                max_fee: 0,
+            },
+        );
         env.storage()
             .instance()
             .set(&DataKey::DisputeCounter, &0u64);
+        Ok(())
     }
 
-    pub fn authorize_arbitrator(env: Env, admin: Address, arbitrator: Address) {
+    pub fn authorize_arbitrator(
+        env: Env,
+        admin: Address,
+        arbitrator: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         let _ttl_key = DataKey::ArbitratorApproved(arbitrator);
         env.storage().persistent().set(&_ttl_key, &true);
@@ -107,95 +152,155 @@ impl DisputeResolutionContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
-    pub fn file_dispute(
+    pub fn set_filing_fee_schedule(
         env: Env,
-        claimant: Address,
-        respondent: Address,
-        campaign_id: u64,
-        claim_amount: i128,
-        description: String,
-        evidence_hash: String,
-    ) -> u64 {
+        admin: Address,
+        schedule: FilingFeeSchedule,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        claimant.require_auth();
-        if claim_amount <= 0 {
-            panic!("invalid claim amount");
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        if schedule.fee_bps > TOTAL_BPS {
+            return Err(PulsarError::InvalidAmount);
         }
+        if schedule.min_fee < 0 || (schedule.max_fee != 0 && schedule.max_fee < schedule.min_fee) {
+            return Err(PulsarError::InvalidAmount);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::FilingFeeSchedule, &schedule);
+        Ok(())
+    }
 
-        // Collect filing fee
-        let fee: i128 = env
-            .storage()
+    /// Open exposure at which a respondent is signalled; 0 disables it
+    pub fn set_exposure_threshold(
+        env: Env,
+        admin: Address,
+        threshold: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
             .instance()
-            .get(&DataKey::FilingFee)
-            .unwrap_or(0);
-        let token_addr: Address = env
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey::TokenAddress)
-            .unwrap();
-        let token_client = token::Client::new(&env, &token_addr);
-        if fee > 0 {
-            token_client.transfer(&claimant, &env.current_contract_address(), &fee);
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
         }
+        if threshold < 0 {
+            return Err(PulsarError::InvalidInput);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ExposureThreshold, &threshold);
+        Ok(())
+    }
 
-        // Lock claim funds in this contract until dispute settlement.
-        token_client.transfer(&claimant, &env.current_contract_address(), &claim_amount);
-
-        let counter: u64 = env
+    pub fn set_fraud_contract(
+        env: Env,
+        admin: Address,
+        fraud_contract: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey::DisputeCounter)
-            .unwrap_or(0);
-        let dispute_id = counter + 1;
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::FraudContract, &fraud_contract);
+        Ok(())
+    }
 
-        let dispute = Dispute {
-            dispute_id,
-            claimant: claimant.clone(),
+    pub fn file_dispute(
+        env: Env,
+        claimant: Address,
+        respondent: Address,
+        campaign_id: u64,
+        claim_amount: i128,
+        description: String,
+        evidence_hash: String,
+    ) -> Result<u64, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_file_dispute(
+            &env,
+            claimant,
             respondent,
             campaign_id,
+            None,
             claim_amount,
-            token: token_addr,
             description,
             evidence_hash,
-            status: DisputeStatus::Filed,
-            outcome: DisputeOutcome::Pending,
-            resolution_notes: String::from_str(&env, ""),
-            filed_at: env.ledger().timestamp(),
-            resolved_at: None,
-            arbitrator: None,
-        };
+        )
+    }
 
-        let _ttl_key = DataKey::Dispute(dispute_id);
-        env.storage().persistent().set(&_ttl_key, &dispute);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+    /// File a dispute over a creative-marketplace purchase. The listing id is
+    /// kept apart from campaign ids so the two never collide.
+    pub fn file_listing_dispute(
+        env: Env,
+        claimant: Address,
+        respondent: Address,
+        listing_id: u64,
+        claim_amount: i128,
+        description: String,
+        evidence_hash: String,
+    ) -> Result<u64, PulsarError> {
         env.storage()
             .instance()
-            .set(&DataKey::DisputeCounter, &dispute_id);
-
-        env.events().publish(
-            (symbol_short!("dispute"), symbol_short!("filed")),
-            (dispute_id, claimant),
-        );
-
-        dispute_id
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_file_dispute(
+            &env,
+            claimant,
+            respondent,
+            0,
+            Some(listing_id),
+            claim_amount,
+            description,
+            evidence_hash,
+        )
     }
 
-    pub fn assign_arbitrator(env: Env, admin: Address, dispute_id: u64, arbitrator: Address) {
+    pub fn assign_arbitrator(
+        env: Env,
+        admin: Address,
+        dispute_id: u64,
+        arbitrator: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let is_authorized: bool = env
@@ -205,14 +310,14 @@ impl DisputeResolutionContract {
             .unwrap_or(false);
 
         if !is_authorized {
-            panic!("arbitrator not authorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut dispute: Dispute = env
             .storage()
             .persistent()
             .get(&DataKey::Dispute(dispute_id))
-            .expect("dispute not found");
+            .ok_or(PulsarError::NotFound)?;
 
         dispute.arbitrator = Some(arbitrator);
         dispute.status = DisputeStatus::UnderReview;
@@ -223,6 +328,7 @@ impl DisputeResolutionContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     pub fn resolve_dispute(
@@ -231,7 +337,7 @@ impl DisputeResolutionContract {
         dispute_id: u64,
         outcome: DisputeOutcome,
         notes: String,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -241,18 +347,18 @@ impl DisputeResolutionContract {
             .storage()
             .persistent()
             .get(&DataKey::Dispute(dispute_id))
-            .expect("dispute not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if let Some(ref assigned) = dispute.arbitrator {
             if *assigned != arbitrator {
-                panic!("not assigned arbitrator");
+                return Err(PulsarError::Unauthorized);
             }
         } else {
-            panic!("not assigned arbitrator");
+            return Err(PulsarError::Unauthorized);
         }
 
         if dispute.status == DisputeStatus::Resolved {
-            panic!("already resolved");
+            return Err(PulsarError::AlreadyExists);
         }
 
         let (claimant_amount, respondent_amount) = match outcome {
@@ -295,11 +401,8 @@ impl DisputeResolutionContract {
             }
         }
 
-        let fee: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::FilingFee)
-            .unwrap_or(0);
+        // Refund what was paid at filing, not the current schedule
+        let fee = dispute.filing_fee;
         if fee > 0 {
             let token_client = token::Client::new(&env, &dispute.token);
             match outcome {
@@ -331,6 +434,8 @@ impl DisputeResolutionContract {
             }
         }
 
+        Self::_add_exposure(&env, &dispute.respondent, -dispute.claim_amount);
+
         dispute.outcome = outcome;
         dispute.resolution_notes = notes;
         dispute.status = DisputeStatus::Resolved;
@@ -344,10 +449,13 @@ impl DisputeResolutionContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("dispute"), symbol_short!("resolved")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("dispute"),
+            symbol_short!("resolved"),
             dispute_id,
         );
+        Ok(())
     }
 
     pub fn get_dispute(env: Env, dispute_id: u64) -> Option<Dispute> {
@@ -359,6 +467,58 @@ impl DisputeResolutionContract {
             .get(&DataKey::Dispute(dispute_id))
     }
 
+    pub fn get_filing_fee_schedule(env: Env) -> FilingFeeSchedule {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_fee_schedule(&env)
+    }
+
+    /// Filing fee `file_dispute` would charge for `claim_amount`
+    pub fn quote_filing_fee(env: Env, claim_amount: i128) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let schedule = Self::_fee_schedule(&env);
+        let fee = (claim_amount * schedule.fee_bps as i128 / TOTAL_BPS as i128)
+            .max(schedule.min_fee);
+        if schedule.max_fee > 0 {
+            fee.min(schedule.max_fee)
+        } else {
+            fee
+        }
+    }
+
+    /// Sum of claim amounts in unresolved disputes against `respondent`
+    pub fn get_open_exposure(env: Env, respondent: Address) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::OpenExposure(respondent))
+            .unwrap_or(0)
+    }
+
+    /// Disputes with ids after `start`, paged by `pulsar_common_admin::page`
+    pub fn list_disputes(
+        env: Env,
+        start: u64,
+        limit: u32,
+    ) -> (soroban_sdk::Vec<Dispute>, Option<u64>) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::DisputeCounter)
+            .unwrap_or(0);
+        pulsar_common_admin::page(&env, counter, start, limit, |id| {
+            env.storage().persistent().get(&DataKey::Dispute(id))
+        })
+    }
+
     pub fn get_dispute_count(env: Env) -> u64 {
         env.storage()
             .instance()
@@ -369,31 +529,49 @@ impl DisputeResolutionContract {
             .unwrap_or(0)
     }
 
-    pub fn set_escrow_contract(env: Env, admin: Address, escrow_contract: Address) {
+    pub fn set_escrow_contract(
+        env: Env,
+        admin: Address,
+        escrow_contract: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         env.storage()
             .instance()
             .set(&DataKey::EscrowContract, &escrow_contract);
+        Ok(())
     }
 
-    pub fn link_dispute_escrow(env: Env, admin: Address, dispute_id: u64, escrow_id: u64) {
+    pub fn link_dispute_escrow(
+        env: Env,
+        admin: Address,
+        dispute_id: u64,
+        escrow_id: u64,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         if !env.storage().persistent().has(&DataKey::Dispute(dispute_id)) {
-            panic!("dispute not found");
+            return Err(PulsarError::NotFound);
         }
         let _ttl_key = DataKey::DisputeEscrow(dispute_id);
         env.storage().persistent().set(&_ttl_key, &escrow_id);
@@ -402,20 +580,172 @@ impl DisputeResolutionContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn _file_dispute(
+        env: &Env,
+        claimant: Address,
+        respondent: Address,
+        campaign_id: u64,
+        listing_id: Option<u64>,
+        claim_amount: i128,
+        description: String,
+        evidence_hash: String,
+    ) -> Result<u64, PulsarError> {
+        claimant.require_auth();
+        if claim_amount <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+
+        // Collect filing fee
+        let fee = Self::quote_filing_fee(env.clone(), claim_amount);
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenAddress)
+            .unwrap();
+        let token_client = token::Client::new(env, &token_addr);
+        if fee > 0 {
+            token_client.transfer(&claimant, &env.current_contract_address(), &fee);
+        }
+
+        // Lock claim funds in this contract until dispute settlement.
+        token_client.transfer(&claimant, &env.current_contract_address(), &claim_amount);
+
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::DisputeCounter)
+            .unwrap_or(0);
+        let dispute_id = counter + 1;
+
+        Self::_add_exposure(env, &respondent, claim_amount);
+
+        let dispute = Dispute {
+            dispute_id,
+            claimant: claimant.clone(),
+            respondent,
+            campaign_id,
+            listing_id,
+            claim_amount,
+            token: token_addr,
+            description,
+            evidence_hash,
+            status: DisputeStatus::Filed,
+            outcome: DisputeOutcome::Pending,
+            resolution_notes: String::from_str(env, ""),
+            filed_at: env.ledger().timestamp(),
+            resolved_at: None,
+            arbitrator: None,
+            filing_fee: fee,
+        };
+
+        let _ttl_key = DataKey::Dispute(dispute_id);
+        env.storage().persistent().set(&_ttl_key, &dispute);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::DisputeCounter, &dispute_id);
+
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("dispute"),
+            symbol_short!("filed"),
+            (dispute_id, claimant),
         );
+
+        Ok(dispute_id)
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    /// Adjust `respondent`'s open exposure and signal an upward crossing of
+    /// the threshold.
+    fn _add_exposure(env: &Env, respondent: &Address, delta: i128) {
+        let _ttl_key = DataKey::OpenExposure(respondent.clone());
+        let previous: i128 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+        let exposure = (previous + delta).max(0);
+        env.storage().persistent().set(&_ttl_key, &exposure);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let threshold: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ExposureThreshold)
+            .unwrap_or(0);
+        if threshold == 0 || previous >= threshold || exposure < threshold {
+            return;
+        }
+
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("dispute"),
+            symbol_short!("exposure"),
+            (respondent.clone(), exposure, threshold),
+        );
+
+        if let Some(fraud_contract) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::FraudContract)
+        {
+            env.invoke_contract::<()>(
+                &fraud_contract,
+                &Symbol::new(env, "flag_suspicious"),
+                SdkVec::from_array(
+                    env,
+                    [
+                        env.current_contract_address().into_val(env),
+                        respondent.clone().into_val(env),
+                    ],
+                ),
+            );
+        }
+    }
+
+    fn _fee_schedule(env: &Env) -> FilingFeeSchedule {
+        env.storage()
+            .instance()
+            .get(&DataKey::FilingFeeSchedule)
+            .unwrap_or_else(|| FilingFeeSchedule {
+                fee_bps: 0,
+                min_fee: env.storage().instance().get(&DataKey::FilingFee).unwrap_or(0),
+                max_fee: 0,
+            })
     }
 
     fn try_settle_linked_escrow(
@@ -63,7 +63,6 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
@@ -76,7 +75,10 @@ fn test_initialize_twice() {
     let filing_fee = 1000i128;
 
     client.initialize(&admin, &token, &filing_fee);
-    client.initialize(&admin, &token, &filing_fee);
+    assert_eq!(
+        client.try_initialize(&admin, &token, &filing_fee),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -109,7 +111,6 @@ fn test_authorize_arbitrator() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_authorize_arbitrator_by_stranger() {
     let env = Env::default();
     env.mock_all_auths();
@@ -117,7 +118,10 @@ fn test_authorize_arbitrator_by_stranger() {
     let stranger = Address::generate(&env);
     let arbitrator = Address::generate(&env);
 
-    client.authorize_arbitrator(&stranger, &arbitrator);
+    assert_eq!(
+        client.try_authorize_arbitrator(&stranger, &arbitrator),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 // ─── file_dispute ────────────────────────────────────────────────────────────
@@ -154,6 +158,41 @@ fn test_file_dispute() {
     let _ = token_admin;
 }
 
+#[test]
+fn test_file_listing_dispute_keeps_listing_apart_from_campaigns() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, token_addr) = setup(&env);
+
+    let claimant = Address::generate(&env);
+    let respondent = Address::generate(&env);
+    mint(&env, &token_addr, &claimant, 1_000_000);
+
+    let campaign_dispute = client.file_dispute(
+        &claimant,
+        &respondent,
+        &3u64,
+        &10_000i128,
+        &make_desc(&env),
+        &make_evidence(&env),
+    );
+    let listing_dispute = client.file_listing_dispute(
+        &claimant,
+        &respondent,
+        &3u64,
+        &10_000i128,
+        &make_desc(&env),
+        &make_evidence(&env),
+    );
+
+    let campaign = client.get_dispute(&campaign_dispute).unwrap();
+    assert_eq!(campaign.campaign_id, 3);
+    assert_eq!(campaign.listing_id, None);
+    let listing = client.get_dispute(&listing_dispute).unwrap();
+    assert_eq!(listing.campaign_id, 0);
+    assert_eq!(listing.listing_id, Some(3));
+}
+
 #[test]
 fn test_file_multiple_disputes() {
     let env = Env::default();
@@ -184,6 +223,13 @@ fn test_file_multiple_disputes() {
     assert_eq!(id1, 1);
     assert_eq!(id2, 2);
     assert_eq!(client.get_dispute_count(), 2);
+
+    let (page, cursor) = client.list_disputes(&0u64, &1u32);
+    assert_eq!(page.get(0).unwrap().claim_amount, 10_000);
+    assert_eq!(cursor, Some(1));
+    let (page, cursor) = client.list_disputes(&1u64, &10u32);
+    assert_eq!(page.get(0).unwrap().claim_amount, 20_000);
+    assert_eq!(cursor, None);
 }
 
 // ─── assign_arbitrator ───────────────────────────────────────────────────────
@@ -217,7 +263,6 @@ fn test_assign_arbitrator() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_assign_arbitrator_by_stranger() {
     let env = Env::default();
     env.mock_all_auths();
@@ -238,11 +283,13 @@ fn test_assign_arbitrator_by_stranger() {
         &make_evidence(&env),
     );
     client.authorize_arbitrator(&admin, &arbitrator);
-    client.assign_arbitrator(&stranger, &dispute_id, &arbitrator);
+    assert_eq!(
+        client.try_assign_arbitrator(&stranger, &dispute_id, &arbitrator),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
-#[should_panic(expected = "arbitrator not authorized")]
 fn test_assign_unauthorized_arbitrator() {
     let env = Env::default();
     env.mock_all_auths();
@@ -262,7 +309,10 @@ fn test_assign_unauthorized_arbitrator() {
         &make_evidence(&env),
     );
     // arbitrator not authorized first
-    client.assign_arbitrator(&admin, &dispute_id, &arbitrator);
+    assert_eq!(
+        client.try_assign_arbitrator(&admin, &dispute_id, &arbitrator),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 // ─── resolve_dispute ─────────────────────────────────────────────────────────
@@ -307,7 +357,6 @@ fn test_resolve_dispute() {
 }
 
 #[test]
-#[should_panic(expected = "not assigned arbitrator")]
 fn test_resolve_by_wrong_arbitrator() {
     let env = Env::default();
     env.mock_all_auths();
@@ -330,12 +379,13 @@ fn test_resolve_by_wrong_arbitrator() {
     client.authorize_arbitrator(&admin, &arbitrator);
     client.assign_arbitrator(&admin, &dispute_id, &arbitrator);
 
-    client.resolve_dispute(
+    let result = client.try_resolve_dispute(
         &wrong_arb,
         &dispute_id,
         &DisputeOutcome::Respondent,
         &String::from_str(&env, "wrong"),
     );
+    assert_eq!(result, Err(Ok(PulsarError::Unauthorized)));
 }
 
 // ─── read-only ───────────────────────────────────────────────────────────────
@@ -357,3 +407,194 @@ fn test_get_dispute_count_initial() {
 
     assert_eq!(client.get_dispute_count(), 0);
 }
+
+// ─── filing fee schedule ─────────────────────────────────────────────────────
+
+#[test]
+fn test_filing_fee_scales_with_claim_within_bounds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, _) = setup(&env);
+
+    // Flat fee from initialize
+    assert_eq!(client.quote_filing_fee(&50_000i128), 1000);
+
+    client.set_filing_fee_schedule(
+        &admin,
+        &FilingFeeSchedule {
+            fee_bps: 200,
+            min_fee: 500,
+            max_fee: 5_000,
+        },
+    );
+    assert_eq!(client.quote_filing_fee(&10_000i128), 500);
+    assert_eq!(client.quote_filing_fee(&100_000i128), 2_000);
+    assert_eq!(client.quote_filing_fee(&1_000_000i128), 5_000);
+}
+
+#[test]
+fn test_refund_uses_fee_recorded_at_filing() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, token_addr) = setup(&env);
+    client.set_filing_fee_schedule(
+        &admin,
+        &FilingFeeSchedule {
+            fee_bps: 200,
+            min_fee: 0,
+            max_fee: 0,
+        },
+    );
+
+    let claimant = Address::generate(&env);
+    let respondent = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+    mint(&env, &token_addr, &claimant, 1_000_000);
+
+    let dispute_id = client.file_dispute(
+        &claimant,
+        &respondent,
+        &1u64,
+        &100_000i128,
+        &make_desc(&env),
+        &make_evidence(&env),
+    );
+    assert_eq!(client.get_dispute(&dispute_id).unwrap().filing_fee, 2_000);
+
+    // A later schedule change must not alter the refund
+    client.set_filing_fee_schedule(
+        &admin,
+        &FilingFeeSchedule {
+            fee_bps: 1_000,
+            min_fee: 0,
+            max_fee: 0,
+        },
+    );
+    client.authorize_arbitrator(&admin, &arbitrator);
+    client.assign_arbitrator(&admin, &dispute_id, &arbitrator);
+    client.resolve_dispute(
+        &arbitrator,
+        &dispute_id,
+        &DisputeOutcome::Claimant,
+        &String::from_str(&env, "claimant wins"),
+    );
+
+    let token_client = token::Client::new(&env, &token_addr);
+    assert_eq!(token_client.balance(&claimant), 1_000_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_filing_fee_schedule_cap_below_floor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, _) = setup(&env);
+    let result = client.try_set_filing_fee_schedule(
+        &admin,
+        &FilingFeeSchedule {
+            fee_bps: 100,
+            min_fee: 1_000,
+            max_fee: 500,
+        },
+    );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidAmount)));
+}
+
+// ─── respondent exposure ─────────────────────────────────────────────────────
+
+mod fraud_mock {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockFraud;
+
+    #[contractimpl]
+    impl MockFraud {
+        pub fn flag_suspicious(env: Env, caller: Address, publisher: Address) {
+            caller.require_auth();
+            let flags: u32 = env.storage().persistent().get(&publisher).unwrap_or(0);
+            env.storage().persistent().set(&publisher, &(flags + 1));
+        }
+
+        pub fn flags(env: Env, publisher: Address) -> u32 {
+            env.storage().persistent().get(&publisher).unwrap_or(0)
+        }
+    }
+}
+
+fn file(
+    env: &Env,
+    client: &DisputeResolutionContractClient<'_>,
+    claimant: &Address,
+    respondent: &Address,
+    claim_amount: i128,
+) -> u64 {
+    client.file_dispute(
+        claimant,
+        respondent,
+        &1u64,
+        &claim_amount,
+        &make_desc(env),
+        &make_evidence(env),
+    )
+}
+
+#[test]
+fn test_exposure_crossing_flags_respondent_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, token_addr) = setup(&env);
+    let fraud = env.register(fraud_mock::MockFraud, ());
+    let fraud_client = fraud_mock::MockFraudClient::new(&env, &fraud);
+    client.set_fraud_contract(&admin, &fraud);
+    client.set_exposure_threshold(&admin, &100_000i128);
+
+    let claimant = Address::generate(&env);
+    let respondent = Address::generate(&env);
+    mint(&env, &token_addr, &claimant, 1_000_000);
+
+    file(&env, &client, &claimant, &respondent, 50_000);
+    assert_eq!(fraud_client.flags(&respondent), 0);
+    let crossing = file(&env, &client, &claimant, &respondent, 60_000);
+    assert_eq!(fraud_client.flags(&respondent), 1);
+    file(&env, &client, &claimant, &respondent, 10_000);
+    assert_eq!(fraud_client.flags(&respondent), 1);
+    assert_eq!(client.get_open_exposure(&respondent), 120_000);
+
+    // Resolution releases exposure; crossing again signals again
+    let arbitrator = Address::generate(&env);
+    client.authorize_arbitrator(&admin, &arbitrator);
+    client.assign_arbitrator(&admin, &crossing, &arbitrator);
+    client.resolve_dispute(
+        &arbitrator,
+        &crossing,
+        &DisputeOutcome::Respondent,
+        &String::from_str(&env, "no fault"),
+    );
+    assert_eq!(client.get_open_exposure(&respondent), 60_000);
+    file(&env, &client, &claimant, &respondent, 40_000);
+    assert_eq!(fraud_client.flags(&respondent), 2);
+}
+
+#[test]
+fn test_exposure_tracked_without_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, token_addr) = setup(&env);
+    let fraud = env.register(fraud_mock::MockFraud, ());
+    client.set_fraud_contract(&admin, &fraud);
+
+    let claimant = Address::generate(&env);
+    let respondent = Address::generate(&env);
+    let other = Address::generate(&env);
+    mint(&env, &token_addr, &claimant, 1_000_000);
+
+    file(&env, &client, &claimant, &respondent, 500_000);
+    file(&env, &client, &claimant, &other, 1_000);
+    assert_eq!(client.get_open_exposure(&respondent), 500_000);
+    assert_eq!(client.get_open_exposure(&other), 1_000);
+    assert_eq!(
+        fraud_mock::MockFraudClient::new(&env, &fraud).flags(&respondent),
+        0
+    );
+}
@@ -1,15 +1,21 @@
 //! PulsarTrack - Escrow Vault (Soroban)
 //! Advanced escrow with time-locked funds, performance triggers, and multi-party approval.
 //!
-//! Events:
+//! Events (third topic is `pulsar_common_admin::EVENT_SCHEMA_VERSION`):
 //! - ("escrow", "created"): [escrow_id: u64, campaign_id: u64, amount: i128]
 //! - ("escrow", "release"): [escrow_id: u64, amount: i128]
 //! - ("escrow", "release_partial"): [escrow_id: u64, amount: i128]
 //! - ("escrow", "refund"): [escrow_id: u64, amount: i128]
+//! - ("escrow", "funded"): [escrow_id: u64, amount: i128]
+//! - ("escrow", "milestone"): [escrow_id: u64, index: u32, amount: i128]
+//! - ("escrow", "held"): [escrow_id: u64]
+//! - ("escrow", "settled"): [escrow_id: u64, claimant_amount: i128, respondent_amount: i128]
 
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env, Vec};
 
+pub use pulsar_common_errors::PulsarError;
+
 // ============================================================
 // Data Types
 // ============================================================
@@ -51,6 +57,15 @@ pub struct EscrowApproval {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone)]
+pub struct Milestone {
+    pub amount: i128,
+    pub released: bool,
+    pub released_at: Option<u64>,
+    pub refunded: bool, // returned to the depositor by an expiry refund
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct PerformanceMetrics {
@@ -80,6 +95,8 @@ pub enum DataKey {
     ApprovalCount(u64),
     RequiredApprover(u64, Address),
     Performance(u64),
+    Milestones(u64),
+    OpenEscrowCount(Address), // beneficiary -> escrows still holding locked funds
 }
 
 // ============================================================
@@ -97,12 +114,17 @@ pub struct EscrowVaultContract;
 #[contractimpl]
 impl EscrowVaultContract {
     /// Initialize the contract
-    pub fn initialize(env: Env, admin: Address, token_address: Address, oracle: Address) {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token_address: Address,
+        oracle: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -116,37 +138,60 @@ impl EscrowVaultContract {
             .instance()
             .set(&DataKey::MinApprovalThreshold, &1u32);
         env.storage().instance().set(&DataKey::EscrowNonce, &0u64);
+        Ok(())
     }
 
-    pub fn set_fraud_contract(env: Env, admin: Address, fraud_contract: Address) {
+    pub fn set_fraud_contract(
+        env: Env,
+        admin: Address,
+        fraud_contract: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         env.storage()
             .instance()
             .set(&DataKey::FraudContract, &fraud_contract);
+        Ok(())
     }
 
-    pub fn set_dispute_contract(env: Env, admin: Address, dispute_contract: Address) {
+    pub fn set_dispute_contract(
+        env: Env,
+        admin: Address,
+        dispute_contract: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         env.storage()
             .instance()
             .set(&DataKey::DisputeContract, &dispute_contract);
+        Ok(())
     }
 
-    pub fn hold_for_fraud(env: Env, fraud_contract: Address, escrow_id: u64) {
+    pub fn hold_for_fraud(
+        env: Env,
+        fraud_contract: Address,
+        escrow_id: u64,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -156,16 +201,20 @@ impl EscrowVaultContract {
             .storage()
             .instance()
             .get(&DataKey::FraudContract)
-            .expect("fraud contract not set");
+            .ok_or(PulsarError::NotInitialized)?;
         if fraud_contract != stored_fraud {
-            panic!("unauthorized fraud contract");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut escrow: Escrow = env
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("escrow not found");
+            .ok_or(PulsarError::NotFound)?;
+
+        if escrow.state == EscrowState::Released || escrow.state == EscrowState::Refunded {
+            return Err(PulsarError::AlreadyExists);
+        }
 
         escrow.state = EscrowState::Disputed;
 
@@ -176,9 +225,18 @@ impl EscrowVaultContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("held"),
+            escrow_id,
+        );
+        Ok(())
     }
 
     /// Create a new escrow
+    #[allow(clippy::too_many_arguments)]
     pub fn create_escrow(
         env: Env,
         depositor: Address,
@@ -189,17 +247,17 @@ impl EscrowVaultContract {
         performance_threshold: u32,
         expires_in: u64,
         required_approvers: Vec<Address>,
-    ) -> u64 {
+    ) -> Result<u64, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         depositor.require_auth();
 
         if amount <= 0 {
-            panic!("invalid amount");
+            return Err(PulsarError::InvalidAmount);
         }
         if performance_threshold > 100 {
-            panic!("invalid performance threshold");
+            return Err(PulsarError::InvalidInput);
         }
 
         // Transfer funds to escrow contract
@@ -222,7 +280,7 @@ impl EscrowVaultContract {
         let escrow = Escrow {
             campaign_id,
             depositor: depositor.clone(),
-            beneficiary,
+            beneficiary: beneficiary.clone(),
             amount,
             locked_amount: amount,
             released_amount: 0,
@@ -265,17 +323,236 @@ impl EscrowVaultContract {
         env.storage()
             .instance()
             .set(&DataKey::EscrowNonce, &escrow_id);
+        Self::_adjust_open_count(&env, &beneficiary, true);
 
-        env.events().publish(
-            (symbol_short!("escrow"), symbol_short!("created")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("created"),
             (escrow_id, campaign_id, amount),
         );
 
-        escrow_id
+        Ok(escrow_id)
+    }
+
+    /// Top up a locked escrow. Not allowed once milestones split the amount.
+    pub fn fund_escrow(
+        env: Env,
+        depositor: Address,
+        escrow_id: u64,
+        amount: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        depositor.require_auth();
+
+        if amount <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if escrow.depositor != depositor {
+            return Err(PulsarError::Unauthorized);
+        }
+        if escrow.state != EscrowState::Locked {
+            return Err(PulsarError::InvalidState);
+        }
+        if env.storage().persistent().has(&DataKey::Milestones(escrow_id)) {
+            return Err(PulsarError::AlreadyExists);
+        }
+
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenAddress)
+            .unwrap();
+        let token_client = token::Client::new(&env, &token_addr);
+        token_client.transfer(&depositor, &env.current_contract_address(), &amount);
+
+        escrow.amount += amount;
+        escrow.locked_amount += amount;
+
+        let _ttl_key = DataKey::Escrow(escrow_id);
+        env.storage().persistent().set(&_ttl_key, &escrow);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("funded"),
+            (escrow_id, amount),
+        );
+        Ok(())
+    }
+
+    /// Split a locked escrow into milestones that must add up to its amount
+    pub fn set_milestones(
+        env: Env,
+        depositor: Address,
+        escrow_id: u64,
+        amounts: Vec<i128>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        depositor.require_auth();
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if escrow.depositor != depositor {
+            return Err(PulsarError::Unauthorized);
+        }
+        if escrow.state != EscrowState::Locked {
+            return Err(PulsarError::InvalidState);
+        }
+        if env.storage().persistent().has(&DataKey::Milestones(escrow_id)) {
+            return Err(PulsarError::AlreadyExists);
+        }
+        if amounts.is_empty() {
+            return Err(PulsarError::NotFound);
+        }
+
+        let mut milestones: Vec<Milestone> = Vec::new(&env);
+        let mut total: i128 = 0;
+        for amount in amounts.iter() {
+            if amount <= 0 {
+                return Err(PulsarError::InvalidAmount);
+            }
+            total += amount;
+            milestones.push_back(Milestone {
+                amount,
+                released: false,
+                released_at: None,
+                refunded: false,
+            });
+        }
+        if total != escrow.amount {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let _ttl_key = DataKey::Milestones(escrow_id);
+        env.storage().persistent().set(&_ttl_key, &milestones);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Ok(())
+    }
+
+    /// Release one milestone to the beneficiary, under the same time lock,
+    /// approval, performance and fraud-hold checks as a full release
+    pub fn release_milestone(
+        env: Env,
+        caller: Address,
+        escrow_id: u64,
+        index: u32,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        caller.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if caller != escrow.depositor && caller != admin {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        Self::_check_can_release(&env, &escrow, escrow_id)?;
+
+        let mut milestones: Vec<Milestone> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Milestones(escrow_id))
+            .ok_or(PulsarError::NotFound)?;
+        let mut milestone = milestones.get(index).ok_or(PulsarError::NotFound)?;
+        if milestone.released {
+            return Err(PulsarError::AlreadyExists);
+        }
+        if milestone.refunded {
+            return Err(PulsarError::InvalidState);
+        }
+        if milestone.amount > escrow.locked_amount {
+            return Err(PulsarError::InsufficientBalance);
+        }
+
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenAddress)
+            .unwrap();
+        let token_client = token::Client::new(&env, &token_addr);
+        token_client.transfer(
+            &env.current_contract_address(),
+            &escrow.beneficiary,
+            &milestone.amount,
+        );
+
+        let now = env.ledger().timestamp();
+        milestone.released = true;
+        milestone.released_at = Some(now);
+        milestones.set(index, milestone.clone());
+        let _ttl_key = DataKey::Milestones(escrow_id);
+        env.storage().persistent().set(&_ttl_key, &milestones);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        escrow.locked_amount -= milestone.amount;
+        escrow.released_amount += milestone.amount;
+        if escrow.locked_amount == 0 {
+            escrow.state = EscrowState::Released;
+            escrow.released_at = Some(now);
+            Self::_adjust_open_count(&env, &escrow.beneficiary, false);
+        } else {
+            escrow.state = EscrowState::PartiallyReleased;
+        }
+
+        let _ttl_key = DataKey::Escrow(escrow_id);
+        env.storage().persistent().set(&_ttl_key, &escrow);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("milestone"),
+            (escrow_id, index, milestone.amount),
+        );
+        Ok(())
     }
 
     /// Approve escrow release
-    pub fn approve_release(env: Env, approver: Address, escrow_id: u64) {
+    pub fn approve_release(env: Env, approver: Address, escrow_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -288,22 +565,22 @@ impl EscrowVaultContract {
             .unwrap_or(false);
 
         if !is_required {
-            panic!("not a required approver");
+            return Err(PulsarError::Unauthorized);
         }
 
         let approval_key = DataKey::Approval(escrow_id, approver.clone());
         if env.storage().persistent().has(&approval_key) {
-            panic!("already approved");
+            return Err(PulsarError::AlreadyExists);
         }
 
         let escrow: Escrow = env
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("escrow not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if escrow.state == EscrowState::Released {
-            panic!("already released");
+            return Err(PulsarError::AlreadyExists);
         }
 
         let approval = EscrowApproval {
@@ -331,10 +608,12 @@ impl EscrowVaultContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
-    /// Release full escrow to beneficiary
-    pub fn release_escrow(env: Env, caller: Address, escrow_id: u64) {
+    /// Release full escrow to beneficiary. Escrows split into milestones are
+    /// only released through `release_milestone`.
+    pub fn release_escrow(env: Env, caller: Address, escrow_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -344,19 +623,26 @@ impl EscrowVaultContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("escrow not found");
+            .ok_or(PulsarError::NotFound)?;
 
         // Must be depositor or admin
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if caller != escrow.depositor && caller != admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
-        Self::_check_can_release(&env, &escrow, escrow_id);
+        Self::_check_can_release(&env, &escrow, escrow_id)?;
+        if env.storage().persistent().has(&DataKey::Milestones(escrow_id)) {
+            return Err(PulsarError::InvalidState);
+        }
 
         let locked = escrow.locked_amount;
         if locked <= 0 {
-            panic!("nothing to release");
+            return Err(PulsarError::InsufficientBalance);
         }
 
         let token_addr: Address = env
@@ -375,6 +661,7 @@ impl EscrowVaultContract {
         escrow.released_amount = escrow.amount;
         escrow.state = EscrowState::Released;
         escrow.released_at = Some(env.ledger().timestamp());
+        Self::_adjust_open_count(&env, &escrow.beneficiary, false);
 
         let _ttl_key = DataKey::Escrow(escrow_id);
         env.storage().persistent().set(&_ttl_key, &escrow);
@@ -384,14 +671,23 @@ impl EscrowVaultContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("escrow"), symbol_short!("release")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("release"),
             (escrow_id, locked),
         );
+        Ok(())
     }
 
-    /// Partial release
-    pub fn release_partial(env: Env, caller: Address, escrow_id: u64, amount: i128) {
+    /// Partial release. Escrows split into milestones are only released
+    /// through `release_milestone`.
+    pub fn release_partial(
+        env: Env,
+        caller: Address,
+        escrow_id: u64,
+        amount: i128,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -401,17 +697,24 @@ impl EscrowVaultContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("escrow not found");
+            .ok_or(PulsarError::NotFound)?;
 
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if caller != escrow.depositor && caller != admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
-        Self::_check_can_release(&env, &escrow, escrow_id);
+        Self::_check_can_release(&env, &escrow, escrow_id)?;
+        if env.storage().persistent().has(&DataKey::Milestones(escrow_id)) {
+            return Err(PulsarError::InvalidState);
+        }
 
         if amount <= 0 || amount > escrow.locked_amount {
-            panic!("invalid amount");
+            return Err(PulsarError::InvalidAmount);
         }
 
         let token_addr: Address = env
@@ -429,6 +732,9 @@ impl EscrowVaultContract {
         escrow.locked_amount -= amount;
         escrow.released_amount += amount;
         escrow.state = EscrowState::PartiallyReleased;
+        if escrow.locked_amount == 0 {
+            Self::_adjust_open_count(&env, &escrow.beneficiary, false);
+        }
 
         let _ttl_key = DataKey::Escrow(escrow_id);
         env.storage().persistent().set(&_ttl_key, &escrow);
@@ -438,14 +744,18 @@ impl EscrowVaultContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("escrow"), symbol_short!("release_p")), // "release_partial" is too long for symbol_short
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("release_p"), // "release_partial" is too long for symbol_short
             (escrow_id, amount),
         );
+        Ok(())
     }
 
-    /// Refund escrow if expired
-    pub fn refund_escrow(env: Env, caller: Address, escrow_id: u64) {
+    /// Refund escrow if expired. Milestones not yet released are marked
+    /// refunded.
+    pub fn refund_escrow(env: Env, caller: Address, escrow_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -455,15 +765,19 @@ impl EscrowVaultContract {
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("escrow not found");
+            .ok_or(PulsarError::NotFound)?;
 
+        // A fraud hold keeps funds in place until the dispute is settled
+        if escrow.state == EscrowState::Disputed {
+            return Err(PulsarError::InvalidState);
+        }
         let now = env.ledger().timestamp();
         if now < escrow.expires_at {
-            panic!("escrow not yet expired");
+            return Err(PulsarError::TooEarly);
         }
 
         if escrow.locked_amount <= 0 {
-            panic!("nothing to refund");
+            return Err(PulsarError::InsufficientBalance);
         }
 
         let refund = escrow.locked_amount;
@@ -478,6 +792,28 @@ impl EscrowVaultContract {
         escrow.locked_amount = 0;
         escrow.refunded_amount = refund;
         escrow.state = EscrowState::Refunded;
+        Self::_adjust_open_count(&env, &escrow.beneficiary, false);
+
+        if let Some(milestones) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Vec<Milestone>>(&DataKey::Milestones(escrow_id))
+        {
+            let mut closed: Vec<Milestone> = Vec::new(&env);
+            for mut milestone in milestones.iter() {
+                if !milestone.released {
+                    milestone.refunded = true;
+                }
+                closed.push_back(milestone);
+            }
+            let _ttl_key = DataKey::Milestones(escrow_id);
+            env.storage().persistent().set(&_ttl_key, &closed);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
 
         let _ttl_key = DataKey::Escrow(escrow_id);
         env.storage().persistent().set(&_ttl_key, &escrow);
@@ -487,10 +823,13 @@ impl EscrowVaultContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("escrow"), symbol_short!("refund")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("refund"),
             (escrow_id, refund),
         );
+        Ok(())
     }
 
     /// Settle escrow based on dispute outcome.
@@ -502,41 +841,45 @@ impl EscrowVaultContract {
         respondent: Address,
         claimant_amount: i128,
         respondent_amount: i128,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         caller.require_auth();
 
         if claimant_amount < 0 || respondent_amount < 0 {
-            panic!("invalid amount");
+            return Err(PulsarError::InvalidAmount);
         }
 
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         let dispute_contract: Option<Address> = env.storage().instance().get(&DataKey::DisputeContract);
         let is_authorized_dispute = dispute_contract
             .map(|addr| addr == caller)
             .unwrap_or(false);
         if caller != admin && !is_authorized_dispute {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut escrow: Escrow = env
             .storage()
             .persistent()
             .get(&DataKey::Escrow(escrow_id))
-            .expect("escrow not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if escrow.state == EscrowState::Released || escrow.state == EscrowState::Refunded {
-            panic!("already settled");
+            return Err(PulsarError::AlreadyExists);
         }
 
         let total_settlement = claimant_amount + respondent_amount;
         if total_settlement <= 0 {
-            panic!("invalid amount");
+            return Err(PulsarError::InvalidAmount);
         }
         if total_settlement > escrow.locked_amount {
-            panic!("insufficient escrow");
+            return Err(PulsarError::InsufficientBalance);
         }
 
         let token_addr: Address = env
@@ -576,6 +919,9 @@ impl EscrowVaultContract {
         } else {
             EscrowState::PartiallyReleased
         };
+        if escrow.locked_amount == 0 {
+            Self::_adjust_open_count(&env, &escrow.beneficiary, false);
+        }
 
         let _ttl_key = DataKey::Escrow(escrow_id);
         env.storage().persistent().set(&_ttl_key, &escrow);
@@ -585,10 +931,13 @@ impl EscrowVaultContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("escrow"), symbol_short!("settled")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("settled"),
             (escrow_id, claimant_amount, respondent_amount),
         );
+        Ok(())
     }
 
     /// Update performance metrics (oracle only)
@@ -599,7 +948,7 @@ impl EscrowVaultContract {
         performance: u32,
         views: u64,
         clicks: u64,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -610,11 +959,11 @@ impl EscrowVaultContract {
             .get(&DataKey::OracleAddress)
             .unwrap();
         if oracle != stored_oracle {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         if performance > 100 {
-            panic!("invalid performance");
+            return Err(PulsarError::InvalidInput);
         }
 
         let metrics = PerformanceMetrics {
@@ -631,6 +980,7 @@ impl EscrowVaultContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     // ============================================================
@@ -644,6 +994,17 @@ impl EscrowVaultContract {
         env.storage().persistent().get(&DataKey::Escrow(escrow_id))
     }
 
+    /// Escrows paying `beneficiary` that still hold locked funds
+    pub fn get_open_escrow_count(env: Env, beneficiary: Address) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::OpenEscrowCount(beneficiary))
+            .unwrap_or(0)
+    }
+
     pub fn get_performance(env: Env, escrow_id: u64) -> Option<PerformanceMetrics> {
         env.storage()
             .instance()
@@ -653,6 +1014,16 @@ impl EscrowVaultContract {
             .get(&DataKey::Performance(escrow_id))
     }
 
+    pub fn get_milestones(env: Env, escrow_id: u64) -> Vec<Milestone> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Milestones(escrow_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
     pub fn get_approval_count(env: Env, escrow_id: u64) -> u32 {
         env.storage()
             .instance()
@@ -706,13 +1077,25 @@ impl EscrowVaultContract {
     // Internal Helpers
     // ============================================================
 
-    fn _check_can_release(env: &Env, escrow: &Escrow, escrow_id: u64) {
+    fn _adjust_open_count(env: &Env, beneficiary: &Address, opened: bool) {
+        let _ttl_key = DataKey::OpenEscrowCount(beneficiary.clone());
+        let count: u32 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+        let count = if opened { count + 1 } else { count.saturating_sub(1) };
+        env.storage().persistent().set(&_ttl_key, &count);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _check_can_release(env: &Env, escrow: &Escrow, escrow_id: u64) -> Result<(), PulsarError> {
         if escrow.state == EscrowState::Disputed {
-            panic!("escrow is disputed due to fraud");
+            return Err(PulsarError::InvalidState);
         }
         let now = env.ledger().timestamp();
         if now < escrow.time_lock_until {
-            panic!("time lock active");
+            return Err(PulsarError::TooEarly);
         }
 
         let min_threshold: u32 = env
@@ -726,7 +1109,7 @@ impl EscrowVaultContract {
             .get(&DataKey::ApprovalCount(escrow_id))
             .unwrap_or(0);
         if approvals < min_threshold {
-            panic!("approval required");
+            return Err(PulsarError::Unauthorized);
         }
 
         if let Some(perf) = env
@@ -735,23 +1118,36 @@ impl EscrowVaultContract {
             .get::<DataKey, PerformanceMetrics>(&DataKey::Performance(escrow_id))
         {
             if perf.current_performance < escrow.performance_threshold {
-                panic!("performance threshold not met");
+                return Err(PulsarError::InvalidState);
             }
         }
+        Ok(())
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
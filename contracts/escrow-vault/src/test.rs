@@ -19,7 +19,7 @@ fn setup(env: &Env) -> (EscrowVaultContractClient<'_>, Address, Address, Address
     let oracle = Address::generate(env);
 
     let token_addr = deploy_token(env, &token_admin);
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -41,13 +41,12 @@ fn test_initialize() {
     let token = deploy_token(&env, &admin);
     let oracle = Address::generate(&env);
 
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token, &oracle);
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
@@ -55,10 +54,13 @@ fn test_initialize_twice() {
     let token = deploy_token(&env, &admin);
     let oracle = Address::generate(&env);
 
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token, &oracle);
-    client.initialize(&admin, &token, &oracle);
+    assert_eq!(
+        client.try_initialize(&admin, &token, &oracle),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -70,7 +72,7 @@ fn test_initialize_non_admin_fails() {
     let token = deploy_token(&env, &admin);
     let oracle = Address::generate(&env);
 
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token, &oracle);
 }
@@ -87,7 +89,7 @@ fn test_create_escrow() {
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
 
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -123,7 +125,6 @@ fn test_create_escrow() {
 }
 
 #[test]
-#[should_panic(expected = "invalid amount")]
 fn test_create_escrow_zero_amount() {
     let env = Env::default();
     env.mock_all_auths();
@@ -131,7 +132,7 @@ fn test_create_escrow_zero_amount() {
     let depositor = Address::generate(&env);
     let beneficiary = Address::generate(&env);
 
-    client.create_escrow(
+    let result = client.try_create_escrow(
         &depositor,
         &1u64,
         &beneficiary,
@@ -141,10 +142,10 @@ fn test_create_escrow_zero_amount() {
         &86_400u64,
         &vec![&env],
     );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidAmount)));
 }
 
 #[test]
-#[should_panic(expected = "invalid performance threshold")]
 fn test_create_escrow_invalid_performance_threshold() {
     let env = Env::default();
     env.mock_all_auths();
@@ -153,7 +154,7 @@ fn test_create_escrow_invalid_performance_threshold() {
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
 
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -161,7 +162,7 @@ fn test_create_escrow_invalid_performance_threshold() {
     let beneficiary = Address::generate(&env);
     mint(&env, &token_admin, &token_addr, &depositor, 1_000_000);
 
-    client.create_escrow(
+    let result = client.try_create_escrow(
         &depositor,
         &1u64,
         &beneficiary,
@@ -171,6 +172,7 @@ fn test_create_escrow_invalid_performance_threshold() {
         &86_400u64,
         &vec![&env],
     );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidInput)));
 }
 
 // ─── approve_release ─────────────────────────────────────────────────────────
@@ -183,7 +185,7 @@ fn test_approve_release() {
     let token_addr = deploy_token(&env, &token_admin);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -209,7 +211,6 @@ fn test_approve_release() {
 }
 
 #[test]
-#[should_panic(expected = "already approved")]
 fn test_approve_release_duplicate_fails() {
     let env = Env::default();
     env.mock_all_auths();
@@ -234,11 +235,13 @@ fn test_approve_release_duplicate_fails() {
     assert_eq!(client.get_approval_count(&escrow_id), 1);
     
     // Attempt second approval from same address
-    client.approve_release(&approver, &escrow_id); // should panic
+    assert_eq!(
+        client.try_approve_release(&approver, &escrow_id),
+        Err(Ok(PulsarError::AlreadyExists))
+    ); // should panic
 }
 
 #[test]
-#[should_panic(expected = "not a required approver")]
 fn test_approve_release_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
@@ -246,7 +249,7 @@ fn test_approve_release_unauthorized() {
     let token_addr = deploy_token(&env, &token_admin);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -266,7 +269,10 @@ fn test_approve_release_unauthorized() {
         &vec![&env], // no required approvers
     );
 
-    client.approve_release(&stranger, &escrow_id); // should panic
+    assert_eq!(
+        client.try_approve_release(&stranger, &escrow_id),
+        Err(Ok(PulsarError::Unauthorized))
+    ); // should panic
 }
 
 // ─── release_escrow ──────────────────────────────────────────────────────────
@@ -279,7 +285,7 @@ fn test_release_escrow() {
     let token_addr = deploy_token(&env, &token_admin);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -299,12 +305,14 @@ fn test_release_escrow() {
         &vec![&env, approver.clone()],
     );
 
+    assert_eq!(client.get_open_escrow_count(&beneficiary), 1);
     client.approve_release(&approver, &escrow_id);
     client.release_escrow(&depositor, &escrow_id);
 
     let escrow = client.get_escrow(&escrow_id).unwrap();
     assert_eq!(escrow.released_amount, 100_000);
     assert_eq!(escrow.locked_amount, 0);
+    assert_eq!(client.get_open_escrow_count(&beneficiary), 0);
 
     let tc = TokenClient::new(&env, &token_addr);
     assert_eq!(tc.balance(&beneficiary), 100_000);
@@ -312,7 +320,6 @@ fn test_release_escrow() {
 }
 
 #[test]
-#[should_panic(expected = "time lock active")]
 fn test_release_escrow_time_lock_active() {
     let env = Env::default();
     env.mock_all_auths();
@@ -320,7 +327,7 @@ fn test_release_escrow_time_lock_active() {
     let token_addr = deploy_token(&env, &token_admin);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -341,11 +348,10 @@ fn test_release_escrow_time_lock_active() {
     );
 
     client.approve_release(&approver, &escrow_id);
-    client.release_escrow(&depositor, &escrow_id); // panics: time lock active
+    assert_eq!(client.try_release_escrow(&depositor, &escrow_id), Err(Ok(PulsarError::TooEarly))); // panics: time lock active
 }
 
 #[test]
-#[should_panic(expected = "approval required")]
 fn test_release_escrow_no_approval() {
     let env = Env::default();
     env.mock_all_auths();
@@ -353,7 +359,7 @@ fn test_release_escrow_no_approval() {
     let token_addr = deploy_token(&env, &token_admin);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -373,11 +379,13 @@ fn test_release_escrow_no_approval() {
     );
 
     // min_threshold = 1, approvals = 0 → panic
-    client.release_escrow(&depositor, &escrow_id);
+    assert_eq!(
+        client.try_release_escrow(&depositor, &escrow_id),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_release_escrow_unauthorized_caller() {
     let env = Env::default();
     env.mock_all_auths();
@@ -385,7 +393,7 @@ fn test_release_escrow_unauthorized_caller() {
     let token_addr = deploy_token(&env, &token_admin);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -407,7 +415,10 @@ fn test_release_escrow_unauthorized_caller() {
     );
 
     client.approve_release(&approver, &escrow_id);
-    client.release_escrow(&stranger, &escrow_id); // not depositor or admin
+    assert_eq!(
+        client.try_release_escrow(&stranger, &escrow_id),
+        Err(Ok(PulsarError::Unauthorized))
+    ); // not depositor or admin
 }
 
 // ─── release_partial ─────────────────────────────────────────────────────────
@@ -420,7 +431,7 @@ fn test_release_partial() {
     let token_addr = deploy_token(&env, &token_admin);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -452,7 +463,6 @@ fn test_release_partial() {
 }
 
 #[test]
-#[should_panic(expected = "invalid amount")]
 fn test_release_partial_exceeds_locked() {
     let env = Env::default();
     env.mock_all_auths();
@@ -460,7 +470,7 @@ fn test_release_partial_exceeds_locked() {
     let token_addr = deploy_token(&env, &token_admin);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -481,7 +491,10 @@ fn test_release_partial_exceeds_locked() {
     );
 
     client.approve_release(&approver, &escrow_id);
-    client.release_partial(&depositor, &escrow_id, &200_000i128); // more than locked
+    assert_eq!(
+        client.try_release_partial(&depositor, &escrow_id, &200_000i128),
+        Err(Ok(PulsarError::InvalidAmount))
+    ); // more than locked
 }
 
 // ─── refund_escrow ───────────────────────────────────────────────────────────
@@ -494,7 +507,7 @@ fn test_refund_escrow() {
     let token_addr = deploy_token(&env, &token_admin);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -524,13 +537,13 @@ fn test_refund_escrow() {
     let escrow = client.get_escrow(&escrow_id).unwrap();
     assert_eq!(escrow.refunded_amount, 100_000);
     assert_eq!(escrow.locked_amount, 0);
+    assert_eq!(client.get_open_escrow_count(&beneficiary), 0);
 
     let tc = TokenClient::new(&env, &token_addr);
     assert_eq!(tc.balance(&depositor), 1_000_000); // got funds back
 }
 
 #[test]
-#[should_panic(expected = "escrow not yet expired")]
 fn test_refund_escrow_not_expired() {
     let env = Env::default();
     env.mock_all_auths();
@@ -538,7 +551,7 @@ fn test_refund_escrow_not_expired() {
     let token_addr = deploy_token(&env, &token_admin);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -557,7 +570,7 @@ fn test_refund_escrow_not_expired() {
         &vec![&env],
     );
 
-    client.refund_escrow(&depositor, &escrow_id); // too early
+    assert_eq!(client.try_refund_escrow(&depositor, &escrow_id), Err(Ok(PulsarError::TooEarly))); // too early
 }
 
 // ─── update_performance ──────────────────────────────────────────────────────
@@ -570,7 +583,7 @@ fn test_update_performance() {
     let token_addr = deploy_token(&env, &token_admin);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -599,7 +612,6 @@ fn test_update_performance() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_update_performance_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
@@ -607,7 +619,7 @@ fn test_update_performance_unauthorized() {
     let token_addr = deploy_token(&env, &token_admin);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -627,11 +639,13 @@ fn test_update_performance_unauthorized() {
     );
 
     let fake_oracle = Address::generate(&env);
-    client.update_performance(&fake_oracle, &escrow_id, &50u32, &100u64, &5u64);
+    assert_eq!(
+        client.try_update_performance(&fake_oracle, &escrow_id, &50u32, &100u64, &5u64),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
-#[should_panic(expected = "performance threshold not met")]
 fn test_release_blocked_by_performance_threshold() {
     let env = Env::default();
     env.mock_all_auths();
@@ -639,7 +653,7 @@ fn test_release_blocked_by_performance_threshold() {
     let token_addr = deploy_token(&env, &token_admin);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -662,7 +676,10 @@ fn test_release_blocked_by_performance_threshold() {
 
     client.update_performance(&oracle, &escrow_id, &50u32, &500u64, &10u64); // below threshold
     client.approve_release(&approver, &escrow_id);
-    client.release_escrow(&depositor, &escrow_id); // should panic
+    assert_eq!(
+        client.try_release_escrow(&depositor, &escrow_id),
+        Err(Ok(PulsarError::InvalidState))
+    ); // should panic
 }
 
 // ─── hold_for_fraud ──────────────────────────────────────────────────────────
@@ -675,7 +692,7 @@ fn test_hold_for_fraud() {
     let token_addr = deploy_token(&env, &token_admin);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -704,7 +721,6 @@ fn test_hold_for_fraud() {
 }
 
 #[test]
-#[should_panic(expected = "escrow is disputed due to fraud")]
 fn test_release_disputed_escrow_fails() {
     let env = Env::default();
     env.mock_all_auths();
@@ -712,7 +728,7 @@ fn test_release_disputed_escrow_fails() {
     let token_addr = deploy_token(&env, &token_admin);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
@@ -737,7 +753,217 @@ fn test_release_disputed_escrow_fails() {
 
     client.hold_for_fraud(&fraud_contract, &escrow_id);
     client.approve_release(&approver, &escrow_id);
-    client.release_escrow(&depositor, &escrow_id); // should panic
+    assert_eq!(
+        client.try_release_escrow(&depositor, &escrow_id),
+        Err(Ok(PulsarError::InvalidState))
+    ); // should panic
+}
+
+#[test]
+fn test_refund_held_escrow_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, token_addr, _) = setup(&env);
+    let fraud_contract = Address::generate(&env);
+    client.set_fraud_contract(&admin, &fraud_contract);
+
+    let depositor = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    mint(&env, &admin, &token_addr, &depositor, 1_000_000);
+
+    let escrow_id = client.create_escrow(
+        &depositor,
+        &1u64,
+        &beneficiary,
+        &100_000i128,
+        &0u64,
+        &0u32,
+        &100u64,
+        &vec![&env],
+    );
+
+    client.hold_for_fraud(&fraud_contract, &escrow_id);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 200;
+    });
+    assert_eq!(
+        client.try_refund_escrow(&depositor, &escrow_id),
+        Err(Ok(PulsarError::InvalidState))
+    ); // should panic
+}
+
+// ─── milestones ──────────────────────────────────────────────────────────────
+
+#[test]
+fn test_fund_and_release_by_milestone() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, token_addr, _) = setup(&env);
+
+    let depositor = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    let approver = Address::generate(&env);
+    mint(&env, &admin, &token_addr, &depositor, 1_000_000);
+
+    let escrow_id = client.create_escrow(
+        &depositor,
+        &1u64,
+        &beneficiary,
+        &60_000i128,
+        &0u64,
+        &0u32,
+        &999_999u64,
+        &vec![&env, approver.clone()],
+    );
+    client.fund_escrow(&depositor, &escrow_id, &40_000i128);
+    client.set_milestones(&depositor, &escrow_id, &vec![&env, 30_000i128, 70_000i128]);
+    client.approve_release(&approver, &escrow_id);
+
+    client.release_milestone(&depositor, &escrow_id, &0u32);
+    let tc = TokenClient::new(&env, &token_addr);
+    assert_eq!(tc.balance(&beneficiary), 30_000);
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.locked_amount, 70_000);
+    assert!(matches!(escrow.state, EscrowState::PartiallyReleased));
+    assert!(client.get_milestones(&escrow_id).get(0).unwrap().released);
+
+    client.release_milestone(&depositor, &escrow_id, &1u32);
+    assert_eq!(tc.balance(&beneficiary), 100_000);
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert!(matches!(escrow.state, EscrowState::Released));
+}
+
+#[test]
+fn test_set_milestones_wrong_total() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, token_addr, _) = setup(&env);
+
+    let depositor = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    mint(&env, &admin, &token_addr, &depositor, 1_000_000);
+
+    let escrow_id = client.create_escrow(
+        &depositor,
+        &1u64,
+        &beneficiary,
+        &100_000i128,
+        &0u64,
+        &0u32,
+        &999_999u64,
+        &vec![&env],
+    );
+    assert_eq!(
+        client.try_set_milestones(&depositor, &escrow_id, &vec![&env, 30_000i128, 60_000i128]),
+        Err(Ok(PulsarError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_release_milestone_twice_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, token_addr, _) = setup(&env);
+
+    let depositor = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    let approver = Address::generate(&env);
+    mint(&env, &admin, &token_addr, &depositor, 1_000_000);
+
+    let escrow_id = client.create_escrow(
+        &depositor,
+        &1u64,
+        &beneficiary,
+        &100_000i128,
+        &0u64,
+        &0u32,
+        &999_999u64,
+        &vec![&env, approver.clone()],
+    );
+    client.set_milestones(&depositor, &escrow_id, &vec![&env, 50_000i128, 50_000i128]);
+    client.approve_release(&approver, &escrow_id);
+    client.release_milestone(&depositor, &escrow_id, &0u32);
+    assert_eq!(
+        client.try_release_milestone(&depositor, &escrow_id, &0u32),
+        Err(Ok(PulsarError::AlreadyExists))
+    );
+}
+
+#[test]
+fn test_milestone_escrow_rejects_full_and_partial_release() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, token_addr, _) = setup(&env);
+
+    let depositor = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    let approver = Address::generate(&env);
+    mint(&env, &admin, &token_addr, &depositor, 1_000_000);
+
+    let escrow_id = client.create_escrow(
+        &depositor,
+        &1u64,
+        &beneficiary,
+        &100_000i128,
+        &0u64,
+        &0u32,
+        &999_999u64,
+        &vec![&env, approver.clone()],
+    );
+    client.set_milestones(&depositor, &escrow_id, &vec![&env, 50_000i128, 50_000i128]);
+    client.approve_release(&approver, &escrow_id);
+
+    assert_eq!(
+        client.try_release_escrow(&depositor, &escrow_id),
+        Err(Ok(PulsarError::InvalidState))
+    );
+    assert_eq!(
+        client.try_release_partial(&depositor, &escrow_id, &50_000i128),
+        Err(Ok(PulsarError::InvalidState))
+    );
+    let tc = TokenClient::new(&env, &token_addr);
+    assert_eq!(tc.balance(&beneficiary), 0);
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().locked_amount, 100_000);
+}
+
+#[test]
+fn test_refund_closes_unreleased_milestones() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, token_addr, _) = setup(&env);
+
+    let depositor = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    let approver = Address::generate(&env);
+    mint(&env, &admin, &token_addr, &depositor, 1_000_000);
+
+    let escrow_id = client.create_escrow(
+        &depositor,
+        &1u64,
+        &beneficiary,
+        &100_000i128,
+        &0u64,
+        &0u32,
+        &100u64,
+        &vec![&env, approver.clone()],
+    );
+    client.set_milestones(&depositor, &escrow_id, &vec![&env, 30_000i128, 70_000i128]);
+    client.approve_release(&approver, &escrow_id);
+    client.release_milestone(&depositor, &escrow_id, &0u32);
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.refund_escrow(&depositor, &escrow_id);
+
+    let milestones = client.get_milestones(&escrow_id);
+    let first = milestones.get(0).unwrap();
+    assert!(first.released && !first.refunded);
+    let second = milestones.get(1).unwrap();
+    assert!(!second.released && second.refunded);
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().refunded_amount, 70_000);
+    assert_eq!(
+        client.try_release_milestone(&depositor, &escrow_id, &1u32),
+        Err(Ok(PulsarError::InvalidState))
+    );
 }
 
 // ─── can_release ─────────────────────────────────────────────────────────────
@@ -750,7 +976,7 @@ fn test_can_release_returns_true_when_conditions_met() {
     let token_addr = deploy_token(&env, &token_admin);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
-    let contract_id = env.register_contract(None, EscrowVaultContract);
+    let contract_id = env.register(EscrowVaultContract, ());
     let client = EscrowVaultContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &oracle);
 
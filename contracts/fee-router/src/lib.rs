@@ -0,0 +1,289 @@
+//! PulsarTrack - Fee Router (Soroban)
+//! Single destination for platform fees. Contracts transfer a fee to the
+//! router and call `route_fee`, which splits it between the treasury, a burn
+//! and the rewards pool according to the current rule. The admin is expected
+//! to be handed to the DAO once governance is live.
+//!
+//! Events (third topic is `pulsar_common_admin::EVENT_SCHEMA_VERSION`):
+//! - ("fee", "rule"): [treasury_bps: u32, burn_bps: u32, rewards_bps: u32]
+//! - ("fee", "routed"): [source: Address, token: Address, amount: i128]
+
+#![no_std]
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env};
+
+pub use pulsar_common_errors::PulsarError;
+
+#[contracttype]
+#[derive(Clone)]
+pub struct FeeRule {
+    pub treasury_bps: u32,
+    pub burn_bps: u32,
+    pub rewards_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct FeeStats {
+    pub total_routed: i128,
+    pub to_treasury: i128,
+    pub burned: i128,
+    pub to_rewards: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    PendingAdmin,
+    Treasury,
+    RewardsPool,
+    Rule,
+    TokenStats(Address),
+    SourceTotal(Address, Address), // source, token
+}
+
+const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
+const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
+const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
+
+const TOTAL_BPS: u32 = 10_000;
+
+#[contract]
+pub struct FeeRouterContract;
+
+#[contractimpl]
+impl FeeRouterContract {
+    /// Routes everything to the treasury until a rule is set
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        treasury: Address,
+        rewards_pool: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(PulsarError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardsPool, &rewards_pool);
+        env.storage().instance().set(
+            &DataKey::Rule,
+            &FeeRule {
+                treasury_bps: TOTAL_BPS,
+                burn_bps: 0,
+                rewards_bps: 0,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn set_rule(env: Env, admin: Address, rule: FeeRule) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+        if rule.treasury_bps + rule.burn_bps + rule.rewards_bps != TOTAL_BPS {
+            return Err(PulsarError::InvalidInput);
+        }
+        env.storage().instance().set(&DataKey::Rule, &rule);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("fee"),
+            symbol_short!("rule"),
+            (rule.treasury_bps, rule.burn_bps, rule.rewards_bps),
+        );
+        Ok(())
+    }
+
+    pub fn set_treasury(env: Env, admin: Address, treasury: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        Ok(())
+    }
+
+    pub fn set_rewards_pool(
+        env: Env,
+        admin: Address,
+        rewards_pool: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardsPool, &rewards_pool);
+        Ok(())
+    }
+
+    /// Distribute a fee that `source` has already transferred to the router.
+    /// The treasury receives the rounding remainder.
+    pub fn route_fee(
+        env: Env,
+        source: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        source.require_auth();
+        if amount <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+
+        let router = env.current_contract_address();
+        let token_client = token::Client::new(&env, &token);
+        if token_client.balance(&router) < amount {
+            return Err(PulsarError::InvalidState);
+        }
+
+        let rule: FeeRule = env.storage().instance().get(&DataKey::Rule).unwrap();
+        let burned = amount * rule.burn_bps as i128 / TOTAL_BPS as i128;
+        let to_rewards = amount * rule.rewards_bps as i128 / TOTAL_BPS as i128;
+        let to_treasury = amount - burned - to_rewards;
+
+        if to_treasury > 0 {
+            let treasury: Address = env.storage().instance().get(&DataKey::Treasury).unwrap();
+            token_client.transfer(&router, &treasury, &to_treasury);
+        }
+        if to_rewards > 0 {
+            let pool: Address = env.storage().instance().get(&DataKey::RewardsPool).unwrap();
+            token_client.transfer(&router, &pool, &to_rewards);
+        }
+        if burned > 0 {
+            token_client.burn(&router, &burned);
+        }
+
+        let stats_key = DataKey::TokenStats(token.clone());
+        let mut stats = Self::get_token_stats(env.clone(), token.clone());
+        stats.total_routed += amount;
+        stats.to_treasury += to_treasury;
+        stats.burned += burned;
+        stats.to_rewards += to_rewards;
+        env.storage().persistent().set(&stats_key, &stats);
+        env.storage().persistent().extend_ttl(
+            &stats_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let _ttl_key = DataKey::SourceTotal(source.clone(), token.clone());
+        let total: i128 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+        env.storage().persistent().set(&_ttl_key, &(total + amount));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("fee"),
+            symbol_short!("routed"),
+            (source, token, amount),
+        );
+        Ok(())
+    }
+
+    pub fn get_rule(env: Env) -> FeeRule {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().get(&DataKey::Rule).unwrap()
+    }
+
+    pub fn get_treasury(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().get(&DataKey::Treasury).unwrap()
+    }
+
+    pub fn get_rewards_pool(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().get(&DataKey::RewardsPool).unwrap()
+    }
+
+    pub fn get_token_stats(env: Env, token: Address) -> FeeStats {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::TokenStats(token))
+            .unwrap_or(FeeStats {
+                total_routed: 0,
+                to_treasury: 0,
+                burned: 0,
+                to_rewards: 0,
+            })
+    }
+
+    /// Fees of `token` routed by one contract
+    pub fn get_source_total(env: Env, source: Address, token: Address) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::SourceTotal(source, token))
+            .unwrap_or(0)
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::propose_admin(
+            &env,
+            &DataKey::Admin,
+            &DataKey::PendingAdmin,
+            current_admin,
+            new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
+
+    // ─── internal helpers ────────────────────────────────────────────────────
+
+    fn _require_admin(env: &Env, admin: &Address) -> Result<(), PulsarError> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if *admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        Ok(())
+    }
+}
+
+mod test;
@@ -0,0 +1,134 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{Client as TokenClient, StellarAssetClient},
+    Address, Env,
+};
+
+// ─── helpers ─────────────────────────────────────────────────────────────────
+
+struct Setup<'a> {
+    client: FeeRouterContractClient<'a>,
+    admin: Address,
+    treasury: Address,
+    rewards: Address,
+    token: TokenClient<'a>,
+}
+
+fn setup(env: &Env) -> Setup<'_> {
+    let admin = Address::generate(env);
+    let treasury = Address::generate(env);
+    let rewards = Address::generate(env);
+    let token_admin = Address::generate(env);
+    let token = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+
+    let contract_id = env.register(FeeRouterContract, ());
+    let client = FeeRouterContractClient::new(env, &contract_id);
+    client.initialize(&admin, &treasury, &rewards);
+
+    Setup {
+        client,
+        admin,
+        treasury,
+        rewards,
+        token: TokenClient::new(env, &token),
+    }
+}
+
+/// Deposit `amount` with the router the way a fee-charging contract would
+fn deposit(env: &Env, s: &Setup, amount: i128) {
+    StellarAssetClient::new(env, &s.token.address).mint(&s.client.address, &amount);
+}
+
+// ─── tests ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_default_rule_sends_everything_to_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let source = Address::generate(&env);
+
+    deposit(&env, &s, 1_000);
+    s.client.route_fee(&source, &s.token.address, &1_000i128);
+
+    assert_eq!(s.token.balance(&s.treasury), 1_000);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+    assert_eq!(s.client.get_source_total(&source, &s.token.address), 1_000);
+}
+
+#[test]
+fn test_split_between_treasury_burn_and_rewards() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    s.client.set_rule(
+        &s.admin,
+        &FeeRule {
+            treasury_bps: 5_000,
+            burn_bps: 2_000,
+            rewards_bps: 3_000,
+        },
+    );
+
+    deposit(&env, &s, 10_001);
+    s.client
+        .route_fee(&Address::generate(&env), &s.token.address, &10_001i128);
+
+    // Rounding remainder goes to the treasury
+    assert_eq!(s.token.balance(&s.treasury), 5_001);
+    assert_eq!(s.token.balance(&s.rewards), 3_000);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+
+    let stats = s.client.get_token_stats(&s.token.address);
+    assert_eq!(stats.total_routed, 10_001);
+    assert_eq!(stats.burned, 2_000);
+    assert_eq!(stats.to_rewards, 3_000);
+}
+
+#[test]
+fn test_rule_must_sum_to_full_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let result = s.client.try_set_rule(
+        &s.admin,
+        &FeeRule {
+            treasury_bps: 5_000,
+            burn_bps: 1_000,
+            rewards_bps: 1_000,
+        },
+    );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidInput)));
+}
+
+#[test]
+fn test_route_without_deposit_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    assert_eq!(
+        s.client
+            .try_route_fee(&Address::generate(&env), &s.token.address, &500i128),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_non_admin_cannot_set_rule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let result = s.client.try_set_rule(
+        &Address::generate(&env),
+        &FeeRule {
+            treasury_bps: 0,
+            burn_bps: 10_000,
+            rewards_bps: 0,
+        },
+    );
+    assert_eq!(result, Err(Ok(PulsarError::Unauthorized)));
+}
@@ -1,11 +1,21 @@
 //! PulsarTrack - Fraud Prevention (Soroban)
 //! Advanced fraud prevention and view verification for ad campaigns on Stellar.
+//!
+//! Each viewer carries a `ViewerQuality` of recent verified and rejected
+//! views, halved every `QUALITY_PERIOD_SECS`. Only oracle-attested views
+//! (`verify_view_attested`) update it, so callers cannot forge history. Once a viewer has
+//! `QUALITY_MIN_SAMPLES` views on record, a clean history adds a small bonus
+//! to the verification score and a high rejection ratio subtracts up to
+//! `QUALITY_MAX_PENALTY`.
 
 #![no_std]
 use soroban_sdk::{
     contract, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env,
+    IntoVal, Symbol, Vec,
 };
 
+pub use pulsar_common_errors::PulsarError;
+
 // ============================================================
 // Data Types
 // ============================================================
@@ -39,6 +49,37 @@ pub struct VerificationCache {
     pub average_score: u32,
 }
 
+/// Proof that a view was verified here, kept for publishers to show
+/// delivered volume in disputes. Carries no viewer data.
+#[contracttype]
+#[derive(Clone)]
+pub struct DeliveryReceipt {
+    pub view_id: BytesN<32>,
+    pub campaign_id: u64,
+    pub publisher: Address,
+    pub score: u32,
+    pub issued_at: u64,
+}
+
+/// Decayed view outcomes for one viewer; `period` is the quality period the
+/// counts were last brought up to
+#[contracttype]
+#[derive(Clone)]
+pub struct ViewerQuality {
+    pub verified: u32,
+    pub rejected: u32,
+    pub period: u64,
+}
+
+/// Publishers whose publisher-reputation score is at least `min_reputation`
+/// are held to `threshold` instead of the global verification threshold
+#[contracttype]
+#[derive(Clone)]
+pub struct ReputationBand {
+    pub min_reputation: u32, // 0-1000
+    pub threshold: u32,
+}
+
 // ============================================================
 // Storage Keys
 // ============================================================
@@ -48,6 +89,7 @@ pub struct VerificationCache {
 pub enum DataKey {
     Admin,
     PendingAdmin,
+    Paused,
     CampaignLifecycle,
     PublisherNetwork,
     EscrowVault,
@@ -60,6 +102,13 @@ pub enum DataKey {
     SuspiciousActivity(Address),
     VerificationCache(u64, u64),
     AuthorizedOracle(Address),
+    OracleRegistry,
+    ReputationContract,
+    ReputationBands,
+    Receipt(BytesN<32>),
+    DailyReceipts(Address, u64), // publisher, day
+    PublisherVerifiedViews(u64, Address), // campaign_id, publisher
+    ViewerQuality(Address),
 }
 
 // ============================================================
@@ -71,18 +120,23 @@ const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
 const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
 
+pub const QUALITY_PERIOD_SECS: u64 = 604_800; // counts halve weekly
+pub const QUALITY_MIN_SAMPLES: u32 = 5;
+pub const QUALITY_MAX_BONUS: u32 = 5;
+pub const QUALITY_MAX_PENALTY: u32 = 20;
+
 #[contract]
 pub struct FraudPreventionContract;
 
 #[contractimpl]
 impl FraudPreventionContract {
     /// Initialize the contract
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -96,6 +150,7 @@ impl FraudPreventionContract {
             .instance()
             .set(&DataKey::SuspiciousThreshold, &100u64);
         env.storage().instance().set(&DataKey::VerifyCounter, &0u64);
+        Ok(())
     }
 
     pub fn set_dependent_contracts(
@@ -104,14 +159,18 @@ impl FraudPreventionContract {
         lifecycle: Address,
         network: Address,
         vault: Address,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         env.storage()
             .instance()
@@ -120,123 +179,107 @@ impl FraudPreventionContract {
             .instance()
             .set(&DataKey::PublisherNetwork, &network);
         env.storage().instance().set(&DataKey::EscrowVault, &vault);
+        Ok(())
     }
 
-    pub fn add_oracle(env: Env, admin: Address, oracle: Address) {
+    pub fn add_oracle(env: Env, admin: Address, oracle: Address) -> Result<(), PulsarError> {
         env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         let _ttl_key = DataKey::AuthorizedOracle(oracle.clone());
         env.storage().persistent().set(&_ttl_key, &true);
         env.storage().persistent().extend_ttl(&_ttl_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        Ok(())
     }
 
-    pub fn remove_oracle(env: Env, admin: Address, oracle: Address) {
+    pub fn remove_oracle(env: Env, admin: Address, oracle: Address) -> Result<(), PulsarError> {
         env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         env.storage().persistent().remove(&DataKey::AuthorizedOracle(oracle));
+        Ok(())
+    }
+
+    /// Hand oracle checks over to the shared oracle registry (domain `Fraud`)
+    pub fn set_oracle_registry(
+        env: Env,
+        admin: Address,
+        registry: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage().instance().set(&DataKey::OracleRegistry, &registry);
+        Ok(())
     }
 
-    /// Verify an ad view
+    /// Verify an ad view. Rejected views return false and stay on record.
     pub fn verify_view(
         env: Env,
         campaign_id: u64,
         publisher: Address,
         viewer: Address,
         proof_data: Option<BytesN<32>>,
-    ) -> bool {
+    ) -> Result<bool, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        // Rate limiting: check viewer hasn't exceeded limit in current period
-        let current_period = env.ledger().timestamp() / 3600; // hourly buckets
-        let rate_key = DataKey::ViewerRateLimit(viewer.clone(), current_period);
-        let view_count: u64 = env.storage().temporary().get(&rate_key).unwrap_or(0);
+        pulsar_common_admin::require_not_paused(&env, &DataKey::Paused)?;
+        Self::_verify_view(&env, campaign_id, publisher, viewer, proof_data, false)
+    }
 
-        let max_views: u64 = env
-            .storage()
+    /// `verify_view` reported by the admin or an oracle. Only attested views
+    /// update viewer quality and count toward a publisher's verified views,
+    /// which weigh bonus pools.
+    pub fn verify_view_attested(
+        env: Env,
+        oracle: Address,
+        campaign_id: u64,
+        publisher: Address,
+        viewer: Address,
+        proof_data: Option<BytesN<32>>,
+    ) -> Result<bool, PulsarError> {
+        env.storage()
             .instance()
-            .get(&DataKey::MaxViewsPerPeriod)
-            .unwrap_or(10);
-        if view_count >= max_views {
-            panic!("rate limit exceeded");
-        }
-
-        let view_id = Self::_generate_view_id(&env, campaign_id, &publisher, &viewer);
-        if env.storage().persistent().has(&DataKey::ViewRecord(view_id.clone())) {
-            panic!("duplicate view");
-        }
-
-        let score = Self::_calculate_score(&env, campaign_id, &publisher, &proof_data);
-        let threshold: u32 = env.storage().instance().get(&DataKey::VerificationThreshold).unwrap_or(80);
-        let verified = score >= threshold;
-
-        let record = ViewRecord {
-            campaign_id,
-            publisher: publisher.clone(),
-            viewer: viewer.clone(),
-            timestamp: env.ledger().timestamp(),
-            verification_score: score,
-            verified,
-        };
-
-        let _ttl_key = DataKey::ViewRecord(view_id);
-        env.storage().persistent().set(&_ttl_key, &record);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-        env.storage().temporary().set(&rate_key, &(view_count + 1));
-
-        let current_day = env.ledger().timestamp() / 86_400;
-        let cache_key = DataKey::VerificationCache(campaign_id, current_day);
-        let mut cache: VerificationCache =
-            env.storage()
-                .temporary()
-                .get(&cache_key)
-                .unwrap_or(VerificationCache {
-                    total_views: 0,
-                    verified_views: 0,
-                    rejected_views: 0,
-                    average_score: 0,
-                });
-
-        cache.total_views += 1;
-        if verified {
-            cache.verified_views += 1;
-        } else {
-            cache.rejected_views += 1;
-        }
-        cache.average_score = ((cache.average_score as u64 * (cache.total_views - 1) + score as u64) / cache.total_views) as u32;
-        env.storage().temporary().set(&cache_key, &cache);
-
-        if verified {
-            let counter: u64 = env.storage().instance().get(&DataKey::VerifyCounter).unwrap_or(0);
-            env.storage().instance().set(&DataKey::VerifyCounter, &(counter + 1));
-        }
-
-        env.events().publish((symbol_short!("view"), symbol_short!("verified")), (campaign_id, publisher, verified));
-
-        if !verified {
-            panic!("verification failed");
-        }
-        true
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::require_not_paused(&env, &DataKey::Paused)?;
+        oracle.require_auth();
+        Self::_require_admin_or_oracle(&env, &oracle)?;
+        Self::_verify_view(&env, campaign_id, publisher, viewer, proof_data, true)
     }
 
     /// Flag suspicious publisher activity
-    pub fn flag_suspicious(env: Env, caller: Address, publisher: Address) {
-        Self::_require_admin_or_oracle(&env, &caller);
+    pub fn flag_suspicious(
+        env: Env,
+        caller: Address,
+        publisher: Address,
+    ) -> Result<(), PulsarError> {
+        Self::_require_admin_or_oracle(&env, &caller)?;
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::require_not_paused(&env, &DataKey::Paused)?;
         let key = DataKey::SuspiciousActivity(publisher.clone());
         let mut activity: SuspiciousActivity =
             env.storage()
@@ -279,32 +322,88 @@ impl FraudPreventionContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("publisher"), symbol_short!("flagged")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("publisher"),
+            symbol_short!("flagged"),
             publisher,
         );
+        Ok(())
     }
 
-    pub fn clear_flag(env: Env, admin: Address, publisher: Address) {
+    pub fn clear_flag(env: Env, admin: Address, publisher: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         env.storage().persistent().remove(&DataKey::SuspiciousActivity(publisher));
+        Ok(())
     }
 
-    pub fn suspend_publisher(env: Env, admin: Address, publisher: Address) {
+    /// Clear the publisher's flag and approve their pending reinstatement on the network
+    pub fn reinstate_publisher(
+        env: Env,
+        admin: Address,
+        publisher: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::SuspiciousActivity(publisher.clone()));
+
+        if let Some(network_addr) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::PublisherNetwork)
+        {
+            let network_client = mocks::PublisherNetworkContractClient::new(&env, &network_addr);
+            network_client.approve_reinstatement(&env.current_contract_address(), &publisher);
+        }
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("publisher"),
+            symbol_short!("reinstate"),
+            publisher,
+        );
+        Ok(())
+    }
+
+    pub fn suspend_publisher(
+        env: Env,
+        admin: Address,
+        publisher: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
         }
         let key = DataKey::SuspiciousActivity(publisher.clone());
         let mut activity: SuspiciousActivity =
@@ -334,21 +433,105 @@ impl FraudPreventionContract {
             let network_client = mocks::PublisherNetworkContractClient::new(&env, &network_addr);
             network_client.suspend_publisher(&env.current_contract_address(), &publisher);
         }
+        Ok(())
     }
 
-    pub fn set_threshold(env: Env, admin: Address, threshold: u32) {
+    pub fn set_threshold(env: Env, admin: Address, threshold: u32) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
-        if threshold < 50 || threshold > 100 {
-            panic!("invalid threshold");
+        if !(50..=100).contains(&threshold) {
+            return Err(PulsarError::InvalidInput);
         }
         env.storage().instance().set(&DataKey::VerificationThreshold, &threshold);
+        Ok(())
+    }
+
+    /// Source of publisher scores for `set_reputation_bands`
+    pub fn set_reputation_contract(
+        env: Env,
+        admin: Address,
+        contract_address: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage().instance().set(&DataKey::ReputationContract, &contract_address);
+        Ok(())
+    }
+
+    /// Per-publisher thresholds by reputation score, ascending by
+    /// `min_reputation`. Publishers below the first band, or any publisher
+    /// while no reputation contract is set, use the global threshold. An empty
+    /// list turns banding off.
+    pub fn set_reputation_bands(
+        env: Env,
+        admin: Address,
+        bands: Vec<ReputationBand>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        let mut previous: Option<u32> = None;
+        for band in bands.iter() {
+            if !(50..=100).contains(&band.threshold) {
+                return Err(PulsarError::InvalidInput);
+            }
+            if band.min_reputation > 1000 {
+                return Err(PulsarError::InvalidInput);
+            }
+            if previous.is_some_and(|min| band.min_reputation <= min) {
+                return Err(PulsarError::InvalidInput);
+            }
+            previous = Some(band.min_reputation);
+        }
+        env.storage().instance().set(&DataKey::ReputationBands, &bands);
+        Ok(())
+    }
+
+    pub fn get_reputation_bands(env: Env) -> Vec<ReputationBand> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::ReputationBands)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Score `publisher`'s views must reach to verify
+    pub fn get_effective_threshold(env: Env, publisher: Address) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_effective_threshold(&env, &publisher)
     }
 
     pub fn get_verification_stats(env: Env, campaign_id: u64) -> VerificationCache {
@@ -364,6 +547,53 @@ impl FraudPreventionContract {
         })
     }
 
+    /// Recent verified and rejected views by `viewer`, decayed to now
+    pub fn get_viewer_quality(env: Env, viewer: Address) -> ViewerQuality {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_viewer_quality(&env, &viewer)
+    }
+
+    /// Lifetime oracle-attested verified views `publisher` delivered for
+    /// `campaign_id`
+    pub fn get_publisher_verified_views(env: Env, campaign_id: u64, publisher: Address) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::PublisherVerifiedViews(campaign_id, publisher))
+            .unwrap_or(0)
+    }
+
+    /// Look up a view by the id carried in its ("view", "verified") event
+    pub fn get_view(env: Env, view_id: BytesN<32>) -> Option<ViewRecord> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().persistent().get(&DataKey::ViewRecord(view_id))
+    }
+
+    /// Delivery receipt for a verified view
+    pub fn get_receipt(env: Env, view_id: BytesN<32>) -> Option<DeliveryReceipt> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().persistent().get(&DataKey::Receipt(view_id))
+    }
+
+    /// Receipts issued to `publisher` on `day` (unix timestamp / 86400)
+    pub fn get_daily_receipt_count(env: Env, publisher: Address, day: u64) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::DailyReceipts(publisher, day))
+            .unwrap_or(0)
+    }
+
     pub fn get_suspicious_status(env: Env, publisher: Address) -> Option<SuspiciousActivity> {
         env.storage()
             .instance()
@@ -402,6 +632,152 @@ impl FraudPreventionContract {
     // Internal Helpers
     // ============================================================
 
+    fn _verify_view(
+        env: &Env,
+        campaign_id: u64,
+        publisher: Address,
+        viewer: Address,
+        proof_data: Option<BytesN<32>>,
+        attested: bool,
+    ) -> Result<bool, PulsarError> {
+        // Rate limiting: check viewer hasn't exceeded limit in current period
+        let current_period = env.ledger().timestamp() / 3600; // hourly buckets
+        let rate_key = DataKey::ViewerRateLimit(viewer.clone(), current_period);
+        let view_count: u64 = env.storage().temporary().get(&rate_key).unwrap_or(0);
+
+        let max_views: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxViewsPerPeriod)
+            .unwrap_or(10);
+        if view_count >= max_views {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        let view_id = Self::_generate_view_id(env, campaign_id, &publisher, &viewer);
+        if env.storage().persistent().has(&DataKey::ViewRecord(view_id.clone())) {
+            return Err(PulsarError::AlreadyExists);
+        }
+
+        let mut quality = Self::_viewer_quality(env, &viewer);
+        let score = Self::_calculate_score(env, campaign_id, &publisher, &quality, &proof_data);
+        let threshold = Self::_effective_threshold(env, &publisher);
+        let verified = score >= threshold;
+
+        let record = ViewRecord {
+            campaign_id,
+            publisher: publisher.clone(),
+            viewer: viewer.clone(),
+            timestamp: env.ledger().timestamp(),
+            verification_score: score,
+            verified,
+        };
+
+        let _ttl_key = DataKey::ViewRecord(view_id.clone());
+        env.storage().persistent().set(&_ttl_key, &record);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage().temporary().set(&rate_key, &(view_count + 1));
+
+        if attested {
+            if verified {
+                quality.verified = quality.verified.saturating_add(1);
+            } else {
+                quality.rejected = quality.rejected.saturating_add(1);
+            }
+            let _ttl_key = DataKey::ViewerQuality(viewer.clone());
+            env.storage().persistent().set(&_ttl_key, &quality);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+
+        let current_day = env.ledger().timestamp() / 86_400;
+        let cache_key = DataKey::VerificationCache(campaign_id, current_day);
+        let mut cache: VerificationCache =
+            env.storage()
+                .temporary()
+                .get(&cache_key)
+                .unwrap_or(VerificationCache {
+                    total_views: 0,
+                    verified_views: 0,
+                    rejected_views: 0,
+                    average_score: 0,
+                });
+
+        cache.total_views += 1;
+        if verified {
+            cache.verified_views += 1;
+        } else {
+            cache.rejected_views += 1;
+        }
+        cache.average_score = ((cache.average_score as u64 * (cache.total_views - 1) + score as u64) / cache.total_views) as u32;
+        env.storage().temporary().set(&cache_key, &cache);
+
+        if verified {
+            let counter: u64 = env.storage().instance().get(&DataKey::VerifyCounter).unwrap_or(0);
+            env.storage().instance().set(&DataKey::VerifyCounter, &(counter + 1));
+            Self::_issue_receipt(env, &view_id, campaign_id, &publisher, score, current_day);
+        }
+        if verified && attested {
+            let views_key = DataKey::PublisherVerifiedViews(campaign_id, publisher.clone());
+            let views: u64 = env.storage().persistent().get(&views_key).unwrap_or(0);
+            env.storage().persistent().set(&views_key, &(views + 1));
+            env.storage().persistent().extend_ttl(
+                &views_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("view"),
+            symbol_short!("verified"),
+            (campaign_id, publisher, verified, view_id),
+        );
+
+        Ok(verified)
+    }
+
+    fn _issue_receipt(
+        env: &Env,
+        view_id: &BytesN<32>,
+        campaign_id: u64,
+        publisher: &Address,
+        score: u32,
+        day: u64,
+    ) {
+        let receipt = DeliveryReceipt {
+            view_id: view_id.clone(),
+            campaign_id,
+            publisher: publisher.clone(),
+            score,
+            issued_at: env.ledger().timestamp(),
+        };
+        let _ttl_key = DataKey::Receipt(view_id.clone());
+        env.storage().persistent().set(&_ttl_key, &receipt);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let _ttl_key = DataKey::DailyReceipts(publisher.clone(), day);
+        let count: u64 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+        env.storage().persistent().set(&_ttl_key, &(count + 1));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
     fn _generate_view_id(
         env: &Env,
         campaign_id: u64,
@@ -423,27 +799,89 @@ impl FraudPreventionContract {
         env.crypto().sha256(&data).into()
     }
 
-    fn _require_admin_or_oracle(env: &Env, caller: &Address) {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+    /// `viewer`'s quality with counts halved for every period since the last view
+    fn _viewer_quality(env: &Env, viewer: &Address) -> ViewerQuality {
+        let period = env.ledger().timestamp() / QUALITY_PERIOD_SECS;
+        let mut quality: ViewerQuality = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ViewerQuality(viewer.clone()))
+            .unwrap_or(ViewerQuality {
+                verified: 0,
+                rejected: 0,
+                period,
+            });
+        let elapsed = period.saturating_sub(quality.period).min(31) as u32;
+        quality.verified >>= elapsed;
+        quality.rejected >>= elapsed;
+        quality.period = period;
+        quality
+    }
+
+    fn _effective_threshold(env: &Env, publisher: &Address) -> u32 {
+        let base: u32 = env.storage().instance().get(&DataKey::VerificationThreshold).unwrap_or(80);
+        let bands: Vec<ReputationBand> = match env.storage().instance().get(&DataKey::ReputationBands) {
+            Some(bands) => bands,
+            None => return base,
+        };
+        let reputation_addr = match env.storage().instance().get::<DataKey, Address>(&DataKey::ReputationContract) {
+            Some(addr) if !bands.is_empty() => addr,
+            _ => return base,
+        };
+        let reputation: u32 = env.invoke_contract(
+            &reputation_addr,
+            &Symbol::new(env, "get_score"),
+            Vec::from_array(env, [publisher.clone().into_val(env)]),
+        );
+        let mut threshold = base;
+        for band in bands.iter() {
+            if reputation < band.min_reputation {
+                break;
+            }
+            threshold = band.threshold;
+        }
+        threshold
+    }
+
+    fn _require_admin_or_oracle(env: &Env, caller: &Address) -> Result<(), PulsarError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if caller == &admin {
-            return;
+            return Ok(());
         }
-        let is_oracle = env.storage().persistent().get(&DataKey::AuthorizedOracle(caller.clone())).unwrap_or(false);
+        // With a shared registry configured it replaces the local oracle list
+        let is_oracle = match env.storage().instance().get::<DataKey, Address>(&DataKey::OracleRegistry) {
+            Some(registry) => pulsar_common_rbac::registry_allows(env, &registry, caller, pulsar_common_rbac::OracleDomain::Fraud),
+            None => env.storage().persistent().get(&DataKey::AuthorizedOracle(caller.clone())).unwrap_or(false),
+        };
         if !is_oracle {
-            panic!("unauthorized - only admin or oracle can flag publishers");
+            return Err(PulsarError::Unauthorized);
         }
+        Ok(())
     }
 
     fn _calculate_score(
         env: &Env,
         campaign_id: u64,
         _publisher: &Address,
+        quality: &ViewerQuality,
         proof_data: &Option<BytesN<32>>,
     ) -> u32 {
         let base_score: u32 = 80;
         let proof_bonus: u32 = if proof_data.is_some() { 10 } else { 0 };
 
-        let score = base_score + proof_bonus;
+        // Clean viewers earn up to the bonus; the penalty grows with the
+        // rejected share and cancels the bonus at 20% rejected
+        let mut score = base_score + proof_bonus;
+        let samples = quality.verified as u64 + quality.rejected as u64;
+        if samples >= QUALITY_MIN_SAMPLES as u64 {
+            let rejected_pct = (quality.rejected as u64 * 100 / samples) as u32;
+            let penalty = rejected_pct * (QUALITY_MAX_BONUS + QUALITY_MAX_PENALTY) / 100;
+            score = (score + QUALITY_MAX_BONUS).saturating_sub(penalty);
+        }
 
         // If score is very low, trigger campaign pause
         if score < 50 {
@@ -459,18 +897,41 @@ impl FraudPreventionContract {
         score
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    /// Emergency stop for view verification and flagging
+    pub fn set_paused(env: Env, admin: Address, paused: bool) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::set_paused(&env, &DataKey::Admin, &DataKey::Paused, admin, paused)
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::is_paused(&env, &DataKey::Paused)
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
@@ -491,8 +952,17 @@ mod mocks {
     pub struct PublisherNetworkContract;
     #[contractimpl]
     impl PublisherNetworkContract {
-        pub fn suspend_publisher(env: Env, fraud_contract: Address, publisher: Address) {
+        pub fn suspend_publisher(
+            env: Env,
+            fraud_contract: Address,
+            publisher: Address,
+        ) -> Result<(), PulsarError> {
             let _ = (env, fraud_contract, publisher);
+            Ok(())
+        }
+
+        pub fn approve_reinstatement(env: Env, caller: Address, publisher: Address) {
+            let _ = (env, caller, publisher);
         }
     }
 
@@ -1,13 +1,32 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::{Address as _, Ledger}, Address, BytesN, Env};
+use soroban_sdk::{testutils::{Address as _, Events, Ledger}, Address, BytesN, Env, IntoVal};
+
+mod registry_mock {
+    use pulsar_common_rbac::OracleDomain;
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockOracleRegistry;
+
+    #[contractimpl]
+    impl MockOracleRegistry {
+        pub fn allow(env: Env, oracle: Address, domain: OracleDomain) {
+            env.storage().persistent().set(&(oracle, domain), &true);
+        }
+
+        pub fn is_authorized(env: Env, oracle: Address, domain: OracleDomain) -> bool {
+            env.storage().persistent().has(&(oracle, domain))
+        }
+    }
+}
 
 // ─── helpers ─────────────────────────────────────────────────────────────────
 
 fn setup(env: &Env) -> (FraudPreventionContractClient<'_>, Address) {
     let admin = Address::generate(env);
 
-    let contract_id = env.register_contract(None, FraudPreventionContract);
+    let contract_id = env.register(FraudPreventionContract, ());
     let client = FraudPreventionContractClient::new(env, &contract_id);
     client.initialize(&admin);
 
@@ -21,7 +40,7 @@ fn test_initialize() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let contract_id = env.register_contract(None, FraudPreventionContract);
+    let contract_id = env.register(FraudPreventionContract, ());
     let client = FraudPreventionContractClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
@@ -30,18 +49,17 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let contract_id = env.register_contract(None, FraudPreventionContract);
+    let contract_id = env.register(FraudPreventionContract, ());
     let client = FraudPreventionContractClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
 
     client.initialize(&admin);
-    client.initialize(&admin);
+    assert_eq!(client.try_initialize(&admin), Err(Ok(PulsarError::AlreadyInitialized)));
 }
 
 #[test]
@@ -49,7 +67,7 @@ fn test_initialize_twice() {
 fn test_initialize_non_admin_fails() {
     let env = Env::default();
 
-    let contract_id = env.register_contract(None, FraudPreventionContract);
+    let contract_id = env.register(FraudPreventionContract, ());
     let client = FraudPreventionContractClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
@@ -74,7 +92,6 @@ fn test_set_dependent_contracts() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_set_dependent_contracts_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
@@ -85,7 +102,10 @@ fn test_set_dependent_contracts_unauthorized() {
     let network = Address::generate(&env);
     let vault = Address::generate(&env);
 
-    client.set_dependent_contracts(&stranger, &lifecycle, &network, &vault);
+    assert_eq!(
+        client.try_set_dependent_contracts(&stranger, &lifecycle, &network, &vault),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 // ─── set_threshold ───────────────────────────────────────────────────────────
@@ -100,34 +120,31 @@ fn test_set_threshold() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_set_threshold_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, _) = setup(&env);
     let stranger = Address::generate(&env);
 
-    client.set_threshold(&stranger, &90u32);
+    assert_eq!(client.try_set_threshold(&stranger, &90u32), Err(Ok(PulsarError::Unauthorized)));
 }
 
 #[test]
-#[should_panic(expected = "invalid threshold")]
 fn test_set_threshold_too_low() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, admin) = setup(&env);
 
-    client.set_threshold(&admin, &40u32); // < 50
+    assert_eq!(client.try_set_threshold(&admin, &40u32), Err(Ok(PulsarError::InvalidInput))); // < 50
 }
 
 #[test]
-#[should_panic(expected = "invalid threshold")]
 fn test_set_threshold_too_high() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, admin) = setup(&env);
 
-    client.set_threshold(&admin, &101u32); // > 100
+    assert_eq!(client.try_set_threshold(&admin, &101u32), Err(Ok(PulsarError::InvalidInput))); // > 100
 }
 
 // ─── oracle management ───────────────────────────────────────────────────────
@@ -143,7 +160,6 @@ fn test_add_oracle_admin() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_add_oracle_unauthorized_fails() {
     let env = Env::default();
     env.mock_all_auths();
@@ -151,7 +167,7 @@ fn test_add_oracle_unauthorized_fails() {
     let stranger = Address::generate(&env);
     let oracle = Address::generate(&env);
 
-    client.add_oracle(&stranger, &oracle);
+    assert_eq!(client.try_add_oracle(&stranger, &oracle), Err(Ok(PulsarError::Unauthorized)));
 }
 
 #[test]
@@ -201,7 +217,6 @@ fn test_flag_suspicious_oracle() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized - only admin or oracle can flag publishers")]
 fn test_flag_suspicious_unauthorized_fails() {
     let env = Env::default();
     env.mock_all_auths();
@@ -209,7 +224,10 @@ fn test_flag_suspicious_unauthorized_fails() {
     let stranger = Address::generate(&env);
     let publisher = Address::generate(&env);
 
-    client.flag_suspicious(&stranger, &publisher);
+    assert_eq!(
+        client.try_flag_suspicious(&stranger, &publisher),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
@@ -244,7 +262,6 @@ fn test_suspend_publisher_admin() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_suspend_publisher_unauthorized_fails() {
     let env = Env::default();
     env.mock_all_auths();
@@ -252,11 +269,13 @@ fn test_suspend_publisher_unauthorized_fails() {
     let stranger = Address::generate(&env);
     let publisher = Address::generate(&env);
 
-    client.suspend_publisher(&stranger, &publisher);
+    assert_eq!(
+        client.try_suspend_publisher(&stranger, &publisher),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_suspend_publisher_oracle_fails() {
     let env = Env::default();
     env.mock_all_auths();
@@ -267,7 +286,10 @@ fn test_suspend_publisher_oracle_fails() {
 
     client.add_oracle(&admin, &oracle);
     // Oracle can flag, but cannot suspend
-    client.suspend_publisher(&oracle, &publisher);
+    assert_eq!(
+        client.try_suspend_publisher(&oracle, &publisher),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 // ─── clear_flag ──────────────────────────────────────────────────────────────
@@ -287,7 +309,6 @@ fn test_clear_flag() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_clear_flag_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
@@ -296,7 +317,25 @@ fn test_clear_flag_unauthorized() {
     let publisher = Address::generate(&env);
 
     client.flag_suspicious(&admin, &publisher);
-    client.clear_flag(&stranger, &publisher);
+    assert_eq!(client.try_clear_flag(&stranger, &publisher), Err(Ok(PulsarError::Unauthorized)));
+}
+
+#[test]
+fn test_reinstate_publisher() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let network = env.register(mocks::PublisherNetworkContract, ());
+    let lifecycle = Address::generate(&env);
+    let vault = Address::generate(&env);
+    client.set_dependent_contracts(&admin, &lifecycle, &network, &vault);
+
+    let publisher = Address::generate(&env);
+    client.suspend_publisher(&admin, &publisher);
+    assert!(client.is_publisher_suspended(&publisher));
+
+    client.reinstate_publisher(&admin, &publisher);
+    assert!(!client.is_publisher_suspended(&publisher));
 }
 
 // ─── verify_view ─────────────────────────────────────────────────────────────
@@ -323,6 +362,42 @@ fn test_verify_view_with_proof() {
     assert_eq!(client.get_total_verifications(), 1);
 }
 
+#[test]
+fn test_get_view_by_event_id() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+
+    let publisher = Address::generate(&env);
+    let viewer = Address::generate(&env);
+    let proof = BytesN::from_array(&env, &[1u8; 32]);
+    client.verify_view(&7u64, &publisher, &viewer, &Some(proof));
+
+    let (_, _, data) = env.events().all().last().unwrap();
+    let (_, _, _, view_id): (u64, Address, bool, BytesN<32>) = data.into_val(&env);
+    let record = client.get_view(&view_id).unwrap();
+    assert_eq!(record.campaign_id, 7);
+    assert_eq!(record.viewer, viewer);
+    assert!(record.verified);
+    assert!(client.get_view(&BytesN::from_array(&env, &[0u8; 32])).is_none());
+}
+
+#[test]
+fn test_verify_view_while_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    client.set_paused(&admin, &true);
+    let result = client.try_verify_view(
+        &1u64,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &None,
+    );
+    assert_eq!(result, Err(Ok(PulsarError::Paused)));
+}
+
 #[test]
 fn test_verify_view_without_proof() {
     let env = Env::default();
@@ -390,7 +465,7 @@ fn test_fraud_integration() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let contract_id = env.register_contract(None, FraudPreventionContract);
+    let contract_id = env.register(FraudPreventionContract, ());
     let client = FraudPreventionContractClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
@@ -435,3 +510,249 @@ fn test_duplicate_view_prevention_same_campaign() {
     });
     assert!(client.verify_view(&1u64, &publisher1, &viewer1, &None));
 }
+
+#[test]
+fn test_oracle_registry_replaces_local_oracles() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let local = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let publisher = Address::generate(&env);
+    client.add_oracle(&admin, &local);
+    let registry_id = env.register(registry_mock::MockOracleRegistry, ());
+    let registry = registry_mock::MockOracleRegistryClient::new(&env, &registry_id);
+    registry.allow(&reporter, &pulsar_common_rbac::OracleDomain::Fraud);
+    client.set_oracle_registry(&admin, &registry_id);
+
+    client.flag_suspicious(&reporter, &publisher);
+    assert_eq!(client.get_suspicious_status(&publisher).unwrap().total_flags, 1);
+    assert!(client.try_flag_suspicious(&local, &publisher).is_err());
+}
+
+mod reputation_mock {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockReputation;
+
+    #[contractimpl]
+    impl MockReputation {
+        pub fn set_score(env: Env, publisher: Address, score: u32) {
+            env.storage().persistent().set(&publisher, &score);
+        }
+
+        pub fn get_score(env: Env, publisher: Address) -> u32 {
+            env.storage().persistent().get(&publisher).unwrap_or(0)
+        }
+    }
+}
+
+fn setup_bands(env: &Env) -> (FraudPreventionContractClient<'_>, reputation_mock::MockReputationClient<'_>) {
+    let (client, admin) = setup(env);
+    let reputation_id = env.register(reputation_mock::MockReputation, ());
+    client.set_reputation_contract(&admin, &reputation_id);
+    client.set_reputation_bands(
+        &admin,
+        &soroban_sdk::vec![
+            env,
+            ReputationBand { min_reputation: 0, threshold: 90 },
+            ReputationBand { min_reputation: 300, threshold: 80 },
+            ReputationBand { min_reputation: 800, threshold: 70 },
+        ],
+    );
+    (client, reputation_mock::MockReputationClient::new(env, &reputation_id))
+}
+
+#[test]
+fn test_effective_threshold_follows_reputation_bands() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, reputation) = setup_bands(&env);
+
+    let fresh = Address::generate(&env);
+    let established = Address::generate(&env);
+    let trusted = Address::generate(&env);
+    reputation.set_score(&established, &500u32);
+    reputation.set_score(&trusted, &800u32);
+
+    assert_eq!(client.get_effective_threshold(&fresh), 90);
+    assert_eq!(client.get_effective_threshold(&established), 80);
+    assert_eq!(client.get_effective_threshold(&trusted), 70);
+
+    // A new node needs the proof bonus to clear the raised bar
+    assert!(client.verify_view(&1u64, &fresh, &Address::generate(&env), &Some(BytesN::from_array(&env, &[1u8; 32]))));
+    assert!(!client.verify_view(&1u64, &fresh, &Address::generate(&env), &None));
+    assert!(client.verify_view(&1u64, &trusted, &Address::generate(&env), &None));
+}
+
+#[test]
+fn test_bands_ignored_without_reputation_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.set_reputation_bands(&admin, &soroban_sdk::vec![&env, ReputationBand { min_reputation: 0, threshold: 95 }]);
+    assert_eq!(client.get_effective_threshold(&Address::generate(&env)), 80);
+}
+
+#[test]
+fn test_reputation_bands_must_ascend() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let result = client.try_set_reputation_bands(
+        &admin,
+        &soroban_sdk::vec![
+            &env,
+            ReputationBand { min_reputation: 500, threshold: 70 },
+            ReputationBand { min_reputation: 500, threshold: 60 },
+        ],
+    );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidInput)));
+}
+
+#[test]
+fn test_verified_view_issues_delivery_receipt() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+
+    let publisher = Address::generate(&env);
+    let proof = BytesN::from_array(&env, &[1u8; 32]);
+    client.verify_view(&7u64, &publisher, &Address::generate(&env), &Some(proof.clone()));
+    client.verify_view(&8u64, &publisher, &Address::generate(&env), &Some(proof));
+
+    let (_, _, data) = env.events().all().last().unwrap();
+    let (_, _, _, view_id): (u64, Address, bool, BytesN<32>) = data.into_val(&env);
+    let receipt = client.get_receipt(&view_id).unwrap();
+    assert_eq!(receipt.campaign_id, 8);
+    assert_eq!(receipt.publisher, publisher);
+    assert_eq!(receipt.score, 90);
+
+    let today = env.ledger().timestamp() / 86_400;
+    assert_eq!(client.get_daily_receipt_count(&publisher, &today), 2);
+    assert_eq!(client.get_daily_receipt_count(&publisher, &(today + 1)), 0);
+    assert!(client.get_receipt(&BytesN::from_array(&env, &[0u8; 32])).is_none());
+
+    // Unattested views never count toward bonus weight
+    assert_eq!(client.get_publisher_verified_views(&8u64, &publisher), 0);
+}
+
+#[test]
+fn test_only_attested_views_count_for_publisher() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let oracle = Address::generate(&env);
+    client.add_oracle(&admin, &oracle);
+
+    let publisher = Address::generate(&env);
+    client.verify_view(&8u64, &publisher, &Address::generate(&env), &None);
+    assert!(client.verify_view_attested(&oracle, &8u64, &publisher, &Address::generate(&env), &None));
+    assert_eq!(client.get_publisher_verified_views(&8u64, &publisher), 1);
+    assert_eq!(client.get_publisher_verified_views(&9u64, &publisher), 0);
+}
+
+#[test]
+fn test_verify_view_attested_requires_oracle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let result = client.try_verify_view_attested(
+        &Address::generate(&env),
+        &8u64,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &None,
+    );
+    assert_eq!(result, Err(Ok(PulsarError::Unauthorized)));
+}
+
+// ─── viewer quality ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_clean_viewer_earns_quality_bonus() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let oracle = Address::generate(&env);
+    client.add_oracle(&admin, &oracle);
+    let viewer = Address::generate(&env);
+
+    for _ in 0..QUALITY_MIN_SAMPLES {
+        assert!(client.verify_view_attested(&oracle, &1u64, &Address::generate(&env), &viewer, &None));
+    }
+    let quality = client.get_viewer_quality(&viewer);
+    assert_eq!(quality.verified, QUALITY_MIN_SAMPLES);
+    assert_eq!(quality.rejected, 0);
+
+    // 80 + 5 clears a bar a first-time viewer without proof does not
+    client.set_threshold(&admin, &85u32);
+    assert!(client.verify_view(&1u64, &Address::generate(&env), &viewer, &None));
+    assert!(!client.verify_view(&1u64, &Address::generate(&env), &Address::generate(&env), &None));
+}
+
+#[test]
+fn test_rejected_viewer_is_discounted_until_history_decays() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let oracle = Address::generate(&env);
+    client.add_oracle(&admin, &oracle);
+    client.set_threshold(&admin, &90u32);
+    let viewer = Address::generate(&env);
+    let proof = BytesN::from_array(&env, &[1u8; 32]);
+
+    for _ in 0..QUALITY_MIN_SAMPLES {
+        assert!(!client.verify_view_attested(&oracle, &1u64, &Address::generate(&env), &viewer, &None));
+    }
+    assert_eq!(client.get_viewer_quality(&viewer).rejected, QUALITY_MIN_SAMPLES);
+    assert_eq!(client.get_verification_stats(&1u64).rejected_views, 5);
+
+    // 90 + 5 - 25: even the proof bonus no longer clears the bar
+    assert!(!client.verify_view(&1u64, &Address::generate(&env), &viewer, &Some(proof.clone())));
+    let fresh = Address::generate(&env);
+    assert!(client.verify_view(&1u64, &Address::generate(&env), &fresh, &Some(proof.clone())));
+
+    env.ledger().with_mut(|li| li.timestamp += 3 * QUALITY_PERIOD_SECS);
+    assert_eq!(client.get_viewer_quality(&viewer).rejected, 0);
+    assert!(client.verify_view(&1u64, &Address::generate(&env), &viewer, &Some(proof)));
+}
+
+#[test]
+fn test_unattested_views_leave_viewer_quality_alone() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.set_threshold(&admin, &90u32);
+    let victim = Address::generate(&env);
+
+    for _ in 0..QUALITY_MIN_SAMPLES {
+        assert!(!client.verify_view(&1u64, &Address::generate(&env), &victim, &None));
+    }
+    let quality = client.get_viewer_quality(&victim);
+    assert_eq!(quality.verified, 0);
+    assert_eq!(quality.rejected, 0);
+}
+
+#[test]
+fn test_saturated_viewer_quality_does_not_overflow() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let viewer = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(
+            &DataKey::ViewerQuality(viewer.clone()),
+            &ViewerQuality {
+                verified: u32::MAX,
+                rejected: u32::MAX,
+                period: env.ledger().timestamp() / QUALITY_PERIOD_SECS,
+            },
+        );
+    });
+
+    // Half rejected: 80 + 5 - 12 falls below the default threshold
+    assert!(!client.verify_view(&1u64, &Address::generate(&env), &viewer, &None));
+}
@@ -4,6 +4,8 @@
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env};
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone, PartialEq)]
 pub enum Role {
@@ -54,12 +56,12 @@ pub struct GovernanceCoreContract;
 
 #[contractimpl]
 impl GovernanceCoreContract {
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -78,6 +80,7 @@ impl GovernanceCoreContract {
         env.storage()
             .instance()
             .set(&DataKey::ActiveProposalCount, &0u32);
+        Ok(())
     }
 
     pub fn grant_role(
@@ -86,14 +89,18 @@ impl GovernanceCoreContract {
         account: Address,
         role: Role,
         expires_at: Option<u64>,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let grant = RoleGrant {
@@ -120,18 +127,32 @@ impl GovernanceCoreContract {
             .instance()
             .set(&DataKey::RoleCount(role), &(count + 1));
 
-        env.events()
-            .publish((symbol_short!("role"), symbol_short!("granted")), account);
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("role"),
+            symbol_short!("granted"),
+            account,
+        );
+        Ok(())
     }
 
-    pub fn revoke_role(env: Env, admin: Address, account: Address, role: Role) {
+    pub fn revoke_role(
+        env: Env,
+        admin: Address,
+        account: Address,
+        role: Role,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         env.storage()
@@ -148,6 +169,7 @@ impl GovernanceCoreContract {
                 .instance()
                 .set(&DataKey::RoleCount(role), &(count - 1));
         }
+        Ok(())
     }
 
     pub fn has_role(env: Env, account: Address, role: Role) -> bool {
@@ -169,28 +191,37 @@ impl GovernanceCoreContract {
         }
     }
 
-    pub fn update_params(env: Env, admin: Address, params: GovernanceParams) {
+    pub fn update_params(
+        env: Env,
+        admin: Address,
+        params: GovernanceParams,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         env.storage()
             .instance()
             .set(&DataKey::GovernanceParams, &params);
+        Ok(())
     }
 
-    pub fn get_params(env: Env) -> GovernanceParams {
+    pub fn get_params(env: Env) -> Result<GovernanceParams, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         env.storage()
             .instance()
             .get(&DataKey::GovernanceParams)
-            .expect("not initialized")
+            .ok_or(PulsarError::NotInitialized)
     }
 
     pub fn get_role_grant(env: Env, account: Address, role: Role) -> Option<RoleGrant> {
@@ -202,18 +233,30 @@ impl GovernanceCoreContract {
             .get(&DataKey::RoleGrant(account, role))
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
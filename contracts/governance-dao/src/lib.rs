@@ -1,16 +1,26 @@
 //! PulsarTrack - Governance DAO (Soroban)
 //! On-chain DAO governance with proposals and voting on Stellar.
 //!
-//! Events:
+//! Events (third topic is `pulsar_common_admin::EVENT_SCHEMA_VERSION`):
 //! - ("proposal", "created"): [proposal_id: u64, proposer: Address]
 //! - ("gov", "voted"): [proposal_id: u64, voter: Address, power: i128]
 //! - ("proposal", "finalized"): [proposal_id: u64, status: ProposalStatus]
+//! - ("gov", "emergency"): [proposal_id: u64, paused: bool, targets: u32]
+//!
+//! Emergency actions flip the standard `set_paused(admin, paused)` switch on
+//! suite contracts the DAO administers. They vote over the much shorter
+//! emergency period but must clear the higher emergency threshold.
 
 #![no_std]
+use pulsar_common_meta::MetaAuth;
+use pulsar_common_rbac::OracleDomain;
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, token, Address, Env, String,
+    contract, contractimpl, contracttype, symbol_short, token, vec, Address, BytesN, Env, IntoVal,
+    String, Symbol, Val, Vec,
 };
 
+pub use pulsar_common_errors::PulsarError;
+
 // ============================================================
 // Data Types
 // ============================================================
@@ -53,6 +63,27 @@ pub struct Proposal {
     pub executed_at: Option<u64>,
 }
 
+/// What a proposal does when executed. `Generic` proposals carry no calls;
+/// the others are built by the `propose_*` templates.
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub enum ProposalKind {
+    Generic,
+    FeeChange,
+    TreasuryGrant,
+    OracleRotation,
+    EmergencyAction,
+}
+
+/// One call made by `execute_proposal`, with the DAO as invoker
+#[contracttype]
+#[derive(Clone)]
+pub struct ProposalCall {
+    pub target: Address,
+    pub function_name: Symbol,
+    pub args: Vec<Val>,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct Vote {
@@ -79,6 +110,11 @@ pub enum DataKey {
     Proposal(u64),
     Vote(u64, Address),
     HasVoted(u64, Address),
+    VestingContract,
+    ProposalKind(u64),
+    ProposalCalls(u64),
+    EmergencyVotingPeriod,
+    EmergencyThreshold,
 }
 
 // ============================================================
@@ -90,6 +126,10 @@ const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 34_560;
 const PERSISTENT_BUMP_AMOUNT: u32 = 259_200;
 
+const DEFAULT_EMERGENCY_VOTING_PERIOD: u32 = 720; // ledgers, ~1 hour
+const DEFAULT_EMERGENCY_THRESHOLD: u32 = 67;
+const MAX_EMERGENCY_TARGETS: u32 = 10;
+
 #[contract]
 pub struct GovernanceDaoContract;
 
@@ -104,12 +144,12 @@ impl GovernanceDaoContract {
         quorum_bps: u32,     // basis points (e.g., 1000 = 10%)
         pass_threshold: u32, // percentage (e.g., 51)
         proposer_min: i128,  // min tokens to create proposal
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -131,6 +171,7 @@ impl GovernanceDaoContract {
         env.storage()
             .instance()
             .set(&DataKey::ProposerMinTokens, &proposer_min);
+        Ok(())
     }
 
     /// Create a new governance proposal
@@ -140,178 +181,336 @@ impl GovernanceDaoContract {
         title: String,
         description: String,
         target_contract: Option<Address>,
-    ) -> u64 {
+    ) -> Result<u64, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         proposer.require_auth();
+        Self::_create_proposal(&env, proposer, title, description, target_contract)
+    }
 
-        // Enforce minimum token requirement for proposal creation
-        let min_tokens: i128 = env
-            .storage()
+    /// Template: set `target`'s platform fee (`set_platform_fee(admin, fee)`,
+    /// as on campaign-orchestrator and payment-processor). The DAO must be the
+    /// target's admin by execution time.
+    pub fn propose_fee_change(
+        env: Env,
+        proposer: Address,
+        target: Address,
+        new_fee: u32,
+        description: String,
+    ) -> Result<u64, PulsarError> {
+        env.storage()
             .instance()
-            .get(&DataKey::ProposerMinTokens)
-            .unwrap_or(0);
-        if min_tokens > 0 {
-            let gov_token: Address = env
-                .storage()
-                .instance()
-                .get(&DataKey::GovernanceToken)
-                .unwrap();
-            let balance = token::Client::new(&env, &gov_token).balance(&proposer);
-            if balance < min_tokens {
-                panic!("insufficient tokens to create proposal");
-            }
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        proposer.require_auth();
+        if new_fee > 10_000 {
+            return Err(PulsarError::InvalidAmount);
         }
+        let dao = env.current_contract_address();
+        let calls = vec![
+            &env,
+            ProposalCall {
+                target: target.clone(),
+                function_name: Symbol::new(&env, "set_platform_fee"),
+                args: vec![&env, dao.into_val(&env), new_fee.into_val(&env)],
+            },
+        ];
+        Self::_create_executable(
+            &env,
+            proposer,
+            String::from_str(&env, "Fee change"),
+            description,
+            target,
+            ProposalKind::FeeChange,
+            calls,
+        )
+    }
 
-        let counter: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::ProposalCounter)
-            .unwrap_or(0);
-        let proposal_id = counter + 1;
-
-        let voting_period: u32 = env
-            .storage()
+    /// Template: pay `amount` of the governance token from the DAO's balance
+    pub fn propose_treasury_grant(
+        env: Env,
+        proposer: Address,
+        recipient: Address,
+        amount: i128,
+        description: String,
+    ) -> Result<u64, PulsarError> {
+        env.storage()
             .instance()
-            .get(&DataKey::VotingPeriod)
-            .unwrap_or(1_000);
-        let quorum_bps: u32 = env
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        proposer.require_auth();
+        let dao = env.current_contract_address();
+        if amount <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        if recipient == dao {
+            return Err(PulsarError::InvalidInput);
+        }
+        let gov_token: Address = env
             .storage()
             .instance()
-            .get(&DataKey::QuorumBps)
-            .unwrap_or(0);
-        let threshold: u32 = env
-            .storage()
+            .get(&DataKey::GovernanceToken)
+            .unwrap();
+        let calls = vec![
+            &env,
+            ProposalCall {
+                target: gov_token.clone(),
+                function_name: Symbol::new(&env, "transfer"),
+                args: vec![
+                    &env,
+                    dao.into_val(&env),
+                    recipient.into_val(&env),
+                    amount.into_val(&env),
+                ],
+            },
+        ];
+        Self::_create_executable(
+            &env,
+            proposer,
+            String::from_str(&env, "Treasury grant"),
+            description,
+            gov_token,
+            ProposalKind::TreasuryGrant,
+            calls,
+        )
+    }
+
+    /// Template: on oracle-registry `registry`, revoke `domain` from
+    /// `old_oracle` and grant it to `new_oracle`
+    pub fn propose_oracle_rotation(
+        env: Env,
+        proposer: Address,
+        registry: Address,
+        old_oracle: Address,
+        new_oracle: Address,
+        domain: OracleDomain,
+        description: String,
+    ) -> Result<u64, PulsarError> {
+        env.storage()
             .instance()
-            .get(&DataKey::PassThreshold)
-            .unwrap_or(51);
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        proposer.require_auth();
+        if old_oracle == new_oracle {
+            return Err(PulsarError::InvalidInput);
+        }
+        let dao = env.current_contract_address();
+        let calls = vec![
+            &env,
+            ProposalCall {
+                target: registry.clone(),
+                function_name: Symbol::new(&env, "revoke_domain"),
+                args: vec![
+                    &env,
+                    dao.clone().into_val(&env),
+                    old_oracle.into_val(&env),
+                    domain.clone().into_val(&env),
+                ],
+            },
+            ProposalCall {
+                target: registry.clone(),
+                function_name: Symbol::new(&env, "grant_domain"),
+                args: vec![
+                    &env,
+                    dao.into_val(&env),
+                    new_oracle.into_val(&env),
+                    domain.into_val(&env),
+                ],
+            },
+        ];
+        Self::_create_executable(
+            &env,
+            proposer,
+            String::from_str(&env, "Oracle rotation"),
+            description,
+            registry,
+            ProposalKind::OracleRotation,
+            calls,
+        )
+    }
 
-        let start = env.ledger().sequence();
-        let proposal = Proposal {
-            proposer: proposer.clone(),
-            title,
+    /// Template: call `set_paused(dao, paused)` on each of `targets`, e.g.
+    /// the orchestrator, payment-processor and bridge. The DAO must be each
+    /// target's admin (or hold its pauser role) by execution time.
+    pub fn propose_emergency_action(
+        env: Env,
+        proposer: Address,
+        targets: Vec<Address>,
+        paused: bool,
+        description: String,
+    ) -> Result<u64, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        proposer.require_auth();
+        if targets.is_empty() || targets.len() > MAX_EMERGENCY_TARGETS {
+            return Err(PulsarError::InvalidInput);
+        }
+        let dao = env.current_contract_address();
+        let mut calls = Vec::new(&env);
+        for target in targets.iter() {
+            calls.push_back(ProposalCall {
+                target,
+                function_name: Symbol::new(&env, "set_paused"),
+                args: vec![&env, dao.clone().into_val(&env), paused.into_val(&env)],
+            });
+        }
+        let proposal_id = Self::_create_executable(
+            &env,
+            proposer,
+            String::from_str(&env, "Emergency action"),
             description,
-            target_contract,
-            status: ProposalStatus::Active,
-            votes_for: 0,
-            votes_against: 0,
-            votes_abstain: 0,
-            quorum_bps,
-            threshold_pct: threshold,
-            start_ledger: start,
-            end_ledger: start + voting_period,
-            created_at: env.ledger().timestamp(),
-            executed_at: None,
-        };
+            targets.get(0).unwrap(),
+            ProposalKind::EmergencyAction,
+            calls,
+        )?;
 
+        let (voting_period, threshold) = Self::get_emergency_params(env.clone());
         let _ttl_key = DataKey::Proposal(proposal_id);
+        let mut proposal: Proposal = env.storage().persistent().get(&_ttl_key).unwrap();
+        proposal.end_ledger = proposal.start_ledger + voting_period;
+        proposal.threshold_pct = threshold;
         env.storage().persistent().set(&_ttl_key, &proposal);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-        env.storage()
-            .instance()
-            .set(&DataKey::ProposalCounter, &proposal_id);
 
-        env.events().publish(
-            (symbol_short!("proposal"), symbol_short!("created")),
-            (proposal_id, proposer),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("gov"),
+            symbol_short!("emergency"),
+            (proposal_id, paused, targets.len()),
         );
-
-        proposal_id
+        Ok(proposal_id)
     }
 
-    /// Cast a vote on a proposal
-    pub fn cast_vote(env: Env, voter: Address, proposal_id: u64, choice: VoteChoice, power: i128) {
+    /// Voting period (ledgers) and pass threshold (%) for emergency actions.
+    /// The period may not exceed the regular one and the threshold may not be
+    /// below the regular one.
+    pub fn set_emergency_params(
+        env: Env,
+        admin: Address,
+        voting_period: u32,
+        threshold_pct: u32,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        voter.require_auth();
-
-        // Check not already voted
-        if env
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
-            .persistent()
-            .has(&DataKey::HasVoted(proposal_id, voter.clone()))
-        {
-            panic!("already voted");
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
         }
 
-        let gov_token: Address = env
+        let regular_period: u32 = env
             .storage()
             .instance()
-            .get(&DataKey::GovernanceToken)
-            .unwrap();
-        let token_client = token::Client::new(&env, &gov_token);
-        let balance = token_client.balance(&voter);
-        if power > balance {
-            panic!("insufficient governance tokens");
-        }
-
-        let mut proposal: Proposal = env
+            .get(&DataKey::VotingPeriod)
+            .unwrap_or(1_000);
+        let regular_threshold: u32 = env
             .storage()
-            .persistent()
-            .get(&DataKey::Proposal(proposal_id))
-            .expect("proposal not found");
-
-        if proposal.status != ProposalStatus::Active {
-            panic!("proposal not active");
-        }
-
-        if env.ledger().sequence() > proposal.end_ledger {
-            panic!("voting period ended");
+            .instance()
+            .get(&DataKey::PassThreshold)
+            .unwrap_or(51);
+        if voting_period == 0
+            || voting_period > regular_period
+            || threshold_pct < regular_threshold
+            || threshold_pct > 100
+        {
+            return Err(PulsarError::InvalidInput);
         }
+        env.storage()
+            .instance()
+            .set(&DataKey::EmergencyVotingPeriod, &voting_period);
+        env.storage()
+            .instance()
+            .set(&DataKey::EmergencyThreshold, &threshold_pct);
+        Ok(())
+    }
 
-        if power <= 0 {
-            panic!("invalid voting power");
-        }
+    /// (voting period in ledgers, pass threshold %) applied to emergency
+    /// actions. Defaults never exceed the regular period or undercut the
+    /// regular threshold.
+    pub fn get_emergency_params(env: Env) -> (u32, u32) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let regular_period: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::VotingPeriod)
+            .unwrap_or(1_000);
+        let regular_threshold: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PassThreshold)
+            .unwrap_or(51);
+        let period = env
+            .storage()
+            .instance()
+            .get(&DataKey::EmergencyVotingPeriod)
+            .unwrap_or(DEFAULT_EMERGENCY_VOTING_PERIOD.min(regular_period));
+        let threshold = env
+            .storage()
+            .instance()
+            .get(&DataKey::EmergencyThreshold)
+            .unwrap_or(DEFAULT_EMERGENCY_THRESHOLD.max(regular_threshold));
+        (period, threshold)
+    }
 
-        // Record vote
-        match choice {
-            VoteChoice::For => proposal.votes_for += power,
-            VoteChoice::Against => proposal.votes_against += power,
-            VoteChoice::Abstain => proposal.votes_abstain += power,
-        }
+    /// Cast a vote on a proposal
+    pub fn cast_vote(
+        env: Env,
+        voter: Address,
+        proposal_id: u64,
+        choice: VoteChoice,
+        power: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        voter.require_auth();
+        Self::_cast_vote(&env, voter, proposal_id, choice, power)
+    }
 
-        let vote = Vote {
-            choice,
-            power,
-            voted_at: env.ledger().timestamp(),
-        };
+    /// Relayed `cast_vote`; the voter is the account whose key signed
+    /// `(proposal_id, choice, power)` (see pulsar-common-meta)
+    pub fn cast_vote_signed(
+        env: Env,
+        proposal_id: u64,
+        choice: VoteChoice,
+        power: i128,
+        auth: MetaAuth,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let voter = pulsar_common_meta::verify(
+            &env,
+            Symbol::new(&env, "cast_vote"),
+            (proposal_id, choice.clone(), power),
+            &auth,
+        )?;
+        Self::_cast_vote(&env, voter, proposal_id, choice, power)
+    }
 
-        let _ttl_key = DataKey::Vote(proposal_id, voter.clone());
-        env.storage().persistent().set(&_ttl_key, &vote);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-        let _ttl_key = DataKey::HasVoted(proposal_id, voter.clone());
-        env.storage().persistent().set(&_ttl_key, &true);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-        let _ttl_key = DataKey::Proposal(proposal_id);
-        env.storage().persistent().set(&_ttl_key, &proposal);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+    /// Opt the signing key's account in or out of relayed calls; needs the
+    /// account's own authorization (see pulsar-common-meta)
+    pub fn set_meta_enabled(env: Env, signer: BytesN<32>, enabled: bool) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_meta::set_enabled(&env, &signer, enabled);
+    }
 
-        env.events().publish(
-            (symbol_short!("gov"), symbol_short!("voted")),
-            (proposal_id, voter, power),
-        );
+    pub fn get_meta_nonce(env: Env, signer: BytesN<32>) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_meta::nonce(&env, &signer)
     }
 
     /// Finalize a proposal after voting period
-    pub fn finalize_proposal(env: Env, proposal_id: u64) {
+    pub fn finalize_proposal(env: Env, proposal_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -319,14 +518,14 @@ impl GovernanceDaoContract {
             .storage()
             .persistent()
             .get(&DataKey::Proposal(proposal_id))
-            .expect("proposal not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if proposal.status != ProposalStatus::Active {
-            panic!("proposal not active");
+            return Err(PulsarError::InvalidState);
         }
 
         if env.ledger().sequence() <= proposal.end_ledger {
-            panic!("voting period not ended");
+            return Err(PulsarError::TooEarly);
         }
 
         let token_address: Address = env
@@ -352,8 +551,6 @@ impl GovernanceDaoContract {
 
         proposal.status = if quorum_met && for_pct as u32 >= proposal.threshold_pct {
             ProposalStatus::Passed
-        } else if !quorum_met {
-            ProposalStatus::Rejected
         } else {
             ProposalStatus::Rejected
         };
@@ -366,31 +563,47 @@ impl GovernanceDaoContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("proposal"), symbol_short!("finalized")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("proposal"),
+            symbol_short!("finalized"),
             (proposal_id, proposal.status),
         );
+        Ok(())
     }
 
-    /// Mark proposal as executed (admin only)
-    pub fn execute_proposal(env: Env, admin: Address, proposal_id: u64) {
+    /// Run a passed proposal's calls, if it has any, and mark it executed (admin only)
+    pub fn execute_proposal(env: Env, admin: Address, proposal_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut proposal: Proposal = env
             .storage()
             .persistent()
             .get(&DataKey::Proposal(proposal_id))
-            .expect("proposal not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if proposal.status != ProposalStatus::Passed {
-            panic!("proposal not passed");
+            return Err(PulsarError::InvalidState);
+        }
+
+        let calls: Vec<ProposalCall> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProposalCalls(proposal_id))
+            .unwrap_or(Vec::new(&env));
+        for call in calls.iter() {
+            let _: Val = env.invoke_contract(&call.target, &call.function_name, call.args);
         }
 
         proposal.status = ProposalStatus::Executed;
@@ -403,24 +616,29 @@ impl GovernanceDaoContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     /// Cancel a proposal (proposer or admin)
-    pub fn cancel_proposal(env: Env, caller: Address, proposal_id: u64) {
+    pub fn cancel_proposal(env: Env, caller: Address, proposal_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         caller.require_auth();
 
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         let mut proposal: Proposal = env
             .storage()
             .persistent()
             .get(&DataKey::Proposal(proposal_id))
-            .expect("proposal not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if caller != proposal.proposer && caller != admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         proposal.status = ProposalStatus::Cancelled;
@@ -431,6 +649,7 @@ impl GovernanceDaoContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     // ============================================================
@@ -465,6 +684,27 @@ impl GovernanceDaoContract {
             .unwrap_or(false)
     }
 
+    pub fn get_proposal_kind(env: Env, proposal_id: u64) -> ProposalKind {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::ProposalKind(proposal_id))
+            .unwrap_or(ProposalKind::Generic)
+    }
+
+    /// Calls `execute_proposal` will make, in order
+    pub fn get_proposal_calls(env: Env, proposal_id: u64) -> Vec<ProposalCall> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::ProposalCalls(proposal_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
     pub fn get_proposal_count(env: Env) -> u64 {
         env.storage()
             .instance()
@@ -475,18 +715,279 @@ impl GovernanceDaoContract {
             .unwrap_or(0)
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    /// Count locked token-vesting grants delegated to a voter as voting power
+    pub fn set_vesting_contract(
+        env: Env,
+        admin: Address,
+        vesting: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::VestingContract, &vesting);
+        Ok(())
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
+
+    /// Vesting power delegated to `voter` at the end of the ledger before
+    /// the proposal started, so locked tokens redelegated mid-vote count once
+    fn _vesting_power(env: &Env, voter: &Address, proposal: &Proposal) -> i128 {
+        match env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::VestingContract)
+        {
+            Some(vesting) => env.invoke_contract(
+                &vesting,
+                &soroban_sdk::Symbol::new(env, "get_past_votes"),
+                soroban_sdk::vec![
+                    env,
+                    voter.into_val(env),
+                    proposal.start_ledger.saturating_sub(1).into_val(env),
+                ],
+            ),
+            None => 0,
+        }
+    }
+
+    fn _cast_vote(
+        env: &Env,
+        voter: Address,
+        proposal_id: u64,
+        choice: VoteChoice,
+        power: i128,
+    ) -> Result<(), PulsarError> {
+        // Check not already voted
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::HasVoted(proposal_id, voter.clone()))
+        {
+            return Err(PulsarError::AlreadyExists);
+        }
+
+        let mut proposal: Proposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        let gov_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GovernanceToken)
+            .unwrap();
+        let token_client = token::Client::new(env, &gov_token);
+        let balance = token_client.balance(&voter) + Self::_vesting_power(env, &voter, &proposal);
+        if power > balance {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        if proposal.status != ProposalStatus::Active {
+            return Err(PulsarError::InvalidState);
+        }
+
+        if env.ledger().sequence() > proposal.end_ledger {
+            return Err(PulsarError::Expired);
+        }
+
+        if power <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+
+        // Record vote
+        match choice {
+            VoteChoice::For => proposal.votes_for += power,
+            VoteChoice::Against => proposal.votes_against += power,
+            VoteChoice::Abstain => proposal.votes_abstain += power,
+        }
+
+        let vote = Vote {
+            choice,
+            power,
+            voted_at: env.ledger().timestamp(),
+        };
+
+        let _ttl_key = DataKey::Vote(proposal_id, voter.clone());
+        env.storage().persistent().set(&_ttl_key, &vote);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        let _ttl_key = DataKey::HasVoted(proposal_id, voter.clone());
+        env.storage().persistent().set(&_ttl_key, &true);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        let _ttl_key = DataKey::Proposal(proposal_id);
+        env.storage().persistent().set(&_ttl_key, &proposal);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("gov"),
+            symbol_short!("voted"),
+            (proposal_id, voter, power),
+        );
+        Ok(())
+    }
+
+    fn _create_executable(
+        env: &Env,
+        proposer: Address,
+        title: String,
+        description: String,
+        target: Address,
+        kind: ProposalKind,
+        calls: Vec<ProposalCall>,
+    ) -> Result<u64, PulsarError> {
+        let proposal_id = Self::_create_proposal(env, proposer, title, description, Some(target))?;
+
+        let _ttl_key = DataKey::ProposalKind(proposal_id);
+        env.storage().persistent().set(&_ttl_key, &kind);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
         );
+        let _ttl_key = DataKey::ProposalCalls(proposal_id);
+        env.storage().persistent().set(&_ttl_key, &calls);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Ok(proposal_id)
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    fn _create_proposal(
+        env: &Env,
+        proposer: Address,
+        title: String,
+        description: String,
+        target_contract: Option<Address>,
+    ) -> Result<u64, PulsarError> {
+        // Enforce minimum token requirement for proposal creation
+        let min_tokens: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ProposerMinTokens)
+            .unwrap_or(0);
+        if min_tokens > 0 {
+            let gov_token: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::GovernanceToken)
+                .unwrap();
+            let balance = token::Client::new(env, &gov_token).balance(&proposer);
+            if balance < min_tokens {
+                return Err(PulsarError::Unauthorized);
+            }
+        }
+
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ProposalCounter)
+            .unwrap_or(0);
+        let proposal_id = counter + 1;
+
+        let voting_period: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::VotingPeriod)
+            .unwrap_or(1_000);
+        let quorum_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::QuorumBps)
+            .unwrap_or(0);
+        let threshold: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PassThreshold)
+            .unwrap_or(51);
+
+        let start = env.ledger().sequence();
+        let proposal = Proposal {
+            proposer: proposer.clone(),
+            title,
+            description,
+            target_contract,
+            status: ProposalStatus::Active,
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            quorum_bps,
+            threshold_pct: threshold,
+            start_ledger: start,
+            end_ledger: start + voting_period,
+            created_at: env.ledger().timestamp(),
+            executed_at: None,
+        };
+
+        let _ttl_key = DataKey::Proposal(proposal_id);
+        env.storage().persistent().set(&_ttl_key, &proposal);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::ProposalCounter, &proposal_id);
+
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("proposal"),
+            symbol_short!("created"),
+            (proposal_id, proposer),
+        );
+
+        Ok(proposal_id)
     }
 }
 
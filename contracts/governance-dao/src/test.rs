@@ -1,9 +1,10 @@
 #![cfg(test)]
 use super::*;
+use ed25519_dalek::{Signer, SigningKey};
 use soroban_sdk::{
     contract as soroban_contract, contractimpl as soroban_contractimpl,
     testutils::{Address as _, Ledger},
-    Address, Env, String,
+    Address, BytesN, Env, String, Symbol,
 };
 
 // ─── Mock governance token ───────────────────────────────────────────────────
@@ -41,13 +42,47 @@ impl MockGovToken {
             .get::<Address, i128>(&id)
             .unwrap_or(1_000_000)
     }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        let from_balance = Self::balance(env.clone(), from.clone());
+        let to_balance = Self::balance(env.clone(), to.clone());
+        env.storage()
+            .persistent()
+            .set(&from, &(from_balance - amount));
+        env.storage().persistent().set(&to, &(to_balance + amount));
+    }
+}
+
+#[soroban_contract]
+pub struct MockVesting;
+
+#[soroban_contractimpl]
+impl MockVesting {
+    /// Power delegated to `account` from the current ledger onwards
+    pub fn set_power(env: Env, account: Address, amount: i128) {
+        env.storage()
+            .persistent()
+            .set(&account, &(env.ledger().sequence(), amount));
+    }
+
+    pub fn get_past_votes(env: Env, account: Address, ledger: u32) -> i128 {
+        match env
+            .storage()
+            .persistent()
+            .get::<Address, (u32, i128)>(&account)
+        {
+            Some((from, amount)) if from <= ledger => amount,
+            _ => 0,
+        }
+    }
 }
 
 // ─── helpers ─────────────────────────────────────────────────────────────────
 
 /// Deploy MockGovToken and set its total supply.
 fn deploy_mock_gov_token(env: &Env, total_supply: i128) -> Address {
-    let id = env.register_contract(None, MockGovToken);
+    let id = env.register(MockGovToken, ());
     let client = MockGovTokenClient::new(env, &id);
     client.set_supply(&total_supply);
     id
@@ -60,7 +95,7 @@ fn setup(env: &Env) -> (GovernanceDaoContractClient<'_>, Address, Address, Addre
     let admin = Address::generate(env);
     let token_addr = deploy_mock_gov_token(env, 10_000_000);
 
-    let contract_id = env.register_contract(None, GovernanceDaoContract);
+    let contract_id = env.register(GovernanceDaoContract, ());
     let client = GovernanceDaoContractClient::new(env, &contract_id);
     client.initialize(&admin, &token_addr, &100u32, &1_000u32, &51u32, &0i128);
 
@@ -76,13 +111,46 @@ fn setup_with_mock_token(
     let admin = Address::generate(env);
     let token_addr = deploy_mock_gov_token(env, total_supply);
 
-    let contract_id = env.register_contract(None, GovernanceDaoContract);
+    let contract_id = env.register(GovernanceDaoContract, ());
     let client = GovernanceDaoContractClient::new(env, &contract_id);
     client.initialize(&admin, &token_addr, &100u32, &1_000u32, &51u32, &0i128);
 
     (client, admin, token_addr)
 }
 
+/// Sign a relayed `cast_vote` the way a wallet would
+fn sign_vote(
+    env: &Env,
+    client: &GovernanceDaoContractClient,
+    key: &SigningKey,
+    proposal_id: u64,
+    choice: VoteChoice,
+    power: i128,
+    nonce: u64,
+) -> MetaAuth {
+    let expiry = env.ledger().timestamp() + 300;
+    let digest = env.as_contract(&client.address, || {
+        pulsar_common_meta::message(
+            env,
+            &Symbol::new(env, "cast_vote"),
+            (proposal_id, choice, power),
+            nonce,
+            expiry,
+        )
+    });
+    MetaAuth {
+        signer: BytesN::from_array(env, &key.verifying_key().to_bytes()),
+        nonce,
+        expiry,
+        signature: BytesN::from_array(env, &key.sign(&digest.to_array()).to_bytes()),
+    }
+}
+
+fn account_of(env: &Env, key: &SigningKey) -> Address {
+    let strkey = stellar_strkey::ed25519::PublicKey(key.verifying_key().to_bytes()).to_string();
+    Address::from_str(env, &strkey)
+}
+
 fn make_title(env: &Env) -> String {
     String::from_str(env, "Increase treasury allocation")
 }
@@ -100,23 +168,25 @@ fn test_initialize() {
     let admin = Address::generate(&env);
     let token = Address::generate(&env);
 
-    let contract_id = env.register_contract(None, GovernanceDaoContract);
+    let contract_id = env.register(GovernanceDaoContract, ());
     let client = GovernanceDaoContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token, &3600u32, &1000u32, &51u32, &100i128);
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
     let admin = Address::generate(&env);
     let token = Address::generate(&env);
 
-    let contract_id = env.register_contract(None, GovernanceDaoContract);
+    let contract_id = env.register(GovernanceDaoContract, ());
     let client = GovernanceDaoContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token, &3600u32, &1000u32, &51u32, &100i128);
-    client.initialize(&admin, &token, &3600u32, &1000u32, &51u32, &100i128);
+    assert_eq!(
+        client.try_initialize(&admin, &token, &3600u32, &1000u32, &51u32, &100i128),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -127,7 +197,7 @@ fn test_initialize_non_admin_fails() {
     let admin = Address::generate(&env);
     let token = Address::generate(&env);
 
-    let contract_id = env.register_contract(None, GovernanceDaoContract);
+    let contract_id = env.register(GovernanceDaoContract, ());
     let client = GovernanceDaoContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token, &3600u32, &1000u32, &51u32, &100i128);
 }
@@ -191,6 +261,157 @@ fn test_cast_vote_for() {
     assert_eq!(vote.power, 1_000);
 }
 
+#[test]
+fn test_vesting_power_counts_toward_vote() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, token_addr) = setup(&env);
+    let voter = Address::generate(&env);
+    MockGovTokenClient::new(&env, &token_addr).set_balance(&voter, &400);
+    let vesting_id = env.register(MockVesting, ());
+    MockVestingClient::new(&env, &vesting_id).set_power(&voter, &600);
+
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    let proposal_id = client.create_proposal(&voter, &make_title(&env), &make_desc(&env), &None);
+    assert!(client
+        .try_cast_vote(&voter, &proposal_id, &VoteChoice::For, &1_000i128)
+        .is_err());
+
+    client.set_vesting_contract(&admin, &vesting_id);
+    client.cast_vote(&voter, &proposal_id, &VoteChoice::For, &1_000i128);
+    assert_eq!(client.get_proposal(&proposal_id).unwrap().votes_for, 1_000);
+}
+
+#[test]
+fn test_vesting_power_delegated_after_start_ignored() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, token_addr) = setup(&env);
+    let voter = Address::generate(&env);
+    MockGovTokenClient::new(&env, &token_addr).set_balance(&voter, &400);
+    let vesting_id = env.register(MockVesting, ());
+    client.set_vesting_contract(&admin, &vesting_id);
+
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    let proposal_id = client.create_proposal(&voter, &make_title(&env), &make_desc(&env), &None);
+    // Power that arrives once the vote is open only counts for later proposals
+    MockVestingClient::new(&env, &vesting_id).set_power(&voter, &600);
+    assert_eq!(
+        client.try_cast_vote(&voter, &proposal_id, &VoteChoice::For, &1_000i128),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+    client.cast_vote(&voter, &proposal_id, &VoteChoice::For, &400i128);
+}
+
+#[test]
+fn test_cast_vote_signed_acts_for_signing_account() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, _) = setup(&env);
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let proposal_id = client.create_proposal(
+        &Address::generate(&env),
+        &make_title(&env),
+        &make_desc(&env),
+        &None,
+    );
+
+    let auth = sign_vote(&env, &client, &key, proposal_id, VoteChoice::For, 500, 0);
+    client.set_meta_enabled(&auth.signer, &true);
+    client.cast_vote_signed(&proposal_id, &VoteChoice::For, &500i128, &auth);
+
+    let voter = account_of(&env, &key);
+    assert!(client.has_voted(&proposal_id, &voter));
+    assert_eq!(client.get_proposal(&proposal_id).unwrap().votes_for, 500);
+    assert_eq!(client.get_meta_nonce(&auth.signer), 1);
+}
+
+#[test]
+fn test_cast_vote_signed_rejects_replay_and_tampering() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, _) = setup(&env);
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let first = client.create_proposal(
+        &Address::generate(&env),
+        &make_title(&env),
+        &make_desc(&env),
+        &None,
+    );
+    let second = client.create_proposal(
+        &Address::generate(&env),
+        &make_title(&env),
+        &make_desc(&env),
+        &None,
+    );
+
+    let auth = sign_vote(&env, &client, &key, first, VoteChoice::For, 500, 0);
+    client.set_meta_enabled(&auth.signer, &true);
+    // Same signature with different arguments fails verification
+    assert!(client
+        .try_cast_vote_signed(&first, &VoteChoice::For, &9_000i128, &auth)
+        .is_err());
+    client.cast_vote_signed(&first, &VoteChoice::For, &500i128, &auth);
+    assert!(client
+        .try_cast_vote_signed(&second, &VoteChoice::For, &500i128, &auth)
+        .is_err());
+}
+
+#[test]
+fn test_cast_vote_signed_expired() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, _) = setup(&env);
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let proposal_id = client.create_proposal(
+        &Address::generate(&env),
+        &make_title(&env),
+        &make_desc(&env),
+        &None,
+    );
+
+    let auth = sign_vote(&env, &client, &key, proposal_id, VoteChoice::For, 500, 0);
+    client.set_meta_enabled(&auth.signer, &true);
+    env.ledger().with_mut(|li| li.timestamp += 301);
+    assert_eq!(
+        client.try_cast_vote_signed(&proposal_id, &VoteChoice::For, &500i128, &auth),
+        Err(Ok(PulsarError::Expired))
+    );
+}
+
+#[test]
+fn test_cast_vote_signed_requires_opt_in() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, _) = setup(&env);
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let proposal_id = client.create_proposal(
+        &Address::generate(&env),
+        &make_title(&env),
+        &make_desc(&env),
+        &None,
+    );
+
+    let auth = sign_vote(&env, &client, &key, proposal_id, VoteChoice::For, 500, 0);
+    assert_eq!(
+        client.try_cast_vote_signed(&proposal_id, &VoteChoice::For, &500i128, &auth),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+
+    // Opting in goes through the account's own authorization
+    client.set_meta_enabled(&auth.signer, &true);
+    let account = account_of(&env, &key);
+    assert_eq!(env.auths()[0].0, account);
+
+    // A key the account switched off again is refused
+    client.set_meta_enabled(&auth.signer, &false);
+    assert_eq!(
+        client.try_cast_vote_signed(&proposal_id, &VoteChoice::For, &500i128, &auth),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+    assert!(!client.has_voted(&proposal_id, &account));
+}
+
 #[test]
 fn test_cast_vote_against() {
     let env = Env::default();
@@ -246,7 +467,6 @@ fn test_vote_tally_accumulates() {
 }
 
 #[test]
-#[should_panic(expected = "already voted")]
 fn test_double_vote_rejected() {
     let env = Env::default();
     env.mock_all_auths();
@@ -257,11 +477,13 @@ fn test_double_vote_rejected() {
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
     client.cast_vote(&voter, &proposal_id, &VoteChoice::For, &1_000i128);
-    client.cast_vote(&voter, &proposal_id, &VoteChoice::Against, &1_000i128); // panic
+    assert_eq!(
+        client.try_cast_vote(&voter, &proposal_id, &VoteChoice::Against, &1_000i128),
+        Err(Ok(PulsarError::AlreadyExists))
+    ); // panic
 }
 
 #[test]
-#[should_panic(expected = "invalid voting power")]
 fn test_vote_zero_power_rejected() {
     let env = Env::default();
     env.mock_all_auths();
@@ -271,11 +493,13 @@ fn test_vote_zero_power_rejected() {
 
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
-    client.cast_vote(&voter, &proposal_id, &VoteChoice::For, &0i128);
+    assert_eq!(
+        client.try_cast_vote(&voter, &proposal_id, &VoteChoice::For, &0i128),
+        Err(Ok(PulsarError::InvalidAmount))
+    );
 }
 
 #[test]
-#[should_panic(expected = "voting period ended")]
 fn test_vote_after_period_ends() {
     let env = Env::default();
     env.mock_all_auths();
@@ -290,11 +514,13 @@ fn test_vote_after_period_ends() {
         li.sequence_number = 200;
     });
 
-    client.cast_vote(&voter, &proposal_id, &VoteChoice::For, &1_000i128);
+    assert_eq!(
+        client.try_cast_vote(&voter, &proposal_id, &VoteChoice::For, &1_000i128),
+        Err(Ok(PulsarError::Expired))
+    );
 }
 
 #[test]
-#[should_panic(expected = "proposal not active")]
 fn test_vote_on_cancelled_proposal() {
     let env = Env::default();
     env.mock_all_auths();
@@ -305,7 +531,10 @@ fn test_vote_on_cancelled_proposal() {
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
     client.cancel_proposal(&proposer, &proposal_id);
-    client.cast_vote(&voter, &proposal_id, &VoteChoice::For, &1_000i128);
+    assert_eq!(
+        client.try_cast_vote(&voter, &proposal_id, &VoteChoice::For, &1_000i128),
+        Err(Ok(PulsarError::InvalidState))
+    );
 }
 
 // ─── finalize_proposal ────────────────────────────────────────────────────────
@@ -368,7 +597,7 @@ fn test_finalize_proposal_rejected_not_enough_for_votes() {
     let admin = Address::generate(&env);
     // total supply = 1_000; pass_threshold = 60%; for=40%, against=60% → Rejected
     let token_addr = deploy_mock_gov_token(&env, 1_000);
-    let contract_id = env.register_contract(None, GovernanceDaoContract);
+    let contract_id = env.register(GovernanceDaoContract, ());
     let client = GovernanceDaoContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &100u32, &1_000u32, &60u32, &0i128);
 
@@ -391,7 +620,6 @@ fn test_finalize_proposal_rejected_not_enough_for_votes() {
 }
 
 #[test]
-#[should_panic(expected = "voting period not ended")]
 fn test_finalize_still_active() {
     let env = Env::default();
     env.mock_all_auths();
@@ -401,7 +629,10 @@ fn test_finalize_still_active() {
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
     // sequence_number=0, end_ledger=100 → still active
-    client.finalize_proposal(&proposal_id);
+    assert_eq!(
+        client.try_finalize_proposal(&proposal_id),
+        Err(Ok(PulsarError::TooEarly))
+    );
 }
 
 // ─── execute_proposal ────────────────────────────────────────────────────────
@@ -436,7 +667,6 @@ fn test_execute_proposal() {
 }
 
 #[test]
-#[should_panic(expected = "proposal not passed")]
 fn test_execute_active_proposal_fails() {
     let env = Env::default();
     env.mock_all_auths();
@@ -445,11 +675,13 @@ fn test_execute_active_proposal_fails() {
 
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
-    client.execute_proposal(&admin, &proposal_id);
+    assert_eq!(
+        client.try_execute_proposal(&admin, &proposal_id),
+        Err(Ok(PulsarError::InvalidState))
+    );
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_execute_proposal_by_stranger_fails() {
     let env = Env::default();
     env.mock_all_auths();
@@ -468,7 +700,10 @@ fn test_execute_proposal_by_stranger_fails() {
     });
 
     client.finalize_proposal(&proposal_id);
-    client.execute_proposal(&stranger, &proposal_id);
+    assert_eq!(
+        client.try_execute_proposal(&stranger, &proposal_id),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 // ─── cancel_proposal ─────────────────────────────────────────────────────────
@@ -504,7 +739,6 @@ fn test_cancel_proposal_by_admin() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_cancel_proposal_by_stranger_fails() {
     let env = Env::default();
     env.mock_all_auths();
@@ -514,7 +748,10 @@ fn test_cancel_proposal_by_stranger_fails() {
 
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
-    client.cancel_proposal(&stranger, &proposal_id);
+    assert_eq!(
+        client.try_cancel_proposal(&stranger, &proposal_id),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 // ─── read-only helpers ────────────────────────────────────────────────────────
@@ -575,7 +812,7 @@ fn setup_with_token_gate(
     let admin = Address::generate(env);
     let token_addr = deploy_mock_gov_token(env, total_supply);
 
-    let contract_id = env.register_contract(None, GovernanceDaoContract);
+    let contract_id = env.register(GovernanceDaoContract, ());
     let client = GovernanceDaoContractClient::new(env, &contract_id);
     client.initialize(
         &admin,
@@ -608,7 +845,6 @@ fn test_create_proposal_with_sufficient_tokens() {
 }
 
 #[test]
-#[should_panic(expected = "insufficient tokens to create proposal")]
 fn test_create_proposal_insufficient_tokens() {
     let env = Env::default();
     env.mock_all_auths();
@@ -620,11 +856,13 @@ fn test_create_proposal_insufficient_tokens() {
     MockGovTokenClient::new(&env, &token_addr).set_balance(&proposer, &50);
 
     // Should panic
-    client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
+    assert_eq!(
+        client.try_create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
-#[should_panic(expected = "insufficient tokens to create proposal")]
 fn test_create_proposal_zero_balance_rejected() {
     let env = Env::default();
     env.mock_all_auths();
@@ -634,7 +872,10 @@ fn test_create_proposal_zero_balance_rejected() {
 
     // Proposer has no tokens at all (set explicitly)
     MockGovTokenClient::new(&env, &token_addr).set_balance(&proposer, &0);
-    client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
+    assert_eq!(
+        client.try_create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
@@ -667,3 +908,276 @@ fn test_create_proposal_exact_minimum_accepted() {
 
     assert_eq!(proposal_id, 1);
 }
+
+// ─── proposal templates ──────────────────────────────────────────────────────
+
+#[soroban_contract]
+pub struct MockFeeTarget;
+
+#[soroban_contractimpl]
+impl MockFeeTarget {
+    pub fn set_platform_fee(env: Env, admin: Address, fee: u32) {
+        admin.require_auth();
+        env.storage().instance().set(&0u32, &(admin, fee));
+    }
+
+    pub fn last_set(env: Env) -> Option<(Address, u32)> {
+        env.storage().instance().get(&0u32)
+    }
+}
+
+#[soroban_contract]
+pub struct MockOracleRegistry;
+
+#[soroban_contractimpl]
+impl MockOracleRegistry {
+    pub fn grant_domain(env: Env, admin: Address, oracle: Address, domain: OracleDomain) {
+        admin.require_auth();
+        env.storage().persistent().set(&(oracle, domain), &true);
+    }
+
+    pub fn revoke_domain(env: Env, admin: Address, oracle: Address, domain: OracleDomain) {
+        admin.require_auth();
+        env.storage().persistent().remove(&(oracle, domain));
+    }
+
+    pub fn is_authorized(env: Env, oracle: Address, domain: OracleDomain) -> bool {
+        env.storage().persistent().has(&(oracle, domain))
+    }
+}
+
+/// Vote a proposal through and finalize it as Passed
+fn pass_proposal(env: &Env, client: &GovernanceDaoContractClient<'_>, proposal_id: u64) {
+    client.cast_vote(
+        &Address::generate(env),
+        &proposal_id,
+        &VoteChoice::For,
+        &200i128,
+    );
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 200;
+    });
+    client.finalize_proposal(&proposal_id);
+}
+
+#[test]
+fn test_fee_change_template_executes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_with_mock_token(&env, 1_000);
+    let target = env.register(MockFeeTarget, ());
+
+    let proposal_id =
+        client.propose_fee_change(&Address::generate(&env), &target, &5u32, &make_desc(&env));
+    assert_eq!(
+        client.get_proposal_kind(&proposal_id),
+        ProposalKind::FeeChange
+    );
+    assert_eq!(
+        client.get_proposal(&proposal_id).unwrap().target_contract,
+        Some(target.clone())
+    );
+    assert_eq!(client.get_proposal_calls(&proposal_id).len(), 1);
+
+    pass_proposal(&env, &client, proposal_id);
+    client.execute_proposal(&admin, &proposal_id);
+
+    assert_eq!(
+        MockFeeTargetClient::new(&env, &target).last_set(),
+        Some((client.address.clone(), 5u32))
+    );
+    assert!(matches!(
+        client.get_proposal(&proposal_id).unwrap().status,
+        ProposalStatus::Executed
+    ));
+}
+
+#[test]
+fn test_treasury_grant_template_pays_recipient() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, token) = setup_with_mock_token(&env, 1_000);
+    let recipient = Address::generate(&env);
+    let gov = MockGovTokenClient::new(&env, &token);
+    gov.set_balance(&client.address, &5_000i128);
+    gov.set_balance(&recipient, &0i128);
+
+    let proposal_id = client.propose_treasury_grant(
+        &Address::generate(&env),
+        &recipient,
+        &1_500i128,
+        &make_desc(&env),
+    );
+    pass_proposal(&env, &client, proposal_id);
+    client.execute_proposal(&admin, &proposal_id);
+
+    assert_eq!(gov.balance(&recipient), 1_500);
+    assert_eq!(gov.balance(&client.address), 3_500);
+}
+
+#[test]
+fn test_oracle_rotation_template_swaps_domain() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_with_mock_token(&env, 1_000);
+    let registry_id = env.register(MockOracleRegistry, ());
+    let registry = MockOracleRegistryClient::new(&env, &registry_id);
+    let old_oracle = Address::generate(&env);
+    let new_oracle = Address::generate(&env);
+    registry.grant_domain(&client.address, &old_oracle, &OracleDomain::Fraud);
+
+    let proposal_id = client.propose_oracle_rotation(
+        &Address::generate(&env),
+        &registry_id,
+        &old_oracle,
+        &new_oracle,
+        &OracleDomain::Fraud,
+        &make_desc(&env),
+    );
+    assert_eq!(
+        client.get_proposal_kind(&proposal_id),
+        ProposalKind::OracleRotation
+    );
+    pass_proposal(&env, &client, proposal_id);
+    client.execute_proposal(&admin, &proposal_id);
+
+    assert!(!registry.is_authorized(&old_oracle, &OracleDomain::Fraud));
+    assert!(registry.is_authorized(&new_oracle, &OracleDomain::Fraud));
+}
+
+#[test]
+fn test_treasury_grant_template_rejects_zero_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _) = setup_with_mock_token(&env, 1_000);
+    let result = client.try_propose_treasury_grant(
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &0i128,
+        &make_desc(&env),
+    );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidAmount)));
+}
+
+#[test]
+fn test_generic_proposal_has_no_calls() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, _) = setup(&env);
+    let proposal_id = client.create_proposal(
+        &Address::generate(&env),
+        &make_title(&env),
+        &make_desc(&env),
+        &None,
+    );
+    assert_eq!(
+        client.get_proposal_kind(&proposal_id),
+        ProposalKind::Generic
+    );
+    assert!(client.get_proposal_calls(&proposal_id).is_empty());
+}
+
+#[soroban_contract]
+pub struct MockPausable;
+
+#[soroban_contractimpl]
+impl MockPausable {
+    pub fn set_paused(env: Env, admin: Address, paused: bool) {
+        admin.require_auth();
+        env.storage().instance().set(&0u32, &paused);
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage().instance().get(&0u32).unwrap_or(false)
+    }
+}
+
+fn vote_emergency(
+    env: &Env,
+    client: &GovernanceDaoContractClient<'_>,
+    proposal_id: u64,
+    for_power: i128,
+    against_power: i128,
+) {
+    client.cast_vote(
+        &Address::generate(env),
+        &proposal_id,
+        &VoteChoice::For,
+        &for_power,
+    );
+    client.cast_vote(
+        &Address::generate(env),
+        &proposal_id,
+        &VoteChoice::Against,
+        &against_power,
+    );
+    let end_ledger = client.get_proposal(&proposal_id).unwrap().end_ledger;
+    env.ledger().with_mut(|li| {
+        li.sequence_number = end_ledger + 1;
+    });
+    client.finalize_proposal(&proposal_id);
+}
+
+#[test]
+fn test_emergency_action_pauses_targets_after_short_vote() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_with_mock_token(&env, 1_000);
+    client.set_emergency_params(&admin, &10u32, &75u32);
+    let orchestrator = env.register(MockPausable, ());
+    let bridge = env.register(MockPausable, ());
+
+    let proposal_id = client.propose_emergency_action(
+        &Address::generate(&env),
+        &soroban_sdk::vec![&env, orchestrator.clone(), bridge.clone()],
+        &true,
+        &make_desc(&env),
+    );
+    assert_eq!(
+        client.get_proposal_kind(&proposal_id),
+        ProposalKind::EmergencyAction
+    );
+    let proposal = client.get_proposal(&proposal_id).unwrap();
+    assert_eq!(proposal.end_ledger, proposal.start_ledger + 10);
+    assert_eq!(proposal.threshold_pct, 75);
+
+    vote_emergency(&env, &client, proposal_id, 200, 50);
+    client.execute_proposal(&admin, &proposal_id);
+
+    assert!(MockPausableClient::new(&env, &orchestrator).is_paused());
+    assert!(MockPausableClient::new(&env, &bridge).is_paused());
+}
+
+#[test]
+fn test_emergency_action_needs_higher_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_with_mock_token(&env, 1_000);
+    client.set_emergency_params(&admin, &10u32, &75u32);
+    let target = env.register(MockPausable, ());
+
+    let proposal_id = client.propose_emergency_action(
+        &Address::generate(&env),
+        &soroban_sdk::vec![&env, target],
+        &true,
+        &make_desc(&env),
+    );
+    // 70% in favour passes a regular proposal but not an emergency one
+    vote_emergency(&env, &client, proposal_id, 140, 60);
+
+    assert!(matches!(
+        client.get_proposal(&proposal_id).unwrap().status,
+        ProposalStatus::Rejected
+    ));
+}
+
+#[test]
+fn test_emergency_threshold_below_regular_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_with_mock_token(&env, 1_000);
+    assert_eq!(
+        client.try_set_emergency_params(&admin, &10u32, &50u32),
+        Err(Ok(PulsarError::InvalidInput))
+    );
+}
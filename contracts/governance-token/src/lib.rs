@@ -1,9 +1,16 @@
 //! PulsarTrack - Governance Token (Soroban / SEP-41 compatible)
 //! PULSAR governance token with voting power and delegation on Stellar.
+//!
+//! An account's votes are its own balance (unless it has delegated) plus the
+//! balances delegated to it. Every balance change and delegation update
+//! adjusts those aggregates and writes a checkpoint in the same call, so
+//! `get_votes` and `get_past_votes` never lag behind transfers.
 
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
 
+pub use pulsar_common_errors::PulsarError;
+
 // ============================================================
 // Data Types
 // ============================================================
@@ -15,6 +22,14 @@ pub struct Delegation {
     pub delegated_at: u64,
 }
 
+/// Votes held by an account from `ledger` onwards
+#[contracttype]
+#[derive(Clone)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub votes: i128,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct TokenMetadata {
@@ -39,6 +54,9 @@ pub enum DataKey {
     Allowance(Address, Address),
     Delegation(Address),
     VotingSnapshot(Address, u32), // Address, ledger_sequence
+    DelegatedPower(Address),      // sum of balances delegated to the address
+    CheckpointCount(Address),
+    Checkpoint(Address, u32), // (account, index)
 }
 
 pub const MAX_SUPPLY: i128 = 1_000_000_000_000; // 1M tokens with 6 decimals
@@ -58,12 +76,12 @@ pub struct GovernanceTokenContract;
 #[contractimpl]
 impl GovernanceTokenContract {
     /// Initialize the PULSAR governance token
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -78,6 +96,7 @@ impl GovernanceTokenContract {
             decimals: 7,
         };
         env.storage().instance().set(&DataKey::Metadata, &metadata);
+        Ok(())
     }
 
     /// Get token name
@@ -130,14 +149,14 @@ impl GovernanceTokenContract {
     }
 
     /// Transfer tokens
-    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         from.require_auth();
 
         if amount <= 0 {
-            panic!("invalid amount");
+            return Err(PulsarError::InvalidAmount);
         }
 
         let from_balance: i128 = env
@@ -147,7 +166,7 @@ impl GovernanceTokenContract {
             .unwrap_or(0);
 
         if from_balance < amount {
-            panic!("insufficient balance");
+            return Err(PulsarError::InsufficientBalance);
         }
 
         let _ttl_key = DataKey::Balance(from.clone());
@@ -174,13 +193,22 @@ impl GovernanceTokenContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Self::_move_votes(&env, &from, -amount);
+        Self::_move_votes(&env, &to, amount);
 
         env.events()
             .publish((symbol_short!("transfer"),), (from, to, amount));
+        Ok(())
     }
 
     /// Transfer from (requires prior approval)
-    pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -193,7 +221,7 @@ impl GovernanceTokenContract {
             .unwrap_or(0);
 
         if allowance < amount {
-            panic!("insufficient allowance");
+            return Err(PulsarError::InsufficientBalance);
         }
 
         let from_balance: i128 = env
@@ -203,7 +231,7 @@ impl GovernanceTokenContract {
             .unwrap_or(0);
 
         if from_balance < amount {
-            panic!("insufficient balance");
+            return Err(PulsarError::InsufficientBalance);
         }
 
         let _ttl_key = DataKey::Allowance(from.clone(), spender);
@@ -239,6 +267,9 @@ impl GovernanceTokenContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Self::_move_votes(&env, &from, -amount);
+        Self::_move_votes(&env, &to, amount);
+        Ok(())
     }
 
     /// Approve token spending
@@ -268,14 +299,23 @@ impl GovernanceTokenContract {
     }
 
     /// Mint new tokens (admin only)
-    pub fn mint(env: Env, admin: Address, recipient: Address, amount: i128) {
+    pub fn mint(
+        env: Env,
+        admin: Address,
+        recipient: Address,
+        amount: i128,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let current_supply: i128 = env
@@ -285,7 +325,7 @@ impl GovernanceTokenContract {
             .unwrap_or(0);
 
         if current_supply + amount > MAX_SUPPLY {
-            panic!("exceeds max supply");
+            return Err(PulsarError::LimitExceeded);
         }
 
         let balance: i128 = env
@@ -293,7 +333,7 @@ impl GovernanceTokenContract {
             .persistent()
             .get(&DataKey::Balance(recipient.clone()))
             .unwrap_or(0);
-        let _ttl_key = DataKey::Balance(recipient);
+        let _ttl_key = DataKey::Balance(recipient.clone());
         env.storage()
             .persistent()
             .set(&_ttl_key, &(balance + amount));
@@ -302,13 +342,15 @@ impl GovernanceTokenContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Self::_move_votes(&env, &recipient, amount);
         env.storage()
             .instance()
             .set(&DataKey::TotalSupply, &(current_supply + amount));
+        Ok(())
     }
 
     /// Burn tokens
-    pub fn burn(env: Env, from: Address, amount: i128) {
+    pub fn burn(env: Env, from: Address, amount: i128) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -321,10 +363,10 @@ impl GovernanceTokenContract {
             .unwrap_or(0);
 
         if balance < amount {
-            panic!("insufficient balance");
+            return Err(PulsarError::InsufficientBalance);
         }
 
-        let _ttl_key = DataKey::Balance(from);
+        let _ttl_key = DataKey::Balance(from.clone());
         env.storage()
             .persistent()
             .set(&_ttl_key, &(balance - amount));
@@ -333,6 +375,7 @@ impl GovernanceTokenContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Self::_move_votes(&env, &from, -amount);
 
         let supply: i128 = env
             .storage()
@@ -342,6 +385,7 @@ impl GovernanceTokenContract {
         env.storage()
             .instance()
             .set(&DataKey::TotalSupply, &(supply - amount));
+        Ok(())
     }
 
     /// Delegate voting power
@@ -351,6 +395,12 @@ impl GovernanceTokenContract {
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         delegator.require_auth();
 
+        let balance = Self::balance(env.clone(), delegator.clone());
+        let previous = Self::get_delegation(env.clone(), delegator.clone());
+        if let Some(previous) = &previous {
+            Self::_add_delegated(&env, &previous.delegate, -balance);
+        }
+
         let delegation = Delegation {
             delegate: delegate_to.clone(),
             delegated_at: env.ledger().timestamp(),
@@ -363,6 +413,13 @@ impl GovernanceTokenContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Self::_add_delegated(&env, &delegate_to, balance);
+
+        match previous {
+            Some(previous) => Self::_write_checkpoint(&env, &previous.delegate),
+            None => Self::_write_checkpoint(&env, &delegator),
+        }
+        Self::_write_checkpoint(&env, &delegate_to);
 
         env.events()
             .publish((symbol_short!("delegate"),), (delegator, delegate_to));
@@ -374,30 +431,90 @@ impl GovernanceTokenContract {
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         delegator.require_auth();
+        let previous = match Self::get_delegation(env.clone(), delegator.clone()) {
+            Some(previous) => previous,
+            None => return,
+        };
+
+        let balance = Self::balance(env.clone(), delegator.clone());
+        Self::_add_delegated(&env, &previous.delegate, -balance);
         env.storage()
             .persistent()
-            .remove(&DataKey::Delegation(delegator));
+            .remove(&DataKey::Delegation(delegator.clone()));
+        Self::_write_checkpoint(&env, &previous.delegate);
+        Self::_write_checkpoint(&env, &delegator);
     }
 
-    /// Get voting power (0 if delegated)
+    /// Get voting power (0 if delegated, plus any power delegated to `voter`)
     pub fn voting_power(env: Env, voter: Address) -> i128 {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        let delegation = env
-            .storage()
-            .persistent()
-            .get::<DataKey, Delegation>(&DataKey::Delegation(voter.clone()));
+        Self::_votes(&env, &voter)
+    }
 
-        if delegation.is_some() {
-            // Delegated - no direct voting power
-            0
-        } else {
-            env.storage()
+    /// Current votes of `account`; same as `voting_power`
+    pub fn get_votes(env: Env, account: Address) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_votes(&env, &account)
+    }
+
+    /// Votes `account` held at the end of ledger `ledger`
+    pub fn get_past_votes(env: Env, account: Address, ledger: u32) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if ledger > env.ledger().sequence() {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let count = Self::get_checkpoint_count(env.clone(), account.clone());
+        // Binary search for the last checkpoint at or before `ledger`
+        let mut low = 0u32;
+        let mut high = count;
+        while low < high {
+            let mid = (low + high) / 2;
+            let checkpoint: Checkpoint = env
+                .storage()
                 .persistent()
-                .get(&DataKey::Balance(voter))
-                .unwrap_or(0)
+                .get(&DataKey::Checkpoint(account.clone(), mid))
+                .unwrap();
+            if checkpoint.ledger > ledger {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        if low == 0 {
+            return Ok(0);
         }
+        Ok(env
+            .storage()
+            .persistent()
+            .get::<DataKey, Checkpoint>(&DataKey::Checkpoint(account, low - 1))
+            .unwrap()
+            .votes)
+    }
+
+    pub fn get_checkpoint_count(env: Env, account: Address) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::CheckpointCount(account))
+            .unwrap_or(0)
+    }
+
+    pub fn get_checkpoint(env: Env, account: Address, index: u32) -> Option<Checkpoint> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Checkpoint(account, index))
     }
 
     /// Get delegation info
@@ -410,18 +527,118 @@ impl GovernanceTokenContract {
             .get(&DataKey::Delegation(delegator))
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
+
+    // ============================================================
+    // Vote Accounting
+    // ============================================================
+
+    fn _votes(env: &Env, account: &Address) -> i128 {
+        let own = if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Delegation(account.clone()))
+        {
+            0
+        } else {
+            env.storage()
+                .persistent()
+                .get(&DataKey::Balance(account.clone()))
+                .unwrap_or(0)
+        };
+        let delegated: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DelegatedPower(account.clone()))
+            .unwrap_or(0);
+        own + delegated
+    }
+
+    /// Hook for a balance change of `delta` on `account`, called after the
+    /// balance is written. The votes move with the account's delegation.
+    fn _move_votes(env: &Env, account: &Address, delta: i128) {
+        match env
+            .storage()
+            .persistent()
+            .get::<DataKey, Delegation>(&DataKey::Delegation(account.clone()))
+        {
+            Some(delegation) => {
+                Self::_add_delegated(env, &delegation.delegate, delta);
+                Self::_write_checkpoint(env, &delegation.delegate);
+            }
+            None => Self::_write_checkpoint(env, account),
+        }
+    }
+
+    fn _add_delegated(env: &Env, delegate: &Address, delta: i128) {
+        let _ttl_key = DataKey::DelegatedPower(delegate.clone());
+        let power: i128 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+        env.storage().persistent().set(&_ttl_key, &(power + delta));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
         );
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    /// Record `account`'s current votes; several changes in one ledger
+    /// share a checkpoint.
+    fn _write_checkpoint(env: &Env, account: &Address) {
+        let ledger = env.ledger().sequence();
+        let checkpoint = Checkpoint {
+            ledger,
+            votes: Self::_votes(env, account),
+        };
+
+        let count_key = DataKey::CheckpointCount(account.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let last_same_ledger = count > 0
+            && env
+                .storage()
+                .persistent()
+                .get::<DataKey, Checkpoint>(&DataKey::Checkpoint(account.clone(), count - 1))
+                .is_some_and(|last| last.ledger == ledger);
+        let index = if last_same_ledger { count - 1 } else { count };
+
+        let _ttl_key = DataKey::Checkpoint(account.clone(), index);
+        env.storage().persistent().set(&_ttl_key, &checkpoint);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        if !last_same_ledger {
+            env.storage().persistent().set(&count_key, &(count + 1));
+        }
+        env.storage().persistent().extend_ttl(
+            &count_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
     }
 }
 
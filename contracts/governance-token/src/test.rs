@@ -1,6 +1,9 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
 
 fn setup(env: &Env) -> (GovernanceTokenContractClient<'_>, Address) {
     let admin = Address::generate(env);
@@ -20,7 +23,6 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
@@ -28,7 +30,10 @@ fn test_initialize_twice() {
     let c = GovernanceTokenContractClient::new(&env, &id);
     let a = Address::generate(&env);
     c.initialize(&a);
-    c.initialize(&a);
+    assert_eq!(
+        c.try_initialize(&a),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -52,16 +57,16 @@ fn test_mint() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_mint_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, _) = setup(&env);
-    c.mint(
+    let result = c.try_mint(
         &Address::generate(&env),
         &Address::generate(&env),
         &1_000i128,
     );
+    assert_eq!(result, Err(Ok(PulsarError::Unauthorized)));
 }
 
 #[test]
@@ -146,3 +151,76 @@ fn test_balance_zero() {
     let (c, _) = setup(&env);
     assert_eq!(c.balance(&Address::generate(&env)), 0);
 }
+
+#[test]
+fn test_transfers_move_delegated_votes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let delegator = Address::generate(&env);
+    let delegate = Address::generate(&env);
+    let other = Address::generate(&env);
+    let spender = Address::generate(&env);
+    c.mint(&admin, &delegator, &1_000i128);
+    c.delegate(&delegator, &delegate);
+    assert_eq!(c.get_votes(&delegate), 1_000);
+
+    c.transfer(&delegator, &other, &300i128);
+    assert_eq!(c.get_votes(&delegate), 700);
+    assert_eq!(c.get_votes(&other), 300);
+
+    c.approve(&delegator, &spender, &200i128, &1000u32);
+    c.transfer_from(&spender, &delegator, &other, &200i128);
+    c.burn(&delegator, &100i128);
+    c.mint(&admin, &delegator, &50i128);
+    assert_eq!(c.get_votes(&delegate), 450);
+    assert_eq!(c.get_votes(&other), 500);
+    assert_eq!(c.voting_power(&delegate), 450);
+
+    // Switching delegate moves the whole balance
+    c.delegate(&delegator, &other);
+    assert_eq!(c.get_votes(&delegate), 0);
+    assert_eq!(c.get_votes(&other), 950);
+
+    c.revoke_delegation(&delegator);
+    assert_eq!(c.get_votes(&other), 500);
+    assert_eq!(c.get_votes(&delegator), 450);
+}
+
+#[test]
+fn test_past_votes_follow_checkpoints() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let delegator = Address::generate(&env);
+    let delegate = Address::generate(&env);
+    let start = env.ledger().sequence();
+
+    c.mint(&admin, &delegator, &1_000i128);
+    env.ledger().set_sequence_number(start + 10);
+    c.delegate(&delegator, &delegate);
+    env.ledger().set_sequence_number(start + 20);
+    c.transfer(&delegator, &admin, &400i128);
+    c.transfer(&delegator, &admin, &100i128);
+    env.ledger().set_sequence_number(start + 30);
+
+    assert_eq!(c.get_past_votes(&delegate, &(start + 5)), 0);
+    assert_eq!(c.get_past_votes(&delegate, &(start + 10)), 1_000);
+    assert_eq!(c.get_past_votes(&delegate, &(start + 25)), 500);
+    assert_eq!(c.get_past_votes(&delegator, &start), 1_000);
+    assert_eq!(c.get_past_votes(&delegator, &(start + 10)), 0);
+
+    // Both transfers in one ledger share a checkpoint
+    assert_eq!(c.get_checkpoint_count(&delegate), 2);
+}
+
+#[test]
+fn test_past_votes_future_ledger() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    assert_eq!(
+        c.try_get_past_votes(&Address::generate(&env), &(env.ledger().sequence() + 1)),
+        Err(Ok(PulsarError::InvalidInput))
+    );
+}
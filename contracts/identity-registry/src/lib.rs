@@ -2,7 +2,12 @@
 //! Decentralized identity and credential management for the PulsarTrack ecosystem on Stellar.
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
+use pulsar_common_rbac::Role;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, Address, Env, String, Symbol, Vec,
+};
+
+pub use pulsar_common_errors::PulsarError;
 
 #[contracttype]
 #[derive(Clone, PartialEq)]
@@ -15,7 +20,7 @@ pub enum IdentityStatus {
 }
 
 #[contracttype]
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub enum IdentityType {
     Advertiser,
     Publisher,
@@ -30,13 +35,37 @@ pub struct Identity {
     pub identity_type: IdentityType,
     pub status: IdentityStatus,
     pub display_name: String,
-    pub metadata_hash: String,    // IPFS hash for extended metadata
-    pub credentials_hash: String, // hash of verified credentials
+    pub metadata_hash: String, // IPFS hash for extended metadata
     pub registered_at: u64,
     pub verified_at: Option<u64>,
+    pub verified_by: Option<Address>,
     pub last_activity: u64,
 }
 
+/// A discrete attestation about a subject, e.g. `biz_lic`, `tax_id` or
+/// `content`. Each subject holds at most one credential per type.
+#[contracttype]
+#[derive(Clone)]
+pub struct Credential {
+    pub subject: Address,
+    pub issuer: Address,
+    pub credential_type: Symbol,
+    pub hash: String, // hash of the off-chain credential document
+    pub issued_at: u64,
+    pub expires_at: Option<u64>,
+    pub revoked: bool,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct StatusChange {
+    pub actor: Address,
+    pub from: IdentityStatus,
+    pub to: IdentityStatus,
+    pub reason: String,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
@@ -45,8 +74,22 @@ pub enum DataKey {
     IdentityCount,
     Identity(Address),
     NameOwner(String),
+    TypeCount(IdentityType),
+    IdentitiesByType(IdentityType, u64),
+    StatusHistoryCount(Address),
+    StatusHistory(Address, u32),
+    Verifier(Address),
+    Issuer(Address),
+    Credential(Address, Symbol),
+    CredentialTypes(Address),
+    PrimaryOf(Address),
+    LinkedAddresses(Address),
 }
 
+const MAX_LINKED_ADDRESSES: u32 = 10;
+const MAX_PAGE_SIZE: u32 = 50;
+const NAME_EXPIRY_SECS: u64 = 31_536_000; // names lapse after a year without identity activity
+
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
 const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
@@ -57,16 +100,17 @@ pub struct IdentityRegistryContract;
 
 #[contractimpl]
 impl IdentityRegistryContract {
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::IdentityCount, &0u64);
+        Ok(())
     }
 
     pub fn register(
@@ -75,7 +119,7 @@ impl IdentityRegistryContract {
         identity_type: IdentityType,
         display_name: String,
         metadata_hash: String,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -86,26 +130,28 @@ impl IdentityRegistryContract {
             .persistent()
             .has(&DataKey::Identity(account.clone()))
         {
-            panic!("already registered");
+            return Err(PulsarError::AlreadyExists);
         }
 
         if env
             .storage()
             .persistent()
-            .has(&DataKey::NameOwner(display_name.clone()))
+            .has(&DataKey::PrimaryOf(account.clone()))
         {
-            panic!("name taken");
+            return Err(PulsarError::AlreadyExists);
         }
 
+        Self::_take_name(&env, &display_name, &account)?;
+
         let identity = Identity {
             account: account.clone(),
-            identity_type,
+            identity_type: identity_type.clone(),
             status: IdentityStatus::Pending,
             display_name: display_name.clone(),
             metadata_hash,
-            credentials_hash: String::from_str(&env, ""),
             registered_at: env.ledger().timestamp(),
             verified_at: None,
+            verified_by: None,
             last_activity: env.ledger().timestamp(),
         };
 
@@ -116,12 +162,13 @@ impl IdentityRegistryContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
-        let _ttl_key = DataKey::NameOwner(display_name);
-        env.storage().persistent().set(&_ttl_key, &account);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
+        Self::_record_status_change(
+            &env,
+            &account,
+            &account,
+            IdentityStatus::Unverified,
+            IdentityStatus::Pending,
+            String::from_str(&env, ""),
         );
 
         let count: u64 = env
@@ -133,31 +180,76 @@ impl IdentityRegistryContract {
             .instance()
             .set(&DataKey::IdentityCount, &(count + 1));
 
-        env.events().publish(
-            (symbol_short!("identity"), symbol_short!("register")),
+        let type_count: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TypeCount(identity_type.clone()))
+            .unwrap_or(0);
+        let _ttl_key = DataKey::IdentitiesByType(identity_type.clone(), type_count);
+        env.storage().persistent().set(&_ttl_key, &account);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        let _ttl_key = DataKey::TypeCount(identity_type);
+        env.storage().persistent().set(&_ttl_key, &(type_count + 1));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("identity"),
+            symbol_short!("register"),
             account,
         );
+        Ok(())
     }
 
-    pub fn verify_identity(env: Env, admin: Address, account: Address, credentials_hash: String) {
+    /// Verify an identity (admin or an approved verifier)
+    pub fn verify_identity(
+        env: Env,
+        verifier: Address,
+        account: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if admin != stored_admin {
-            panic!("unauthorized");
+        verifier.require_auth();
+        if !pulsar_common_rbac::has_role(&env, &DataKey::Admin, Role::Reviewer, &verifier)
+            && !env
+                .storage()
+                .instance()
+                .has(&DataKey::Verifier(verifier.clone()))
+        {
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut identity: Identity = env
             .storage()
             .persistent()
             .get(&DataKey::Identity(account.clone()))
-            .expect("identity not found");
+            .ok_or(PulsarError::NotFound)?;
 
+        if identity.status == IdentityStatus::Revoked {
+            return Err(PulsarError::InvalidState);
+        }
+
+        let previous = identity.status.clone();
         identity.status = IdentityStatus::Verified;
-        identity.credentials_hash = credentials_hash;
         identity.verified_at = Some(env.ledger().timestamp());
+        identity.verified_by = Some(verifier.clone());
+        Self::_record_status_change(
+            &env,
+            &account,
+            &verifier,
+            previous,
+            IdentityStatus::Verified,
+            String::from_str(&env, ""),
+        );
 
         let _ttl_key = DataKey::Identity(account.clone());
         env.storage().persistent().set(&_ttl_key, &identity);
@@ -167,13 +259,65 @@ impl IdentityRegistryContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("identity"), symbol_short!("verified")),
-            account,
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("identity"),
+            symbol_short!("verified"),
+            (account, verifier),
+        );
+        Ok(())
+    }
+
+    pub fn add_verifier(env: Env, admin: Address, verifier: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::require_role(&env, &DataKey::Admin, Role::Admin, &admin)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Verifier(verifier.clone()), &true);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("identity"),
+            symbol_short!("verifier"),
+            (verifier, true),
+        );
+        Ok(())
+    }
+
+    pub fn remove_verifier(env: Env, admin: Address, verifier: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::require_role(&env, &DataKey::Admin, Role::Admin, &admin)?;
+
+        env.storage()
+            .instance()
+            .remove(&DataKey::Verifier(verifier.clone()));
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("identity"),
+            symbol_short!("verifier"),
+            (verifier, false),
         );
+        Ok(())
+    }
+
+    pub fn is_verifier(env: Env, verifier: Address) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().has(&DataKey::Verifier(verifier))
     }
 
-    pub fn update_metadata(env: Env, account: Address, metadata_hash: String) {
+    pub fn update_metadata(
+        env: Env,
+        account: Address,
+        metadata_hash: String,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -183,7 +327,7 @@ impl IdentityRegistryContract {
             .storage()
             .persistent()
             .get(&DataKey::Identity(account.clone()))
-            .expect("identity not found");
+            .ok_or(PulsarError::NotFound)?;
 
         identity.metadata_hash = metadata_hash;
         identity.last_activity = env.ledger().timestamp();
@@ -195,87 +339,955 @@ impl IdentityRegistryContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
-    pub fn suspend_identity(env: Env, admin: Address, account: Address) {
+    pub fn suspend_identity(
+        env: Env,
+        reviewer: Address,
+        account: Address,
+        reason: String,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if admin != stored_admin {
-            panic!("unauthorized");
-        }
+        pulsar_common_rbac::require_role(&env, &DataKey::Admin, Role::Reviewer, &reviewer)?;
 
         let mut identity: Identity = env
             .storage()
             .persistent()
             .get(&DataKey::Identity(account.clone()))
-            .expect("identity not found");
+            .ok_or(PulsarError::NotFound)?;
 
+        if identity.status == IdentityStatus::Revoked {
+            return Err(PulsarError::InvalidState);
+        }
+        if identity.status == IdentityStatus::Suspended {
+            return Err(PulsarError::AlreadyExists);
+        }
+
+        let previous = identity.status.clone();
         identity.status = IdentityStatus::Suspended;
-        let _ttl_key = DataKey::Identity(account);
-        env.storage().persistent().set(&_ttl_key, &identity);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
+        Self::_save_identity(&env, &identity);
+        Self::_record_status_change(
+            &env,
+            &account,
+            &reviewer,
+            previous,
+            IdentityStatus::Suspended,
+            reason,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("identity"),
+            symbol_short!("suspended"),
+            account,
         );
+        Ok(())
     }
 
-    pub fn get_identity(env: Env, account: Address) -> Option<Identity> {
+    /// Lift a suspension, restoring Verified if the identity was verified before
+    pub fn reinstate_identity(
+        env: Env,
+        reviewer: Address,
+        account: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        env.storage().persistent().get(&DataKey::Identity(account))
+        pulsar_common_rbac::require_role(&env, &DataKey::Admin, Role::Reviewer, &reviewer)?;
+
+        let mut identity: Identity = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Identity(account.clone()))
+            .ok_or(PulsarError::NotFound)?;
+
+        if identity.status != IdentityStatus::Suspended {
+            return Err(PulsarError::InvalidState);
+        }
+
+        let restored = if identity.verified_at.is_some() {
+            IdentityStatus::Verified
+        } else {
+            IdentityStatus::Pending
+        };
+        identity.status = restored.clone();
+        identity.last_activity = env.ledger().timestamp();
+        Self::_save_identity(&env, &identity);
+        Self::_record_status_change(
+            &env,
+            &account,
+            &reviewer,
+            IdentityStatus::Suspended,
+            restored,
+            String::from_str(&env, ""),
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("identity"),
+            symbol_short!("reinstate"),
+            account,
+        );
+        Ok(())
     }
 
-    pub fn is_verified(env: Env, account: Address) -> bool {
+    /// Revoke an identity permanently (reviewer only); its name becomes reclaimable
+    pub fn revoke_identity(
+        env: Env,
+        reviewer: Address,
+        account: Address,
+        reason: String,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        if let Some(identity) = env
+        pulsar_common_rbac::require_role(&env, &DataKey::Admin, Role::Reviewer, &reviewer)?;
+
+        let mut identity: Identity = env
             .storage()
             .persistent()
-            .get::<DataKey, Identity>(&DataKey::Identity(account))
-        {
-            matches!(identity.status, IdentityStatus::Verified)
-        } else {
-            false
+            .get(&DataKey::Identity(account.clone()))
+            .ok_or(PulsarError::NotFound)?;
+
+        if identity.status == IdentityStatus::Revoked {
+            return Err(PulsarError::InvalidState);
         }
+
+        let previous = identity.status.clone();
+        identity.status = IdentityStatus::Revoked;
+        Self::_save_identity(&env, &identity);
+        Self::_record_status_change(
+            &env,
+            &account,
+            &reviewer,
+            previous,
+            IdentityStatus::Revoked,
+            reason,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("identity"),
+            symbol_short!("revoked"),
+            account,
+        );
+        Ok(())
     }
 
-    pub fn get_by_name(env: Env, display_name: String) -> Option<Address> {
+    pub fn get_status_history(
+        env: Env,
+        account: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<StatusChange> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::StatusHistoryCount(account.clone()))
+            .unwrap_or(0);
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+
+        let mut history = Vec::new(&env);
+        for index in start..end {
+            if let Some(change) = env
+                .storage()
+                .persistent()
+                .get(&DataKey::StatusHistory(account.clone(), index))
+            {
+                history.push_back(change);
+            }
+        }
+        history
+    }
+
+    // ============================================================
+    // Display Names
+    // ============================================================
+
+    /// Claim a display name for an identity that currently has none
+    pub fn claim_name(env: Env, account: Address, display_name: String) -> Result<(), PulsarError> {
         env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        account.require_auth();
+
+        let mut identity: Identity = env
+            .storage()
             .persistent()
-            .get(&DataKey::NameOwner(display_name))
+            .get(&DataKey::Identity(account.clone()))
+            .ok_or(PulsarError::NotFound)?;
+
+        if !identity.display_name.is_empty() {
+            return Err(PulsarError::AlreadyExists);
+        }
+
+        Self::_take_name(&env, &display_name, &account)?;
+        identity.display_name = display_name;
+        identity.last_activity = env.ledger().timestamp();
+        Self::_save_identity(&env, &identity);
+        Ok(())
     }
 
-    pub fn get_identity_count(env: Env) -> u64 {
+    /// Keep a name from lapsing by recording identity activity
+    pub fn renew_name(env: Env, account: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        account.require_auth();
+
+        let mut identity: Identity = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Identity(account.clone()))
+            .ok_or(PulsarError::NotFound)?;
+
+        let owner: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::NameOwner(identity.display_name.clone()));
+        if owner != Some(account.clone()) {
+            return Err(PulsarError::NotFound);
+        }
+
+        identity.last_activity = env.ledger().timestamp();
+        Self::_save_identity(&env, &identity);
+
+        let _ttl_key = DataKey::NameOwner(identity.display_name);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Ok(())
+    }
+
+    pub fn release_name(env: Env, account: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
-            .get(&DataKey::IdentityCount)
-            .unwrap_or(0)
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        account.require_auth();
+
+        let mut identity: Identity = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Identity(account.clone()))
+            .ok_or(PulsarError::NotFound)?;
+
+        let name = identity.display_name.clone();
+        let owner: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::NameOwner(name.clone()));
+        if owner != Some(account.clone()) {
+            return Err(PulsarError::NotFound);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::NameOwner(name.clone()));
+        identity.display_name = String::from_str(&env, "");
+        identity.last_activity = env.ledger().timestamp();
+        Self::_save_identity(&env, &identity);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("name"),
+            symbol_short!("released"),
+            (account, name),
+        );
+        Ok(())
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
-        pulsar_common_admin::propose_admin(
+    /// Hand a display name to another identity (both parties must sign)
+    pub fn transfer_name(env: Env, from: Address, to: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        from.require_auth();
+        to.require_auth();
+
+        let mut sender: Identity = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Identity(from.clone()))
+            .ok_or(PulsarError::NotFound)?;
+        let mut recipient: Identity = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Identity(to.clone()))
+            .ok_or(PulsarError::NotFound)?;
+
+        if !recipient.display_name.is_empty() {
+            return Err(PulsarError::AlreadyExists);
+        }
+
+        let name = sender.display_name.clone();
+        let owner: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::NameOwner(name.clone()));
+        if owner != Some(from.clone()) {
+            return Err(PulsarError::NotFound);
+        }
+
+        let now = env.ledger().timestamp();
+        sender.display_name = String::from_str(&env, "");
+        sender.last_activity = now;
+        Self::_save_identity(&env, &sender);
+        recipient.display_name = name.clone();
+        recipient.last_activity = now;
+        Self::_save_identity(&env, &recipient);
+
+        let _ttl_key = DataKey::NameOwner(name.clone());
+        env.storage().persistent().set(&_ttl_key, &to);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
             &env,
-            &DataKey::Admin,
-            &DataKey::PendingAdmin,
-            current_admin,
-            new_admin,
+            symbol_short!("name"),
+            symbol_short!("transfer"),
+            (from, to, name),
         );
+        Ok(())
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    // ============================================================
+    // Linked Addresses
+    // ============================================================
+
+    /// Link an operational wallet to a registered primary identity (both must sign)
+    pub fn link_address(env: Env, primary: Address, secondary: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        primary.require_auth();
+        secondary.require_auth();
+
+        if primary == secondary {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Identity(primary.clone()))
+        {
+            return Err(PulsarError::NotFound);
+        }
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::PrimaryOf(primary.clone()))
+        {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Identity(secondary.clone()))
+        {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::PrimaryOf(secondary.clone()))
+        {
+            return Err(PulsarError::AlreadyExists);
+        }
+
+        let links_key = DataKey::LinkedAddresses(primary.clone());
+        let mut links: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&links_key)
+            .unwrap_or(Vec::new(&env));
+        if links.len() >= MAX_LINKED_ADDRESSES {
+            return Err(PulsarError::LimitExceeded);
+        }
+        links.push_back(secondary.clone());
+        env.storage().persistent().set(&links_key, &links);
+        env.storage().persistent().extend_ttl(
+            &links_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let _ttl_key = DataKey::PrimaryOf(secondary.clone());
+        env.storage().persistent().set(&_ttl_key, &primary);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("identity"),
+            symbol_short!("linked"),
+            (primary, secondary),
+        );
+        Ok(())
+    }
+
+    /// Unlink a secondary address (the primary or the secondary itself)
+    pub fn unlink_address(
+        env: Env,
+        caller: Address,
+        secondary: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        caller.require_auth();
+
+        let primary: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PrimaryOf(secondary.clone()))
+            .ok_or(PulsarError::NotFound)?;
+
+        if caller != primary && caller != secondary {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        let links_key = DataKey::LinkedAddresses(primary.clone());
+        let mut links: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&links_key)
+            .unwrap_or(Vec::new(&env));
+        if let Some(index) = links.first_index_of(&secondary) {
+            links.remove(index);
+        }
+        env.storage().persistent().set(&links_key, &links);
+        env.storage().persistent().extend_ttl(
+            &links_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PrimaryOf(secondary.clone()));
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("identity"),
+            symbol_short!("unlinked"),
+            (primary, secondary),
+        );
+        Ok(())
+    }
+
+    /// The identity an address acts for: its primary if linked, otherwise itself
+    pub fn resolve_primary(env: Env, address: Address) -> Address {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_resolve_primary(&env, &address)
+    }
+
+    pub fn get_linked_addresses(env: Env, primary: Address) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::LinkedAddresses(primary))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // ============================================================
+    // Credentials
+    // ============================================================
+
+    pub fn add_issuer(env: Env, admin: Address, issuer: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::require_role(&env, &DataKey::Admin, Role::Admin, &admin)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Issuer(issuer.clone()), &true);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("identity"),
+            symbol_short!("issuer"),
+            (issuer, true),
+        );
+        Ok(())
+    }
+
+    pub fn remove_issuer(env: Env, admin: Address, issuer: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::require_role(&env, &DataKey::Admin, Role::Admin, &admin)?;
+
+        env.storage()
+            .instance()
+            .remove(&DataKey::Issuer(issuer.clone()));
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("identity"),
+            symbol_short!("issuer"),
+            (issuer, false),
+        );
+        Ok(())
+    }
+
+    pub fn is_issuer(env: Env, issuer: Address) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().has(&DataKey::Issuer(issuer))
+    }
+
+    /// Issue (or reissue) a credential of the given type to a registered identity
+    pub fn issue_credential(
+        env: Env,
+        issuer: Address,
+        subject: Address,
+        credential_type: Symbol,
+        hash: String,
+        expires_at: Option<u64>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        issuer.require_auth();
+
+        if !env
+            .storage()
+            .instance()
+            .has(&DataKey::Issuer(issuer.clone()))
+        {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Identity(subject.clone()))
+        {
+            return Err(PulsarError::NotFound);
+        }
+
+        let now = env.ledger().timestamp();
+        if let Some(expiry) = expires_at {
+            if expiry <= now {
+                return Err(PulsarError::Expired);
+            }
+        }
+
+        let credential = Credential {
+            subject: subject.clone(),
+            issuer: issuer.clone(),
+            credential_type: credential_type.clone(),
+            hash,
+            issued_at: now,
+            expires_at,
+            revoked: false,
+        };
+
+        let _ttl_key = DataKey::Credential(subject.clone(), credential_type.clone());
+        env.storage().persistent().set(&_ttl_key, &credential);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let types_key = DataKey::CredentialTypes(subject.clone());
+        let mut types: Vec<Symbol> = env
+            .storage()
+            .persistent()
+            .get(&types_key)
+            .unwrap_or(Vec::new(&env));
+        if !types.contains(&credential_type) {
+            types.push_back(credential_type.clone());
+        }
+        env.storage().persistent().set(&types_key, &types);
+        env.storage().persistent().extend_ttl(
+            &types_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("cred"),
+            symbol_short!("issued"),
+            (subject, credential_type, issuer),
+        );
+        Ok(())
+    }
+
+    /// Revoke a credential (original issuer or admin)
+    pub fn revoke_credential(
+        env: Env,
+        caller: Address,
+        subject: Address,
+        credential_type: Symbol,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        caller.require_auth();
+
+        let _ttl_key = DataKey::Credential(subject.clone(), credential_type.clone());
+        let mut credential: Credential = env
+            .storage()
+            .persistent()
+            .get(&_ttl_key)
+            .ok_or(PulsarError::NotFound)?;
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if caller != credential.issuer && caller != admin {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        if credential.revoked {
+            return Err(PulsarError::AlreadyExists);
+        }
+
+        credential.revoked = true;
+        env.storage().persistent().set(&_ttl_key, &credential);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("cred"),
+            symbol_short!("revoked"),
+            (subject, credential_type, caller),
+        );
+        Ok(())
+    }
+
+    pub fn get_credential(
+        env: Env,
+        subject: Address,
+        credential_type: Symbol,
+    ) -> Option<Credential> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Credential(subject, credential_type))
+    }
+
+    pub fn get_credential_types(env: Env, subject: Address) -> Vec<Symbol> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::CredentialTypes(subject))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// True if the subject holds an unrevoked, unexpired credential of this type
+    pub fn has_valid_credential(env: Env, subject: Address, credential_type: Symbol) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        match env
+            .storage()
+            .persistent()
+            .get::<DataKey, Credential>(&DataKey::Credential(subject, credential_type))
+        {
+            Some(credential) => {
+                !credential.revoked
+                    && credential
+                        .expires_at
+                        .is_none_or(|expiry| env.ledger().timestamp() < expiry)
+            }
+            None => false,
+        }
+    }
+
+    pub fn get_identity(env: Env, account: Address) -> Option<Identity> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().persistent().get(&DataKey::Identity(account))
+    }
+
+    pub fn is_verified(env: Env, account: Address) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let primary = Self::_resolve_primary(&env, &account);
+        if let Some(identity) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Identity>(&DataKey::Identity(primary))
+        {
+            matches!(identity.status, IdentityStatus::Verified)
+        } else {
+            false
+        }
+    }
+
+    /// O(1) gate for other contracts: true only if the account (or the
+    /// primary it is linked to) holds a Verified identity of the given type
+    pub fn require_verified(env: Env, account: Address, identity_type: IdentityType) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let primary = Self::_resolve_primary(&env, &account);
+        match env
+            .storage()
+            .persistent()
+            .get::<DataKey, Identity>(&DataKey::Identity(primary))
+        {
+            Some(identity) => {
+                identity.status == IdentityStatus::Verified
+                    && identity.identity_type == identity_type
+            }
+            None => false,
+        }
+    }
+
+    pub fn get_by_name(env: Env, display_name: String) -> Option<Address> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::NameOwner(display_name))
+    }
+
+    pub fn get_identity_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::IdentityCount)
+            .unwrap_or(0)
+    }
+
+    /// Assign a name to `account`, reclaiming it first if the current holder
+    /// is revoked or has been inactive for longer than the name expiry.
+    fn _take_name(env: &Env, name: &String, account: &Address) -> Result<(), PulsarError> {
+        let name_key = DataKey::NameOwner(name.clone());
+        if let Some(holder) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Address>(&name_key)
+        {
+            let mut holder_identity: Identity = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Identity(holder.clone()))
+                .unwrap();
+            let lapsed = env.ledger().timestamp()
+                > holder_identity
+                    .last_activity
+                    .saturating_add(NAME_EXPIRY_SECS);
+            if holder_identity.status != IdentityStatus::Revoked && !lapsed {
+                return Err(PulsarError::AlreadyExists);
+            }
+
+            holder_identity.display_name = String::from_str(env, "");
+            Self::_save_identity(env, &holder_identity);
+
+            pulsar_common_admin::publish_event(
+                env,
+                symbol_short!("name"),
+                symbol_short!("reclaimed"),
+                (holder, account.clone(), name.clone()),
+            );
+        }
+
+        env.storage().persistent().set(&name_key, account);
+        env.storage().persistent().extend_ttl(
+            &name_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Ok(())
+    }
+
+    fn _record_status_change(
+        env: &Env,
+        account: &Address,
+        actor: &Address,
+        from: IdentityStatus,
+        to: IdentityStatus,
+        reason: String,
+    ) {
+        let count_key = DataKey::StatusHistoryCount(account.clone());
+        let index: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+        let change = StatusChange {
+            actor: actor.clone(),
+            from,
+            to,
+            reason,
+            timestamp: env.ledger().timestamp(),
+        };
+        let _ttl_key = DataKey::StatusHistory(account.clone(), index);
+        env.storage().persistent().set(&_ttl_key, &change);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        env.storage().persistent().set(&count_key, &(index + 1));
+        env.storage().persistent().extend_ttl(
+            &count_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _save_identity(env: &Env, identity: &Identity) {
+        let _ttl_key = DataKey::Identity(identity.account.clone());
+        env.storage().persistent().set(&_ttl_key, identity);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _resolve_primary(env: &Env, address: &Address) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PrimaryOf(address.clone()))
+            .unwrap_or(address.clone())
+    }
+
+    pub fn get_identity_count_by_type(env: Env, identity_type: IdentityType) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::TypeCount(identity_type))
+            .unwrap_or(0)
+    }
+
+    /// Page through identities of one type in registration order
+    pub fn list_identities(
+        env: Env,
+        identity_type: IdentityType,
+        start: u64,
+        limit: u32,
+    ) -> Vec<Identity> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let count: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TypeCount(identity_type.clone()))
+            .unwrap_or(0);
+        let end = start
+            .saturating_add(limit.min(MAX_PAGE_SIZE) as u64)
+            .min(count);
+
+        let mut identities = Vec::new(&env);
+        for index in start..end {
+            let account: Option<Address> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::IdentitiesByType(identity_type.clone(), index));
+            if let Some(identity) = account.and_then(|account| {
+                env.storage()
+                    .persistent()
+                    .get::<DataKey, Identity>(&DataKey::Identity(account))
+            }) {
+                identities.push_back(identity);
+            }
+        }
+        identities
+    }
+
+    pub fn grant_role(
+        env: Env,
+        admin: Address,
+        role: Role,
+        account: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::grant_role(&env, &DataKey::Admin, admin, role, account)
+    }
+
+    pub fn revoke_role(
+        env: Env,
+        admin: Address,
+        role: Role,
+        account: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::revoke_role(&env, &DataKey::Admin, admin, role, account)
+    }
+
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::has_role(&env, &DataKey::Admin, role, &account)
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::propose_admin(
+            &env,
+            &DataKey::Admin,
+            &DataKey::PendingAdmin,
+            current_admin,
+            new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
@@ -1,10 +1,14 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
 
 fn setup(env: &Env) -> (IdentityRegistryContractClient<'_>, Address) {
     let admin = Address::generate(env);
-    let id = env.register_contract(None, IdentityRegistryContract);
+    let id = env.register(IdentityRegistryContract, ());
     let c = IdentityRegistryContractClient::new(env, &id);
     c.initialize(&admin);
     (c, admin)
@@ -18,29 +22,31 @@ fn s(env: &Env, v: &str) -> String {
 fn test_initialize() {
     let env = Env::default();
     env.mock_all_auths();
-    let id = env.register_contract(None, IdentityRegistryContract);
+    let id = env.register(IdentityRegistryContract, ());
     let c = IdentityRegistryContractClient::new(&env, &id);
     c.initialize(&Address::generate(&env));
     assert_eq!(c.get_identity_count(), 0);
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
-    let id = env.register_contract(None, IdentityRegistryContract);
+    let id = env.register(IdentityRegistryContract, ());
     let c = IdentityRegistryContractClient::new(&env, &id);
     let a = Address::generate(&env);
     c.initialize(&a);
-    c.initialize(&a);
+    assert_eq!(
+        c.try_initialize(&a),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
 #[should_panic]
 fn test_initialize_non_admin_fails() {
     let env = Env::default();
-    let id = env.register_contract(None, IdentityRegistryContract);
+    let id = env.register(IdentityRegistryContract, ());
     let c = IdentityRegistryContractClient::new(&env, &id);
     c.initialize(&Address::generate(&env));
 }
@@ -65,7 +71,6 @@ fn test_register() {
 }
 
 #[test]
-#[should_panic(expected = "already registered")]
 fn test_register_duplicate() {
     let env = Env::default();
     env.mock_all_auths();
@@ -77,16 +82,16 @@ fn test_register_duplicate() {
         &s(&env, "Bob"),
         &s(&env, "QmMeta"),
     );
-    c.register(
+    let result = c.try_register(
         &account,
         &IdentityType::Publisher,
         &s(&env, "Bob2"),
         &s(&env, "QmMeta"),
     );
+    assert_eq!(result, Err(Ok(PulsarError::AlreadyExists)));
 }
 
 #[test]
-#[should_panic(expected = "name taken")]
 fn test_register_duplicate_name() {
     let env = Env::default();
     env.mock_all_auths();
@@ -99,12 +104,13 @@ fn test_register_duplicate_name() {
         &s(&env, "Alice"),
         &s(&env, "QmMeta"),
     );
-    c.register(
+    let result = c.try_register(
         &a2,
         &IdentityType::Publisher,
         &s(&env, "Alice"),
         &s(&env, "QmMeta2"),
     );
+    assert_eq!(result, Err(Ok(PulsarError::AlreadyExists)));
 }
 
 #[test]
@@ -119,7 +125,7 @@ fn test_verify_identity() {
         &s(&env, "Alice"),
         &s(&env, "QmMeta"),
     );
-    c.verify_identity(&admin, &account, &s(&env, "CredHash"));
+    c.verify_identity(&admin, &account);
     let id = c.get_identity(&account).unwrap();
     assert!(matches!(id.status, IdentityStatus::Verified));
     assert!(id.verified_at.is_some());
@@ -127,7 +133,6 @@ fn test_verify_identity() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_verify_identity_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
@@ -139,7 +144,10 @@ fn test_verify_identity_unauthorized() {
         &s(&env, "Alice"),
         &s(&env, "QmMeta"),
     );
-    c.verify_identity(&Address::generate(&env), &account, &s(&env, "CredHash"));
+    assert_eq!(
+        c.try_verify_identity(&Address::generate(&env), &account),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
@@ -171,7 +179,7 @@ fn test_suspend_identity() {
         &s(&env, "Alice"),
         &s(&env, "QmMeta"),
     );
-    c.suspend_identity(&admin, &account);
+    c.suspend_identity(&admin, &account, &s(&env, "Spam"));
     let id = c.get_identity(&account).unwrap();
     assert!(matches!(id.status, IdentityStatus::Suspended));
     assert!(!c.is_verified(&account));
@@ -208,3 +216,430 @@ fn test_is_verified_nonexistent() {
     let (c, _) = setup(&env);
     assert!(!c.is_verified(&Address::generate(&env)));
 }
+
+fn register_alice(env: &Env, c: &IdentityRegistryContractClient) -> Address {
+    let account = Address::generate(env);
+    c.register(
+        &account,
+        &IdentityType::Advertiser,
+        &s(env, "Alice"),
+        &s(env, "QmMeta"),
+    );
+    account
+}
+
+#[test]
+fn test_issue_multiple_credentials() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let account = register_alice(&env, &c);
+    let issuer = Address::generate(&env);
+    c.add_issuer(&admin, &issuer);
+
+    c.issue_credential(
+        &issuer,
+        &account,
+        &symbol_short!("biz_lic"),
+        &s(&env, "LicHash"),
+        &None,
+    );
+    c.issue_credential(
+        &issuer,
+        &account,
+        &symbol_short!("tax_id"),
+        &s(&env, "TaxHash"),
+        &Some(env.ledger().timestamp() + 1_000),
+    );
+
+    assert!(c.has_valid_credential(&account, &symbol_short!("biz_lic")));
+    assert!(c.has_valid_credential(&account, &symbol_short!("tax_id")));
+    assert!(!c.has_valid_credential(&account, &symbol_short!("content")));
+    assert_eq!(c.get_credential_types(&account).len(), 2);
+    let cred = c
+        .get_credential(&account, &symbol_short!("tax_id"))
+        .unwrap();
+    assert_eq!(cred.issuer, issuer);
+    assert_eq!(cred.hash, s(&env, "TaxHash"));
+}
+
+#[test]
+fn test_credential_expires() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let account = register_alice(&env, &c);
+    let issuer = Address::generate(&env);
+    c.add_issuer(&admin, &issuer);
+    let expiry = env.ledger().timestamp() + 100;
+    c.issue_credential(
+        &issuer,
+        &account,
+        &symbol_short!("content"),
+        &s(&env, "CertHash"),
+        &Some(expiry),
+    );
+    env.ledger().with_mut(|li| li.timestamp = expiry);
+    assert!(!c.has_valid_credential(&account, &symbol_short!("content")));
+}
+
+#[test]
+fn test_revoke_credential() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let account = register_alice(&env, &c);
+    let issuer = Address::generate(&env);
+    c.add_issuer(&admin, &issuer);
+    c.issue_credential(
+        &issuer,
+        &account,
+        &symbol_short!("biz_lic"),
+        &s(&env, "LicHash"),
+        &None,
+    );
+    c.revoke_credential(&issuer, &account, &symbol_short!("biz_lic"));
+    assert!(!c.has_valid_credential(&account, &symbol_short!("biz_lic")));
+    assert!(
+        c.get_credential(&account, &symbol_short!("biz_lic"))
+            .unwrap()
+            .revoked
+    );
+}
+
+#[test]
+fn test_issue_credential_unregistered_issuer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let account = register_alice(&env, &c);
+    let result = c.try_issue_credential(
+        &Address::generate(&env),
+        &account,
+        &symbol_short!("biz_lic"),
+        &s(&env, "LicHash"),
+        &None,
+    );
+    assert_eq!(result, Err(Ok(PulsarError::Unauthorized)));
+}
+
+#[test]
+fn test_revoke_credential_by_other_issuer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let account = register_alice(&env, &c);
+    let issuer = Address::generate(&env);
+    let other = Address::generate(&env);
+    c.add_issuer(&admin, &issuer);
+    c.add_issuer(&admin, &other);
+    c.issue_credential(
+        &issuer,
+        &account,
+        &symbol_short!("biz_lic"),
+        &s(&env, "LicHash"),
+        &None,
+    );
+    assert_eq!(
+        c.try_revoke_credential(&other, &account, &symbol_short!("biz_lic")),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_link_address_resolves_to_primary() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let primary = register_alice(&env, &c);
+    let hot = Address::generate(&env);
+    c.link_address(&primary, &hot);
+
+    assert_eq!(c.resolve_primary(&hot), primary);
+    assert_eq!(c.resolve_primary(&primary), primary);
+    assert_eq!(c.get_linked_addresses(&primary).len(), 1);
+
+    assert!(!c.is_verified(&hot));
+    c.verify_identity(&admin, &primary);
+    assert!(c.is_verified(&hot));
+}
+
+#[test]
+fn test_unlink_address() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let primary = register_alice(&env, &c);
+    c.verify_identity(&admin, &primary);
+    let hot = Address::generate(&env);
+    c.link_address(&primary, &hot);
+    c.unlink_address(&hot, &hot);
+
+    assert_eq!(c.resolve_primary(&hot), hot);
+    assert!(!c.is_verified(&hot));
+    assert_eq!(c.get_linked_addresses(&primary).len(), 0);
+}
+
+#[test]
+fn test_link_address_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let primary = register_alice(&env, &c);
+    let other = Address::generate(&env);
+    c.register(
+        &other,
+        &IdentityType::Publisher,
+        &s(&env, "Bob"),
+        &s(&env, "QmMeta"),
+    );
+    let hot = Address::generate(&env);
+    c.link_address(&primary, &hot);
+    assert_eq!(
+        c.try_link_address(&other, &hot),
+        Err(Ok(PulsarError::AlreadyExists))
+    );
+}
+
+#[test]
+fn test_unlink_address_by_stranger() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let primary = register_alice(&env, &c);
+    let hot = Address::generate(&env);
+    c.link_address(&primary, &hot);
+    assert_eq!(
+        c.try_unlink_address(&Address::generate(&env), &hot),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+fn register_bob(env: &Env, c: &IdentityRegistryContractClient, name: &str) -> Address {
+    let account = Address::generate(env);
+    c.register(
+        &account,
+        &IdentityType::Publisher,
+        &s(env, name),
+        &s(env, "QmMeta"),
+    );
+    account
+}
+
+#[test]
+fn test_release_and_claim_name() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let alice = register_alice(&env, &c);
+    c.release_name(&alice);
+    assert!(c.get_by_name(&s(&env, "Alice")).is_none());
+    assert_eq!(c.get_identity(&alice).unwrap().display_name, s(&env, ""));
+
+    let bob = register_bob(&env, &c, "Bob");
+    c.release_name(&bob);
+    c.claim_name(&bob, &s(&env, "Alice"));
+    assert_eq!(c.get_by_name(&s(&env, "Alice")).unwrap(), bob);
+}
+
+#[test]
+fn test_transfer_name() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let alice = register_alice(&env, &c);
+    let bob = register_bob(&env, &c, "Bob");
+    c.release_name(&bob);
+    c.transfer_name(&alice, &bob);
+
+    assert_eq!(c.get_by_name(&s(&env, "Alice")).unwrap(), bob);
+    assert_eq!(c.get_identity(&bob).unwrap().display_name, s(&env, "Alice"));
+    assert_eq!(c.get_identity(&alice).unwrap().display_name, s(&env, ""));
+}
+
+#[test]
+fn test_reclaim_lapsed_name() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let alice = register_alice(&env, &c);
+    env.ledger()
+        .with_mut(|li| li.timestamp += NAME_EXPIRY_SECS + 1);
+    let squatter = register_bob(&env, &c, "Alice");
+
+    assert_eq!(c.get_by_name(&s(&env, "Alice")).unwrap(), squatter);
+    assert_eq!(c.get_identity(&alice).unwrap().display_name, s(&env, ""));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_renewed_name_not_reclaimable() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let alice = register_alice(&env, &c);
+    env.ledger().with_mut(|li| li.timestamp += NAME_EXPIRY_SECS);
+    c.renew_name(&alice);
+    env.ledger().with_mut(|li| li.timestamp += 10);
+    register_bob(&env, &c, "Alice");
+}
+
+#[test]
+fn test_reclaim_name_from_revoked_identity() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let alice = register_alice(&env, &c);
+    c.revoke_identity(&admin, &alice, &s(&env, "Fraud"));
+    let bob = register_bob(&env, &c, "Alice");
+    assert_eq!(c.get_by_name(&s(&env, "Alice")).unwrap(), bob);
+    assert!(matches!(
+        c.get_identity(&alice).unwrap().status,
+        IdentityStatus::Revoked
+    ));
+}
+
+#[test]
+fn test_verify_identity_by_approved_verifier() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let account = register_alice(&env, &c);
+    let verifier = Address::generate(&env);
+    c.add_verifier(&admin, &verifier);
+    assert!(c.is_verifier(&verifier));
+
+    c.verify_identity(&verifier, &account);
+    let id = c.get_identity(&account).unwrap();
+    assert!(c.is_verified(&account));
+    assert_eq!(id.verified_by, Some(verifier));
+}
+
+#[test]
+fn test_reviewer_role_can_verify_and_suspend() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let account = register_alice(&env, &c);
+    let reviewer = Address::generate(&env);
+    c.grant_role(&admin, &Role::Reviewer, &reviewer);
+
+    c.verify_identity(&reviewer, &account);
+    assert!(c.is_verified(&account));
+    c.suspend_identity(&reviewer, &account, &s(&env, "Spam"));
+    assert!(!c.is_verified(&account));
+}
+
+#[test]
+fn test_verify_identity_by_removed_verifier() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let account = register_alice(&env, &c);
+    let verifier = Address::generate(&env);
+    c.add_verifier(&admin, &verifier);
+    c.remove_verifier(&admin, &verifier);
+    assert_eq!(
+        c.try_verify_identity(&verifier, &account),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_reinstate_identity_records_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let account = register_alice(&env, &c);
+    c.verify_identity(&admin, &account);
+    c.suspend_identity(&admin, &account, &s(&env, "Chargebacks"));
+    assert!(!c.is_verified(&account));
+    c.reinstate_identity(&admin, &account);
+    assert!(c.is_verified(&account));
+
+    let history = c.get_status_history(&account, &0u32, &10u32);
+    assert_eq!(history.len(), 4);
+    let suspension = history.get(2).unwrap();
+    assert_eq!(suspension.actor, admin);
+    assert!(matches!(suspension.from, IdentityStatus::Verified));
+    assert!(matches!(suspension.to, IdentityStatus::Suspended));
+    assert_eq!(suspension.reason, s(&env, "Chargebacks"));
+    assert!(matches!(
+        history.get(3).unwrap().to,
+        IdentityStatus::Verified
+    ));
+
+    let page = c.get_status_history(&account, &3u32, &10u32);
+    assert_eq!(page.len(), 1);
+}
+
+#[test]
+fn test_reinstate_unverified_identity_returns_to_pending() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let account = register_alice(&env, &c);
+    c.suspend_identity(&admin, &account, &s(&env, "Review"));
+    c.reinstate_identity(&admin, &account);
+    assert!(matches!(
+        c.get_identity(&account).unwrap().status,
+        IdentityStatus::Pending
+    ));
+}
+
+#[test]
+fn test_verify_revoked_identity_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let account = register_alice(&env, &c);
+    c.revoke_identity(&admin, &account, &s(&env, "Fraud"));
+    assert_eq!(
+        c.try_verify_identity(&admin, &account),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_list_identities_by_type() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    register_alice(&env, &c);
+    let p1 = register_bob(&env, &c, "Pub1");
+    let p2 = register_bob(&env, &c, "Pub2");
+    let p3 = register_bob(&env, &c, "Pub3");
+
+    assert_eq!(c.get_identity_count_by_type(&IdentityType::Publisher), 3);
+    assert_eq!(c.get_identity_count_by_type(&IdentityType::Advertiser), 1);
+    assert_eq!(c.get_identity_count_by_type(&IdentityType::Operator), 0);
+
+    let first = c.list_identities(&IdentityType::Publisher, &0u64, &2u32);
+    assert_eq!(first.len(), 2);
+    assert_eq!(first.get(0).unwrap().account, p1);
+    assert_eq!(first.get(1).unwrap().account, p2);
+    let rest = c.list_identities(&IdentityType::Publisher, &2u64, &2u32);
+    assert_eq!(rest.len(), 1);
+    assert_eq!(rest.get(0).unwrap().account, p3);
+    assert_eq!(
+        c.list_identities(&IdentityType::DataProvider, &0u64, &10u32)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_require_verified() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let account = register_alice(&env, &c);
+    assert!(!c.require_verified(&account, &IdentityType::Advertiser));
+    c.verify_identity(&admin, &account);
+    assert!(c.require_verified(&account, &IdentityType::Advertiser));
+    assert!(!c.require_verified(&account, &IdentityType::Publisher));
+
+    let hot = Address::generate(&env);
+    c.link_address(&account, &hot);
+    assert!(c.require_verified(&hot, &IdentityType::Advertiser));
+}
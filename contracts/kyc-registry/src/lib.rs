@@ -1,8 +1,31 @@
 //! PulsarTrack - KYC Registry (Soroban)
 //! Know Your Customer verification registry for compliance on Stellar.
+//!
+//! A verified account moves to a higher level with `upgrade_kyc`. The new
+//! submission waits as a pending upgrade while the current record stays in
+//! force; once a provider calls `verify_upgrade` the old record is appended to
+//! the account's `KycHistory` and the upgrade replaces it.
+//!
+//! `expire_stale_kyc` is a permissionless sweep: records past `expires_at` are
+//! marked unverified with a ("kyc", "expired") event, and records expiring
+//! within `RENEWAL_NOTICE_SECS` get one ("kyc", "expiring") reminder. The
+//! account then calls `renew_kyc` with fresh documents; like an upgrade, the
+//! current record stays in force until the same provider approves the renewal
+//! through `verify_kyc`. Revoked records cannot be renewed.
+//!
+//! The admin may restrict a provider to a set of jurisdictions; submissions
+//! and verifications outside that coverage are rejected. A provider with no
+//! jurisdictions listed covers all of them.
+//!
+//! Other contracts gate actions through `check_operation_allowed`, which
+//! compares the account's valid level with the level the admin requires for
+//! the named operation. Operations without a required level are open.
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
+use pulsar_common_rbac::Role;
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec};
+
+pub use pulsar_common_errors::PulsarError;
 
 #[contracttype]
 #[derive(Clone, PartialEq, Debug)]
@@ -35,6 +58,7 @@ pub struct KycProvider {
     pub name: String,
     pub is_active: bool,
     pub total_verifications: u64,
+    pub jurisdictions: Vec<String>, // empty = any jurisdiction
 }
 
 #[contracttype]
@@ -45,6 +69,12 @@ pub enum DataKey {
     KycRecord(Address),
     Provider(Address),
     RequiredLevel(String), // operation -> required KYC level
+    PendingUpgrade(Address),
+    KycHistoryCount(Address),
+    KycHistory(Address, u32), // (account, index) -> superseded record
+    Reminded(Address),        // expires_at of the record last reminded about
+    PendingRenewal(Address),
+    Revoked(Address), // set by revoke_kyc until the account submits afresh
 }
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
@@ -52,37 +82,44 @@ const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
 const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
 
+const MAX_PAGE_SIZE: u32 = 50;
+pub const RENEWAL_NOTICE_SECS: u64 = 2_592_000; // 30 days
+const MAX_PROVIDER_JURISDICTIONS: u32 = 50;
+
 #[contract]
 pub struct KycRegistryContract;
 
 #[contractimpl]
 impl KycRegistryContract {
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
     }
 
-    pub fn register_provider(env: Env, admin: Address, provider: Address, name: String) {
+    pub fn register_provider(
+        env: Env,
+        operator: Address,
+        provider: Address,
+        name: String,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if admin != stored_admin {
-            panic!("unauthorized");
-        }
+        pulsar_common_rbac::require_role(&env, &DataKey::Admin, Role::Operator, &operator)?;
 
         let kyc_provider = KycProvider {
             provider_address: provider.clone(),
             name,
             is_active: true,
             total_verifications: 0,
+            jurisdictions: Vec::new(&env),
         };
 
         let _ttl_key = DataKey::Provider(provider);
@@ -92,6 +129,46 @@ impl KycRegistryContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
+    }
+
+    /// Restrict `provider` to `jurisdictions`; an empty list lifts the
+    /// restriction. Records already verified are left as they are.
+    pub fn set_provider_jurisdictions(
+        env: Env,
+        admin: Address,
+        provider: Address,
+        jurisdictions: Vec<String>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::require_role(&env, &DataKey::Admin, Role::Admin, &admin)?;
+        if jurisdictions.len() > MAX_PROVIDER_JURISDICTIONS {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        let _ttl_key = DataKey::Provider(provider.clone());
+        let mut kyc_provider: KycProvider = env
+            .storage()
+            .persistent()
+            .get(&_ttl_key)
+            .ok_or(PulsarError::NotFound)?;
+        kyc_provider.jurisdictions = jurisdictions.clone();
+        env.storage().persistent().set(&_ttl_key, &kyc_provider);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("kyc"),
+            symbol_short!("coverage"),
+            (provider, jurisdictions.len()),
+        );
+        Ok(())
     }
 
     pub fn submit_kyc(
@@ -101,7 +178,7 @@ impl KycRegistryContract {
         level: KycLevel,
         document_hash: String,
         jurisdiction: String,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -111,11 +188,12 @@ impl KycRegistryContract {
             .storage()
             .persistent()
             .get(&DataKey::Provider(provider.clone()))
-            .expect("provider not registered");
+            .ok_or(PulsarError::NotFound)?;
 
         if !kyc_provider.is_active {
-            panic!("provider not active");
+            return Err(PulsarError::InvalidState);
         }
+        Self::_require_covered(&kyc_provider, &jurisdiction)?;
 
         let record = KycRecord {
             account: account.clone(),
@@ -137,14 +215,177 @@ impl KycRegistryContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        // A fresh submission supersedes any upgrade or renewal in flight and
+        // goes through review like a first one, even after a revocation
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingUpgrade(account.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingRenewal(account.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Revoked(account.clone()));
 
-        env.events().publish(
-            (symbol_short!("kyc"), symbol_short!("submitted")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("kyc"),
+            symbol_short!("submitted"),
             (account, level),
         );
+        Ok(())
     }
 
-    pub fn verify_kyc(env: Env, provider: Address, account: Address, expires_in_secs: Option<u64>) {
+    /// Submit documents for a higher level. The current verified record stays
+    /// effective until a provider verifies the upgrade.
+    pub fn upgrade_kyc(
+        env: Env,
+        account: Address,
+        provider: Address,
+        level: KycLevel,
+        document_hash: String,
+        jurisdiction: String,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        account.require_auth();
+
+        let current: KycRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::KycRecord(account.clone()))
+            .ok_or(PulsarError::NotVerified)?;
+        if !Self::_is_valid(&env, &current) {
+            return Err(PulsarError::NotVerified);
+        }
+        if Self::_rank(&level) <= Self::_rank(&current.level) {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let kyc_provider: KycProvider = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Provider(provider))
+            .ok_or(PulsarError::NotFound)?;
+        if !kyc_provider.is_active {
+            return Err(PulsarError::InvalidState);
+        }
+        Self::_require_covered(&kyc_provider, &jurisdiction)?;
+
+        let upgrade = KycRecord {
+            account: account.clone(),
+            level: level.clone(),
+            provider: kyc_provider.name,
+            document_hash,
+            jurisdiction,
+            verified: false,
+            submitted_at: env.ledger().timestamp(),
+            verified_at: None,
+            expires_at: None,
+            verifier: None,
+        };
+        let _ttl_key = DataKey::PendingUpgrade(account.clone());
+        env.storage().persistent().set(&_ttl_key, &upgrade);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("kyc"),
+            symbol_short!("upgrade"),
+            (account, level),
+        );
+        Ok(())
+    }
+
+    /// Provider: approve a pending upgrade. The current record is archived to
+    /// history and the upgrade becomes effective.
+    pub fn verify_upgrade(
+        env: Env,
+        provider: Address,
+        account: Address,
+        expires_in_secs: Option<u64>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        provider.require_auth();
+
+        let mut provider_data: KycProvider = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Provider(provider.clone()))
+            .ok_or(PulsarError::NotFound)?;
+        if !provider_data.is_active {
+            return Err(PulsarError::InvalidState);
+        }
+
+        let upgrade_key = DataKey::PendingUpgrade(account.clone());
+        let mut upgrade: KycRecord = env
+            .storage()
+            .persistent()
+            .get(&upgrade_key)
+            .ok_or(PulsarError::NotFound)?;
+        if upgrade.provider != provider_data.name {
+            return Err(PulsarError::InvalidInput);
+        }
+        Self::_require_covered(&provider_data, &upgrade.jurisdiction)?;
+        let current: KycRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::KycRecord(account.clone()))
+            .ok_or(PulsarError::NotVerified)?;
+        if !current.verified {
+            return Err(PulsarError::NotVerified);
+        }
+
+        Self::_archive(&env, &account, &current);
+
+        let now = env.ledger().timestamp();
+        upgrade.verified = true;
+        upgrade.verified_at = Some(now);
+        upgrade.expires_at = expires_in_secs.map(|d| now + d);
+        upgrade.verifier = Some(provider.clone());
+        let _ttl_key = DataKey::KycRecord(account.clone());
+        env.storage().persistent().set(&_ttl_key, &upgrade);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage().persistent().remove(&upgrade_key);
+
+        provider_data.total_verifications += 1;
+        let _ttl_key = DataKey::Provider(provider);
+        env.storage().persistent().set(&_ttl_key, &provider_data);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("kyc"),
+            symbol_short!("upgraded"),
+            (account, upgrade.level),
+        );
+        Ok(())
+    }
+
+    /// Provider: verify a submitted record, or approve a pending renewal, in
+    /// which case the previous record is archived to history. Revoked
+    /// records cannot be verified again until the account submits afresh.
+    pub fn verify_kyc(
+        env: Env,
+        provider: Address,
+        account: Address,
+        expires_in_secs: Option<u64>,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -154,20 +395,35 @@ impl KycRegistryContract {
             .storage()
             .persistent()
             .get(&DataKey::Provider(provider.clone()))
-            .expect("provider not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if !provider_data.is_active {
-            panic!("provider not active");
+            return Err(PulsarError::InvalidState);
         }
 
-        let mut record: KycRecord = env
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Revoked(account.clone()))
+        {
+            return Err(PulsarError::InvalidState);
+        }
+        let current: KycRecord = env
             .storage()
             .persistent()
             .get(&DataKey::KycRecord(account.clone()))
-            .expect("kyc not submitted");
+            .ok_or(PulsarError::NotVerified)?;
+        let renewal_key = DataKey::PendingRenewal(account.clone());
+        let renewal: Option<KycRecord> = env.storage().persistent().get(&renewal_key);
+        let mut record = renewal.clone().unwrap_or(current.clone());
 
         if record.provider != provider_data.name {
-            panic!("provider mismatch");
+            return Err(PulsarError::InvalidInput);
+        }
+        Self::_require_covered(&provider_data, &record.jurisdiction)?;
+        if renewal.is_some() {
+            Self::_archive(&env, &account, &current);
+            env.storage().persistent().remove(&renewal_key);
         }
 
         let now = env.ledger().timestamp();
@@ -193,56 +449,174 @@ impl KycRegistryContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events()
-            .publish((symbol_short!("kyc"), symbol_short!("verified")), account);
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("kyc"),
+            symbol_short!("verified"),
+            account,
+        );
+        Ok(())
     }
 
-    pub fn revoke_kyc(env: Env, admin: Address, account: Address) {
+    /// Re-attest an existing record with new documents. Level, provider and
+    /// jurisdiction carry over. Like an upgrade, the renewal waits beside the
+    /// current record, which stays effective until the same provider calls
+    /// `verify_kyc`. Revoked records cannot be renewed.
+    pub fn renew_kyc(env: Env, account: Address, document_hash: String) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if admin != stored_admin {
-            panic!("unauthorized");
+        account.require_auth();
+
+        let mut record: KycRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::KycRecord(account.clone()))
+            .ok_or(PulsarError::NotVerified)?;
+        if record.verified_at.is_none() {
+            return Err(PulsarError::NotVerified);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Revoked(account.clone()))
+        {
+            return Err(PulsarError::InvalidState);
+        }
+
+        record.document_hash = document_hash;
+        record.verified = false;
+        record.submitted_at = env.ledger().timestamp();
+        record.verified_at = None;
+        record.expires_at = None;
+        record.verifier = None;
+        let _ttl_key = DataKey::PendingRenewal(account.clone());
+        env.storage().persistent().set(&_ttl_key, &record);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingUpgrade(account.clone()));
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("kyc"),
+            symbol_short!("renewal"),
+            (account, record.level),
+        );
+        Ok(())
+    }
+
+    /// Mark each listed record past `expires_at` as unverified and remind
+    /// accounts expiring within `RENEWAL_NOTICE_SECS`, once per expiry. Anyone
+    /// may call this. Returns the number of records expired.
+    ///
+    /// Data: expired [account: Address], expiring [account: Address, expires_at: u64]
+    pub fn expire_stale_kyc(env: Env, accounts: Vec<Address>) -> Result<u32, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if accounts.len() > MAX_PAGE_SIZE {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut expired = 0u32;
+        for account in accounts.iter() {
+            let _ttl_key = DataKey::KycRecord(account.clone());
+            let mut record: KycRecord = match env.storage().persistent().get(&_ttl_key) {
+                Some(record) => record,
+                None => continue,
+            };
+            let expires_at = match record.expires_at {
+                Some(expires_at) if record.verified => expires_at,
+                _ => continue,
+            };
+
+            if expires_at <= now {
+                record.verified = false;
+                env.storage().persistent().set(&_ttl_key, &record);
+                env.storage().persistent().extend_ttl(
+                    &_ttl_key,
+                    PERSISTENT_LIFETIME_THRESHOLD,
+                    PERSISTENT_BUMP_AMOUNT,
+                );
+                pulsar_common_admin::publish_event(
+                    &env,
+                    symbol_short!("kyc"),
+                    symbol_short!("expired"),
+                    account,
+                );
+                expired += 1;
+            } else if expires_at <= now + RENEWAL_NOTICE_SECS {
+                let key = DataKey::Reminded(account.clone());
+                if env.storage().persistent().get::<DataKey, u64>(&key) == Some(expires_at) {
+                    continue;
+                }
+                env.storage().persistent().set(&key, &expires_at);
+                env.storage().persistent().extend_ttl(
+                    &key,
+                    PERSISTENT_LIFETIME_THRESHOLD,
+                    PERSISTENT_BUMP_AMOUNT,
+                );
+                pulsar_common_admin::publish_event(
+                    &env,
+                    symbol_short!("kyc"),
+                    symbol_short!("expiring"),
+                    (account, expires_at),
+                );
+            }
         }
+        Ok(expired)
+    }
+
+    pub fn revoke_kyc(env: Env, reviewer: Address, account: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::require_role(&env, &DataKey::Admin, Role::Reviewer, &reviewer)?;
 
         let mut record: KycRecord = env
             .storage()
             .persistent()
             .get(&DataKey::KycRecord(account.clone()))
-            .expect("kyc not found");
+            .ok_or(PulsarError::NotFound)?;
 
         record.verified = false;
-        let _ttl_key = DataKey::KycRecord(account);
+        let _ttl_key = DataKey::KycRecord(account.clone());
         env.storage().persistent().set(&_ttl_key, &record);
         env.storage().persistent().extend_ttl(
             &_ttl_key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        let _ttl_key = DataKey::Revoked(account.clone());
+        env.storage().persistent().set(&_ttl_key, &true);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingUpgrade(account.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingRenewal(account));
+        Ok(())
     }
 
     pub fn is_kyc_valid(env: Env, account: Address) -> bool {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        if let Some(record) = env
-            .storage()
+        env.storage()
             .persistent()
             .get::<DataKey, KycRecord>(&DataKey::KycRecord(account))
-        {
-            if !record.verified {
-                return false;
-            }
-            if let Some(expires) = record.expires_at {
-                expires > env.ledger().timestamp()
-            } else {
-                true
-            }
-        } else {
-            false
-        }
+            .is_some_and(|record| Self::_is_valid(&env, &record))
     }
 
     pub fn get_kyc_record(env: Env, account: Address) -> Option<KycRecord> {
@@ -252,6 +626,139 @@ impl KycRegistryContract {
         env.storage().persistent().get(&DataKey::KycRecord(account))
     }
 
+    pub fn get_pending_upgrade(env: Env, account: Address) -> Option<KycRecord> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingUpgrade(account))
+    }
+
+    pub fn get_pending_renewal(env: Env, account: Address) -> Option<KycRecord> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingRenewal(account))
+    }
+
+    pub fn get_kyc_history_count(env: Env, account: Address) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::KycHistoryCount(account))
+            .unwrap_or(0)
+    }
+
+    /// Superseded records, oldest first
+    pub fn get_kyc_history(env: Env, account: Address, index: u32) -> Option<KycRecord> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::KycHistory(account, index))
+    }
+
+    /// Require `level` for `operation`; `KycLevel::None` removes the gate
+    pub fn set_required_level(
+        env: Env,
+        admin: Address,
+        operation: String,
+        level: KycLevel,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::require_role(&env, &DataKey::Admin, Role::Admin, &admin)?;
+
+        let _ttl_key = DataKey::RequiredLevel(operation.clone());
+        if level == KycLevel::None {
+            env.storage().persistent().remove(&_ttl_key);
+        } else {
+            env.storage().persistent().set(&_ttl_key, &level);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("kyc"),
+            symbol_short!("req_level"),
+            (operation, level),
+        );
+        Ok(())
+    }
+
+    pub fn get_required_level(env: Env, operation: String) -> KycLevel {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::RequiredLevel(operation))
+            .unwrap_or(KycLevel::None)
+    }
+
+    /// Whether `account` holds a valid record at or above the level required
+    /// for `operation`
+    pub fn check_operation_allowed(env: Env, account: Address, operation: String) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let required: KycLevel = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::RequiredLevel(operation))
+        {
+            Some(level) => level,
+            None => return true,
+        };
+        env.storage()
+            .persistent()
+            .get::<DataKey, KycRecord>(&DataKey::KycRecord(account))
+            .is_some_and(|record| {
+                Self::_is_valid(&env, &record)
+                    && Self::_rank(&record.level) >= Self::_rank(&required)
+            })
+    }
+
+    pub fn get_provider(env: Env, provider: Address) -> Option<KycProvider> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().persistent().get(&DataKey::Provider(provider))
+    }
+
+    /// Jurisdictions `provider` may verify in; empty means all of them
+    pub fn get_provider_jurisdictions(env: Env, provider: Address) -> Vec<String> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get::<DataKey, KycProvider>(&DataKey::Provider(provider))
+            .map(|kyc_provider| kyc_provider.jurisdictions)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn provider_covers(env: Env, provider: Address, jurisdiction: String) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get::<DataKey, KycProvider>(&DataKey::Provider(provider))
+            .is_some_and(|kyc_provider| Self::_covers(&kyc_provider, &jurisdiction))
+    }
+
     pub fn get_kyc_level(env: Env, account: Address) -> KycLevel {
         env.storage()
             .instance()
@@ -271,18 +778,107 @@ impl KycRegistryContract {
         }
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn grant_role(
+        env: Env,
+        admin: Address,
+        role: Role,
+        account: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::grant_role(&env, &DataKey::Admin, admin, role, account)
+    }
+
+    pub fn revoke_role(
+        env: Env,
+        admin: Address,
+        role: Role,
+        account: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::revoke_role(&env, &DataKey::Admin, admin, role, account)
+    }
+
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::has_role(&env, &DataKey::Admin, role, &account)
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
+
+    fn _is_valid(env: &Env, record: &KycRecord) -> bool {
+        record.verified
+            && record
+                .expires_at
+                .is_none_or(|expires| expires > env.ledger().timestamp())
+    }
+
+    /// Append a superseded record to the account's history
+    fn _archive(env: &Env, account: &Address, record: &KycRecord) {
+        let count_key = DataKey::KycHistoryCount(account.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let _ttl_key = DataKey::KycHistory(account.clone(), count);
+        env.storage().persistent().set(&_ttl_key, record);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
         );
+        env.storage().persistent().set(&count_key, &(count + 1));
+        env.storage().persistent().extend_ttl(
+            &count_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _covers(provider: &KycProvider, jurisdiction: &String) -> bool {
+        provider.jurisdictions.is_empty() || provider.jurisdictions.contains(jurisdiction)
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    fn _require_covered(provider: &KycProvider, jurisdiction: &String) -> Result<(), PulsarError> {
+        if !Self::_covers(provider, jurisdiction) {
+            return Err(PulsarError::InvalidState);
+        }
+        Ok(())
+    }
+
+    fn _rank(level: &KycLevel) -> u32 {
+        match level {
+            KycLevel::None => 0,
+            KycLevel::Basic => 1,
+            KycLevel::Standard => 2,
+            KycLevel::Enhanced => 3,
+        }
     }
 }
 
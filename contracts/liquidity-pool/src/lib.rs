@@ -4,6 +4,8 @@
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env};
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone)]
 pub struct PoolState {
@@ -59,12 +61,12 @@ pub struct LiquidityPoolContract;
 
 #[contractimpl]
 impl LiquidityPoolContract {
-    pub fn initialize(env: Env, admin: Address, token: Address) {
+    pub fn initialize(env: Env, admin: Address, token: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -81,16 +83,17 @@ impl LiquidityPoolContract {
                 last_updated: env.ledger().timestamp(),
             },
         );
+        Ok(())
     }
 
-    pub fn deposit(env: Env, provider: Address, amount: i128) -> i128 {
+    pub fn deposit(env: Env, provider: Address, amount: i128) -> Result<i128, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         provider.require_auth();
 
         if amount <= 0 {
-            panic!("invalid amount");
+            return Err(PulsarError::InvalidAmount);
         }
 
         let token_addr: Address = env
@@ -144,15 +147,17 @@ impl LiquidityPoolContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("pool"), symbol_short!("deposit")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("pool"),
+            symbol_short!("deposit"),
             (provider, amount, shares),
         );
 
-        shares
+        Ok(shares)
     }
 
-    pub fn withdraw(env: Env, provider: Address, shares: i128) -> i128 {
+    pub fn withdraw(env: Env, provider: Address, shares: i128) -> Result<i128, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -162,10 +167,10 @@ impl LiquidityPoolContract {
             .storage()
             .persistent()
             .get(&DataKey::Provider(provider.clone()))
-            .expect("no position");
+            .ok_or(PulsarError::NotFound)?;
 
         if position.shares < shares {
-            panic!("insufficient shares");
+            return Err(PulsarError::InsufficientBalance);
         }
 
         let mut pool: PoolState = env.storage().instance().get(&DataKey::PoolState).unwrap();
@@ -179,7 +184,7 @@ impl LiquidityPoolContract {
         let available = pool.total_liquidity - pool.total_borrowed;
 
         if amount > available {
-            panic!("insufficient liquidity");
+            return Err(PulsarError::InsufficientBalance);
         }
 
         pool.total_liquidity -= amount;
@@ -207,10 +212,16 @@ impl LiquidityPoolContract {
         let token_client = token::Client::new(&env, &token_addr);
         token_client.transfer(&env.current_contract_address(), &provider, &amount);
 
-        amount
+        Ok(amount)
     }
 
-    pub fn borrow(env: Env, borrower: Address, campaign_id: u64, amount: i128, duration_secs: u64) {
+    pub fn borrow(
+        env: Env,
+        borrower: Address,
+        campaign_id: u64,
+        amount: i128,
+        duration_secs: u64,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -220,7 +231,7 @@ impl LiquidityPoolContract {
         let available = pool.total_liquidity - pool.total_borrowed;
 
         if amount > available {
-            panic!("insufficient liquidity");
+            return Err(PulsarError::InsufficientBalance);
         }
 
         if env
@@ -228,7 +239,7 @@ impl LiquidityPoolContract {
             .persistent()
             .has(&DataKey::Borrow(campaign_id))
         {
-            panic!("already has borrow");
+            return Err(PulsarError::AlreadyExists);
         }
 
         pool.total_borrowed += amount;
@@ -261,9 +272,15 @@ impl LiquidityPoolContract {
             .unwrap();
         let token_client = token::Client::new(&env, &token_addr);
         token_client.transfer(&env.current_contract_address(), &borrower, &amount);
+        Ok(())
     }
 
-    pub fn repay(env: Env, borrower: Address, campaign_id: u64, amount: i128) {
+    pub fn repay(
+        env: Env,
+        borrower: Address,
+        campaign_id: u64,
+        amount: i128,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -273,10 +290,10 @@ impl LiquidityPoolContract {
             .storage()
             .persistent()
             .get(&DataKey::Borrow(campaign_id))
-            .expect("borrow not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if borrow.borrower != borrower {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let token_addr: Address = env
@@ -299,16 +316,17 @@ impl LiquidityPoolContract {
         env.storage()
             .persistent()
             .remove(&DataKey::Borrow(campaign_id));
+        Ok(())
     }
 
-    pub fn get_pool_state(env: Env) -> PoolState {
+    pub fn get_pool_state(env: Env) -> Result<PoolState, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         env.storage()
             .instance()
             .get(&DataKey::PoolState)
-            .expect("not initialized")
+            .ok_or(PulsarError::NotInitialized)
     }
 
     pub fn get_provider_position(env: Env, provider: Address) -> Option<ProviderPosition> {
@@ -327,18 +345,30 @@ impl LiquidityPoolContract {
             .get(&DataKey::Borrow(campaign_id))
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
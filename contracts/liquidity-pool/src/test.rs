@@ -28,12 +28,14 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, admin, _, token) = setup(&env);
-    c.initialize(&admin, &token);
+    assert_eq!(
+        c.try_initialize(&admin, &token),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -4,6 +4,8 @@
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone, PartialEq)]
 pub enum MilestoneStatus {
@@ -51,12 +53,12 @@ pub struct MilestoneTrackerContract;
 
 #[contractimpl]
 impl MilestoneTrackerContract {
-    pub fn initialize(env: Env, admin: Address, oracle: Address) {
+    pub fn initialize(env: Env, admin: Address, oracle: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -66,6 +68,7 @@ impl MilestoneTrackerContract {
         env.storage()
             .instance()
             .set(&DataKey::MilestoneCounter, &0u64);
+        Ok(())
     }
 
     pub fn create_milestone(
@@ -131,7 +134,12 @@ impl MilestoneTrackerContract {
         milestone_id
     }
 
-    pub fn update_progress(env: Env, oracle: Address, milestone_id: u64, current_value: u64) {
+    pub fn update_progress(
+        env: Env,
+        oracle: Address,
+        milestone_id: u64,
+        current_value: u64,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -142,17 +150,17 @@ impl MilestoneTrackerContract {
             .get(&DataKey::OracleAddress)
             .unwrap();
         if oracle != stored_oracle {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut milestone: Milestone = env
             .storage()
             .persistent()
             .get(&DataKey::Milestone(milestone_id))
-            .expect("milestone not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if milestone.status == MilestoneStatus::Achieved {
-            return; // Already achieved, no update needed
+            return Ok(()); // Already achieved, no update needed
         }
 
         milestone.current_value = current_value;
@@ -161,8 +169,10 @@ impl MilestoneTrackerContract {
             milestone.status = MilestoneStatus::Achieved;
             milestone.achieved_at = Some(env.ledger().timestamp());
 
-            env.events().publish(
-                (symbol_short!("milestone"), symbol_short!("achieved")),
+            pulsar_common_admin::publish_event(
+                &env,
+                symbol_short!("milestone"),
+                symbol_short!("achieved"),
                 (milestone_id, milestone.campaign_id),
             );
         } else if env.ledger().timestamp() > milestone.deadline {
@@ -178,9 +188,14 @@ impl MilestoneTrackerContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
-    pub fn dispute_milestone(env: Env, caller: Address, milestone_id: u64) {
+    pub fn dispute_milestone(
+        env: Env,
+        caller: Address,
+        milestone_id: u64,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -190,7 +205,7 @@ impl MilestoneTrackerContract {
             .storage()
             .persistent()
             .get(&DataKey::Milestone(milestone_id))
-            .expect("milestone not found");
+            .ok_or(PulsarError::NotFound)?;
 
         milestone.status = MilestoneStatus::Disputed;
         let _ttl_key = DataKey::Milestone(milestone_id);
@@ -200,23 +215,33 @@ impl MilestoneTrackerContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
-    pub fn resolve_dispute(env: Env, admin: Address, milestone_id: u64, achieved: bool) {
+    pub fn resolve_dispute(
+        env: Env,
+        admin: Address,
+        milestone_id: u64,
+        achieved: bool,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut milestone: Milestone = env
             .storage()
             .persistent()
             .get(&DataKey::Milestone(milestone_id))
-            .expect("milestone not found");
+            .ok_or(PulsarError::NotFound)?;
 
         milestone.status = if achieved {
             MilestoneStatus::Achieved
@@ -231,6 +256,7 @@ impl MilestoneTrackerContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     pub fn get_milestone(env: Env, milestone_id: u64) -> Option<Milestone> {
@@ -252,18 +278,30 @@ impl MilestoneTrackerContract {
             .unwrap_or(0)
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
@@ -22,12 +22,11 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, admin, oracle) = setup(&env);
-    c.initialize(&admin, &oracle);
+    assert_eq!(c.try_initialize(&admin, &oracle), Err(Ok(PulsarError::AlreadyInitialized)));
 }
 
 #[test]
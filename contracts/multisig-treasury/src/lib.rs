@@ -1,11 +1,17 @@
 //! PulsarTrack - Multisig Treasury (Soroban)
 //! Multi-signature treasury for platform fund management on Stellar.
+//!
+//! Every transaction carries a `TxMetadata` classifying the outflow, so
+//! accounting can filter by `TxCategory` and match invoices or governance
+//! proposals without parsing descriptions.
 
 #![no_std]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, token, Address, Env, String, Vec,
+    contract, contractimpl, contracttype, symbol_short, token, Address, BytesN, Env, String, Vec,
 };
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone, PartialEq)]
 pub enum TxStatus {
@@ -16,6 +22,24 @@ pub enum TxStatus {
     Expired,
 }
 
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TxCategory {
+    Payroll,
+    Grant,
+    Infrastructure,
+    Operations,
+    Other,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct TxMetadata {
+    pub category: TxCategory,
+    pub invoice_hash: Option<BytesN<32>>, // hash of the off-chain invoice
+    pub proposal_id: Option<u64>,         // governance proposal that authorized it
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct TreasuryTx {
@@ -25,6 +49,7 @@ pub struct TreasuryTx {
     pub token: Address,
     pub amount: i128,
     pub description: String,
+    pub metadata: TxMetadata,
     pub status: TxStatus,
     pub approvals: u32,
     pub rejections: u32,
@@ -34,6 +59,13 @@ pub struct TreasuryTx {
     pub executed_at: Option<u64>,
 }
 
+#[contracttype]
+#[derive(Clone)]
+pub struct TokenHolding {
+    pub token: Address,
+    pub balance: i128,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
@@ -44,6 +76,7 @@ pub enum DataKey {
     TxCounter,
     Tx(u64),
     TxApproval(u64, Address),
+    HeldTokens,
 }
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
@@ -51,22 +84,29 @@ const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
 const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
 
+const MAX_HELD_TOKENS: u32 = 50;
+
 #[contract]
 pub struct MultisigTreasuryContract;
 
 #[contractimpl]
 impl MultisigTreasuryContract {
-    pub fn initialize(env: Env, admin: Address, initial_signers: Vec<Address>, required: u32) {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        initial_signers: Vec<Address>,
+        required: u32,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
 
         if required == 0 || required > initial_signers.len() as u32 {
-            panic!("invalid required signers");
+            return Err(PulsarError::InvalidInput);
         }
 
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -77,6 +117,7 @@ impl MultisigTreasuryContract {
             .instance()
             .set(&DataKey::RequiredSigners, &required);
         env.storage().instance().set(&DataKey::TxCounter, &0u64);
+        Ok(())
     }
 
     pub fn propose_transaction(
@@ -87,7 +128,36 @@ impl MultisigTreasuryContract {
         amount: i128,
         description: String,
         expires_in: u64,
-    ) -> u64 {
+    ) -> Result<u64, PulsarError> {
+        let metadata = TxMetadata {
+            category: TxCategory::Other,
+            invoice_hash: None,
+            proposal_id: None,
+        };
+        Self::propose_classified_transaction(
+            env,
+            proposer,
+            recipient,
+            token,
+            amount,
+            description,
+            expires_in,
+            metadata,
+        )
+    }
+
+    /// `propose_transaction` with the outflow classified by `metadata`
+    #[allow(clippy::too_many_arguments)]
+    pub fn propose_classified_transaction(
+        env: Env,
+        proposer: Address,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+        description: String,
+        expires_in: u64,
+        metadata: TxMetadata,
+    ) -> Result<u64, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -97,11 +167,11 @@ impl MultisigTreasuryContract {
         let is_signer = signers.contains(&proposer);
 
         if !is_signer {
-            panic!("not a signer");
+            return Err(PulsarError::Unauthorized);
         }
 
         if amount <= 0 {
-            panic!("invalid amount");
+            return Err(PulsarError::InvalidAmount);
         }
 
         let counter: u64 = env
@@ -123,6 +193,7 @@ impl MultisigTreasuryContract {
             token,
             amount,
             description,
+            metadata: metadata.clone(),
             status: TxStatus::Pending,
             approvals: 0,
             rejections: 0,
@@ -141,15 +212,17 @@ impl MultisigTreasuryContract {
         );
         env.storage().instance().set(&DataKey::TxCounter, &tx_id);
 
-        env.events().publish(
-            (symbol_short!("treasury"), symbol_short!("proposed")),
-            (tx_id, proposer),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("treasury"),
+            symbol_short!("proposed"),
+            (tx_id, proposer, metadata.category),
         );
 
-        tx_id
+        Ok(tx_id)
     }
 
-    pub fn approve_transaction(env: Env, signer: Address, tx_id: u64) {
+    pub fn approve_transaction(env: Env, signer: Address, tx_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -157,7 +230,7 @@ impl MultisigTreasuryContract {
 
         let signers: Vec<Address> = env.storage().instance().get(&DataKey::Signers).unwrap();
         if !signers.contains(&signer) {
-            panic!("not a signer");
+            return Err(PulsarError::Unauthorized);
         }
 
         if env
@@ -165,17 +238,17 @@ impl MultisigTreasuryContract {
             .persistent()
             .has(&DataKey::TxApproval(tx_id, signer.clone()))
         {
-            panic!("already voted");
+            return Err(PulsarError::AlreadyExists);
         }
 
         let mut tx: TreasuryTx = env
             .storage()
             .persistent()
             .get(&DataKey::Tx(tx_id))
-            .expect("tx not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if tx.status != TxStatus::Pending {
-            panic!("tx not pending");
+            return Err(PulsarError::InvalidState);
         }
 
         if env.ledger().timestamp() > tx.expires_at {
@@ -187,7 +260,7 @@ impl MultisigTreasuryContract {
                 PERSISTENT_LIFETIME_THRESHOLD,
                 PERSISTENT_BUMP_AMOUNT,
             );
-            panic!("tx expired");
+            return Err(PulsarError::Expired);
         }
 
         tx.approvals += 1;
@@ -210,9 +283,10 @@ impl MultisigTreasuryContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
-    pub fn execute_transaction(env: Env, caller: Address, tx_id: u64) {
+    pub fn execute_transaction(env: Env, caller: Address, tx_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -220,17 +294,17 @@ impl MultisigTreasuryContract {
 
         let signers: Vec<Address> = env.storage().instance().get(&DataKey::Signers).unwrap();
         if !signers.contains(&caller) {
-            panic!("not a signer");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut tx: TreasuryTx = env
             .storage()
             .persistent()
             .get(&DataKey::Tx(tx_id))
-            .expect("tx not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if tx.status != TxStatus::Approved {
-            panic!("tx not approved");
+            return Err(PulsarError::InvalidState);
         }
 
         let token_client = token::Client::new(&env, &tx.token);
@@ -246,13 +320,16 @@ impl MultisigTreasuryContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("treasury"), symbol_short!("executed")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("treasury"),
+            symbol_short!("executed"),
             (tx_id, tx.amount),
         );
+        Ok(())
     }
 
-    pub fn reject_transaction(env: Env, signer: Address, tx_id: u64) {
+    pub fn reject_transaction(env: Env, signer: Address, tx_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -260,17 +337,17 @@ impl MultisigTreasuryContract {
 
         let signers: Vec<Address> = env.storage().instance().get(&DataKey::Signers).unwrap();
         if !signers.contains(&signer) {
-            panic!("not a signer");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut tx: TreasuryTx = env
             .storage()
             .persistent()
             .get(&DataKey::Tx(tx_id))
-            .expect("tx not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if tx.status != TxStatus::Pending {
-            panic!("tx not pending");
+            return Err(PulsarError::InvalidState);
         }
 
         tx.rejections += 1;
@@ -294,6 +371,7 @@ impl MultisigTreasuryContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     pub fn get_transaction(env: Env, tx_id: u64) -> Option<TreasuryTx> {
@@ -303,6 +381,45 @@ impl MultisigTreasuryContract {
         env.storage().persistent().get(&DataKey::Tx(tx_id))
     }
 
+    /// Transactions with ids after `start`, paged by `pulsar_common_admin::page`
+    pub fn list_transactions(env: Env, start: u64, limit: u32) -> (Vec<TreasuryTx>, Option<u64>) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TxCounter)
+            .unwrap_or(0);
+        pulsar_common_admin::page(&env, counter, start, limit, |id| {
+            env.storage().persistent().get(&DataKey::Tx(id))
+        })
+    }
+
+    /// Like `list_transactions`, keeping only `category`. Pages may be short
+    /// or empty before the cursor runs out.
+    pub fn list_transactions_by_category(
+        env: Env,
+        category: TxCategory,
+        start: u64,
+        limit: u32,
+    ) -> (Vec<TreasuryTx>, Option<u64>) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TxCounter)
+            .unwrap_or(0);
+        pulsar_common_admin::page(&env, counter, start, limit, |id| {
+            env.storage()
+                .persistent()
+                .get::<DataKey, TreasuryTx>(&DataKey::Tx(id))
+                .filter(|tx| tx.metadata.category == category)
+        })
+    }
+
     pub fn get_signers(env: Env) -> Vec<Address> {
         env.storage()
             .instance()
@@ -310,7 +427,7 @@ impl MultisigTreasuryContract {
         env.storage().instance().get(&DataKey::Signers).unwrap()
     }
 
-    pub fn add_signer(env: Env, admin: Address, new_signer: Address) {
+    pub fn add_signer(env: Env, admin: Address, new_signer: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -320,26 +437,29 @@ impl MultisigTreasuryContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("not initialized");
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut signers: Vec<Address> = env.storage().instance().get(&DataKey::Signers).unwrap();
         if signers.contains(&new_signer) {
-            panic!("already a signer");
+            return Err(PulsarError::AlreadyExists);
         }
 
         signers.push_back(new_signer.clone());
         env.storage().instance().set(&DataKey::Signers, &signers);
 
-        env.events().publish(
-            (symbol_short!("treasury"), symbol_short!("sgn_add")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("treasury"),
+            symbol_short!("sgn_add"),
             new_signer,
         );
+        Ok(())
     }
 
-    pub fn remove_signer(env: Env, admin: Address, signer: Address) {
+    pub fn remove_signer(env: Env, admin: Address, signer: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -349,9 +469,9 @@ impl MultisigTreasuryContract {
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("not initialized");
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut signers: Vec<Address> = env.storage().instance().get(&DataKey::Signers).unwrap();
@@ -362,12 +482,12 @@ impl MultisigTreasuryContract {
             .unwrap();
 
         if !signers.contains(&signer) {
-            panic!("not a signer");
+            return Err(PulsarError::Unauthorized);
         }
 
         // Removing must not drop total signers below required threshold
         if signers.len() as u32 - 1 < required {
-            panic!("cannot remove: would breach required signers threshold");
+            return Err(PulsarError::InvalidInput);
         }
 
         // Rebuild vec without the removed signer
@@ -382,24 +502,139 @@ impl MultisigTreasuryContract {
             .instance()
             .set(&DataKey::Signers, &new_signers);
 
-        env.events().publish(
-            (symbol_short!("treasury"), symbol_short!("sgn_rem")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("treasury"),
+            symbol_short!("sgn_rem"),
             signer,
         );
+        Ok(())
+    }
+
+    /// Add `token` to the inventory reported by `get_portfolio`
+    pub fn register_held_token(
+        env: Env,
+        admin: Address,
+        token: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        let mut tokens = Self::get_held_tokens(env.clone());
+        if tokens.contains(&token) {
+            return Err(PulsarError::AlreadyExists);
+        }
+        if tokens.len() >= MAX_HELD_TOKENS {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        // Fails for addresses that are not token contracts
+        token::Client::new(&env, &token).balance(&env.current_contract_address());
+
+        tokens.push_back(token.clone());
+        env.storage().instance().set(&DataKey::HeldTokens, &tokens);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("treasury"),
+            symbol_short!("tok_add"),
+            token,
+        );
+        Ok(())
+    }
+
+    pub fn unregister_held_token(
+        env: Env,
+        admin: Address,
+        token: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        let mut tokens = Self::get_held_tokens(env.clone());
+        let index = tokens.first_index_of(&token).ok_or(PulsarError::NotFound)?;
+        tokens.remove(index);
+        env.storage().instance().set(&DataKey::HeldTokens, &tokens);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("treasury"),
+            symbol_short!("tok_rem"),
+            token,
+        );
+        Ok(())
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn get_held_tokens(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::HeldTokens)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Treasury balance of every registered token, in registration order
+    pub fn get_portfolio(env: Env) -> Vec<TokenHolding> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let treasury = env.current_contract_address();
+        let mut portfolio = Vec::new(&env);
+        for token in Self::get_held_tokens(env.clone()).iter() {
+            let balance = token::Client::new(&env, &token).balance(&treasury);
+            portfolio.push_back(TokenHolding { token, balance });
+        }
+        portfolio
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
@@ -66,7 +66,6 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
@@ -77,7 +76,10 @@ fn test_initialize_twice() {
     let contract_id = env.register_contract(None, MultisigTreasuryContract);
     let client = MultisigTreasuryContractClient::new(&env, &contract_id);
     client.initialize(&admin, &signers, &1u32);
-    client.initialize(&admin, &signers, &1u32);
+    assert_eq!(
+        client.try_initialize(&admin, &signers, &1u32),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -94,7 +96,6 @@ fn test_initialize_non_admin_fails() {
 }
 
 #[test]
-#[should_panic(expected = "invalid required signers")]
 fn test_initialize_zero_required() {
     let env = Env::default();
     env.mock_all_auths();
@@ -104,11 +105,13 @@ fn test_initialize_zero_required() {
 
     let contract_id = env.register_contract(None, MultisigTreasuryContract);
     let client = MultisigTreasuryContractClient::new(&env, &contract_id);
-    client.initialize(&admin, &signers, &0u32);
+    assert_eq!(
+        client.try_initialize(&admin, &signers, &0u32),
+        Err(Ok(PulsarError::InvalidInput))
+    );
 }
 
 #[test]
-#[should_panic(expected = "invalid required signers")]
 fn test_initialize_required_exceeds_signers() {
     let env = Env::default();
     env.mock_all_auths();
@@ -118,7 +121,10 @@ fn test_initialize_required_exceeds_signers() {
 
     let contract_id = env.register_contract(None, MultisigTreasuryContract);
     let client = MultisigTreasuryContractClient::new(&env, &contract_id);
-    client.initialize(&admin, &signers, &5u32);
+    assert_eq!(
+        client.try_initialize(&admin, &signers, &5u32),
+        Err(Ok(PulsarError::InvalidInput))
+    );
 }
 
 // ─── propose_transaction ────────────────────────────────────────────────────
@@ -148,10 +154,61 @@ fn test_propose_transaction() {
     assert_eq!(tx.amount, 10_000);
     assert!(matches!(tx.status, TxStatus::Pending));
     assert_eq!(tx.approvals, 0);
+
+    let (page, cursor) = client.list_transactions(&0u64, &50u32);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().tx_id, tx_id);
+    assert_eq!(cursor, None);
+}
+
+#[test]
+fn test_propose_classified_transaction() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, signers, _, token_addr) = setup(&env);
+
+    let recipient = Address::generate(&env);
+    let proposer = signers.get(0).unwrap();
+    let invoice = BytesN::from_array(&env, &[7u8; 32]);
+
+    let unclassified = client.propose_transaction(
+        &proposer,
+        &recipient,
+        &token_addr,
+        &10_000i128,
+        &make_desc(&env),
+        &86_400u64,
+    );
+    let grant = client.propose_classified_transaction(
+        &proposer,
+        &recipient,
+        &token_addr,
+        &25_000i128,
+        &make_desc(&env),
+        &86_400u64,
+        &TxMetadata {
+            category: TxCategory::Grant,
+            invoice_hash: Some(invoice.clone()),
+            proposal_id: Some(42),
+        },
+    );
+
+    let tx = client.get_transaction(&unclassified).unwrap();
+    assert_eq!(tx.metadata.category, TxCategory::Other);
+    assert!(tx.metadata.invoice_hash.is_none());
+    let tx = client.get_transaction(&grant).unwrap();
+    assert_eq!(tx.metadata.invoice_hash, Some(invoice));
+    assert_eq!(tx.metadata.proposal_id, Some(42));
+
+    let (page, cursor) = client.list_transactions_by_category(&TxCategory::Grant, &0u64, &50u32);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().tx_id, grant);
+    assert_eq!(cursor, None);
+    let (page, _) = client.list_transactions_by_category(&TxCategory::Payroll, &0u64, &50u32);
+    assert_eq!(page.len(), 0);
 }
 
 #[test]
-#[should_panic(expected = "not a signer")]
 fn test_propose_by_non_signer() {
     let env = Env::default();
     env.mock_all_auths();
@@ -160,7 +217,7 @@ fn test_propose_by_non_signer() {
     let stranger = Address::generate(&env);
     let recipient = Address::generate(&env);
 
-    client.propose_transaction(
+    let result = client.try_propose_transaction(
         &stranger,
         &recipient,
         &token_addr,
@@ -168,10 +225,10 @@ fn test_propose_by_non_signer() {
         &make_desc(&env),
         &86_400u64,
     );
+    assert_eq!(result, Err(Ok(PulsarError::Unauthorized)));
 }
 
 #[test]
-#[should_panic(expected = "invalid amount")]
 fn test_propose_zero_amount() {
     let env = Env::default();
     env.mock_all_auths();
@@ -180,7 +237,7 @@ fn test_propose_zero_amount() {
     let proposer = signers.get(0).unwrap();
     let recipient = Address::generate(&env);
 
-    client.propose_transaction(
+    let result = client.try_propose_transaction(
         &proposer,
         &recipient,
         &token_addr,
@@ -188,6 +245,7 @@ fn test_propose_zero_amount() {
         &make_desc(&env),
         &86_400u64,
     );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidAmount)));
 }
 
 // ─── approve_transaction ─────────────────────────────────────────────────────
@@ -223,7 +281,6 @@ fn test_approve_transaction() {
 }
 
 #[test]
-#[should_panic(expected = "already voted")]
 fn test_approve_twice() {
     let env = Env::default();
     env.mock_all_auths();
@@ -242,11 +299,13 @@ fn test_approve_twice() {
     );
 
     client.approve_transaction(&proposer, &tx_id);
-    client.approve_transaction(&proposer, &tx_id);
+    assert_eq!(
+        client.try_approve_transaction(&proposer, &tx_id),
+        Err(Ok(PulsarError::AlreadyExists))
+    );
 }
 
 #[test]
-#[should_panic(expected = "not a signer")]
 fn test_approve_by_non_signer() {
     let env = Env::default();
     env.mock_all_auths();
@@ -265,11 +324,13 @@ fn test_approve_by_non_signer() {
         &86_400u64,
     );
 
-    client.approve_transaction(&stranger, &tx_id);
+    assert_eq!(
+        client.try_approve_transaction(&stranger, &tx_id),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
-#[should_panic(expected = "tx expired")]
 fn test_approve_expired_transaction() {
     let env = Env::default();
     env.mock_all_auths();
@@ -291,7 +352,10 @@ fn test_approve_expired_transaction() {
         li.timestamp = 200;
     });
 
-    client.approve_transaction(&proposer, &tx_id);
+    assert_eq!(
+        client.try_approve_transaction(&proposer, &tx_id),
+        Err(Ok(PulsarError::Expired))
+    );
 }
 
 // ─── execute_transaction ─────────────────────────────────────────────────────
@@ -339,7 +403,6 @@ fn test_execute_transaction() {
 }
 
 #[test]
-#[should_panic(expected = "tx not approved")]
 fn test_execute_pending_transaction() {
     let env = Env::default();
     env.mock_all_auths();
@@ -358,7 +421,10 @@ fn test_execute_pending_transaction() {
     );
 
     // Not yet approved → should panic
-    client.execute_transaction(&proposer, &tx_id);
+    assert_eq!(
+        client.try_execute_transaction(&proposer, &tx_id),
+        Err(Ok(PulsarError::InvalidState))
+    );
 }
 
 // ─── reject_transaction ─────────────────────────────────────────────────────
@@ -395,7 +461,6 @@ fn test_reject_transaction() {
 }
 
 #[test]
-#[should_panic(expected = "not a signer")]
 fn test_reject_by_non_signer() {
     let env = Env::default();
     env.mock_all_auths();
@@ -414,7 +479,10 @@ fn test_reject_by_non_signer() {
         &86_400u64,
     );
 
-    client.reject_transaction(&stranger, &tx_id);
+    assert_eq!(
+        client.try_reject_transaction(&stranger, &tx_id),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 // ─── read-only ───────────────────────────────────────────────────────────────
@@ -455,18 +523,19 @@ fn test_add_signer() {
 }
 
 #[test]
-#[should_panic(expected = "already a signer")]
 fn test_add_signer_duplicate() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, admin, signers, _, _) = setup(&env);
 
     let existing = signers.get(0).unwrap();
-    client.add_signer(&admin, &existing);
+    assert_eq!(
+        client.try_add_signer(&admin, &existing),
+        Err(Ok(PulsarError::AlreadyExists))
+    );
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_add_signer_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
@@ -474,7 +543,10 @@ fn test_add_signer_unauthorized() {
 
     let stranger = Address::generate(&env);
     let new_signer = Address::generate(&env);
-    client.add_signer(&stranger, &new_signer);
+    assert_eq!(
+        client.try_add_signer(&stranger, &new_signer),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
@@ -492,7 +564,6 @@ fn test_remove_signer() {
 }
 
 #[test]
-#[should_panic(expected = "cannot remove: would breach required signers threshold")]
 fn test_remove_signer_below_required() {
     let env = Env::default();
     env.mock_all_auths();
@@ -502,22 +573,26 @@ fn test_remove_signer_below_required() {
     let s1 = signers.get(0).unwrap();
     let s2 = signers.get(1).unwrap();
     client.remove_signer(&admin, &s1);
-    client.remove_signer(&admin, &s2); // this should panic
+    assert_eq!(
+        client.try_remove_signer(&admin, &s2),
+        Err(Ok(PulsarError::InvalidInput))
+    ); // this should panic
 }
 
 #[test]
-#[should_panic(expected = "not a signer")]
 fn test_remove_signer_nonexistent() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, admin, _, _, _) = setup(&env);
 
     let ghost = Address::generate(&env);
-    client.remove_signer(&admin, &ghost);
+    assert_eq!(
+        client.try_remove_signer(&admin, &ghost),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_remove_signer_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
@@ -525,5 +600,55 @@ fn test_remove_signer_unauthorized() {
 
     let stranger = Address::generate(&env);
     let target = signers.get(0).unwrap();
-    client.remove_signer(&stranger, &target);
+    assert_eq!(
+        client.try_remove_signer(&stranger, &target),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+// ─── token inventory ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_portfolio_reports_registered_token_balances() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, token_admin, token_addr) = setup(&env);
+    let other_token = deploy_token(&env, &token_admin);
+    mint(&env, &token_addr, &client.address, 5_000);
+
+    client.register_held_token(&admin, &token_addr);
+    client.register_held_token(&admin, &other_token);
+    mint(&env, &other_token, &client.address, 700);
+
+    let portfolio = client.get_portfolio();
+    assert_eq!(portfolio.len(), 2);
+    assert_eq!(portfolio.get(0).unwrap().token, token_addr);
+    assert_eq!(portfolio.get(0).unwrap().balance, 5_000);
+    assert_eq!(portfolio.get(1).unwrap().balance, 700);
+
+    client.unregister_held_token(&admin, &token_addr);
+    assert_eq!(client.get_held_tokens(), vec![&env, other_token]);
+}
+
+#[test]
+fn test_register_held_token_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, _, token_addr) = setup(&env);
+    client.register_held_token(&admin, &token_addr);
+    assert_eq!(
+        client.try_register_held_token(&admin, &token_addr),
+        Err(Ok(PulsarError::AlreadyExists))
+    );
+}
+
+#[test]
+fn test_register_held_token_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, signers, _, token_addr) = setup(&env);
+    assert_eq!(
+        client.try_register_held_token(&signers.get(0).unwrap(), &token_addr),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
@@ -4,6 +4,8 @@
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone)]
 pub struct PriceFeed {
@@ -46,26 +48,31 @@ pub struct OracleIntegrationContract;
 
 #[contractimpl]
 impl OracleIntegrationContract {
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::OracleCount, &0u32);
+        Ok(())
     }
 
-    pub fn add_oracle(env: Env, admin: Address, oracle: Address) {
+    pub fn add_oracle(env: Env, admin: Address, oracle: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         let _ttl_key = DataKey::AuthorizedOracle(oracle.clone());
         env.storage().persistent().set(&_ttl_key, &true);
@@ -83,20 +90,26 @@ impl OracleIntegrationContract {
         env.storage()
             .instance()
             .set(&DataKey::OracleCount, &(count + 1));
+        Ok(())
     }
 
-    pub fn remove_oracle(env: Env, admin: Address, oracle: Address) {
+    pub fn remove_oracle(env: Env, admin: Address, oracle: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         env.storage()
             .persistent()
             .remove(&DataKey::AuthorizedOracle(oracle));
+        Ok(())
     }
 
     pub fn update_price(
@@ -106,12 +119,12 @@ impl OracleIntegrationContract {
         price_usd: i128,
         confidence: u32,
         source: String,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         oracle.require_auth();
-        Self::_require_oracle(&env, &oracle);
+        Self::_require_oracle(&env, &oracle)?;
 
         let feed = PriceFeed {
             asset: asset.clone(),
@@ -129,10 +142,13 @@ impl OracleIntegrationContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("oracle"), symbol_short!("price")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("oracle"),
+            symbol_short!("price"),
             (asset, price_usd),
         );
+        Ok(())
     }
 
     pub fn update_performance(
@@ -143,12 +159,12 @@ impl OracleIntegrationContract {
         clicks: u64,
         conversions: u64,
         fraud_score: u32,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         oracle.require_auth();
-        Self::_require_oracle(&env, &oracle);
+        Self::_require_oracle(&env, &oracle)?;
 
         let data = PerformanceData {
             campaign_id,
@@ -167,10 +183,13 @@ impl OracleIntegrationContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("oracle"), symbol_short!("perf")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("oracle"),
+            symbol_short!("perf"),
             campaign_id,
         );
+        Ok(())
     }
 
     pub fn get_price(env: Env, asset: String) -> Option<PriceFeed> {
@@ -199,29 +218,42 @@ impl OracleIntegrationContract {
             .unwrap_or(false)
     }
 
-    fn _require_oracle(env: &Env, oracle: &Address) {
+    fn _require_oracle(env: &Env, oracle: &Address) -> Result<(), PulsarError> {
         let is_auth: bool = env
             .storage()
             .persistent()
             .get(&DataKey::AuthorizedOracle(oracle.clone()))
             .unwrap_or(false);
         if !is_auth {
-            panic!("not authorized oracle");
+            return Err(PulsarError::Unauthorized);
         }
+        Ok(())
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
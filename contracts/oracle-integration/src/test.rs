@@ -21,7 +21,6 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
@@ -29,7 +28,10 @@ fn test_initialize_twice() {
     let c = OracleIntegrationContractClient::new(&env, &id);
     let a = Address::generate(&env);
     c.initialize(&a);
-    c.initialize(&a);
+    assert_eq!(
+        c.try_initialize(&a),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -63,12 +65,14 @@ fn test_remove_oracle() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_add_oracle_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, _) = setup(&env);
-    c.add_oracle(&Address::generate(&env), &Address::generate(&env));
+    assert_eq!(
+        c.try_add_oracle(&Address::generate(&env), &Address::generate(&env)),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
@@ -0,0 +1,546 @@
+//! PulsarTrack - Oracle Registry (Soroban)
+//! Single source of truth for off-chain reporters. Oracles stake the registry
+//! token, the admin grants them per-domain permissions, and regular
+//! heartbeats keep them live. Consumer contracts ask `is_authorized` instead
+//! of each keeping their own oracle address.
+//!
+//! Events (third topic is `pulsar_common_admin::EVENT_SCHEMA_VERSION`):
+//! - ("oracle", "register"): [oracle: Address, stake: i128]
+//! - ("oracle", "staked"): [oracle: Address, amount: i128]
+//! - ("oracle", "granted"): [oracle: Address, domain: OracleDomain]
+//! - ("oracle", "revoked"): [oracle: Address, domain: OracleDomain]
+//! - ("oracle", "slashed"): [oracle: Address, amount: i128]
+//! - ("oracle", "exiting"): [oracle: Address, unbonds_at: u64]
+//! - ("oracle", "withdrawn"): [oracle: Address, amount: i128]
+
+#![no_std]
+pub use pulsar_common_rbac::OracleDomain;
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env, Vec};
+
+pub use pulsar_common_errors::PulsarError;
+
+#[contracttype]
+#[derive(Clone)]
+pub struct RegistryConfig {
+    pub token: Address,
+    pub min_stake: i128,
+    pub heartbeat_window: u64, // 0 disables the liveness check
+    pub unbonding_secs: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct OracleInfo {
+    pub oracle: Address,
+    pub stake: i128,
+    pub domains: Vec<OracleDomain>,
+    pub registered_at: u64,
+    pub last_heartbeat: u64,
+    pub active: bool,
+    pub unbonds_at: Option<u64>, // set on deregistration
+    pub slashed_total: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    PendingAdmin,
+    Config,
+    OracleCount,
+    SlashedPool,
+    Oracle(Address),
+}
+
+const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
+const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
+const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
+
+#[contract]
+pub struct OracleRegistryContract;
+
+#[contractimpl]
+impl OracleRegistryContract {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        min_stake: i128,
+        heartbeat_window: u64,
+        unbonding_secs: u64,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(PulsarError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        if min_stake < 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(
+            &DataKey::Config,
+            &RegistryConfig {
+                token,
+                min_stake,
+                heartbeat_window,
+                unbonding_secs,
+            },
+        );
+        env.storage().instance().set(&DataKey::OracleCount, &0u32);
+        Ok(())
+    }
+
+    pub fn set_config(
+        env: Env,
+        admin: Address,
+        min_stake: i128,
+        heartbeat_window: u64,
+        unbonding_secs: u64,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+        if min_stake < 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        let mut config = Self::_config(&env)?;
+        config.min_stake = min_stake;
+        config.heartbeat_window = heartbeat_window;
+        config.unbonding_secs = unbonding_secs;
+        env.storage().instance().set(&DataKey::Config, &config);
+        Ok(())
+    }
+
+    /// Stake and register; the oracle has no domains until the admin grants them
+    pub fn register(env: Env, oracle: Address, stake: i128) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        oracle.require_auth();
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Oracle(oracle.clone()))
+        {
+            return Err(PulsarError::AlreadyExists);
+        }
+        let config = Self::_config(&env)?;
+        if stake < config.min_stake {
+            return Err(PulsarError::InvalidAmount);
+        }
+        if stake > 0 {
+            token::Client::new(&env, &config.token).transfer(
+                &oracle,
+                &env.current_contract_address(),
+                &stake,
+            );
+        }
+
+        let now = env.ledger().timestamp();
+        let info = OracleInfo {
+            oracle: oracle.clone(),
+            stake,
+            domains: Vec::new(&env),
+            registered_at: now,
+            last_heartbeat: now,
+            active: true,
+            unbonds_at: None,
+            slashed_total: 0,
+        };
+        Self::_save(&env, &info);
+
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::OracleCount)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::OracleCount, &(count + 1));
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("oracle"),
+            symbol_short!("register"),
+            (oracle, stake),
+        );
+        Ok(())
+    }
+
+    /// Top up stake, e.g. after a slash pushed it below the minimum
+    pub fn add_stake(env: Env, oracle: Address, amount: i128) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        oracle.require_auth();
+        if amount <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        let mut info = Self::_load(&env, &oracle)?;
+        if !info.active {
+            return Err(PulsarError::InvalidState);
+        }
+
+        let config = Self::_config(&env)?;
+        token::Client::new(&env, &config.token).transfer(
+            &oracle,
+            &env.current_contract_address(),
+            &amount,
+        );
+        info.stake += amount;
+        Self::_save(&env, &info);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("oracle"),
+            symbol_short!("staked"),
+            (oracle, amount),
+        );
+        Ok(())
+    }
+
+    pub fn grant_domain(
+        env: Env,
+        admin: Address,
+        oracle: Address,
+        domain: OracleDomain,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+        let mut info = Self::_load(&env, &oracle)?;
+        if !info.domains.contains(domain) {
+            info.domains.push_back(domain);
+            Self::_save(&env, &info);
+        }
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("oracle"),
+            symbol_short!("granted"),
+            (oracle, domain),
+        );
+        Ok(())
+    }
+
+    pub fn revoke_domain(
+        env: Env,
+        admin: Address,
+        oracle: Address,
+        domain: OracleDomain,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+        let mut info = Self::_load(&env, &oracle)?;
+        if let Some(index) = info.domains.first_index_of(domain) {
+            info.domains.remove(index);
+            Self::_save(&env, &info);
+        }
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("oracle"),
+            symbol_short!("revoked"),
+            (oracle, domain),
+        );
+        Ok(())
+    }
+
+    pub fn heartbeat(env: Env, oracle: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        oracle.require_auth();
+        let mut info = Self::_load(&env, &oracle)?;
+        if !info.active {
+            return Err(PulsarError::InvalidState);
+        }
+        info.last_heartbeat = env.ledger().timestamp();
+        Self::_save(&env, &info);
+        Ok(())
+    }
+
+    /// Confiscate up to `amount` of stake into the slashed pool
+    pub fn slash(
+        env: Env,
+        admin: Address,
+        oracle: Address,
+        amount: i128,
+    ) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+        if amount <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        let mut info = Self::_load(&env, &oracle)?;
+        let slashed = amount.min(info.stake);
+        info.stake -= slashed;
+        info.slashed_total += slashed;
+        Self::_save(&env, &info);
+
+        let pool: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::SlashedPool)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::SlashedPool, &(pool + slashed));
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("oracle"),
+            symbol_short!("slashed"),
+            (oracle, slashed),
+        );
+        Ok(slashed)
+    }
+
+    pub fn withdraw_slashed(env: Env, admin: Address, to: Address) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+        let pool: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::SlashedPool)
+            .unwrap_or(0);
+        if pool <= 0 {
+            return Err(PulsarError::InsufficientBalance);
+        }
+        env.storage().instance().set(&DataKey::SlashedPool, &0i128);
+        let config = Self::_config(&env)?;
+        token::Client::new(&env, &config.token).transfer(
+            &env.current_contract_address(),
+            &to,
+            &pool,
+        );
+        Ok(pool)
+    }
+
+    /// Stop reporting immediately; stake unlocks after the unbonding period
+    pub fn deregister(env: Env, oracle: Address) -> Result<u64, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        oracle.require_auth();
+        let mut info = Self::_load(&env, &oracle)?;
+        if !info.active {
+            return Err(PulsarError::InvalidState);
+        }
+        let unbonds_at = env.ledger().timestamp() + Self::_config(&env)?.unbonding_secs;
+        info.active = false;
+        info.unbonds_at = Some(unbonds_at);
+        Self::_save(&env, &info);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("oracle"),
+            symbol_short!("exiting"),
+            (oracle, unbonds_at),
+        );
+        Ok(unbonds_at)
+    }
+
+    /// Return the remaining stake and drop the registration
+    pub fn withdraw_stake(env: Env, oracle: Address) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        oracle.require_auth();
+        let info = Self::_load(&env, &oracle)?;
+        let unbonds_at = info.unbonds_at.ok_or(PulsarError::InvalidState)?;
+        if env.ledger().timestamp() < unbonds_at {
+            return Err(PulsarError::TooEarly);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Oracle(oracle.clone()));
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::OracleCount)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::OracleCount, &count.saturating_sub(1));
+
+        if info.stake > 0 {
+            let config = Self::_config(&env)?;
+            token::Client::new(&env, &config.token).transfer(
+                &env.current_contract_address(),
+                &oracle,
+                &info.stake,
+            );
+        }
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("oracle"),
+            symbol_short!("withdrawn"),
+            (oracle, info.stake),
+        );
+        Ok(info.stake)
+    }
+
+    /// Active, staked at least the current minimum, heartbeat within the window
+    pub fn is_live(env: Env, oracle: Address) -> Result<bool, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Ok(
+            match env
+                .storage()
+                .persistent()
+                .get::<DataKey, OracleInfo>(&DataKey::Oracle(oracle))
+            {
+                Some(info) => Self::_is_live(&env, &info)?,
+                None => false,
+            },
+        )
+    }
+
+    /// What consumer contracts call before accepting a report
+    pub fn is_authorized(
+        env: Env,
+        oracle: Address,
+        domain: OracleDomain,
+    ) -> Result<bool, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Ok(
+            match env
+                .storage()
+                .persistent()
+                .get::<DataKey, OracleInfo>(&DataKey::Oracle(oracle))
+            {
+                Some(info) => info.domains.contains(domain) && Self::_is_live(&env, &info)?,
+                None => false,
+            },
+        )
+    }
+
+    pub fn get_oracle(env: Env, oracle: Address) -> Option<OracleInfo> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().persistent().get(&DataKey::Oracle(oracle))
+    }
+
+    pub fn get_config(env: Env) -> Result<RegistryConfig, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_config(&env)
+    }
+
+    pub fn get_oracle_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::OracleCount)
+            .unwrap_or(0)
+    }
+
+    pub fn get_slashed_pool(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::SlashedPool)
+            .unwrap_or(0)
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::propose_admin(
+            &env,
+            &DataKey::Admin,
+            &DataKey::PendingAdmin,
+            current_admin,
+            new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
+
+    // ─── internal helpers ────────────────────────────────────────────────────
+
+    fn _require_admin(env: &Env, admin: &Address) -> Result<(), PulsarError> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if *admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn _config(env: &Env) -> Result<RegistryConfig, PulsarError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(PulsarError::NotInitialized)
+    }
+
+    fn _load(env: &Env, oracle: &Address) -> Result<OracleInfo, PulsarError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Oracle(oracle.clone()))
+            .ok_or(PulsarError::NotFound)
+    }
+
+    fn _save(env: &Env, info: &OracleInfo) {
+        let _ttl_key = DataKey::Oracle(info.oracle.clone());
+        env.storage().persistent().set(&_ttl_key, info);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _is_live(env: &Env, info: &OracleInfo) -> Result<bool, PulsarError> {
+        let config = Self::_config(env)?;
+        Ok(info.active
+            && info.stake >= config.min_stake
+            && (config.heartbeat_window == 0
+                || env.ledger().timestamp().saturating_sub(info.last_heartbeat)
+                    <= config.heartbeat_window))
+    }
+}
+
+mod test;
@@ -0,0 +1,135 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    Address, Env,
+};
+
+// ─── helpers ─────────────────────────────────────────────────────────────────
+
+fn setup(env: &Env) -> (OracleRegistryContractClient<'_>, Address, Address) {
+    let admin = Address::generate(env);
+    let token_admin = Address::generate(env);
+    let token = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+
+    let id = env.register(OracleRegistryContract, ());
+    let client = OracleRegistryContractClient::new(env, &id);
+    // min stake 1_000, heartbeat every hour, one day unbonding
+    client.initialize(&admin, &token, &1_000i128, &3_600u64, &86_400u64);
+    (client, admin, token)
+}
+
+fn staked_oracle(env: &Env, client: &OracleRegistryContractClient<'_>, token: &Address) -> Address {
+    let oracle = Address::generate(env);
+    StellarAssetClient::new(env, token).mint(&oracle, &10_000);
+    client.register(&oracle, &1_000i128);
+    oracle
+}
+
+fn advance(env: &Env, secs: u64) {
+    env.ledger().with_mut(|li| li.timestamp += secs);
+}
+
+// ─── tests ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_register_and_grant_domain() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, token) = setup(&env);
+    let oracle = staked_oracle(&env, &client, &token);
+
+    assert!(client.is_live(&oracle));
+    assert!(!client.is_authorized(&oracle, &OracleDomain::Fraud));
+
+    client.grant_domain(&admin, &oracle, &OracleDomain::Fraud);
+    assert!(client.is_authorized(&oracle, &OracleDomain::Fraud));
+    assert!(!client.is_authorized(&oracle, &OracleDomain::Budget));
+    assert_eq!(client.get_oracle_count(), 1);
+    assert_eq!(TokenClient::new(&env, &token).balance(&oracle), 9_000);
+
+    client.revoke_domain(&admin, &oracle, &OracleDomain::Fraud);
+    assert!(!client.is_authorized(&oracle, &OracleDomain::Fraud));
+}
+
+#[test]
+fn test_register_below_min_stake() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, token) = setup(&env);
+    let oracle = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&oracle, &10_000);
+    assert_eq!(
+        client.try_register(&oracle, &999i128),
+        Err(Ok(PulsarError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_grant_domain_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, token) = setup(&env);
+    let oracle = staked_oracle(&env, &client, &token);
+    assert_eq!(
+        client.try_grant_domain(&oracle, &oracle, &OracleDomain::Fraud),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_missed_heartbeat_loses_authorization() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, token) = setup(&env);
+    let oracle = staked_oracle(&env, &client, &token);
+    client.grant_domain(&admin, &oracle, &OracleDomain::Anomaly);
+
+    advance(&env, 3_601);
+    assert!(!client.is_authorized(&oracle, &OracleDomain::Anomaly));
+
+    client.heartbeat(&oracle);
+    assert!(client.is_authorized(&oracle, &OracleDomain::Anomaly));
+}
+
+#[test]
+fn test_slash_below_minimum_until_topped_up() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, token) = setup(&env);
+    let oracle = staked_oracle(&env, &client, &token);
+    client.grant_domain(&admin, &oracle, &OracleDomain::Reputation);
+
+    assert_eq!(client.slash(&admin, &oracle, &400i128), 400);
+    assert!(!client.is_authorized(&oracle, &OracleDomain::Reputation));
+    assert_eq!(client.get_slashed_pool(), 400);
+
+    client.add_stake(&oracle, &400i128);
+    assert!(client.is_authorized(&oracle, &OracleDomain::Reputation));
+
+    let treasury = Address::generate(&env);
+    assert_eq!(client.withdraw_slashed(&admin, &treasury), 400);
+    assert_eq!(TokenClient::new(&env, &token).balance(&treasury), 400);
+}
+
+#[test]
+fn test_deregister_and_withdraw_after_unbonding() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, token) = setup(&env);
+    let oracle = staked_oracle(&env, &client, &token);
+    client.grant_domain(&admin, &oracle, &OracleDomain::Budget);
+
+    client.deregister(&oracle);
+    assert!(!client.is_authorized(&oracle, &OracleDomain::Budget));
+    assert!(client.try_withdraw_stake(&oracle).is_err());
+
+    advance(&env, 86_400);
+    assert_eq!(client.withdraw_stake(&oracle), 1_000);
+    assert!(client.get_oracle(&oracle).is_none());
+    assert_eq!(client.get_oracle_count(), 0);
+    assert_eq!(TokenClient::new(&env, &token).balance(&oracle), 10_000);
+}
@@ -2,7 +2,12 @@
 //! Multi-token payment support with fee distribution on Stellar.
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env};
+use pulsar_common_rbac::Role;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, vec, Address, Env, IntoVal, Symbol,
+};
+
+pub use pulsar_common_errors::PulsarError;
 
 // ============================================================
 // Data Types
@@ -64,6 +69,7 @@ pub struct RevenueStats {
 pub enum DataKey {
     Admin,
     PendingAdmin,
+    Paused,
     TreasuryAddress,
     NextPaymentId,
     PlatformFeeBps, // basis points (250 = 2.5%)
@@ -72,6 +78,7 @@ pub enum DataKey {
     UserStats(Address),
     RevenueStats(Address),
     DailyVolume(Address, u64),
+    FeeRouter,
 }
 
 // ============================================================
@@ -89,12 +96,12 @@ pub struct PaymentProcessorContract;
 #[contractimpl]
 impl PaymentProcessorContract {
     /// Initialize the contract
-    pub fn initialize(env: Env, admin: Address, treasury: Address) {
+    pub fn initialize(env: Env, admin: Address, treasury: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -105,24 +112,21 @@ impl PaymentProcessorContract {
         env.storage()
             .instance()
             .set(&DataKey::PlatformFeeBps, &250u32); // 2.5%
+        Ok(())
     }
 
     /// Whitelist a token for payments
     pub fn add_token(
         env: Env,
-        admin: Address,
+        operator: Address,
         token: Address,
         min_amount: i128,
         daily_limit: i128,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if admin != stored_admin {
-            panic!("unauthorized");
-        }
+        pulsar_common_rbac::require_role(&env, &DataKey::Admin, Role::Operator, &operator)?;
 
         let config = TokenConfig {
             enabled: true,
@@ -138,22 +142,20 @@ impl PaymentProcessorContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     /// Disable a payment token
-    pub fn remove_token(env: Env, admin: Address, token: Address) {
+    pub fn remove_token(env: Env, operator: Address, token: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if admin != stored_admin {
-            panic!("unauthorized");
-        }
+        pulsar_common_rbac::require_role(&env, &DataKey::Admin, Role::Operator, &operator)?;
 
         env.storage()
             .persistent()
             .remove(&DataKey::TokenConfig(token));
+        Ok(())
     }
 
     /// Process a payment
@@ -163,32 +165,33 @@ impl PaymentProcessorContract {
         recipient: Address,
         token: Address,
         amount: i128,
-    ) -> u64 {
+    ) -> Result<u64, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::require_not_paused(&env, &DataKey::Paused)?;
         payer.require_auth();
 
         if payer == recipient {
-            panic!("cannot pay yourself");
+            return Err(PulsarError::InvalidInput);
         }
 
         if amount <= 0 {
-            panic!("invalid amount");
+            return Err(PulsarError::InvalidAmount);
         }
 
         let config: TokenConfig = env
             .storage()
             .persistent()
             .get(&DataKey::TokenConfig(token.clone()))
-            .expect("token not whitelisted");
+            .ok_or(PulsarError::InvalidState)?;
 
         if !config.enabled {
-            panic!("token disabled");
+            return Err(PulsarError::InvalidState);
         }
 
         if amount < config.min_amount {
-            panic!("amount below minimum");
+            return Err(PulsarError::InvalidAmount);
         }
 
         // Check daily limit
@@ -197,7 +200,7 @@ impl PaymentProcessorContract {
         let daily_vol: i128 = env.storage().temporary().get(&daily_key).unwrap_or(0);
 
         if daily_vol + amount > config.daily_limit {
-            panic!("daily limit exceeded");
+            return Err(PulsarError::LimitExceeded);
         }
 
         // Calculate fee
@@ -219,19 +222,40 @@ impl PaymentProcessorContract {
         let token_client = token::Client::new(&env, &token);
 
         if token_client.balance(&payer) < amount {
-            panic!("insufficient balance");
+            return Err(PulsarError::InsufficientBalance);
         }
 
         token_client.transfer(&payer, &recipient, &net_amount);
 
-        // Transfer fee to treasury
+        // Transfer fee to the fee router when configured, else the treasury
         if fee > 0 {
-            let treasury: Address = env
+            match env
                 .storage()
                 .instance()
-                .get(&DataKey::TreasuryAddress)
-                .unwrap();
-            token_client.transfer(&payer, &treasury, &fee);
+                .get::<DataKey, Address>(&DataKey::FeeRouter)
+            {
+                Some(router) => {
+                    token_client.transfer(&payer, &router, &fee);
+                    let _: () = env.invoke_contract(
+                        &router,
+                        &Symbol::new(&env, "route_fee"),
+                        vec![
+                            &env,
+                            env.current_contract_address().into_val(&env),
+                            token.into_val(&env),
+                            fee.into_val(&env),
+                        ],
+                    );
+                }
+                None => {
+                    let treasury: Address = env
+                        .storage()
+                        .instance()
+                        .get(&DataKey::TreasuryAddress)
+                        .unwrap();
+                    token_client.transfer(&payer, &treasury, &fee);
+                }
+            }
         }
 
         // Record payment
@@ -268,30 +292,39 @@ impl PaymentProcessorContract {
         // Update revenue stats
         Self::_update_revenue_stats(&env, &token, fee, amount);
 
-        env.events().publish(
-            (symbol_short!("payment"), symbol_short!("processed")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("payment"),
+            symbol_short!("processed"),
             (payment_id, payer, recipient, amount),
         );
 
-        payment_id
+        Ok(payment_id)
     }
 
     /// Update platform fee (admin only)
-    pub fn set_platform_fee(env: Env, admin: Address, fee_bps: u32) {
+    pub fn set_platform_fee(env: Env, operator: Address, fee_bps: u32) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if admin != stored_admin {
-            panic!("unauthorized");
-        }
+        pulsar_common_rbac::require_role(&env, &DataKey::Admin, Role::Operator, &operator)?;
         if fee_bps > 1000 {
-            panic!("fee too high"); // max 10%
+            return Err(PulsarError::LimitExceeded); // max 10%
         }
         env.storage()
             .instance()
             .set(&DataKey::PlatformFeeBps, &fee_bps);
+        Ok(())
+    }
+
+    /// Send fees to the fee router instead of the treasury (admin only)
+    pub fn set_fee_router(env: Env, admin: Address, router: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::require_role(&env, &DataKey::Admin, Role::Admin, &admin)?;
+        env.storage().instance().set(&DataKey::FeeRouter, &router);
+        Ok(())
     }
 
     // ============================================================
@@ -380,18 +413,78 @@ impl PaymentProcessorContract {
         );
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    /// Emergency stop for payment processing; callable by any pauser
+    pub fn set_paused(env: Env, pauser: Address, paused: bool) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::require_role(&env, &DataKey::Admin, Role::Pauser, &pauser)?;
+        pulsar_common_admin::write_paused(&env, &DataKey::Paused, paused);
+        Ok(())
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::is_paused(&env, &DataKey::Paused)
+    }
+
+    pub fn grant_role(
+        env: Env,
+        admin: Address,
+        role: Role,
+        account: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::grant_role(&env, &DataKey::Admin, admin, role, account)
+    }
+
+    pub fn revoke_role(
+        env: Env,
+        admin: Address,
+        role: Role,
+        account: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::revoke_role(&env, &DataKey::Admin, admin, role, account)
+    }
+
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_rbac::has_role(&env, &DataKey::Admin, role, &account)
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
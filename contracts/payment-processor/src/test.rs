@@ -6,6 +6,25 @@ use soroban_sdk::{
     Address, Env,
 };
 
+mod router_mock {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockFeeRouter;
+
+    #[contractimpl]
+    impl MockFeeRouter {
+        pub fn route_fee(env: Env, source: Address, _token: Address, amount: i128) {
+            source.require_auth();
+            env.storage().instance().set(&source, &amount);
+        }
+
+        pub fn routed(env: Env, source: Address) -> i128 {
+            env.storage().instance().get(&source).unwrap_or(0)
+        }
+    }
+}
+
 // ─── helpers ─────────────────────────────────────────────────────────────────
 
 fn deploy_token(env: &Env, admin: &Address) -> Address {
@@ -32,7 +51,7 @@ fn setup(
     let token_admin = Address::generate(env);
     let token_addr = deploy_token(env, &token_admin);
 
-    let contract_id = env.register_contract(None, PaymentProcessorContract);
+    let contract_id = env.register(PaymentProcessorContract, ());
     let client = PaymentProcessorContractClient::new(env, &contract_id);
     client.initialize(&admin, &treasury);
 
@@ -48,23 +67,25 @@ fn test_initialize() {
     let admin = Address::generate(&env);
     let treasury = Address::generate(&env);
 
-    let contract_id = env.register_contract(None, PaymentProcessorContract);
+    let contract_id = env.register(PaymentProcessorContract, ());
     let client = PaymentProcessorContractClient::new(&env, &contract_id);
     client.initialize(&admin, &treasury);
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
     let admin = Address::generate(&env);
     let treasury = Address::generate(&env);
 
-    let contract_id = env.register_contract(None, PaymentProcessorContract);
+    let contract_id = env.register(PaymentProcessorContract, ());
     let client = PaymentProcessorContractClient::new(&env, &contract_id);
     client.initialize(&admin, &treasury);
-    client.initialize(&admin, &treasury);
+    assert_eq!(
+        client.try_initialize(&admin, &treasury),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -74,7 +95,7 @@ fn test_initialize_non_admin_fails() {
     let admin = Address::generate(&env);
     let treasury = Address::generate(&env);
 
-    let contract_id = env.register_contract(None, PaymentProcessorContract);
+    let contract_id = env.register(PaymentProcessorContract, ());
     let client = PaymentProcessorContractClient::new(&env, &contract_id);
     client.initialize(&admin, &treasury);
 }
@@ -97,7 +118,6 @@ fn test_add_token() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_add_token_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
@@ -105,7 +125,10 @@ fn test_add_token_unauthorized() {
     let stranger = Address::generate(&env);
     let _ = token_admin;
 
-    client.add_token(&stranger, &token_addr, &1_000i128, &10_000_000i128);
+    assert_eq!(
+        client.try_add_token(&stranger, &token_addr, &1_000i128, &10_000_000i128),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
@@ -133,7 +156,7 @@ fn test_process_payment() {
     let token_admin = Address::generate(&env);
     let token_addr = deploy_token(&env, &token_admin);
 
-    let contract_id = env.register_contract(None, PaymentProcessorContract);
+    let contract_id = env.register(PaymentProcessorContract, ());
     let client = PaymentProcessorContractClient::new(&env, &contract_id);
     client.initialize(&admin, &treasury);
     client.add_token(&admin, &token_addr, &1_000i128, &100_000_000i128);
@@ -162,7 +185,7 @@ fn test_process_payment_fee_split() {
     let token_admin = Address::generate(&env);
     let token_addr = deploy_token(&env, &token_admin);
 
-    let contract_id = env.register_contract(None, PaymentProcessorContract);
+    let contract_id = env.register(PaymentProcessorContract, ());
     let client = PaymentProcessorContractClient::new(&env, &contract_id);
     client.initialize(&admin, &treasury);
     client.add_token(&admin, &token_addr, &1_000i128, &100_000_000i128);
@@ -182,6 +205,28 @@ fn test_process_payment_fee_split() {
     assert_eq!(tc.balance(&treasury), 1_000);
 }
 
+#[test]
+fn test_fee_forwarded_to_fee_router() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, treasury, token_admin, token_addr) = setup(&env);
+    client.add_token(&admin, &token_addr, &1_000i128, &100_000_000i128);
+    let router_id = env.register(router_mock::MockFeeRouter, ());
+    client.set_fee_router(&admin, &router_id);
+
+    let payer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    mint(&env, &token_addr, &token_admin, &payer, 1_000_000);
+    client.process_payment(&payer, &recipient, &token_addr, &10_000i128);
+
+    let tc = TokenClient::new(&env, &token_addr);
+    assert_eq!(tc.balance(&recipient), 9_750);
+    assert_eq!(tc.balance(&treasury), 0);
+    assert_eq!(tc.balance(&router_id), 250);
+    let router = router_mock::MockFeeRouterClient::new(&env, &router_id);
+    assert_eq!(router.routed(&client.address), 250);
+}
+
 #[test]
 fn test_payment_records_stored() {
     let env = Env::default();
@@ -192,7 +237,7 @@ fn test_payment_records_stored() {
     let token_admin = Address::generate(&env);
     let token_addr = deploy_token(&env, &token_admin);
 
-    let contract_id = env.register_contract(None, PaymentProcessorContract);
+    let contract_id = env.register(PaymentProcessorContract, ());
     let client = PaymentProcessorContractClient::new(&env, &contract_id);
     client.initialize(&admin, &treasury);
     client.add_token(&admin, &token_addr, &1_000i128, &100_000_000i128);
@@ -219,7 +264,7 @@ fn test_user_stats_updated() {
     let token_admin = Address::generate(&env);
     let token_addr = deploy_token(&env, &token_admin);
 
-    let contract_id = env.register_contract(None, PaymentProcessorContract);
+    let contract_id = env.register(PaymentProcessorContract, ());
     let client = PaymentProcessorContractClient::new(&env, &contract_id);
     client.initialize(&admin, &treasury);
     client.add_token(&admin, &token_addr, &1_000i128, &100_000_000i128);
@@ -246,7 +291,7 @@ fn test_revenue_stats_updated() {
     let token_admin = Address::generate(&env);
     let token_addr = deploy_token(&env, &token_admin);
 
-    let contract_id = env.register_contract(None, PaymentProcessorContract);
+    let contract_id = env.register(PaymentProcessorContract, ());
     let client = PaymentProcessorContractClient::new(&env, &contract_id);
     client.initialize(&admin, &treasury);
     client.add_token(&admin, &token_addr, &1_000i128, &100_000_000i128);
@@ -267,7 +312,6 @@ fn test_revenue_stats_updated() {
 // ─── payment error paths ──────────────────────────────────────────────────────
 
 #[test]
-#[should_panic(expected = "cannot pay yourself")]
 fn test_cannot_pay_yourself() {
     let env = Env::default();
     env.mock_all_auths();
@@ -277,11 +321,13 @@ fn test_cannot_pay_yourself() {
     let payer = Address::generate(&env);
     mint(&env, &token_addr, &token_admin, &payer, 1_000_000);
 
-    client.process_payment(&payer, &payer, &token_addr, &10_000i128);
+    assert_eq!(
+        client.try_process_payment(&payer, &payer, &token_addr, &10_000i128),
+        Err(Ok(PulsarError::InvalidInput))
+    );
 }
 
 #[test]
-#[should_panic(expected = "invalid amount")]
 fn test_payment_zero_amount() {
     let env = Env::default();
     env.mock_all_auths();
@@ -292,11 +338,13 @@ fn test_payment_zero_amount() {
     let recipient = Address::generate(&env);
     mint(&env, &token_addr, &token_admin, &payer, 1_000_000);
 
-    client.process_payment(&payer, &recipient, &token_addr, &0i128);
+    assert_eq!(
+        client.try_process_payment(&payer, &recipient, &token_addr, &0i128),
+        Err(Ok(PulsarError::InvalidAmount))
+    );
 }
 
 #[test]
-#[should_panic(expected = "token not whitelisted")]
 fn test_payment_token_not_whitelisted() {
     let env = Env::default();
     env.mock_all_auths();
@@ -306,11 +354,13 @@ fn test_payment_token_not_whitelisted() {
     let recipient = Address::generate(&env);
     let random_token = Address::generate(&env);
 
-    client.process_payment(&payer, &recipient, &random_token, &10_000i128);
+    assert_eq!(
+        client.try_process_payment(&payer, &recipient, &random_token, &10_000i128),
+        Err(Ok(PulsarError::InvalidState))
+    );
 }
 
 #[test]
-#[should_panic(expected = "amount below minimum")]
 fn test_payment_below_minimum() {
     let env = Env::default();
     env.mock_all_auths();
@@ -321,11 +371,13 @@ fn test_payment_below_minimum() {
     let recipient = Address::generate(&env);
     mint(&env, &token_addr, &token_admin, &payer, 1_000_000);
 
-    client.process_payment(&payer, &recipient, &token_addr, &1_000i128); // below min
+    assert_eq!(
+        client.try_process_payment(&payer, &recipient, &token_addr, &1_000i128),
+        Err(Ok(PulsarError::InvalidAmount))
+    ); // below min
 }
 
 #[test]
-#[should_panic(expected = "daily limit exceeded")]
 fn test_payment_daily_limit_exceeded() {
     let env = Env::default();
     env.mock_all_auths();
@@ -338,7 +390,10 @@ fn test_payment_daily_limit_exceeded() {
     mint(&env, &token_addr, &token_admin, &payer, 1_000_000);
 
     client.process_payment(&payer, &recipient, &token_addr, &10_000i128);
-    client.process_payment(&payer, &recipient, &token_addr, &10_000i128); // 20_000 > 15_000
+    assert_eq!(
+        client.try_process_payment(&payer, &recipient, &token_addr, &10_000i128),
+        Err(Ok(PulsarError::LimitExceeded))
+    ); // 20_000 > 15_000
 }
 
 // ─── set_platform_fee ────────────────────────────────────────────────────────
@@ -352,22 +407,26 @@ fn test_set_platform_fee() {
 }
 
 #[test]
-#[should_panic(expected = "fee too high")]
 fn test_set_platform_fee_too_high() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, admin, _, _, _) = setup(&env);
-    client.set_platform_fee(&admin, &1001u32); // > 10%
+    assert_eq!(
+        client.try_set_platform_fee(&admin, &1001u32),
+        Err(Ok(PulsarError::LimitExceeded))
+    ); // > 10%
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_set_platform_fee_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, _, _, _, _) = setup(&env);
     let stranger = Address::generate(&env);
-    client.set_platform_fee(&stranger, &100u32);
+    assert_eq!(
+        client.try_set_platform_fee(&stranger, &100u32),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 #[test]
 fn test_admin_transfer_flow() {
@@ -407,3 +466,83 @@ fn test_accept_admin_unauthorized() {
     c.propose_admin(&admin, &new_admin);
     c.accept_admin(&stranger);
 }
+
+// ─── emergency stop ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_process_payment_after_unpause() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, token_admin, token_addr) = setup(&env);
+    client.add_token(&admin, &token_addr, &1_000i128, &100_000_000i128);
+
+    let payer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    mint(&env, &token_addr, &token_admin, &payer, 1_000_000);
+
+    client.set_paused(&admin, &true);
+    assert!(client.is_paused());
+    let paused = client.try_process_payment(&payer, &recipient, &token_addr, &10_000i128);
+    assert!(paused.is_err());
+
+    client.set_paused(&admin, &false);
+    assert_eq!(
+        client.process_payment(&payer, &recipient, &token_addr, &10_000i128),
+        1
+    );
+}
+
+// ─── upgrade ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_upgrade_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, _, _) = setup(&env);
+    let hash = soroban_sdk::BytesN::from_array(&env, &[0u8; 32]);
+    assert_eq!(
+        client.try_upgrade(&Address::generate(&env), &hash),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+// ─── roles ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_operator_and_pauser_roles() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, _, token_addr) = setup(&env);
+    let operator = Address::generate(&env);
+    let pauser = Address::generate(&env);
+
+    client.grant_role(&admin, &Role::Operator, &operator);
+    client.grant_role(&admin, &Role::Pauser, &pauser);
+    assert!(client.has_role(&Role::Operator, &operator));
+    assert!(!client.has_role(&Role::Pauser, &operator));
+    assert!(client.has_role(&Role::Pauser, &admin));
+
+    client.add_token(&operator, &token_addr, &1_000i128, &10_000_000i128);
+    assert!(client.get_token_config(&token_addr).is_some());
+
+    client.set_paused(&pauser, &true);
+    assert!(client.is_paused());
+    assert!(client.try_set_paused(&operator, &false).is_err());
+
+    client.revoke_role(&admin, &Role::Operator, &operator);
+    assert!(!client.has_role(&Role::Operator, &operator));
+    assert!(client.try_remove_token(&operator, &token_addr).is_err());
+}
+
+#[test]
+fn test_grant_role_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, _, _) = setup(&env);
+    let operator = Address::generate(&env);
+
+    assert_eq!(
+        client.try_grant_role(&operator, &Role::Operator, &operator),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
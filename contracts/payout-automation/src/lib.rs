@@ -1,13 +1,15 @@
 //! PulsarTrack - Payout Automation (Soroban)
 //! Automated publisher payouts and scheduled payments on Stellar.
 //!
-//! Events:
+//! Events (third topic is `pulsar_common_admin::EVENT_SCHEMA_VERSION`):
 //! - ("payout", "schedule"): [payout_id: u64, recipient: Address, amount: i128]
 //! - ("payout", "execute"): [payout_id: u64, amount: i128]
 
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env};
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone, PartialEq)]
 pub enum PayoutStatus {
@@ -63,12 +65,12 @@ pub struct PayoutAutomationContract;
 
 #[contractimpl]
 impl PayoutAutomationContract {
-    pub fn initialize(env: Env, admin: Address, token: Address) {
+    pub fn initialize(env: Env, admin: Address, token: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -77,6 +79,7 @@ impl PayoutAutomationContract {
         env.storage()
             .instance()
             .set(&DataKey::MinPayoutAmount, &1_000_000i128);
+        Ok(())
     }
 
     pub fn schedule_payout(
@@ -86,14 +89,18 @@ impl PayoutAutomationContract {
         amount: i128,
         execute_after: u64,
         campaign_id: Option<u64>,
-    ) -> u64 {
+    ) -> Result<u64, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let counter: u64 = env
@@ -132,35 +139,41 @@ impl PayoutAutomationContract {
             .instance()
             .set(&DataKey::PayoutCounter, &payout_id);
 
-        env.events().publish(
-            (symbol_short!("payout"), symbol_short!("schedule")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("payout"),
+            symbol_short!("schedule"),
             (payout_id, recipient, amount),
         );
 
-        payout_id
+        Ok(payout_id)
     }
 
-    pub fn execute_payout(env: Env, caller: Address, payout_id: u64) {
+    pub fn execute_payout(env: Env, caller: Address, payout_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         caller.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if caller != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         let mut payout: ScheduledPayout = env
             .storage()
             .persistent()
             .get(&DataKey::Payout(payout_id))
-            .expect("payout not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if payout.status != PayoutStatus::Scheduled {
-            panic!("payout not scheduled");
+            return Err(PulsarError::InvalidState);
         }
 
         if env.ledger().timestamp() < payout.execute_after {
-            panic!("too early to execute");
+            return Err(PulsarError::TooEarly);
         }
 
         let token_client = token::Client::new(&env, &payout.token);
@@ -203,20 +216,32 @@ impl PayoutAutomationContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("payout"), symbol_short!("execute")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("payout"),
+            symbol_short!("execute"),
             (payout_id, payout.amount),
         );
+        Ok(())
     }
 
-    pub fn add_publisher_earnings(env: Env, admin: Address, publisher: Address, amount: i128) {
+    pub fn add_publisher_earnings(
+        env: Env,
+        admin: Address,
+        publisher: Address,
+        amount: i128,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let key = DataKey::PublisherEarnings(publisher.clone());
@@ -238,6 +263,7 @@ impl PayoutAutomationContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     pub fn get_payout(env: Env, payout_id: u64) -> Option<ScheduledPayout> {
@@ -256,18 +282,30 @@ impl PayoutAutomationContract {
             .get(&DataKey::PublisherEarnings(publisher))
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
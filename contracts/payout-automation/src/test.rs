@@ -52,7 +52,6 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
@@ -62,7 +61,10 @@ fn test_initialize_twice() {
     let contract_id = env.register_contract(None, PayoutAutomationContract);
     let client = PayoutAutomationContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token);
-    client.initialize(&admin, &token);
+    assert_eq!(
+        client.try_initialize(&admin, &token),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -106,7 +108,6 @@ fn test_schedule_payout() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_schedule_payout_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
@@ -114,7 +115,10 @@ fn test_schedule_payout_unauthorized() {
     let stranger = Address::generate(&env);
     let recipient = Address::generate(&env);
 
-    client.schedule_payout(&stranger, &recipient, &1_000_000i128, &3600u64, &None);
+    assert_eq!(
+        client.try_schedule_payout(&stranger, &recipient, &1_000_000i128, &3600u64, &None),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
@@ -171,7 +175,6 @@ fn test_execute_payout() {
 }
 
 #[test]
-#[should_panic(expected = "too early to execute")]
 fn test_execute_payout_too_early() {
     let env = Env::default();
     env.mock_all_auths();
@@ -196,11 +199,13 @@ fn test_execute_payout_too_early() {
     );
 
     // ledger timestamp is still 0 → too early
-    client.execute_payout(&admin, &payout_id);
+    assert_eq!(
+        client.try_execute_payout(&admin, &payout_id),
+        Err(Ok(PulsarError::TooEarly))
+    );
 }
 
 #[test]
-#[should_panic(expected = "payout not scheduled")]
 fn test_execute_payout_twice() {
     let env = Env::default();
     env.mock_all_auths();
@@ -219,11 +224,13 @@ fn test_execute_payout_twice() {
     let payout_id = client.schedule_payout(&admin, &recipient, &1_000_000i128, &0u64, &None);
 
     client.execute_payout(&admin, &payout_id);
-    client.execute_payout(&admin, &payout_id); // second attempt → "payout not scheduled"
+    assert_eq!(
+        client.try_execute_payout(&admin, &payout_id),
+        Err(Ok(PulsarError::InvalidState))
+    ); // second attempt → "payout not scheduled"
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_execute_payout_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
@@ -242,7 +249,10 @@ fn test_execute_payout_unauthorized() {
     let payout_id = client.schedule_payout(&admin, &recipient, &1_000_000i128, &0u64, &None);
 
     let stranger = Address::generate(&env);
-    client.execute_payout(&stranger, &payout_id);
+    assert_eq!(
+        client.try_execute_payout(&stranger, &payout_id),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 // ─── publisher earnings ───────────────────────────────────────────────────────
@@ -276,7 +286,6 @@ fn test_add_publisher_earnings_accumulates() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_add_publisher_earnings_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
@@ -284,7 +293,10 @@ fn test_add_publisher_earnings_unauthorized() {
     let stranger = Address::generate(&env);
     let publisher = Address::generate(&env);
 
-    client.add_publisher_earnings(&stranger, &publisher, &500_000i128);
+    assert_eq!(
+        client.try_add_publisher_earnings(&stranger, &publisher, &500_000i128),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
@@ -4,6 +4,8 @@
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env};
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone)]
 pub struct PerformanceAttestation {
@@ -55,12 +57,12 @@ pub struct PerformanceOracleContract;
 
 #[contractimpl]
 impl PerformanceOracleContract {
-    pub fn initialize(env: Env, admin: Address, min_attesters: u32) {
+    pub fn initialize(env: Env, admin: Address, min_attesters: u32) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -70,16 +72,25 @@ impl PerformanceOracleContract {
         env.storage()
             .instance()
             .set(&DataKey::ConsensusThresholdPct, &67u32); // 2/3 majority
+        Ok(())
     }
 
-    pub fn authorize_attester(env: Env, admin: Address, attester: Address) {
+    pub fn authorize_attester(
+        env: Env,
+        admin: Address,
+        attester: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
         let _ttl_key = DataKey::Attester(attester);
         env.storage().persistent().set(&_ttl_key, &true);
@@ -88,6 +99,7 @@ impl PerformanceOracleContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     pub fn submit_attestation(
@@ -99,7 +111,7 @@ impl PerformanceOracleContract {
         fraud_rate: u32,
         quality_score: u32,
         data_hash: BytesN<32>,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -112,7 +124,7 @@ impl PerformanceOracleContract {
             .unwrap_or(false);
 
         if !is_auth {
-            panic!("not authorized attester");
+            return Err(PulsarError::Unauthorized);
         }
 
         if env
@@ -120,7 +132,7 @@ impl PerformanceOracleContract {
             .persistent()
             .has(&DataKey::Attestation(campaign_id, attester.clone()))
         {
-            panic!("already attested");
+            return Err(PulsarError::AlreadyExists);
         }
 
         let attestation = PerformanceAttestation {
@@ -167,12 +179,15 @@ impl PerformanceOracleContract {
         );
 
         // Attempt to build consensus with actual averaging
-        Self::_try_build_consensus(&env, campaign_id, count + 1);
+        Self::_try_build_consensus(&env, campaign_id, count + 1)?;
 
-        env.events().publish(
-            (symbol_short!("oracle"), symbol_short!("attested")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("oracle"),
+            symbol_short!("attested"),
             campaign_id,
         );
+        Ok(())
     }
 
     pub fn get_attestation(
@@ -207,7 +222,11 @@ impl PerformanceOracleContract {
             .unwrap_or(0)
     }
 
-    fn _try_build_consensus(env: &Env, campaign_id: u64, total_attesters: u32) {
+    fn _try_build_consensus(
+        env: &Env,
+        campaign_id: u64,
+        total_attesters: u32,
+    ) -> Result<(), PulsarError> {
         let min_attesters: u32 = env
             .storage()
             .instance()
@@ -215,7 +234,7 @@ impl PerformanceOracleContract {
             .unwrap_or(3);
 
         if total_attesters < min_attesters {
-            return;
+            return Ok(());
         }
 
         // Compute actual averages by reading all attestations
@@ -229,13 +248,13 @@ impl PerformanceOracleContract {
                 .storage()
                 .persistent()
                 .get(&DataKey::CampaignAttesterIndex(campaign_id, i))
-                .expect("attester index not found");
+                .ok_or(PulsarError::NotFound)?;
 
             let attestation: PerformanceAttestation = env
                 .storage()
                 .persistent()
                 .get(&DataKey::Attestation(campaign_id, attester))
-                .expect("attestation not found");
+                .ok_or(PulsarError::NotFound)?;
 
             sum_impressions += attestation.impressions_verified;
             sum_clicks += attestation.clicks_verified;
@@ -267,20 +286,29 @@ impl PerformanceOracleContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
@@ -18,7 +18,6 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
@@ -26,7 +25,7 @@ fn test_initialize_twice() {
     let c = PerformanceOracleContractClient::new(&env, &id);
     let a = Address::generate(&env);
     c.initialize(&a, &2u32);
-    c.initialize(&a, &2u32);
+    assert_eq!(c.try_initialize(&a, &2u32), Err(Ok(PulsarError::AlreadyInitialized)));
 }
 
 #[test]
@@ -8,6 +8,8 @@ use soroban_sdk::{
     Address, Bytes, BytesN, Env, String,
 };
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone)]
 pub struct PrivacyConsent {
@@ -44,6 +46,7 @@ pub struct DataRequest {
 }
 
 #[contracttype]
+#[derive(Clone)]
 pub enum DataKey {
     Admin,
     RequestCounter,
@@ -62,14 +65,15 @@ pub struct PrivacyLayerContract;
 
 #[contractimpl]
 impl PrivacyLayerContract {
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), PulsarError> {
         env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::RequestCounter, &0u64);
+        Ok(())
     }
 
     pub fn set_consent(
@@ -107,8 +111,10 @@ impl PrivacyLayerContract {
         env.storage().persistent().set(&_ttl_key, &consent);
         env.storage().persistent().extend_ttl(&_ttl_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
 
-        env.events().publish(
-            (symbol_short!("privacy"), symbol_short!("consent")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("privacy"),
+            symbol_short!("consent"),
             user,
         );
     }
@@ -118,8 +124,10 @@ impl PrivacyLayerContract {
         user.require_auth();
         env.storage().persistent().remove(&DataKey::Consent(user.clone()));
 
-        env.events().publish(
-            (symbol_short!("privacy"), symbol_short!("revoked")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("privacy"),
+            symbol_short!("revoked"),
             user,
         );
     }
@@ -154,32 +162,39 @@ impl PrivacyLayerContract {
         env.storage().persistent().set(&_ttl_key, &proof);
         env.storage().persistent().extend_ttl(&_ttl_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
 
-        env.events().publish(
-            (symbol_short!("zkp"), symbol_short!("submitted")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("zkp"),
+            symbol_short!("submitted"),
             prover,
         );
 
         proof_id.into()
     }
 
-    pub fn verify_zkp(env: Env, admin: Address, proof_id: BytesN<32>) {
+    pub fn verify_zkp(env: Env, admin: Address, proof_id: BytesN<32>) -> Result<(), PulsarError> {
         env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut proof: AnonymousSegmentProof = env
             .storage()
             .persistent()
             .get(&DataKey::Proof(proof_id.clone()))
-            .expect("proof not found");
+            .ok_or(PulsarError::NotFound)?;
 
         proof.verified = true;
         let _ttl_key = DataKey::Proof(proof_id);
         env.storage().persistent().set(&_ttl_key, &proof);
         env.storage().persistent().extend_ttl(&_ttl_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        Ok(())
     }
 
     pub fn has_consent(env: Env, user: Address, consent_type: String) -> bool {
@@ -215,6 +230,10 @@ impl PrivacyLayerContract {
         env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         env.storage().persistent().get(&DataKey::Proof(proof_id))
     }
+
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
 }
 
 mod test;
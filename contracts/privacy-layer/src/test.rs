@@ -2,9 +2,9 @@
 use super::*;
 use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
 
-fn setup(env: &Env) -> (PrivacyLayerContractClient, Address) {
+fn setup(env: &Env) -> (PrivacyLayerContractClient<'_>, Address) {
     let admin = Address::generate(env);
-    let id = env.register_contract(None, PrivacyLayerContract);
+    let id = env.register(PrivacyLayerContract, ());
     let c = PrivacyLayerContractClient::new(env, &id);
     c.initialize(&admin);
     (c, admin)
@@ -15,12 +15,11 @@ fn s(env: &Env, v: &str) -> String { String::from_str(env, v) }
 fn test_initialize() { let env = Env::default(); env.mock_all_auths(); setup(&env); }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default(); env.mock_all_auths();
-    let id = env.register_contract(None, PrivacyLayerContract);
+    let id = env.register(PrivacyLayerContract, ());
     let c = PrivacyLayerContractClient::new(&env, &id);
-    let a = Address::generate(&env); c.initialize(&a); c.initialize(&a);
+    let a = Address::generate(&env); c.initialize(&a); assert_eq!(c.try_initialize(&a), Err(Ok(PulsarError::AlreadyInitialized)));
 }
 
 #[test]
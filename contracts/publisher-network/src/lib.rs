@@ -2,7 +2,12 @@
 //! Manages the decentralized publisher network on Stellar.
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, Address, Env, IntoVal, String,
+    Symbol, Vec,
+};
+
+pub use pulsar_common_errors::PulsarError;
 
 #[contracttype]
 #[derive(Clone)]
@@ -16,6 +21,9 @@ pub struct NetworkNode {
     pub is_active: bool,
     pub joined_at: u64,
     pub last_heartbeat: u64,
+    pub stake: i128,                 // PULSAR locked against declared capacity
+    pub deactivated_at: Option<u64>, // starts the unstake cooldown
+    pub is_suspended: bool,          // set by fraud suspension, blocks self-reactivation
 }
 
 #[contracttype]
@@ -36,15 +44,65 @@ pub struct NetworkStats {
     pub last_updated: u64,
 }
 
+/// Stake requirement for joining: `stake_per_capacity` PULSAR per unit of
+/// declared daily capacity, `slash_bps` of it forfeited on fraud suspension.
+#[contracttype]
+#[derive(Clone)]
+pub struct StakingConfig {
+    pub token: Address,
+    pub stake_per_capacity: i128,
+    pub unstake_cooldown: u64,
+    pub slash_bps: u32,
+}
+
+/// Mirror of identity-registry's `IdentityType` for cross-contract calls
+#[contracttype]
+#[derive(Clone)]
+pub enum IdentityType {
+    Advertiser,
+    Publisher,
+    DataProvider,
+    Operator,
+}
+
+/// What a node must meet before it can move up to a given `NodeType`
+#[contracttype]
+#[derive(Clone)]
+pub struct UpgradeRequirement {
+    pub min_reputation: u32, // publisher-reputation score, 0-1000
+    pub min_stake: i128,
+    pub min_tenure_days: u64,
+}
+
+/// Impressions a node served on a given day against its declared capacity
+#[contracttype]
+#[derive(Clone)]
+pub struct NodeUtilization {
+    pub day: u64,
+    pub impressions: u64,
+    pub capacity: u64,
+    pub utilization_bps: u32,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
     Admin,
     PendingAdmin,
     FraudContract,
+    IdentityRegistry,
+    Orchestrator,
+    ReputationContract,
+    StakingConfig,
+    SlashedFunds,
+    LivenessWindow,
     NodeCount,
     NetworkStats,
     Node(Address),
+    NodeImpressions(Address, u64), // (publisher, day)
+    ReinstatementRequest(Address),
+    ZoneStats(String), // geographic_zone -> NetworkStats scoped to that zone
+    UpgradeRequirement(NodeType),
 }
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
@@ -57,12 +115,12 @@ pub struct PublisherNetworkContract;
 
 #[contractimpl]
 impl PublisherNetworkContract {
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -77,6 +135,7 @@ impl PublisherNetworkContract {
                 last_updated: env.ledger().timestamp(),
             },
         );
+        Ok(())
     }
 
     pub fn join_network(
@@ -87,7 +146,7 @@ impl PublisherNetworkContract {
         min_cpm: i128,
         geographic_zone: String,
         content_categories: Vec<String>,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -98,10 +157,31 @@ impl PublisherNetworkContract {
             .persistent()
             .has(&DataKey::Node(publisher.clone()))
         {
-            panic!("already in network");
+            return Err(PulsarError::AlreadyExists);
+        }
+
+        if let Some(registry) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::IdentityRegistry)
+        {
+            let verified: bool = env.invoke_contract(
+                &registry,
+                &Symbol::new(&env, "require_verified"),
+                Vec::from_array(
+                    &env,
+                    [
+                        publisher.clone().into_val(&env),
+                        IdentityType::Publisher.into_val(&env),
+                    ],
+                ),
+            );
+            if !verified {
+                return Err(PulsarError::NotVerified);
+            }
         }
 
-        let node = NetworkNode {
+        let mut node = NetworkNode {
             publisher: publisher.clone(),
             node_type,
             capacity,
@@ -111,7 +191,11 @@ impl PublisherNetworkContract {
             is_active: true,
             joined_at: env.ledger().timestamp(),
             last_heartbeat: env.ledger().timestamp(),
+            stake: 0,
+            deactivated_at: None,
+            is_suspended: false,
         };
+        Self::_lock_required_stake(&env, &mut node)?;
 
         let _ttl_key = DataKey::Node(publisher.clone());
         env.storage().persistent().set(&_ttl_key, &node);
@@ -140,13 +224,22 @@ impl PublisherNetworkContract {
         stats.total_capacity += capacity;
         env.storage().instance().set(&DataKey::NetworkStats, &stats);
 
-        env.events().publish(
-            (symbol_short!("network"), symbol_short!("joined")),
+        let mut zone_stats = Self::_load_zone_stats(&env, &node.geographic_zone);
+        zone_stats.total_nodes += 1;
+        zone_stats.active_nodes += 1;
+        zone_stats.total_capacity += capacity;
+        Self::_save_zone_stats(&env, &node.geographic_zone, &mut zone_stats);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("network"),
+            symbol_short!("joined"),
             publisher,
         );
+        Ok(())
     }
 
-    pub fn heartbeat(env: Env, publisher: Address) {
+    pub fn heartbeat(env: Env, publisher: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -156,7 +249,7 @@ impl PublisherNetworkContract {
             .storage()
             .persistent()
             .get(&DataKey::Node(publisher.clone()))
-            .expect("not in network");
+            .ok_or(PulsarError::NotFound)?;
 
         node.last_heartbeat = env.ledger().timestamp();
         let _ttl_key = DataKey::Node(publisher);
@@ -166,32 +259,658 @@ impl PublisherNetworkContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
+    }
+
+    /// Update a node's declared profile; capacity increases lock additional
+    /// stake, while stake freed by decreases stays locked until unstake.
+    pub fn update_node(
+        env: Env,
+        publisher: Address,
+        capacity: u64,
+        min_cpm: i128,
+        geographic_zone: String,
+        content_categories: Vec<String>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        publisher.require_auth();
+
+        let mut node: NetworkNode = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Node(publisher.clone()))
+            .ok_or(PulsarError::NotFound)?;
+
+        if node.is_suspended {
+            return Err(PulsarError::InvalidState);
+        }
+
+        let previous_capacity = node.capacity;
+        let previous_zone = node.geographic_zone.clone();
+        node.capacity = capacity;
+        node.min_cpm = min_cpm;
+        node.geographic_zone = geographic_zone;
+        node.content_categories = content_categories;
+        if node.is_active {
+            Self::_lock_required_stake(&env, &mut node)?;
+        }
+
+        let _ttl_key = DataKey::Node(publisher.clone());
+        env.storage().persistent().set(&_ttl_key, &node);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let mut stats: NetworkStats = env
+            .storage()
+            .instance()
+            .get(&DataKey::NetworkStats)
+            .unwrap();
+        stats.total_capacity = stats.total_capacity - previous_capacity + capacity;
+        stats.last_updated = env.ledger().timestamp();
+        env.storage().instance().set(&DataKey::NetworkStats, &stats);
+
+        let mut old_zone = Self::_load_zone_stats(&env, &previous_zone);
+        old_zone.total_capacity = old_zone.total_capacity.saturating_sub(previous_capacity);
+        if previous_zone != node.geographic_zone {
+            old_zone.total_nodes = old_zone.total_nodes.saturating_sub(1);
+            if node.is_active {
+                old_zone.active_nodes = old_zone.active_nodes.saturating_sub(1);
+            }
+            Self::_save_zone_stats(&env, &previous_zone, &mut old_zone);
+
+            let mut new_zone = Self::_load_zone_stats(&env, &node.geographic_zone);
+            new_zone.total_nodes += 1;
+            if node.is_active {
+                new_zone.active_nodes += 1;
+            }
+            new_zone.total_capacity += capacity;
+            Self::_save_zone_stats(&env, &node.geographic_zone, &mut new_zone);
+        } else {
+            old_zone.total_capacity += capacity;
+            Self::_save_zone_stats(&env, &previous_zone, &mut old_zone);
+        }
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("network"),
+            symbol_short!("updated"),
+            (publisher, capacity),
+        );
+        Ok(())
+    }
+
+    /// Bring a self-deactivated node back online, re-locking any missing stake
+    pub fn reactivate(env: Env, publisher: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        publisher.require_auth();
+
+        let mut node: NetworkNode = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Node(publisher.clone()))
+            .ok_or(PulsarError::NotFound)?;
+
+        if node.is_suspended {
+            return Err(PulsarError::InvalidState);
+        }
+        if node.is_active {
+            return Err(PulsarError::AlreadyExists);
+        }
+
+        Self::_activate_node(&env, &mut node)?;
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("network"),
+            symbol_short!("reactive"),
+            publisher,
+        );
+        Ok(())
     }
 
-    pub fn deactivate(env: Env, publisher: Address) {
+    /// Ask to be let back in after a fraud suspension. Any stake slashed at
+    /// suspension must be topped back up here.
+    pub fn request_reinstatement(env: Env, publisher: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         publisher.require_auth();
 
-        Self::_deactivate_node(&env, publisher);
+        let mut node: NetworkNode = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Node(publisher.clone()))
+            .ok_or(PulsarError::NotFound)?;
+        if !node.is_suspended {
+            return Err(PulsarError::InvalidState);
+        }
+
+        Self::_lock_required_stake(&env, &mut node)?;
+        let _ttl_key = DataKey::Node(publisher.clone());
+        env.storage().persistent().set(&_ttl_key, &node);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let _ttl_key = DataKey::ReinstatementRequest(publisher.clone());
+        env.storage()
+            .persistent()
+            .set(&_ttl_key, &env.ledger().timestamp());
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("network"),
+            symbol_short!("reinst_rq"),
+            publisher,
+        );
+        Ok(())
     }
 
-    pub fn set_fraud_contract(env: Env, admin: Address, fraud_contract: Address) {
+    /// Lift a fraud suspension and reactivate the node. The fraud contract may
+    /// approve directly once it clears its flag; the admin may approve only if
+    /// the fraud contract no longer reports the publisher as suspended.
+    pub fn approve_reinstatement(
+        env: Env,
+        caller: Address,
+        publisher: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        caller.require_auth();
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        let fraud_contract: Option<Address> = env.storage().instance().get(&DataKey::FraudContract);
+        if caller == admin {
+            if let Some(fraud_addr) = fraud_contract {
+                let flagged: bool = env.invoke_contract(
+                    &fraud_addr,
+                    &Symbol::new(&env, "is_publisher_suspended"),
+                    Vec::from_array(&env, [publisher.clone().into_val(&env)]),
+                );
+                if flagged {
+                    return Err(PulsarError::InvalidState);
+                }
+            }
+        } else if fraud_contract != Some(caller) {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        let request_key = DataKey::ReinstatementRequest(publisher.clone());
+        if !env.storage().persistent().has(&request_key) {
+            return Err(PulsarError::NotFound);
+        }
+
+        let mut node: NetworkNode = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Node(publisher.clone()))
+            .ok_or(PulsarError::NotFound)?;
+        if !node.is_suspended {
+            return Err(PulsarError::InvalidState);
+        }
+
+        node.is_suspended = false;
+        Self::_activate_node(&env, &mut node)?;
+        env.storage().persistent().remove(&request_key);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("network"),
+            symbol_short!("reinstate"),
+            publisher,
+        );
+        Ok(())
+    }
+
+    pub fn set_reputation_contract(
+        env: Env,
+        admin: Address,
+        reputation: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ReputationContract, &reputation);
+        Ok(())
+    }
+
+    pub fn set_upgrade_requirement(
+        env: Env,
+        admin: Address,
+        node_type: NodeType,
+        requirement: UpgradeRequirement,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::UpgradeRequirement(node_type), &requirement);
+        Ok(())
+    }
+
+    pub fn get_upgrade_requirement(env: Env, node_type: NodeType) -> Option<UpgradeRequirement> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::UpgradeRequirement(node_type))
+    }
+
+    pub fn upgrade_node_type(
+        env: Env,
+        publisher: Address,
+        new_type: NodeType,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        publisher.require_auth();
+
+        let mut node: NetworkNode = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Node(publisher.clone()))
+            .ok_or(PulsarError::NotFound)?;
+        if !node.is_active {
+            return Err(PulsarError::InvalidState);
+        }
+        if Self::_node_rank(&new_type) <= Self::_node_rank(&node.node_type) {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let requirement: UpgradeRequirement = env
+            .storage()
+            .instance()
+            .get(&DataKey::UpgradeRequirement(new_type.clone()))
+            .ok_or(PulsarError::NotInitialized)?;
+
+        if node.stake < requirement.min_stake {
+            return Err(PulsarError::Unauthorized);
+        }
+        let tenure_days = env.ledger().timestamp().saturating_sub(node.joined_at) / 86_400;
+        if tenure_days < requirement.min_tenure_days {
+            return Err(PulsarError::Unauthorized);
+        }
+        if requirement.min_reputation > 0 {
+            let reputation: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::ReputationContract)
+                .ok_or(PulsarError::NotInitialized)?;
+            let score: u32 = env.invoke_contract(
+                &reputation,
+                &Symbol::new(&env, "get_score"),
+                Vec::from_array(&env, [publisher.clone().into_val(&env)]),
+            );
+            if score < requirement.min_reputation {
+                return Err(PulsarError::Unauthorized);
+            }
+        }
+
+        node.node_type = new_type;
+        let _ttl_key = DataKey::Node(publisher.clone());
+        env.storage().persistent().set(&_ttl_key, &node);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("network"),
+            symbol_short!("upgraded"),
+            (publisher, node.node_type),
+        );
+        Ok(())
+    }
+
+    /// Matching priority for routing: 0 for Standard or inactive nodes,
+    /// 1 for Premium, 2 for Enterprise.
+    pub fn get_node_priority(env: Env, publisher: Address) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        match env
+            .storage()
+            .persistent()
+            .get::<DataKey, NetworkNode>(&DataKey::Node(publisher))
+        {
+            Some(node) if node.is_active => Self::_node_rank(&node.node_type),
+            _ => 0,
+        }
+    }
+
+    pub fn is_priority_node(env: Env, publisher: Address) -> bool {
+        Self::get_node_priority(env, publisher) > 0
+    }
+
+    /// Seconds a node may go without a heartbeat before it counts as stale (0 disables)
+    pub fn set_liveness_window(env: Env, admin: Address, window: u64) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::LivenessWindow, &window);
+        Ok(())
+    }
+
+    pub fn get_liveness_window(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::LivenessWindow)
+            .unwrap_or(0)
+    }
+
+    /// Deactivate any listed nodes whose heartbeat is older than the liveness
+    /// window. Callable by anyone; returns how many nodes were swept.
+    pub fn mark_stale(env: Env, publishers: Vec<Address>) -> Result<u32, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let window: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::LivenessWindow)
+            .unwrap_or(0);
+        if window == 0 {
+            return Err(PulsarError::NotInitialized);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut swept: u32 = 0;
+        for publisher in publishers.iter() {
+            let Some(node) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, NetworkNode>(&DataKey::Node(publisher.clone()))
+            else {
+                continue;
+            };
+            if node.is_active && now.saturating_sub(node.last_heartbeat) > window {
+                Self::_deactivate_node(&env, publisher.clone())?;
+                swept += 1;
+                pulsar_common_admin::publish_event(
+                    &env,
+                    symbol_short!("network"),
+                    symbol_short!("stale"),
+                    publisher,
+                );
+            }
+        }
+        Ok(swept)
+    }
+
+    /// True if the node is active and has sent a heartbeat within the liveness window
+    pub fn is_live(env: Env, publisher: Address) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let Some(node) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, NetworkNode>(&DataKey::Node(publisher))
+        else {
+            return false;
+        };
+        let window: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::LivenessWindow)
+            .unwrap_or(0);
+        node.is_active
+            && (window == 0
+                || env.ledger().timestamp().saturating_sub(node.last_heartbeat) <= window)
+    }
+
+    pub fn deactivate(env: Env, publisher: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        publisher.require_auth();
+
+        Self::_deactivate_node(&env, publisher)
+    }
+
+    pub fn set_fraud_contract(
+        env: Env,
+        admin: Address,
+        fraud_contract: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
         }
         env.storage()
             .instance()
             .set(&DataKey::FraudContract, &fraud_contract);
+        Ok(())
+    }
+
+    /// Require a verified Publisher identity to join; unset leaves joining open
+    pub fn set_identity_registry(
+        env: Env,
+        admin: Address,
+        registry: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::IdentityRegistry, &registry);
+        Ok(())
+    }
+
+    pub fn set_staking_config(
+        env: Env,
+        admin: Address,
+        token: Address,
+        stake_per_capacity: i128,
+        unstake_cooldown: u64,
+        slash_bps: u32,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        if stake_per_capacity < 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        if slash_bps > 10_000 {
+            return Err(PulsarError::InvalidInput);
+        }
+        env.storage().instance().set(
+            &DataKey::StakingConfig,
+            &StakingConfig {
+                token,
+                stake_per_capacity,
+                unstake_cooldown,
+                slash_bps,
+            },
+        );
+        Ok(())
     }
 
-    pub fn suspend_publisher(env: Env, fraud_contract: Address, publisher: Address) {
+    pub fn get_staking_config(env: Env) -> Option<StakingConfig> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().get(&DataKey::StakingConfig)
+    }
+
+    /// Withdraw a deactivated node's stake once the cooldown has elapsed
+    pub fn unstake(env: Env, publisher: Address) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        publisher.require_auth();
+
+        let mut node: NetworkNode = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Node(publisher.clone()))
+            .ok_or(PulsarError::NotFound)?;
+
+        if node.is_active {
+            return Err(PulsarError::InvalidState);
+        }
+        if node.stake <= 0 {
+            return Err(PulsarError::InsufficientBalance);
+        }
+
+        let config: StakingConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::StakingConfig)
+            .ok_or(PulsarError::NotInitialized)?;
+        let deactivated_at = node.deactivated_at.unwrap_or(0);
+        if env.ledger().timestamp() < deactivated_at + config.unstake_cooldown {
+            return Err(PulsarError::TooEarly);
+        }
+
+        let amount = node.stake;
+        node.stake = 0;
+        let _ttl_key = DataKey::Node(publisher.clone());
+        env.storage().persistent().set(&_ttl_key, &node);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let token_client = token::Client::new(&env, &config.token);
+        token_client.transfer(&env.current_contract_address(), &publisher, &amount);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("network"),
+            symbol_short!("unstaked"),
+            (publisher, amount),
+        );
+
+        Ok(amount)
+    }
+
+    /// Sweep slashed stake to a destination of the admin's choosing
+    pub fn withdraw_slashed(env: Env, admin: Address, to: Address) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        let amount: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::SlashedFunds)
+            .unwrap_or(0);
+        if amount <= 0 {
+            return Err(PulsarError::InsufficientBalance);
+        }
+        let config: StakingConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::StakingConfig)
+            .ok_or(PulsarError::NotInitialized)?;
+        env.storage().instance().set(&DataKey::SlashedFunds, &0i128);
+
+        let token_client = token::Client::new(&env, &config.token);
+        token_client.transfer(&env.current_contract_address(), &to, &amount);
+        Ok(amount)
+    }
+
+    pub fn suspend_publisher(
+        env: Env,
+        fraud_contract: Address,
+        publisher: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -201,26 +920,137 @@ impl PublisherNetworkContract {
             .storage()
             .instance()
             .get(&DataKey::FraudContract)
-            .expect("fraud contract not set");
+            .ok_or(PulsarError::NotInitialized)?;
         if fraud_contract != stored_fraud {
-            panic!("unauthorized fraud contract");
+            return Err(PulsarError::Unauthorized);
+        }
+
+        Self::_deactivate_node(&env, publisher.clone())?;
+        let mut node: NetworkNode = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Node(publisher.clone()))
+            .unwrap();
+        node.is_suspended = true;
+        let _ttl_key = DataKey::Node(publisher.clone());
+        env.storage().persistent().set(&_ttl_key, &node);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Self::_slash_stake(&env, &publisher);
+        Ok(())
+    }
+
+    fn _activate_node(env: &Env, node: &mut NetworkNode) -> Result<(), PulsarError> {
+        Self::_lock_required_stake(env, node)?;
+        node.is_active = true;
+        node.deactivated_at = None;
+        node.last_heartbeat = env.ledger().timestamp();
+
+        let _ttl_key = DataKey::Node(node.publisher.clone());
+        env.storage().persistent().set(&_ttl_key, node);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let mut stats: NetworkStats = env
+            .storage()
+            .instance()
+            .get(&DataKey::NetworkStats)
+            .unwrap();
+        stats.active_nodes += 1;
+        stats.last_updated = env.ledger().timestamp();
+        env.storage().instance().set(&DataKey::NetworkStats, &stats);
+
+        let mut zone_stats = Self::_load_zone_stats(env, &node.geographic_zone);
+        zone_stats.active_nodes += 1;
+        Self::_save_zone_stats(env, &node.geographic_zone, &mut zone_stats);
+        Ok(())
+    }
+
+    /// Pull whatever stake the node is short of for its declared capacity
+    fn _lock_required_stake(env: &Env, node: &mut NetworkNode) -> Result<(), PulsarError> {
+        let Some(config) = env
+            .storage()
+            .instance()
+            .get::<DataKey, StakingConfig>(&DataKey::StakingConfig)
+        else {
+            return Ok(());
+        };
+        let required = config
+            .stake_per_capacity
+            .checked_mul(node.capacity as i128)
+            .ok_or(PulsarError::LimitExceeded)?;
+        let shortfall = required - node.stake;
+        if shortfall > 0 {
+            let token_client = token::Client::new(env, &config.token);
+            token_client.transfer(&node.publisher, &env.current_contract_address(), &shortfall);
+            node.stake += shortfall;
+        }
+        Ok(())
+    }
+
+    fn _slash_stake(env: &Env, publisher: &Address) {
+        let Some(config) = env
+            .storage()
+            .instance()
+            .get::<DataKey, StakingConfig>(&DataKey::StakingConfig)
+        else {
+            return;
+        };
+        let mut node: NetworkNode = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Node(publisher.clone()))
+            .unwrap();
+        let slashed = node.stake * config.slash_bps as i128 / 10_000;
+        if slashed <= 0 {
+            return;
         }
 
-        Self::_deactivate_node(&env, publisher);
+        node.stake -= slashed;
+        let _ttl_key = DataKey::Node(publisher.clone());
+        env.storage().persistent().set(&_ttl_key, &node);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let total: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::SlashedFunds)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::SlashedFunds, &(total + slashed));
+
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("network"),
+            symbol_short!("slashed"),
+            (publisher.clone(), slashed),
+        );
     }
 
-    fn _deactivate_node(env: &Env, publisher: Address) {
+    fn _deactivate_node(env: &Env, publisher: Address) -> Result<(), PulsarError> {
         let mut node: NetworkNode = env
             .storage()
             .persistent()
             .get(&DataKey::Node(publisher.clone()))
-            .expect("not in network");
+            .ok_or(PulsarError::NotFound)?;
 
         if !node.is_active {
-            return;
+            return Ok(());
         }
 
         node.is_active = false;
+        node.deactivated_at = Some(env.ledger().timestamp());
         let _ttl_key = DataKey::Node(publisher);
         env.storage().persistent().set(&_ttl_key, &node);
         env.storage().persistent().extend_ttl(
@@ -238,20 +1068,179 @@ impl PublisherNetworkContract {
             stats.active_nodes -= 1;
         }
         env.storage().instance().set(&DataKey::NetworkStats, &stats);
+
+        let mut zone_stats = Self::_load_zone_stats(env, &node.geographic_zone);
+        zone_stats.active_nodes = zone_stats.active_nodes.saturating_sub(1);
+        Self::_save_zone_stats(env, &node.geographic_zone, &mut zone_stats);
+        Ok(())
+    }
+
+    fn _node_rank(node_type: &NodeType) -> u32 {
+        match node_type {
+            NodeType::Standard => 0,
+            NodeType::Premium => 1,
+            NodeType::Enterprise => 2,
+        }
+    }
+
+    fn _load_zone_stats(env: &Env, zone: &String) -> NetworkStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ZoneStats(zone.clone()))
+            .unwrap_or(NetworkStats {
+                total_nodes: 0,
+                active_nodes: 0,
+                total_capacity: 0,
+                total_impressions_served: 0,
+                last_updated: 0,
+            })
+    }
+
+    fn _save_zone_stats(env: &Env, zone: &String, stats: &mut NetworkStats) {
+        stats.last_updated = env.ledger().timestamp();
+        let _ttl_key = DataKey::ZoneStats(zone.clone());
+        env.storage().persistent().set(&_ttl_key, stats);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
     }
 
-    pub fn record_impression(env: Env, _publisher: Address) {
+    pub fn set_orchestrator(
+        env: Env,
+        admin: Address,
+        orchestrator: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Orchestrator, &orchestrator);
+        Ok(())
+    }
+
+    /// Count one impression against the publisher's daily capacity (orchestrator only)
+    pub fn record_impression(
+        env: Env,
+        caller: Address,
+        publisher: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_orchestrator(&env, &caller)?;
+        Self::_record_impressions(&env, publisher, 1)
+    }
+
+    /// Batched `record_impression` for the orchestrator's `record_views_batch`
+    pub fn record_impressions(
+        env: Env,
+        caller: Address,
+        publisher: Address,
+        count: u64,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_orchestrator(&env, &caller)?;
+        if count == 0 {
+            return Err(PulsarError::InvalidInput);
+        }
+        Self::_record_impressions(&env, publisher, count)
+    }
+
+    fn _require_orchestrator(env: &Env, caller: &Address) -> Result<(), PulsarError> {
+        caller.require_auth();
+        let orchestrator: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Orchestrator)
+            .ok_or(PulsarError::NotInitialized)?;
+        if *caller != orchestrator {
+            return Err(PulsarError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn _record_impressions(env: &Env, publisher: Address, count: u64) -> Result<(), PulsarError> {
+        let node: NetworkNode = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Node(publisher.clone()))
+            .ok_or(PulsarError::NotFound)?;
+        if !node.is_active {
+            return Err(PulsarError::InvalidState);
+        }
+
+        let day = env.ledger().timestamp() / 86_400;
+        let _ttl_key = DataKey::NodeImpressions(publisher, day);
+        let served: u64 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+        if served + count > node.capacity {
+            return Err(PulsarError::LimitExceeded);
+        }
+        env.storage().persistent().set(&_ttl_key, &(served + count));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
         let mut stats: NetworkStats = env
             .storage()
             .instance()
             .get(&DataKey::NetworkStats)
             .unwrap();
-        stats.total_impressions_served += 1;
+        stats.total_impressions_served += count;
         stats.last_updated = env.ledger().timestamp();
         env.storage().instance().set(&DataKey::NetworkStats, &stats);
+
+        let mut zone_stats = Self::_load_zone_stats(env, &node.geographic_zone);
+        zone_stats.total_impressions_served += count;
+        Self::_save_zone_stats(env, &node.geographic_zone, &mut zone_stats);
+        Ok(())
+    }
+
+    /// `day` is the ledger timestamp divided by 86_400
+    pub fn get_node_utilization(
+        env: Env,
+        publisher: Address,
+        day: u64,
+    ) -> Result<NodeUtilization, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let node: NetworkNode = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Node(publisher.clone()))
+            .ok_or(PulsarError::NotFound)?;
+        let impressions: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::NodeImpressions(publisher, day))
+            .unwrap_or(0);
+        let utilization_bps = impressions
+            .saturating_mul(10_000)
+            .checked_div(node.capacity)
+            .unwrap_or(0)
+            .min(10_000) as u32;
+        Ok(NodeUtilization {
+            day,
+            impressions,
+            capacity: node.capacity,
+            utilization_bps,
+        })
     }
 
     pub fn get_node(env: Env, publisher: Address) -> Option<NetworkNode> {
@@ -261,6 +1250,14 @@ impl PublisherNetworkContract {
         env.storage().persistent().get(&DataKey::Node(publisher))
     }
 
+    /// Aggregates for nodes declaring `zone` as their geographic zone
+    pub fn get_zone_stats(env: Env, zone: String) -> NetworkStats {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_load_zone_stats(&env, &zone)
+    }
+
     pub fn get_network_stats(env: Env) -> NetworkStats {
         env.storage()
             .instance()
@@ -287,18 +1284,30 @@ impl PublisherNetworkContract {
             .unwrap_or(0)
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
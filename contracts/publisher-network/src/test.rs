@@ -1,10 +1,70 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env, String,
+};
+
+mod identity_mock {
+    use super::IdentityType;
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockIdentityRegistry;
+
+    #[contractimpl]
+    impl MockIdentityRegistry {
+        pub fn set_verified(env: Env, account: Address) {
+            env.storage().persistent().set(&account, &true);
+        }
+
+        pub fn require_verified(env: Env, account: Address, identity_type: IdentityType) -> bool {
+            matches!(identity_type, IdentityType::Publisher)
+                && env.storage().persistent().get(&account).unwrap_or(false)
+        }
+    }
+}
+
+mod fraud_mock {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockFraudPrevention;
+
+    #[contractimpl]
+    impl MockFraudPrevention {
+        pub fn set_suspended(env: Env, publisher: Address, suspended: bool) {
+            env.storage().persistent().set(&publisher, &suspended);
+        }
+
+        pub fn is_publisher_suspended(env: Env, publisher: Address) -> bool {
+            env.storage().persistent().get(&publisher).unwrap_or(false)
+        }
+    }
+}
+
+mod reputation_mock {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockReputation;
+
+    #[contractimpl]
+    impl MockReputation {
+        pub fn set_score(env: Env, publisher: Address, score: u32) {
+            env.storage().persistent().set(&publisher, &score);
+        }
+
+        pub fn get_score(env: Env, publisher: Address) -> u32 {
+            env.storage().persistent().get(&publisher).unwrap_or(0)
+        }
+    }
+}
 
 fn setup(env: &Env) -> (PublisherNetworkContractClient<'_>, Address) {
     let admin = Address::generate(env);
-    let id = env.register_contract(None, PublisherNetworkContract);
+    let id = env.register(PublisherNetworkContract, ());
     let c = PublisherNetworkContractClient::new(env, &id);
     c.initialize(&admin);
     (c, admin)
@@ -17,7 +77,7 @@ fn s(env: &Env, v: &str) -> String {
 fn test_initialize() {
     let env = Env::default();
     env.mock_all_auths();
-    let id = env.register_contract(None, PublisherNetworkContract);
+    let id = env.register(PublisherNetworkContract, ());
     let c = PublisherNetworkContractClient::new(&env, &id);
     c.initialize(&Address::generate(&env));
     assert_eq!(c.get_node_count(), 0);
@@ -26,22 +86,24 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
-    let id = env.register_contract(None, PublisherNetworkContract);
+    let id = env.register(PublisherNetworkContract, ());
     let c = PublisherNetworkContractClient::new(&env, &id);
     let a = Address::generate(&env);
     c.initialize(&a);
-    c.initialize(&a);
+    assert_eq!(
+        c.try_initialize(&a),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
 #[should_panic]
 fn test_initialize_non_admin_fails() {
     let env = Env::default();
-    let id = env.register_contract(None, PublisherNetworkContract);
+    let id = env.register(PublisherNetworkContract, ());
     let c = PublisherNetworkContractClient::new(&env, &id);
     c.initialize(&Address::generate(&env));
 }
@@ -72,7 +134,6 @@ fn test_join_network() {
 }
 
 #[test]
-#[should_panic(expected = "already in network")]
 fn test_join_network_duplicate() {
     let env = Env::default();
     env.mock_all_auths();
@@ -87,7 +148,7 @@ fn test_join_network_duplicate() {
         &s(&env, "US-East"),
         &cats,
     );
-    c.join_network(
+    let result = c.try_join_network(
         &pub1,
         &NodeType::Premium,
         &20_000u64,
@@ -95,6 +156,7 @@ fn test_join_network_duplicate() {
         &s(&env, "EU-West"),
         &cats,
     );
+    assert_eq!(result, Err(Ok(PulsarError::AlreadyExists)));
 }
 
 #[test]
@@ -116,12 +178,14 @@ fn test_heartbeat() {
 }
 
 #[test]
-#[should_panic(expected = "not in network")]
 fn test_heartbeat_not_in_network() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, _) = setup(&env);
-    c.heartbeat(&Address::generate(&env));
+    assert_eq!(
+        c.try_heartbeat(&Address::generate(&env)),
+        Err(Ok(PulsarError::NotFound))
+    );
 }
 
 #[test]
@@ -169,7 +233,6 @@ fn test_suspend_publisher() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized fraud contract")]
 fn test_suspend_publisher_wrong_fraud() {
     let env = Env::default();
     env.mock_all_auths();
@@ -186,27 +249,104 @@ fn test_suspend_publisher_wrong_fraud() {
         &s(&env, "US"),
         &cats,
     );
-    c.suspend_publisher(&Address::generate(&env), &pub1);
+    assert_eq!(
+        c.try_suspend_publisher(&Address::generate(&env), &pub1),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
 fn test_record_impression() {
     let env = Env::default();
     env.mock_all_auths();
-    let (c, _) = setup(&env);
+    let (c, admin) = setup(&env);
+    let orchestrator = Address::generate(&env);
+    c.set_orchestrator(&admin, &orchestrator);
     let pub1 = Address::generate(&env);
     let cats = vec![&env, s(&env, "tech")];
     c.join_network(
         &pub1,
         &NodeType::Standard,
-        &10_000u64,
+        &4u64,
         &100i128,
         &s(&env, "US"),
         &cats,
     );
-    c.record_impression(&pub1);
+    c.record_impression(&orchestrator, &pub1);
     let stats = c.get_network_stats();
     assert_eq!(stats.total_impressions_served, 1);
+
+    let day = env.ledger().timestamp() / 86_400;
+    let util = c.get_node_utilization(&pub1, &day);
+    assert_eq!(util.impressions, 1);
+    assert_eq!(util.capacity, 4);
+    assert_eq!(util.utilization_bps, 2_500);
+    assert_eq!(c.get_node_utilization(&pub1, &(day + 1)).impressions, 0);
+}
+
+#[test]
+fn test_record_impression_over_capacity() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let orchestrator = Address::generate(&env);
+    c.set_orchestrator(&admin, &orchestrator);
+    let pub1 = Address::generate(&env);
+    c.join_network(
+        &pub1,
+        &NodeType::Standard,
+        &1u64,
+        &100i128,
+        &s(&env, "US"),
+        &vec![&env, s(&env, "tech")],
+    );
+    c.record_impression(&orchestrator, &pub1);
+    assert_eq!(
+        c.try_record_impression(&orchestrator, &pub1),
+        Err(Ok(PulsarError::LimitExceeded))
+    );
+}
+
+#[test]
+fn test_record_impressions_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let orchestrator = Address::generate(&env);
+    c.set_orchestrator(&admin, &orchestrator);
+    let pub1 = Address::generate(&env);
+    c.join_network(
+        &pub1,
+        &NodeType::Standard,
+        &10u64,
+        &100i128,
+        &s(&env, "US"),
+        &vec![&env, s(&env, "tech")],
+    );
+    c.record_impressions(&orchestrator, &pub1, &7u64);
+    assert_eq!(c.get_network_stats().total_impressions_served, 7);
+
+    // The batch may fill the node but not overflow it
+    assert!(c
+        .try_record_impressions(&orchestrator, &pub1, &4u64)
+        .is_err());
+    c.record_impressions(&orchestrator, &pub1, &3u64);
+    let day = env.ledger().timestamp() / 86_400;
+    assert_eq!(c.get_node_utilization(&pub1, &day).impressions, 10);
+}
+
+#[test]
+fn test_record_impression_not_orchestrator() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    c.set_orchestrator(&admin, &Address::generate(&env));
+    let pub1 = Address::generate(&env);
+    join(&env, &c, &pub1);
+    assert_eq!(
+        c.try_record_impression(&Address::generate(&env), &pub1),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
@@ -218,10 +358,433 @@ fn test_get_node_nonexistent() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_set_fraud_contract_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, _) = setup(&env);
-    c.set_fraud_contract(&Address::generate(&env), &Address::generate(&env));
+    assert_eq!(
+        c.try_set_fraud_contract(&Address::generate(&env), &Address::generate(&env)),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+fn join(env: &Env, c: &PublisherNetworkContractClient<'_>, publisher: &Address) {
+    c.join_network(
+        publisher,
+        &NodeType::Standard,
+        &10_000u64,
+        &100i128,
+        &s(env, "us-east"),
+        &vec![env, s(env, "tech")],
+    );
+}
+
+#[test]
+fn test_join_network_with_verified_identity() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let registry = env.register(identity_mock::MockIdentityRegistry, ());
+    c.set_identity_registry(&admin, &registry);
+    let pub1 = Address::generate(&env);
+    identity_mock::MockIdentityRegistryClient::new(&env, &registry).set_verified(&pub1);
+    join(&env, &c, &pub1);
+    assert_eq!(c.get_node_count(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #13)")]
+fn test_join_network_unverified_identity() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let registry = env.register(identity_mock::MockIdentityRegistry, ());
+    c.set_identity_registry(&admin, &registry);
+    join(&env, &c, &Address::generate(&env));
+}
+
+fn setup_staking(env: &Env, c: &PublisherNetworkContractClient<'_>, admin: &Address) -> Address {
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    c.set_staking_config(admin, &token, &2i128, &1_000u64, &5_000u32);
+    token
+}
+
+#[test]
+fn test_join_network_locks_stake() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let token = setup_staking(&env, &c, &admin);
+    let pub1 = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&pub1, &50_000);
+    join(&env, &c, &pub1);
+
+    assert_eq!(c.get_node(&pub1).unwrap().stake, 20_000);
+    assert_eq!(TokenClient::new(&env, &token).balance(&pub1), 30_000);
+}
+
+#[test]
+fn test_unstake_after_cooldown() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let token = setup_staking(&env, &c, &admin);
+    let pub1 = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&pub1, &20_000);
+    join(&env, &c, &pub1);
+    c.deactivate(&pub1);
+    env.ledger().with_mut(|li| li.timestamp += 1_000);
+
+    assert_eq!(c.unstake(&pub1), 20_000);
+    assert_eq!(TokenClient::new(&env, &token).balance(&pub1), 20_000);
+    assert_eq!(c.get_node(&pub1).unwrap().stake, 0);
+}
+
+#[test]
+fn test_unstake_during_cooldown() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let token = setup_staking(&env, &c, &admin);
+    let pub1 = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&pub1, &20_000);
+    join(&env, &c, &pub1);
+    c.deactivate(&pub1);
+    env.ledger().with_mut(|li| li.timestamp += 999);
+    assert_eq!(c.try_unstake(&pub1), Err(Ok(PulsarError::TooEarly)));
+}
+
+#[test]
+fn test_fraud_suspension_slashes_stake() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let token = setup_staking(&env, &c, &admin);
+    let fraud = Address::generate(&env);
+    c.set_fraud_contract(&admin, &fraud);
+    let pub1 = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&pub1, &20_000);
+    join(&env, &c, &pub1);
+    c.suspend_publisher(&fraud, &pub1);
+
+    assert_eq!(c.get_node(&pub1).unwrap().stake, 10_000);
+    let treasury = Address::generate(&env);
+    assert_eq!(c.withdraw_slashed(&admin, &treasury), 10_000);
+    assert_eq!(TokenClient::new(&env, &token).balance(&treasury), 10_000);
+}
+
+#[test]
+fn test_update_node_adjusts_capacity() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let pub1 = Address::generate(&env);
+    join(&env, &c, &pub1);
+    c.update_node(
+        &pub1,
+        &4_000u64,
+        &150i128,
+        &s(&env, "eu-west"),
+        &vec![&env, s(&env, "sports")],
+    );
+
+    let node = c.get_node(&pub1).unwrap();
+    assert_eq!(node.capacity, 4_000);
+    assert_eq!(node.min_cpm, 150);
+    assert_eq!(node.geographic_zone, s(&env, "eu-west"));
+    assert_eq!(c.get_network_stats().total_capacity, 4_000);
+}
+
+#[test]
+fn test_update_node_tops_up_stake() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let token = setup_staking(&env, &c, &admin);
+    let pub1 = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&pub1, &50_000);
+    join(&env, &c, &pub1);
+    c.update_node(
+        &pub1,
+        &15_000u64,
+        &100i128,
+        &s(&env, "us-east"),
+        &vec![&env, s(&env, "tech")],
+    );
+    assert_eq!(c.get_node(&pub1).unwrap().stake, 30_000);
+    assert_eq!(TokenClient::new(&env, &token).balance(&pub1), 20_000);
+}
+
+#[test]
+fn test_reactivate_after_deactivate() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let pub1 = Address::generate(&env);
+    join(&env, &c, &pub1);
+    c.deactivate(&pub1);
+    c.reactivate(&pub1);
+    let node = c.get_node(&pub1).unwrap();
+    assert!(node.is_active);
+    assert!(node.deactivated_at.is_none());
+    assert_eq!(c.get_network_stats().active_nodes, 1);
+}
+
+#[test]
+fn test_reactivate_suspended_node_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let fraud = Address::generate(&env);
+    c.set_fraud_contract(&admin, &fraud);
+    let pub1 = Address::generate(&env);
+    join(&env, &c, &pub1);
+    c.suspend_publisher(&fraud, &pub1);
+    assert_eq!(c.try_reactivate(&pub1), Err(Ok(PulsarError::InvalidState)));
+}
+
+#[test]
+fn test_mark_stale_sweeps_silent_nodes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    c.set_liveness_window(&admin, &600u64);
+    let silent = Address::generate(&env);
+    let chatty = Address::generate(&env);
+    join(&env, &c, &silent);
+    join(&env, &c, &chatty);
+
+    env.ledger().with_mut(|li| li.timestamp += 500);
+    c.heartbeat(&chatty);
+    env.ledger().with_mut(|li| li.timestamp += 200);
+    assert!(!c.is_live(&silent));
+    assert!(c.is_live(&chatty));
+
+    let swept = c.mark_stale(&vec![&env, silent.clone(), chatty.clone()]);
+    assert_eq!(swept, 1);
+    assert!(!c.get_node(&silent).unwrap().is_active);
+    assert!(c.get_node(&chatty).unwrap().is_active);
+    assert_eq!(c.get_network_stats().active_nodes, 1);
+}
+
+#[test]
+fn test_mark_stale_without_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    assert_eq!(
+        c.try_mark_stale(&vec![&env, Address::generate(&env)]),
+        Err(Ok(PulsarError::NotInitialized))
+    );
+}
+
+#[test]
+fn test_reinstatement_by_fraud_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let token = setup_staking(&env, &c, &admin);
+    let fraud = Address::generate(&env);
+    c.set_fraud_contract(&admin, &fraud);
+    let pub1 = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&pub1, &30_000);
+    join(&env, &c, &pub1);
+    c.suspend_publisher(&fraud, &pub1);
+    assert_eq!(c.get_node(&pub1).unwrap().stake, 10_000);
+
+    c.request_reinstatement(&pub1);
+    assert_eq!(c.get_node(&pub1).unwrap().stake, 20_000);
+    c.approve_reinstatement(&fraud, &pub1);
+
+    let node = c.get_node(&pub1).unwrap();
+    assert!(node.is_active);
+    assert!(!node.is_suspended);
+    assert_eq!(c.get_network_stats().active_nodes, 1);
+}
+
+#[test]
+fn test_admin_reinstatement_requires_cleared_flag() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let fraud = env.register(fraud_mock::MockFraudPrevention, ());
+    c.set_fraud_contract(&admin, &fraud);
+    let pub1 = Address::generate(&env);
+    join(&env, &c, &pub1);
+    c.suspend_publisher(&fraud, &pub1);
+    fraud_mock::MockFraudPreventionClient::new(&env, &fraud).set_suspended(&pub1, &true);
+    c.request_reinstatement(&pub1);
+    assert_eq!(
+        c.try_approve_reinstatement(&admin, &pub1),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_admin_reinstatement_after_flag_cleared() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let fraud = env.register(fraud_mock::MockFraudPrevention, ());
+    c.set_fraud_contract(&admin, &fraud);
+    let pub1 = Address::generate(&env);
+    join(&env, &c, &pub1);
+    c.suspend_publisher(&fraud, &pub1);
+    c.request_reinstatement(&pub1);
+    c.approve_reinstatement(&admin, &pub1);
+    assert!(c.get_node(&pub1).unwrap().is_active);
+}
+
+#[test]
+fn test_reinstatement_without_request() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let fraud = Address::generate(&env);
+    c.set_fraud_contract(&admin, &fraud);
+    let pub1 = Address::generate(&env);
+    join(&env, &c, &pub1);
+    c.suspend_publisher(&fraud, &pub1);
+    assert_eq!(
+        c.try_approve_reinstatement(&fraud, &pub1),
+        Err(Ok(PulsarError::NotFound))
+    );
+}
+
+#[test]
+fn test_zone_stats_follow_node_lifecycle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let orchestrator = Address::generate(&env);
+    c.set_orchestrator(&admin, &orchestrator);
+    let pub1 = Address::generate(&env);
+    let pub2 = Address::generate(&env);
+    join(&env, &c, &pub1);
+    join(&env, &c, &pub2);
+    c.record_impression(&orchestrator, &pub1);
+
+    let east = c.get_zone_stats(&s(&env, "us-east"));
+    assert_eq!(east.total_nodes, 2);
+    assert_eq!(east.active_nodes, 2);
+    assert_eq!(east.total_capacity, 20_000);
+    assert_eq!(east.total_impressions_served, 1);
+
+    c.deactivate(&pub2);
+    c.update_node(
+        &pub2,
+        &5_000u64,
+        &100i128,
+        &s(&env, "eu-west"),
+        &vec![&env, s(&env, "tech")],
+    );
+    let east = c.get_zone_stats(&s(&env, "us-east"));
+    assert_eq!(east.total_nodes, 1);
+    assert_eq!(east.active_nodes, 1);
+    assert_eq!(east.total_capacity, 10_000);
+
+    c.reactivate(&pub2);
+    let west = c.get_zone_stats(&s(&env, "eu-west"));
+    assert_eq!(west.total_nodes, 1);
+    assert_eq!(west.active_nodes, 1);
+    assert_eq!(west.total_capacity, 5_000);
+    assert_eq!(c.get_zone_stats(&s(&env, "apac")).total_nodes, 0);
+}
+
+fn setup_premium_upgrade(
+    env: &Env,
+    c: &PublisherNetworkContractClient<'_>,
+    admin: &Address,
+) -> Address {
+    let reputation = env.register(reputation_mock::MockReputation, ());
+    c.set_reputation_contract(admin, &reputation);
+    c.set_upgrade_requirement(
+        admin,
+        &NodeType::Premium,
+        &UpgradeRequirement {
+            min_reputation: 700,
+            min_stake: 0,
+            min_tenure_days: 30,
+        },
+    );
+    reputation
+}
+
+#[test]
+fn test_upgrade_node_type() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let reputation = setup_premium_upgrade(&env, &c, &admin);
+    let pub1 = Address::generate(&env);
+    join(&env, &c, &pub1);
+    assert!(!c.is_priority_node(&pub1));
+
+    reputation_mock::MockReputationClient::new(&env, &reputation).set_score(&pub1, &750);
+    env.ledger().with_mut(|li| li.timestamp += 30 * 86_400);
+    c.upgrade_node_type(&pub1, &NodeType::Premium);
+
+    assert!(matches!(
+        c.get_node(&pub1).unwrap().node_type,
+        NodeType::Premium
+    ));
+    assert_eq!(c.get_node_priority(&pub1), 1);
+    assert!(c.is_priority_node(&pub1));
+    c.deactivate(&pub1);
+    assert!(!c.is_priority_node(&pub1));
+}
+
+#[test]
+fn test_upgrade_node_type_low_reputation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let reputation = setup_premium_upgrade(&env, &c, &admin);
+    let pub1 = Address::generate(&env);
+    join(&env, &c, &pub1);
+    reputation_mock::MockReputationClient::new(&env, &reputation).set_score(&pub1, &600);
+    env.ledger().with_mut(|li| li.timestamp += 30 * 86_400);
+    assert_eq!(
+        c.try_upgrade_node_type(&pub1, &NodeType::Premium),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_upgrade_node_type_too_new() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let reputation = setup_premium_upgrade(&env, &c, &admin);
+    let pub1 = Address::generate(&env);
+    join(&env, &c, &pub1);
+    reputation_mock::MockReputationClient::new(&env, &reputation).set_score(&pub1, &900);
+    assert_eq!(
+        c.try_upgrade_node_type(&pub1, &NodeType::Premium),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_upgrade_node_type_insufficient_stake() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    c.set_upgrade_requirement(
+        &admin,
+        &NodeType::Enterprise,
+        &UpgradeRequirement {
+            min_reputation: 0,
+            min_stake: 1_000,
+            min_tenure_days: 0,
+        },
+    );
+    let pub1 = Address::generate(&env);
+    join(&env, &c, &pub1);
+    assert_eq!(
+        c.try_upgrade_node_type(&pub1, &NodeType::Enterprise),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
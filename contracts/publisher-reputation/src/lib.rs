@@ -1,8 +1,25 @@
 //! PulsarTrack - Publisher Reputation (Soroban)
 //! On-chain reputation scoring system for publishers on Stellar.
+//!
+//! Reviews are logged as they arrive but only score once enough distinct
+//! reviewers have weighed in: a publisher's pending reviews are applied
+//! together when `ReviewQuorum::min_reviewers` of them fall within
+//! `window_secs`. Pending reviews older than the window lapse without scoring.
+//!
+//! Distinct addresses cost nothing, so on their own they are no defence
+//! against one advertiser reviewing through fresh keys. With the campaign
+//! orchestrator set, a review is only accepted from the advertiser of
+//! `campaign_id`, which makes each reviewer in the quorum a funded campaign.
+//! Whether that campaign actually ran with the reviewed publisher is not
+//! checked. Without an orchestrator any address may review, and the quorum
+//! can be met with Sybil addresses.
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, Address, Env, IntoVal, Symbol, Vec,
+};
+
+pub use pulsar_common_errors::PulsarError;
 
 #[contracttype]
 #[derive(Clone)]
@@ -29,15 +46,58 @@ pub struct ReviewEntry {
     pub timestamp: u64,
 }
 
+/// Mirror of campaign-orchestrator's `CampaignStatus`, decoded from `get_campaign`
+#[contracttype]
+#[derive(Clone)]
+pub enum CampaignStatus {
+    Active,
+    Paused,
+    Completed,
+    Cancelled,
+    Expired,
+}
+
+/// Mirror of campaign-orchestrator's `Campaign`, decoded from `get_campaign`
+#[contracttype]
+#[derive(Clone)]
+pub struct Campaign {
+    pub advertiser: Address,
+    pub campaign_type: u32,
+    pub budget: i128,
+    pub remaining_budget: i128,
+    pub cost_per_view: i128,
+    pub start_ledger: u32,
+    pub end_ledger: u32,
+    pub status: CampaignStatus,
+    pub target_views: u64,
+    pub current_views: u64,
+    pub daily_view_limit: u64,
+    pub refundable: bool,
+    pub platform_fee: i128,
+    pub created_at: u64,
+    pub last_updated: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ReviewQuorum {
+    pub min_reviewers: u32,
+    pub window_secs: u64,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
     Admin,
     PendingAdmin,
     ReputationOracle,
+    OracleRegistry,
     Reputation(Address),
     Review(Address, u64), // publisher, review_index
     ReviewCount(Address),
+    ReviewQuorum,
+    PendingReviews(Address), // publisher -> reviews awaiting quorum
+    CampaignOrchestrator,
 }
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
@@ -45,26 +105,31 @@ const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
 const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
 
+const DEFAULT_MIN_REVIEWERS: u32 = 3;
+const DEFAULT_REVIEW_WINDOW: u64 = 7 * 86_400;
+const MAX_MIN_REVIEWERS: u32 = 20;
+
 #[contract]
 pub struct PublisherReputationContract;
 
 #[contractimpl]
 impl PublisherReputationContract {
-    pub fn initialize(env: Env, admin: Address, oracle: Address) {
+    pub fn initialize(env: Env, admin: Address, oracle: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage()
             .instance()
             .set(&DataKey::ReputationOracle, &oracle);
+        Ok(())
     }
 
-    pub fn init_publisher(env: Env, publisher: Address) {
+    pub fn init_publisher(env: Env, publisher: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -73,7 +138,7 @@ impl PublisherReputationContract {
             .persistent()
             .has(&DataKey::Reputation(publisher.clone()))
         {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
 
         let score = ReputationScore {
@@ -96,6 +161,7 @@ impl PublisherReputationContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     pub fn submit_review(
@@ -105,28 +171,42 @@ impl PublisherReputationContract {
         campaign_id: u64,
         positive: bool,
         rating: u32,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         advertiser.require_auth();
 
-        if rating < 1 || rating > 5 {
-            panic!("invalid rating");
+        if !(1..=5).contains(&rating) {
+            return Err(PulsarError::InvalidInput);
         }
+        Self::_require_campaign_advertiser(&env, &advertiser, campaign_id)?;
 
         let mut rep: ReputationScore = env
             .storage()
             .persistent()
             .get(&DataKey::Reputation(publisher.clone()))
-            .expect("publisher not registered");
+            .ok_or(PulsarError::NotFound)?;
+
+        let now = env.ledger().timestamp();
+        let quorum = Self::_review_quorum(&env);
+        let mut pending = Vec::new(&env);
+        for entry in Self::_pending_reviews(&env, &publisher).iter() {
+            if entry.timestamp + quorum.window_secs <= now {
+                continue; // lapsed without reaching quorum
+            }
+            if entry.reviewer == advertiser {
+                return Err(PulsarError::AlreadyExists);
+            }
+            pending.push_back(entry);
+        }
 
         let review = ReviewEntry {
             reviewer: advertiser,
             campaign_id,
             positive,
             rating,
-            timestamp: env.ledger().timestamp(),
+            timestamp: now,
         };
 
         let count: u64 = env
@@ -149,50 +229,120 @@ impl PublisherReputationContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        rep.total_reviews += 1;
-        if positive {
-            rep.positive_reviews += 1;
-            // Increase score (max 1000)
-            rep.score = (rep.score + rating as u32 * 2).min(1000);
-        } else {
-            rep.negative_reviews += 1;
-            // Decrease score (min 0)
-            rep.score = rep.score.saturating_sub(rating as u32 * 3);
+        // Every pending review is from a distinct reviewer
+        pending.push_back(review);
+        let pending_key = DataKey::PendingReviews(publisher.clone());
+        if pending.len() < quorum.min_reviewers {
+            env.storage().persistent().set(&pending_key, &pending);
+            env.storage().persistent().extend_ttl(
+                &pending_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+            return Ok(());
         }
-        rep.last_updated = env.ledger().timestamp();
 
-        let _ttl_key = DataKey::Reputation(publisher);
+        for entry in pending.iter() {
+            rep.total_reviews += 1;
+            if entry.positive {
+                rep.positive_reviews += 1;
+                // Increase score (max 1000)
+                rep.score = (rep.score + entry.rating * 2).min(1000);
+            } else {
+                rep.negative_reviews += 1;
+                // Decrease score (min 0)
+                rep.score = rep.score.saturating_sub(entry.rating * 3);
+            }
+        }
+        rep.last_updated = now;
+        env.storage().persistent().remove(&pending_key);
+
+        let _ttl_key = DataKey::Reputation(publisher.clone());
         env.storage().persistent().set(&_ttl_key, &rep);
         env.storage().persistent().extend_ttl(
             &_ttl_key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("reviews"),
+            symbol_short!("applied"),
+            (publisher, pending.len(), rep.score),
+        );
+        Ok(())
     }
 
-    pub fn slash_publisher(env: Env, oracle: Address, publisher: Address, penalty: u32) {
+    /// Admin: how many distinct reviewers within `window_secs` it takes for
+    /// pending reviews to affect a score. 1 applies every review at once.
+    pub fn set_review_quorum(
+        env: Env,
+        admin: Address,
+        min_reviewers: u32,
+        window_secs: u64,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        oracle.require_auth();
-        let stored_oracle: Address = env
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey::ReputationOracle)
-            .unwrap();
-        if oracle != stored_oracle {
-            panic!("unauthorized");
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        if min_reviewers == 0 || min_reviewers > MAX_MIN_REVIEWERS || window_secs == 0 {
+            return Err(PulsarError::InvalidInput);
         }
+        env.storage().instance().set(
+            &DataKey::ReviewQuorum,
+            &ReviewQuorum {
+                min_reviewers,
+                window_secs,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn get_review_quorum(env: Env) -> ReviewQuorum {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_review_quorum(&env)
+    }
+
+    /// Reviews that have not scored yet, including lapsed ones until the
+    /// next review for the publisher clears them
+    pub fn get_pending_reviews(env: Env, publisher: Address) -> Vec<ReviewEntry> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_pending_reviews(&env, &publisher)
+    }
+
+    pub fn slash_publisher(
+        env: Env,
+        oracle: Address,
+        publisher: Address,
+        penalty: u32,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_oracle(&env, &oracle)?;
 
         let mut rep: ReputationScore = env
             .storage()
             .persistent()
             .get(&DataKey::Reputation(publisher.clone()))
-            .expect("publisher not registered");
+            .ok_or(PulsarError::NotFound)?;
 
         let current_ledger = env.ledger().sequence();
         if current_ledger <= rep.last_slash_ledger + 100 {
-            panic!("slash cooldown active");
+            return Err(PulsarError::TooEarly);
         }
 
         let penalty = penalty.min(100);
@@ -210,35 +360,35 @@ impl PublisherReputationContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("publisher"), symbol_short!("slashed")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("publisher"),
+            symbol_short!("slashed"),
             (publisher, penalty),
         );
+        Ok(())
     }
 
-    pub fn update_uptime(env: Env, oracle: Address, publisher: Address, uptime: u32) {
+    pub fn update_uptime(
+        env: Env,
+        oracle: Address,
+        publisher: Address,
+        uptime: u32,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        oracle.require_auth();
-        let stored_oracle: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::ReputationOracle)
-            .unwrap();
-        if oracle != stored_oracle {
-            panic!("unauthorized");
-        }
+        Self::_require_oracle(&env, &oracle)?;
 
         if uptime > 100 {
-            panic!("invalid uptime");
+            return Err(PulsarError::InvalidInput);
         }
 
         let mut rep: ReputationScore = env
             .storage()
             .persistent()
             .get(&DataKey::Reputation(publisher.clone()))
-            .expect("publisher not registered");
+            .ok_or(PulsarError::NotFound)?;
 
         rep.uptime_score = uptime;
         if uptime < 90 {
@@ -257,6 +407,7 @@ impl PublisherReputationContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     pub fn get_reputation(env: Env, publisher: Address) -> Option<ReputationScore> {
@@ -268,6 +419,18 @@ impl PublisherReputationContract {
             .get(&DataKey::Reputation(publisher))
     }
 
+    /// Current 0-1000 score, or 0 for publishers without a reputation record
+    pub fn get_score(env: Env, publisher: Address) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get::<DataKey, ReputationScore>(&DataKey::Reputation(publisher))
+            .map(|rep| rep.score)
+            .unwrap_or(0)
+    }
+
     pub fn get_review(env: Env, publisher: Address, index: u64) -> Option<ReviewEntry> {
         env.storage()
             .instance()
@@ -287,18 +450,149 @@ impl PublisherReputationContract {
             .unwrap_or(0)
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    /// Once set, oracle checks go through the shared oracle registry
+    /// (domain `Reputation`) instead of the single stored oracle address
+    /// Only accept reviews from the advertiser of the reviewed campaign
+    pub fn set_campaign_orchestrator(
+        env: Env,
+        admin: Address,
+        orchestrator: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::CampaignOrchestrator, &orchestrator);
+        Ok(())
+    }
+
+    pub fn set_oracle_registry(
+        env: Env,
+        admin: Address,
+        registry: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::OracleRegistry, &registry);
+        Ok(())
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
+
+    fn _require_campaign_advertiser(
+        env: &Env,
+        reviewer: &Address,
+        campaign_id: u64,
+    ) -> Result<(), PulsarError> {
+        let Some(orchestrator) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::CampaignOrchestrator)
+        else {
+            return Ok(());
+        };
+        let campaign: Option<Campaign> = env.invoke_contract(
+            &orchestrator,
+            &Symbol::new(env, "get_campaign"),
+            Vec::from_array(env, [campaign_id.into_val(env)]),
         );
+        match campaign {
+            Some(campaign) if campaign.advertiser == *reviewer => Ok(()),
+            Some(_) => Err(PulsarError::Unauthorized),
+            None => Err(PulsarError::NotFound),
+        }
+    }
+
+    fn _review_quorum(env: &Env) -> ReviewQuorum {
+        env.storage()
+            .instance()
+            .get(&DataKey::ReviewQuorum)
+            .unwrap_or(ReviewQuorum {
+                min_reviewers: DEFAULT_MIN_REVIEWERS,
+                window_secs: DEFAULT_REVIEW_WINDOW,
+            })
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    fn _pending_reviews(env: &Env, publisher: &Address) -> Vec<ReviewEntry> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingReviews(publisher.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn _is_oracle(env: &Env, oracle: &Address) -> bool {
+        if let Some(registry) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::OracleRegistry)
+        {
+            return pulsar_common_rbac::registry_allows(
+                env,
+                &registry,
+                oracle,
+                pulsar_common_rbac::OracleDomain::Reputation,
+            );
+        }
+        let stored_oracle: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::ReputationOracle)
+            .unwrap();
+        *oracle == stored_oracle
+    }
+
+    fn _require_oracle(env: &Env, oracle: &Address) -> Result<(), PulsarError> {
+        oracle.require_auth();
+        if !Self::_is_oracle(env, oracle) {
+            return Err(PulsarError::Unauthorized);
+        }
+        Ok(())
     }
 }
 
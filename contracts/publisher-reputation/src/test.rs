@@ -5,10 +5,65 @@ use soroban_sdk::{
     Address, Env,
 };
 
+mod registry_mock {
+    use pulsar_common_rbac::OracleDomain;
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockOracleRegistry;
+
+    #[contractimpl]
+    impl MockOracleRegistry {
+        pub fn allow(env: Env, oracle: Address, domain: OracleDomain) {
+            env.storage().persistent().set(&(oracle, domain), &true);
+        }
+
+        pub fn is_authorized(env: Env, oracle: Address, domain: OracleDomain) -> bool {
+            env.storage().persistent().has(&(oracle, domain))
+        }
+    }
+}
+
+mod orchestrator_mock {
+    use crate::{Campaign, CampaignStatus};
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct MockOrchestrator;
+
+    #[contractimpl]
+    impl MockOrchestrator {
+        pub fn add_campaign(env: Env, campaign_id: u64, advertiser: Address) {
+            let campaign = Campaign {
+                advertiser,
+                campaign_type: 1,
+                budget: 1_000_000,
+                remaining_budget: 1_000_000,
+                cost_per_view: 100,
+                start_ledger: 0,
+                end_ledger: 1_000,
+                status: CampaignStatus::Active,
+                target_views: 10_000,
+                current_views: 0,
+                daily_view_limit: 1_000,
+                refundable: true,
+                platform_fee: 0,
+                created_at: 0,
+                last_updated: 0,
+            };
+            env.storage().persistent().set(&campaign_id, &campaign);
+        }
+
+        pub fn get_campaign(env: Env, campaign_id: u64) -> Option<Campaign> {
+            env.storage().persistent().get(&campaign_id)
+        }
+    }
+}
+
 fn setup(env: &Env) -> (PublisherReputationContractClient<'_>, Address, Address) {
     let admin = Address::generate(env);
     let oracle = Address::generate(env);
-    let id = env.register_contract(None, PublisherReputationContract);
+    let id = env.register(PublisherReputationContract, ());
     let c = PublisherReputationContractClient::new(env, &id);
     c.initialize(&admin, &oracle);
     (c, admin, oracle)
@@ -18,29 +73,31 @@ fn setup(env: &Env) -> (PublisherReputationContractClient<'_>, Address, Address)
 fn test_initialize() {
     let env = Env::default();
     env.mock_all_auths();
-    let id = env.register_contract(None, PublisherReputationContract);
+    let id = env.register(PublisherReputationContract, ());
     let c = PublisherReputationContractClient::new(&env, &id);
     c.initialize(&Address::generate(&env), &Address::generate(&env));
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
-    let id = env.register_contract(None, PublisherReputationContract);
+    let id = env.register(PublisherReputationContract, ());
     let c = PublisherReputationContractClient::new(&env, &id);
     let a = Address::generate(&env);
     let o = Address::generate(&env);
     c.initialize(&a, &o);
-    c.initialize(&a, &o);
+    assert_eq!(
+        c.try_initialize(&a, &o),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
 #[should_panic]
 fn test_initialize_non_admin_fails() {
     let env = Env::default();
-    let id = env.register_contract(None, PublisherReputationContract);
+    let id = env.register(PublisherReputationContract, ());
     let c = PublisherReputationContractClient::new(&env, &id);
     c.initialize(&Address::generate(&env), &Address::generate(&env));
 }
@@ -56,24 +113,29 @@ fn test_init_publisher() {
     assert_eq!(rep.score, 500);
     assert_eq!(rep.total_reviews, 0);
     assert_eq!(rep.uptime_score, 100);
+    assert_eq!(c.get_score(&pub1), 500);
+    assert_eq!(c.get_score(&Address::generate(&env)), 0);
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_init_publisher_duplicate() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, _, _) = setup(&env);
     let pub1 = Address::generate(&env);
     c.init_publisher(&pub1);
-    c.init_publisher(&pub1);
+    assert_eq!(
+        c.try_init_publisher(&pub1),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
 fn test_submit_positive_review() {
     let env = Env::default();
     env.mock_all_auths();
-    let (c, _, _) = setup(&env);
+    let (c, admin, _) = setup(&env);
+    c.set_review_quorum(&admin, &1u32, &86_400u64);
     let pub1 = Address::generate(&env);
     let adv = Address::generate(&env);
     c.init_publisher(&pub1);
@@ -92,7 +154,8 @@ fn test_submit_positive_review() {
 fn test_submit_negative_review() {
     let env = Env::default();
     env.mock_all_auths();
-    let (c, _, _) = setup(&env);
+    let (c, admin, _) = setup(&env);
+    c.set_review_quorum(&admin, &1u32, &86_400u64);
     let pub1 = Address::generate(&env);
     let adv = Address::generate(&env);
     c.init_publisher(&pub1);
@@ -103,25 +166,109 @@ fn test_submit_negative_review() {
 }
 
 #[test]
-#[should_panic(expected = "invalid rating")]
+fn test_reviews_wait_for_quorum() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _) = setup(&env);
+    let pub1 = Address::generate(&env);
+    c.init_publisher(&pub1);
+
+    // Reviews from fewer than three distinct reviewers stay pending
+    c.submit_review(&Address::generate(&env), &pub1, &1u64, &false, &5u32);
+    c.submit_review(&Address::generate(&env), &pub1, &1u64, &false, &5u32);
+    assert_eq!(c.get_score(&pub1), 500);
+    assert_eq!(c.get_pending_reviews(&pub1).len(), 2);
+    assert_eq!(c.get_review_count(&pub1), 2);
+
+    // The third distinct reviewer applies all three
+    c.submit_review(&Address::generate(&env), &pub1, &1u64, &true, &5u32);
+    let rep = c.get_reputation(&pub1).unwrap();
+    assert_eq!(rep.score, 480); // 500 - 15 - 15 + 10
+    assert_eq!(rep.total_reviews, 3);
+    assert_eq!(c.get_pending_reviews(&pub1).len(), 0);
+}
+
+#[test]
+fn test_review_requires_campaign_advertiser() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _) = setup(&env);
+    let orchestrator_id = env.register(orchestrator_mock::MockOrchestrator, ());
+    let orchestrator = orchestrator_mock::MockOrchestratorClient::new(&env, &orchestrator_id);
+    c.set_campaign_orchestrator(&admin, &orchestrator_id);
+    let pub1 = Address::generate(&env);
+    c.init_publisher(&pub1);
+    let advertiser = Address::generate(&env);
+    orchestrator.add_campaign(&1u64, &advertiser);
+
+    // Fresh keys cannot review someone else's campaign or a missing one
+    assert_eq!(
+        c.try_submit_review(&Address::generate(&env), &pub1, &1u64, &false, &5u32),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+    assert_eq!(
+        c.try_submit_review(&advertiser, &pub1, &2u64, &false, &5u32),
+        Err(Ok(PulsarError::NotFound))
+    );
+    c.submit_review(&advertiser, &pub1, &1u64, &false, &5u32);
+    assert_eq!(c.get_pending_reviews(&pub1).len(), 1);
+}
+
+#[test]
+fn test_pending_reviews_lapse_after_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _) = setup(&env);
+    c.set_review_quorum(&admin, &2u32, &3_600u64);
+    let pub1 = Address::generate(&env);
+    c.init_publisher(&pub1);
+
+    c.submit_review(&Address::generate(&env), &pub1, &1u64, &false, &5u32);
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    c.submit_review(&Address::generate(&env), &pub1, &1u64, &false, &5u32);
+    assert_eq!(c.get_score(&pub1), 500);
+    assert_eq!(c.get_pending_reviews(&pub1).len(), 1);
+}
+
+#[test]
+fn test_reviewer_cannot_stack_pending_reviews() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _) = setup(&env);
+    let pub1 = Address::generate(&env);
+    let adv = Address::generate(&env);
+    c.init_publisher(&pub1);
+    c.submit_review(&adv, &pub1, &1u64, &false, &5u32);
+    assert_eq!(
+        c.try_submit_review(&adv, &pub1, &2u64, &false, &5u32),
+        Err(Ok(PulsarError::AlreadyExists))
+    );
+}
+
+#[test]
 fn test_submit_review_invalid_rating_zero() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, _, _) = setup(&env);
     let pub1 = Address::generate(&env);
     c.init_publisher(&pub1);
-    c.submit_review(&Address::generate(&env), &pub1, &1u64, &true, &0u32);
+    assert_eq!(
+        c.try_submit_review(&Address::generate(&env), &pub1, &1u64, &true, &0u32),
+        Err(Ok(PulsarError::InvalidInput))
+    );
 }
 
 #[test]
-#[should_panic(expected = "invalid rating")]
 fn test_submit_review_invalid_rating_high() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, _, _) = setup(&env);
     let pub1 = Address::generate(&env);
     c.init_publisher(&pub1);
-    c.submit_review(&Address::generate(&env), &pub1, &1u64, &true, &6u32);
+    assert_eq!(
+        c.try_submit_review(&Address::generate(&env), &pub1, &1u64, &true, &6u32),
+        Err(Ok(PulsarError::InvalidInput))
+    );
 }
 
 #[test]
@@ -143,14 +290,16 @@ fn test_slash_publisher() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_slash_publisher_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, _, _) = setup(&env);
     let pub1 = Address::generate(&env);
     c.init_publisher(&pub1);
-    c.slash_publisher(&Address::generate(&env), &pub1, &100u32);
+    assert_eq!(
+        c.try_slash_publisher(&Address::generate(&env), &pub1, &100u32),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
@@ -185,14 +334,16 @@ fn test_update_uptime() {
 }
 
 #[test]
-#[should_panic(expected = "invalid uptime")]
 fn test_update_uptime_too_high() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, _, oracle) = setup(&env);
     let pub1 = Address::generate(&env);
     c.init_publisher(&pub1);
-    c.update_uptime(&oracle, &pub1, &101u32);
+    assert_eq!(
+        c.try_update_uptime(&oracle, &pub1, &101u32),
+        Err(Ok(PulsarError::InvalidInput))
+    );
 }
 
 #[test]
@@ -218,3 +369,23 @@ fn test_get_review_count_initial() {
     let (c, _, _) = setup(&env);
     assert_eq!(c.get_review_count(&Address::generate(&env)), 0);
 }
+
+#[test]
+fn test_oracle_registry_replaces_stored_oracle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, oracle) = setup(&env);
+    let pub1 = Address::generate(&env);
+    c.init_publisher(&pub1);
+    let registry_id = env.register(registry_mock::MockOracleRegistry, ());
+    let registry = registry_mock::MockOracleRegistryClient::new(&env, &registry_id);
+    let reporter = Address::generate(&env);
+    registry.allow(&reporter, &pulsar_common_rbac::OracleDomain::Reputation);
+    // Permission for another domain does not carry over
+    registry.allow(&oracle, &pulsar_common_rbac::OracleDomain::Fraud);
+    c.set_oracle_registry(&admin, &registry_id);
+
+    c.update_uptime(&reporter, &pub1, &90u32);
+    assert_eq!(c.get_reputation(&pub1).unwrap().uptime_score, 90);
+    assert!(c.try_update_uptime(&oracle, &pub1, &80u32).is_err());
+}
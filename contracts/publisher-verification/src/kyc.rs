@@ -0,0 +1,73 @@
+//! Publisher KYC backed by kyc-registry. Once the admin sets a registry,
+//! `verify_publisher` requires a verified, unexpired registry record at the
+//! configured level and the local `KycRecord` is no longer written.
+
+use crate::DataKey;
+use crate::PulsarError;
+use soroban_sdk::{contracttype, Address, Env, IntoVal, String, Symbol, Vec};
+
+/// Mirror of kyc-registry's `KycLevel` for cross-contract calls
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub enum KycLevel {
+    None,
+    Basic,
+    Standard,
+    Enhanced,
+}
+
+/// Mirror of kyc-registry's `KycRecord` for cross-contract calls
+#[contracttype]
+#[derive(Clone)]
+pub struct RegistryKycRecord {
+    pub account: Address,
+    pub level: KycLevel,
+    pub provider: String,
+    pub document_hash: String,
+    pub jurisdiction: String,
+    pub verified: bool,
+    pub submitted_at: u64,
+    pub verified_at: Option<u64>,
+    pub expires_at: Option<u64>,
+    pub verifier: Option<Address>,
+}
+
+fn _rank(level: &KycLevel) -> u32 {
+    match level {
+        KycLevel::None => 0,
+        KycLevel::Basic => 1,
+        KycLevel::Standard => 2,
+        KycLevel::Enhanced => 3,
+    }
+}
+
+pub fn required_level(env: &Env) -> KycLevel {
+    env.storage()
+        .instance()
+        .get(&DataKey::RequiredKycLevel)
+        .unwrap_or(KycLevel::Basic)
+}
+
+/// Errors unless `registry` holds a valid record for `publisher` at the
+/// required level
+pub fn enforce(env: &Env, registry: &Address, publisher: &Address) -> Result<(), PulsarError> {
+    let record: Option<RegistryKycRecord> = env.invoke_contract(
+        registry,
+        &Symbol::new(env, "get_kyc_record"),
+        Vec::from_array(env, [publisher.clone().into_val(env)]),
+    );
+    let record = match record {
+        Some(record) if record.verified => record,
+        _ => return Err(PulsarError::NotVerified),
+    };
+    if record
+        .expires_at
+        .is_some_and(|expires| expires <= env.ledger().timestamp())
+    {
+        return Err(PulsarError::NotVerified);
+    }
+    if _rank(&record.level) < _rank(&required_level(env)) {
+        return Err(PulsarError::NotVerified);
+    }
+    Ok(())
+}
@@ -1,8 +1,33 @@
 //! PulsarTrack - Publisher Verification (Soroban)
 //! Publisher registration, KYC, and verification on Stellar.
+//!
+//! Domain ownership is proven before verification: the publisher publishes
+//! `get_domain_challenge(publisher, domain)` (hex) in a DNS TXT record, and a
+//! domain verifier that found it calls `attest_domain` with that hash.
+//! `verify_publisher` refuses publishers without an attested domain.
+//!
+//! KYC lives in kyc-registry once `set_kyc_registry` is called (see `kyc`);
+//! the local `KycRecord` and `submit_kyc` are kept only for deployments
+//! without a registry.
+//!
+//! `deregister_publisher` releases the domain once the configured dispute and
+//! escrow contracts report nothing outstanding. The record stays behind as a
+//! tombstone and the address may not register again until the cooldown ends.
+//!
+//! Suspension is temporary and undone by `reinstate_publisher`; revocation is
+//! permanent, releases the domain and bars the address from registering again.
+//! Every status change is appended to the publisher's status history.
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env, IntoVal,
+    String, Symbol, Vec,
+};
+
+pub use pulsar_common_errors::PulsarError;
+
+mod kyc;
+pub use kyc::{KycLevel, RegistryKycRecord};
 
 // ============================================================
 // Data Types
@@ -16,6 +41,42 @@ pub enum VerificationStatus {
     Rejected,
     Suspended,
     Revoked,
+    Deregistered,
+}
+
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub enum SuspensionReason {
+    Investigation,
+    PolicyViolation,
+    KycLapsed,
+    Other,
+}
+
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub enum RevocationReason {
+    Fraud,
+    RepeatedViolations,
+    LegalRequest,
+    Other,
+}
+
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub enum StatusReason {
+    None,
+    Suspension(SuspensionReason),
+    Revocation(RevocationReason),
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct StatusChange {
+    pub status: VerificationStatus,
+    pub reason: StatusReason,
+    pub actor: Address,
+    pub changed_at: u64,
 }
 
 #[contracttype]
@@ -42,6 +103,7 @@ pub struct Publisher {
     pub last_active: u64,
 }
 
+/// Deprecated: local KYC, unused once a KYC registry is set
 #[contracttype]
 #[derive(Clone)]
 pub struct KycRecord {
@@ -53,6 +115,23 @@ pub struct KycRecord {
     pub verified_at: Option<u64>,
 }
 
+/// Economic benefits of a tier, applied by campaign-orchestrator
+#[contracttype]
+#[derive(Clone)]
+pub struct TierParams {
+    pub revenue_share_bonus_bps: u32, // paid on top of cost_per_view
+    pub daily_cap_multiplier: u32,    // hundredths of a campaign's daily view limit; 100 = 1x
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct DomainAttestation {
+    pub domain: String,
+    pub verifier: Address,
+    pub proof_hash: BytesN<32>,
+    pub attested_at: u64,
+}
+
 // ============================================================
 // Storage Keys
 // ============================================================
@@ -64,8 +143,20 @@ pub enum DataKey {
     PendingAdmin,
     PublisherCount,
     Publisher(Address),
-    KycRecord(Address),
+    KycRecord(Address), // deprecated: local KYC, unused once KycRegistry is set
     DomainOwner(String),
+    VerificationPeriod,
+    DomainVerifier(Address),
+    DomainAttestation(Address), // publisher -> attestation of its domain
+    TierParams(PublisherTier),
+    DisputeContract,
+    EscrowContract,
+    ReregistrationCooldown,
+    DeregisteredAt(Address),
+    KycRegistry,
+    RequiredKycLevel,
+    StatusHistoryCount(Address),
+    StatusHistory(Address, u32), // publisher, index
 }
 
 // ============================================================
@@ -77,39 +168,60 @@ const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
 const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
 
+const DEFAULT_REREGISTRATION_COOLDOWN: u64 = 30 * 86_400;
+const MAX_PAGE_SIZE: u32 = 50;
+
 #[contract]
 pub struct PublisherVerificationContract;
 
 #[contractimpl]
 impl PublisherVerificationContract {
     /// Initialize the contract
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage()
             .instance()
             .set(&DataKey::PublisherCount, &0u64);
+        Ok(())
     }
 
     /// Register as a publisher (self-registration)
-    pub fn register_publisher(env: Env, publisher: Address, domain: String) {
+    pub fn register_publisher(
+        env: Env,
+        publisher: Address,
+        domain: String,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         publisher.require_auth();
 
-        if env
+        if let Some(existing) = env
             .storage()
             .persistent()
-            .has(&DataKey::Publisher(publisher.clone()))
+            .get::<DataKey, Publisher>(&DataKey::Publisher(publisher.clone()))
         {
-            panic!("already registered");
+            if existing.status == VerificationStatus::Revoked {
+                return Err(PulsarError::InvalidState);
+            }
+            if existing.status != VerificationStatus::Deregistered {
+                return Err(PulsarError::AlreadyExists);
+            }
+            let deregistered_at: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::DeregisteredAt(publisher.clone()))
+                .unwrap_or(0);
+            if env.ledger().timestamp() < deregistered_at + Self::_reregistration_cooldown(&env) {
+                return Err(PulsarError::TooEarly);
+            }
         }
 
         // Check domain not taken
@@ -118,7 +230,7 @@ impl PublisherVerificationContract {
             .persistent()
             .has(&DataKey::DomainOwner(domain.clone()))
         {
-            panic!("domain already registered");
+            return Err(PulsarError::AlreadyExists);
         }
 
         let pub_data = Publisher {
@@ -157,26 +269,45 @@ impl PublisherVerificationContract {
         env.storage()
             .instance()
             .set(&DataKey::PublisherCount, &(count + 1));
+        Self::_record_status(
+            &env,
+            &publisher,
+            VerificationStatus::Pending,
+            StatusReason::None,
+            &publisher,
+        );
 
-        env.events().publish(
-            (symbol_short!("publisher"), symbol_short!("register")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("publisher"),
+            symbol_short!("register"),
             publisher,
         );
+        Ok(())
     }
 
-    /// Submit KYC documents (publisher)
-    pub fn submit_kyc(env: Env, publisher: Address, kyc_hash: String, kyc_provider: String) {
+    /// Submit KYC documents (publisher). Deprecated: refused once a KYC
+    /// registry is set, where publishers submit KYC instead.
+    pub fn submit_kyc(
+        env: Env,
+        publisher: Address,
+        kyc_hash: String,
+        kyc_provider: String,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         publisher.require_auth();
+        if env.storage().instance().has(&DataKey::KycRegistry) {
+            return Err(PulsarError::InvalidState);
+        }
 
         if !env
             .storage()
             .persistent()
             .has(&DataKey::Publisher(publisher.clone()))
         {
-            panic!("not registered");
+            return Err(PulsarError::NotFound);
         }
 
         let kyc = KycRecord {
@@ -196,10 +327,13 @@ impl PublisherVerificationContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("kyc"), symbol_short!("submitted")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("kyc"),
+            symbol_short!("submitted"),
             publisher,
         );
+        Ok(())
     }
 
     /// Verify a publisher (admin only)
@@ -208,208 +342,1132 @@ impl PublisherVerificationContract {
         admin: Address,
         publisher: Address,
         initial_tier: PublisherTier,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
-        let mut pub_data: Publisher = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Publisher(publisher.clone()))
-            .expect("publisher not found");
-
-        pub_data.status = VerificationStatus::Verified;
-        pub_data.tier = initial_tier;
-        pub_data.verified_at = Some(env.ledger().timestamp());
-        pub_data.reputation_score = 100;
-
-        let _ttl_key = DataKey::Publisher(publisher.clone());
-        env.storage().persistent().set(&_ttl_key, &pub_data);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+        Self::_verify(&env, &admin, publisher, initial_tier)
+    }
 
-        // KYC submission is mandatory before verification (compliance requirement)
-        let mut kyc = env
+    /// Verify up to 50 publishers in one call (admin only). Any failure
+    /// reverts the whole batch.
+    pub fn verify_publishers_batch(
+        env: Env,
+        admin: Address,
+        publishers: Vec<(Address, PublisherTier)>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
-            .persistent()
-            .get::<DataKey, KycRecord>(&DataKey::KycRecord(publisher.clone()))
-            .expect("KYC submission required before publisher verification");
-        kyc.verified = true;
-        kyc.verified_at = Some(env.ledger().timestamp());
-        let _ttl_key = DataKey::KycRecord(publisher.clone());
-        env.storage().persistent().set(&_ttl_key, &kyc);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        if publishers.len() > MAX_PAGE_SIZE {
+            return Err(PulsarError::LimitExceeded);
+        }
 
-        env.events().publish(
-            (symbol_short!("publisher"), symbol_short!("verified")),
-            publisher,
-        );
+        for (publisher, tier) in publishers.iter() {
+            Self::_verify(&env, &admin, publisher, tier)?;
+        }
+        Ok(())
     }
 
-    /// Suspend a publisher (admin only)
-    pub fn suspend_publisher(env: Env, admin: Address, publisher: Address) {
+    /// Admin: take publisher KYC from kyc-registry from now on
+    pub fn set_kyc_registry(
+        env: Env,
+        admin: Address,
+        registry: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
+        env.storage()
+            .instance()
+            .set(&DataKey::KycRegistry, &registry);
+        Ok(())
+    }
 
-        let mut pub_data: Publisher = env
+    /// Admin: minimum registry KYC level for verification (default Basic)
+    pub fn set_required_kyc_level(
+        env: Env,
+        admin: Address,
+        level: KycLevel,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
-            .persistent()
-            .get(&DataKey::Publisher(publisher.clone()))
-            .expect("publisher not found");
-
-        pub_data.status = VerificationStatus::Suspended;
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::RequiredKycLevel, &level);
+        Ok(())
+    }
 
-        let _ttl_key = DataKey::Publisher(publisher);
-        env.storage().persistent().set(&_ttl_key, &pub_data);
+    /// Admin: allow `verifier` to attest DNS proofs
+    pub fn add_domain_verifier(
+        env: Env,
+        admin: Address,
+        verifier: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        let _ttl_key = DataKey::DomainVerifier(verifier);
+        env.storage().persistent().set(&_ttl_key, &true);
         env.storage().persistent().extend_ttl(
             &_ttl_key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
-    /// Update publisher reputation score (admin only)
-    pub fn update_reputation(env: Env, admin: Address, publisher: Address, score: u32) {
+    pub fn remove_domain_verifier(
+        env: Env,
+        admin: Address,
+        verifier: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::DomainVerifier(verifier));
+        Ok(())
+    }
 
-        if score > 1000 {
-            panic!("invalid score");
+    /// Verifier (or admin): the challenge for `publisher`'s registered domain
+    /// was found in its DNS TXT record. `proof_hash` must equal that challenge.
+    pub fn attest_domain(
+        env: Env,
+        verifier: Address,
+        publisher: Address,
+        proof_hash: BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        verifier.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if verifier != stored_admin
+            && !env
+                .storage()
+                .persistent()
+                .get(&DataKey::DomainVerifier(verifier.clone()))
+                .unwrap_or(false)
+        {
+            return Err(PulsarError::Unauthorized);
         }
 
-        let mut pub_data: Publisher = env
+        let pub_data: Publisher = env
             .storage()
             .persistent()
             .get(&DataKey::Publisher(publisher.clone()))
-            .expect("publisher not found");
-
-        pub_data.reputation_score = score;
-        pub_data.tier = Self::_score_to_tier(score);
+            .ok_or(PulsarError::NotFound)?;
+        if pub_data.status == VerificationStatus::Deregistered
+            || pub_data.status == VerificationStatus::Revoked
+        {
+            return Err(PulsarError::InvalidState);
+        }
+        if proof_hash != Self::_domain_challenge(&env, &publisher, &pub_data.domain) {
+            return Err(PulsarError::InvalidInput);
+        }
 
-        let _ttl_key = DataKey::Publisher(publisher);
-        env.storage().persistent().set(&_ttl_key, &pub_data);
+        let attestation = DomainAttestation {
+            domain: pub_data.domain,
+            verifier: verifier.clone(),
+            proof_hash,
+            attested_at: env.ledger().timestamp(),
+        };
+        let _ttl_key = DataKey::DomainAttestation(publisher.clone());
+        env.storage().persistent().set(&_ttl_key, &attestation);
         env.storage().persistent().extend_ttl(
             &_ttl_key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("domain"),
+            symbol_short!("attested"),
+            (publisher, verifier),
+        );
+        Ok(())
     }
 
-    /// Record impression (called by campaign orchestrator)
-    pub fn record_impression(env: Env, _caller: Address, publisher: Address, earning: i128) {
+    /// Leave the network (publisher). Refused while the dispute contract
+    /// reports open exposure or the escrow contract open escrows for them.
+    pub fn deregister_publisher(env: Env, publisher: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        // In production, restrict to campaign orchestrator contract only
-        let mut pub_data: Publisher = env
+        publisher.require_auth();
+
+        let pub_data: Publisher = env
             .storage()
             .persistent()
             .get(&DataKey::Publisher(publisher.clone()))
-            .expect("publisher not found");
-
-        match pub_data.status {
-            VerificationStatus::Verified => {}
-            _ => panic!("publisher not verified"),
+            .ok_or(PulsarError::NotFound)?;
+        if pub_data.status == VerificationStatus::Deregistered {
+            return Err(PulsarError::InvalidState);
+        }
+        if pub_data.status == VerificationStatus::Revoked {
+            return Err(PulsarError::InvalidState);
         }
 
-        pub_data.total_earnings += earning;
-        pub_data.total_impressions += 1;
-        pub_data.last_active = env.ledger().timestamp();
+        if let Some(dispute) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::DisputeContract)
+        {
+            let exposure: i128 = env.invoke_contract(
+                &dispute,
+                &Symbol::new(&env, "get_open_exposure"),
+                Vec::from_array(&env, [publisher.clone().into_val(&env)]),
+            );
+            if exposure > 0 {
+                return Err(PulsarError::InvalidState);
+            }
+        }
+        if let Some(escrow) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::EscrowContract)
+        {
+            let open: u32 = env.invoke_contract(
+                &escrow,
+                &Symbol::new(&env, "get_open_escrow_count"),
+                Vec::from_array(&env, [publisher.clone().into_val(&env)]),
+            );
+            if open > 0 {
+                return Err(PulsarError::InvalidState);
+            }
+        }
 
-        let _ttl_key = DataKey::Publisher(publisher);
-        env.storage().persistent().set(&_ttl_key, &pub_data);
+        Self::_retire(&env, &publisher, pub_data, VerificationStatus::Deregistered);
+        Self::_record_status(
+            &env,
+            &publisher,
+            VerificationStatus::Deregistered,
+            StatusReason::None,
+            &publisher,
+        );
+        let _ttl_key = DataKey::DeregisteredAt(publisher.clone());
+        env.storage()
+            .persistent()
+            .set(&_ttl_key, &env.ledger().timestamp());
         env.storage().persistent().extend_ttl(
             &_ttl_key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
-    }
 
-    // ============================================================
-    // Read-Only Functions
-    // ============================================================
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("publisher"),
+            symbol_short!("dereg"),
+            publisher,
+        );
+        Ok(())
+    }
 
-    pub fn get_publisher(env: Env, publisher: Address) -> Option<Publisher> {
+    /// Admin: dispute-resolution contract checked by `deregister_publisher`
+    pub fn set_dispute_contract(
+        env: Env,
+        admin: Address,
+        dispute_contract: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
         env.storage()
-            .persistent()
-            .get(&DataKey::Publisher(publisher))
+            .instance()
+            .set(&DataKey::DisputeContract, &dispute_contract);
+        Ok(())
     }
 
-    pub fn get_kyc(env: Env, publisher: Address) -> Option<KycRecord> {
+    /// Admin: escrow-vault contract checked by `deregister_publisher`
+    pub fn set_escrow_contract(
+        env: Env,
+        admin: Address,
+        escrow_contract: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
         env.storage()
-            .persistent()
-            .get(&DataKey::KycRecord(publisher))
+            .instance()
+            .set(&DataKey::EscrowContract, &escrow_contract);
+        Ok(())
     }
 
-    pub fn is_verified(env: Env, publisher: Address) -> bool {
+    /// Admin: how long a deregistered address must wait before registering again
+    pub fn set_reregistration_cooldown(
+        env: Env,
+        admin: Address,
+        cooldown_secs: u64,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        if let Some(pub_data) = env
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
-            .persistent()
-            .get::<DataKey, Publisher>(&DataKey::Publisher(publisher))
-        {
-            matches!(pub_data.status, VerificationStatus::Verified)
-        } else {
-            false
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
         }
+        env.storage()
+            .instance()
+            .set(&DataKey::ReregistrationCooldown, &cooldown_secs);
+        Ok(())
     }
 
-    pub fn get_domain_owner(env: Env, domain: String) -> Option<Address> {
+    /// Suspend a publisher (admin only) until `reinstate_publisher`
+    pub fn suspend_publisher(
+        env: Env,
+        admin: Address,
+        publisher: Address,
+        reason: SuspensionReason,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        env.storage()
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        let mut pub_data: Publisher = env
+            .storage()
             .persistent()
-            .get(&DataKey::DomainOwner(domain))
+            .get(&DataKey::Publisher(publisher.clone()))
+            .ok_or(PulsarError::NotFound)?;
+        if pub_data.status == VerificationStatus::Deregistered
+            || pub_data.status == VerificationStatus::Revoked
+        {
+            return Err(PulsarError::InvalidState);
+        }
+
+        pub_data.status = VerificationStatus::Suspended;
+
+        let _ttl_key = DataKey::Publisher(publisher.clone());
+        env.storage().persistent().set(&_ttl_key, &pub_data);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Self::_record_status(
+            &env,
+            &publisher,
+            VerificationStatus::Suspended,
+            StatusReason::Suspension(reason.clone()),
+            &admin,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("publisher"),
+            symbol_short!("suspended"),
+            (publisher, reason),
+        );
+        Ok(())
     }
 
-    pub fn get_publisher_count(env: Env) -> u64 {
+    /// Lift a suspension (admin only). The original verification and its
+    /// expiry stand; a publisher is never reinstated past `verified_at + period`.
+    pub fn reinstate_publisher(
+        env: Env,
+        admin: Address,
+        publisher: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        env.storage()
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
             .instance()
-            .get(&DataKey::PublisherCount)
-            .unwrap_or(0)
-    }
-
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        let mut pub_data: Publisher = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Publisher(publisher.clone()))
+            .ok_or(PulsarError::NotFound)?;
+        if pub_data.status != VerificationStatus::Suspended {
+            return Err(PulsarError::InvalidState);
+        }
+        // Suspended before ever being verified: back to the review queue
+        pub_data.status = if pub_data.verified_at.is_some() {
+            VerificationStatus::Verified
+        } else {
+            VerificationStatus::Pending
+        };
+
+        let _ttl_key = DataKey::Publisher(publisher.clone());
+        env.storage().persistent().set(&_ttl_key, &pub_data);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Self::_record_status(
+            &env,
+            &publisher,
+            pub_data.status,
+            StatusReason::None,
+            &admin,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("publisher"),
+            symbol_short!("reinstate"),
+            publisher,
+        );
+        Ok(())
+    }
+
+    /// Permanently revoke a publisher (admin only). The domain is released
+    /// and the address can never register again.
+    pub fn revoke_publisher(
+        env: Env,
+        admin: Address,
+        publisher: Address,
+        reason: RevocationReason,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        let pub_data: Publisher = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Publisher(publisher.clone()))
+            .ok_or(PulsarError::NotFound)?;
+        if pub_data.status == VerificationStatus::Revoked {
+            return Err(PulsarError::InvalidState);
+        }
+
+        // A deregistered publisher already released its domain and count
+        if pub_data.status == VerificationStatus::Deregistered {
+            let mut pub_data = pub_data;
+            pub_data.status = VerificationStatus::Revoked;
+            let _ttl_key = DataKey::Publisher(publisher.clone());
+            env.storage().persistent().set(&_ttl_key, &pub_data);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        } else {
+            Self::_retire(&env, &publisher, pub_data, VerificationStatus::Revoked);
+        }
+        Self::_record_status(
+            &env,
+            &publisher,
+            VerificationStatus::Revoked,
+            StatusReason::Revocation(reason.clone()),
+            &admin,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("publisher"),
+            symbol_short!("revoked"),
+            (publisher, reason),
+        );
+        Ok(())
+    }
+
+    /// Admin: how long a verification stays valid, in seconds. Publishers past
+    /// `verified_at + period` read as unverified until verified again; 0 means
+    /// verifications never expire.
+    pub fn set_verification_period(
+        env: Env,
+        admin: Address,
+        period_secs: u64,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::VerificationPeriod, &period_secs);
+        Ok(())
+    }
+
+    /// Admin: override the default benefits of `tier`
+    pub fn set_tier_params(
+        env: Env,
+        admin: Address,
+        tier: PublisherTier,
+        params: TierParams,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        if params.revenue_share_bonus_bps > 10_000 || params.daily_cap_multiplier < 100 {
+            return Err(PulsarError::InvalidInput);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::TierParams(tier), &params);
+        Ok(())
+    }
+
+    /// Update publisher reputation score (admin only)
+    pub fn update_reputation(
+        env: Env,
+        admin: Address,
+        publisher: Address,
+        score: u32,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        Self::_update_reputation(&env, publisher, score)
+    }
+
+    /// Set up to 50 reputation scores in one call (admin only). Any failure
+    /// reverts the whole batch.
+    pub fn update_reputations_batch(
+        env: Env,
+        admin: Address,
+        scores: Vec<(Address, u32)>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        if scores.len() > MAX_PAGE_SIZE {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        for (publisher, score) in scores.iter() {
+            Self::_update_reputation(&env, publisher, score)?;
+        }
+        Ok(())
+    }
+
+    /// Record impression (called by campaign orchestrator)
+    pub fn record_impression(
+        env: Env,
+        _caller: Address,
+        publisher: Address,
+        earning: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        // In production, restrict to campaign orchestrator contract only
+        Self::_record_impressions(&env, publisher, 1, earning)
+    }
+
+    /// Batched `record_impression` for the orchestrator's `record_views_batch`;
+    /// `earning` is the total for all `count` impressions
+    pub fn record_impressions(
+        env: Env,
+        _caller: Address,
+        publisher: Address,
+        count: u64,
+        earning: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if count == 0 {
+            return Err(PulsarError::InvalidInput);
+        }
+        Self::_record_impressions(&env, publisher, count, earning)
+    }
+
+    fn _record_impressions(
+        env: &Env,
+        publisher: Address,
+        count: u64,
+        earning: i128,
+    ) -> Result<(), PulsarError> {
+        let mut pub_data: Publisher = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Publisher(publisher.clone()))
+            .ok_or(PulsarError::NotFound)?;
+
+        if !Self::_is_current(env, &pub_data) {
+            return Err(PulsarError::NotVerified);
+        }
+
+        pub_data.total_earnings += earning;
+        pub_data.total_impressions += count;
+        pub_data.last_active = env.ledger().timestamp();
+
+        let _ttl_key = DataKey::Publisher(publisher);
+        env.storage().persistent().set(&_ttl_key, &pub_data);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Ok(())
+    }
+
+    // ============================================================
+    // Read-Only Functions
+    // ============================================================
+
+    pub fn get_publisher(env: Env, publisher: Address) -> Option<Publisher> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Publisher(publisher))
+    }
+
+    pub fn get_tier(env: Env, publisher: Address) -> Option<PublisherTier> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get::<DataKey, Publisher>(&DataKey::Publisher(publisher))
+            .map(|pub_data| pub_data.tier)
+    }
+
+    pub fn get_tier_benefits(env: Env, tier: PublisherTier) -> TierParams {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_tier_params(&env, tier)
+    }
+
+    /// Benefits `publisher` currently earns; publishers that are not
+    /// currently verified get Bronze benefits
+    pub fn get_tier_params(env: Env, publisher: Address) -> TierParams {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let tier = match env
+            .storage()
+            .persistent()
+            .get::<DataKey, Publisher>(&DataKey::Publisher(publisher))
+        {
+            Some(pub_data) if Self::_is_current(&env, &pub_data) => pub_data.tier,
+            _ => PublisherTier::Bronze,
+        };
+        Self::_tier_params(&env, tier)
+    }
+
+    pub fn get_kyc_registry(env: Env) -> Option<Address> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().get(&DataKey::KycRegistry)
+    }
+
+    pub fn get_required_kyc_level(env: Env) -> KycLevel {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        kyc::required_level(&env)
+    }
+
+    /// Deprecated: local KYC only; query kyc-registry once it is set
+    pub fn get_kyc(env: Env, publisher: Address) -> Option<KycRecord> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::KycRecord(publisher))
+    }
+
+    pub fn is_verified(env: Env, publisher: Address) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if let Some(pub_data) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Publisher>(&DataKey::Publisher(publisher))
+        {
+            Self::_is_current(&env, &pub_data)
+        } else {
+            false
+        }
+    }
+
+    /// Timestamp the current verification lapses at; None if the publisher
+    /// is not verified or verifications don't expire
+    pub fn get_verification_expiry(env: Env, publisher: Address) -> Option<u64> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let pub_data: Publisher = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Publisher(publisher))?;
+        if pub_data.status != VerificationStatus::Verified {
+            return None;
+        }
+        let period = Self::_verification_period(&env);
+        if period == 0 {
+            return None;
+        }
+        pub_data.verified_at.map(|at| at + period)
+    }
+
+    pub fn get_domain_owner(env: Env, domain: String) -> Option<Address> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::DomainOwner(domain))
+    }
+
+    /// Value `publisher` must publish in `domain`'s TXT record: sha256 over
+    /// this contract's address, the publisher and the domain
+    pub fn get_domain_challenge(env: Env, publisher: Address, domain: String) -> BytesN<32> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_domain_challenge(&env, &publisher, &domain)
+    }
+
+    pub fn get_domain_attestation(env: Env, publisher: Address) -> Option<DomainAttestation> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::DomainAttestation(publisher))
+    }
+
+    pub fn get_reregistration_cooldown(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_reregistration_cooldown(&env)
+    }
+
+    pub fn get_deregistered_at(env: Env, publisher: Address) -> Option<u64> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::DeregisteredAt(publisher))
+    }
+
+    pub fn get_status_history_count(env: Env, publisher: Address) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::StatusHistoryCount(publisher))
+            .unwrap_or(0)
+    }
+
+    /// Status changes of `publisher`, oldest first
+    pub fn get_status_history(
+        env: Env,
+        publisher: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<StatusChange> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::StatusHistoryCount(publisher.clone()))
+            .unwrap_or(0);
+        let end = count.min(start.saturating_add(limit.min(MAX_PAGE_SIZE)));
+
+        let mut result = Vec::new(&env);
+        for i in start..end {
+            if let Some(change) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, StatusChange>(&DataKey::StatusHistory(publisher.clone(), i))
+            {
+                result.push_back(change);
+            }
+        }
+        result
+    }
+
+    pub fn get_publisher_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::PublisherCount)
+            .unwrap_or(0)
+    }
+
     // ============================================================
     // Internal Helpers
     // ============================================================
 
+    fn _verification_period(env: &Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::VerificationPeriod)
+            .unwrap_or(0)
+    }
+
+    fn _verify(
+        env: &Env,
+        admin: &Address,
+        publisher: Address,
+        initial_tier: PublisherTier,
+    ) -> Result<(), PulsarError> {
+        let mut pub_data: Publisher = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Publisher(publisher.clone()))
+            .ok_or(PulsarError::NotFound)?;
+        if pub_data.status == VerificationStatus::Deregistered {
+            return Err(PulsarError::InvalidState);
+        }
+        if pub_data.status == VerificationStatus::Revoked {
+            return Err(PulsarError::InvalidState);
+        }
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::DomainAttestation(publisher.clone()))
+        {
+            return Err(PulsarError::NotVerified);
+        }
+        let registry = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::KycRegistry);
+        if let Some(registry) = &registry {
+            kyc::enforce(env, registry, &publisher)?;
+        }
+
+        pub_data.status = VerificationStatus::Verified;
+        pub_data.tier = initial_tier;
+        pub_data.verified_at = Some(env.ledger().timestamp());
+        pub_data.reputation_score = 100;
+
+        let _ttl_key = DataKey::Publisher(publisher.clone());
+        env.storage().persistent().set(&_ttl_key, &pub_data);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        // KYC submission is mandatory before verification (compliance requirement)
+        if registry.is_none() {
+            let mut kyc = env
+                .storage()
+                .persistent()
+                .get::<DataKey, KycRecord>(&DataKey::KycRecord(publisher.clone()))
+                .ok_or(PulsarError::NotVerified)?;
+            kyc.verified = true;
+            kyc.verified_at = Some(env.ledger().timestamp());
+            let _ttl_key = DataKey::KycRecord(publisher.clone());
+            env.storage().persistent().set(&_ttl_key, &kyc);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+
+        Self::_record_status(
+            env,
+            &publisher,
+            VerificationStatus::Verified,
+            StatusReason::None,
+            admin,
+        );
+
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("publisher"),
+            symbol_short!("verified"),
+            publisher,
+        );
+        Ok(())
+    }
+
+    fn _update_reputation(env: &Env, publisher: Address, score: u32) -> Result<(), PulsarError> {
+        if score > 1000 {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let mut pub_data: Publisher = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Publisher(publisher.clone()))
+            .ok_or(PulsarError::NotFound)?;
+
+        pub_data.reputation_score = score;
+        pub_data.tier = Self::_score_to_tier(score);
+
+        let _ttl_key = DataKey::Publisher(publisher);
+        env.storage().persistent().set(&_ttl_key, &pub_data);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Ok(())
+    }
+
+    /// Release the domain, store `status` and drop the publisher from the count
+    fn _retire(
+        env: &Env,
+        publisher: &Address,
+        mut pub_data: Publisher,
+        status: VerificationStatus,
+    ) {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::DomainOwner(pub_data.domain.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::DomainAttestation(publisher.clone()));
+
+        pub_data.status = status;
+        let _ttl_key = DataKey::Publisher(publisher.clone());
+        env.storage().persistent().set(&_ttl_key, &pub_data);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PublisherCount)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::PublisherCount, &count.saturating_sub(1));
+    }
+
+    fn _record_status(
+        env: &Env,
+        publisher: &Address,
+        status: VerificationStatus,
+        reason: StatusReason,
+        actor: &Address,
+    ) {
+        let count_key = DataKey::StatusHistoryCount(publisher.clone());
+        let index: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let change = StatusChange {
+            status,
+            reason,
+            actor: actor.clone(),
+            changed_at: env.ledger().timestamp(),
+        };
+        let _ttl_key = DataKey::StatusHistory(publisher.clone(), index);
+        env.storage().persistent().set(&_ttl_key, &change);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage().persistent().set(&count_key, &(index + 1));
+        env.storage().persistent().extend_ttl(
+            &count_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _reregistration_cooldown(env: &Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ReregistrationCooldown)
+            .unwrap_or(DEFAULT_REREGISTRATION_COOLDOWN)
+    }
+
+    /// Verified, and within the verification period if one is set
+    fn _is_current(env: &Env, pub_data: &Publisher) -> bool {
+        if pub_data.status != VerificationStatus::Verified {
+            return false;
+        }
+        let period = Self::_verification_period(env);
+        match pub_data.verified_at {
+            Some(at) if period > 0 => env.ledger().timestamp() <= at + period,
+            _ => true,
+        }
+    }
+
+    fn _domain_challenge(env: &Env, publisher: &Address, domain: &String) -> BytesN<32> {
+        let mut data = env.current_contract_address().to_xdr(env);
+        data.append(&publisher.clone().to_xdr(env));
+        data.append(&domain.clone().to_xdr(env));
+        env.crypto().sha256(&data).into()
+    }
+
+    fn _tier_params(env: &Env, tier: PublisherTier) -> TierParams {
+        if let Some(params) = env
+            .storage()
+            .instance()
+            .get(&DataKey::TierParams(tier.clone()))
+        {
+            return params;
+        }
+        let (revenue_share_bonus_bps, daily_cap_multiplier) = match tier {
+            PublisherTier::Bronze => (0, 100),
+            PublisherTier::Silver => (250, 110),
+            PublisherTier::Gold => (500, 125),
+            PublisherTier::Platinum => (1_000, 150),
+        };
+        TierParams {
+            revenue_share_bonus_bps,
+            daily_cap_multiplier,
+        }
+    }
+
     fn _score_to_tier(score: u32) -> PublisherTier {
         if score >= 800 {
             PublisherTier::Platinum
@@ -422,18 +1480,30 @@ impl PublisherVerificationContract {
         }
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
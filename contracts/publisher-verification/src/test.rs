@@ -1,10 +1,13 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, String,
+};
 
 fn setup(env: &Env) -> (PublisherVerificationContractClient<'_>, Address) {
     let admin = Address::generate(env);
-    let id = env.register_contract(None, PublisherVerificationContract);
+    let id = env.register(PublisherVerificationContract, ());
     let c = PublisherVerificationContractClient::new(env, &id);
     c.initialize(&admin);
     (c, admin)
@@ -12,34 +15,42 @@ fn setup(env: &Env) -> (PublisherVerificationContractClient<'_>, Address) {
 fn s(env: &Env, v: &str) -> String {
     String::from_str(env, v)
 }
+/// Attest `publisher`'s registered domain as the admin
+fn attest(c: &PublisherVerificationContractClient<'_>, admin: &Address, publisher: &Address) {
+    let domain = c.get_publisher(publisher).unwrap().domain;
+    let challenge = c.get_domain_challenge(publisher, &domain);
+    c.attest_domain(admin, publisher, &challenge);
+}
 
 #[test]
 fn test_initialize() {
     let env = Env::default();
     env.mock_all_auths();
-    let id = env.register_contract(None, PublisherVerificationContract);
+    let id = env.register(PublisherVerificationContract, ());
     let c = PublisherVerificationContractClient::new(&env, &id);
     c.initialize(&Address::generate(&env));
     assert_eq!(c.get_publisher_count(), 0);
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
-    let id = env.register_contract(None, PublisherVerificationContract);
+    let id = env.register(PublisherVerificationContract, ());
     let c = PublisherVerificationContractClient::new(&env, &id);
     let a = Address::generate(&env);
     c.initialize(&a);
-    c.initialize(&a);
+    assert_eq!(
+        c.try_initialize(&a),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
 #[should_panic]
 fn test_initialize_non_admin_fails() {
     let env = Env::default();
-    let id = env.register_contract(None, PublisherVerificationContract);
+    let id = env.register(PublisherVerificationContract, ());
     let c = PublisherVerificationContractClient::new(&env, &id);
     c.initialize(&Address::generate(&env));
 }
@@ -58,18 +69,19 @@ fn test_register_publisher() {
 }
 
 #[test]
-#[should_panic(expected = "already registered")]
 fn test_register_publisher_duplicate() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, _) = setup(&env);
     let pub1 = Address::generate(&env);
     c.register_publisher(&pub1, &s(&env, "example.com"));
-    c.register_publisher(&pub1, &s(&env, "other.com"));
+    assert_eq!(
+        c.try_register_publisher(&pub1, &s(&env, "other.com")),
+        Err(Ok(PulsarError::AlreadyExists))
+    );
 }
 
 #[test]
-#[should_panic(expected = "domain already registered")]
 fn test_register_publisher_duplicate_domain() {
     let env = Env::default();
     env.mock_all_auths();
@@ -77,7 +89,10 @@ fn test_register_publisher_duplicate_domain() {
     let p1 = Address::generate(&env);
     let p2 = Address::generate(&env);
     c.register_publisher(&p1, &s(&env, "example.com"));
-    c.register_publisher(&p2, &s(&env, "example.com"));
+    assert_eq!(
+        c.try_register_publisher(&p2, &s(&env, "example.com")),
+        Err(Ok(PulsarError::AlreadyExists))
+    );
 }
 
 #[test]
@@ -93,16 +108,16 @@ fn test_submit_kyc() {
 }
 
 #[test]
-#[should_panic(expected = "not registered")]
 fn test_submit_kyc_unregistered() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, _) = setup(&env);
-    c.submit_kyc(
+    let result = c.try_submit_kyc(
         &Address::generate(&env),
         &s(&env, "KycHash"),
         &s(&env, "KycProvider"),
     );
+    assert_eq!(result, Err(Ok(PulsarError::NotFound)));
 }
 
 #[test]
@@ -113,6 +128,7 @@ fn test_verify_publisher() {
     let pub1 = Address::generate(&env);
     c.register_publisher(&pub1, &s(&env, "example.com"));
     c.submit_kyc(&pub1, &s(&env, "KycHash"), &s(&env, "KycProvider"));
+    attest(&c, &admin, &pub1);
     c.verify_publisher(&admin, &pub1, &PublisherTier::Gold);
     let p = c.get_publisher(&pub1).unwrap();
     assert!(matches!(p.status, VerificationStatus::Verified));
@@ -121,17 +137,20 @@ fn test_verify_publisher() {
     assert!(c.is_verified(&pub1));
     let kyc = c.get_kyc(&pub1).unwrap();
     assert!(kyc.verified);
+    assert!(matches!(c.get_tier(&pub1), Some(PublisherTier::Gold)));
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_verify_publisher_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, _) = setup(&env);
     let pub1 = Address::generate(&env);
     c.register_publisher(&pub1, &s(&env, "example.com"));
-    c.verify_publisher(&Address::generate(&env), &pub1, &PublisherTier::Bronze);
+    assert_eq!(
+        c.try_verify_publisher(&Address::generate(&env), &pub1, &PublisherTier::Bronze),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
@@ -141,9 +160,84 @@ fn test_suspend_publisher() {
     let (c, admin) = setup(&env);
     let pub1 = Address::generate(&env);
     c.register_publisher(&pub1, &s(&env, "example.com"));
+    c.submit_kyc(&pub1, &s(&env, "KycHash"), &s(&env, "KycProvider"));
+    attest(&c, &admin, &pub1);
+    c.verify_publisher(&admin, &pub1, &PublisherTier::Silver);
+    c.suspend_publisher(&admin, &pub1, &SuspensionReason::Investigation);
+    assert!(!c.is_verified(&pub1));
+}
+
+#[test]
+fn test_reinstate_suspended_publisher() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let pub1 = Address::generate(&env);
+    c.register_publisher(&pub1, &s(&env, "example.com"));
+    c.submit_kyc(&pub1, &s(&env, "KycHash"), &s(&env, "KycProvider"));
+    attest(&c, &admin, &pub1);
     c.verify_publisher(&admin, &pub1, &PublisherTier::Silver);
-    c.suspend_publisher(&admin, &pub1);
+    c.suspend_publisher(&admin, &pub1, &SuspensionReason::PolicyViolation);
+    c.reinstate_publisher(&admin, &pub1);
+    assert!(c.is_verified(&pub1));
+
+    // register, verify, suspend, reinstate
+    assert_eq!(c.get_status_history_count(&pub1), 4);
+    let history = c.get_status_history(&pub1, &1, &10);
+    assert_eq!(history.len(), 3);
+    let suspension = history.get(1).unwrap();
+    assert!(matches!(suspension.status, VerificationStatus::Suspended));
+    assert_eq!(
+        suspension.reason,
+        StatusReason::Suspension(SuspensionReason::PolicyViolation)
+    );
+    assert_eq!(suspension.actor, admin);
+    assert_eq!(history.get(2).unwrap().reason, StatusReason::None);
+}
+
+#[test]
+fn test_reinstate_requires_suspension() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let pub1 = Address::generate(&env);
+    c.register_publisher(&pub1, &s(&env, "example.com"));
+    c.revoke_publisher(&admin, &pub1, &RevocationReason::Fraud);
+    assert_eq!(
+        c.try_reinstate_publisher(&admin, &pub1),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_revoke_releases_domain_permanently() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let pub1 = Address::generate(&env);
+    let domain = s(&env, "example.com");
+    c.register_publisher(&pub1, &domain);
+    c.submit_kyc(&pub1, &s(&env, "KycHash"), &s(&env, "KycProvider"));
+    attest(&c, &admin, &pub1);
+    c.verify_publisher(&admin, &pub1, &PublisherTier::Gold);
+
+    c.revoke_publisher(&admin, &pub1, &RevocationReason::Fraud);
     assert!(!c.is_verified(&pub1));
+    assert!(c.get_domain_owner(&domain).is_none());
+    assert_eq!(c.get_publisher_count(), 0);
+    let last = c.get_status_history(&pub1, &2, &1).get(0).unwrap();
+    assert_eq!(
+        last.reason,
+        StatusReason::Revocation(RevocationReason::Fraud)
+    );
+
+    // Neither a cooldown nor deregistration opens the way back
+    env.ledger().with_mut(|li| li.timestamp += 365 * 86_400);
+    assert!(c.try_register_publisher(&pub1, &domain).is_err());
+    assert!(c.try_deregister_publisher(&pub1).is_err());
+    assert!(c
+        .try_verify_publisher(&admin, &pub1, &PublisherTier::Gold)
+        .is_err());
 }
 
 #[test]
@@ -159,14 +253,73 @@ fn test_update_reputation() {
 }
 
 #[test]
-#[should_panic(expected = "invalid score")]
 fn test_update_reputation_too_high() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, admin) = setup(&env);
     let pub1 = Address::generate(&env);
     c.register_publisher(&pub1, &s(&env, "example.com"));
-    c.update_reputation(&admin, &pub1, &1001u32);
+    assert_eq!(
+        c.try_update_reputation(&admin, &pub1, &1001u32),
+        Err(Ok(PulsarError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_batch_verify_and_reputation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let pub1 = Address::generate(&env);
+    let pub2 = Address::generate(&env);
+    for (publisher, domain) in [(&pub1, "one.com"), (&pub2, "two.com")] {
+        c.register_publisher(publisher, &s(&env, domain));
+        c.submit_kyc(publisher, &s(&env, "KycHash"), &s(&env, "KycProvider"));
+        attest(&c, &admin, publisher);
+    }
+
+    c.verify_publishers_batch(
+        &admin,
+        &vec![
+            &env,
+            (pub1.clone(), PublisherTier::Silver),
+            (pub2.clone(), PublisherTier::Gold),
+        ],
+    );
+    assert!(c.is_verified(&pub1));
+    assert!(matches!(c.get_tier(&pub2), Some(PublisherTier::Gold)));
+
+    c.update_reputations_batch(
+        &admin,
+        &vec![&env, (pub1.clone(), 300), (pub2.clone(), 950)],
+    );
+    assert_eq!(c.get_publisher(&pub1).unwrap().reputation_score, 300);
+    assert_eq!(c.get_publisher(&pub2).unwrap().reputation_score, 950);
+}
+
+#[test]
+fn test_batch_verify_is_atomic() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let pub1 = Address::generate(&env);
+    let pub2 = Address::generate(&env);
+    c.register_publisher(&pub1, &s(&env, "one.com"));
+    c.submit_kyc(&pub1, &s(&env, "KycHash"), &s(&env, "KycProvider"));
+    attest(&c, &admin, &pub1);
+    // pub2 never attested its domain
+    c.register_publisher(&pub2, &s(&env, "two.com"));
+
+    let result = c.try_verify_publishers_batch(
+        &admin,
+        &vec![
+            &env,
+            (pub1.clone(), PublisherTier::Silver),
+            (pub2.clone(), PublisherTier::Silver),
+        ],
+    );
+    assert!(result.is_err());
+    assert!(!c.is_verified(&pub1));
 }
 
 #[test]
@@ -177,6 +330,8 @@ fn test_record_impression() {
     let pub1 = Address::generate(&env);
     let caller = Address::generate(&env);
     c.register_publisher(&pub1, &s(&env, "example.com"));
+    c.submit_kyc(&pub1, &s(&env, "KycHash"), &s(&env, "KycProvider"));
+    attest(&c, &admin, &pub1);
     c.verify_publisher(&admin, &pub1, &PublisherTier::Gold);
     c.record_impression(&caller, &pub1, &1000i128);
     let p = c.get_publisher(&pub1).unwrap();
@@ -185,7 +340,23 @@ fn test_record_impression() {
 }
 
 #[test]
-#[should_panic(expected = "publisher not verified")]
+fn test_record_impressions_counts_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let pub1 = Address::generate(&env);
+    let caller = Address::generate(&env);
+    c.register_publisher(&pub1, &s(&env, "example.com"));
+    c.submit_kyc(&pub1, &s(&env, "KycHash"), &s(&env, "KycProvider"));
+    attest(&c, &admin, &pub1);
+    c.verify_publisher(&admin, &pub1, &PublisherTier::Gold);
+    c.record_impressions(&caller, &pub1, &25u64, &2_500i128);
+    let p = c.get_publisher(&pub1).unwrap();
+    assert_eq!(p.total_impressions, 25);
+    assert_eq!(p.total_earnings, 2_500);
+}
+
+#[test]
 fn test_record_impression_unverified() {
     let env = Env::default();
     env.mock_all_auths();
@@ -193,7 +364,10 @@ fn test_record_impression_unverified() {
     let pub1 = Address::generate(&env);
     let caller = Address::generate(&env);
     c.register_publisher(&pub1, &s(&env, "example.com"));
-    c.record_impression(&caller, &pub1, &1000i128);
+    assert_eq!(
+        c.try_record_impression(&caller, &pub1, &1000i128),
+        Err(Ok(PulsarError::NotVerified))
+    );
 }
 
 #[test]
@@ -214,3 +388,350 @@ fn test_is_verified_nonexistent() {
     let (c, _) = setup(&env);
     assert!(!c.is_verified(&Address::generate(&env)));
 }
+
+#[test]
+fn test_verification_expires_after_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let pub1 = Address::generate(&env);
+    c.register_publisher(&pub1, &s(&env, "example.com"));
+    c.submit_kyc(&pub1, &s(&env, "KycHash"), &s(&env, "KycProvider"));
+    c.set_verification_period(&admin, &1_000u64);
+    attest(&c, &admin, &pub1);
+    c.verify_publisher(&admin, &pub1, &PublisherTier::Gold);
+    let verified_at = env.ledger().timestamp();
+    assert_eq!(c.get_verification_expiry(&pub1), Some(verified_at + 1_000));
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = verified_at + 1_000);
+    assert!(c.is_verified(&pub1));
+    env.ledger()
+        .with_mut(|li| li.timestamp = verified_at + 1_001);
+    assert!(!c.is_verified(&pub1));
+
+    // Re-verifying starts a new period; the attestation still stands
+    c.verify_publisher(&admin, &pub1, &PublisherTier::Gold);
+    assert!(c.is_verified(&pub1));
+}
+
+#[test]
+fn test_record_impression_expired() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let pub1 = Address::generate(&env);
+    c.register_publisher(&pub1, &s(&env, "example.com"));
+    c.submit_kyc(&pub1, &s(&env, "KycHash"), &s(&env, "KycProvider"));
+    c.set_verification_period(&admin, &1_000u64);
+    attest(&c, &admin, &pub1);
+    c.verify_publisher(&admin, &pub1, &PublisherTier::Gold);
+    env.ledger().with_mut(|li| li.timestamp += 1_001);
+    assert_eq!(
+        c.try_record_impression(&Address::generate(&env), &pub1, &1000i128),
+        Err(Ok(PulsarError::NotVerified))
+    );
+}
+
+#[test]
+fn test_attested_domain_by_verifier() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let verifier = Address::generate(&env);
+    c.add_domain_verifier(&admin, &verifier);
+    let pub1 = Address::generate(&env);
+    let pub2 = Address::generate(&env);
+    c.register_publisher(&pub1, &s(&env, "example.com"));
+
+    // Challenges are bound to both the publisher and the domain
+    let challenge = c.get_domain_challenge(&pub1, &s(&env, "example.com"));
+    assert_ne!(
+        challenge,
+        c.get_domain_challenge(&pub2, &s(&env, "example.com"))
+    );
+    assert_ne!(
+        challenge,
+        c.get_domain_challenge(&pub1, &s(&env, "other.com"))
+    );
+
+    c.attest_domain(&verifier, &pub1, &challenge);
+    let attestation = c.get_domain_attestation(&pub1).unwrap();
+    assert_eq!(attestation.verifier, verifier);
+    assert_eq!(attestation.domain, s(&env, "example.com"));
+
+    c.submit_kyc(&pub1, &s(&env, "KycHash"), &s(&env, "KycProvider"));
+    c.verify_publisher(&admin, &pub1, &PublisherTier::Bronze);
+    assert!(c.is_verified(&pub1));
+}
+
+#[test]
+fn test_verify_publisher_requires_attested_domain() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let pub1 = Address::generate(&env);
+    c.register_publisher(&pub1, &s(&env, "example.com"));
+    c.submit_kyc(&pub1, &s(&env, "KycHash"), &s(&env, "KycProvider"));
+    assert_eq!(
+        c.try_verify_publisher(&admin, &pub1, &PublisherTier::Bronze),
+        Err(Ok(PulsarError::NotVerified))
+    );
+}
+
+#[test]
+fn test_attest_domain_wrong_proof() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let pub1 = Address::generate(&env);
+    c.register_publisher(&pub1, &s(&env, "example.com"));
+    let squatted = c.get_domain_challenge(&Address::generate(&env), &s(&env, "example.com"));
+    assert_eq!(
+        c.try_attest_domain(&admin, &pub1, &squatted),
+        Err(Ok(PulsarError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_attest_domain_unknown_verifier() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let pub1 = Address::generate(&env);
+    c.register_publisher(&pub1, &s(&env, "example.com"));
+    let challenge = c.get_domain_challenge(&pub1, &s(&env, "example.com"));
+    assert_eq!(
+        c.try_attest_domain(&Address::generate(&env), &pub1, &challenge),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_tier_params_follow_current_tier() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let pub1 = Address::generate(&env);
+    c.register_publisher(&pub1, &s(&env, "example.com"));
+
+    // Unverified publishers earn Bronze benefits
+    assert_eq!(c.get_tier_params(&pub1).revenue_share_bonus_bps, 0);
+
+    c.submit_kyc(&pub1, &s(&env, "KycHash"), &s(&env, "KycProvider"));
+    attest(&c, &admin, &pub1);
+    c.verify_publisher(&admin, &pub1, &PublisherTier::Gold);
+    let params = c.get_tier_params(&pub1);
+    assert_eq!(params.revenue_share_bonus_bps, 500);
+    assert_eq!(params.daily_cap_multiplier, 125);
+
+    c.set_tier_params(
+        &admin,
+        &PublisherTier::Gold,
+        &TierParams {
+            revenue_share_bonus_bps: 700,
+            daily_cap_multiplier: 200,
+        },
+    );
+    assert_eq!(c.get_tier_params(&pub1).daily_cap_multiplier, 200);
+
+    c.suspend_publisher(&admin, &pub1, &SuspensionReason::Investigation);
+    assert_eq!(c.get_tier_params(&pub1).daily_cap_multiplier, 100);
+}
+
+#[test]
+fn test_set_tier_params_below_base_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let result = c.try_set_tier_params(
+        &admin,
+        &PublisherTier::Silver,
+        &TierParams {
+            revenue_share_bonus_bps: 0,
+            daily_cap_multiplier: 50,
+        },
+    );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidInput)));
+}
+
+mod obligations_mock {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    /// Answers both the dispute and the escrow exit checks
+    #[contract]
+    pub struct MockObligations;
+
+    #[contractimpl]
+    impl MockObligations {
+        pub fn set_exposure(env: Env, account: Address, exposure: i128) {
+            env.storage().persistent().set(&(account, 0u32), &exposure);
+        }
+
+        pub fn set_open_escrows(env: Env, account: Address, open: u32) {
+            env.storage().persistent().set(&(account, 1u32), &open);
+        }
+
+        pub fn get_open_exposure(env: Env, account: Address) -> i128 {
+            env.storage()
+                .persistent()
+                .get(&(account, 0u32))
+                .unwrap_or(0)
+        }
+
+        pub fn get_open_escrow_count(env: Env, account: Address) -> u32 {
+            env.storage()
+                .persistent()
+                .get(&(account, 1u32))
+                .unwrap_or(0)
+        }
+    }
+}
+
+#[test]
+fn test_deregister_releases_domain_after_cooldown() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let pub1 = Address::generate(&env);
+    let domain = s(&env, "example.com");
+    c.register_publisher(&pub1, &domain);
+    c.submit_kyc(&pub1, &s(&env, "KycHash"), &s(&env, "KycProvider"));
+    attest(&c, &admin, &pub1);
+    c.verify_publisher(&admin, &pub1, &PublisherTier::Silver);
+    c.set_reregistration_cooldown(&admin, &3_600);
+
+    c.deregister_publisher(&pub1);
+    assert!(!c.is_verified(&pub1));
+    assert_eq!(c.get_publisher_count(), 0);
+    assert!(c.get_domain_owner(&domain).is_none());
+    assert!(c.get_domain_attestation(&pub1).is_none());
+    let tombstone = c.get_publisher(&pub1).unwrap();
+    assert!(matches!(tombstone.status, VerificationStatus::Deregistered));
+
+    // The domain is free for someone else right away
+    let pub2 = Address::generate(&env);
+    c.register_publisher(&pub2, &domain);
+
+    let other = s(&env, "other.com");
+    assert!(c.try_register_publisher(&pub1, &other).is_err());
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    c.register_publisher(&pub1, &other);
+    assert!(matches!(
+        c.get_publisher(&pub1).unwrap().status,
+        VerificationStatus::Pending
+    ));
+    assert_eq!(c.get_publisher_count(), 2);
+}
+
+#[test]
+fn test_deregister_blocked_by_open_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let obligations_id = env.register(obligations_mock::MockObligations, ());
+    let obligations = obligations_mock::MockObligationsClient::new(&env, &obligations_id);
+    c.set_dispute_contract(&admin, &obligations_id);
+    c.set_escrow_contract(&admin, &obligations_id);
+    let pub1 = Address::generate(&env);
+    c.register_publisher(&pub1, &s(&env, "example.com"));
+
+    obligations.set_exposure(&pub1, &500);
+    assert!(c.try_deregister_publisher(&pub1).is_err());
+    obligations.set_exposure(&pub1, &0);
+    obligations.set_open_escrows(&pub1, &1);
+    assert_eq!(
+        c.try_deregister_publisher(&pub1),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+mod kyc_mock {
+    use super::{KycLevel, RegistryKycRecord};
+    use soroban_sdk::{contract, contractimpl, Address, Env, String};
+
+    #[contract]
+    pub struct MockKycRegistry;
+
+    #[contractimpl]
+    impl MockKycRegistry {
+        pub fn set_record(env: Env, account: Address, level: KycLevel) {
+            let record = RegistryKycRecord {
+                account: account.clone(),
+                level,
+                provider: String::from_str(&env, "provider"),
+                document_hash: String::from_str(&env, "hash"),
+                jurisdiction: String::from_str(&env, "DE"),
+                verified: true,
+                submitted_at: 0,
+                verified_at: Some(0),
+                expires_at: None,
+                verifier: None,
+            };
+            env.storage().persistent().set(&account, &record);
+        }
+
+        pub fn get_kyc_record(env: Env, account: Address) -> Option<RegistryKycRecord> {
+            env.storage().persistent().get(&account)
+        }
+    }
+}
+
+#[test]
+fn test_verify_with_kyc_registry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let registry_id = env.register(kyc_mock::MockKycRegistry, ());
+    let registry = kyc_mock::MockKycRegistryClient::new(&env, &registry_id);
+    c.set_kyc_registry(&admin, &registry_id);
+    c.set_required_kyc_level(&admin, &KycLevel::Standard);
+    let pub1 = Address::generate(&env);
+    c.register_publisher(&pub1, &s(&env, "example.com"));
+    attest(&c, &admin, &pub1);
+
+    // Local KYC is no longer accepted
+    assert!(c
+        .try_submit_kyc(&pub1, &s(&env, "KycHash"), &s(&env, "KycProvider"))
+        .is_err());
+
+    registry.set_record(&pub1, &KycLevel::Standard);
+    c.verify_publisher(&admin, &pub1, &PublisherTier::Bronze);
+    assert!(c.is_verified(&pub1));
+    assert!(c.get_kyc(&pub1).is_none());
+}
+
+#[test]
+fn test_verify_registry_kyc_level_too_low() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let registry_id = env.register(kyc_mock::MockKycRegistry, ());
+    let registry = kyc_mock::MockKycRegistryClient::new(&env, &registry_id);
+    c.set_kyc_registry(&admin, &registry_id);
+    c.set_required_kyc_level(&admin, &KycLevel::Enhanced);
+    let pub1 = Address::generate(&env);
+    c.register_publisher(&pub1, &s(&env, "example.com"));
+    attest(&c, &admin, &pub1);
+    registry.set_record(&pub1, &KycLevel::Basic);
+    assert_eq!(
+        c.try_verify_publisher(&admin, &pub1, &PublisherTier::Bronze),
+        Err(Ok(PulsarError::NotVerified))
+    );
+}
+
+#[test]
+fn test_verify_without_registry_record() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let registry_id = env.register(kyc_mock::MockKycRegistry, ());
+    c.set_kyc_registry(&admin, &registry_id);
+    let pub1 = Address::generate(&env);
+    c.register_publisher(&pub1, &s(&env, "example.com"));
+    attest(&c, &admin, &pub1);
+    assert_eq!(
+        c.try_verify_publisher(&admin, &pub1, &PublisherTier::Bronze),
+        Err(Ok(PulsarError::NotVerified))
+    );
+}
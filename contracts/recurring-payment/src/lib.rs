@@ -2,7 +2,12 @@
 //! Automated recurring payment subscriptions for ad campaigns on Stellar.
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env};
+#![allow(clippy::too_many_arguments)]
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, Address, Env, IntoVal, Symbol, Vec,
+};
+
+pub use pulsar_common_errors::PulsarError;
 
 #[contracttype]
 #[derive(Clone, PartialEq)]
@@ -28,6 +33,61 @@ pub struct RecurringPayment {
     pub created_at: u64,
     pub last_payment: u64,
     pub next_payment: u64,
+    pub max_per_charge: i128, // payer-set ceiling for a single execution
+    pub max_total: i128,      // payer-set ceiling across the schedule's lifetime
+    pub total_charged: i128,
+    pub failed_attempts: u32, // consecutive attempts the payer could not cover
+    pub last_failed_at: u64,
+    pub keeper_fee: KeeperFee, // unless Disabled, anyone may execute and earn it
+    pub renewal: SubscriptionRenewal,
+    pub catch_up: CatchUpPolicy,
+}
+
+/// Terms queued by `amend`, applied right after the next successful charge
+#[contracttype]
+#[derive(Clone)]
+pub struct PendingAmendment {
+    pub amount: i128,
+    pub interval_secs: u64,
+    pub requested_at: u64,
+}
+
+/// Paid by the payer, on top of `amount`, to whoever executes a due charge
+#[contracttype]
+#[derive(Clone)]
+pub enum KeeperFee {
+    Disabled,
+    Flat(i128),
+    Bps(u32), // of the charge amount
+}
+
+/// Renewal target for schedules whose recipient is a subscription-manager
+/// contract: execution calls its `renew_from_allowance()` for the payer
+/// instead of transferring `amount` to the recipient.
+#[contracttype]
+#[derive(Clone)]
+pub enum SubscriptionRenewal {
+    Disabled,
+    Monthly,
+    Annual,
+}
+
+/// What a late execution does with the cycles that elapsed since next_payment
+#[contracttype]
+#[derive(Clone)]
+pub enum CatchUpPolicy {
+    SkipMissed,       // charge once and realign to the original schedule
+    CatchUpAll(u32),  // charge every missed cycle, at most this many per call
+    ProRate,          // charge once, scaled by the time elapsed since the cycle began
+}
+
+/// Payer-authorized ceilings; execution can never pull more than these,
+/// whatever the recipient or keeper asks for.
+#[contracttype]
+#[derive(Clone)]
+pub struct SpendingCaps {
+    pub max_per_charge: i128,
+    pub max_total: i128,
 }
 
 #[contracttype]
@@ -36,7 +96,15 @@ pub enum DataKey {
     Admin,
     PendingAdmin,
     PaymentCounter,
+    MaxFailedAttempts,
+    DueFloor, // no due bucket below this holds a schedule
     Payment(u64),
+    Amendment(u64),
+    PayerScheduleCount(Address),
+    SchedulesByPayer(Address, u32), // (payer, index) -> payment_id
+    RecipientScheduleCount(Address),
+    SchedulesByRecipient(Address, u32), // (recipient, index) -> payment_id
+    DueBucket(u64), // next_payment / DUE_BUCKET_SECS -> payment ids
 }
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
@@ -44,23 +112,31 @@ const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
 const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
 
+const DEFAULT_MAX_FAILED_ATTEMPTS: u32 = 3;
+const RETRY_DELAY_SECS: u64 = 3_600;
+const DUE_BUCKET_SECS: u64 = 86_400;
+const MAX_BUCKET_SCAN: u64 = 366;
+const MAX_PAGE_SIZE: u32 = 50;
+const MAX_CATCH_UP_CYCLES: u32 = 24;
+
 #[contract]
 pub struct RecurringPaymentContract;
 
 #[contractimpl]
 impl RecurringPaymentContract {
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage()
             .instance()
             .set(&DataKey::PaymentCounter, &0u64);
+        Ok(())
     }
 
     pub fn create_recurring(
@@ -71,17 +147,21 @@ impl RecurringPaymentContract {
         amount: i128,
         interval_secs: u64,
         max_payments: Option<u32>,
-    ) -> u64 {
+        caps: SpendingCaps,
+    ) -> Result<u64, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         payer.require_auth();
 
         if amount <= 0 {
-            panic!("invalid amount");
+            return Err(PulsarError::InvalidAmount);
         }
         if interval_secs == 0 {
-            panic!("invalid interval");
+            return Err(PulsarError::InvalidInput);
+        }
+        if amount > caps.max_per_charge || amount > caps.max_total {
+            return Err(PulsarError::LimitExceeded);
         }
 
         let counter: u64 = env
@@ -95,7 +175,7 @@ impl RecurringPaymentContract {
         let recurring = RecurringPayment {
             payment_id,
             payer: payer.clone(),
-            recipient,
+            recipient: recipient.clone(),
             token,
             amount,
             interval_secs,
@@ -105,6 +185,14 @@ impl RecurringPaymentContract {
             created_at: now,
             last_payment: now,
             next_payment: now + interval_secs,
+            max_per_charge: caps.max_per_charge,
+            max_total: caps.max_total,
+            total_charged: 0,
+            failed_attempts: 0,
+            last_failed_at: 0,
+            keeper_fee: KeeperFee::Disabled,
+            renewal: SubscriptionRenewal::Disabled,
+            catch_up: CatchUpPolicy::SkipMissed,
         };
 
         let _ttl_key = DataKey::Payment(payment_id);
@@ -114,14 +202,62 @@ impl RecurringPaymentContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Self::_index_due(&env, &recurring);
         env.storage()
             .instance()
             .set(&DataKey::PaymentCounter, &payment_id);
+        Self::_index_schedule(
+            &env,
+            DataKey::PayerScheduleCount(payer.clone()),
+            |i| DataKey::SchedulesByPayer(payer.clone(), i),
+            payment_id,
+        );
+        Self::_index_schedule(
+            &env,
+            DataKey::RecipientScheduleCount(recipient.clone()),
+            |i| DataKey::SchedulesByRecipient(recipient.clone(), i),
+            payment_id,
+        );
 
-        payment_id
+        Ok(payment_id)
+    }
+
+    /// Consecutive uncovered attempts after which a schedule moves to Failed
+    pub fn set_max_failed_attempts(
+        env: Env,
+        admin: Address,
+        attempts: u32,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        if attempts == 0 {
+            return Err(PulsarError::InvalidInput);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxFailedAttempts, &attempts);
+        Ok(())
     }
 
-    pub fn execute_payment(env: Env, caller: Address, payment_id: u64) {
+    /// Returns false when the payer's balance or allowance could not cover the
+    /// charge; the attempt is counted and, after MaxFailedAttempts in a row,
+    /// the schedule is marked Failed until the payer reactivates it. The
+    /// payment that reaches `max_payments` closes the schedule as Cancelled.
+    pub fn execute_payment(
+        env: Env,
+        caller: Address,
+        payment_id: u64,
+    ) -> Result<bool, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -132,31 +268,36 @@ impl RecurringPaymentContract {
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("payment not found");
+            .ok_or(PulsarError::NotFound)?;
 
-        // Only payer, recipient, or admin can execute
-        if caller != recurring.payer && caller != recurring.recipient {
+        // Only payer, recipient, or admin can execute, unless a keeper fee
+        // opens execution to anyone
+        if matches!(recurring.keeper_fee, KeeperFee::Disabled)
+            && caller != recurring.payer
+            && caller != recurring.recipient
+        {
             let admin: Address = env
                 .storage()
                 .instance()
                 .get(&DataKey::Admin)
-                .expect("admin not found");
+                .ok_or(PulsarError::NotFound)?;
             if caller != admin {
-                panic!("unauthorized");
+                return Err(PulsarError::Unauthorized);
             }
         }
 
         if recurring.status != RecurringStatus::Active {
-            panic!("payment not active");
+            return Err(PulsarError::InvalidState);
         }
 
         let now = env.ledger().timestamp();
         if now < recurring.next_payment {
-            panic!("too early");
+            return Err(PulsarError::TooEarly);
         }
 
         if let Some(max) = recurring.max_payments {
             if recurring.total_payments >= max {
+                Self::_unindex_due(&env, &recurring);
                 recurring.status = RecurringStatus::Cancelled;
                 let _ttl_key = DataKey::Payment(payment_id);
                 env.storage().persistent().set(&_ttl_key, &recurring);
@@ -165,21 +306,126 @@ impl RecurringPaymentContract {
                     PERSISTENT_LIFETIME_THRESHOLD,
                     PERSISTENT_BUMP_AMOUNT,
                 );
-                panic!("max payments reached");
+                return Ok(false);
             }
         }
 
-        // Require payer's auth for the transfer (unless caller is the payer)
-        if caller != recurring.payer {
-            recurring.payer.require_auth();
+        // Cycles due since next_payment, and how many this execution charges
+        let interval = recurring.interval_secs;
+        let due_cycles = (now - recurring.next_payment) / interval + 1;
+        let mut cycles = match recurring.catch_up {
+            CatchUpPolicy::CatchUpAll(max) => due_cycles.min(max as u64),
+            _ => 1,
+        };
+        if let Some(max) = recurring.max_payments {
+            cycles = cycles.min((max - recurring.total_payments) as u64);
+        }
+        let renewal = !matches!(recurring.renewal, SubscriptionRenewal::Disabled);
+        let amount = match recurring.catch_up {
+            // Renewals are priced by the subscription contract, not prorated
+            CatchUpPolicy::ProRate if !renewal => {
+                let period_start = recurring.next_payment.saturating_sub(interval);
+                let prorated = recurring.amount * (now - period_start) as i128 / interval as i128;
+                prorated.min(Self::_max_amount_under_cap(&recurring))
+            }
+            _ => recurring.amount,
+        };
+
+        let fee = Self::_fee_on(&recurring.keeper_fee, amount);
+        let charge = amount + fee;
+        if charge > recurring.max_per_charge {
+            return Err(PulsarError::LimitExceeded);
         }
+        let room = (recurring.max_total - recurring.total_charged) / charge;
+        if room <= 0 {
+            return Err(PulsarError::LimitExceeded);
+        }
+        cycles = cycles.min(room as u64);
 
+        // Pull from the payer's allowance to this contract, then forward.
+        // Renewals are charged by the subscription contract itself, so only
+        // the keeper fee goes through the allowance. Catch-up charges as
+        // many cycles as the payer can cover.
         let token_client = token::Client::new(&env, &recurring.token);
-        token_client.transfer(&recurring.payer, &recurring.recipient, &recurring.amount);
+        let contract = env.current_contract_address();
+        let pulled = if renewal { fee } else { charge };
+        let mut affordable = token_client.balance(&recurring.payer) / charge;
+        if pulled > 0 {
+            affordable = affordable.min(token_client.allowance(&recurring.payer, &contract) / pulled);
+        }
+        if affordable <= 0 {
+            Self::_mark_failed(&env, &mut recurring)?;
+            return Ok(false);
+        }
+        cycles = cycles.min(affordable as u64);
+
+        if pulled > 0 {
+            token_client.transfer_from(
+                &contract,
+                &recurring.payer,
+                &contract,
+                &(pulled * cycles as i128),
+            );
+        }
 
-        recurring.total_payments += 1;
+        Self::_unindex_due(&env, &recurring);
+        for cycle in 0..cycles {
+            let paid = if renewal {
+                Self::_renew_subscription(&env, &recurring, &token_client, fee)?
+            } else {
+                token_client.transfer(&contract, &recurring.recipient, &amount);
+                amount
+            };
+            recurring.total_charged += paid + fee;
+            recurring.total_payments += 1;
+
+            pulsar_common_admin::publish_event(
+                &env,
+                symbol_short!("recurring"),
+                symbol_short!("paid"),
+                (payment_id, paid, recurring.next_payment + cycle * interval),
+            );
+        }
+        let fees = fee * cycles as i128;
+        if fees > 0 {
+            token_client.transfer(&contract, &caller, &fees);
+        }
+
+        recurring.failed_attempts = 0;
         recurring.last_payment = now;
-        recurring.next_payment = now + recurring.interval_secs;
+        recurring.next_payment = match recurring.catch_up {
+            CatchUpPolicy::SkipMissed => recurring.next_payment + due_cycles * interval,
+            CatchUpPolicy::CatchUpAll(_) => recurring.next_payment + cycles * interval,
+            CatchUpPolicy::ProRate => now + interval,
+        };
+        let skipped = match recurring.catch_up {
+            CatchUpPolicy::SkipMissed => due_cycles - 1,
+            _ => 0,
+        };
+        if let Some(amendment) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, PendingAmendment>(&DataKey::Amendment(payment_id))
+        {
+            recurring.amount = amendment.amount;
+            recurring.interval_secs = amendment.interval_secs;
+            recurring.next_payment = now + amendment.interval_secs;
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Amendment(payment_id));
+            pulsar_common_admin::publish_event(
+                &env,
+                symbol_short!("recurring"),
+                symbol_short!("amended"),
+                (payment_id, amendment.amount, amendment.interval_secs),
+            );
+        }
+
+        // The last allowed payment closes the schedule instead of re-indexing it
+        let completed = recurring.max_payments == Some(recurring.total_payments);
+        if completed {
+            recurring.status = RecurringStatus::Cancelled;
+        }
 
         let _ttl_key = DataKey::Payment(payment_id);
         env.storage().persistent().set(&_ttl_key, &recurring);
@@ -187,14 +433,104 @@ impl RecurringPaymentContract {
             &_ttl_key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT);
+        if completed {
+            pulsar_common_admin::publish_event(
+                &env,
+                symbol_short!("recurring"),
+                symbol_short!("completed"),
+                payment_id,
+            );
+        } else {
+            Self::_index_due(&env, &recurring);
+        }
+
+        if skipped > 0 {
+            pulsar_common_admin::publish_event(
+                &env,
+                symbol_short!("recurring"),
+                symbol_short!("skipped"),
+                (payment_id, skipped),
+            );
+        }
+        if fees > 0 {
+            pulsar_common_admin::publish_event(
+                &env,
+                symbol_short!("recurring"),
+                symbol_short!("keeper"),
+                (payment_id, caller, fees),
+            );
+        }
+        Ok(true)
+    }
+
+    /// How missed cycles are charged on a late execution
+    pub fn set_catch_up_policy(
+        env: Env,
+        payer: Address,
+        payment_id: u64,
+        policy: CatchUpPolicy,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        payer.require_auth();
 
-        env.events().publish(
-            (symbol_short!("recurring"), symbol_short!("paid")),
-            (payment_id, recurring.amount),
+        let mut recurring: RecurringPayment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Payment(payment_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if recurring.payer != payer {
+            return Err(PulsarError::Unauthorized);
+        }
+        if let CatchUpPolicy::CatchUpAll(max) = policy {
+            if max == 0 || max > MAX_CATCH_UP_CYCLES {
+                return Err(PulsarError::InvalidInput);
+            }
+        }
+
+        recurring.catch_up = policy;
+        Self::_save_payment(&env, &recurring);
+        Ok(())
+    }
+
+    /// Bring a Failed schedule back; the overdue charge becomes executable again
+    pub fn reactivate(env: Env, payer: Address, payment_id: u64) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        payer.require_auth();
+
+        let mut recurring: RecurringPayment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Payment(payment_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if recurring.payer != payer {
+            return Err(PulsarError::Unauthorized);
+        }
+        if recurring.status != RecurringStatus::Failed {
+            return Err(PulsarError::InvalidState);
+        }
+
+        recurring.status = RecurringStatus::Active;
+        recurring.failed_attempts = 0;
+        recurring.last_failed_at = 0;
+        Self::_save_payment(&env, &recurring);
+        Self::_index_due(&env, &recurring);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("recurring"),
+            symbol_short!("reactive"),
+            payment_id,
         );
+        Ok(())
     }
 
-    pub fn pause_payment(env: Env, payer: Address, payment_id: u64) {
+    pub fn pause_payment(env: Env, payer: Address, payment_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -204,12 +540,17 @@ impl RecurringPaymentContract {
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("payment not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if recurring.payer != payer {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
+        }
+
+        if recurring.status != RecurringStatus::Active {
+            return Err(PulsarError::InvalidState);
         }
 
+        Self::_unindex_due(&env, &recurring);
         recurring.status = RecurringStatus::Paused;
         let _ttl_key = DataKey::Payment(payment_id);
         env.storage().persistent().set(&_ttl_key, &recurring);
@@ -218,9 +559,18 @@ impl RecurringPaymentContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("recurring"),
+            symbol_short!("paused"),
+            payment_id,
+        );
+        Ok(())
     }
 
-    pub fn resume_payment(env: Env, payer: Address, payment_id: u64) {
+    /// Resume a paused schedule; the next charge falls one interval from now
+    pub fn resume_payment(env: Env, payer: Address, payment_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -230,10 +580,14 @@ impl RecurringPaymentContract {
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("payment not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if recurring.payer != payer {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
+        }
+
+        if recurring.status != RecurringStatus::Paused {
+            return Err(PulsarError::InvalidState);
         }
 
         recurring.status = RecurringStatus::Active;
@@ -245,9 +599,18 @@ impl RecurringPaymentContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Self::_index_due(&env, &recurring);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("recurring"),
+            symbol_short!("resumed"),
+            (payment_id, recurring.next_payment),
+        );
+        Ok(())
     }
 
-    pub fn cancel_payment(env: Env, payer: Address, payment_id: u64) {
+    pub fn cancel_recurring(env: Env, payer: Address, payment_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -257,12 +620,17 @@ impl RecurringPaymentContract {
             .storage()
             .persistent()
             .get(&DataKey::Payment(payment_id))
-            .expect("payment not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if recurring.payer != payer {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
+        if recurring.status == RecurringStatus::Cancelled {
+            return Err(PulsarError::AlreadyExists);
+        }
+
+        Self::_unindex_due(&env, &recurring);
         recurring.status = RecurringStatus::Cancelled;
         let _ttl_key = DataKey::Payment(payment_id);
         env.storage().persistent().set(&_ttl_key, &recurring);
@@ -271,6 +639,197 @@ impl RecurringPaymentContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Amendment(payment_id));
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("recurring"),
+            symbol_short!("cancelled"),
+            payment_id,
+        );
+        Ok(())
+    }
+
+    /// Queue new terms; the already scheduled charge still uses the old ones
+    pub fn amend(
+        env: Env,
+        payer: Address,
+        payment_id: u64,
+        new_amount: i128,
+        new_interval: u64,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        payer.require_auth();
+
+        let recurring: RecurringPayment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Payment(payment_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if recurring.payer != payer {
+            return Err(PulsarError::Unauthorized);
+        }
+        if recurring.status == RecurringStatus::Cancelled {
+            return Err(PulsarError::InvalidState);
+        }
+        if new_amount <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        if new_interval == 0 {
+            return Err(PulsarError::InvalidInput);
+        }
+        let mut amended = recurring.clone();
+        amended.amount = new_amount;
+        if new_amount + Self::_keeper_fee(&amended) > recurring.max_per_charge {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        let amendment = PendingAmendment {
+            amount: new_amount,
+            interval_secs: new_interval,
+            requested_at: env.ledger().timestamp(),
+        };
+        let _ttl_key = DataKey::Amendment(payment_id);
+        env.storage().persistent().set(&_ttl_key, &amendment);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("recurring"),
+            symbol_short!("amend_req"),
+            (payment_id, new_amount, new_interval),
+        );
+        Ok(())
+    }
+
+    pub fn get_pending_amendment(env: Env, payment_id: u64) -> Option<PendingAmendment> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Amendment(payment_id))
+    }
+
+    pub fn set_keeper_fee(
+        env: Env,
+        payer: Address,
+        payment_id: u64,
+        fee: KeeperFee,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        payer.require_auth();
+
+        let mut recurring: RecurringPayment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Payment(payment_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if recurring.payer != payer {
+            return Err(PulsarError::Unauthorized);
+        }
+        match &fee {
+            KeeperFee::Flat(amount) if *amount <= 0 => return Err(PulsarError::InvalidAmount),
+            KeeperFee::Bps(bps) if *bps == 0 || *bps > 10_000 => {
+                return Err(PulsarError::InvalidAmount)
+            }
+            _ => {}
+        }
+
+        recurring.keeper_fee = fee;
+        if recurring.amount + Self::_keeper_fee(&recurring) > recurring.max_per_charge {
+            return Err(PulsarError::LimitExceeded);
+        }
+        Self::_save_payment(&env, &recurring);
+        Ok(())
+    }
+
+    /// Flag a schedule as a subscription-manager renewal. The recipient must
+    /// be that contract; it charges the plan price from the payer's allowance
+    /// to it, so keepers can renew without the payer's signature. The
+    /// subscription itself must have auto-renewal on.
+    pub fn set_subscription_renewal(
+        env: Env,
+        payer: Address,
+        payment_id: u64,
+        renewal: SubscriptionRenewal,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        payer.require_auth();
+
+        let mut recurring: RecurringPayment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Payment(payment_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if recurring.payer != payer {
+            return Err(PulsarError::Unauthorized);
+        }
+        if recurring.status == RecurringStatus::Cancelled {
+            return Err(PulsarError::InvalidState);
+        }
+
+        recurring.renewal = renewal;
+        Self::_save_payment(&env, &recurring);
+        Ok(())
+    }
+
+    /// Active schedules due at `now`, oldest bucket first. Skips `start`
+    /// matches and returns at most MAX_PAGE_SIZE; scans up to MAX_BUCKET_SCAN
+    /// daily buckets.
+    pub fn list_due(env: Env, now: u64, start: u32, limit: u32) -> Vec<u64> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let mut due = Vec::new(&env);
+        let Some(floor) = env.storage().instance().get::<DataKey, u64>(&DataKey::DueFloor) else {
+            return due;
+        };
+
+        let last = (now / DUE_BUCKET_SECS).min(floor + MAX_BUCKET_SCAN - 1);
+        let mut skipped: u32 = 0;
+        for bucket in floor..=last {
+            let ids: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::DueBucket(bucket))
+                .unwrap_or(Vec::new(&env));
+            for id in ids.iter() {
+                let recurring: RecurringPayment = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::Payment(id))
+                    .unwrap();
+                if recurring.status != RecurringStatus::Active || recurring.next_payment > now {
+                    continue;
+                }
+                if skipped < start {
+                    skipped += 1;
+                    continue;
+                }
+                due.push_back(id);
+                if due.len() >= limit {
+                    return due;
+                }
+            }
+        }
+        due
     }
 
     pub fn get_payment(env: Env, payment_id: u64) -> Option<RecurringPayment> {
@@ -282,18 +841,289 @@ impl RecurringPaymentContract {
             .get(&DataKey::Payment(payment_id))
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn get_payer_schedule_count(env: Env, payer: Address) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::PayerScheduleCount(payer))
+            .unwrap_or(0)
+    }
+
+    /// Schedules created by `payer` in creation order, any status; callers
+    /// filter on `status` for the active ones.
+    pub fn list_by_payer(
+        env: Env,
+        payer: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<RecurringPayment> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PayerScheduleCount(payer.clone()))
+            .unwrap_or(0);
+        Self::_collect_indexed(&env, count, start, limit, |i| {
+            DataKey::SchedulesByPayer(payer.clone(), i)
+        })
+    }
+
+    pub fn get_recipient_schedule_count(env: Env, recipient: Address) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::RecipientScheduleCount(recipient))
+            .unwrap_or(0)
+    }
+
+    /// Schedules paying `recipient`, paged by index position.
+    pub fn list_by_recipient(
+        env: Env,
+        recipient: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<RecurringPayment> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RecipientScheduleCount(recipient.clone()))
+            .unwrap_or(0);
+        Self::_collect_indexed(&env, count, start, limit, |i| {
+            DataKey::SchedulesByRecipient(recipient.clone(), i)
+        })
+    }
+
+    fn _mark_failed(env: &Env, recurring: &mut RecurringPayment) -> Result<(), PulsarError> {
+        let now = env.ledger().timestamp();
+        if recurring.failed_attempts > 0 && now < recurring.last_failed_at + RETRY_DELAY_SECS {
+            return Err(PulsarError::TooEarly);
+        }
+
+        let max_attempts: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxFailedAttempts)
+            .unwrap_or(DEFAULT_MAX_FAILED_ATTEMPTS);
+        recurring.failed_attempts += 1;
+        recurring.last_failed_at = now;
+
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("recurring"),
+            symbol_short!("failed"),
+            (recurring.payment_id, recurring.failed_attempts),
+        );
+        if recurring.failed_attempts >= max_attempts {
+            Self::_unindex_due(env, recurring);
+            recurring.status = RecurringStatus::Failed;
+            pulsar_common_admin::publish_event(
+                env,
+                symbol_short!("recurring"),
+                symbol_short!("suspended"),
+                recurring.payment_id,
+            );
+        }
+        Self::_save_payment(env, recurring);
+        Ok(())
+    }
+
+    /// Calls `renew_from_allowance()` on the recipient for the payer and
+    /// returns what it actually charged, checked against the payer's caps.
+    fn _renew_subscription(
+        env: &Env,
+        recurring: &RecurringPayment,
+        token_client: &token::Client,
+        fee: i128,
+    ) -> Result<i128, PulsarError> {
+        let is_annual = matches!(recurring.renewal, SubscriptionRenewal::Annual);
+        let before = token_client.balance(&recurring.payer);
+        env.invoke_contract::<()>(
+            &recurring.recipient,
+            &Symbol::new(env, "renew_from_allowance"),
+            Vec::from_array(env, [recurring.payer.into_val(env), is_annual.into_val(env)]),
+        );
+        let spent = before - token_client.balance(&recurring.payer);
+        if spent + fee > recurring.max_per_charge {
+            return Err(PulsarError::LimitExceeded);
+        }
+        if recurring.total_charged + spent + fee > recurring.max_total {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("recurring"),
+            symbol_short!("renewed"),
+            (recurring.payment_id, spent, is_annual),
+        );
+        Ok(spent)
+    }
+
+    fn _keeper_fee(recurring: &RecurringPayment) -> i128 {
+        Self::_fee_on(&recurring.keeper_fee, recurring.amount)
+    }
+
+    fn _fee_on(fee: &KeeperFee, amount: i128) -> i128 {
+        match fee {
+            KeeperFee::Disabled => 0,
+            KeeperFee::Flat(flat) => *flat,
+            KeeperFee::Bps(bps) => amount * *bps as i128 / 10_000,
+        }
+    }
+
+    /// Largest charge amount that still fits max_per_charge once the keeper
+    /// fee is added on top
+    fn _max_amount_under_cap(recurring: &RecurringPayment) -> i128 {
+        match recurring.keeper_fee {
+            KeeperFee::Disabled => recurring.max_per_charge,
+            KeeperFee::Flat(flat) => recurring.max_per_charge - flat,
+            KeeperFee::Bps(bps) => recurring.max_per_charge * 10_000 / (10_000 + bps as i128),
+        }
+    }
+
+    fn _index_due(env: &Env, recurring: &RecurringPayment) {
+        let bucket = recurring.next_payment / DUE_BUCKET_SECS;
+        let _ttl_key = DataKey::DueBucket(bucket);
+        let mut ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&_ttl_key)
+            .unwrap_or(Vec::new(env));
+        ids.push_back(recurring.payment_id);
+        env.storage().persistent().set(&_ttl_key, &ids);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let floor: Option<u64> = env.storage().instance().get(&DataKey::DueFloor);
+        if floor.is_none_or(|f| bucket < f) {
+            env.storage().instance().set(&DataKey::DueFloor, &bucket);
+        }
+    }
+
+    fn _unindex_due(env: &Env, recurring: &RecurringPayment) {
+        if recurring.status != RecurringStatus::Active {
+            return;
+        }
+        let bucket = recurring.next_payment / DUE_BUCKET_SECS;
+        let _ttl_key = DataKey::DueBucket(bucket);
+        let Some(mut ids) = env.storage().persistent().get::<DataKey, Vec<u64>>(&_ttl_key) else {
+            return;
+        };
+        if let Some(index) = ids.first_index_of(recurring.payment_id) {
+            ids.remove(index);
+        }
+        if !ids.is_empty() {
+            env.storage().persistent().set(&_ttl_key, &ids);
+            return;
+        }
+        env.storage().persistent().remove(&_ttl_key);
+
+        // Move the floor past buckets that are now known to be empty
+        let floor: u64 = env.storage().instance().get(&DataKey::DueFloor).unwrap_or(0);
+        if bucket != floor {
+            return;
+        }
+        let mut next = floor + 1;
+        while next < floor + MAX_BUCKET_SCAN
+            && !env.storage().persistent().has(&DataKey::DueBucket(next))
+        {
+            next += 1;
+        }
+        env.storage().instance().set(&DataKey::DueFloor, &next);
+    }
+
+    fn _index_schedule<F>(env: &Env, count_key: DataKey, entry_key: F, payment_id: u64)
+    where
+        F: Fn(u32) -> DataKey,
+    {
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let key = entry_key(count);
+        env.storage().persistent().set(&key, &payment_id);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage().persistent().set(&count_key, &(count + 1));
+        env.storage().persistent().extend_ttl(
+            &count_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _collect_indexed<F>(
+        env: &Env,
+        count: u32,
+        start: u32,
+        limit: u32,
+        entry_key: F,
+    ) -> Vec<RecurringPayment>
+    where
+        F: Fn(u32) -> DataKey,
+    {
+        let end = count.min(start.saturating_add(limit.min(MAX_PAGE_SIZE)));
+        let mut result = Vec::new(env);
+        for i in start..end {
+            if let Some(payment_id) = env.storage().persistent().get::<DataKey, u64>(&entry_key(i)) {
+                if let Some(recurring) = env
+                    .storage()
+                    .persistent()
+                    .get::<DataKey, RecurringPayment>(&DataKey::Payment(payment_id))
+                {
+                    result.push_back(recurring);
+                }
+            }
+        }
+        result
+    }
+
+    fn _save_payment(env: &Env, recurring: &RecurringPayment) {
+        let _ttl_key = DataKey::Payment(recurring.payment_id);
+        env.storage().persistent().set(&_ttl_key, recurring);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
@@ -1,6 +1,6 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::{Address as _, Ledger as _}, token::StellarAssetClient, Address, Env};
+use soroban_sdk::{testutils::{Address as _, Ledger as _}, token::{StellarAssetClient, TokenClient}, vec, Address, Env};
 
 fn deploy_token(env: &Env, admin: &Address) -> Address {
     env.register_stellar_asset_contract_v2(admin.clone())
@@ -11,9 +11,56 @@ fn mint(env: &Env, token: &Address, to: &Address, amount: i128) {
     StellarAssetClient::new(env, token).mint(to, &amount);
 }
 
+fn approve(
+    env: &Env,
+    c: &RecurringPaymentContractClient<'_>,
+    token: &Address,
+    payer: &Address,
+    amount: i128,
+) {
+    let expiration = env.ledger().sequence() + 10_000;
+    TokenClient::new(env, token).approve(payer, &c.address, &amount, &expiration);
+}
+
+fn caps(max_per_charge: i128, max_total: i128) -> SpendingCaps {
+    SpendingCaps {
+        max_per_charge,
+        max_total,
+    }
+}
+
+mod subscription_mock {
+    use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Env};
+
+    #[contract]
+    pub struct MockSubscriptionManager;
+
+    #[contractimpl]
+    impl MockSubscriptionManager {
+        pub fn init(env: Env, token: Address, price: i128) {
+            env.storage().instance().set(&symbol_short!("token"), &token);
+            env.storage().instance().set(&symbol_short!("price"), &price);
+        }
+
+        pub fn renew_from_allowance(env: Env, subscriber: Address, is_annual: bool) {
+            let token: Address = env.storage().instance().get(&symbol_short!("token")).unwrap();
+            let price: i128 = env.storage().instance().get(&symbol_short!("price")).unwrap();
+            let amount = if is_annual { price * 10 } else { price };
+            let contract = env.current_contract_address();
+            token::Client::new(&env, &token).transfer_from(&contract, &subscriber, &contract, &amount);
+            let renewals: u32 = env.storage().instance().get(&symbol_short!("renewals")).unwrap_or(0);
+            env.storage().instance().set(&symbol_short!("renewals"), &(renewals + 1));
+        }
+
+        pub fn renewals(env: Env) -> u32 {
+            env.storage().instance().get(&symbol_short!("renewals")).unwrap_or(0)
+        }
+    }
+}
+
 fn setup(env: &Env) -> (RecurringPaymentContractClient<'_>, Address) {
     let admin = Address::generate(env);
-    let id = env.register_contract(None, RecurringPaymentContract);
+    let id = env.register(RecurringPaymentContract, ());
     let c = RecurringPaymentContractClient::new(env, &id);
     c.initialize(&admin);
     (c, admin)
@@ -27,15 +74,14 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
-    let id = env.register_contract(None, RecurringPaymentContract);
+    let id = env.register(RecurringPaymentContract, ());
     let c = RecurringPaymentContractClient::new(&env, &id);
     let a = Address::generate(&env);
     c.initialize(&a);
-    c.initialize(&a);
+    assert_eq!(c.try_initialize(&a), Err(Ok(PulsarError::AlreadyInitialized)));
 }
 
 #[test]
@@ -46,7 +92,7 @@ fn test_create_recurring() {
     let payer = Address::generate(&env);
     let payee = Address::generate(&env);
     let token = Address::generate(&env);
-    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &86_400u64, &Some(12u32));
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &86_400u64, &Some(12u32), &caps(1000, 10_000));
     assert_eq!(id, 1);
     let payment = c.get_payment(&id).unwrap();
     assert!(matches!(payment.status, RecurringStatus::Active));
@@ -61,7 +107,7 @@ fn test_create_recurring_no_limit() {
     let payer = Address::generate(&env);
     let payee = Address::generate(&env);
     let token = Address::generate(&env);
-    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &86_400u64, &None);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &86_400u64, &None, &caps(1000, 10_000));
     assert_eq!(id, 1);
 }
 
@@ -73,7 +119,7 @@ fn test_pause_payment() {
     let payer = Address::generate(&env);
     let payee = Address::generate(&env);
     let token = Address::generate(&env);
-    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &86_400u64, &None);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &86_400u64, &None, &caps(1000, 10_000));
     c.pause_payment(&payer, &id);
     let payment = c.get_payment(&id).unwrap();
     assert!(matches!(payment.status, RecurringStatus::Paused));
@@ -87,7 +133,7 @@ fn test_resume_payment() {
     let payer = Address::generate(&env);
     let payee = Address::generate(&env);
     let token = Address::generate(&env);
-    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &86_400u64, &None);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &86_400u64, &None, &caps(1000, 10_000));
     c.pause_payment(&payer, &id);
     c.resume_payment(&payer, &id);
     let payment = c.get_payment(&id).unwrap();
@@ -102,8 +148,8 @@ fn test_cancel_payment() {
     let payer = Address::generate(&env);
     let payee = Address::generate(&env);
     let token = Address::generate(&env);
-    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &86_400u64, &None);
-    c.cancel_payment(&payer, &id);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &86_400u64, &None, &caps(1000, 10_000));
+    c.cancel_recurring(&payer, &id);
     let payment = c.get_payment(&id).unwrap();
     assert!(matches!(payment.status, RecurringStatus::Cancelled));
 }
@@ -125,7 +171,8 @@ fn test_execute_payment_by_payer() {
     let payee = Address::generate(&env);
     let token = deploy_token(&env, &admin);
     mint(&env, &token, &payer, 10_000);
-    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &1u64, &None);
+    approve(&env, &c, &token, &payer, 10_000);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &1u64, &None, &caps(1000, 10_000));
     
     // Fast forward time to allow execution
     env.ledger().with_mut(|li| li.timestamp = 2);
@@ -145,7 +192,8 @@ fn test_execute_payment_by_recipient() {
     let payee = Address::generate(&env);
     let token = deploy_token(&env, &admin);
     mint(&env, &token, &payer, 10_000);
-    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &1u64, &None);
+    approve(&env, &c, &token, &payer, 10_000);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &1u64, &None, &caps(1000, 10_000));
     
     // Fast forward time to allow execution
     env.ledger().with_mut(|li| li.timestamp = 2);
@@ -165,7 +213,8 @@ fn test_execute_payment_by_admin() {
     let payee = Address::generate(&env);
     let token = deploy_token(&env, &admin);
     mint(&env, &token, &payer, 10_000);
-    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &1u64, &None);
+    approve(&env, &c, &token, &payer, 10_000);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &1u64, &None, &caps(1000, 10_000));
     
     // Fast forward time to allow execution
     env.ledger().with_mut(|li| li.timestamp = 2);
@@ -177,7 +226,6 @@ fn test_execute_payment_by_admin() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_execute_payment_by_stranger_fails() {
     let env = Env::default();
     env.mock_all_auths();
@@ -186,17 +234,16 @@ fn test_execute_payment_by_stranger_fails() {
     let payee = Address::generate(&env);
     let token = Address::generate(&env);
     let stranger = Address::generate(&env);
-    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &1u64, &None);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &1u64, &None, &caps(1000, 10_000));
     
     // Fast forward time to allow execution
     env.ledger().with_mut(|li| li.timestamp = 2);
     
     // Stranger cannot execute
-    c.execute_payment(&stranger, &id);
+    assert_eq!(c.try_execute_payment(&stranger, &id), Err(Ok(PulsarError::Unauthorized)));
 }
 
 #[test]
-#[should_panic(expected = "too early")]
 fn test_execute_payment_too_early() {
     let env = Env::default();
     env.mock_all_auths();
@@ -204,14 +251,13 @@ fn test_execute_payment_too_early() {
     let payer = Address::generate(&env);
     let payee = Address::generate(&env);
     let token = Address::generate(&env);
-    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &86_400u64, &None);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &86_400u64, &None, &caps(1000, 10_000));
     
     // Try to execute immediately (too early)
-    c.execute_payment(&payer, &id);
+    assert_eq!(c.try_execute_payment(&payer, &id), Err(Ok(PulsarError::TooEarly)));
 }
 
 #[test]
-#[should_panic(expected = "max payments reached")]
 fn test_execute_payment_max_reached() {
     let env = Env::default();
     env.mock_all_auths();
@@ -220,13 +266,435 @@ fn test_execute_payment_max_reached() {
     let payee = Address::generate(&env);
     let token = deploy_token(&env, &admin);
     mint(&env, &token, &payer, 10_000);
-    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &1u64, &Some(1u32));
+    approve(&env, &c, &token, &payer, 10_000);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &1u64, &Some(1u32), &caps(1000, 10_000));
     
     // Execute first payment
     env.ledger().with_mut(|li| li.timestamp = 2);
     c.execute_payment(&payer, &id);
     
-    // Try to execute second payment (should fail - max reached)
+    // The last allowed payment closed the schedule
+    assert!(matches!(c.get_payment(&id).unwrap().status, RecurringStatus::Cancelled));
     env.ledger().with_mut(|li| li.timestamp = 4);
+    assert_eq!(c.try_execute_payment(&payer, &id), Err(Ok(PulsarError::InvalidState)));
+}
+
+#[test]
+fn test_capped_schedule_leaves_due_index() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = deploy_token(&env, &admin);
+    mint(&env, &token, &payer, 10_000);
+    approve(&env, &c, &token, &payer, 10_000);
+    let id = c.create_recurring(&payer, &payee, &token, &100i128, &86_400u64, &Some(3u32), &caps(100, 10_000));
+
+    for day in 1..=3u64 {
+        env.ledger().with_mut(|li| li.timestamp = day * 86_400);
+        assert!(c.execute_payment(&payer, &id));
+    }
+
+    let payment = c.get_payment(&id).unwrap();
+    assert_eq!(payment.total_payments, 3);
+    assert!(matches!(payment.status, RecurringStatus::Cancelled));
+    assert_eq!(c.list_due(&(400 * 86_400u64), &0u32, &10u32).len(), 0);
+    assert_eq!(TokenClient::new(&env, &token).balance(&payee), 300);
+}
+
+#[test]
+fn test_execute_payment_pulls_from_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = deploy_token(&env, &admin);
+    mint(&env, &token, &payer, 10_000);
+    approve(&env, &c, &token, &payer, 3_000);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &1u64, &None, &caps(1000, 10_000));
+
+    env.ledger().with_mut(|li| li.timestamp = 2);
+    c.execute_payment(&payee, &id);
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&payee), 1000);
+    assert_eq!(tc.allowance(&payer, &c.address), 2_000);
+    assert_eq!(c.get_payment(&id).unwrap().total_charged, 1000);
+}
+
+#[test]
+fn test_execute_payment_without_allowance_counts_failure() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = deploy_token(&env, &admin);
+    mint(&env, &token, &payer, 10_000);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &1u64, &None, &caps(1000, 10_000));
+    env.ledger().with_mut(|li| li.timestamp = 2);
+    assert!(!c.execute_payment(&payee, &id));
+    let payment = c.get_payment(&id).unwrap();
+    assert_eq!(payment.failed_attempts, 1);
+    assert_eq!(payment.total_payments, 0);
+    assert!(matches!(payment.status, RecurringStatus::Active));
+}
+
+#[test]
+fn test_repeated_failures_suspend_until_reactivated() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    c.set_max_failed_attempts(&admin, &2u32);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = deploy_token(&env, &admin);
+    approve(&env, &c, &token, &payer, 10_000);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &1u64, &None, &caps(1000, 10_000));
+
+    env.ledger().with_mut(|li| li.timestamp = 2);
+    assert!(!c.execute_payment(&payee, &id));
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    assert!(!c.execute_payment(&payee, &id));
+    assert!(matches!(c.get_payment(&id).unwrap().status, RecurringStatus::Failed));
+
+    mint(&env, &token, &payer, 10_000);
+    c.reactivate(&payer, &id);
+    assert!(c.execute_payment(&payee, &id));
+    let payment = c.get_payment(&id).unwrap();
+    assert_eq!(payment.failed_attempts, 0);
+    assert_eq!(payment.total_payments, 1);
+}
+
+#[test]
+fn test_failed_payment_retry_too_soon() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = deploy_token(&env, &admin);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &1u64, &None, &caps(1000, 10_000));
+    env.ledger().with_mut(|li| li.timestamp = 2);
+    c.execute_payment(&payee, &id);
+    assert_eq!(c.try_execute_payment(&payee, &id), Err(Ok(PulsarError::TooEarly)));
+}
+
+#[test]
+fn test_execute_payment_total_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = deploy_token(&env, &admin);
+    mint(&env, &token, &payer, 10_000);
+    approve(&env, &c, &token, &payer, 10_000);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &1u64, &None, &caps(1000, 1500));
+    env.ledger().with_mut(|li| li.timestamp = 2);
+    c.execute_payment(&payee, &id);
+    env.ledger().with_mut(|li| li.timestamp = 4);
+    assert_eq!(c.try_execute_payment(&payee, &id), Err(Ok(PulsarError::LimitExceeded)));
+}
+
+#[test]
+fn test_create_recurring_amount_over_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = Address::generate(&env);
+    assert_eq!(
+        c.try_create_recurring(&payer, &payee, &token, &1000i128, &1u64, &None, &caps(500, 10_000)),
+        Err(Ok(PulsarError::LimitExceeded))
+    );
+}
+
+#[test]
+fn test_keeper_executes_for_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let token = deploy_token(&env, &admin);
+    mint(&env, &token, &payer, 10_000);
+    approve(&env, &c, &token, &payer, 10_000);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &1u64, &None, &caps(1100, 10_000));
+    c.set_keeper_fee(&payer, &id, &KeeperFee::Bps(500));
+
+    env.ledger().with_mut(|li| li.timestamp = 2);
+    assert!(c.execute_payment(&keeper, &id));
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&payee), 1000);
+    assert_eq!(tc.balance(&keeper), 50);
+    assert_eq!(tc.balance(&payer), 8_950);
+    assert_eq!(c.get_payment(&id).unwrap().total_charged, 1050);
+}
+
+#[test]
+fn test_keeper_fee_must_fit_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = Address::generate(&env);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &1u64, &None, &caps(1000, 10_000));
+    assert_eq!(
+        c.try_set_keeper_fee(&payer, &id, &KeeperFee::Flat(10)),
+        Err(Ok(PulsarError::LimitExceeded))
+    );
+}
+
+#[test]
+fn test_list_due() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = deploy_token(&env, &admin);
+    mint(&env, &token, &payer, 10_000);
+    approve(&env, &c, &token, &payer, 10_000);
+    let daily = c.create_recurring(&payer, &payee, &token, &100i128, &86_400u64, &None, &caps(100, 10_000));
+    let weekly = c.create_recurring(&payer, &payee, &token, &100i128, &604_800u64, &None, &caps(100, 10_000));
+    let paused = c.create_recurring(&payer, &payee, &token, &100i128, &86_400u64, &None, &caps(100, 10_000));
+    c.pause_payment(&payer, &paused);
+
+    assert_eq!(c.list_due(&0u64, &0u32, &10u32).len(), 0);
+    assert_eq!(c.list_due(&86_400u64, &0u32, &10u32), vec![&env, daily]);
+
+    env.ledger().with_mut(|li| li.timestamp = 604_800);
+    assert_eq!(c.list_due(&604_800u64, &0u32, &10u32), vec![&env, daily, weekly]);
+    assert_eq!(c.list_due(&604_800u64, &1u32, &10u32), vec![&env, weekly]);
+
+    c.execute_payment(&payer, &daily);
+    assert_eq!(c.list_due(&604_800u64, &0u32, &10u32), vec![&env, weekly]);
+    assert_eq!(c.list_due(&691_200u64, &0u32, &10u32), vec![&env, weekly, daily]);
+}
+
+#[test]
+fn test_resume_cancelled_payment_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = Address::generate(&env);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &86_400u64, &None, &caps(1000, 10_000));
+    c.cancel_recurring(&payer, &id);
+    assert_eq!(c.try_resume_payment(&payer, &id), Err(Ok(PulsarError::InvalidState)));
+}
+
+#[test]
+fn test_resume_recomputes_next_payment() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = Address::generate(&env);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &86_400u64, &None, &caps(1000, 10_000));
+    c.pause_payment(&payer, &id);
+    env.ledger().with_mut(|li| li.timestamp = 500_000);
+    c.resume_payment(&payer, &id);
+    assert_eq!(c.get_payment(&id).unwrap().next_payment, 586_400);
+}
+
+#[test]
+fn test_amend_applies_from_next_cycle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = deploy_token(&env, &admin);
+    mint(&env, &token, &payer, 10_000);
+    approve(&env, &c, &token, &payer, 10_000);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &100u64, &None, &caps(1000, 10_000));
+    c.amend(&payer, &id, &600i128, &300u64);
+    assert_eq!(c.get_pending_amendment(&id).unwrap().amount, 600);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
     c.execute_payment(&payer, &id);
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&payee), 1000);
+    let payment = c.get_payment(&id).unwrap();
+    assert_eq!(payment.amount, 600);
+    assert_eq!(payment.interval_secs, 300);
+    assert_eq!(payment.next_payment, 400);
+    assert!(c.get_pending_amendment(&id).is_none());
+
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    c.execute_payment(&payer, &id);
+    assert_eq!(tc.balance(&payee), 1600);
+}
+
+#[test]
+fn test_amend_over_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = Address::generate(&env);
+    let id = c.create_recurring(&payer, &payee, &token, &1000i128, &100u64, &None, &caps(1000, 10_000));
+    assert_eq!(c.try_amend(&payer, &id, &1001i128, &100u64), Err(Ok(PulsarError::LimitExceeded)));
+}
+
+#[test]
+fn test_list_by_payer_and_recipient() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let payer = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    let other = Address::generate(&env);
+    let token = Address::generate(&env);
+    let first = c.create_recurring(&payer, &merchant, &token, &100i128, &100u64, &None, &caps(100, 1000));
+    let second = c.create_recurring(&payer, &other, &token, &100i128, &100u64, &None, &caps(100, 1000));
+    c.create_recurring(&other, &merchant, &token, &100i128, &100u64, &None, &caps(100, 1000));
+    c.cancel_recurring(&payer, &second);
+
+    assert_eq!(c.get_payer_schedule_count(&payer), 2);
+    let mine = c.list_by_payer(&payer, &0, &10);
+    assert_eq!(mine.len(), 2);
+    assert_eq!(mine.get(0).unwrap().payment_id, first);
+    assert!(mine.get(1).unwrap().status == RecurringStatus::Cancelled);
+
+    assert_eq!(c.get_recipient_schedule_count(&merchant), 2);
+    let page = c.list_by_recipient(&merchant, &1, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().payer, other);
+}
+
+#[test]
+fn test_subscription_renewal_calls_renew() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let payer = Address::generate(&env);
+    let token = deploy_token(&env, &admin);
+    mint(&env, &token, &payer, 10_000);
+    let sub_id = env.register(subscription_mock::MockSubscriptionManager, ());
+    let sub = subscription_mock::MockSubscriptionManagerClient::new(&env, &sub_id);
+    sub.init(&token, &500);
+    TokenClient::new(&env, &token).approve(&payer, &sub_id, &10_000, &(env.ledger().sequence() + 10_000));
+
+    let id = c.create_recurring(&payer, &sub_id, &token, &500i128, &100u64, &None, &caps(500, 10_000));
+    c.set_subscription_renewal(&payer, &id, &SubscriptionRenewal::Monthly);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert!(c.execute_payment(&payer, &id));
+    assert_eq!(sub.renewals(), 1);
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&sub_id), 500);
+    assert_eq!(tc.balance(&payer), 9_500);
+    assert_eq!(c.get_payment(&id).unwrap().total_charged, 500);
+}
+
+#[test]
+fn test_subscription_renewal_over_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let payer = Address::generate(&env);
+    let token = deploy_token(&env, &admin);
+    mint(&env, &token, &payer, 10_000);
+    let sub_id = env.register(subscription_mock::MockSubscriptionManager, ());
+    let sub = subscription_mock::MockSubscriptionManagerClient::new(&env, &sub_id);
+    sub.init(&token, &500);
+    TokenClient::new(&env, &token).approve(&payer, &sub_id, &10_000, &(env.ledger().sequence() + 10_000));
+
+    let id = c.create_recurring(&payer, &sub_id, &token, &500i128, &100u64, &None, &caps(500, 10_000));
+    c.set_subscription_renewal(&payer, &id, &SubscriptionRenewal::Annual);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(c.try_execute_payment(&payer, &id), Err(Ok(PulsarError::LimitExceeded)));
+}
+
+#[test]
+fn test_skip_missed_realigns_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = deploy_token(&env, &admin);
+    mint(&env, &token, &payer, 10_000);
+    approve(&env, &c, &token, &payer, 10_000);
+    let id = c.create_recurring(&payer, &payee, &token, &100i128, &100u64, &None, &caps(100, 10_000));
+
+    env.ledger().with_mut(|li| li.timestamp = 350);
+    assert!(c.execute_payment(&payer, &id));
+    let payment = c.get_payment(&id).unwrap();
+    assert_eq!(payment.total_payments, 1);
+    assert_eq!(payment.next_payment, 400);
+    assert_eq!(TokenClient::new(&env, &token).balance(&payee), 100);
+}
+
+#[test]
+fn test_catch_up_all_charges_missed_cycles_up_to_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = deploy_token(&env, &admin);
+    mint(&env, &token, &payer, 10_000);
+    approve(&env, &c, &token, &payer, 10_000);
+    let id = c.create_recurring(&payer, &payee, &token, &100i128, &100u64, &None, &caps(100, 10_000));
+    c.set_catch_up_policy(&payer, &id, &CatchUpPolicy::CatchUpAll(3));
+
+    env.ledger().with_mut(|li| li.timestamp = 550);
+    assert!(c.execute_payment(&payer, &id));
+    let payment = c.get_payment(&id).unwrap();
+    assert_eq!(payment.total_payments, 3);
+    assert_eq!(payment.next_payment, 400);
+    assert_eq!(TokenClient::new(&env, &token).balance(&payee), 300);
+
+    // Still behind, so the remaining cycles are due straight away
+    assert!(c.execute_payment(&payer, &id));
+    let payment = c.get_payment(&id).unwrap();
+    assert_eq!(payment.total_payments, 5);
+    assert_eq!(payment.next_payment, 600);
+}
+
+#[test]
+fn test_pro_rate_scales_late_charge() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = deploy_token(&env, &admin);
+    mint(&env, &token, &payer, 10_000);
+    approve(&env, &c, &token, &payer, 10_000);
+    let id = c.create_recurring(&payer, &payee, &token, &100i128, &100u64, &None, &caps(500, 10_000));
+    c.set_catch_up_policy(&payer, &id, &CatchUpPolicy::ProRate);
+
+    env.ledger().with_mut(|li| li.timestamp = 250);
+    assert!(c.execute_payment(&payer, &id));
+    let payment = c.get_payment(&id).unwrap();
+    assert_eq!(payment.total_charged, 250);
+    assert_eq!(payment.next_payment, 350);
+}
+
+#[test]
+fn test_catch_up_cap_must_be_positive() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let payer = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = Address::generate(&env);
+    let id = c.create_recurring(&payer, &payee, &token, &100i128, &100u64, &None, &caps(100, 10_000));
+    assert_eq!(
+        c.try_set_catch_up_policy(&payer, &id, &CatchUpPolicy::CatchUpAll(0)),
+        Err(Ok(PulsarError::InvalidInput))
+    );
 }
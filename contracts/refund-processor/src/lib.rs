@@ -6,6 +6,8 @@ use soroban_sdk::{
     contract, contractimpl, contracttype, symbol_short, token, Address, Env, String,
 };
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone, PartialEq)]
 pub enum RefundStatus {
@@ -52,12 +54,12 @@ pub struct RefundProcessorContract;
 
 #[contractimpl]
 impl RefundProcessorContract {
-    pub fn initialize(env: Env, admin: Address, token: Address) {
+    pub fn initialize(env: Env, admin: Address, token: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -66,6 +68,7 @@ impl RefundProcessorContract {
         env.storage()
             .instance()
             .set(&DataKey::AutoRefundPeriod, &604_800u64); // 7 days
+        Ok(())
     }
 
     pub fn request_refund(
@@ -74,14 +77,14 @@ impl RefundProcessorContract {
         campaign_id: u64,
         amount: i128,
         reason: String,
-    ) -> u64 {
+    ) -> Result<u64, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         requester.require_auth();
 
         if amount <= 0 {
-            panic!("invalid amount");
+            return Err(PulsarError::InvalidAmount);
         }
 
         let counter: u64 = env
@@ -121,27 +124,36 @@ impl RefundProcessorContract {
             .instance()
             .set(&DataKey::RefundCounter, &refund_id);
 
-        refund_id
+        Ok(refund_id)
     }
 
-    pub fn approve_refund(env: Env, admin: Address, refund_id: u64, approved_amount: i128) {
+    pub fn approve_refund(
+        env: Env,
+        admin: Address,
+        refund_id: u64,
+        approved_amount: i128,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut refund: RefundRequest = env
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("refund not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if refund.status != RefundStatus::Requested && refund.status != RefundStatus::UnderReview {
-            panic!("invalid status");
+            return Err(PulsarError::InvalidState);
         }
 
         refund.amount_approved = approved_amount.min(refund.amount_requested);
@@ -155,23 +167,28 @@ impl RefundProcessorContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
-    pub fn reject_refund(env: Env, admin: Address, refund_id: u64) {
+    pub fn reject_refund(env: Env, admin: Address, refund_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut refund: RefundRequest = env
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("refund not found");
+            .ok_or(PulsarError::NotFound)?;
 
         refund.status = RefundStatus::Rejected;
         refund.resolved_at = Some(env.ledger().timestamp());
@@ -183,32 +200,37 @@ impl RefundProcessorContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
-    pub fn process_refund(env: Env, caller: Address, refund_id: u64) {
+    pub fn process_refund(env: Env, caller: Address, refund_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         caller.require_auth();
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         let mut refund: RefundRequest = env
             .storage()
             .persistent()
             .get(&DataKey::Refund(refund_id))
-            .expect("refund not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if caller != admin && caller != refund.requester {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         if refund.status != RefundStatus::Approved {
-            panic!("refund not approved");
+            return Err(PulsarError::InvalidState);
         }
 
         let token_client = token::Client::new(&env, &refund.token);
         let balance = token_client.balance(&env.current_contract_address());
         if balance < refund.amount_approved {
-            panic!("insufficient contract balance for refund");
+            return Err(PulsarError::InsufficientBalance);
         }
         token_client.transfer(
             &env.current_contract_address(),
@@ -225,10 +247,13 @@ impl RefundProcessorContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("refund"), symbol_short!("processed")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("refund"),
+            symbol_short!("processed"),
             (refund_id, refund.amount_approved),
         );
+        Ok(())
     }
 
     pub fn get_refund(env: Env, refund_id: u64) -> Option<RefundRequest> {
@@ -238,18 +263,30 @@ impl RefundProcessorContract {
         env.storage().persistent().get(&DataKey::Refund(refund_id))
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
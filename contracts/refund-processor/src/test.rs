@@ -31,12 +31,14 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, admin, _, token) = setup(&env);
-    c.initialize(&admin, &token);
+    assert_eq!(
+        c.try_initialize(&admin, &token),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -78,14 +80,16 @@ fn test_reject_refund() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_approve_refund_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, _, _, _) = setup(&env);
     let requester = Address::generate(&env);
     let id = c.request_refund(&requester, &1u64, &50_000i128, &s(&env, "reason"));
-    c.approve_refund(&Address::generate(&env), &id, &30_000i128);
+    assert_eq!(
+        c.try_approve_refund(&Address::generate(&env), &id, &30_000i128),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 #[test]
@@ -1,8 +1,17 @@
 //! PulsarTrack - Revenue Settlement (Soroban)
 //! Automated revenue distribution and settlement for the PulsarTrack ecosystem on Stellar.
+//!
+//! Flows are also totalled per epoch (`EPOCH_LENGTH` seconds of ledger time,
+//! counted from 0) so treasury reporting can read `get_current_epoch` and
+//! `get_period_summary` instead of replaying settlements.
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env};
+use pulsar_common_meta::MetaAuth;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, Address, BytesN, Env, Symbol,
+};
+
+pub use pulsar_common_errors::PulsarError;
 
 #[contracttype]
 #[derive(Clone)]
@@ -30,6 +39,18 @@ pub struct SettlementRecord {
     pub settled_at: u64,
 }
 
+/// Totals for one epoch, accumulated as revenue is recorded and paid out
+#[contracttype]
+#[derive(Clone)]
+pub struct PeriodSummary {
+    pub epoch: u32,
+    pub recorded_revenue: i128,
+    pub burned: i128,
+    pub distributed: i128, // sent to the platform or claimed by publishers
+    pub treasury_sent: i128,
+    pub settlements: u32,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
@@ -42,6 +63,7 @@ pub enum DataKey {
     SettlementCounter,
     Settlement(u64),
     PublisherBalance(Address),
+    PeriodSummary(u32),
 }
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
@@ -49,6 +71,9 @@ const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
 const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
 
+/// One reporting quarter (90 days)
+pub const EPOCH_LENGTH: u64 = 7_776_000;
+
 #[contract]
 pub struct RevenueSettlementContract;
 
@@ -60,12 +85,12 @@ impl RevenueSettlementContract {
         token: Address,
         treasury: Address,
         platform: Address,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -94,6 +119,7 @@ impl RevenueSettlementContract {
                 last_settlement: 0,
             },
         );
+        Ok(())
     }
 
     pub fn record_revenue(
@@ -102,14 +128,18 @@ impl RevenueSettlementContract {
         campaign_id: u64,
         amount: i128,
         publisher: Address,
-    ) -> u64 {
+    ) -> Result<u64, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut pool: RevenuePool = env.storage().instance().get(&DataKey::RevenuePool).unwrap();
@@ -173,17 +203,33 @@ impl RevenueSettlementContract {
             .instance()
             .set(&DataKey::SettlementCounter, &settlement_id);
 
-        settlement_id
+        let mut summary = Self::_current_period(&env);
+        summary.recorded_revenue += amount;
+        summary.settlements += 1;
+        Self::_store_period(&env, &summary);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("revenue"),
+            symbol_short!("recorded"),
+            (settlement_id, campaign_id, publisher, amount),
+        );
+
+        Ok(settlement_id)
     }
 
-    pub fn distribute_platform_revenue(env: Env, admin: Address) {
+    pub fn distribute_platform_revenue(env: Env, admin: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut pool: RevenuePool = env.storage().instance().get(&DataKey::RevenuePool).unwrap();
@@ -193,6 +239,7 @@ impl RevenueSettlementContract {
             .get(&DataKey::TokenAddress)
             .unwrap();
         let token_client = token::Client::new(&env, &token_addr);
+        let mut summary = Self::_current_period(&env);
 
         if pool.platform_share > 0 {
             let platform: Address = env
@@ -205,6 +252,7 @@ impl RevenueSettlementContract {
                 &platform,
                 &pool.platform_share,
             );
+            summary.distributed += pool.platform_share;
             pool.platform_share = 0;
         }
 
@@ -219,60 +267,69 @@ impl RevenueSettlementContract {
                 &treasury,
                 &pool.treasury_share,
             );
+            summary.treasury_sent += pool.treasury_share;
             pool.treasury_share = 0;
         }
 
         if pool.burn_amount > 0 {
             token_client.burn(&env.current_contract_address(), &pool.burn_amount);
+            summary.burned += pool.burn_amount;
             pool.burn_amount = 0;
         }
 
         pool.last_settlement = env.ledger().timestamp();
         env.storage().instance().set(&DataKey::RevenuePool, &pool);
+        Self::_store_period(&env, &summary);
+        Ok(())
     }
 
-    pub fn claim_publisher_balance(env: Env, publisher: Address) {
+    pub fn claim_publisher_balance(env: Env, publisher: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         publisher.require_auth();
+        Self::_claim_publisher_balance(&env, publisher)
+    }
 
-        let pub_key = DataKey::PublisherBalance(publisher.clone());
-        let balance: i128 = env.storage().persistent().get(&pub_key).unwrap_or(0);
-
-        if balance <= 0 {
-            panic!("no balance to claim");
-        }
-
-        let token_addr: Address = env
-            .storage()
+    /// Relayed `claim_publisher_balance` for the account whose key signed
+    /// `()` (see pulsar-common-meta)
+    pub fn claim_publisher_balance_signed(env: Env, auth: MetaAuth) -> Result<(), PulsarError> {
+        env.storage()
             .instance()
-            .get(&DataKey::TokenAddress)
-            .unwrap();
-        let token_client = token::Client::new(&env, &token_addr);
-        token_client.transfer(&env.current_contract_address(), &publisher, &balance);
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let publisher = pulsar_common_meta::verify(
+            &env,
+            Symbol::new(&env, "claim_publisher_balance"),
+            (),
+            &auth,
+        )?;
+        Self::_claim_publisher_balance(&env, publisher)
+    }
 
-        env.storage().persistent().set(&pub_key, &0i128);
-        env.storage().persistent().extend_ttl(
-            &pub_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+    /// Opt the signing key's account in or out of relayed calls; needs the
+    /// account's own authorization (see pulsar-common-meta)
+    pub fn set_meta_enabled(env: Env, signer: BytesN<32>, enabled: bool) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_meta::set_enabled(&env, &signer, enabled);
+    }
 
-        env.events().publish(
-            (symbol_short!("revenue"), symbol_short!("claimed")),
-            (publisher, balance),
-        );
+    pub fn get_meta_nonce(env: Env, signer: BytesN<32>) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_meta::nonce(&env, &signer)
     }
 
-    pub fn get_revenue_pool(env: Env) -> RevenuePool {
+    pub fn get_revenue_pool(env: Env) -> Result<RevenuePool, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         env.storage()
             .instance()
             .get(&DataKey::RevenuePool)
-            .expect("not initialized")
+            .ok_or(PulsarError::NotInitialized)
     }
 
     pub fn get_publisher_balance(env: Env, publisher: Address) -> i128 {
@@ -294,18 +351,113 @@ impl RevenueSettlementContract {
             .get(&DataKey::Settlement(settlement_id))
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn get_current_epoch(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_epoch_at(env.ledger().timestamp())
+    }
+
+    /// Totals for `epoch`; zeroed for epochs without activity
+    pub fn get_period_summary(env: Env, epoch: u32) -> PeriodSummary {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_period(&env, epoch)
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
+
+    fn _claim_publisher_balance(env: &Env, publisher: Address) -> Result<(), PulsarError> {
+        let pub_key = DataKey::PublisherBalance(publisher.clone());
+        let balance: i128 = env.storage().persistent().get(&pub_key).unwrap_or(0);
+
+        if balance <= 0 {
+            return Err(PulsarError::InsufficientBalance);
+        }
+
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenAddress)
+            .unwrap();
+        let token_client = token::Client::new(env, &token_addr);
+        token_client.transfer(&env.current_contract_address(), &publisher, &balance);
+
+        env.storage().persistent().set(&pub_key, &0i128);
+        env.storage().persistent().extend_ttl(
+            &pub_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let mut summary = Self::_current_period(env);
+        summary.distributed += balance;
+        Self::_store_period(env, &summary);
+
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("revenue"),
+            symbol_short!("claimed"),
+            (publisher, balance),
         );
+        Ok(())
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    fn _epoch_at(timestamp: u64) -> u32 {
+        (timestamp / EPOCH_LENGTH) as u32
+    }
+
+    fn _period(env: &Env, epoch: u32) -> PeriodSummary {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PeriodSummary(epoch))
+            .unwrap_or(PeriodSummary {
+                epoch,
+                recorded_revenue: 0,
+                burned: 0,
+                distributed: 0,
+                treasury_sent: 0,
+                settlements: 0,
+            })
+    }
+
+    fn _current_period(env: &Env) -> PeriodSummary {
+        Self::_period(env, Self::_epoch_at(env.ledger().timestamp()))
+    }
+
+    fn _store_period(env: &Env, summary: &PeriodSummary) {
+        let _ttl_key = DataKey::PeriodSummary(summary.epoch);
+        env.storage().persistent().set(&_ttl_key, summary);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
     }
 }
 
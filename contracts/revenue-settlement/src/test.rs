@@ -1,11 +1,66 @@
 #![cfg(test)]
 use super::*;
+use ed25519_dalek::{Signer, SigningKey};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
+    testutils::{Address as _, Events, Ledger},
     token::{Client as TokenClient, StellarAssetClient},
-    Address, Env,
+    Address, BytesN, Env, IntoVal, Symbol, Val, Vec,
 };
 
+mod token_mock {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    // The test SAC cannot pay G-addresses without a trustline, so relayed
+    // calls acting for a signing key's account settle in this token instead.
+    #[contract]
+    pub struct MockToken;
+
+    #[contractimpl]
+    impl MockToken {
+        pub fn mint(env: Env, to: Address, amount: i128) {
+            let balance = Self::balance(env.clone(), to.clone());
+            env.storage().persistent().set(&to, &(balance + amount));
+        }
+
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().persistent().get(&id).unwrap_or(0)
+        }
+
+        pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+            from.require_auth();
+            Self::_move(&env, &from, &to, amount);
+        }
+
+        pub fn approve(env: Env, from: Address, spender: Address, amount: i128, _expiration: u32) {
+            from.require_auth();
+            env.storage().persistent().set(&(from, spender), &amount);
+        }
+
+        pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+            spender.require_auth();
+            let key = (from.clone(), spender);
+            let allowance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            if allowance < amount {
+                panic!("insufficient allowance");
+            }
+            env.storage().persistent().set(&key, &(allowance - amount));
+            Self::_move(&env, &from, &to, amount);
+        }
+
+        fn _move(env: &Env, from: &Address, to: &Address, amount: i128) {
+            let from_balance = Self::balance(env.clone(), from.clone());
+            if from_balance < amount {
+                panic!("insufficient balance");
+            }
+            env.storage()
+                .persistent()
+                .set(from, &(from_balance - amount));
+            let to_balance = Self::balance(env.clone(), to.clone());
+            env.storage().persistent().set(to, &(to_balance + amount));
+        }
+    }
+}
+
 // ─── helpers ─────────────────────────────────────────────────────────────────
 
 fn deploy_token(env: &Env, admin: &Address) -> Address {
@@ -34,13 +89,39 @@ fn setup(
     let treasury = Address::generate(env);
     let platform = Address::generate(env);
 
-    let contract_id = env.register_contract(None, RevenueSettlementContract);
+    let contract_id = env.register(RevenueSettlementContract, ());
     let client = RevenueSettlementContractClient::new(env, &contract_id);
     client.initialize(&admin, &token_addr, &treasury, &platform);
 
     (client, admin, token_admin, token_addr, treasury, platform)
 }
 
+/// Sign a relayed call the way a wallet would
+fn sign_meta<P: soroban_sdk::xdr::ToXdr>(
+    env: &Env,
+    contract: &Address,
+    key: &SigningKey,
+    action: &str,
+    payload: P,
+    nonce: u64,
+) -> MetaAuth {
+    let expiry = env.ledger().timestamp() + 300;
+    let digest = env.as_contract(contract, || {
+        pulsar_common_meta::message(env, &Symbol::new(env, action), payload, nonce, expiry)
+    });
+    MetaAuth {
+        signer: BytesN::from_array(env, &key.verifying_key().to_bytes()),
+        nonce,
+        expiry,
+        signature: BytesN::from_array(env, &key.sign(&digest.to_array()).to_bytes()),
+    }
+}
+
+fn account_of(env: &Env, key: &SigningKey) -> Address {
+    let strkey = stellar_strkey::ed25519::PublicKey(key.verifying_key().to_bytes()).to_string();
+    Address::from_str(env, &strkey)
+}
+
 // ─── initialize ──────────────────────────────────────────────────────────────
 
 #[test]
@@ -48,7 +129,7 @@ fn test_initialize() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let contract_id = env.register_contract(None, RevenueSettlementContract);
+    let contract_id = env.register(RevenueSettlementContract, ());
     let client = RevenueSettlementContractClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
@@ -67,12 +148,11 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let contract_id = env.register_contract(None, RevenueSettlementContract);
+    let contract_id = env.register(RevenueSettlementContract, ());
     let client = RevenueSettlementContractClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
@@ -81,7 +161,10 @@ fn test_initialize_twice() {
     let platform = Address::generate(&env);
 
     client.initialize(&admin, &token, &treasury, &platform);
-    client.initialize(&admin, &token, &treasury, &platform);
+    assert_eq!(
+        client.try_initialize(&admin, &token, &treasury, &platform),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -89,7 +172,7 @@ fn test_initialize_twice() {
 fn test_initialize_non_admin_fails() {
     let env = Env::default();
 
-    let contract_id = env.register_contract(None, RevenueSettlementContract);
+    let contract_id = env.register(RevenueSettlementContract, ());
     let client = RevenueSettlementContractClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
@@ -102,6 +185,28 @@ fn test_initialize_non_admin_fails() {
 
 // ─── record_revenue ──────────────────────────────────────────────────────────
 
+#[test]
+fn test_record_revenue_emits_versioned_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, _, _, _) = setup(&env);
+    let publisher = Address::generate(&env);
+
+    let settlement_id = client.record_revenue(&admin, &7u64, &100_000i128, &publisher);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    let expected: Vec<Val> = (
+        symbol_short!("revenue"),
+        symbol_short!("recorded"),
+        pulsar_common_admin::EVENT_SCHEMA_VERSION,
+    )
+        .into_val(&env);
+    assert_eq!(topics, expected);
+    let (id, campaign_id, who, amount): (u64, u64, Address, i128) = data.into_val(&env);
+    assert_eq!((id, campaign_id, amount), (settlement_id, 7, 100_000));
+    assert_eq!(who, publisher);
+}
+
 #[test]
 fn test_record_revenue_fee_split() {
     let env = Env::default();
@@ -154,7 +259,6 @@ fn test_record_revenue_accumulates() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_record_revenue_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
@@ -162,7 +266,10 @@ fn test_record_revenue_unauthorized() {
     let stranger = Address::generate(&env);
     let publisher = Address::generate(&env);
 
-    client.record_revenue(&stranger, &1u64, &100_000i128, &publisher);
+    assert_eq!(
+        client.try_record_revenue(&stranger, &1u64, &100_000i128, &publisher),
+        Err(Ok(PulsarError::Unauthorized))
+    );
 }
 
 // ─── distribute_platform_revenue ─────────────────────────────────────────────
@@ -178,7 +285,7 @@ fn test_distribute_platform_revenue() {
     let treasury = Address::generate(&env);
     let platform = Address::generate(&env);
 
-    let contract_id = env.register_contract(None, RevenueSettlementContract);
+    let contract_id = env.register(RevenueSettlementContract, ());
     let client = RevenueSettlementContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &treasury, &platform);
 
@@ -208,14 +315,51 @@ fn test_distribute_platform_revenue() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_distribute_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, _, _, _, _, _) = setup(&env);
     let stranger = Address::generate(&env);
 
-    client.distribute_platform_revenue(&stranger);
+    assert_eq!(
+        client.try_distribute_platform_revenue(&stranger),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_period_summary_tracks_each_epoch() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, token_addr, _, _) = setup(&env);
+    mint(&env, &token_addr, &client.address, 10_000_000);
+    let publisher = Address::generate(&env);
+
+    client.record_revenue(&admin, &1u64, &100_000i128, &publisher);
+    client.distribute_platform_revenue(&admin);
+    assert_eq!(client.get_current_epoch(), 0);
+
+    // Revenue recorded next quarter, claimed there
+    env.ledger().with_mut(|li| li.timestamp = EPOCH_LENGTH);
+    client.record_revenue(&admin, &2u64, &40_000i128, &publisher);
+    client.claim_publisher_balance(&publisher);
+    assert_eq!(client.get_current_epoch(), 1);
+
+    let first = client.get_period_summary(&0u32);
+    assert_eq!(first.recorded_revenue, 100_000);
+    assert_eq!(first.distributed, 2_500);
+    assert_eq!(first.treasury_sent, 5_000);
+    assert_eq!(first.burned, 2_500);
+    assert_eq!(first.settlements, 1);
+
+    let second = client.get_period_summary(&1u32);
+    assert_eq!(second.recorded_revenue, 40_000);
+    assert_eq!(second.distributed, 90_000 + 36_000);
+    assert_eq!(second.treasury_sent, 0);
+    assert_eq!(second.burned, 0);
+    assert_eq!(second.settlements, 1);
+
+    assert_eq!(client.get_period_summary(&7u32).recorded_revenue, 0);
 }
 
 // ─── claim_publisher_balance ─────────────────────────────────────────────────
@@ -231,7 +375,7 @@ fn test_claim_publisher_balance() {
     let treasury = Address::generate(&env);
     let platform = Address::generate(&env);
 
-    let contract_id = env.register_contract(None, RevenueSettlementContract);
+    let contract_id = env.register(RevenueSettlementContract, ());
     let client = RevenueSettlementContractClient::new(&env, &contract_id);
     client.initialize(&admin, &token_addr, &treasury, &platform);
 
@@ -248,14 +392,47 @@ fn test_claim_publisher_balance() {
 }
 
 #[test]
-#[should_panic(expected = "no balance to claim")]
+fn test_claim_publisher_balance_signed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let token_id = env.register(token_mock::MockToken, ());
+    let token = token_mock::MockTokenClient::new(&env, &token_id);
+    let contract_id = env.register(RevenueSettlementContract, ());
+    let client = RevenueSettlementContractClient::new(&env, &contract_id);
+    client.initialize(
+        &admin,
+        &token_id,
+        &Address::generate(&env),
+        &Address::generate(&env),
+    );
+    token.mint(&contract_id, &10_000_000);
+
+    let key = SigningKey::from_bytes(&[3u8; 32]);
+    let publisher = account_of(&env, &key);
+    client.record_revenue(&admin, &1u64, &100_000i128, &publisher);
+
+    let auth = sign_meta(&env, &contract_id, &key, "claim_publisher_balance", (), 0);
+    client.set_meta_enabled(&auth.signer, &true);
+    client.claim_publisher_balance_signed(&auth);
+
+    assert_eq!(client.get_publisher_balance(&publisher), 0);
+    assert_eq!(token.balance(&publisher), 90_000);
+    // The signature cannot be replayed
+    assert!(client.try_claim_publisher_balance_signed(&auth).is_err());
+}
+
+#[test]
 fn test_claim_zero_balance() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, _, _, _, _, _) = setup(&env);
     let publisher = Address::generate(&env);
 
-    client.claim_publisher_balance(&publisher);
+    assert_eq!(
+        client.try_claim_publisher_balance(&publisher),
+        Err(Ok(PulsarError::InsufficientBalance))
+    );
 }
 
 // ─── read-only ───────────────────────────────────────────────────────────────
@@ -2,7 +2,13 @@
 //! Distributes PULSAR governance token rewards to ecosystem participants on Stellar.
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env};
+use pulsar_common_meta::MetaAuth;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, xdr::ToXdr, Address, Bytes, BytesN,
+    Env, IntoVal, Symbol, Vec,
+};
+
+pub use pulsar_common_errors::PulsarError;
 
 #[contracttype]
 #[derive(Clone)]
@@ -15,6 +21,20 @@ pub struct RewardProgram {
     pub start_ledger: u32,
     pub end_ledger: u32,
     pub is_active: bool,
+    pub is_closed: bool,
+    pub closed_at: Option<u64>,
+    pub claim_window: u64, // seconds after close before unclaimed rewards expire, 0 = never
+    pub vesting: VestingConfig,
+}
+
+/// Unlock schedule applied to every tranche earned from a program. A zero
+/// `duration` releases rewards immediately; otherwise rewards vest linearly
+/// over `duration` seconds with nothing claimable before `cliff` has passed.
+#[contracttype]
+#[derive(Clone)]
+pub struct VestingConfig {
+    pub cliff: u64,
+    pub duration: u64,
 }
 
 use soroban_sdk::String;
@@ -27,8 +47,59 @@ pub struct UserRewards {
     pub total_claimed: i128,
     pub pending: i128,
     pub last_earned: u64,
-    pub vesting_start: u64,     // timestamp when vesting begins
-    pub vesting_duration: u64,  // total vesting period in seconds
+}
+
+/// Rewards a user has earned from a single program, vesting on that
+/// program's schedule from the first distribution.
+#[contracttype]
+#[derive(Clone)]
+pub struct RewardTranche {
+    pub program_id: u32,
+    pub earned: i128,
+    pub claimed: i128,
+    pub vesting_start: u64,
+    pub cliff: u64,
+    pub duration: u64,
+}
+
+/// Snapshot of a user's rewards across all tranches. `next_unlock_ts` is 0
+/// when nothing is locked, and equals the current time once a linear
+/// schedule is past its cliff and unlocking continuously.
+#[contracttype]
+#[derive(Clone)]
+pub struct ClaimableRewards {
+    pub vested: i128,
+    pub locked: i128,
+    pub next_unlock_ts: u64,
+}
+
+/// On-chain activity that can earn rewards through an accrual hook.
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ActivityKind {
+    Impressions,
+    Reviews,
+}
+
+/// Mirror of the publisher-verification tier enum, decoded from `get_tier`.
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PublisherTier {
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct DistributionRoot {
+    pub program_id: u32,
+    pub epoch: u32,
+    pub root: BytesN<32>,
+    pub total: i128,
+    pub claimed: i128,
+    pub posted_at: u64,
 }
 
 #[contracttype]
@@ -37,11 +108,25 @@ pub enum DataKey {
     Admin,
     PendingAdmin,
     RewardToken,
+    Treasury,
+    DaoContract,
     ProgramCounter,
     Program(u32),
     UserRewards(Address),
+    UserTranche(Address, u32), // (user, program_id)
+    UserPrograms(Address),
+    AuthorizedCaller(Address),
+    ActivityProgram(ActivityKind),
+    PublisherVerification,
+    TierMultiplier(PublisherTier),    // basis points, 10_000 = 1x
+    DistributionRoot(u32, u32),       // (program_id, epoch)
+    MerkleClaimed(u32, u32, Address), // (program_id, epoch, user)
+    ProgramEpochs(u32),               // epochs posted for a program
 }
 
+const BASE_MULTIPLIER_BPS: u32 = 10_000;
+const MAX_MULTIPLIER_BPS: u32 = 50_000;
+
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
 const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
@@ -52,12 +137,12 @@ pub struct RewardsDistributorContract;
 
 #[contractimpl]
 impl RewardsDistributorContract {
-    pub fn initialize(env: Env, admin: Address, reward_token: Address) {
+    pub fn initialize(env: Env, admin: Address, reward_token: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -67,6 +152,7 @@ impl RewardsDistributorContract {
         env.storage()
             .instance()
             .set(&DataKey::ProgramCounter, &0u32);
+        Ok(())
     }
 
     pub fn create_program(
@@ -76,158 +162,365 @@ impl RewardsDistributorContract {
         budget: i128,
         reward_per_unit: i128,
         duration_ledgers: u32,
-    ) -> u32 {
+        vesting: VestingConfig,
+    ) -> Result<u32, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
-        let counter: u32 = env
+        if budget < 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+
+        Self::_create_program(
+            &env,
+            &admin,
+            name,
+            budget,
+            reward_per_unit,
+            duration_ledgers,
+            vesting,
+        )
+    }
+
+    /// Creates a program on behalf of the governance DAO. The DAO contract
+    /// must authorize the call and, like the admin in `create_program`, funds
+    /// the full budget up front, so emissions are decided by token-holder
+    /// vote rather than the admin key.
+    pub fn create_program_from_dao(
+        env: Env,
+        dao: Address,
+        name: String,
+        budget: i128,
+        reward_per_unit: i128,
+        duration_ledgers: u32,
+        vesting: VestingConfig,
+    ) -> Result<u32, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        dao.require_auth();
+        let stored_dao: Address = env
             .storage()
             .instance()
-            .get(&DataKey::ProgramCounter)
-            .unwrap_or(0);
-        let program_id = counter + 1;
+            .get(&DataKey::DaoContract)
+            .ok_or(PulsarError::NotInitialized)?;
+        if dao != stored_dao {
+            return Err(PulsarError::Unauthorized);
+        }
 
-        let start = env.ledger().sequence();
-        let program = RewardProgram {
-            program_id,
+        if budget <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+
+        let program_id = Self::_create_program(
+            &env,
+            &dao,
             name,
-            total_budget: budget,
-            distributed: 0,
+            budget,
             reward_per_unit,
-            start_ledger: start,
-            end_ledger: start + duration_ledgers,
-            is_active: true,
-        };
+            duration_ledgers,
+            vesting,
+        )?;
 
-        let _ttl_key = DataKey::Program(program_id);
-        env.storage().persistent().set(&_ttl_key, &program);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("program"),
+            symbol_short!("dao"),
+            (program_id, dao, budget),
         );
+
+        Ok(program_id)
+    }
+
+    pub fn set_dao_contract(env: Env, admin: Address, dao: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
-            .set(&DataKey::ProgramCounter, &program_id);
-
-        program_id
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage().instance().set(&DataKey::DaoContract, &dao);
+        Ok(())
     }
 
-    pub fn distribute_rewards(
-        env: Env,
-        admin: Address,
-        recipient: Address,
-        amount: i128,
-        program_id: u32,
-    ) {
+    pub fn set_treasury(env: Env, admin: Address, treasury: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        Ok(())
+    }
 
-        let mut program: RewardProgram = env
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().get(&DataKey::Treasury)
+    }
+
+    pub fn pause_program(env: Env, admin: Address, program_id: u32) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
-            .persistent()
-            .get(&DataKey::Program(program_id))
-            .expect("program not found");
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
 
+        let mut program = Self::_load_open_program(&env, program_id)?;
         if !program.is_active {
-            panic!("program not active");
+            return Err(PulsarError::AlreadyExists);
         }
+        program.is_active = false;
+        Self::_save_program(&env, &program);
 
-        if program.distributed + amount > program.total_budget {
-            panic!("exceeds budget");
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("program"),
+            symbol_short!("paused"),
+            program_id,
+        );
+        Ok(())
+    }
+
+    pub fn resume_program(env: Env, admin: Address, program_id: u32) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
         }
 
-        if env.ledger().sequence() > program.end_ledger {
-            panic!("program ended");
+        let mut program = Self::_load_open_program(&env, program_id)?;
+        if program.is_active {
+            return Err(PulsarError::InvalidState);
         }
+        program.is_active = true;
+        Self::_save_program(&env, &program);
 
-        program.distributed += amount;
-        let _ttl_key = DataKey::Program(program_id);
-        env.storage().persistent().set(&_ttl_key, &program);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("program"),
+            symbol_short!("resumed"),
+            program_id,
         );
+        Ok(())
+    }
 
-        // Update user rewards with vesting schedule
-        let key = DataKey::UserRewards(recipient.clone());
-        let now = env.ledger().timestamp();
-        let vesting_duration = 365 * 24 * 3600; // 365 days in seconds
-        let mut rewards: UserRewards =
-            env.storage().persistent().get(&key).unwrap_or(UserRewards {
-                user: recipient.clone(),
-                total_earned: 0,
-                total_claimed: 0,
-                pending: 0,
-                last_earned: 0,
-                vesting_start: now,
-                vesting_duration,
-            });
+    /// Pushes out the end ledger and tops up the budget. The extra budget is
+    /// pulled from the admin, as the original budget was at creation, so the
+    /// contract holds every program's undistributed budget in full.
+    pub fn extend_program(
+        env: Env,
+        admin: Address,
+        program_id: u32,
+        extra_ledgers: u32,
+        extra_budget: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
 
-        // Initialize vesting_start on first distribution if not set
-        if rewards.total_earned == 0 {
-            rewards.vesting_start = now;
-            rewards.vesting_duration = vesting_duration;
+        if extra_budget < 0 {
+            return Err(PulsarError::InvalidAmount);
         }
 
-        rewards.total_earned += amount;
-        rewards.last_earned = now;
-        env.storage().persistent().set(&key, &rewards);
-        env.storage().persistent().extend_ttl(
-            &key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
+        let mut program = Self::_load_open_program(&env, program_id)?;
+        if extra_budget > 0 {
+            let token: Address = env.storage().instance().get(&DataKey::RewardToken).unwrap();
+            let token_client = token::Client::new(&env, &token);
+            token_client.transfer(&admin, &env.current_contract_address(), &extra_budget);
+        }
+        program.end_ledger += extra_ledgers;
+        program.total_budget += extra_budget;
+        Self::_save_program(&env, &program);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("program"),
+            symbol_short!("extended"),
+            (program_id, program.end_ledger, program.total_budget),
         );
+        Ok(())
+    }
 
-        env.events().publish(
-            (symbol_short!("rewards"), symbol_short!("earned")),
-            (recipient, amount),
+    /// Permanently closes a program and sweeps its undistributed budget to the
+    /// treasury, including Merkle allocations nobody has claimed yet. Rewards
+    /// already credited to users remain claimable.
+    pub fn close_program(env: Env, admin: Address, program_id: u32) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        let treasury: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Treasury)
+            .ok_or(PulsarError::NotInitialized)?;
+
+        let mut program = Self::_load_open_program(&env, program_id)?;
+        program.distributed -= Self::_release_merkle_remainder(&env, program_id);
+        let remaining = program.total_budget - program.distributed;
+        if remaining > 0 {
+            let token: Address = env.storage().instance().get(&DataKey::RewardToken).unwrap();
+            let token_client = token::Client::new(&env, &token);
+            token_client.transfer(&env.current_contract_address(), &treasury, &remaining);
+        }
+        program.total_budget = program.distributed;
+        program.is_active = false;
+        program.is_closed = true;
+        program.closed_at = Some(env.ledger().timestamp());
+        Self::_save_program(&env, &program);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("program"),
+            symbol_short!("closed"),
+            (program_id, remaining),
         );
+
+        Ok(remaining)
     }
 
-    pub fn claim_rewards(env: Env, user: Address) -> i128 {
+    /// Sets how long users have to claim after the program is closed before
+    /// their remaining allocation can be expired.
+    pub fn set_claim_window(
+        env: Env,
+        admin: Address,
+        program_id: u32,
+        claim_window: u64,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        user.require_auth();
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
 
-        let key = DataKey::UserRewards(user.clone());
-        let mut rewards: UserRewards = env.storage().persistent().get(&key).expect("no rewards");
+        let mut program = Self::_load_open_program(&env, program_id)?;
+        program.claim_window = claim_window;
+        Self::_save_program(&env, &program);
+        Ok(())
+    }
 
-        if rewards.total_earned <= 0 {
-            panic!("no available rewards");
+    /// Forfeits a user's unclaimed rewards from a closed program once its claim
+    /// window has elapsed, returning the tokens to the treasury.
+    pub fn expire_unclaimed(
+        env: Env,
+        admin: Address,
+        user: Address,
+        program_id: u32,
+    ) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
         }
 
-        // Calculate vested amount based on linear vesting schedule
-        let now = env.ledger().timestamp();
-        let elapsed = now.saturating_sub(rewards.vesting_start);
-        let vesting_fraction = elapsed.min(rewards.vesting_duration);
-        let vested_total = (rewards.total_earned as u128 * vesting_fraction as u128
-            / rewards.vesting_duration as u128) as i128;
-        let claimable = vested_total.saturating_sub(rewards.total_claimed);
+        let mut program: RewardProgram = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Program(program_id))
+            .ok_or(PulsarError::NotFound)?;
+        let closed_at = program.closed_at.ok_or(PulsarError::InvalidState)?;
+        if program.claim_window == 0 {
+            return Err(PulsarError::InvalidState);
+        }
+        if env.ledger().timestamp() < closed_at + program.claim_window {
+            return Err(PulsarError::TooEarly);
+        }
 
-        if claimable <= 0 {
-            panic!("no vested rewards available to claim");
+        let tranche_key = DataKey::UserTranche(user.clone(), program_id);
+        let mut tranche: RewardTranche = env
+            .storage()
+            .persistent()
+            .get(&tranche_key)
+            .ok_or(PulsarError::NotFound)?;
+        let forfeited = tranche.earned - tranche.claimed;
+        if forfeited <= 0 {
+            return Err(PulsarError::InsufficientBalance);
         }
 
-        let token: Address = env.storage().instance().get(&DataKey::RewardToken).unwrap();
-        let token_client = token::Client::new(&env, &token);
-        token_client.transfer(&env.current_contract_address(), &user, &claimable);
+        // Settle the tranche so nothing further vests from it
+        tranche.earned = tranche.claimed;
+        tranche.duration = 0;
+        env.storage().persistent().set(&tranche_key, &tranche);
+        env.storage().persistent().extend_ttl(
+            &tranche_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
 
-        rewards.total_claimed += claimable;
+        let key = DataKey::UserRewards(user.clone());
+        let mut rewards: UserRewards = env.storage().persistent().get(&key).unwrap();
+        rewards.total_earned -= forfeited;
         env.storage().persistent().set(&key, &rewards);
         env.storage().persistent().extend_ttl(
             &key,
@@ -235,42 +528,1036 @@ impl RewardsDistributorContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("rewards"), symbol_short!("claimed")),
-            (user, claimable),
+        program.distributed -= forfeited;
+        program.total_budget -= forfeited;
+        Self::_save_program(&env, &program);
+
+        let treasury: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Treasury)
+            .ok_or(PulsarError::NotInitialized)?;
+        let token: Address = env.storage().instance().get(&DataKey::RewardToken).unwrap();
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&env.current_contract_address(), &treasury, &forfeited);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("rewards"),
+            symbol_short!("expired"),
+            (user, program_id, forfeited),
         );
 
-        claimable
+        Ok(forfeited)
     }
 
-    pub fn get_program(env: Env, program_id: u32) -> Option<RewardProgram> {
+    pub fn distribute_rewards(
+        env: Env,
+        admin: Address,
+        recipient: Address,
+        amount: i128,
+        program_id: u32,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        env.storage()
-            .persistent()
-            .get(&DataKey::Program(program_id))
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        let program = Self::_debit_program(&env, program_id, amount)?;
+        Self::_credit_tranche(&env, &recipient, &program, amount);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("rewards"),
+            symbol_short!("earned"),
+            (recipient, amount),
+        );
+        Ok(())
     }
 
-    pub fn get_user_rewards(env: Env, user: Address) -> Option<UserRewards> {
+    /// Credits many recipients from one program in a single invocation. The
+    /// aggregate is checked against the remaining budget once and a single
+    /// `batch` event is emitted for the whole run.
+    pub fn distribute_batch(
+        env: Env,
+        admin: Address,
+        program_id: u32,
+        recipients: Vec<(Address, i128)>,
+    ) -> Result<i128, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        env.storage().persistent().get(&DataKey::UserRewards(user))
-    }
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
-        pulsar_common_admin::propose_admin(
-            &env,
-            &DataKey::Admin,
-            &DataKey::PendingAdmin,
+        if recipients.is_empty() {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let mut total: i128 = 0;
+        for (_, amount) in recipients.iter() {
+            if amount <= 0 {
+                return Err(PulsarError::InvalidAmount);
+            }
+            total = total
+                .checked_add(amount)
+                .ok_or(PulsarError::LimitExceeded)?;
+        }
+
+        let program = Self::_debit_program(&env, program_id, total)?;
+        for (recipient, amount) in recipients.iter() {
+            Self::_credit_tranche(&env, &recipient, &program, amount);
+        }
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("rewards"),
+            symbol_short!("batch"),
+            (program_id, recipients.len(), total),
+        );
+
+        Ok(total)
+    }
+
+    pub fn claim_rewards(env: Env, user: Address) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        user.require_auth();
+        Self::_claim(&env, &user, i128::MAX)
+    }
+
+    /// Relayed `claim_rewards` for the account whose key signed `()`
+    /// (see pulsar-common-meta)
+    pub fn claim_rewards_signed(env: Env, auth: MetaAuth) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let user = pulsar_common_meta::verify(&env, Symbol::new(&env, "claim_rewards"), (), &auth)?;
+        Self::_claim(&env, &user, i128::MAX)
+    }
+
+    /// Opt the signing key's account in or out of relayed calls; needs the
+    /// account's own authorization (see pulsar-common-meta)
+    pub fn set_meta_enabled(env: Env, signer: BytesN<32>, enabled: bool) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_meta::set_enabled(&env, &signer, enabled);
+    }
+
+    pub fn get_meta_nonce(env: Env, signer: BytesN<32>) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_meta::nonce(&env, &signer)
+    }
+
+    /// Withdraws at most `amount` of the caller's vested rewards, drawing
+    /// from program tranches in the order they were first earned.
+    pub fn claim_rewards_partial(
+        env: Env,
+        user: Address,
+        amount: i128,
+    ) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        user.require_auth();
+        if amount <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        let vested = Self::_claimable(&env, &user).vested;
+        if amount > vested {
+            return Err(PulsarError::InsufficientBalance);
+        }
+        Self::_claim(&env, &user, amount)
+    }
+
+    pub fn get_claimable(env: Env, user: Address) -> ClaimableRewards {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_claimable(&env, &user)
+    }
+
+    pub fn authorize_caller(env: Env, admin: Address, caller: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AuthorizedCaller(caller), &true);
+        Ok(())
+    }
+
+    pub fn revoke_caller(env: Env, admin: Address, caller: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .remove(&DataKey::AuthorizedCaller(caller));
+        Ok(())
+    }
+
+    pub fn is_authorized_caller(env: Env, caller: Address) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .has(&DataKey::AuthorizedCaller(caller))
+    }
+
+    /// Routes an activity kind to the program whose `reward_per_unit` prices it.
+    pub fn set_activity_program(
+        env: Env,
+        admin: Address,
+        kind: ActivityKind,
+        program_id: u32,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Program(program_id))
+        {
+            return Err(PulsarError::NotFound);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ActivityProgram(kind), &program_id);
+        Ok(())
+    }
+
+    pub fn get_activity_program(env: Env, kind: ActivityKind) -> Option<u32> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::ActivityProgram(kind))
+    }
+
+    pub fn set_publisher_verification(
+        env: Env,
+        admin: Address,
+        contract: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::PublisherVerification, &contract);
+        Ok(())
+    }
+
+    /// Sets the boost applied to impression accruals for a publisher tier,
+    /// in basis points (e.g. 15_000 for 1.5x).
+    pub fn set_tier_multiplier(
+        env: Env,
+        admin: Address,
+        tier: PublisherTier,
+        multiplier_bps: u32,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        if multiplier_bps == 0 || multiplier_bps > MAX_MULTIPLIER_BPS {
+            return Err(PulsarError::InvalidInput);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::TierMultiplier(tier), &multiplier_bps);
+        Ok(())
+    }
+
+    pub fn get_tier_multiplier(env: Env, tier: PublisherTier) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::TierMultiplier(tier))
+            .unwrap_or(BASE_MULTIPLIER_BPS)
+    }
+
+    /// Called by the campaign orchestrator when a publisher serves impressions.
+    pub fn accrue_for_impressions(
+        env: Env,
+        orchestrator: Address,
+        publisher: Address,
+        units: u64,
+    ) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_accrue(
+            &env,
+            &orchestrator,
+            ActivityKind::Impressions,
+            &publisher,
+            units,
+        )
+    }
+
+    /// Called by review-collecting contracts when a reviewer submits work.
+    pub fn accrue_for_reviews(
+        env: Env,
+        caller: Address,
+        reviewer: Address,
+        units: u64,
+    ) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_accrue(&env, &caller, ActivityKind::Reviews, &reviewer, units)
+    }
+
+    /// Posts the Merkle root for one epoch of a program. The root commits to
+    /// leaves of `sha256(user_xdr || amount_be)` and `total` is reserved from
+    /// the program budget up front; recipients then pull their own share.
+    pub fn set_distribution_root(
+        env: Env,
+        admin: Address,
+        program_id: u32,
+        epoch: u32,
+        root: BytesN<32>,
+        total: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+
+        if total <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+
+        let root_key = DataKey::DistributionRoot(program_id, epoch);
+        if env.storage().persistent().has(&root_key) {
+            return Err(PulsarError::AlreadyExists);
+        }
+
+        let mut program: RewardProgram = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Program(program_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if !program.is_active {
+            return Err(PulsarError::InvalidState);
+        }
+
+        if program.distributed + total > program.total_budget {
+            return Err(PulsarError::InsufficientBalance);
+        }
+
+        program.distributed += total;
+        let _ttl_key = DataKey::Program(program_id);
+        env.storage().persistent().set(&_ttl_key, &program);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let distribution = DistributionRoot {
+            program_id,
+            epoch,
+            root: root.clone(),
+            total,
+            claimed: 0,
+            posted_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&root_key, &distribution);
+        env.storage().persistent().extend_ttl(
+            &root_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let epochs_key = DataKey::ProgramEpochs(program_id);
+        let mut epochs: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&epochs_key)
+            .unwrap_or(Vec::new(&env));
+        epochs.push_back(epoch);
+        env.storage().persistent().set(&epochs_key, &epochs);
+        env.storage().persistent().extend_ttl(
+            &epochs_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("rewards"),
+            symbol_short!("root"),
+            (program_id, epoch, root, total),
+        );
+        Ok(())
+    }
+
+    /// Claims a user's Merkle allocation for an epoch. The proof is a list of
+    /// sibling hashes; each pair is hashed in sorted order so proofs do not
+    /// need to carry left/right flags. The allocation is credited to the
+    /// user's tranche so it vests on the program's schedule, and whatever has
+    /// already vested is paid out; the amount paid is returned.
+    pub fn claim_with_proof(
+        env: Env,
+        user: Address,
+        program_id: u32,
+        epoch: u32,
+        amount: i128,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        user.require_auth();
+
+        if amount <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+
+        let program = Self::_load_open_program(&env, program_id)?;
+        if !program.is_active {
+            return Err(PulsarError::InvalidState);
+        }
+
+        let root_key = DataKey::DistributionRoot(program_id, epoch);
+        let mut distribution: DistributionRoot = env
+            .storage()
+            .persistent()
+            .get(&root_key)
+            .ok_or(PulsarError::NotFound)?;
+
+        let claimed_key = DataKey::MerkleClaimed(program_id, epoch, user.clone());
+        if env.storage().persistent().has(&claimed_key) {
+            return Err(PulsarError::AlreadyExists);
+        }
+
+        let mut computed = Self::_merkle_leaf(&env, &user, amount);
+        for sibling in proof.iter() {
+            computed = Self::_hash_pair(&env, &computed, &sibling);
+        }
+        if computed != distribution.root {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        if distribution.claimed + amount > distribution.total {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        distribution.claimed += amount;
+        env.storage().persistent().set(&root_key, &distribution);
+        env.storage().persistent().extend_ttl(
+            &root_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage().persistent().set(&claimed_key, &amount);
+        env.storage().persistent().extend_ttl(
+            &claimed_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        Self::_credit_tranche(&env, &user, &program, amount);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("rewards"),
+            symbol_short!("mclaimed"),
+            (user.clone(), program_id, epoch, amount),
+        );
+
+        Ok(Self::_claim_tranche(&env, &user, program_id))
+    }
+
+    pub fn get_distribution_root(
+        env: Env,
+        program_id: u32,
+        epoch: u32,
+    ) -> Option<DistributionRoot> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::DistributionRoot(program_id, epoch))
+    }
+
+    pub fn has_claimed_epoch(env: Env, user: Address, program_id: u32, epoch: u32) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .has(&DataKey::MerkleClaimed(program_id, epoch, user))
+    }
+
+    pub fn get_program(env: Env, program_id: u32) -> Option<RewardProgram> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Program(program_id))
+    }
+
+    pub fn get_user_rewards(env: Env, user: Address) -> Option<UserRewards> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().persistent().get(&DataKey::UserRewards(user))
+    }
+
+    pub fn get_tranche(env: Env, user: Address, program_id: u32) -> Option<RewardTranche> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::UserTranche(user, program_id))
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::propose_admin(
+            &env,
+            &DataKey::Admin,
+            &DataKey::PendingAdmin,
             current_admin,
             new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
+
+    // ============================================================
+    // Internal Helpers
+    // ============================================================
+
+    /// Creates the program and pulls its budget from `funder`
+    fn _create_program(
+        env: &Env,
+        funder: &Address,
+        name: String,
+        budget: i128,
+        reward_per_unit: i128,
+        duration_ledgers: u32,
+        vesting: VestingConfig,
+    ) -> Result<u32, PulsarError> {
+        if vesting.duration > 0 && vesting.cliff > vesting.duration {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        if budget > 0 {
+            let token: Address = env.storage().instance().get(&DataKey::RewardToken).unwrap();
+            let token_client = token::Client::new(env, &token);
+            token_client.transfer(funder, &env.current_contract_address(), &budget);
+        }
+
+        let counter: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ProgramCounter)
+            .unwrap_or(0);
+        let program_id = counter + 1;
+
+        let start = env.ledger().sequence();
+        let program = RewardProgram {
+            program_id,
+            name,
+            total_budget: budget,
+            distributed: 0,
+            reward_per_unit,
+            start_ledger: start,
+            end_ledger: start + duration_ledgers,
+            is_active: true,
+            is_closed: false,
+            closed_at: None,
+            claim_window: 0,
+            vesting,
+        };
+
+        let _ttl_key = DataKey::Program(program_id);
+        env.storage().persistent().set(&_ttl_key, &program);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::ProgramCounter, &program_id);
+
+        Ok(program_id)
+    }
+
+    fn _load_open_program(env: &Env, program_id: u32) -> Result<RewardProgram, PulsarError> {
+        let program: RewardProgram = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Program(program_id))
+            .ok_or(PulsarError::NotFound)?;
+        if program.is_closed {
+            return Err(PulsarError::InvalidState);
+        }
+        Ok(program)
+    }
+
+    fn _save_program(env: &Env, program: &RewardProgram) {
+        let _ttl_key = DataKey::Program(program.program_id);
+        env.storage().persistent().set(&_ttl_key, program);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _debit_program(
+        env: &Env,
+        program_id: u32,
+        amount: i128,
+    ) -> Result<RewardProgram, PulsarError> {
+        let mut program: RewardProgram = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Program(program_id))
+            .ok_or(PulsarError::NotFound)?;
+
+        if !program.is_active {
+            return Err(PulsarError::InvalidState);
+        }
+
+        if program.distributed + amount > program.total_budget {
+            return Err(PulsarError::InsufficientBalance);
+        }
+
+        if env.ledger().sequence() > program.end_ledger {
+            return Err(PulsarError::Expired);
+        }
+
+        program.distributed += amount;
+        Self::_save_program(env, &program);
+
+        Ok(program)
+    }
+
+    fn _accrue(
+        env: &Env,
+        caller: &Address,
+        kind: ActivityKind,
+        recipient: &Address,
+        units: u64,
+    ) -> Result<i128, PulsarError> {
+        caller.require_auth();
+        if !env
+            .storage()
+            .instance()
+            .has(&DataKey::AuthorizedCaller(caller.clone()))
+        {
+            return Err(PulsarError::Unauthorized);
+        }
+        if units == 0 {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let program_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ActivityProgram(kind))
+            .ok_or(PulsarError::NotFound)?;
+        let reward_per_unit = env
+            .storage()
+            .persistent()
+            .get::<DataKey, RewardProgram>(&DataKey::Program(program_id))
+            .ok_or(PulsarError::NotFound)?
+            .reward_per_unit;
+        let multiplier_bps = match kind {
+            ActivityKind::Impressions => Self::_publisher_multiplier(env, recipient),
+            ActivityKind::Reviews => BASE_MULTIPLIER_BPS,
+        };
+        let amount = reward_per_unit
+            .checked_mul(units as i128)
+            .and_then(|base| base.checked_mul(multiplier_bps as i128))
+            .ok_or(PulsarError::LimitExceeded)?
+            / BASE_MULTIPLIER_BPS as i128;
+
+        let program = Self::_debit_program(env, program_id, amount)?;
+        Self::_credit_tranche(env, recipient, &program, amount);
+
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("rewards"),
+            symbol_short!("accrued"),
+            (recipient.clone(), program_id, units, multiplier_bps, amount),
+        );
+
+        Ok(amount)
+    }
+
+    /// Tier boost for `publisher`; suspended, revoked or lapsed publishers
+    /// accrue at the base rate whatever their tier
+    fn _publisher_multiplier(env: &Env, publisher: &Address) -> u32 {
+        let verification: Option<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::PublisherVerification);
+        let Some(verification) = verification else {
+            return BASE_MULTIPLIER_BPS;
+        };
+        let verified: bool = env.invoke_contract(
+            &verification,
+            &Symbol::new(env, "is_verified"),
+            Vec::from_array(env, [publisher.clone().into_val(env)]),
+        );
+        if !verified {
+            return BASE_MULTIPLIER_BPS;
+        }
+        let tier: Option<PublisherTier> = env.invoke_contract(
+            &verification,
+            &Symbol::new(env, "get_tier"),
+            Vec::from_array(env, [publisher.clone().into_val(env)]),
+        );
+        match tier {
+            Some(tier) => env
+                .storage()
+                .instance()
+                .get(&DataKey::TierMultiplier(tier))
+                .unwrap_or(BASE_MULTIPLIER_BPS),
+            None => BASE_MULTIPLIER_BPS,
+        }
+    }
+
+    fn _credit_tranche(env: &Env, recipient: &Address, program: &RewardProgram, amount: i128) {
+        let now = env.ledger().timestamp();
+
+        let tranche_key = DataKey::UserTranche(recipient.clone(), program.program_id);
+        let mut tranche: RewardTranche = match env.storage().persistent().get(&tranche_key) {
+            Some(tranche) => tranche,
+            None => {
+                // First earning from this program starts its vesting clock
+                let programs_key = DataKey::UserPrograms(recipient.clone());
+                let mut programs: Vec<u32> = env
+                    .storage()
+                    .persistent()
+                    .get(&programs_key)
+                    .unwrap_or(Vec::new(env));
+                programs.push_back(program.program_id);
+                env.storage().persistent().set(&programs_key, &programs);
+                env.storage().persistent().extend_ttl(
+                    &programs_key,
+                    PERSISTENT_LIFETIME_THRESHOLD,
+                    PERSISTENT_BUMP_AMOUNT,
+                );
+                RewardTranche {
+                    program_id: program.program_id,
+                    earned: 0,
+                    claimed: 0,
+                    vesting_start: now,
+                    cliff: program.vesting.cliff,
+                    duration: program.vesting.duration,
+                }
+            }
+        };
+        tranche.earned += amount;
+        env.storage().persistent().set(&tranche_key, &tranche);
+        env.storage().persistent().extend_ttl(
+            &tranche_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let key = DataKey::UserRewards(recipient.clone());
+        let mut rewards: UserRewards =
+            env.storage().persistent().get(&key).unwrap_or(UserRewards {
+                user: recipient.clone(),
+                total_earned: 0,
+                total_claimed: 0,
+                pending: 0,
+                last_earned: 0,
+            });
+        rewards.total_earned += amount;
+        rewards.last_earned = now;
+        env.storage().persistent().set(&key, &rewards);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _claim(env: &Env, user: &Address, limit: i128) -> Result<i128, PulsarError> {
+        let rewards: UserRewards = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UserRewards(user.clone()))
+            .ok_or(PulsarError::NotFound)?;
+
+        if rewards.total_earned <= 0 {
+            return Err(PulsarError::InsufficientBalance);
+        }
+
+        // Sweep whatever has vested across program tranches, up to the limit
+        let now = env.ledger().timestamp();
+        let programs: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UserPrograms(user.clone()))
+            .unwrap_or(Vec::new(env));
+        let mut claimable: i128 = 0;
+        for program_id in programs.iter() {
+            if claimable >= limit {
+                break;
+            }
+            let tranche_key = DataKey::UserTranche(user.clone(), program_id);
+            let mut tranche: RewardTranche = env.storage().persistent().get(&tranche_key).unwrap();
+            let available =
+                (Self::_vested_amount(&tranche, now) - tranche.claimed).min(limit - claimable);
+            if available <= 0 {
+                continue;
+            }
+            tranche.claimed += available;
+            claimable += available;
+            env.storage().persistent().set(&tranche_key, &tranche);
+            env.storage().persistent().extend_ttl(
+                &tranche_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+
+        if claimable <= 0 {
+            return Err(PulsarError::InsufficientBalance);
+        }
+
+        Self::_pay_claim(env, user, claimable);
+        Ok(claimable)
+    }
+
+    /// Pay out whatever has vested in one program tranche, or 0 if nothing has
+    fn _claim_tranche(env: &Env, user: &Address, program_id: u32) -> i128 {
+        let tranche_key = DataKey::UserTranche(user.clone(), program_id);
+        let mut tranche: RewardTranche = env.storage().persistent().get(&tranche_key).unwrap();
+        let available = Self::_vested_amount(&tranche, env.ledger().timestamp()) - tranche.claimed;
+        if available <= 0 {
+            return 0;
+        }
+        tranche.claimed += available;
+        env.storage().persistent().set(&tranche_key, &tranche);
+        env.storage().persistent().extend_ttl(
+            &tranche_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
         );
+
+        Self::_pay_claim(env, user, available);
+        available
+    }
+
+    fn _pay_claim(env: &Env, user: &Address, amount: i128) {
+        let token: Address = env.storage().instance().get(&DataKey::RewardToken).unwrap();
+        let token_client = token::Client::new(env, &token);
+        token_client.transfer(&env.current_contract_address(), user, &amount);
+
+        let key = DataKey::UserRewards(user.clone());
+        let mut rewards: UserRewards = env.storage().persistent().get(&key).unwrap();
+        rewards.total_claimed += amount;
+        env.storage().persistent().set(&key, &rewards);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("rewards"),
+            symbol_short!("claimed"),
+            (user.clone(), amount),
+        );
+    }
+
+    /// Cap every posted epoch of a program at what has been claimed and
+    /// return the unclaimed remainder released from the reservation
+    fn _release_merkle_remainder(env: &Env, program_id: u32) -> i128 {
+        let epochs: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProgramEpochs(program_id))
+            .unwrap_or(Vec::new(env));
+        let mut released: i128 = 0;
+        for epoch in epochs.iter() {
+            let root_key = DataKey::DistributionRoot(program_id, epoch);
+            let mut distribution: DistributionRoot =
+                env.storage().persistent().get(&root_key).unwrap();
+            let unclaimed = distribution.total - distribution.claimed;
+            if unclaimed <= 0 {
+                continue;
+            }
+            released += unclaimed;
+            distribution.total = distribution.claimed;
+            env.storage().persistent().set(&root_key, &distribution);
+            env.storage().persistent().extend_ttl(
+                &root_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+        released
+    }
+
+    fn _claimable(env: &Env, user: &Address) -> ClaimableRewards {
+        let now = env.ledger().timestamp();
+        let programs: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UserPrograms(user.clone()))
+            .unwrap_or(Vec::new(env));
+
+        let mut summary = ClaimableRewards {
+            vested: 0,
+            locked: 0,
+            next_unlock_ts: 0,
+        };
+        for program_id in programs.iter() {
+            let tranche: RewardTranche = env
+                .storage()
+                .persistent()
+                .get(&DataKey::UserTranche(user.clone(), program_id))
+                .unwrap();
+            let vested = Self::_vested_amount(&tranche, now);
+            summary.vested += vested - tranche.claimed;
+            let locked = tranche.earned - vested;
+            if locked <= 0 {
+                continue;
+            }
+            summary.locked += locked;
+
+            // Past the cliff, linear tranches unlock continuously
+            let cliff_end = tranche.vesting_start + tranche.cliff;
+            let unlock_ts = if now < cliff_end { cliff_end } else { now };
+            if summary.next_unlock_ts == 0 || unlock_ts < summary.next_unlock_ts {
+                summary.next_unlock_ts = unlock_ts;
+            }
+        }
+        summary
+    }
+
+    fn _vested_amount(tranche: &RewardTranche, now: u64) -> i128 {
+        if tranche.duration == 0 {
+            return tranche.earned;
+        }
+        let elapsed = now.saturating_sub(tranche.vesting_start);
+        if elapsed < tranche.cliff {
+            return 0;
+        }
+        let vesting_fraction = elapsed.min(tranche.duration);
+        (tranche.earned as u128 * vesting_fraction as u128 / tranche.duration as u128) as i128
+    }
+
+    fn _merkle_leaf(env: &Env, user: &Address, amount: i128) -> BytesN<32> {
+        let mut data = Bytes::new(env);
+        data.append(&user.clone().to_xdr(env));
+        for b in amount.to_be_bytes().iter() {
+            data.push_back(*b);
+        }
+        env.crypto().sha256(&data).into()
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    fn _hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+        let (first, second) = if a <= b { (a, b) } else { (b, a) };
+        let mut data = Bytes::new(env);
+        data.append(&Bytes::from(first.clone()));
+        data.append(&Bytes::from(second.clone()));
+        env.crypto().sha256(&data).into()
     }
 }
 
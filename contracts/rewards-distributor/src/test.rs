@@ -1,6 +1,11 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, Env, String};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN, Env, String, Vec,
+};
 
 fn deploy_token(env: &Env, admin: &Address) -> Address {
     env.register_stellar_asset_contract_v2(admin.clone())
@@ -10,6 +15,93 @@ fn mint(env: &Env, token: &Address, to: &Address, amount: i128) {
     StellarAssetClient::new(env, token).mint(to, &amount);
 }
 
+mod token_mock {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    // The test SAC cannot pay G-addresses without a trustline, so relayed
+    // calls acting for a signing key's account settle in this token instead.
+    #[contract]
+    pub struct MockToken;
+
+    #[contractimpl]
+    impl MockToken {
+        pub fn mint(env: Env, to: Address, amount: i128) {
+            let balance = Self::balance(env.clone(), to.clone());
+            env.storage().persistent().set(&to, &(balance + amount));
+        }
+
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().persistent().get(&id).unwrap_or(0)
+        }
+
+        pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+            from.require_auth();
+            Self::_move(&env, &from, &to, amount);
+        }
+
+        pub fn approve(env: Env, from: Address, spender: Address, amount: i128, _expiration: u32) {
+            from.require_auth();
+            env.storage().persistent().set(&(from, spender), &amount);
+        }
+
+        pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+            spender.require_auth();
+            let key = (from.clone(), spender);
+            let allowance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            if allowance < amount {
+                panic!("insufficient allowance");
+            }
+            env.storage().persistent().set(&key, &(allowance - amount));
+            Self::_move(&env, &from, &to, amount);
+        }
+
+        fn _move(env: &Env, from: &Address, to: &Address, amount: i128) {
+            let from_balance = Self::balance(env.clone(), from.clone());
+            if from_balance < amount {
+                panic!("insufficient balance");
+            }
+            env.storage()
+                .persistent()
+                .set(from, &(from_balance - amount));
+            let to_balance = Self::balance(env.clone(), to.clone());
+            env.storage().persistent().set(to, &(to_balance + amount));
+        }
+    }
+}
+
+mod verification_mock {
+    use super::PublisherTier;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    #[contract]
+    pub struct MockPublisherVerification;
+
+    #[contractimpl]
+    impl MockPublisherVerification {
+        pub fn set_tier(env: Env, publisher: Address, tier: PublisherTier) {
+            env.storage().persistent().set(&publisher, &tier);
+        }
+
+        pub fn suspend(env: Env, publisher: Address) {
+            env.storage()
+                .persistent()
+                .set(&(symbol_short!("suspended"), publisher), &true);
+        }
+
+        pub fn get_tier(env: Env, publisher: Address) -> Option<PublisherTier> {
+            env.storage().persistent().get(&publisher)
+        }
+
+        pub fn is_verified(env: Env, publisher: Address) -> bool {
+            env.storage().persistent().has(&publisher)
+                && !env
+                    .storage()
+                    .persistent()
+                    .has(&(symbol_short!("suspended"), publisher))
+        }
+    }
+}
+
 fn setup(
     env: &Env,
 ) -> (
@@ -21,14 +113,50 @@ fn setup(
     let admin = Address::generate(env);
     let token_admin = Address::generate(env);
     let token = deploy_token(env, &token_admin);
-    let id = env.register_contract(None, RewardsDistributorContract);
+    let id = env.register(RewardsDistributorContract, ());
     let c = RewardsDistributorContractClient::new(env, &id);
     c.initialize(&admin, &token);
+    mint(env, &token, &admin, ADMIN_FUNDS);
     (c, admin, token_admin, token)
 }
+
+/// Minted to the admin by `setup`; program budgets are pulled from it
+const ADMIN_FUNDS: i128 = 10_000_000;
+/// Sign a relayed call the way a wallet would
+fn sign_meta<P: soroban_sdk::xdr::ToXdr>(
+    env: &Env,
+    contract: &Address,
+    key: &SigningKey,
+    action: &str,
+    payload: P,
+    nonce: u64,
+) -> MetaAuth {
+    let expiry = env.ledger().timestamp() + 300;
+    let digest = env.as_contract(contract, || {
+        pulsar_common_meta::message(env, &Symbol::new(env, action), payload, nonce, expiry)
+    });
+    MetaAuth {
+        signer: BytesN::from_array(env, &key.verifying_key().to_bytes()),
+        nonce,
+        expiry,
+        signature: BytesN::from_array(env, &key.sign(&digest.to_array()).to_bytes()),
+    }
+}
+
+fn account_of(env: &Env, key: &SigningKey) -> Address {
+    let strkey = stellar_strkey::ed25519::PublicKey(key.verifying_key().to_bytes()).to_string();
+    Address::from_str(env, &strkey)
+}
+
 fn s(env: &Env, v: &str) -> String {
     String::from_str(env, v)
 }
+fn no_vesting() -> VestingConfig {
+    VestingConfig {
+        cliff: 0,
+        duration: 0,
+    }
+}
 
 #[test]
 fn test_initialize() {
@@ -38,12 +166,14 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, admin, _, token) = setup(&env);
-    c.initialize(&admin, &token);
+    assert_eq!(
+        c.try_initialize(&admin, &token),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -57,6 +187,7 @@ fn test_create_program() {
         &1_000_000i128,
         &100i128,
         &10_000u32,
+        &no_vesting(),
     );
     let prog = c.get_program(&pid).unwrap();
     assert_eq!(prog.total_budget, 1_000_000);
@@ -68,14 +199,13 @@ fn test_distribute_rewards() {
     env.mock_all_auths();
     let (c, admin, _, token) = setup(&env);
     let recipient = Address::generate(&env);
-    let contract_addr = c.address.clone();
-    mint(&env, &token, &contract_addr, 10_000_000);
     c.create_program(
         &admin,
         &s(&env, "Staking"),
         &1_000_000i128,
         &100i128,
         &10_000u32,
+        &no_vesting(),
     );
     c.distribute_rewards(&admin, &recipient, &5_000i128, &1u32);
     let rewards = c.get_user_rewards(&recipient).unwrap();
@@ -97,3 +227,876 @@ fn test_get_user_rewards_nonexistent() {
     let (c, _, _, _) = setup(&env);
     assert!(c.get_user_rewards(&Address::generate(&env)).is_none());
 }
+
+fn leaf(env: &Env, user: &Address, amount: i128) -> BytesN<32> {
+    RewardsDistributorContract::_merkle_leaf(env, user, amount)
+}
+
+fn pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    RewardsDistributorContract::_hash_pair(env, a, b)
+}
+
+#[test]
+fn test_claim_with_proof() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Airdrop"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let la = leaf(&env, &alice, 1_000);
+    let lb = leaf(&env, &bob, 2_000);
+    let lc = leaf(&env, &carol, 3_000);
+    let ab = pair(&env, &la, &lb);
+    let root = pair(&env, &ab, &lc);
+
+    c.set_distribution_root(&admin, &pid, &1u32, &root, &6_000i128);
+    assert_eq!(c.get_program(&pid).unwrap().distributed, 6_000);
+
+    c.claim_with_proof(
+        &alice,
+        &pid,
+        &1u32,
+        &1_000i128,
+        &vec![&env, lb.clone(), lc.clone()],
+    );
+    c.claim_with_proof(&carol, &pid, &1u32, &3_000i128, &vec![&env, ab]);
+
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&alice), 1_000);
+    assert_eq!(tc.balance(&carol), 3_000);
+    assert!(c.has_claimed_epoch(&alice, &pid, &1u32));
+    assert!(!c.has_claimed_epoch(&bob, &pid, &1u32));
+    assert_eq!(c.get_distribution_root(&pid, &1u32).unwrap().claimed, 4_000);
+}
+
+#[test]
+fn test_claim_with_proof_wrong_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Airdrop"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let la = leaf(&env, &alice, 1_000);
+    let lb = leaf(&env, &bob, 2_000);
+    let root = pair(&env, &la, &lb);
+    c.set_distribution_root(&admin, &pid, &1u32, &root, &3_000i128);
+
+    assert_eq!(
+        c.try_claim_with_proof(&alice, &pid, &1u32, &2_000i128, &vec![&env, lb]),
+        Err(Ok(PulsarError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_claim_with_proof_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Airdrop"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+
+    let alice = Address::generate(&env);
+    let root = leaf(&env, &alice, 1_000);
+    c.set_distribution_root(&admin, &pid, &1u32, &root, &1_000i128);
+
+    let proof: Vec<BytesN<32>> = Vec::new(&env);
+    c.claim_with_proof(&alice, &pid, &1u32, &1_000i128, &proof);
+    assert_eq!(
+        c.try_claim_with_proof(&alice, &pid, &1u32, &1_000i128, &proof),
+        Err(Ok(PulsarError::AlreadyExists))
+    );
+}
+
+#[test]
+fn test_claim_with_proof_follows_vesting() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Airdrop"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &VestingConfig {
+            cliff: 100,
+            duration: 1_000,
+        },
+    );
+
+    let alice = Address::generate(&env);
+    let root = leaf(&env, &alice, 1_000);
+    c.set_distribution_root(&admin, &pid, &1u32, &root, &1_000i128);
+
+    // Inside the cliff the allocation is credited but nothing is paid
+    let proof: Vec<BytesN<32>> = Vec::new(&env);
+    assert_eq!(
+        c.claim_with_proof(&alice, &pid, &1u32, &1_000i128, &proof),
+        0
+    );
+    assert_eq!(c.get_tranche(&alice, &pid).unwrap().earned, 1_000);
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&alice), 0);
+
+    env.ledger().with_mut(|li| li.timestamp += 500);
+    assert_eq!(c.claim_rewards(&alice), 500);
+    assert_eq!(tc.balance(&alice), 500);
+}
+
+#[test]
+fn test_claim_with_proof_paused_program() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Airdrop"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    let alice = Address::generate(&env);
+    let root = leaf(&env, &alice, 1_000);
+    c.set_distribution_root(&admin, &pid, &1u32, &root, &1_000i128);
+    c.pause_program(&admin, &pid);
+
+    assert_eq!(
+        c.try_claim_with_proof(&alice, &pid, &1u32, &1_000i128, &Vec::new(&env)),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_close_program_returns_unclaimed_merkle_remainder() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let treasury = Address::generate(&env);
+    c.set_treasury(&admin, &treasury);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Airdrop"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let la = leaf(&env, &alice, 1_000);
+    let lb = leaf(&env, &bob, 2_000);
+    let root = pair(&env, &la, &lb);
+    c.set_distribution_root(&admin, &pid, &1u32, &root, &3_000i128);
+    c.claim_with_proof(&alice, &pid, &1u32, &1_000i128, &vec![&env, lb]);
+
+    // Bob never claimed: his 2_000 goes back with the unreserved 7_000
+    assert_eq!(c.close_program(&admin, &pid), 9_000);
+    assert_eq!(TokenClient::new(&env, &token).balance(&treasury), 9_000);
+    let distribution = c.get_distribution_root(&pid, &1u32).unwrap();
+    assert_eq!(distribution.total, 1_000);
+    assert_eq!(c.get_program(&pid).unwrap().total_budget, 1_000);
+
+    let result = c.try_claim_with_proof(&bob, &pid, &1u32, &2_000i128, &vec![&env, la]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_distribution_root_exceeds_budget() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, _) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Airdrop"),
+        &1_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    let root = BytesN::from_array(&env, &[7u8; 32]);
+    assert_eq!(
+        c.try_set_distribution_root(&admin, &pid, &1u32, &root, &5_000i128),
+        Err(Ok(PulsarError::InsufficientBalance))
+    );
+}
+
+#[test]
+fn test_claim_immediate_without_vesting() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Bounty"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    let user = Address::generate(&env);
+    c.distribute_rewards(&admin, &user, &700i128, &pid);
+    assert_eq!(c.claim_rewards(&user), 700);
+    assert_eq!(TokenClient::new(&env, &token).balance(&user), 700);
+}
+
+#[test]
+fn test_claim_rewards_signed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let token_id = env.register(token_mock::MockToken, ());
+    let token = token_mock::MockTokenClient::new(&env, &token_id);
+    let id = env.register(RewardsDistributorContract, ());
+    let c = RewardsDistributorContractClient::new(&env, &id);
+    c.initialize(&admin, &token_id);
+    token.mint(&admin, &10_000);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Bounty"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+
+    let key = SigningKey::from_bytes(&[5u8; 32]);
+    let user = account_of(&env, &key);
+    c.distribute_rewards(&admin, &user, &700i128, &pid);
+
+    let auth = sign_meta(&env, &id, &key, "claim_rewards", (), 0);
+    c.set_meta_enabled(&auth.signer, &true);
+    assert_eq!(c.claim_rewards_signed(&auth), 700);
+    assert_eq!(token.balance(&user), 700);
+    assert_eq!(c.get_meta_nonce(&auth.signer), 1);
+}
+
+#[test]
+fn test_vesting_per_program_tranche() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let staking = c.create_program(
+        &admin,
+        &s(&env, "Staking"),
+        &50_000i128,
+        &1i128,
+        &10_000u32,
+        &VestingConfig {
+            cliff: 100,
+            duration: 1_000,
+        },
+    );
+    let referral = c.create_program(
+        &admin,
+        &s(&env, "Referral"),
+        &50_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+
+    let user = Address::generate(&env);
+    c.distribute_rewards(&admin, &user, &1_000i128, &staking);
+    c.distribute_rewards(&admin, &user, &300i128, &referral);
+
+    // Before the cliff only the referral bounty is claimable
+    env.ledger().with_mut(|li| li.timestamp += 50);
+    assert_eq!(c.claim_rewards(&user), 300);
+
+    // Half way through the staking schedule
+    env.ledger().with_mut(|li| li.timestamp += 450);
+    assert_eq!(c.claim_rewards(&user), 500);
+    assert_eq!(c.get_tranche(&user, &staking).unwrap().claimed, 500);
+
+    env.ledger().with_mut(|li| li.timestamp += 10_000);
+    assert_eq!(c.claim_rewards(&user), 500);
+    assert_eq!(c.get_user_rewards(&user).unwrap().total_claimed, 1_300);
+}
+
+#[test]
+fn test_claim_before_cliff_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Staking"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &VestingConfig {
+            cliff: 100,
+            duration: 1_000,
+        },
+    );
+    let user = Address::generate(&env);
+    c.distribute_rewards(&admin, &user, &1_000i128, &pid);
+    env.ledger().with_mut(|li| li.timestamp += 99);
+    assert_eq!(
+        c.try_claim_rewards(&user),
+        Err(Ok(PulsarError::InsufficientBalance))
+    );
+}
+
+#[test]
+fn test_accrue_for_impressions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, _) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Serve"),
+        &10_000i128,
+        &5i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    let orchestrator = Address::generate(&env);
+    let publisher = Address::generate(&env);
+    c.authorize_caller(&admin, &orchestrator);
+    c.set_activity_program(&admin, &ActivityKind::Impressions, &pid);
+
+    assert_eq!(
+        c.accrue_for_impressions(&orchestrator, &publisher, &120u64),
+        600
+    );
+    assert_eq!(c.get_user_rewards(&publisher).unwrap().total_earned, 600);
+    assert_eq!(c.get_program(&pid).unwrap().distributed, 600);
+}
+
+#[test]
+fn test_accrue_unregistered_caller() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, _) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Review"),
+        &10_000i128,
+        &5i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    c.set_activity_program(&admin, &ActivityKind::Reviews, &pid);
+    assert_eq!(
+        c.try_accrue_for_reviews(&Address::generate(&env), &Address::generate(&env), &1u64),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_accrue_without_activity_program() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, _) = setup(&env);
+    let caller = Address::generate(&env);
+    c.authorize_caller(&admin, &caller);
+    assert_eq!(
+        c.try_accrue_for_reviews(&caller, &Address::generate(&env), &1u64),
+        Err(Ok(PulsarError::NotFound))
+    );
+}
+
+#[test]
+fn test_pause_program_blocks_distribution() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, _) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Staking"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    c.pause_program(&admin, &pid);
+    assert_eq!(
+        c.try_distribute_rewards(&admin, &Address::generate(&env), &100i128, &pid),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_pause_and_resume_program() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, _) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Staking"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    c.pause_program(&admin, &pid);
+    assert!(!c.get_program(&pid).unwrap().is_active);
+    c.resume_program(&admin, &pid);
+    c.distribute_rewards(&admin, &Address::generate(&env), &100i128, &pid);
+    assert_eq!(c.get_program(&pid).unwrap().distributed, 100);
+}
+
+#[test]
+fn test_extend_program() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Staking"),
+        &10_000i128,
+        &1i128,
+        &100u32,
+        &no_vesting(),
+    );
+    let before = c.get_program(&pid).unwrap();
+    c.extend_program(&admin, &pid, &500u32, &5_000i128);
+    let after = c.get_program(&pid).unwrap();
+    assert_eq!(after.end_ledger, before.end_ledger + 500);
+    assert_eq!(after.total_budget, 15_000);
+    assert_eq!(TokenClient::new(&env, &token).balance(&c.address), 15_000);
+}
+
+#[test]
+fn test_close_program_sweeps_to_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let treasury = Address::generate(&env);
+    c.set_treasury(&admin, &treasury);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Staking"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    let user = Address::generate(&env);
+    c.distribute_rewards(&admin, &user, &4_000i128, &pid);
+
+    assert_eq!(c.close_program(&admin, &pid), 6_000);
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&treasury), 6_000);
+    let prog = c.get_program(&pid).unwrap();
+    assert!(prog.is_closed);
+    assert_eq!(prog.total_budget, 4_000);
+
+    // Credited rewards stay claimable after close
+    assert_eq!(c.claim_rewards(&user), 4_000);
+}
+
+#[test]
+fn test_create_program_pulls_budget_from_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    c.create_program(
+        &admin,
+        &s(&env, "Staking"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&c.address), 10_000);
+    assert_eq!(tc.balance(&admin), ADMIN_FUNDS - 10_000);
+}
+
+#[test]
+fn test_close_program_leaves_other_programs_funded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let treasury = Address::generate(&env);
+    c.set_treasury(&admin, &treasury);
+    let closing = c.create_program(
+        &admin,
+        &s(&env, "Staking"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    let other = c.create_program(
+        &admin,
+        &s(&env, "Referral"),
+        &3_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+
+    assert_eq!(c.close_program(&admin, &closing), 10_000);
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&c.address), 3_000);
+
+    let user = Address::generate(&env);
+    c.distribute_rewards(&admin, &user, &3_000i128, &other);
+    assert_eq!(c.claim_rewards(&user), 3_000);
+}
+
+#[test]
+fn test_resume_closed_program_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, _) = setup(&env);
+    c.set_treasury(&admin, &Address::generate(&env));
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Staking"),
+        &0i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    c.close_program(&admin, &pid);
+    assert_eq!(
+        c.try_resume_program(&admin, &pid),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_get_claimable() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, _) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Staking"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &VestingConfig {
+            cliff: 100,
+            duration: 1_000,
+        },
+    );
+    let user = Address::generate(&env);
+    let start = env.ledger().timestamp();
+    c.distribute_rewards(&admin, &user, &1_000i128, &pid);
+
+    let before = c.get_claimable(&user);
+    assert_eq!(before.vested, 0);
+    assert_eq!(before.locked, 1_000);
+    assert_eq!(before.next_unlock_ts, start + 100);
+
+    env.ledger().with_mut(|li| li.timestamp += 250);
+    let during = c.get_claimable(&user);
+    assert_eq!(during.vested, 250);
+    assert_eq!(during.locked, 750);
+
+    env.ledger().with_mut(|li| li.timestamp += 1_000);
+    let after = c.get_claimable(&user);
+    assert_eq!(after.vested, 1_000);
+    assert_eq!(after.locked, 0);
+    assert_eq!(after.next_unlock_ts, 0);
+}
+
+#[test]
+fn test_claim_rewards_partial() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Bounty"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    let user = Address::generate(&env);
+    c.distribute_rewards(&admin, &user, &1_000i128, &pid);
+
+    assert_eq!(c.claim_rewards_partial(&user, &300i128), 300);
+    assert_eq!(c.get_claimable(&user).vested, 700);
+    assert_eq!(TokenClient::new(&env, &token).balance(&user), 300);
+    assert_eq!(c.claim_rewards(&user), 700);
+}
+
+#[test]
+fn test_claim_rewards_partial_exceeds_vested() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Bounty"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    let user = Address::generate(&env);
+    c.distribute_rewards(&admin, &user, &1_000i128, &pid);
+    assert_eq!(
+        c.try_claim_rewards_partial(&user, &1_001i128),
+        Err(Ok(PulsarError::InsufficientBalance))
+    );
+}
+
+#[test]
+fn test_accrue_applies_tier_multiplier() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, _) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Serve"),
+        &100_000i128,
+        &10i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    let orchestrator = Address::generate(&env);
+    c.authorize_caller(&admin, &orchestrator);
+    c.set_activity_program(&admin, &ActivityKind::Impressions, &pid);
+
+    let verification_id = env.register(verification_mock::MockPublisherVerification, ());
+    let verification =
+        verification_mock::MockPublisherVerificationClient::new(&env, &verification_id);
+    c.set_publisher_verification(&admin, &verification_id);
+    c.set_tier_multiplier(&admin, &PublisherTier::Platinum, &15_000u32);
+
+    let platinum = Address::generate(&env);
+    let bronze = Address::generate(&env);
+    verification.set_tier(&platinum, &PublisherTier::Platinum);
+    verification.set_tier(&bronze, &PublisherTier::Bronze);
+
+    assert_eq!(
+        c.accrue_for_impressions(&orchestrator, &platinum, &100u64),
+        1_500
+    );
+    assert_eq!(
+        c.accrue_for_impressions(&orchestrator, &bronze, &100u64),
+        1_000
+    );
+    // Unregistered publishers accrue at the base rate
+    assert_eq!(
+        c.accrue_for_impressions(&orchestrator, &Address::generate(&env), &100u64),
+        1_000
+    );
+    // So do suspended ones, whatever their tier
+    verification.suspend(&platinum);
+    assert_eq!(
+        c.accrue_for_impressions(&orchestrator, &platinum, &100u64),
+        1_000
+    );
+}
+
+#[test]
+fn test_set_tier_multiplier_too_high() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, _) = setup(&env);
+    assert_eq!(
+        c.try_set_tier_multiplier(&admin, &PublisherTier::Gold, &60_000u32),
+        Err(Ok(PulsarError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_expire_unclaimed_after_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let treasury = Address::generate(&env);
+    c.set_treasury(&admin, &treasury);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Staking"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &VestingConfig {
+            cliff: 0,
+            duration: 1_000,
+        },
+    );
+    c.set_claim_window(&admin, &pid, &500u64);
+    let user = Address::generate(&env);
+    c.distribute_rewards(&admin, &user, &1_000i128, &pid);
+
+    env.ledger().with_mut(|li| li.timestamp += 400);
+    assert_eq!(c.claim_rewards(&user), 400);
+    c.close_program(&admin, &pid);
+    env.ledger().with_mut(|li| li.timestamp += 500);
+
+    assert_eq!(c.expire_unclaimed(&admin, &user, &pid), 600);
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&treasury), 9_000 + 600);
+    assert_eq!(c.get_user_rewards(&user).unwrap().total_earned, 400);
+    assert_eq!(c.get_program(&pid).unwrap().distributed, 400);
+    assert_eq!(c.get_claimable(&user).vested, 0);
+}
+
+#[test]
+fn test_expire_unclaimed_within_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    c.set_treasury(&admin, &Address::generate(&env));
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Bounty"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    c.set_claim_window(&admin, &pid, &500u64);
+    let user = Address::generate(&env);
+    c.distribute_rewards(&admin, &user, &1_000i128, &pid);
+    c.close_program(&admin, &pid);
+    env.ledger().with_mut(|li| li.timestamp += 499);
+    assert_eq!(
+        c.try_expire_unclaimed(&admin, &user, &pid),
+        Err(Ok(PulsarError::TooEarly))
+    );
+}
+
+#[test]
+fn test_expire_unclaimed_open_program() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, _) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Bounty"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    c.set_claim_window(&admin, &pid, &500u64);
+    let user = Address::generate(&env);
+    c.distribute_rewards(&admin, &user, &1_000i128, &pid);
+    assert_eq!(
+        c.try_expire_unclaimed(&admin, &user, &pid),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_distribute_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, _) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Monthly"),
+        &10_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let batch = vec![
+        &env,
+        (a.clone(), 1_000i128),
+        (b.clone(), 2_500i128),
+        (a.clone(), 500i128),
+    ];
+
+    assert_eq!(c.distribute_batch(&admin, &pid, &batch), 4_000);
+    assert_eq!(c.get_user_rewards(&a).unwrap().total_earned, 1_500);
+    assert_eq!(c.get_user_rewards(&b).unwrap().total_earned, 2_500);
+    assert_eq!(c.get_tranche(&a, &pid).unwrap().earned, 1_500);
+    assert_eq!(c.get_program(&pid).unwrap().distributed, 4_000);
+}
+
+#[test]
+fn test_distribute_batch_exceeds_budget() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, _) = setup(&env);
+    let pid = c.create_program(
+        &admin,
+        &s(&env, "Monthly"),
+        &3_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    let batch = vec![
+        &env,
+        (Address::generate(&env), 2_000i128),
+        (Address::generate(&env), 2_000i128),
+    ];
+    assert_eq!(
+        c.try_distribute_batch(&admin, &pid, &batch),
+        Err(Ok(PulsarError::InsufficientBalance))
+    );
+}
+
+#[test]
+fn test_create_program_from_dao() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token) = setup(&env);
+    let dao = Address::generate(&env);
+    mint(&env, &token, &dao, 50_000);
+    c.set_dao_contract(&admin, &dao);
+
+    let pid = c.create_program_from_dao(
+        &dao,
+        &s(&env, "Grants"),
+        &20_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    assert_eq!(c.get_program(&pid).unwrap().total_budget, 20_000);
+    let tc = TokenClient::new(&env, &token);
+    assert_eq!(tc.balance(&c.address), 20_000);
+    assert_eq!(tc.balance(&dao), 30_000);
+}
+
+#[test]
+fn test_create_program_from_non_dao() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, _) = setup(&env);
+    c.set_dao_contract(&admin, &Address::generate(&env));
+    let result = c.try_create_program_from_dao(
+        &Address::generate(&env),
+        &s(&env, "Grants"),
+        &20_000i128,
+        &1i128,
+        &10_000u32,
+        &no_vesting(),
+    );
+    assert_eq!(result, Err(Ok(PulsarError::Unauthorized)));
+}
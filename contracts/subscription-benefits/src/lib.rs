@@ -4,6 +4,8 @@
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String};
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone)]
 pub struct Benefit {
@@ -45,18 +47,19 @@ pub struct SubscriptionBenefitsContract;
 
 #[contractimpl]
 impl SubscriptionBenefitsContract {
-    pub fn initialize(env: Env, admin: Address) {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage()
             .instance()
             .set(&DataKey::BenefitCounter, &0u32);
+        Ok(())
     }
 
     pub fn add_benefit(
@@ -65,14 +68,18 @@ impl SubscriptionBenefitsContract {
         name: String,
         description: String,
         min_tier: u32,
-    ) -> u32 {
+    ) -> Result<u32, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let counter: u32 = env
@@ -101,7 +108,7 @@ impl SubscriptionBenefitsContract {
             .instance()
             .set(&DataKey::BenefitCounter, &benefit_id);
 
-        benefit_id
+        Ok(benefit_id)
     }
 
     pub fn check_benefit_access(
@@ -124,7 +131,12 @@ impl SubscriptionBenefitsContract {
         }
     }
 
-    pub fn use_benefit(env: Env, subscriber: Address, benefit_id: u32, subscriber_tier: u32) {
+    pub fn use_benefit(
+        env: Env,
+        subscriber: Address,
+        benefit_id: u32,
+        subscriber_tier: u32,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -132,7 +144,7 @@ impl SubscriptionBenefitsContract {
 
         if !Self::check_benefit_access(env.clone(), subscriber.clone(), benefit_id, subscriber_tier)
         {
-            panic!("access denied");
+            return Err(PulsarError::Unauthorized);
         }
 
         let now = env.ledger().timestamp();
@@ -158,7 +170,7 @@ impl SubscriptionBenefitsContract {
         }
 
         if usage.uses_this_period >= usage.max_uses_per_period {
-            panic!("usage limit reached");
+            return Err(PulsarError::LimitExceeded);
         }
 
         usage.uses_this_period += 1;
@@ -168,6 +180,7 @@ impl SubscriptionBenefitsContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     pub fn get_benefit(env: Env, benefit_id: u32) -> Option<Benefit> {
@@ -188,18 +201,30 @@ impl SubscriptionBenefitsContract {
             .get(&DataKey::BenefitUsage(subscriber, benefit_id))
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
@@ -3,16 +3,28 @@
 //!
 //! # Subscription Lifecycle
 //!
-//! - `subscribe()`    — New subscriptions only (panics if one is active).
+//! - `subscribe()`    — New subscriptions only (errors if one is active).
+//! - `subscribe_signed()` — Relayed `subscribe`, paid from the subscriber's allowance.
 //! - `change_tier()`  — Upgrade to a higher tier with prorated credit (blocks downgrades).
 //! - `renew()`        — Extend the current tier; stacks on top of remaining time.
 //! - `cancel()`       — Disables auto-renewal; subscription remains active until expiry.
+//! - `emit_renewal_reminders()` — Permissionless; emits ("sub", "expiring") once
+//!   per period for subscriptions inside the reminder window.
+//! - `purge_lapsed()` — Admin or keeper; archives ("sub", "archived") and
+//!   deletes subscriptions expired for longer than the retention window.
+//!
+//! Every charge, including zero-amount upgrades, issues an `Invoice` listed by
+//! `list_invoices()` for off-chain reconciliation.
 
 #![no_std]
+use pulsar_common_meta::MetaAuth;
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, token, Address, Env, String, Symbol,
+    contract, contractimpl, contracttype, symbol_short, token, Address, BytesN, Env, String,
+    Symbol, Vec,
 };
 
+pub use pulsar_common_errors::PulsarError;
+
 // ============================================================
 // Data Types
 // ============================================================
@@ -55,6 +67,35 @@ pub struct Subscription {
     pub impressions_used: u64, // preserved across upgrades and renewals
 }
 
+/// What a charge paid for
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub enum InvoiceKind {
+    New,
+    Renewal,
+    AutoRenewal,
+    Upgrade,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Invoice {
+    pub invoice_id: u64,
+    pub subscriber: Address,
+    pub kind: InvoiceKind,
+    pub tier: SubscriptionTier,
+    pub is_annual: bool,
+    pub period_start: u64,
+    pub period_end: u64,
+    pub amount: i128, // charged to the subscriber; 0 when credit covered it
+    pub credit: i128, // prorated credit applied (upgrades only)
+    pub token: Address,
+    pub treasury: Address,
+    pub relayed: bool, // paid from an allowance via `subscribe_signed`
+    pub ledger: u32,
+    pub issued_at: u64,
+}
+
 // ============================================================
 // Storage Keys
 // ============================================================
@@ -68,6 +109,14 @@ pub enum DataKey {
     TreasuryAddress,
     Plan(SubscriptionTier),
     Subscription(Address),
+    InvoiceCounter,
+    Invoice(u64),
+    SubscriberInvoiceCount(Address),
+    SubscriberInvoice(Address, u32), // (subscriber, index) -> invoice_id
+    ReminderWindow,
+    Reminded(Address), // expires_at of the period last reminded about
+    RetentionWindow,
+    PurgeKeeper,
 }
 
 // ============================================================
@@ -79,6 +128,10 @@ const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
 const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
 
+const MAX_PAGE_SIZE: u32 = 50;
+const DEFAULT_REMINDER_WINDOW: u64 = 7 * 24 * 3600;
+const DEFAULT_RETENTION_WINDOW: u64 = 90 * 24 * 3600;
+
 // ============================================================
 // Pure Helpers  (zero allocations, O(1))
 // ============================================================
@@ -144,6 +197,77 @@ fn save_subscription(env: &Env, sub: &Subscription) {
     );
 }
 
+/// Record the charge that produced `sub`'s current period. Returns the
+/// invoice id.
+fn issue_invoice(
+    env: &Env,
+    sub: &Subscription,
+    kind: InvoiceKind,
+    period_start: u64,
+    amount: i128,
+    credit: i128,
+    relayed: bool,
+) -> u64 {
+    let invoice_id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::InvoiceCounter)
+        .unwrap_or(0u64)
+        + 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::InvoiceCounter, &invoice_id);
+
+    let (token, treasury) = load_token_and_treasury(env);
+    let invoice = Invoice {
+        invoice_id,
+        subscriber: sub.subscriber.clone(),
+        kind,
+        tier: sub.tier.clone(),
+        is_annual: sub.is_annual,
+        period_start,
+        period_end: sub.expires_at,
+        amount,
+        credit,
+        token,
+        treasury,
+        relayed,
+        ledger: env.ledger().sequence(),
+        issued_at: env.ledger().timestamp(),
+    };
+    let key = DataKey::Invoice(invoice_id);
+    env.storage().persistent().set(&key, &invoice);
+    env.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+
+    let count_key = DataKey::SubscriberInvoiceCount(sub.subscriber.clone());
+    let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+    let index_key = DataKey::SubscriberInvoice(sub.subscriber.clone(), count);
+    env.storage().persistent().set(&index_key, &invoice_id);
+    env.storage().persistent().extend_ttl(
+        &index_key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+    env.storage().persistent().set(&count_key, &(count + 1));
+    env.storage().persistent().extend_ttl(
+        &count_key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+
+    pulsar_common_admin::publish_event(
+        env,
+        symbol_short!("sub"),
+        symbol_short!("invoice"),
+        (invoice_id, sub.subscriber.clone(), amount),
+    );
+    invoice_id
+}
+
 #[inline]
 fn load_subscription(env: &Env, subscriber: &Address) -> Option<Subscription> {
     env.storage()
@@ -152,11 +276,11 @@ fn load_subscription(env: &Env, subscriber: &Address) -> Option<Subscription> {
 }
 
 #[inline]
-fn load_plan(env: &Env, tier: SubscriptionTier) -> SubscriptionPlan {
+fn load_plan(env: &Env, tier: SubscriptionTier) -> Result<SubscriptionPlan, PulsarError> {
     env.storage()
         .persistent()
         .get(&DataKey::Plan(tier))
-        .expect("plan not found")
+        .ok_or(PulsarError::NotFound)
 }
 
 #[inline]
@@ -184,6 +308,34 @@ fn charge(env: &Env, from: &Address, amount: i128) {
     }
 }
 
+/// Relayed calls carry no Soroban auth from the subscriber, so payment is
+/// drawn from an allowance the subscriber granted this contract.
+fn charge_allowance(env: &Env, from: &Address, amount: i128) {
+    if amount > 0 {
+        let (token_addr, treasury) = load_token_and_treasury(env);
+        token::Client::new(env, &token_addr).transfer_from(
+            &env.current_contract_address(),
+            from,
+            &treasury,
+            &amount,
+        );
+    }
+}
+
+/// (tier, name, monthly_stroops, annual_stroops, max_campaigns,
+///  max_impressions/month, max_publishers, analytics, api_access)
+type PlanRow = (
+    SubscriptionTier,
+    &'static str,
+    i128,
+    i128,
+    u32,
+    u64,
+    u32,
+    bool,
+    bool,
+);
+
 #[inline]
 fn plan_price(plan: &SubscriptionPlan, is_annual: bool) -> i128 {
     if is_annual {
@@ -206,12 +358,17 @@ impl SubscriptionManagerContract {
     // Admin
     // ----------------------------------------------------------
 
-    pub fn initialize(env: Env, admin: Address, token: Address, treasury: Address) {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        treasury: Address,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -220,6 +377,35 @@ impl SubscriptionManagerContract {
             .instance()
             .set(&DataKey::TreasuryAddress, &treasury);
         Self::_init_plans(&env);
+        Ok(())
+    }
+
+    /// How long before expiry `emit_renewal_reminders` starts reporting a
+    /// subscription. Defaults to 7 days.
+    pub fn set_reminder_window(
+        env: Env,
+        admin: Address,
+        window_secs: u64,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        if window_secs == 0 {
+            return Err(PulsarError::InvalidInput);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ReminderWindow, &window_secs);
+        Ok(())
     }
 
     // ----------------------------------------------------------
@@ -228,7 +414,8 @@ impl SubscriptionManagerContract {
 
     /// Create a **new** subscription.
     ///
-    /// Panics if the subscriber already has an active subscription.
+    /// Errors with `AlreadyExists` if the subscriber already has an active
+    /// subscription.
     /// Use `change_tier()` to upgrade, or `renew()` to extend.
     pub fn subscribe(
         env: Env,
@@ -236,44 +423,50 @@ impl SubscriptionManagerContract {
         tier: SubscriptionTier,
         is_annual: bool,
         auto_renew: bool,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         subscriber.require_auth();
+        Self::_subscribe(&env, subscriber, tier, is_annual, auto_renew, false)
+    }
 
-        let now = env.ledger().timestamp();
-
-        // Guard: block re-subscription while active.
-        if let Some(existing) = load_subscription(&env, &subscriber) {
-            if existing.expires_at > now {
-                panic!("already active: use change_tier or renew");
-            }
-        }
-
-        let plan = load_plan(&env, tier.clone());
-        let amount = plan_price(&plan, is_annual);
-        let period = plan_period_secs(is_annual);
-
-        charge(&env, &subscriber, amount);
+    /// Relayed `subscribe` for the account whose key signed
+    /// `(tier, is_annual, auto_renew)` (see pulsar-common-meta). The price
+    /// is paid from the subscriber's token allowance to this contract.
+    pub fn subscribe_signed(
+        env: Env,
+        tier: SubscriptionTier,
+        is_annual: bool,
+        auto_renew: bool,
+        auth: MetaAuth,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let subscriber = pulsar_common_meta::verify(
+            &env,
+            symbol_short!("subscribe"),
+            (tier.clone(), is_annual, auto_renew),
+            &auth,
+        )?;
+        Self::_subscribe(&env, subscriber, tier, is_annual, auto_renew, true)
+    }
 
-        let sub = Subscription {
-            subscriber: subscriber.clone(),
-            tier,
-            is_annual,
-            amount_paid: amount,
-            started_at: now,
-            expires_at: now + period,
-            auto_renew,
-            campaigns_used: 0,
-            impressions_used: 0,
-        };
-        save_subscription(&env, &sub);
+    /// Opt the signing key's account in or out of relayed calls; needs the
+    /// account's own authorization (see pulsar-common-meta)
+    pub fn set_meta_enabled(env: Env, signer: BytesN<32>, enabled: bool) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_meta::set_enabled(&env, &signer, enabled);
+    }
 
-        env.events().publish(
-            (symbol_short!("sub"), symbol_short!("new")),
-            (subscriber, amount),
-        );
+    pub fn get_meta_nonce(env: Env, signer: BytesN<32>) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_meta::nonce(&env, &signer)
     }
 
     // ----------------------------------------------------------
@@ -287,7 +480,7 @@ impl SubscriptionManagerContract {
     /// - Preserves `campaigns_used` and `impressions_used`.
     /// - Resets the billing period from now for the new tier.
     ///
-    /// Panics:
+    /// Errors:
     /// - no active subscription exists.
     /// - `new_tier` has a lower rank than the current tier (downgrade blocked).
     /// - `new_tier` is identical to the current tier; use `renew()` instead.
@@ -297,7 +490,7 @@ impl SubscriptionManagerContract {
         new_tier: SubscriptionTier,
         is_annual: bool,
         auto_renew: bool,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -307,16 +500,16 @@ impl SubscriptionManagerContract {
 
         let existing = load_subscription(&env, &subscriber)
             .filter(|s| s.expires_at > now)
-            .unwrap_or_else(|| panic!("no active subscription"));
+            .ok_or(PulsarError::NotFound)?;
 
         let current_rank = tier_rank(&existing.tier);
         let new_rank = tier_rank(&new_tier);
 
         if new_rank < current_rank {
-            panic!("downgrade not allowed while active");
+            return Err(PulsarError::InvalidInput);
         }
         if new_rank == current_rank {
-            panic!("same tier: use renew instead");
+            return Err(PulsarError::InvalidInput);
         }
 
         // Prorate credit from the existing subscription.
@@ -327,7 +520,7 @@ impl SubscriptionManagerContract {
             now,
         );
 
-        let new_plan = load_plan(&env, new_tier.clone());
+        let new_plan = load_plan(&env, new_tier.clone())?;
         let new_price = plan_price(&new_plan, is_annual);
 
         // Net charge clamped to 0 (credit can never exceed what was paid).
@@ -348,11 +541,23 @@ impl SubscriptionManagerContract {
             impressions_used: existing.impressions_used,
         };
         save_subscription(&env, &sub);
+        issue_invoice(
+            &env,
+            &sub,
+            InvoiceKind::Upgrade,
+            now,
+            net_charge,
+            credit,
+            false,
+        );
 
-        env.events().publish(
-            (symbol_short!("sub"), symbol_short!("upgrade")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("sub"),
+            symbol_short!("upgrade"),
             (subscriber, net_charge, credit),
         );
+        Ok(())
     }
 
     // ----------------------------------------------------------
@@ -367,58 +572,64 @@ impl SubscriptionManagerContract {
     /// - Preserves `campaigns_used` and `impressions_used`.
     /// - `started_at` is preserved (reflects the original join date).
     ///
-    /// Panics if no subscription record exists for the address.
-    pub fn renew(env: Env, subscriber: Address, is_annual: bool, auto_renew: bool) {
+    /// Errors with `NotFound` if no subscription record exists for the
+    /// address.
+    pub fn renew(
+        env: Env,
+        subscriber: Address,
+        is_annual: bool,
+        auto_renew: bool,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         subscriber.require_auth();
 
-        let now = env.ledger().timestamp();
-
-        let mut existing =
-            load_subscription(&env, &subscriber).unwrap_or_else(|| panic!("no subscription found"));
-
-        let plan = load_plan(&env, existing.tier.clone());
-        let amount = plan_price(&plan, is_annual);
-        let period = plan_period_secs(is_annual);
-
-        charge(&env, &subscriber, amount);
-
-        // Stack on top of remaining time; never go backwards.
-        let base = existing.expires_at.max(now);
-        existing.expires_at = base + period;
-        existing.is_annual = is_annual;
-        existing.auto_renew = auto_renew;
-        existing.amount_paid = amount;
+        let existing = load_subscription(&env, &subscriber).ok_or(PulsarError::NotFound)?;
+        Self::_renew(&env, existing, is_annual, auto_renew, false)
+    }
 
-        save_subscription(&env, &existing);
+    /// `renew` paid from the subscriber's token allowance to this contract,
+    /// for payment schedulers running without the subscriber's signature.
+    /// Only subscriptions with `auto_renew` set can be renewed this way, and
+    /// the flag is kept.
+    ///
+    /// Errors with `NotFound` if no subscription record exists, or
+    /// `InvalidState` if auto-renewal is off.
+    pub fn renew_from_allowance(
+        env: Env,
+        subscriber: Address,
+        is_annual: bool,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        env.events().publish(
-            (symbol_short!("sub"), symbol_short!("renew")),
-            (subscriber, amount),
-        );
+        let existing = load_subscription(&env, &subscriber).ok_or(PulsarError::NotFound)?;
+        if !existing.auto_renew {
+            return Err(PulsarError::InvalidState);
+        }
+        Self::_renew(&env, existing, is_annual, true, true)
     }
 
     /// Auto-renew an expired subscription (keeper bot pattern).
     /// Anyone can call this for a subscriber whose subscription has expired and has auto_renew = true.
-    pub fn auto_renew_subscription(env: Env, subscriber: Address) {
+    pub fn auto_renew_subscription(env: Env, subscriber: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
         let now = env.ledger().timestamp();
-        let mut sub =
-            load_subscription(&env, &subscriber).unwrap_or_else(|| panic!("no subscription found"));
+        let mut sub = load_subscription(&env, &subscriber).ok_or(PulsarError::NotFound)?;
 
         if !sub.auto_renew {
-            panic!("auto renew not enabled");
+            return Err(PulsarError::InvalidState);
         }
         if sub.expires_at > now {
-            panic!("subscription not yet expired");
+            return Err(PulsarError::TooEarly);
         }
 
-        let plan = load_plan(&env, sub.tier.clone());
+        let plan = load_plan(&env, sub.tier.clone())?;
         let amount = plan_price(&plan, sub.is_annual);
         let period = plan_period_secs(sub.is_annual);
 
@@ -426,7 +637,7 @@ impl SubscriptionManagerContract {
         let (token_addr, _) = load_token_and_treasury(&env);
         let token_client = token::Client::new(&env, &token_addr);
         if token_client.balance(&subscriber) < amount {
-            panic!("insufficient balance for auto-renewal");
+            return Err(PulsarError::InsufficientBalance);
         }
 
         // Transfer funds (requires subscriber to have authorized the contract, e.g., via allowance)
@@ -437,11 +648,15 @@ impl SubscriptionManagerContract {
         sub.amount_paid = amount;
 
         save_subscription(&env, &sub);
+        issue_invoice(&env, &sub, InvoiceKind::AutoRenewal, base, amount, 0, false);
 
-        env.events().publish(
-            (symbol_short!("sub"), Symbol::new(&env, "auto_renew")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("sub"),
+            Symbol::new(&env, "auto_renew"),
             (subscriber, amount),
         );
+        Ok(())
     }
 
     // ----------------------------------------------------------
@@ -450,18 +665,189 @@ impl SubscriptionManagerContract {
 
     /// Disable auto-renewal. The subscription remains active until `expires_at`.
     ///
-    /// Panics if no subscription record exists.
-    pub fn cancel(env: Env, subscriber: Address) {
+    /// Errors with `NotFound` if no subscription record exists.
+    pub fn cancel(env: Env, subscriber: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         subscriber.require_auth();
 
-        let mut sub = load_subscription(&env, &subscriber)
-            .unwrap_or_else(|| panic!("subscription not found"));
+        let mut sub = load_subscription(&env, &subscriber).ok_or(PulsarError::NotFound)?;
 
         sub.auto_renew = false;
         save_subscription(&env, &sub);
+        Ok(())
+    }
+
+    // ----------------------------------------------------------
+    // Renewal Reminders
+    // ----------------------------------------------------------
+
+    /// Emit ("sub", "expiring") for each listed subscription that is still
+    /// active and expires within the reminder window. Anyone may call this;
+    /// each period is reported at most once, so repeated or overlapping
+    /// batches from notification bots are harmless. Returns the number of
+    /// reminders emitted.
+    ///
+    /// Data: [subscriber: Address, tier: SubscriptionTier, expires_at: u64, auto_renew: bool]
+    pub fn emit_renewal_reminders(env: Env, subscribers: Vec<Address>) -> Result<u32, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if subscribers.len() > MAX_PAGE_SIZE {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        let now = env.ledger().timestamp();
+        let window = Self::get_reminder_window(env.clone());
+        let mut emitted = 0u32;
+        for subscriber in subscribers.iter() {
+            let sub = match load_subscription(&env, &subscriber) {
+                Some(sub) if sub.expires_at > now && sub.expires_at <= now + window => sub,
+                _ => continue,
+            };
+            let key = DataKey::Reminded(subscriber.clone());
+            if env.storage().persistent().get::<DataKey, u64>(&key) == Some(sub.expires_at) {
+                continue;
+            }
+            env.storage().persistent().set(&key, &sub.expires_at);
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+
+            pulsar_common_admin::publish_event(
+                &env,
+                symbol_short!("sub"),
+                symbol_short!("expiring"),
+                (subscriber, sub.tier, sub.expires_at, sub.auto_renew),
+            );
+            emitted += 1;
+        }
+        Ok(emitted)
+    }
+
+    pub fn get_reminder_window(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::ReminderWindow)
+            .unwrap_or(DEFAULT_REMINDER_WINDOW)
+    }
+
+    // ----------------------------------------------------------
+    // Storage Reclamation
+    // ----------------------------------------------------------
+
+    /// How long after expiry a subscription is kept before `purge_lapsed`
+    /// may delete it. Defaults to 90 days.
+    pub fn set_retention_window(
+        env: Env,
+        admin: Address,
+        window_secs: u64,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        if window_secs == 0 {
+            return Err(PulsarError::InvalidInput);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::RetentionWindow, &window_secs);
+        Ok(())
+    }
+
+    /// Allow `keeper` to call `purge_lapsed` alongside the admin.
+    pub fn set_purge_keeper(env: Env, admin: Address, keeper: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        env.storage().instance().set(&DataKey::PurgeKeeper, &keeper);
+        Ok(())
+    }
+
+    /// Delete the listed subscriptions that expired more than the retention
+    /// window ago, emitting ("sub", "archived") with each final record first.
+    /// Others are skipped. Invoices are kept. Returns the number purged.
+    ///
+    /// Data: [subscription: Subscription]
+    pub fn purge_lapsed(
+        env: Env,
+        caller: Address,
+        subscribers: Vec<Address>,
+    ) -> Result<u32, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        caller.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        let keeper: Option<Address> = env.storage().instance().get(&DataKey::PurgeKeeper);
+        if caller != stored_admin && Some(caller) != keeper {
+            return Err(PulsarError::Unauthorized);
+        }
+        if subscribers.len() > MAX_PAGE_SIZE {
+            return Err(PulsarError::LimitExceeded);
+        }
+
+        let now = env.ledger().timestamp();
+        let retention = Self::get_retention_window(env.clone());
+        let mut purged = 0u32;
+        for subscriber in subscribers.iter() {
+            let sub = match load_subscription(&env, &subscriber) {
+                Some(sub) if sub.expires_at.saturating_add(retention) < now => sub,
+                _ => continue,
+            };
+            pulsar_common_admin::publish_event(
+                &env,
+                symbol_short!("sub"),
+                symbol_short!("archived"),
+                sub,
+            );
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Subscription(subscriber.clone()));
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Reminded(subscriber));
+            purged += 1;
+        }
+        Ok(purged)
+    }
+
+    pub fn get_retention_window(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::RetentionWindow)
+            .unwrap_or(DEFAULT_RETENTION_WINDOW)
     }
 
     // ----------------------------------------------------------
@@ -469,31 +855,31 @@ impl SubscriptionManagerContract {
     // ----------------------------------------------------------
 
     /// Increment campaign usage counter for the subscriber.
-    /// Panics if no subscription exists.
-    pub fn record_campaign_used(env: Env, subscriber: Address) {
+    /// Errors with `NotFound` if no subscription exists.
+    pub fn record_campaign_used(env: Env, subscriber: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        let mut sub = load_subscription(&env, &subscriber)
-            .unwrap_or_else(|| panic!("subscription not found"));
+        let mut sub = load_subscription(&env, &subscriber).ok_or(PulsarError::NotFound)?;
 
         sub.campaigns_used += 1;
         save_subscription(&env, &sub);
+        Ok(())
     }
 
     /// Increment impression usage counter for the subscriber.
-    /// Panics if no subscription exists.
-    pub fn record_impression(env: Env, subscriber: Address, count: u64) {
+    /// Errors with `NotFound` if no subscription exists.
+    pub fn record_impression(env: Env, subscriber: Address, count: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        let mut sub = load_subscription(&env, &subscriber)
-            .unwrap_or_else(|| panic!("subscription not found"));
+        let mut sub = load_subscription(&env, &subscriber).ok_or(PulsarError::NotFound)?;
 
         sub.impressions_used += count;
         save_subscription(&env, &sub);
+        Ok(())
     }
 
     // ----------------------------------------------------------
@@ -523,24 +909,62 @@ impl SubscriptionManagerContract {
         env.storage().persistent().get(&DataKey::Plan(tier))
     }
 
+    pub fn get_invoice(env: Env, invoice_id: u64) -> Option<Invoice> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Invoice(invoice_id))
+    }
+
+    pub fn get_invoice_count(env: Env, subscriber: Address) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::SubscriberInvoiceCount(subscriber))
+            .unwrap_or(0)
+    }
+
+    /// Invoices issued to `subscriber`, oldest first, paged by index position.
+    pub fn list_invoices(env: Env, subscriber: Address, start: u32, limit: u32) -> Vec<Invoice> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SubscriberInvoiceCount(subscriber.clone()))
+            .unwrap_or(0);
+        let end = count.min(start.saturating_add(limit.min(MAX_PAGE_SIZE)));
+
+        let mut result = Vec::new(&env);
+        for i in start..end {
+            if let Some(invoice_id) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, u64>(&DataKey::SubscriberInvoice(subscriber.clone(), i))
+            {
+                if let Some(invoice) = env
+                    .storage()
+                    .persistent()
+                    .get::<DataKey, Invoice>(&DataKey::Invoice(invoice_id))
+                {
+                    result.push_back(invoice);
+                }
+            }
+        }
+        result
+    }
+
     // ----------------------------------------------------------
     // Internal
     // ----------------------------------------------------------
 
     fn _init_plans(env: &Env) {
-        // (tier, name, monthly_stroops, annual_stroops, max_campaigns,
-        //  max_impressions/month, max_publishers, analytics, api_access)
-        let plans: [(
-            SubscriptionTier,
-            &str,
-            i128,
-            i128,
-            u32,
-            u64,
-            u32,
-            bool,
-            bool,
-        ); 4] = [
+        let plans: [PlanRow; 4] = [
             (
                 SubscriptionTier::Starter,
                 "Starter",
@@ -620,18 +1044,126 @@ impl SubscriptionManagerContract {
         }
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
+
+    fn _renew(
+        env: &Env,
+        mut existing: Subscription,
+        is_annual: bool,
+        auto_renew: bool,
+        relayed: bool,
+    ) -> Result<(), PulsarError> {
+        let now = env.ledger().timestamp();
+
+        let plan = load_plan(env, existing.tier.clone())?;
+        let amount = plan_price(&plan, is_annual);
+        let period = plan_period_secs(is_annual);
+
+        if relayed {
+            charge_allowance(env, &existing.subscriber, amount);
+        } else {
+            charge(env, &existing.subscriber, amount);
+        }
+
+        // Stack on top of remaining time; never go backwards.
+        let base = existing.expires_at.max(now);
+        existing.expires_at = base + period;
+        existing.is_annual = is_annual;
+        existing.auto_renew = auto_renew;
+        existing.amount_paid = amount;
+
+        save_subscription(env, &existing);
+        issue_invoice(
+            env,
+            &existing,
+            InvoiceKind::Renewal,
+            base,
+            amount,
+            0,
+            relayed,
+        );
+
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("sub"),
+            symbol_short!("renew"),
+            (existing.subscriber, amount),
         );
+        Ok(())
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    fn _subscribe(
+        env: &Env,
+        subscriber: Address,
+        tier: SubscriptionTier,
+        is_annual: bool,
+        auto_renew: bool,
+        relayed: bool,
+    ) -> Result<(), PulsarError> {
+        let now = env.ledger().timestamp();
+
+        // Guard: block re-subscription while active.
+        if let Some(existing) = load_subscription(env, &subscriber) {
+            if existing.expires_at > now {
+                return Err(PulsarError::AlreadyExists);
+            }
+        }
+
+        let plan = load_plan(env, tier.clone())?;
+        let amount = plan_price(&plan, is_annual);
+        let period = plan_period_secs(is_annual);
+
+        if relayed {
+            charge_allowance(env, &subscriber, amount);
+        } else {
+            charge(env, &subscriber, amount);
+        }
+
+        let sub = Subscription {
+            subscriber: subscriber.clone(),
+            tier,
+            is_annual,
+            amount_paid: amount,
+            started_at: now,
+            expires_at: now + period,
+            auto_renew,
+            campaigns_used: 0,
+            impressions_used: 0,
+        };
+        save_subscription(env, &sub);
+        issue_invoice(env, &sub, InvoiceKind::New, now, amount, 0, relayed);
+
+        pulsar_common_admin::publish_event(
+            env,
+            symbol_short!("sub"),
+            symbol_short!("new"),
+            (subscriber, amount),
+        );
+        Ok(())
     }
 }
 
@@ -1,9 +1,65 @@
 #![cfg(test)]
 use super::*;
+use ed25519_dalek::{Signer, SigningKey};
 use soroban_sdk::{
-    testutils::Address as _, testutils::Ledger as _, token::StellarAssetClient, Address, Env,
+    testutils::Address as _, testutils::Ledger as _, token::StellarAssetClient, Address, BytesN,
+    Env, Symbol,
 };
 
+mod token_mock {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    // The test SAC cannot pay G-addresses without a trustline, so relayed
+    // calls acting for a signing key's account settle in this token instead.
+    #[contract]
+    pub struct MockToken;
+
+    #[contractimpl]
+    impl MockToken {
+        pub fn mint(env: Env, to: Address, amount: i128) {
+            let balance = Self::balance(env.clone(), to.clone());
+            env.storage().persistent().set(&to, &(balance + amount));
+        }
+
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().persistent().get(&id).unwrap_or(0)
+        }
+
+        pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+            from.require_auth();
+            Self::_move(&env, &from, &to, amount);
+        }
+
+        pub fn approve(env: Env, from: Address, spender: Address, amount: i128, _expiration: u32) {
+            from.require_auth();
+            env.storage().persistent().set(&(from, spender), &amount);
+        }
+
+        pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+            spender.require_auth();
+            let key = (from.clone(), spender);
+            let allowance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            if allowance < amount {
+                panic!("insufficient allowance");
+            }
+            env.storage().persistent().set(&key, &(allowance - amount));
+            Self::_move(&env, &from, &to, amount);
+        }
+
+        fn _move(env: &Env, from: &Address, to: &Address, amount: i128) {
+            let from_balance = Self::balance(env.clone(), from.clone());
+            if from_balance < amount {
+                panic!("insufficient balance");
+            }
+            env.storage()
+                .persistent()
+                .set(from, &(from_balance - amount));
+            let to_balance = Self::balance(env.clone(), to.clone());
+            env.storage().persistent().set(to, &(to_balance + amount));
+        }
+    }
+}
+
 // ============================================================
 // Test Helpers
 // ============================================================
@@ -35,12 +91,38 @@ fn setup(
     let token_admin = Address::generate(env);
     let token = deploy_token(env, &token_admin);
     let treasury = Address::generate(env);
-    let id = env.register_contract(None, SubscriptionManagerContract);
+    let id = env.register(SubscriptionManagerContract, ());
     let c = SubscriptionManagerContractClient::new(env, &id);
     c.initialize(&admin, &token, &treasury);
     (c, admin, token_admin, token, treasury)
 }
 
+/// Sign a relayed call the way a wallet would
+fn sign_meta<P: soroban_sdk::xdr::ToXdr>(
+    env: &Env,
+    contract: &Address,
+    key: &SigningKey,
+    action: &str,
+    payload: P,
+    nonce: u64,
+) -> MetaAuth {
+    let expiry = env.ledger().timestamp() + 300;
+    let digest = env.as_contract(contract, || {
+        pulsar_common_meta::message(env, &Symbol::new(env, action), payload, nonce, expiry)
+    });
+    MetaAuth {
+        signer: BytesN::from_array(env, &key.verifying_key().to_bytes()),
+        nonce,
+        expiry,
+        signature: BytesN::from_array(env, &key.sign(&digest.to_array()).to_bytes()),
+    }
+}
+
+fn account_of(env: &Env, key: &SigningKey) -> Address {
+    let strkey = stellar_strkey::ed25519::PublicKey(key.verifying_key().to_bytes()).to_string();
+    Address::from_str(env, &strkey)
+}
+
 /// Mint enough tokens for the subscriber to cover a Business annual plan (worst case).
 fn fund_subscriber(env: &Env, token: &Address, subscriber: &Address) {
     // Enterprise annual: 19_990_000_000 stroops — mint 2× for safety
@@ -72,12 +154,14 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, admin, _, token, _) = setup(&env);
-    c.initialize(&admin, &token, &Address::generate(&env));
+    assert_eq!(
+        c.try_initialize(&admin, &token, &Address::generate(&env)),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 // ============================================================
@@ -102,6 +186,61 @@ fn test_subscribe_new_subscriber() {
     assert_eq!(sub.amount_paid, STARTER_MONTHLY);
 }
 
+/// Relayed subscription that pays from the subscriber's allowance
+fn setup_relayed<'a>(
+    env: &'a Env,
+    key: &SigningKey,
+) -> (
+    SubscriptionManagerContractClient<'a>,
+    token_mock::MockTokenClient<'a>,
+    Address,
+    Address,
+) {
+    let token_id = env.register(token_mock::MockToken, ());
+    let token = token_mock::MockTokenClient::new(env, &token_id);
+    let treasury = Address::generate(env);
+    let id = env.register(SubscriptionManagerContract, ());
+    let c = SubscriptionManagerContractClient::new(env, &id);
+    c.initialize(&Address::generate(env), &token_id, &treasury);
+
+    let subscriber = account_of(env, key);
+    token.mint(&subscriber, &STARTER_MONTHLY);
+    (c, token, subscriber, treasury)
+}
+
+#[test]
+fn test_subscribe_signed_pays_from_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let key = SigningKey::from_bytes(&[9u8; 32]);
+    let (c, token, subscriber, treasury) = setup_relayed(&env, &key);
+    token.approve(&subscriber, &c.address, &STARTER_MONTHLY, &1_000u32);
+
+    let payload = (SubscriptionTier::Starter, false, true);
+    let auth = sign_meta(&env, &c.address, &key, "subscribe", payload, 0);
+    c.set_meta_enabled(&auth.signer, &true);
+    c.subscribe_signed(&SubscriptionTier::Starter, &false, &true, &auth);
+
+    let sub = c.get_subscription(&subscriber).unwrap();
+    assert!(sub.tier == SubscriptionTier::Starter);
+    assert_eq!(token.balance(&treasury), STARTER_MONTHLY);
+    assert_eq!(token.balance(&subscriber), 0);
+}
+
+#[test]
+#[should_panic(expected = "insufficient allowance")]
+fn test_subscribe_signed_without_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let key = SigningKey::from_bytes(&[9u8; 32]);
+    let (c, _, _, _) = setup_relayed(&env, &key);
+
+    let payload = (SubscriptionTier::Starter, false, true);
+    let auth = sign_meta(&env, &c.address, &key, "subscribe", payload, 0);
+    c.set_meta_enabled(&auth.signer, &true);
+    c.subscribe_signed(&SubscriptionTier::Starter, &false, &true, &auth);
+}
+
 #[test]
 fn test_subscribe_charges_full_price() {
     let env = Env::default();
@@ -135,7 +274,6 @@ fn test_subscribe_annual_charges_annual_price() {
 }
 
 #[test]
-#[should_panic(expected = "already active")]
 fn test_subscribe_panics_if_active_subscription_exists() {
     let env = Env::default();
     env.mock_all_auths();
@@ -145,7 +283,10 @@ fn test_subscribe_panics_if_active_subscription_exists() {
 
     c.subscribe(&subscriber, &SubscriptionTier::Starter, &false, &true);
     // Second call must panic
-    c.subscribe(&subscriber, &SubscriptionTier::Starter, &false, &true);
+    assert_eq!(
+        c.try_subscribe(&subscriber, &SubscriptionTier::Starter, &false, &true),
+        Err(Ok(PulsarError::AlreadyExists))
+    );
 }
 
 #[test]
@@ -288,21 +429,20 @@ fn test_upgrade_stores_full_price_for_future_proration() {
 // ============================================================
 
 #[test]
-#[should_panic(expected = "no active subscription")]
 fn test_change_tier_panics_with_no_subscription() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, ..) = setup(&env);
-    c.change_tier(
+    let result = c.try_change_tier(
         &Address::generate(&env),
         &SubscriptionTier::Business,
         &false,
         &true,
     );
+    assert_eq!(result, Err(Ok(PulsarError::NotFound)));
 }
 
 #[test]
-#[should_panic(expected = "downgrade not allowed")]
 fn test_change_tier_blocks_downgrade() {
     let env = Env::default();
     env.mock_all_auths();
@@ -311,11 +451,13 @@ fn test_change_tier_blocks_downgrade() {
     fund_subscriber(&env, &token, &subscriber);
 
     c.subscribe(&subscriber, &SubscriptionTier::Business, &false, &true);
-    c.change_tier(&subscriber, &SubscriptionTier::Growth, &false, &true);
+    assert_eq!(
+        c.try_change_tier(&subscriber, &SubscriptionTier::Growth, &false, &true),
+        Err(Ok(PulsarError::InvalidInput))
+    );
 }
 
 #[test]
-#[should_panic(expected = "same tier")]
 fn test_change_tier_blocks_same_tier() {
     let env = Env::default();
     env.mock_all_auths();
@@ -324,7 +466,10 @@ fn test_change_tier_blocks_same_tier() {
     fund_subscriber(&env, &token, &subscriber);
 
     c.subscribe(&subscriber, &SubscriptionTier::Growth, &false, &true);
-    c.change_tier(&subscriber, &SubscriptionTier::Growth, &false, &true);
+    assert_eq!(
+        c.try_change_tier(&subscriber, &SubscriptionTier::Growth, &false, &true),
+        Err(Ok(PulsarError::InvalidInput))
+    );
 }
 
 // ============================================================
@@ -420,6 +565,42 @@ fn test_renew_panics_with_no_subscription() {
     c.renew(&Address::generate(&env), &false, &true);
 }
 
+#[test]
+fn test_renew_from_allowance_needs_no_subscriber_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token, treasury) = setup(&env);
+    let subscriber = Address::generate(&env);
+    mint(&env, &token, &subscriber, STARTER_MONTHLY * 2);
+    c.subscribe(&subscriber, &SubscriptionTier::Starter, &false, &true);
+    let first_expiry = c.get_subscription(&subscriber).unwrap().expires_at;
+    token::Client::new(&env, &token).approve(&subscriber, &c.address, &STARTER_MONTHLY, &1_000u32);
+
+    // No auths at all from here on
+    env.set_auths(&[]);
+    c.renew_from_allowance(&subscriber, &false);
+
+    let sub = c.get_subscription(&subscriber).unwrap();
+    assert_eq!(sub.expires_at, first_expiry + MONTHLY_SECS);
+    assert!(sub.auto_renew);
+    assert_eq!(balance(&env, &token, &treasury), STARTER_MONTHLY * 2);
+    assert!(c.get_invoice(&2).unwrap().relayed);
+}
+
+#[test]
+fn test_renew_from_allowance_requires_auto_renew() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token, _) = setup(&env);
+    let subscriber = Address::generate(&env);
+    mint(&env, &token, &subscriber, STARTER_MONTHLY * 2);
+    c.subscribe(&subscriber, &SubscriptionTier::Starter, &false, &false);
+    token::Client::new(&env, &token).approve(&subscriber, &c.address, &STARTER_MONTHLY, &1_000u32);
+
+    let result = c.try_renew_from_allowance(&subscriber, &false);
+    assert_eq!(result, Err(Ok(PulsarError::InvalidState)));
+}
+
 // ============================================================
 // cancel()
 // ============================================================
@@ -513,3 +694,180 @@ fn test_is_active_returns_false_after_expiry() {
         .set_timestamp(env.ledger().timestamp() + MONTHLY_SECS + 1);
     assert!(!c.is_active(&subscriber));
 }
+
+// ============================================================
+// Invoices
+// ============================================================
+
+#[test]
+fn test_every_charge_issues_an_invoice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token, treasury) = setup(&env);
+    let subscriber = Address::generate(&env);
+    fund_subscriber(&env, &token, &subscriber);
+
+    c.subscribe(&subscriber, &SubscriptionTier::Starter, &false, &true);
+    let first_expiry = c.get_subscription(&subscriber).unwrap().expires_at;
+    c.renew(&subscriber, &false, &true);
+    c.change_tier(&subscriber, &SubscriptionTier::Growth, &false, &true);
+
+    let invoices = c.list_invoices(&subscriber, &0, &10);
+    assert_eq!(invoices.len(), 3);
+    assert_eq!(c.get_invoice_count(&subscriber), 3);
+
+    let new = invoices.get(0).unwrap();
+    assert_eq!(new.kind, InvoiceKind::New);
+    assert_eq!(new.amount, STARTER_MONTHLY);
+    assert_eq!(new.period_end, first_expiry);
+    assert_eq!(new.token, token);
+    assert_eq!(new.treasury, treasury);
+
+    // Renewal stacks on top of the first period
+    let renewal = invoices.get(1).unwrap();
+    assert_eq!(renewal.kind, InvoiceKind::Renewal);
+    assert_eq!(renewal.period_start, first_expiry);
+    assert_eq!(renewal.period_end, first_expiry + MONTHLY_SECS);
+
+    let upgrade = invoices.get(2).unwrap();
+    assert_eq!(upgrade.kind, InvoiceKind::Upgrade);
+    assert!(matches!(upgrade.tier, SubscriptionTier::Growth));
+    assert_eq!(upgrade.amount + upgrade.credit, GROWTH_MONTHLY);
+
+    // Invoices add up to what the treasury received
+    let billed: i128 = invoices.iter().map(|i| i.amount).sum();
+    assert_eq!(balance(&env, &token, &treasury), billed);
+    assert_eq!(
+        c.get_invoice(&upgrade.invoice_id).unwrap().amount,
+        upgrade.amount
+    );
+}
+
+#[test]
+fn test_list_invoices_pages_per_subscriber() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, _, token, _) = setup(&env);
+    let subscriber = Address::generate(&env);
+    let other = Address::generate(&env);
+    fund_subscriber(&env, &token, &subscriber);
+    fund_subscriber(&env, &token, &other);
+
+    c.subscribe(&subscriber, &SubscriptionTier::Starter, &false, &true);
+    c.subscribe(&other, &SubscriptionTier::Starter, &false, &true);
+    c.renew(&subscriber, &false, &true);
+    c.renew(&subscriber, &false, &true);
+
+    let page = c.list_invoices(&subscriber, &1, &5);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().kind, InvoiceKind::Renewal);
+    assert_eq!(c.list_invoices(&subscriber, &3, &5).len(), 0);
+    assert_eq!(c.get_invoice_count(&other), 1);
+    assert_eq!(
+        c.list_invoices(&other, &0, &5).get(0).unwrap().invoice_id,
+        2
+    );
+}
+
+// ============================================================
+// Renewal Reminders
+// ============================================================
+
+#[test]
+fn test_renewal_reminders_only_inside_window_and_once_per_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token, _) = setup(&env);
+    c.set_reminder_window(&admin, &(3 * 24 * 3600));
+    let expiring = Address::generate(&env);
+    let annual = Address::generate(&env);
+    let unknown = Address::generate(&env);
+    fund_subscriber(&env, &token, &expiring);
+    fund_subscriber(&env, &token, &annual);
+
+    c.subscribe(&expiring, &SubscriptionTier::Starter, &false, &true);
+    c.subscribe(&annual, &SubscriptionTier::Starter, &true, &true);
+    let batch = soroban_sdk::vec![&env, expiring.clone(), annual.clone(), unknown];
+
+    // Outside the window nothing is reported
+    assert_eq!(c.emit_renewal_reminders(&batch), 0);
+
+    let start = env.ledger().timestamp();
+    env.ledger()
+        .set_timestamp(start + MONTHLY_SECS - 2 * 24 * 3600);
+    assert_eq!(c.emit_renewal_reminders(&batch), 1);
+    assert_eq!(c.emit_renewal_reminders(&batch), 0);
+
+    // A renewal opens a new period that is reminded about again
+    c.renew(&expiring, &false, &true);
+    env.ledger()
+        .set_timestamp(start + 2 * MONTHLY_SECS - 24 * 3600);
+    assert_eq!(c.emit_renewal_reminders(&batch), 1);
+
+    // Lapsed subscriptions are skipped
+    env.ledger().set_timestamp(start + 2 * MONTHLY_SECS + 1);
+    assert_eq!(c.emit_renewal_reminders(&batch), 0);
+}
+
+#[test]
+fn test_set_reminder_window_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, ..) = setup(&env);
+    assert_eq!(
+        c.try_set_reminder_window(&Address::generate(&env), &3600),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+// ============================================================
+// Storage Reclamation
+// ============================================================
+
+#[test]
+fn test_purge_lapsed_after_retention_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _, token, _) = setup(&env);
+    c.set_retention_window(&admin, &(10 * 24 * 3600));
+    let keeper = Address::generate(&env);
+    c.set_purge_keeper(&admin, &keeper);
+    let churned = Address::generate(&env);
+    let annual = Address::generate(&env);
+    fund_subscriber(&env, &token, &churned);
+    fund_subscriber(&env, &token, &annual);
+
+    c.subscribe(&churned, &SubscriptionTier::Starter, &false, &false);
+    c.subscribe(&annual, &SubscriptionTier::Starter, &true, &false);
+    let batch = soroban_sdk::vec![&env, churned.clone(), annual.clone()];
+
+    // Lapsed, but still inside the retention window
+    let start = env.ledger().timestamp();
+    env.ledger().set_timestamp(start + MONTHLY_SECS + 24 * 3600);
+    assert_eq!(c.purge_lapsed(&keeper, &batch), 0);
+    assert!(c.get_subscription(&churned).is_some());
+
+    env.ledger()
+        .set_timestamp(start + MONTHLY_SECS + 11 * 24 * 3600);
+    assert_eq!(c.purge_lapsed(&keeper, &batch), 1);
+    assert!(c.get_subscription(&churned).is_none());
+    assert!(c.get_subscription(&annual).is_some());
+    // Billing history survives the purge
+    assert_eq!(c.get_invoice_count(&churned), 1);
+
+    // A purged subscriber can start over
+    c.subscribe(&churned, &SubscriptionTier::Growth, &false, &false);
+    assert!(c.is_active(&churned));
+}
+
+#[test]
+fn test_purge_lapsed_rejects_non_keeper() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, ..) = setup(&env);
+    let result = c.try_purge_lapsed(
+        &Address::generate(&env),
+        &soroban_sdk::vec![&env, Address::generate(&env)],
+    );
+    assert_eq!(result, Err(Ok(PulsarError::Unauthorized)));
+}
@@ -0,0 +1,353 @@
+//! PulsarTrack - Test Utilities
+//! Deterministic fixtures for integration tests that exercise the
+//! cross-contract paths. `SuiteBuilder` deploys the core ad graph into one
+//! `Env`, registers every cross-contract address, and funds and verifies
+//! actors on request; `ledger` moves time and sequence forward.
+//!
+//! Contract unit tests keep their own mocks; this crate is for tests that
+//! need the real contracts talking to each other.
+
+#![cfg(not(target_family = "wasm"))]
+
+use core::cell::Cell;
+use pulsar_budget_optimizer::{
+    BudgetOptimizerContract, BudgetOptimizerContractClient, OptimizationMode,
+};
+use pulsar_campaign_lifecycle::{CampaignLifecycleContract, CampaignLifecycleContractClient};
+use pulsar_campaign_orchestrator::{
+    CampaignOrchestratorContract, CampaignOrchestratorContractClient,
+};
+use pulsar_escrow_vault::{EscrowVaultContract, EscrowVaultContractClient};
+use pulsar_fee_router::{FeeRouterContract, FeeRouterContractClient, FeeRule};
+use pulsar_fraud_prevention::{FraudPreventionContract, FraudPreventionContractClient};
+use pulsar_identity_registry::{
+    IdentityRegistryContract, IdentityRegistryContractClient, IdentityType,
+};
+use pulsar_payment_processor::{PaymentProcessorContract, PaymentProcessorContractClient};
+use pulsar_publisher_network::{
+    NodeType, PublisherNetworkContract, PublisherNetworkContractClient,
+};
+use pulsar_publisher_verification::{
+    PublisherTier, PublisherVerificationContract, PublisherVerificationContractClient,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger, LedgerInfo},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, String, Vec,
+};
+
+/// Ledger state every suite starts from
+pub const GENESIS_TIMESTAMP: u64 = 1_700_000_000;
+pub const GENESIS_SEQUENCE: u32 = 1_000;
+
+/// Tokens minted to each advertiser from `Suite::advertiser`
+pub const ADVERTISER_FUNDS: i128 = 1_000_000_000;
+
+/// Campaign created by `Suite::campaign`: fits the orchestrator's default
+/// campaign type (min budget 1_000_000, duration 100..=10_000 ledgers).
+pub const CAMPAIGN_BUDGET: i128 = 10_000_000;
+pub const CAMPAIGN_COST_PER_VIEW: i128 = 10_000;
+pub const CAMPAIGN_DURATION: u32 = 1_000;
+pub const CAMPAIGN_TARGET_VIEWS: u64 = 1_000;
+pub const CAMPAIGN_DAILY_VIEWS: u64 = 500;
+
+/// Fresh `Env` with mocked auths and a fixed ledger start, so addresses,
+/// timestamps and ids are identical on every run.
+pub fn env() -> Env {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set(LedgerInfo {
+        timestamp: GENESIS_TIMESTAMP,
+        protocol_version: env.ledger().protocol_version(),
+        sequence_number: GENESIS_SEQUENCE,
+        network_id: [0u8; 32],
+        base_reserve: 10,
+        min_temp_entry_ttl: 16,
+        min_persistent_entry_ttl: 4_096,
+        max_entry_ttl: 6_312_000,
+    });
+    env
+}
+
+pub mod ledger {
+    use soroban_sdk::{testutils::Ledger, Env};
+
+    /// Average ledger close time used when moving both clocks together
+    pub const SECS_PER_LEDGER: u64 = 5;
+
+    pub fn advance_time(env: &Env, secs: u64) {
+        env.ledger().with_mut(|li| li.timestamp += secs);
+    }
+
+    pub fn advance_ledgers(env: &Env, ledgers: u32) {
+        env.ledger().with_mut(|li| {
+            li.sequence_number += ledgers;
+            li.timestamp += ledgers as u64 * SECS_PER_LEDGER;
+        });
+    }
+
+    /// Jump to the start of the next UTC day, for daily limits and rollups
+    pub fn next_day(env: &Env) {
+        env.ledger()
+            .with_mut(|li| li.timestamp = (li.timestamp / 86_400 + 1) * 86_400);
+    }
+}
+
+pub struct SuiteBuilder<'a> {
+    env: &'a Env,
+    platform_fee_pct: Option<u32>,
+    fee_rule: Option<FeeRule>,
+    liveness_window: Option<u64>,
+    verification_period: Option<u64>,
+}
+
+impl<'a> SuiteBuilder<'a> {
+    pub fn new(env: &'a Env) -> Self {
+        SuiteBuilder {
+            env,
+            platform_fee_pct: None,
+            fee_rule: None,
+            liveness_window: None,
+            verification_period: None,
+        }
+    }
+
+    /// Orchestrator fee on campaign budgets (default 2%)
+    pub fn platform_fee_pct(mut self, pct: u32) -> Self {
+        self.platform_fee_pct = Some(pct);
+        self
+    }
+
+    /// Fee-router split (default: everything to the treasury)
+    pub fn fee_rule(mut self, rule: FeeRule) -> Self {
+        self.fee_rule = Some(rule);
+        self
+    }
+
+    /// Publisher heartbeat window (default: unset, nodes stay live)
+    pub fn liveness_window(mut self, secs: u64) -> Self {
+        self.liveness_window = Some(secs);
+        self
+    }
+
+    /// Publisher verification lifetime (default: unset, never expires)
+    pub fn verification_period(mut self, secs: u64) -> Self {
+        self.verification_period = Some(secs);
+        self
+    }
+
+    pub fn build(self) -> Suite<'a> {
+        let env = self.env;
+        let admin = Address::generate(env);
+        let treasury = Address::generate(env);
+        let rewards_pool = Address::generate(env);
+        let oracle = Address::generate(env);
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+
+        let orchestrator = CampaignOrchestratorContractClient::new(
+            env,
+            &env.register(CampaignOrchestratorContract, ()),
+        );
+        let lifecycle =
+            CampaignLifecycleContractClient::new(env, &env.register(CampaignLifecycleContract, ()));
+        let escrow = EscrowVaultContractClient::new(env, &env.register(EscrowVaultContract, ()));
+        let fraud =
+            FraudPreventionContractClient::new(env, &env.register(FraudPreventionContract, ()));
+        let identity =
+            IdentityRegistryContractClient::new(env, &env.register(IdentityRegistryContract, ()));
+        let network =
+            PublisherNetworkContractClient::new(env, &env.register(PublisherNetworkContract, ()));
+        let optimizer =
+            BudgetOptimizerContractClient::new(env, &env.register(BudgetOptimizerContract, ()));
+        let fee_router = FeeRouterContractClient::new(env, &env.register(FeeRouterContract, ()));
+        let verification = PublisherVerificationContractClient::new(
+            env,
+            &env.register(PublisherVerificationContract, ()),
+        );
+        let payments =
+            PaymentProcessorContractClient::new(env, &env.register(PaymentProcessorContract, ()));
+
+        orchestrator.initialize(&admin, &token);
+        lifecycle.initialize(&admin);
+        escrow.initialize(&admin, &token, &oracle);
+        fraud.initialize(&admin);
+        identity.initialize(&admin);
+        network.initialize(&admin);
+        optimizer.initialize(&admin, &oracle);
+        fee_router.initialize(&admin, &treasury, &rewards_pool);
+        payments.initialize(&admin, &treasury);
+        verification.initialize(&admin);
+
+        orchestrator.set_lifecycle_contract(&admin, &lifecycle.address);
+        orchestrator.set_escrow_contract(&admin, &escrow.address);
+        orchestrator.set_network_contract(&admin, &network.address);
+        orchestrator.set_budget_optimizer(&admin, &optimizer.address);
+        orchestrator.set_fee_router(&admin, &fee_router.address);
+        orchestrator.set_identity_registry(&admin, &identity.address);
+        orchestrator.set_publisher_verification(&admin, &verification.address);
+        lifecycle.set_fraud_contract(&admin, &fraud.address);
+        escrow.set_fraud_contract(&admin, &fraud.address);
+        fraud.set_dependent_contracts(
+            &admin,
+            &lifecycle.address,
+            &network.address,
+            &escrow.address,
+        );
+        network.set_orchestrator(&admin, &orchestrator.address);
+        network.set_identity_registry(&admin, &identity.address);
+        network.set_fraud_contract(&admin, &fraud.address);
+        optimizer.set_authorized_spender(&admin, &orchestrator.address);
+        payments.add_token(&admin, &token, &1i128, &i128::MAX);
+        payments.set_fee_router(&admin, &fee_router.address);
+
+        if let Some(pct) = self.platform_fee_pct {
+            orchestrator.set_platform_fee(&admin, &pct);
+        }
+        if let Some(rule) = self.fee_rule {
+            fee_router.set_rule(&admin, &rule);
+        }
+        if let Some(window) = self.liveness_window {
+            network.set_liveness_window(&admin, &window);
+        }
+        if let Some(period) = self.verification_period {
+            verification.set_verification_period(&admin, &period);
+        }
+
+        Suite {
+            env,
+            admin,
+            treasury,
+            rewards_pool,
+            oracle,
+            token: TokenClient::new(env, &token),
+            orchestrator,
+            lifecycle,
+            escrow,
+            fraud,
+            identity,
+            network,
+            optimizer,
+            fee_router,
+            payments,
+            verification,
+            actors: Cell::new(0),
+        }
+    }
+}
+
+/// The deployed and wired contract graph
+pub struct Suite<'a> {
+    pub env: &'a Env,
+    pub admin: Address,
+    pub treasury: Address,
+    pub rewards_pool: Address,
+    pub oracle: Address,
+    pub token: TokenClient<'a>,
+    pub orchestrator: CampaignOrchestratorContractClient<'a>,
+    pub lifecycle: CampaignLifecycleContractClient<'a>,
+    pub escrow: EscrowVaultContractClient<'a>,
+    pub fraud: FraudPreventionContractClient<'a>,
+    pub identity: IdentityRegistryContractClient<'a>,
+    pub network: PublisherNetworkContractClient<'a>,
+    pub optimizer: BudgetOptimizerContractClient<'a>,
+    pub fee_router: FeeRouterContractClient<'a>,
+    pub payments: PaymentProcessorContractClient<'a>,
+    pub verification: PublisherVerificationContractClient<'a>,
+    actors: Cell<u32>,
+}
+
+impl<'a> Suite<'a> {
+    /// Suite with default settings
+    pub fn new(env: &'a Env) -> Self {
+        SuiteBuilder::new(env).build()
+    }
+
+    pub fn mint(&self, to: &Address, amount: i128) {
+        StellarAssetClient::new(self.env, &self.token.address).mint(to, &amount);
+    }
+
+    /// Funded advertiser with a verified Advertiser identity
+    pub fn advertiser(&self) -> Address {
+        let advertiser = self.verified_identity(IdentityType::Advertiser);
+        self.mint(&advertiser, ADVERTISER_FUNDS);
+        advertiser
+    }
+
+    /// Publisher with an admin-attested domain, verified in
+    /// publisher-verification and the identity registry, with a live
+    /// Standard node in the publisher network
+    pub fn publisher(&self) -> Address {
+        let publisher = self.verified_identity(IdentityType::Publisher);
+        let domain = std::format!("publisher-{}.example", self.actors.get());
+        self.verification
+            .register_publisher(&publisher, &String::from_str(self.env, &domain));
+        self.verification.submit_kyc(
+            &publisher,
+            &String::from_str(self.env, "kyc-hash"),
+            &String::from_str(self.env, "kyc-provider"),
+        );
+        let challenge = self
+            .verification
+            .get_domain_challenge(&publisher, &String::from_str(self.env, &domain));
+        self.verification
+            .attest_domain(&self.admin, &publisher, &challenge);
+        self.verification
+            .verify_publisher(&self.admin, &publisher, &PublisherTier::Bronze);
+        self.network.join_network(
+            &publisher,
+            &NodeType::Standard,
+            &1_000_000u64,
+            &0i128,
+            &String::from_str(self.env, "global"),
+            &Vec::new(self.env),
+        );
+        publisher
+    }
+
+    /// Default campaign, registered with the lifecycle contract and given a
+    /// matching budget allocation in the optimizer
+    pub fn campaign(&self, advertiser: &Address) -> u64 {
+        let campaign_id = self.orchestrator.create_campaign(
+            advertiser,
+            &1u32,
+            &CAMPAIGN_BUDGET,
+            &CAMPAIGN_COST_PER_VIEW,
+            &CAMPAIGN_DURATION,
+            &CAMPAIGN_TARGET_VIEWS,
+            &CAMPAIGN_DAILY_VIEWS,
+            &true,
+        );
+        let end_ledger = self.env.ledger().sequence() + CAMPAIGN_DURATION;
+        self.lifecycle
+            .register_campaign(advertiser, &campaign_id, &end_ledger);
+        self.optimizer.set_budget_allocation(
+            advertiser,
+            &campaign_id,
+            &CAMPAIGN_BUDGET,
+            &CAMPAIGN_BUDGET,
+            &OptimizationMode::AutoCpm,
+            &0i128,
+            &0u32,
+        );
+        campaign_id
+    }
+
+    fn verified_identity(&self, identity_type: IdentityType) -> Address {
+        let n = self.actors.get() + 1;
+        self.actors.set(n);
+        let account = Address::generate(self.env);
+        let name = std::format!("actor-{}", n);
+        self.identity.register(
+            &account,
+            &identity_type,
+            &String::from_str(self.env, &name),
+            &String::from_str(self.env, "ipfs://metadata"),
+        );
+        self.identity.verify_identity(&self.admin, &account);
+        account
+    }
+}
+
+mod test;
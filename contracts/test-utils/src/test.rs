@@ -0,0 +1,331 @@
+#![cfg(test)]
+use super::*;
+use pulsar_campaign_orchestrator::PulsarError;
+use pulsar_governance_dao::{GovernanceDaoContract, GovernanceDaoContractClient, VoteChoice};
+use pulsar_publisher_reputation::{PublisherReputationContract, PublisherReputationContractClient};
+use pulsar_recurring_payment::{
+    KeeperFee, RecurringPaymentContract, RecurringPaymentContractClient, SpendingCaps,
+    SubscriptionRenewal,
+};
+use pulsar_subscription_manager::{
+    SubscriptionManagerContract, SubscriptionManagerContractClient, SubscriptionTier,
+};
+use pulsar_token_vesting::{TokenVestingContract, TokenVestingContractClient};
+use soroban_sdk::{
+    testutils::{MockAuth, MockAuthInvoke},
+    IntoVal,
+};
+
+#[test]
+fn test_suite_is_deterministic() {
+    let env_a = env();
+    let env_b = env();
+    let a = Suite::new(&env_a);
+    let b = Suite::new(&env_b);
+    assert_eq!(
+        a.orchestrator.address.to_string(),
+        b.orchestrator.address.to_string()
+    );
+    assert_eq!(a.advertiser().to_string(), b.advertiser().to_string());
+    assert_eq!(env_a.ledger().timestamp(), GENESIS_TIMESTAMP);
+}
+
+#[test]
+fn test_view_pays_publisher_and_reaches_every_contract() {
+    let env = env();
+    let suite = Suite::new(&env);
+    let advertiser = suite.advertiser();
+    let publisher = suite.publisher();
+    let campaign_id = suite.campaign(&advertiser);
+
+    // 2% platform fee went through the fee router to the treasury
+    let fee = CAMPAIGN_BUDGET * 2 / 100;
+    assert_eq!(suite.token.balance(&suite.treasury), fee);
+    assert_eq!(
+        suite
+            .fee_router
+            .get_source_total(&suite.orchestrator.address, &suite.token.address),
+        fee
+    );
+    assert_eq!(
+        suite.token.balance(&advertiser),
+        ADVERTISER_FUNDS - CAMPAIGN_BUDGET - fee
+    );
+
+    suite.orchestrator.record_view(&campaign_id, &publisher);
+
+    assert_eq!(suite.token.balance(&publisher), CAMPAIGN_COST_PER_VIEW);
+    let campaign = suite.orchestrator.get_campaign(&campaign_id).unwrap();
+    assert_eq!(campaign.current_views, 1);
+    let allocation = suite.optimizer.get_allocation(&campaign_id).unwrap();
+    assert_eq!(allocation.spent_total, CAMPAIGN_COST_PER_VIEW);
+    let record = suite.verification.get_publisher(&publisher).unwrap();
+    assert_eq!(record.total_earnings, CAMPAIGN_COST_PER_VIEW);
+}
+
+#[test]
+fn test_view_for_campaign_without_allocation() {
+    let env = env();
+    let suite = Suite::new(&env);
+    let advertiser = suite.advertiser();
+    let publisher = suite.publisher();
+    let campaign_id = suite.orchestrator.create_campaign(
+        &advertiser,
+        &1u32,
+        &CAMPAIGN_BUDGET,
+        &CAMPAIGN_COST_PER_VIEW,
+        &CAMPAIGN_DURATION,
+        &CAMPAIGN_TARGET_VIEWS,
+        &CAMPAIGN_DAILY_VIEWS,
+        &true,
+    );
+    let end_ledger = env.ledger().sequence() + CAMPAIGN_DURATION;
+    suite
+        .lifecycle
+        .register_campaign(&advertiser, &campaign_id, &end_ledger);
+    assert!(suite.optimizer.get_allocation(&campaign_id).is_none());
+
+    suite.orchestrator.record_view(&campaign_id, &publisher);
+    // Batches are submitted by the fraud contract
+    suite
+        .orchestrator
+        .set_fraud_contract(&suite.admin, &suite.fraud.address);
+    suite
+        .orchestrator
+        .record_views_batch(&campaign_id, &publisher, &2u64);
+
+    assert_eq!(
+        suite
+            .orchestrator
+            .get_campaign(&campaign_id)
+            .unwrap()
+            .current_views,
+        3
+    );
+    assert!(suite.optimizer.get_allocation(&campaign_id).is_none());
+}
+
+#[test]
+fn test_fee_rule_splits_campaign_fee() {
+    let env = env();
+    let suite = SuiteBuilder::new(&env)
+        .fee_rule(FeeRule {
+            treasury_bps: 5_000,
+            burn_bps: 0,
+            rewards_bps: 5_000,
+        })
+        .build();
+    let advertiser = suite.advertiser();
+    suite.campaign(&advertiser);
+
+    let fee = CAMPAIGN_BUDGET * 2 / 100;
+    assert_eq!(suite.token.balance(&suite.treasury), fee / 2);
+    assert_eq!(suite.token.balance(&suite.rewards_pool), fee / 2);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #13)")]
+fn test_unverified_advertiser_rejected() {
+    let env = env();
+    let suite = Suite::new(&env);
+    let advertiser = Address::generate(&env);
+    suite.mint(&advertiser, ADVERTISER_FUNDS);
+    suite.campaign(&advertiser);
+}
+
+#[test]
+fn test_stale_publisher_node_rejected() {
+    let env = env();
+    let suite = SuiteBuilder::new(&env).liveness_window(600).build();
+    let advertiser = suite.advertiser();
+    let publisher = suite.publisher();
+    let campaign_id = suite.campaign(&advertiser);
+
+    ledger::advance_time(&env, 601);
+    assert_eq!(
+        suite.orchestrator.try_record_view(&campaign_id, &publisher),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_ledger_helpers() {
+    let env = env();
+    ledger::advance_ledgers(&env, 10);
+    assert_eq!(env.ledger().sequence(), GENESIS_SEQUENCE + 10);
+    assert_eq!(
+        env.ledger().timestamp(),
+        GENESIS_TIMESTAMP + 10 * ledger::SECS_PER_LEDGER
+    );
+
+    ledger::next_day(&env);
+    assert_eq!(env.ledger().timestamp() % 86_400, 0);
+}
+
+#[test]
+fn test_expired_publisher_verification_rejected() {
+    let env = env();
+    let suite = SuiteBuilder::new(&env).verification_period(3_600).build();
+    let advertiser = suite.advertiser();
+    let publisher = suite.publisher();
+    let campaign_id = suite.campaign(&advertiser);
+
+    suite.orchestrator.record_view(&campaign_id, &publisher);
+    ledger::advance_time(&env, 3_601);
+    assert_eq!(
+        suite.orchestrator.try_record_view(&campaign_id, &publisher),
+        Err(Ok(PulsarError::NotVerified))
+    );
+}
+
+#[test]
+fn test_keeper_renews_subscription_without_subscriber_auth() {
+    let env = env();
+    let suite = Suite::new(&env);
+    let subscriptions = SubscriptionManagerContractClient::new(
+        &env,
+        &env.register(SubscriptionManagerContract, ()),
+    );
+    let recurring =
+        RecurringPaymentContractClient::new(&env, &env.register(RecurringPaymentContract, ()));
+    subscriptions.initialize(&suite.admin, &suite.token.address, &suite.treasury);
+    recurring.initialize(&suite.admin);
+
+    let subscriber = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let price = subscriptions
+        .get_plan(&SubscriptionTier::Starter)
+        .unwrap()
+        .price_monthly;
+    let fee = 1_000i128;
+    suite.mint(&subscriber, price * 2 + fee);
+    subscriptions.subscribe(&subscriber, &SubscriptionTier::Starter, &false, &true);
+    let first_expiry = subscriptions
+        .get_subscription(&subscriber)
+        .unwrap()
+        .expires_at;
+
+    let expiration = env.ledger().sequence() + 10_000;
+    suite
+        .token
+        .approve(&subscriber, &subscriptions.address, &price, &expiration);
+    suite
+        .token
+        .approve(&subscriber, &recurring.address, &fee, &expiration);
+    let caps = SpendingCaps {
+        max_per_charge: price + fee,
+        max_total: (price + fee) * 12,
+    };
+    let payment_id = recurring.create_recurring(
+        &subscriber,
+        &subscriptions.address,
+        &suite.token.address,
+        &price,
+        &86_400u64,
+        &None,
+        &caps,
+    );
+    recurring.set_subscription_renewal(&subscriber, &payment_id, &SubscriptionRenewal::Monthly);
+    recurring.set_keeper_fee(&subscriber, &payment_id, &KeeperFee::Flat(fee));
+
+    // Only the keeper signs the execution
+    ledger::advance_time(&env, 86_400);
+    env.mock_auths(&[MockAuth {
+        address: &keeper,
+        invoke: &MockAuthInvoke {
+            contract: &recurring.address,
+            fn_name: "execute_payment",
+            args: (keeper.clone(), payment_id).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(recurring.execute_payment(&keeper, &payment_id));
+
+    let sub = subscriptions.get_subscription(&subscriber).unwrap();
+    assert_eq!(sub.expires_at, first_expiry + 30 * 24 * 3600);
+    assert_eq!(suite.token.balance(&subscriber), 0);
+    assert_eq!(suite.token.balance(&keeper), fee);
+    assert_eq!(
+        recurring.get_payment(&payment_id).unwrap().total_charged,
+        price + fee
+    );
+}
+
+#[test]
+fn test_redelegated_vesting_power_votes_once() {
+    let env = env();
+    let suite = Suite::new(&env);
+    let dao = GovernanceDaoContractClient::new(&env, &env.register(GovernanceDaoContract, ()));
+    let vesting = TokenVestingContractClient::new(&env, &env.register(TokenVestingContract, ()));
+    dao.initialize(
+        &suite.admin,
+        &suite.token.address,
+        &100u32,
+        &0u32,
+        &51u32,
+        &0i128,
+    );
+    vesting.initialize(&suite.admin, &suite.token.address);
+    dao.set_vesting_contract(&suite.admin, &vesting.address);
+
+    let beneficiary = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    suite.mint(&suite.admin, 10_000);
+    let grant_id = vesting.create_grant(
+        &suite.admin,
+        &beneficiary,
+        &10_000i128,
+        &env.ledger().timestamp(),
+        &0u64,
+        &1_000_000u64,
+        &false,
+    );
+    vesting.delegate_votes(&beneficiary, &grant_id, &first);
+
+    ledger::advance_ledgers(&env, 1);
+    let proposal_id = dao.create_proposal(
+        &first,
+        &String::from_str(&env, "Fee change"),
+        &String::from_str(&env, "Lower the platform fee"),
+        &None,
+    );
+    dao.cast_vote(&first, &proposal_id, &VoteChoice::For, &10_000i128);
+
+    // The same locked tokens cannot vote again through a new delegate
+    vesting.delegate_votes(&beneficiary, &grant_id, &second);
+    assert_eq!(
+        dao.try_cast_vote(&second, &proposal_id, &VoteChoice::For, &10_000i128),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+    assert_eq!(dao.get_proposal(&proposal_id).unwrap().votes_for, 10_000);
+}
+
+#[test]
+fn test_only_campaign_advertiser_reviews_publisher() {
+    let env = env();
+    let suite = Suite::new(&env);
+    let reputation = PublisherReputationContractClient::new(
+        &env,
+        &env.register(PublisherReputationContract, ()),
+    );
+    reputation.initialize(&suite.admin, &suite.oracle);
+    reputation.set_campaign_orchestrator(&suite.admin, &suite.orchestrator.address);
+    let advertiser = suite.advertiser();
+    let publisher = suite.publisher();
+    let campaign_id = suite.campaign(&advertiser);
+    reputation.init_publisher(&publisher);
+
+    assert_eq!(
+        reputation.try_submit_review(
+            &Address::generate(&env),
+            &publisher,
+            &campaign_id,
+            &false,
+            &1u32
+        ),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+    reputation.submit_review(&advertiser, &publisher, &campaign_id, &true, &5u32);
+    assert_eq!(reputation.get_pending_reviews(&publisher).len(), 1);
+}
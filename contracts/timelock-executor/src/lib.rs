@@ -4,6 +4,8 @@
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String, Symbol, Vec, Val};
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone, PartialEq)]
 pub enum TimelockStatus {
@@ -58,12 +60,12 @@ impl TimelockExecutorContract {
         executor: Address,
         min_delay_secs: u64,
         max_delay_secs: u64,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -80,6 +82,7 @@ impl TimelockExecutorContract {
             .instance()
             .set(&DataKey::GracePeriod, &172_800u64); // 2 days
         env.storage().instance().set(&DataKey::EntryCounter, &0u64);
+        Ok(())
     }
 
     pub fn queue(
@@ -90,15 +93,19 @@ impl TimelockExecutorContract {
         args: Vec<Val>,
         description: String,
         delay_secs: u64,
-    ) -> u64 {
+    ) -> Result<u64, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         proposer.require_auth();
 
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if proposer != admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let min_delay: u64 = env
@@ -113,7 +120,7 @@ impl TimelockExecutorContract {
             .unwrap_or(2_592_000);
 
         if delay_secs < min_delay || delay_secs > max_delay {
-            panic!("invalid delay");
+            return Err(PulsarError::InvalidInput);
         }
 
         let counter: u64 = env
@@ -151,15 +158,17 @@ impl TimelockExecutorContract {
             .instance()
             .set(&DataKey::EntryCounter, &entry_id);
 
-        env.events().publish(
-            (symbol_short!("timelock"), symbol_short!("queued")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("timelock"),
+            symbol_short!("queued"),
             (entry_id, proposer),
         );
 
-        entry_id
+        Ok(entry_id)
     }
 
-    pub fn execute(env: Env, executor: Address, entry_id: u64) {
+    pub fn execute(env: Env, executor: Address, entry_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -171,23 +180,23 @@ impl TimelockExecutorContract {
             .get(&DataKey::ExecutorAddress)
             .unwrap();
         if executor != stored_executor {
-            panic!("unauthorized executor");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut entry: TimelockEntry = env
             .storage()
             .persistent()
             .get(&DataKey::Entry(entry_id))
-            .expect("entry not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if entry.status != TimelockStatus::Queued {
-            panic!("entry not queued");
+            return Err(PulsarError::InvalidState);
         }
 
         let now = env.ledger().timestamp();
 
         if now < entry.eta {
-            panic!("timelock not expired");
+            return Err(PulsarError::TooEarly);
         }
 
         if now > entry.eta + entry.grace_period {
@@ -199,7 +208,7 @@ impl TimelockExecutorContract {
                 PERSISTENT_LIFETIME_THRESHOLD,
                 PERSISTENT_BUMP_AMOUNT,
             );
-            panic!("grace period expired");
+            return Err(PulsarError::Expired);
         }
 
         // Perform the actual cross-contract invocation
@@ -215,30 +224,37 @@ impl TimelockExecutorContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("timelock"), symbol_short!("executed")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("timelock"),
+            symbol_short!("executed"),
             entry_id,
         );
+        Ok(())
     }
 
-    pub fn cancel(env: Env, admin: Address, entry_id: u64) {
+    pub fn cancel(env: Env, admin: Address, entry_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let mut entry: TimelockEntry = env
             .storage()
             .persistent()
             .get(&DataKey::Entry(entry_id))
-            .expect("entry not found");
+            .ok_or(PulsarError::NotFound)?;
 
         if entry.status != TimelockStatus::Queued {
-            panic!("entry not queued");
+            return Err(PulsarError::InvalidState);
         }
 
         entry.status = TimelockStatus::Cancelled;
@@ -249,6 +265,7 @@ impl TimelockExecutorContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     pub fn get_entry(env: Env, entry_id: u64) -> Option<TimelockEntry> {
@@ -276,18 +293,30 @@ impl TimelockExecutorContract {
         }
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
@@ -51,7 +51,6 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
@@ -61,7 +60,10 @@ fn test_initialize_twice() {
     let contract_id = env.register_contract(None, TimelockExecutorContract);
     let client = TimelockExecutorContractClient::new(&env, &contract_id);
     client.initialize(&admin, &executor, &3600u64, &86_400u64);
-    client.initialize(&admin, &executor, &3600u64, &86_400u64);
+    assert_eq!(
+        client.try_initialize(&admin, &executor, &3600u64, &86_400u64),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
@@ -95,7 +97,6 @@ fn test_queue_entry() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_queue_by_non_admin() {
     let env = Env::default();
     env.mock_all_auths();
@@ -103,7 +104,7 @@ fn test_queue_by_non_admin() {
     let stranger = Address::generate(&env);
     let target = Address::generate(&env);
 
-    client.queue(
+    let result = client.try_queue(
         &stranger,
         &target,
         &make_fn(&env),
@@ -111,10 +112,10 @@ fn test_queue_by_non_admin() {
         &make_desc(&env),
         &500u64,
     );
+    assert_eq!(result, Err(Ok(PulsarError::Unauthorized)));
 }
 
 #[test]
-#[should_panic(expected = "invalid delay")]
 fn test_queue_delay_too_short() {
     let env = Env::default();
     env.mock_all_auths();
@@ -122,11 +123,13 @@ fn test_queue_delay_too_short() {
     let target = Address::generate(&env);
 
     // min_delay=100, so 50 is too short
-    client.queue(&admin, &target, &make_fn(&env), &Vec::new(&env), &make_desc(&env), &50u64);
+    assert_eq!(
+        client.try_queue(&admin, &target, &make_fn(&env), &Vec::new(&env), &make_desc(&env), &50u64),
+        Err(Ok(PulsarError::InvalidInput))
+    );
 }
 
 #[test]
-#[should_panic(expected = "invalid delay")]
 fn test_queue_delay_too_long() {
     let env = Env::default();
     env.mock_all_auths();
@@ -143,7 +146,7 @@ fn test_queue_delay_too_long() {
         &86_400u64, // max_delay is 86_400
     );
     // test_queue_delay_too_long needs a value > 86_400
-    client.queue(
+    let result = client.try_queue(
         &admin,
         &target,
         &make_fn(&env),
@@ -151,6 +154,7 @@ fn test_queue_delay_too_long() {
         &make_desc(&env),
         &100_000u64,
     );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidInput)));
 }
 
 // ─── execute ─────────────────────────────────────────────────────────────────
@@ -178,7 +182,6 @@ fn test_execute_entry() {
 }
 
 #[test]
-#[should_panic(expected = "timelock not expired")]
 fn test_execute_too_early() {
     let env = Env::default();
     env.mock_all_auths();
@@ -188,11 +191,10 @@ fn test_execute_too_early() {
     let entry_id = client.queue(&admin, &target, &make_fn(&env), &Vec::new(&env), &make_desc(&env), &500u64);
 
     // timestamp is still 0, ETA is 500
-    client.execute(&executor, &entry_id);
+    assert_eq!(client.try_execute(&executor, &entry_id), Err(Ok(PulsarError::TooEarly)));
 }
 
 #[test]
-#[should_panic(expected = "unauthorized executor")]
 fn test_execute_wrong_executor() {
     let env = Env::default();
     env.mock_all_auths();
@@ -205,11 +207,10 @@ fn test_execute_wrong_executor() {
         li.timestamp = 600;
     });
 
-    client.execute(&stranger, &entry_id);
+    assert_eq!(client.try_execute(&stranger, &entry_id), Err(Ok(PulsarError::Unauthorized)));
 }
 
 #[test]
-#[should_panic(expected = "grace period expired")]
 fn test_execute_after_grace_period() {
     let env = Env::default();
     env.mock_all_auths();
@@ -224,7 +225,7 @@ fn test_execute_after_grace_period() {
         li.timestamp = 500 + 172_800 + 1;
     });
 
-    client.execute(&executor, &entry_id);
+    assert_eq!(client.try_execute(&executor, &entry_id), Err(Ok(PulsarError::Expired)));
 }
 
 // ─── cancel ──────────────────────────────────────────────────────────────────
@@ -244,7 +245,6 @@ fn test_cancel_entry() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_cancel_by_non_admin() {
     let env = Env::default();
     env.mock_all_auths();
@@ -253,11 +253,10 @@ fn test_cancel_by_non_admin() {
     let stranger = Address::generate(&env);
 
     let entry_id = client.queue(&admin, &target, &make_fn(&env), &Vec::new(&env), &make_desc(&env), &500u64);
-    client.cancel(&stranger, &entry_id);
+    assert_eq!(client.try_cancel(&stranger, &entry_id), Err(Ok(PulsarError::Unauthorized)));
 }
 
 #[test]
-#[should_panic(expected = "entry not queued")]
 fn test_cancel_already_cancelled() {
     let env = Env::default();
     env.mock_all_auths();
@@ -266,7 +265,7 @@ fn test_cancel_already_cancelled() {
 
     let entry_id = client.queue(&admin, &target, &make_fn(&env), &Vec::new(&env), &make_desc(&env), &500u64);
     client.cancel(&admin, &entry_id);
-    client.cancel(&admin, &entry_id); // already cancelled
+    assert_eq!(client.try_cancel(&admin, &entry_id), Err(Ok(PulsarError::InvalidState))); // already cancelled
 }
 
 // ─── is_ready ────────────────────────────────────────────────────────────────
@@ -1,11 +1,27 @@
 //! PulsarTrack - Token Bridge (Soroban)
 //! Cross-chain token bridge for multi-network ad campaign funding on Stellar.
+//!
+//! Entrypoints return `PulsarError` codes from pulsar-common-errors instead
+//! of panicking; admin handover and upgrades still go through the shared
+//! pulsar-common-admin helpers.
+//!
+//! Wrapped assets: the bridge is the issuer of each registered wrapped token.
+//! `release_inbound` mints it for assets locked on the source chain and
+//! confirming an outbound deposit back to that chain burns it. The supply
+//! ledger per (source chain, source asset) can be compared against the locked
+//! amount the relayer reports from the remote side.
+//!
+//! Outbound deposits wait as `Pending` until the relayer claims them with
+//! `claim_deposit`. Until then the sender may `cancel_deposit` and get the
+//! amount and fee back.
 
 #![no_std]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, token, Address, BytesN, Env, String,
+    contract, contractimpl, contracttype, symbol_short, token, Address, BytesN, Env, String, Vec,
 };
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone, PartialEq)]
 pub enum BridgeStatus {
@@ -14,6 +30,7 @@ pub enum BridgeStatus {
     Completed,
     Failed,
     Refunded,
+    Cancelled,
 }
 
 #[contracttype]
@@ -32,17 +49,38 @@ pub struct BridgeDeposit {
     pub tx_hash: Option<BytesN<32>>,
 }
 
+/// Source of a wrapped token issued by the bridge
+#[contracttype]
+#[derive(Clone)]
+pub struct WrappedAsset {
+    pub source_chain: String,
+    pub source_asset: String,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct WrappedSupply {
+    pub minted: i128,
+    pub burned: i128,
+    pub remote_locked: i128, // last amount the relayer reported locked remotely
+    pub reported_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
     Admin,
     PendingAdmin,
+    Paused,
     DepositCounter,
     BridgeFeesBps,
     SupportedChain(String),
     DailyVolume(String, u64), // (chain, day_number) -> tracks daily volume per chain
     Deposit(u64),
     RelayerAddress,
+    WrappedAsset(Address),         // stellar token -> source
+    WrappedSupply(String, String), // (source_chain, source_asset)
+    ProcessedInbound(BytesN<32>),  // source chain tx hash, for replay protection
 }
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
@@ -55,12 +93,12 @@ pub struct TokenBridgeContract;
 
 #[contractimpl]
 impl TokenBridgeContract {
-    pub fn initialize(env: Env, admin: Address, relayer: Address) {
+    pub fn initialize(env: Env, admin: Address, relayer: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -73,17 +111,19 @@ impl TokenBridgeContract {
         env.storage()
             .instance()
             .set(&DataKey::BridgeFeesBps, &50u32); // 0.5%
+        Ok(())
     }
 
-    pub fn add_supported_chain(env: Env, admin: Address, chain: String, max_daily_limit: i128) {
+    pub fn add_supported_chain(
+        env: Env,
+        admin: Address,
+        chain: String,
+        max_daily_limit: i128,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if admin != stored_admin {
-            panic!("unauthorized");
-        }
+        Self::_require_admin(&env, &admin)?;
         let _ttl_key = DataKey::SupportedChain(chain);
         env.storage().persistent().set(&_ttl_key, &max_daily_limit);
         env.storage().persistent().extend_ttl(
@@ -91,6 +131,7 @@ impl TokenBridgeContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     pub fn deposit_for_bridge(
@@ -100,14 +141,15 @@ impl TokenBridgeContract {
         amount: i128,
         recipient_chain: String,
         recipient_address: String,
-    ) -> u64 {
+    ) -> Result<u64, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_not_paused(&env)?;
         sender.require_auth();
 
         if amount <= 0 {
-            panic!("invalid amount");
+            return Err(PulsarError::InvalidAmount);
         }
 
         // Verify chain is supported and read max daily limit
@@ -115,7 +157,7 @@ impl TokenBridgeContract {
             .storage()
             .persistent()
             .get(&DataKey::SupportedChain(recipient_chain.clone()))
-            .expect("chain not supported");
+            .ok_or(PulsarError::NotFound)?;
 
         // Enforce daily transfer limit per chain
         let current_day = env.ledger().timestamp() / 86_400;
@@ -127,7 +169,7 @@ impl TokenBridgeContract {
             .unwrap_or(0);
 
         if current_daily_volume + amount > max_daily_limit {
-            panic!("daily transfer limit exceeded for chain");
+            return Err(PulsarError::LimitExceeded);
         }
 
         let fee_bps: u32 = env
@@ -185,36 +227,51 @@ impl TokenBridgeContract {
             .instance()
             .set(&DataKey::DepositCounter, &deposit_id);
 
-        env.events().publish(
-            (symbol_short!("bridge"), symbol_short!("deposit")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("bridge"),
+            symbol_short!("deposit"),
             (deposit_id, sender, net_amount),
         );
 
-        deposit_id
+        Ok(deposit_id)
     }
 
-    pub fn confirm_bridge(env: Env, relayer: Address, deposit_id: u64, tx_hash: BytesN<32>) {
+    pub fn confirm_bridge(
+        env: Env,
+        relayer: Address,
+        deposit_id: u64,
+        tx_hash: BytesN<32>,
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        relayer.require_auth();
-        let stored_relayer: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::RelayerAddress)
-            .unwrap();
-        if relayer != stored_relayer {
-            panic!("unauthorized relayer");
-        }
+        Self::_require_not_paused(&env)?;
+        Self::_require_relayer(&env, &relayer)?;
 
         let mut deposit: BridgeDeposit = env
             .storage()
             .persistent()
             .get(&DataKey::Deposit(deposit_id))
-            .expect("deposit not found");
+            .ok_or(PulsarError::NotFound)?;
 
-        if deposit.status != BridgeStatus::Pending {
-            panic!("not pending");
+        if deposit.status != BridgeStatus::Pending && deposit.status != BridgeStatus::Processing {
+            return Err(PulsarError::InvalidState);
+        }
+
+        // A wrapped token going home is retired here; it was released remotely
+        if let Some(wrapped) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, WrappedAsset>(&DataKey::WrappedAsset(deposit.token.clone()))
+        {
+            if wrapped.source_chain == deposit.recipient_chain {
+                token::Client::new(&env, &deposit.token)
+                    .burn(&env.current_contract_address(), &deposit.amount);
+                let mut supply = Self::_wrapped_supply(&env, &wrapped);
+                supply.burned += deposit.amount;
+                Self::_save_wrapped_supply(&env, &wrapped, &supply);
+            }
         }
 
         deposit.status = BridgeStatus::Completed;
@@ -229,30 +286,125 @@ impl TokenBridgeContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("bridge"), symbol_short!("confirmed")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("bridge"),
+            symbol_short!("confirmed"),
             deposit_id,
         );
+        Ok(())
     }
 
-    pub fn refund_deposit(env: Env, admin: Address, deposit_id: u64) {
+    /// Relayer: take a pending deposit for processing, ending the sender's
+    /// chance to cancel it
+    pub fn claim_deposit(env: Env, relayer: Address, deposit_id: u64) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if admin != stored_admin {
-            panic!("unauthorized");
+        Self::_require_not_paused(&env)?;
+        Self::_require_relayer(&env, &relayer)?;
+
+        let mut deposit: BridgeDeposit = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Deposit(deposit_id))
+            .ok_or(PulsarError::NotFound)?;
+        if deposit.status != BridgeStatus::Pending {
+            return Err(PulsarError::InvalidState);
         }
 
+        deposit.status = BridgeStatus::Processing;
+        let _ttl_key = DataKey::Deposit(deposit_id);
+        env.storage().persistent().set(&_ttl_key, &deposit);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("bridge"),
+            symbol_short!("claimed"),
+            deposit_id,
+        );
+        Ok(())
+    }
+
+    /// Sender: withdraw a deposit the relayer has not claimed yet. Refunds
+    /// the amount and the fee and frees the daily volume it used.
+    pub fn cancel_deposit(env: Env, sender: Address, deposit_id: u64) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_not_paused(&env)?;
+        sender.require_auth();
+
         let mut deposit: BridgeDeposit = env
             .storage()
             .persistent()
             .get(&DataKey::Deposit(deposit_id))
-            .expect("deposit not found");
+            .ok_or(PulsarError::NotFound)?;
+        if deposit.sender != sender {
+            return Err(PulsarError::Unauthorized);
+        }
+        if deposit.status != BridgeStatus::Pending {
+            return Err(PulsarError::InvalidState);
+        }
+
+        let total_refund = deposit.amount + deposit.bridge_fee;
+        token::Client::new(&env, &deposit.token).transfer(
+            &env.current_contract_address(),
+            &sender,
+            &total_refund,
+        );
+
+        let daily_volume_key =
+            DataKey::DailyVolume(deposit.recipient_chain.clone(), deposit.created_at / 86_400);
+        if let Some(volume) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, i128>(&daily_volume_key)
+        {
+            env.storage()
+                .persistent()
+                .set(&daily_volume_key, &(volume - total_refund).max(0));
+        }
+
+        deposit.status = BridgeStatus::Cancelled;
+        deposit.completed_at = Some(env.ledger().timestamp());
+        let _ttl_key = DataKey::Deposit(deposit_id);
+        env.storage().persistent().set(&_ttl_key, &deposit);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("bridge"),
+            symbol_short!("cancelled"),
+            (deposit_id, sender, total_refund),
+        );
+        Ok(())
+    }
+
+    pub fn refund_deposit(env: Env, admin: Address, deposit_id: u64) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_not_paused(&env)?;
+        Self::_require_admin(&env, &admin)?;
+
+        let mut deposit: BridgeDeposit = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Deposit(deposit_id))
+            .ok_or(PulsarError::NotFound)?;
 
         if deposit.status != BridgeStatus::Pending && deposit.status != BridgeStatus::Failed {
-            panic!("cannot refund");
+            return Err(PulsarError::InvalidState);
         }
 
         let total_refund = deposit.amount + deposit.bridge_fee;
@@ -271,6 +423,150 @@ impl TokenBridgeContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
+    }
+
+    /// Register a wrapped token whose issuer is this contract
+    pub fn register_wrapped_asset(
+        env: Env,
+        admin: Address,
+        token: Address,
+        source_chain: String,
+        source_asset: String,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::SupportedChain(source_chain.clone()))
+        {
+            return Err(PulsarError::NotFound);
+        }
+
+        let _ttl_key = DataKey::WrappedAsset(token);
+        env.storage().persistent().set(
+            &_ttl_key,
+            &WrappedAsset {
+                source_chain,
+                source_asset,
+            },
+        );
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Ok(())
+    }
+
+    /// Mint a wrapped token for assets locked on its source chain (relayer
+    /// only). Each source transaction is released at most once.
+    pub fn release_inbound(
+        env: Env,
+        relayer: Address,
+        token: Address,
+        recipient: Address,
+        amount: i128,
+        source_tx: BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_not_paused(&env)?;
+        Self::_require_relayer(&env, &relayer)?;
+
+        if amount <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        let processed_key = DataKey::ProcessedInbound(source_tx.clone());
+        if env.storage().persistent().has(&processed_key) {
+            return Err(PulsarError::InvalidState);
+        }
+        let wrapped: WrappedAsset = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WrappedAsset(token.clone()))
+            .ok_or(PulsarError::NotFound)?;
+
+        token::StellarAssetClient::new(&env, &token).mint(&recipient, &amount);
+
+        let mut supply = Self::_wrapped_supply(&env, &wrapped);
+        supply.minted += amount;
+        Self::_save_wrapped_supply(&env, &wrapped, &supply);
+
+        env.storage().persistent().set(&processed_key, &true);
+        env.storage().persistent().extend_ttl(
+            &processed_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("bridge"),
+            symbol_short!("released"),
+            (source_tx, recipient, amount),
+        );
+        Ok(())
+    }
+
+    /// Relayer: amount of `source_asset` currently locked on `source_chain`
+    pub fn report_remote_locked(
+        env: Env,
+        relayer: Address,
+        source_chain: String,
+        source_asset: String,
+        locked: i128,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_relayer(&env, &relayer)?;
+        if locked < 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+
+        let wrapped = WrappedAsset {
+            source_chain,
+            source_asset,
+        };
+        let mut supply = Self::_wrapped_supply(&env, &wrapped);
+        supply.remote_locked = locked;
+        supply.reported_at = env.ledger().timestamp();
+        Self::_save_wrapped_supply(&env, &wrapped, &supply);
+        Ok(())
+    }
+
+    pub fn get_wrapped_asset(env: Env, token: Address) -> Option<WrappedAsset> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::WrappedAsset(token))
+    }
+
+    pub fn get_wrapped_supply(env: Env, chain: String, asset: String) -> WrappedSupply {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_wrapped_supply(
+            &env,
+            &WrappedAsset {
+                source_chain: chain,
+                source_asset: asset,
+            },
+        )
+    }
+
+    /// Whether outstanding wrapped supply (minted - burned) is covered by the
+    /// last reported remote lock
+    pub fn check_wrapped_supply(env: Env, chain: String, asset: String) -> bool {
+        let supply = Self::get_wrapped_supply(env, chain, asset);
+        supply.minted - supply.burned <= supply.remote_locked
     }
 
     pub fn get_deposit(env: Env, deposit_id: u64) -> Option<BridgeDeposit> {
@@ -282,18 +578,115 @@ impl TokenBridgeContract {
             .get(&DataKey::Deposit(deposit_id))
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    /// Deposits with ids after `start`, paged by `pulsar_common_admin::page`
+    pub fn list_deposits(env: Env, start: u64, limit: u32) -> (Vec<BridgeDeposit>, Option<u64>) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::DepositCounter)
+            .unwrap_or(0);
+        pulsar_common_admin::page(&env, counter, start, limit, |id| {
+            env.storage().persistent().get(&DataKey::Deposit(id))
+        })
+    }
+
+    /// Emergency stop for deposits, confirmations and refunds
+    pub fn set_paused(env: Env, admin: Address, paused: bool) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::set_paused(&env, &DataKey::Admin, &DataKey::Paused, admin, paused)
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        pulsar_common_admin::is_paused(&env, &DataKey::Paused)
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
+
+    fn _require_admin(env: &Env, admin: &Address) -> Result<(), PulsarError> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if *admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn _require_relayer(env: &Env, relayer: &Address) -> Result<(), PulsarError> {
+        relayer.require_auth();
+        let stored_relayer: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::RelayerAddress)
+            .ok_or(PulsarError::NotInitialized)?;
+        if *relayer != stored_relayer {
+            return Err(PulsarError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn _wrapped_supply(env: &Env, wrapped: &WrappedAsset) -> WrappedSupply {
+        env.storage()
+            .persistent()
+            .get(&DataKey::WrappedSupply(
+                wrapped.source_chain.clone(),
+                wrapped.source_asset.clone(),
+            ))
+            .unwrap_or(WrappedSupply {
+                minted: 0,
+                burned: 0,
+                remote_locked: 0,
+                reported_at: 0,
+            })
+    }
+
+    fn _save_wrapped_supply(env: &Env, wrapped: &WrappedAsset, supply: &WrappedSupply) {
+        let _ttl_key =
+            DataKey::WrappedSupply(wrapped.source_chain.clone(), wrapped.source_asset.clone());
+        env.storage().persistent().set(&_ttl_key, supply);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
         );
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    fn _require_not_paused(env: &Env) -> Result<(), PulsarError> {
+        if pulsar_common_admin::is_paused(env, &DataKey::Paused) {
+            return Err(PulsarError::Paused);
+        }
+        Ok(())
     }
 }
 
@@ -1,15 +1,22 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, Env, String};
 
 fn setup(env: &Env) -> (TokenBridgeContractClient<'_>, Address) {
     let admin = Address::generate(env);
     let relayer = Address::generate(env);
-    let id = env.register_contract(None, TokenBridgeContract);
+    let id = env.register(TokenBridgeContract, ());
     let c = TokenBridgeContractClient::new(env, &id);
     c.initialize(&admin, &relayer);
     (c, admin)
 }
+fn funded_token(env: &Env, holder: &Address, amount: i128) -> Address {
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    StellarAssetClient::new(env, &token).mint(holder, &amount);
+    token
+}
 fn s(env: &Env, v: &str) -> String {
     String::from_str(env, v)
 }
@@ -22,23 +29,25 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
-    let id = env.register_contract(None, TokenBridgeContract);
+    let id = env.register(TokenBridgeContract, ());
     let c = TokenBridgeContractClient::new(&env, &id);
     let a = Address::generate(&env);
     let r = Address::generate(&env);
     c.initialize(&a, &r);
-    c.initialize(&a, &r);
+    assert_eq!(
+        c.try_initialize(&a, &r),
+        Err(Ok(PulsarError::AlreadyInitialized))
+    );
 }
 
 #[test]
 #[should_panic]
 fn test_initialize_non_admin_fails() {
     let env = Env::default();
-    let id = env.register_contract(None, TokenBridgeContract);
+    let id = env.register(TokenBridgeContract, ());
     let c = TokenBridgeContractClient::new(&env, &id);
     c.initialize(&Address::generate(&env), &Address::generate(&env));
 }
@@ -52,16 +61,16 @@ fn test_add_supported_chain() {
 }
 
 #[test]
-#[should_panic(expected = "unauthorized")]
 fn test_add_supported_chain_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, _) = setup(&env);
-    c.add_supported_chain(
+    let result = c.try_add_supported_chain(
         &Address::generate(&env),
         &s(&env, "ethereum"),
         &1_000_000i128,
     );
+    assert_eq!(result, Err(Ok(PulsarError::Unauthorized)));
 }
 
 #[test]
@@ -71,3 +80,229 @@ fn test_get_deposit_nonexistent() {
     let (c, _) = setup(&env);
     assert!(c.get_deposit(&999u64).is_none());
 }
+
+#[test]
+fn test_list_deposits_pages_by_cursor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let sender = Address::generate(&env);
+    let token = funded_token(&env, &sender, 10_000);
+    c.add_supported_chain(&admin, &s(&env, "ethereum"), &5_000i128);
+    for _ in 0..3 {
+        c.deposit_for_bridge(
+            &sender,
+            &token,
+            &1_000i128,
+            &s(&env, "ethereum"),
+            &s(&env, "0xabc"),
+        );
+    }
+
+    let (page, cursor) = c.list_deposits(&0u64, &2u32);
+    assert_eq!(page.len(), 2);
+    assert_eq!(cursor, Some(2));
+    let (page, cursor) = c.list_deposits(&2u64, &2u32);
+    assert_eq!(page.get(0).unwrap().deposit_id, 3);
+    assert_eq!(cursor, None);
+    assert_eq!(c.list_deposits(&3u64, &2u32).0.len(), 0);
+}
+
+#[test]
+fn test_deposit_while_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    c.add_supported_chain(&admin, &s(&env, "ethereum"), &1_000_000i128);
+    c.set_paused(&admin, &true);
+    let result = c.try_deposit_for_bridge(
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &1_000i128,
+        &s(&env, "ethereum"),
+        &s(&env, "0xabc"),
+    );
+    assert_eq!(result, Err(Ok(PulsarError::Paused)));
+}
+
+#[test]
+fn test_deposit_error_codes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let sender = Address::generate(&env);
+    let token = funded_token(&env, &sender, 10_000);
+    c.add_supported_chain(&admin, &s(&env, "ethereum"), &5_000i128);
+
+    let unsupported = c.try_deposit_for_bridge(
+        &sender,
+        &token,
+        &1_000i128,
+        &s(&env, "solana"),
+        &s(&env, "x"),
+    );
+    assert_eq!(unsupported, Err(Ok(PulsarError::NotFound)));
+
+    let over_limit = c.try_deposit_for_bridge(
+        &sender,
+        &token,
+        &6_000i128,
+        &s(&env, "ethereum"),
+        &s(&env, "x"),
+    );
+    assert_eq!(over_limit, Err(Ok(PulsarError::LimitExceeded)));
+
+    let zero =
+        c.try_deposit_for_bridge(&sender, &token, &0i128, &s(&env, "ethereum"), &s(&env, "x"));
+    assert_eq!(zero, Err(Ok(PulsarError::InvalidAmount)));
+}
+
+#[test]
+fn test_confirm_and_refund_states() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let id = env.register(TokenBridgeContract, ());
+    let c = TokenBridgeContractClient::new(&env, &id);
+    c.initialize(&admin, &relayer);
+
+    let sender = Address::generate(&env);
+    let token = funded_token(&env, &sender, 10_000);
+    c.add_supported_chain(&admin, &s(&env, "ethereum"), &5_000i128);
+    let deposit_id = c.deposit_for_bridge(
+        &sender,
+        &token,
+        &1_000i128,
+        &s(&env, "ethereum"),
+        &s(&env, "x"),
+    );
+
+    let hash = BytesN::from_array(&env, &[7u8; 32]);
+    let stranger = c.try_confirm_bridge(&Address::generate(&env), &deposit_id, &hash);
+    assert_eq!(stranger, Err(Ok(PulsarError::Unauthorized)));
+
+    c.confirm_bridge(&relayer, &deposit_id, &hash);
+    assert_eq!(
+        c.try_confirm_bridge(&relayer, &deposit_id, &hash),
+        Err(Ok(PulsarError::InvalidState))
+    );
+    assert_eq!(
+        c.try_refund_deposit(&admin, &deposit_id),
+        Err(Ok(PulsarError::InvalidState))
+    );
+    assert_eq!(
+        c.try_refund_deposit(&admin, &999u64),
+        Err(Ok(PulsarError::NotFound))
+    );
+}
+
+#[test]
+fn test_cancel_deposit_before_relayer_claims() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let id = env.register(TokenBridgeContract, ());
+    let c = TokenBridgeContractClient::new(&env, &id);
+    c.initialize(&admin, &relayer);
+
+    let sender = Address::generate(&env);
+    let token = funded_token(&env, &sender, 10_000);
+    c.add_supported_chain(&admin, &s(&env, "ethereum"), &5_000i128);
+    let deposit = |amount: i128| {
+        c.deposit_for_bridge(
+            &sender,
+            &token,
+            &amount,
+            &s(&env, "ethereum"),
+            &s(&env, "0xtypo"),
+        )
+    };
+
+    let first = deposit(4_000);
+    assert_eq!(
+        c.try_cancel_deposit(&Address::generate(&env), &first),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+    c.cancel_deposit(&sender, &first);
+    assert!(c.get_deposit(&first).unwrap().status == BridgeStatus::Cancelled);
+    // Amount and fee come back, and the daily limit is freed
+    assert_eq!(token::Client::new(&env, &token).balance(&sender), 10_000);
+
+    let second = deposit(4_000);
+    c.claim_deposit(&relayer, &second);
+    assert_eq!(
+        c.try_cancel_deposit(&sender, &second),
+        Err(Ok(PulsarError::InvalidState))
+    );
+    c.confirm_bridge(&relayer, &second, &BytesN::from_array(&env, &[1u8; 32]));
+    assert!(c.get_deposit(&second).unwrap().status == BridgeStatus::Completed);
+}
+
+#[test]
+fn test_wrapped_supply_ledger() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let id = env.register(TokenBridgeContract, ());
+    let c = TokenBridgeContractClient::new(&env, &id);
+    c.initialize(&admin, &relayer);
+    let chain = s(&env, "ethereum");
+    let asset = s(&env, "0xusdc");
+    c.add_supported_chain(&admin, &chain, &1_000_000i128);
+
+    // The bridge issues the wrapped token
+    let wrapped = env.register_stellar_asset_contract_v2(id.clone()).address();
+    c.register_wrapped_asset(&admin, &wrapped, &chain, &asset);
+
+    let user = Address::generate(&env);
+    let source_tx = BytesN::from_array(&env, &[1u8; 32]);
+    c.release_inbound(&relayer, &wrapped, &user, &10_000i128, &source_tx);
+    assert_eq!(token::Client::new(&env, &wrapped).balance(&user), 10_000);
+    assert_eq!(
+        c.try_release_inbound(&relayer, &wrapped, &user, &10_000i128, &source_tx),
+        Err(Ok(PulsarError::InvalidState))
+    );
+
+    // Nothing reported locked yet, so the supply is uncovered
+    assert!(!c.check_wrapped_supply(&chain, &asset));
+    c.report_remote_locked(&relayer, &chain, &asset, &10_000i128);
+    assert!(c.check_wrapped_supply(&chain, &asset));
+
+    // Sending 4_000 home burns the net amount once confirmed
+    let deposit_id = c.deposit_for_bridge(&user, &wrapped, &4_000i128, &chain, &s(&env, "x"));
+    c.confirm_bridge(&relayer, &deposit_id, &BytesN::from_array(&env, &[2u8; 32]));
+    let net = c.get_deposit(&deposit_id).unwrap().amount;
+    let supply = c.get_wrapped_supply(&chain, &asset);
+    assert_eq!(supply.minted, 10_000);
+    assert_eq!(supply.burned, net);
+    assert_eq!(token::Client::new(&env, &wrapped).balance(&id), 4_000 - net);
+
+    c.report_remote_locked(&relayer, &chain, &asset, &(10_000 - net - 1));
+    assert!(!c.check_wrapped_supply(&chain, &asset));
+}
+
+#[test]
+fn test_release_requires_registered_asset() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let id = env.register(TokenBridgeContract, ());
+    let c = TokenBridgeContractClient::new(&env, &id);
+    c.initialize(&admin, &relayer);
+    let token = env.register_stellar_asset_contract_v2(id.clone()).address();
+    let user = Address::generate(&env);
+    let source_tx = BytesN::from_array(&env, &[1u8; 32]);
+
+    assert_eq!(
+        c.try_release_inbound(&relayer, &token, &user, &1i128, &source_tx),
+        Err(Ok(PulsarError::NotFound))
+    );
+    assert_eq!(
+        c.try_release_inbound(&Address::generate(&env), &token, &user, &1i128, &source_tx),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
@@ -0,0 +1,512 @@
+//! PulsarTrack - Token Vesting (Soroban)
+//! Escrow for team and investor PULSAR allocations. Each grant vests linearly
+//! from `start` to `start + duration` with nothing releasable before the
+//! cliff. Admin-revocable grants (the admin may be handed to the DAO) return
+//! their unvested part on revocation.
+//!
+//! Tokens still held for a grant count as voting power for the grant's
+//! delegate (the beneficiary unless redelegated). Every change writes a
+//! checkpoint, and governance-dao adds `get_past_votes` as of the ledger
+//! before a proposal started on top of wallet balances, so redelegating
+//! during a vote cannot count the same locked tokens twice.
+//!
+//! Events (third topic is `pulsar_common_admin::EVENT_SCHEMA_VERSION`):
+//! - ("vesting", "granted"): [grant_id: u64, beneficiary: Address, amount: i128]
+//! - ("vesting", "released"): [grant_id: u64, beneficiary: Address, amount: i128]
+//! - ("vesting", "revoked"): [grant_id: u64, returned: i128]
+//! - ("vesting", "delegated"): [grant_id: u64, from: Address, to: Address]
+
+#![no_std]
+#![allow(clippy::too_many_arguments)]
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env, Vec};
+
+pub use pulsar_common_errors::PulsarError;
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Grant {
+    pub grant_id: u64,
+    pub beneficiary: Address,
+    pub total_amount: i128, // cut down to the vested amount on revocation
+    pub released: i128,
+    pub start: u64,
+    pub cliff_secs: u64,
+    pub duration_secs: u64,
+    pub revocable: bool,
+    pub revoked_at: Option<u64>,
+    pub delegate: Address,
+    pub created_at: u64,
+}
+
+/// Power delegated to an account from `ledger` onwards
+#[contracttype]
+#[derive(Clone)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub votes: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    PendingAdmin,
+    Token,
+    GrantCounter,
+    Grant(u64),
+    BeneficiaryGrantCount(Address),
+    BeneficiaryGrant(Address, u32),
+    DelegatedPower(Address),
+    CheckpointCount(Address),
+    Checkpoint(Address, u32), // (account, index)
+}
+
+const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
+const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
+const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
+
+#[contract]
+pub struct TokenVestingContract;
+
+#[contractimpl]
+impl TokenVestingContract {
+    pub fn initialize(env: Env, admin: Address, token: Address) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(PulsarError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage().instance().set(&DataKey::GrantCounter, &0u64);
+        Ok(())
+    }
+
+    /// Fund a grant from the admin's balance. `start` may be in the past to
+    /// honour vesting that began before the grant was put on-chain.
+    pub fn create_grant(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        amount: i128,
+        start: u64,
+        cliff_secs: u64,
+        duration_secs: u64,
+        revocable: bool,
+    ) -> Result<u64, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+        if amount <= 0 {
+            return Err(PulsarError::InvalidAmount);
+        }
+        if duration_secs == 0 || cliff_secs > duration_secs {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        token::Client::new(&env, &token_addr).transfer(
+            &admin,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let grant_id: u64 = env
+            .storage()
+            .instance()
+            .get::<DataKey, u64>(&DataKey::GrantCounter)
+            .unwrap_or(0)
+            + 1;
+        let grant = Grant {
+            grant_id,
+            beneficiary: beneficiary.clone(),
+            total_amount: amount,
+            released: 0,
+            start,
+            cliff_secs,
+            duration_secs,
+            revocable,
+            revoked_at: None,
+            delegate: beneficiary.clone(),
+            created_at: env.ledger().timestamp(),
+        };
+        Self::_save_grant(&env, &grant);
+        env.storage()
+            .instance()
+            .set(&DataKey::GrantCounter, &grant_id);
+
+        let count_key = DataKey::BeneficiaryGrantCount(beneficiary.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let _ttl_key = DataKey::BeneficiaryGrant(beneficiary.clone(), count);
+        env.storage().persistent().set(&_ttl_key, &grant_id);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage().persistent().set(&count_key, &(count + 1));
+        env.storage().persistent().extend_ttl(
+            &count_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        Self::_adjust_power(&env, &beneficiary, amount);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("vesting"),
+            symbol_short!("granted"),
+            (grant_id, beneficiary, amount),
+        );
+
+        Ok(grant_id)
+    }
+
+    /// Transfer everything vested so far to the beneficiary
+    pub fn release(env: Env, beneficiary: Address, grant_id: u64) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        beneficiary.require_auth();
+
+        let mut grant = Self::_load_grant(&env, grant_id)?;
+        if grant.beneficiary != beneficiary {
+            return Err(PulsarError::Unauthorized);
+        }
+        let amount = Self::_vested(&env, &grant) - grant.released;
+        if amount <= 0 {
+            return Err(PulsarError::InsufficientBalance);
+        }
+
+        grant.released += amount;
+        Self::_save_grant(&env, &grant);
+        Self::_adjust_power(&env, &grant.delegate, -amount);
+
+        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        token::Client::new(&env, &token_addr).transfer(
+            &env.current_contract_address(),
+            &beneficiary,
+            &amount,
+        );
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("vesting"),
+            symbol_short!("released"),
+            (grant_id, beneficiary, amount),
+        );
+
+        Ok(amount)
+    }
+
+    /// Stop vesting and return the unvested part to the admin. Whatever had
+    /// vested stays releasable by the beneficiary.
+    pub fn revoke(env: Env, admin: Address, grant_id: u64) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_admin(&env, &admin)?;
+
+        let mut grant = Self::_load_grant(&env, grant_id)?;
+        if !grant.revocable {
+            return Err(PulsarError::InvalidState);
+        }
+        if grant.revoked_at.is_some() {
+            return Err(PulsarError::AlreadyExists);
+        }
+
+        let vested = Self::_vested(&env, &grant);
+        let returned = grant.total_amount - vested;
+        grant.total_amount = vested;
+        grant.revoked_at = Some(env.ledger().timestamp());
+        Self::_save_grant(&env, &grant);
+
+        if returned > 0 {
+            Self::_adjust_power(&env, &grant.delegate, -returned);
+            let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+            token::Client::new(&env, &token_addr).transfer(
+                &env.current_contract_address(),
+                &admin,
+                &returned,
+            );
+        }
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("vesting"),
+            symbol_short!("revoked"),
+            (grant_id, returned),
+        );
+
+        Ok(returned)
+    }
+
+    /// Point the voting power of the grant's locked tokens at another account
+    pub fn delegate_votes(
+        env: Env,
+        beneficiary: Address,
+        grant_id: u64,
+        to: Address,
+    ) -> Result<(), PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        beneficiary.require_auth();
+
+        let mut grant = Self::_load_grant(&env, grant_id)?;
+        if grant.beneficiary != beneficiary {
+            return Err(PulsarError::Unauthorized);
+        }
+        let locked = grant.total_amount - grant.released;
+        let from = grant.delegate.clone();
+        Self::_adjust_power(&env, &from, -locked);
+        Self::_adjust_power(&env, &to, locked);
+        grant.delegate = to.clone();
+        Self::_save_grant(&env, &grant);
+
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("vesting"),
+            symbol_short!("delegated"),
+            (grant_id, from, to),
+        );
+        Ok(())
+    }
+
+    pub fn get_grant(env: Env, grant_id: u64) -> Option<Grant> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().persistent().get(&DataKey::Grant(grant_id))
+    }
+
+    pub fn get_vested(env: Env, grant_id: u64) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let grant = Self::_load_grant(&env, grant_id)?;
+        Ok(Self::_vested(&env, &grant))
+    }
+
+    pub fn get_releasable(env: Env, grant_id: u64) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let grant = Self::_load_grant(&env, grant_id)?;
+        Ok(Self::_vested(&env, &grant) - grant.released)
+    }
+
+    pub fn get_beneficiary_grants(env: Env, beneficiary: Address) -> Vec<u64> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BeneficiaryGrantCount(beneficiary.clone()))
+            .unwrap_or(0);
+        let mut ids = Vec::new(&env);
+        for i in 0..count {
+            if let Some(id) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, u64>(&DataKey::BeneficiaryGrant(beneficiary.clone(), i))
+            {
+                ids.push_back(id);
+            }
+        }
+        ids
+    }
+
+    /// Locked (unreleased) grant tokens currently delegated to `account`
+    pub fn voting_power(env: Env, account: Address) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::DelegatedPower(account))
+            .unwrap_or(0)
+    }
+
+    /// Power delegated to `account` at the end of ledger `ledger`
+    pub fn get_past_votes(env: Env, account: Address, ledger: u32) -> Result<i128, PulsarError> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if ledger > env.ledger().sequence() {
+            return Err(PulsarError::InvalidInput);
+        }
+
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CheckpointCount(account.clone()))
+            .unwrap_or(0);
+        // Binary search for the last checkpoint at or before `ledger`
+        let mut low = 0u32;
+        let mut high = count;
+        while low < high {
+            let mid = (low + high) / 2;
+            let checkpoint: Checkpoint = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Checkpoint(account.clone(), mid))
+                .unwrap();
+            if checkpoint.ledger > ledger {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        if low == 0 {
+            return Ok(0);
+        }
+        Ok(env
+            .storage()
+            .persistent()
+            .get::<DataKey, Checkpoint>(&DataKey::Checkpoint(account, low - 1))
+            .unwrap()
+            .votes)
+    }
+
+    pub fn get_grant_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::GrantCounter)
+            .unwrap_or(0)
+    }
+
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::propose_admin(
+            &env,
+            &DataKey::Admin,
+            &DataKey::PendingAdmin,
+            current_admin,
+            new_admin,
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
+    }
+
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
+    }
+
+    // ─── internal helpers ────────────────────────────────────────────────────
+
+    fn _require_admin(env: &Env, admin: &Address) -> Result<(), PulsarError> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
+        if *admin != stored_admin {
+            return Err(PulsarError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn _load_grant(env: &Env, grant_id: u64) -> Result<Grant, PulsarError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Grant(grant_id))
+            .ok_or(PulsarError::NotFound)
+    }
+
+    fn _save_grant(env: &Env, grant: &Grant) {
+        let _ttl_key = DataKey::Grant(grant.grant_id);
+        env.storage().persistent().set(&_ttl_key, grant);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// Vested amount as of now, or as of revocation for revoked grants, in
+    /// which case `total_amount` already holds it
+    fn _vested(env: &Env, grant: &Grant) -> i128 {
+        if grant.revoked_at.is_some() {
+            return grant.total_amount;
+        }
+        let now = env.ledger().timestamp();
+        if now < grant.start + grant.cliff_secs {
+            return 0;
+        }
+        let elapsed = now - grant.start;
+        if elapsed >= grant.duration_secs {
+            return grant.total_amount;
+        }
+        grant.total_amount * elapsed as i128 / grant.duration_secs as i128
+    }
+
+    fn _adjust_power(env: &Env, account: &Address, delta: i128) {
+        if delta == 0 {
+            return;
+        }
+        let _ttl_key = DataKey::DelegatedPower(account.clone());
+        let power: i128 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+        env.storage().persistent().set(&_ttl_key, &(power + delta));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Self::_write_checkpoint(env, account, power + delta);
+    }
+
+    /// Record `account`'s power; several changes in one ledger share a
+    /// checkpoint.
+    fn _write_checkpoint(env: &Env, account: &Address, votes: i128) {
+        let ledger = env.ledger().sequence();
+        let checkpoint = Checkpoint { ledger, votes };
+
+        let count_key = DataKey::CheckpointCount(account.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let last_same_ledger = count > 0
+            && env
+                .storage()
+                .persistent()
+                .get::<DataKey, Checkpoint>(&DataKey::Checkpoint(account.clone(), count - 1))
+                .is_some_and(|last| last.ledger == ledger);
+        let index = if last_same_ledger { count - 1 } else { count };
+
+        let _ttl_key = DataKey::Checkpoint(account.clone(), index);
+        env.storage().persistent().set(&_ttl_key, &checkpoint);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        if !last_same_ledger {
+            env.storage().persistent().set(&count_key, &(count + 1));
+        }
+        env.storage().persistent().extend_ttl(
+            &count_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+}
+
+mod test;
@@ -0,0 +1,215 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    Address, Env,
+};
+
+// ─── helpers ─────────────────────────────────────────────────────────────────
+
+struct Setup<'a> {
+    client: TokenVestingContractClient<'a>,
+    token: TokenClient<'a>,
+    admin: Address,
+}
+
+fn setup(env: &Env) -> Setup<'_> {
+    let admin = Address::generate(env);
+    let token_admin = Address::generate(env);
+    let token = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    StellarAssetClient::new(env, &token).mint(&admin, &1_000_000);
+
+    let contract_id = env.register(TokenVestingContract, ());
+    let client = TokenVestingContractClient::new(env, &contract_id);
+    client.initialize(&admin, &token);
+
+    Setup {
+        client,
+        token: TokenClient::new(env, &token),
+        admin,
+    }
+}
+
+/// 10_000 tokens over 1_000s with a 250s cliff, starting at t=1_000
+fn grant(env: &Env, s: &Setup, beneficiary: &Address, revocable: bool) -> u64 {
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    s.client.create_grant(
+        &s.admin,
+        beneficiary,
+        &10_000i128,
+        &1_000u64,
+        &250u64,
+        &1_000u64,
+        &revocable,
+    )
+}
+
+fn at(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|li| li.timestamp = timestamp);
+}
+
+// ─── tests ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_create_grant_escrows_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let beneficiary = Address::generate(&env);
+    let id = grant(&env, &s, &beneficiary, false);
+
+    assert_eq!(s.token.balance(&s.client.address), 10_000);
+    assert_eq!(s.token.balance(&s.admin), 990_000);
+    let g = s.client.get_grant(&id).unwrap();
+    assert_eq!(g.beneficiary, beneficiary);
+    assert_eq!(g.delegate, beneficiary);
+    assert_eq!(s.client.get_beneficiary_grants(&beneficiary).len(), 1);
+    assert_eq!(s.client.voting_power(&beneficiary), 10_000);
+}
+
+#[test]
+fn test_nothing_vests_before_cliff() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let beneficiary = Address::generate(&env);
+    let id = grant(&env, &s, &beneficiary, false);
+
+    at(&env, 1_249);
+    assert_eq!(s.client.get_vested(&id), 0);
+    assert!(s.client.try_release(&beneficiary, &id).is_err());
+
+    at(&env, 1_250);
+    assert_eq!(s.client.get_vested(&id), 2_500);
+}
+
+#[test]
+fn test_release_follows_linear_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let beneficiary = Address::generate(&env);
+    let id = grant(&env, &s, &beneficiary, false);
+
+    at(&env, 1_400);
+    assert_eq!(s.client.release(&beneficiary, &id), 4_000);
+    assert_eq!(s.client.get_releasable(&id), 0);
+    assert_eq!(s.client.voting_power(&beneficiary), 6_000);
+
+    at(&env, 3_000);
+    assert_eq!(s.client.release(&beneficiary, &id), 6_000);
+    assert_eq!(s.token.balance(&beneficiary), 10_000);
+    assert_eq!(s.client.voting_power(&beneficiary), 0);
+}
+
+#[test]
+fn test_revoke_returns_unvested_and_keeps_vested() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let beneficiary = Address::generate(&env);
+    let id = grant(&env, &s, &beneficiary, true);
+
+    at(&env, 1_300);
+    assert_eq!(s.client.revoke(&s.admin, &id), 7_000);
+    assert_eq!(s.token.balance(&s.admin), 997_000);
+    assert_eq!(s.client.voting_power(&beneficiary), 3_000);
+
+    // Vesting stops at revocation
+    at(&env, 5_000);
+    assert_eq!(s.client.get_vested(&id), 3_000);
+    assert_eq!(s.client.release(&beneficiary, &id), 3_000);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_revoke_irrevocable_grant_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let beneficiary = Address::generate(&env);
+    let id = grant(&env, &s, &beneficiary, false);
+    assert_eq!(
+        s.client.try_revoke(&s.admin, &id),
+        Err(Ok(PulsarError::InvalidState))
+    );
+}
+
+#[test]
+fn test_delegate_votes_moves_locked_power() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let beneficiary = Address::generate(&env);
+    let delegate = Address::generate(&env);
+    let id = grant(&env, &s, &beneficiary, false);
+
+    s.client.delegate_votes(&beneficiary, &id, &delegate);
+    assert_eq!(s.client.voting_power(&beneficiary), 0);
+    assert_eq!(s.client.voting_power(&delegate), 10_000);
+
+    at(&env, 1_500);
+    s.client.release(&beneficiary, &id);
+    assert_eq!(s.client.voting_power(&delegate), 5_000);
+}
+
+#[test]
+fn test_past_votes_ignore_later_redelegation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let beneficiary = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let id = grant(&env, &s, &beneficiary, false);
+    s.client.delegate_votes(&beneficiary, &id, &first);
+    let before = env.ledger().sequence();
+
+    env.ledger().with_mut(|li| li.sequence_number += 10);
+    s.client.delegate_votes(&beneficiary, &id, &second);
+
+    assert_eq!(s.client.get_past_votes(&first, &before), 10_000);
+    assert_eq!(s.client.get_past_votes(&second, &before), 0);
+    let now = env.ledger().sequence();
+    assert_eq!(s.client.get_past_votes(&first, &now), 0);
+    assert_eq!(s.client.get_past_votes(&second, &now), 10_000);
+    assert_eq!(
+        s.client.try_get_past_votes(&second, &(now + 1)),
+        Err(Ok(PulsarError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_release_by_other_account_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let beneficiary = Address::generate(&env);
+    let id = grant(&env, &s, &beneficiary, false);
+
+    at(&env, 3_000);
+    assert_eq!(
+        s.client.try_release(&Address::generate(&env), &id),
+        Err(Ok(PulsarError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_cliff_longer_than_duration_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let result = s.client.try_create_grant(
+        &s.admin,
+        &Address::generate(&env),
+        &1_000i128,
+        &0u64,
+        &500u64,
+        &100u64,
+        &false,
+    );
+    assert_eq!(result, Err(Ok(PulsarError::InvalidInput)));
+}
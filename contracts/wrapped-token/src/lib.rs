@@ -4,6 +4,8 @@
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
 
+pub use pulsar_common_errors::PulsarError;
+
 #[contracttype]
 #[derive(Clone)]
 pub struct WrappedToken {
@@ -51,12 +53,12 @@ pub struct WrappedTokenContract;
 
 #[contractimpl]
 impl WrappedTokenContract {
-    pub fn initialize(env: Env, admin: Address, relayer: Address) {
+    pub fn initialize(env: Env, admin: Address, relayer: Address) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(PulsarError::AlreadyInitialized);
         }
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -66,6 +68,7 @@ impl WrappedTokenContract {
         env.storage()
             .instance()
             .set(&DataKey::WrapRecordCounter, &0u64);
+        Ok(())
     }
 
     pub fn register_wrapped_token(
@@ -77,14 +80,18 @@ impl WrappedTokenContract {
         underlying_chain: String,
         underlying_address: String,
         stellar_token: Address,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PulsarError::NotInitialized)?;
         if admin != stored_admin {
-            panic!("unauthorized");
+            return Err(PulsarError::Unauthorized);
         }
 
         let wrapped = WrappedToken {
@@ -105,6 +112,7 @@ impl WrappedTokenContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        Ok(())
     }
 
     pub fn mint_wrapped(
@@ -114,7 +122,7 @@ impl WrappedTokenContract {
         recipient: Address,
         amount: i128,
         source_tx: String,
-    ) -> u64 {
+    ) -> Result<u64, PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -125,23 +133,23 @@ impl WrappedTokenContract {
             .get(&DataKey::RelayerAddress)
             .unwrap();
         if relayer != stored_relayer {
-            panic!("unauthorized relayer");
+            return Err(PulsarError::Unauthorized);
         }
 
         // Check for replay attack - ensure source_tx hasn't been processed
         let tx_key = DataKey::ProcessedTx(source_tx.clone());
         if env.storage().persistent().has(&tx_key) {
-            panic!("source transaction already processed");
+            return Err(PulsarError::AlreadyExists);
         }
 
         let mut wrapped: WrappedToken = env
             .storage()
             .persistent()
             .get(&DataKey::WrappedToken(symbol.clone()))
-            .expect("token not registered");
+            .ok_or(PulsarError::NotFound)?;
 
         if !wrapped.is_active {
-            panic!("token not active");
+            return Err(PulsarError::InvalidState);
         }
 
         // Mint stellar-side tokens
@@ -200,12 +208,14 @@ impl WrappedTokenContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("wrapped"), symbol_short!("minted")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("wrapped"),
+            symbol_short!("minted"),
             (record_id, recipient, amount),
         );
 
-        record_id
+        Ok(record_id)
     }
 
     pub fn burn_wrapped(
@@ -214,7 +224,7 @@ impl WrappedTokenContract {
         symbol: String,
         amount: i128,
         target_address: String,
-    ) {
+    ) -> Result<(), PulsarError> {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -224,7 +234,7 @@ impl WrappedTokenContract {
         let current: i128 = env.storage().persistent().get(&key).unwrap_or(0);
 
         if current < amount {
-            panic!("insufficient balance");
+            return Err(PulsarError::InsufficientBalance);
         }
 
         env.storage().persistent().set(&key, &(current - amount));
@@ -238,7 +248,7 @@ impl WrappedTokenContract {
             .storage()
             .persistent()
             .get(&DataKey::WrappedToken(symbol.clone()))
-            .expect("token not registered");
+            .ok_or(PulsarError::NotFound)?;
 
         wrapped.total_wrapped -= amount;
         let _ttl_key = DataKey::WrappedToken(symbol);
@@ -249,10 +259,13 @@ impl WrappedTokenContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("wrapped"), symbol_short!("burned")),
+        pulsar_common_admin::publish_event(
+            &env,
+            symbol_short!("wrapped"),
+            symbol_short!("burned"),
             (user, amount, target_address),
         );
+        Ok(())
     }
 
     pub fn get_wrapped_token(env: Env, symbol: String) -> Option<WrappedToken> {
@@ -274,18 +287,30 @@ impl WrappedTokenContract {
             .unwrap_or(0)
     }
 
-    pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), PulsarError> {
         pulsar_common_admin::propose_admin(
             &env,
             &DataKey::Admin,
             &DataKey::PendingAdmin,
             current_admin,
             new_admin,
-        );
+        )
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), PulsarError> {
+        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin)
     }
 
-    pub fn accept_admin(env: Env, new_admin: Address) {
-        pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), PulsarError> {
+        pulsar_common_admin::upgrade(&env, &DataKey::Admin, admin, new_wasm_hash)
     }
 }
 
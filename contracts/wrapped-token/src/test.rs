@@ -22,7 +22,6 @@ fn test_initialize() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_twice() {
     let env = Env::default();
     env.mock_all_auths();
@@ -31,7 +30,7 @@ fn test_initialize_twice() {
     let a = Address::generate(&env);
     let r = Address::generate(&env);
     c.initialize(&a, &r);
-    c.initialize(&a, &r);
+    assert_eq!(c.try_initialize(&a, &r), Err(Ok(PulsarError::AlreadyInitialized)));
 }
 
 #[test]
@@ -140,7 +139,6 @@ fn test_get_user_balance_zero() {
 }
 
 #[test]
-#[should_panic(expected = "source transaction already processed")]
 fn test_mint_wrapped_replay_attack_fails() {
     let env = Env::default();
     env.mock_all_auths();
@@ -168,13 +166,14 @@ fn test_mint_wrapped_replay_attack_fails() {
     );
     
     // Second mint with same source_tx should fail
-    c.mint_wrapped(
+    let result = c.try_mint_wrapped(
         &relayer,
         &s(&env, "wETH"),
         &user,
         &1_000_000i128,
         &s(&env, "0xTxHash123"),
     );
+    assert_eq!(result, Err(Ok(PulsarError::AlreadyExists)));
 }
 
 #[test]
@@ -217,7 +216,6 @@ fn test_mint_wrapped_different_tx_succeeds() {
 }
 
 #[test]
-#[should_panic(expected = "source transaction already processed")]
 fn test_mint_wrapped_replay_different_recipient_fails() {
     let env = Env::default();
     env.mock_all_auths();
@@ -246,17 +244,17 @@ fn test_mint_wrapped_replay_different_recipient_fails() {
     );
     
     // Attempt to mint same source_tx to different user should fail
-    c.mint_wrapped(
+    let result = c.try_mint_wrapped(
         &relayer,
         &s(&env, "wETH"),
         &user2,
         &1_000_000i128,
         &s(&env, "0xTxHash789"),
     );
+    assert_eq!(result, Err(Ok(PulsarError::AlreadyExists)));
 }
 
 #[test]
-#[should_panic(expected = "source transaction already processed")]
 fn test_mint_wrapped_replay_different_amount_fails() {
     let env = Env::default();
     env.mock_all_auths();
@@ -284,11 +282,12 @@ fn test_mint_wrapped_replay_different_amount_fails() {
     );
     
     // Attempt to mint same source_tx with different amount should fail
-    c.mint_wrapped(
+    let result = c.try_mint_wrapped(
         &relayer,
         &s(&env, "wETH"),
         &user,
         &2_000_000i128,
         &s(&env, "0xTxHashABC"),
     );
+    assert_eq!(result, Err(Ok(PulsarError::AlreadyExists)));
 }